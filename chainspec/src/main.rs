@@ -15,16 +15,62 @@
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
 extern crate serde_json;
+extern crate ethcore_builtin;
 extern crate ethjson;
 
 use std::{fs, env, process};
-use ethjson::spec::Spec;
+use std::convert::TryFrom;
+use ethjson::spec::{Builtin, Pricing, PricingInner, Spec};
 
 fn quit(s: &str) -> ! {
 	println!("{}", s);
 	process::exit(1);
 }
 
+/// Formats an activation point given as a block number or a timestamp.
+fn activation(block: &Option<ethjson::uint::U64>, timestamp: &Option<ethjson::uint::U64>) -> String {
+	match (block, timestamp) {
+		(Some(block), _) => format!("block {}", block.0),
+		(None, Some(timestamp)) => format!("timestamp {}", timestamp.0),
+		(None, None) => "block 0".into(),
+	}
+}
+
+fn pricing(price: &PricingInner) -> String {
+	serde_json::to_string(price).expect("builtin pricing is always serializable; qed")
+}
+
+/// Resolves the pricing schedule of a builtin, one line per pricing.
+fn pricing_schedule(builtin: &Builtin) -> Result<Vec<String>, String> {
+	match builtin.pricing {
+		Pricing::Single(ref price) => {
+			let mut schedule = vec![
+				format!("from {}: {}", activation(&builtin.activate_at, &builtin.activate_at_timestamp), pricing(price)),
+			];
+			// the legacy EIP-1108 fields reprice alt_bn128 operations at `eip1108_transition`
+			if let Some(ref transition) = builtin.eip1108_transition {
+				let repriced = match *price {
+					PricingInner::AltBn128ConstOperations(ref p) => format!("{{\"price\":{}}}", p.eip1108_transition_price),
+					PricingInner::AltBn128Pairing(ref p) => format!(
+						"{{\"base\":{},\"pair\":{}}}", p.eip1108_transition_base, p.eip1108_transition_pair,
+					),
+					_ => return Err("eip1108_transition is only supported by alt_bn128 pricings".into()),
+				};
+				schedule.push(format!("from block {} (EIP-1108): {}", transition.0, repriced));
+			}
+			Ok(schedule)
+		}
+		Pricing::Multi(ref pricings) => {
+			if builtin.eip1108_transition.is_some() {
+				return Err("legacy eip1108_transition conflicts with a multi-pricing schedule".into());
+			}
+			Ok(pricings.iter()
+				.map(|p| format!("from {}: {}", activation(&p.activate_at, &p.activate_at_timestamp), pricing(&p.price)))
+				.collect())
+		}
+	}
+}
+
 fn main() {
 	let mut args = env::args();
 	if args.len() != 2 {
@@ -39,10 +85,31 @@ fn main() {
 		Err(_) => quit(&format!("{} could not be opened", path)),
 	};
 
-	let spec: Result<Spec, _> = serde_json::from_reader(file);
+	let spec: Spec = match serde_json::from_reader(file) {
+		Ok(spec) => spec,
+		Err(err) => quit(&format!("{} {}", path, err.to_string())),
+	};
+
+	let mut errors = Vec::new();
+	for (address, builtin) in spec.accounts.builtins() {
+		let address = format!("{:#x}", address.0);
+		println!("{} {}", address, builtin.name);
+		if let Some(ref deactivate_at) = builtin.deactivate_at {
+			println!("\tuntil block {}", deactivate_at.0);
+		}
+		match pricing_schedule(&builtin) {
+			Ok(schedule) => for line in schedule {
+				println!("\t{}", line);
+			},
+			Err(err) => errors.push(format!("{} {}: {}", address, builtin.name, err)),
+		}
+		if let Err(err) = ethcore_builtin::Builtin::try_from(builtin) {
+			errors.push(format!("{}: {}", address, err));
+		}
+	}
 
-	if let Err(err) = spec {
-		quit(&format!("{} {}", path, err.to_string()));
+	if !errors.is_empty() {
+		quit(&format!("{} has invalid builtins:\n{}", path, errors.join("\n")));
 	}
 
 	println!("{} is valid", path);
@@ -0,0 +1,68 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmark of bulk block import, as done by `parity import`.
+
+#[macro_use]
+extern crate criterion;
+
+extern crate client_traits;
+extern crate common_types as types;
+extern crate ethcore;
+extern crate ethereum_types;
+extern crate spec;
+
+use std::io::Cursor;
+
+use client_traits::{ChainInfo, ImportExportBlocks};
+use criterion::Criterion;
+use ethcore::test_helpers::{generate_dummy_client_with_data, generate_dummy_client_with_spec};
+use ethereum_types::U256;
+use types::{data_format::DataFormat, ids::BlockId};
+
+// Length of the chain imported in every iteration.
+const BLOCKS: u32 = 10_000;
+// Signed transactions in every block of the chain.
+const TXS_PER_BLOCK: usize = 10;
+
+// The chain is generated once rather than bundled, exported in the same binary format
+// `parity export blocks` produces.
+fn exported_chain() -> Vec<u8> {
+	let client = generate_dummy_client_with_data(BLOCKS, TXS_PER_BLOCK, &[U256::from(1)]);
+	let mut out = Vec::new();
+	client.export_blocks(Box::new(&mut out), BlockId::Number(1), BlockId::Latest, Some(DataFormat::Binary))
+		.expect("export of a generated chain succeeds");
+	out
+}
+
+fn import_blocks(c: &mut Criterion) {
+	let chain = exported_chain();
+	c.bench_function("import 10k blocks", move |b| {
+		b.iter(|| {
+			let client = generate_dummy_client_with_spec(spec::new_null);
+			client.import_blocks(Box::new(Cursor::new(&chain)), Some(DataFormat::Binary))
+				.expect("import of an exported chain succeeds");
+			assert_eq!(client.chain_info().best_block_number, BLOCKS as u64);
+		})
+	});
+}
+
+criterion_group! {
+	name = import;
+	config = Criterion::default().sample_size(10);
+	targets = import_blocks
+}
+criterion_main!(import);
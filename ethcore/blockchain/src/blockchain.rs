@@ -17,7 +17,7 @@
 //! Blockchain database.
 
 use std::collections::{HashMap, HashSet};
-use std::{mem, io};
+use std::{cmp, iter, mem, io};
 use std::path::Path;
 use std::sync::Arc;
 
@@ -26,6 +26,7 @@ use blooms_db;
 use common_types::{
 	BlockNumber,
 	blockchain_info::BlockChainInfo,
+	database_stats::ColumnStats,
 	block::{BlockInfo, BlockLocation, BranchBecomingCanonChainData},
 	encoded,
 	engines::ForkChoice,
@@ -42,7 +43,7 @@ use common_types::{
 use ethcore_db::cache_manager::CacheManager;
 use ethcore_db::keys::{BlockReceipts, BlockDetails, TransactionAddress, EPOCH_KEY_PREFIX, EpochTransitions};
 use ethcore_db::{self as db, Writable, Readable, CacheUpdatePolicy};
-use ethereum_types::{H256, Bloom, BloomRef, U256};
+use ethereum_types::{H256, H264, Bloom, BloomRef, U256};
 use util_mem::{MallocSizeOf, allocators::new_malloc_size_ops};
 use itertools::Itertools;
 use kvdb::{DBTransaction, KeyValueDB};
@@ -82,6 +83,16 @@ pub trait BlockChainDB: Send + Sync {
 		self.trace_blooms().reopen()?;
 		Ok(())
 	}
+
+	/// Size statistics of the key-value DB columns. Empty if the backend doesn't report them.
+	fn column_stats(&self) -> Vec<ColumnStats> {
+		Vec::new()
+	}
+
+	/// Compact a column of the key-value DB, blocking until the compaction finishes.
+	fn compact_column(&self, _col: u32) -> Result<(), io::Error> {
+		Err(io::Error::new(io::ErrorKind::Other, "Database backend doesn't support manual compaction"))
+	}
 }
 
 /// Generic database handler. This trait contains one function `open`. When called, it opens database with a
@@ -142,10 +153,11 @@ pub trait BlockProvider {
 	/// Get the block body (uncles and transactions).
 	fn block_body(&self, hash: &H256) -> Option<encoded::Body>;
 
-	/// Get a list of uncles for a given block.
+	/// Get a list of uncles for a given block, see `Header::decode_rlp` for the meaning of
+	/// `eip1559_transition`.
 	/// Returns None if block does not exist.
-	fn uncles(&self, hash: &H256) -> Option<Vec<Header>> {
-		self.block_body(hash).map(|body| body.uncles())
+	fn uncles(&self, hash: &H256, eip1559_transition: BlockNumber) -> Option<Vec<Header>> {
+		self.block_body(hash).map(|body| body.uncles(eip1559_transition))
 	}
 
 	/// Get a list of uncle hashes for a given block.
@@ -226,6 +238,9 @@ pub struct BlockChain {
 	// Stores the last block of the last sequence of blocks. `None` if there are no gaps.
 	// This is calculated on start and does not get updated.
 	first_block: Option<H256>,
+	// Number of the first canonical block whose body and receipts are kept. `None` if no
+	// ancient blocks were pruned.
+	earliest_available_block: RwLock<Option<BlockNumber>>,
 
 	// block cache
 	block_headers: RwLock<HashMap<H256, encoded::Header>>,
@@ -242,10 +257,14 @@ pub struct BlockChain {
 	cache_man: Mutex<CacheManager<CacheId>>,
 
 	pending_best_ancient_block: RwLock<Option<Option<BestAncientBlock>>>,
+	pending_earliest_available_block: RwLock<Option<BlockNumber>>,
 	pending_best_block: RwLock<Option<BestBlock>>,
 	pending_block_hashes: RwLock<HashMap<BlockNumber, H256>>,
 	pending_block_details: RwLock<HashMap<H256, BlockDetails>>,
 	pending_transaction_addresses: RwLock<HashMap<H256, Option<TransactionAddress>>>,
+
+	// number of the first block whose header carries a base fee
+	eip1559_transition: BlockNumber,
 }
 
 impl BlockProvider for BlockChain {
@@ -479,7 +498,7 @@ impl<'a> Iterator for AncestryWithMetadataIter<'a> {
 		} else {
 			let details = self.chain.block_details(&self.current);
 			let header = self.chain.block_header_data(&self.current)
-				.map(|h| h.decode().expect("Stored block header data is valid RLP; qed"));
+				.map(|h| h.decode(self.chain.eip1559_transition).expect("Stored block header data is valid RLP; qed"));
 
 			match (details, header) {
 				(Some(details), Some(header)) => {
@@ -556,6 +575,7 @@ impl BlockChain {
 				block: encoded::Block::new(genesis.into()),
 			}),
 			best_ancient_block: RwLock::new(None),
+			earliest_available_block: RwLock::new(None),
 			block_headers: RwLock::new(HashMap::new()),
 			block_bodies: RwLock::new(HashMap::new()),
 			block_details: RwLock::new(HashMap::new()),
@@ -565,10 +585,12 @@ impl BlockChain {
 			db: db.clone(),
 			cache_man: Mutex::new(cache_man),
 			pending_best_ancient_block: RwLock::new(None),
+			pending_earliest_available_block: RwLock::new(None),
 			pending_best_block: RwLock::new(None),
 			pending_block_hashes: RwLock::new(HashMap::new()),
 			pending_block_details: RwLock::new(HashMap::new()),
 			pending_transaction_addresses: RwLock::new(HashMap::new()),
+			eip1559_transition: config.eip1559_transition,
 		};
 
 		// load best block
@@ -618,7 +640,7 @@ impl BlockChain {
 			let mut best_block = bc.best_block.write();
 			*best_block = BestBlock {
 				total_difficulty: best_block_total_difficulty,
-				header: best_block_rlp.decode_header(),
+				header: best_block_rlp.decode_header(bc.eip1559_transition),
 				block: best_block_rlp,
 			};
 		}
@@ -678,6 +700,12 @@ impl BlockChain {
 			}
 		}
 
+		// load the earliest block not pruned
+		if let Some(raw) = bc.db.key_value().get(db::COL_EXTRA, b"earliest_available")
+			.expect("Low level database error when fetching 'earliest available' block. Some issue with disk?") {
+			*bc.earliest_available_block.write() = rlp::decode(&raw).ok();
+		}
+
 		bc
 	}
 
@@ -899,7 +927,7 @@ impl BlockChain {
 		let mut best_block = self.best_block.write();
 		*best_block = BestBlock {
 			total_difficulty: best_block_total_difficulty,
-			header: best_block_rlp.decode_header(),
+			header: best_block_rlp.decode_header(self.eip1559_transition),
 			block: best_block_rlp,
 		};
 	}
@@ -982,6 +1010,52 @@ impl BlockChain {
 		}
 	}
 
+	/// Number of the first canonical block whose body and receipts are available, if older ones
+	/// were pruned with `prune_ancient_blocks`.
+	pub fn earliest_available_block(&self) -> Option<BlockNumber> {
+		*self.earliest_available_block.read()
+	}
+
+	/// Delete the bodies and receipts of canonical blocks below `until`, going through at most `max`
+	/// blocks from the earliest available one. Headers are always kept, and the blooms of the pruned
+	/// blocks are cleared so that log queries skip them.
+	///
+	/// Returns the numbers and hashes of the pruned blocks. The earliest available block is
+	/// updated on `commit`.
+	pub fn prune_ancient_blocks(&self, batch: &mut DBTransaction, until: BlockNumber, max: u64) -> Vec<(BlockNumber, H256)> {
+		// the genesis block is never pruned
+		let from = self.earliest_available_block().unwrap_or(1);
+		let to = cmp::min(until, from.saturating_add(max));
+		if to <= from {
+			return Vec::new();
+		}
+
+		let pruned: Vec<_> = (from..to)
+			.filter_map(|number| self.block_hash(number).map(|hash| (number, hash)))
+			.collect();
+
+		{
+			let mut block_bodies = self.block_bodies.write();
+			let mut block_receipts = self.block_receipts.write();
+			for (_, hash) in &pruned {
+				batch.delete(db::COL_BODIES, hash.as_bytes());
+				Writable::delete::<BlockReceipts, H264>(batch, db::COL_EXTRA, hash);
+				block_bodies.remove(hash);
+				block_receipts.remove(hash);
+			}
+		}
+		batch.put(db::COL_EXTRA, b"earliest_available", &rlp::encode(&to));
+
+		let empty_bloom = Bloom::default();
+		self.db.blooms()
+			.insert_blooms(from, iter::repeat(&empty_bloom).take((to - from) as usize))
+			.expect("Low level database error when updating blooms. Some issue with disk?");
+
+		trace!(target: "blockchain", "Pruned bodies and receipts of blocks {} to {}.", from, to - 1);
+		*self.pending_earliest_available_block.write() = Some(to);
+		pruned
+	}
+
 	/// Insert an epoch transition. Provide an epoch number being transitioned to
 	/// and epoch transition object.
 	///
@@ -1231,7 +1305,7 @@ impl BlockChain {
 				batch.put(db::COL_EXTRA, b"best", update.info.hash.as_bytes());
 				*best_block = Some(BestBlock {
 					total_difficulty: update.info.total_difficulty,
-					header: update.block.decode_header(),
+					header: update.block.decode_header(self.eip1559_transition),
 					block: update.block,
 				});
 			}
@@ -1267,6 +1341,10 @@ impl BlockChain {
 		if let Some(block) = pending_best_block.take() {
 			*best_block = block;
 		}
+		// update earliest available block
+		if let Some(number) = self.pending_earliest_available_block.write().take() {
+			*self.earliest_available_block.write() = Some(number);
+		}
 
 		let pending_txs = mem::replace(&mut *pending_write_txs, HashMap::new());
 		let (retracted_txs, enacted_txs) = pending_txs.into_iter().partition::<HashMap<_, _>, _>(|&(_, ref value)| value.is_none());
@@ -1529,6 +1607,11 @@ impl BlockChain {
 		self.best_block.read().header.clone()
 	}
 
+	/// Number of the first block whose header carries a base fee.
+	pub fn eip1559_transition(&self) -> BlockNumber {
+		self.eip1559_transition
+	}
+
 	/// Get current cache size.
 	pub fn cache_size(&self) -> CacheSize {
 		let mut ops = new_malloc_size_ops();
@@ -2618,4 +2701,45 @@ mod tests {
 			assert!(!bc.tree_route(a2_hash, a1_hash).unwrap().is_from_route_finalized);
 		}
 	}
+
+	#[test]
+	fn prunes_ancient_block_bodies() {
+		let genesis = BlockBuilder::genesis();
+		let first_10 = genesis.add_blocks(10);
+		let generator = BlockGenerator::new(vec![first_10]);
+
+		let db = new_db();
+		let bc = new_chain(genesis.last().encoded(), db.clone());
+		let mut block_hashes = vec![genesis.last().hash()];
+		for block in generator {
+			block_hashes.push(block.hash());
+			insert_block(&db, &bc, block.encoded(), vec![]);
+		}
+		assert_eq!(bc.earliest_available_block(), None);
+
+		// prune at most 3 blocks at once
+		let mut batch = db.key_value().transaction();
+		let pruned = bc.prune_ancient_blocks(&mut batch, 6, 3);
+		assert_eq!(pruned, (1..4).map(|n| (n, block_hashes[n as usize])).collect::<Vec<_>>());
+		db.key_value().write(batch).unwrap();
+		bc.commit();
+		assert_eq!(bc.earliest_available_block(), Some(4));
+
+		let mut batch = db.key_value().transaction();
+		assert_eq!(bc.prune_ancient_blocks(&mut batch, 6, 100).len(), 2);
+		db.key_value().write(batch).unwrap();
+		bc.commit();
+
+		for (number, hash) in block_hashes.iter().enumerate() {
+			assert!(bc.block_header_data(hash).is_some());
+			assert_eq!(bc.block_body(hash).is_some(), number == 0 || number >= 6, "block {}", number);
+			assert_eq!(bc.block_receipts(hash).is_some(), number >= 6, "block {}", number);
+		}
+
+		// nothing left to prune, and the earliest available block is persisted
+		let mut batch = db.key_value().transaction();
+		assert!(bc.prune_ancient_blocks(&mut batch, 6, 100).is_empty());
+		let bc = new_chain(genesis.last().encoded(), db.clone());
+		assert_eq!(bc.earliest_available_block(), Some(6));
+	}
 }
@@ -19,6 +19,7 @@
 use std::{
 	cmp::{max, min},
 	convert::TryFrom,
+	fmt,
 	io::{self, Read, Cursor},
 	mem::size_of,
 };
@@ -35,11 +36,20 @@ use num::{BigUint, Zero, One};
 use parity_bytes::BytesRef;
 use parity_crypto::digest;
 use eip_152::compress;
+#[cfg(feature = "bls12-381")]
+use eth_pairings::public_interface::eip2537::EIP2537Executor;
 
 /// Native implementation of a built-in contract.
 trait Implementation: Send + Sync {
 	/// execute this built-in on the given input, writing to the given output.
 	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str>;
+
+	/// execute this built-in on the given input, writing to the given output and returning the gas
+	/// actually used, as priced by `pricer` at block `at`, if it is only known once the input is
+	/// parsed. `None` means the upfront cost.
+	fn execute_metered(&self, input: &[u8], output: &mut BytesRef, _pricer: &dyn Pricer, _at: u64) -> Result<Option<U256>, &'static str> {
+		self.execute(input, output).map(|()| None)
+	}
 }
 
 /// A gas pricing scheme for built-in contracts.
@@ -51,17 +61,73 @@ trait Pricer: Send + Sync {
 
 /// Pricing for the Blake2 compression function (aka "F").
 /// Computes the price as a fixed cost per round where the number of rounds is part of the input
-/// byte slice.
-pub type Blake2FPricer = u64;
+/// byte slice. Calls requesting more than `max_rounds` rounds can never be afforded.
+pub struct Blake2FPricer {
+	gas_per_round: u64,
+	max_rounds: Option<u64>,
+}
 
 impl Pricer for Blake2FPricer {
 	fn cost(&self, input: &[u8], _at: u64) -> U256 {
 		use std::convert::TryInto;
-		let (rounds_bytes, _) = input.split_at(std::mem::size_of::<u32>());
-		// Returning zero if the conversion fails is fine because `execute()` will check the length
+		// Returning zero if the input is too short is fine because `execute()` will check the length
 		// and bail with the appropriate error.
-		let rounds = u32::from_be_bytes(rounds_bytes.try_into().unwrap_or([0u8; 4]));
-		U256::from(*self as u128 * rounds as u128)
+		let rounds = input.get(..std::mem::size_of::<u32>())
+			.and_then(|rounds_bytes| rounds_bytes.try_into().ok())
+			.map_or(0, u32::from_be_bytes);
+		if self.max_rounds.map_or(false, |max_rounds| rounds as u64 > max_rounds) {
+			return U256::max_value();
+		}
+		U256::from(self.gas_per_round as u128 * rounds as u128)
+	}
+}
+
+/// A fixed pricing model. Every call costs the same amount regardless of the input.
+struct ConstPricer(u64);
+
+impl Pricer for ConstPricer {
+	fn cost(&self, _input: &[u8], _at: u64) -> U256 {
+		self.0.into()
+	}
+}
+
+/// BLS12-381 pairing pricing model (EIP-2537). This computes a price using a base cost and a cost per pair.
+struct Bls12PairingPricer {
+	base: u64,
+	pair: u64,
+}
+
+impl Pricer for Bls12PairingPricer {
+	fn cost(&self, input: &[u8], _at: u64) -> U256 {
+		// 128 bytes for the G1 point and 256 bytes for the G2 point
+		const PAIR_LEN: usize = 384;
+
+		U256::from(self.base) + U256::from(self.pair) * U256::from(input.len() / PAIR_LEN)
+	}
+}
+
+/// BLS12-381 multi-exponentiation pricing model (EIP-2537).
+/// Computes the price of `k` pairs as `k * base * discount(k) / 1000`.
+struct Bls12MultiExpPricer {
+	base: u64,
+	discount: Vec<u64>,
+	pair_len: usize,
+}
+
+impl Pricer for Bls12MultiExpPricer {
+	fn cost(&self, input: &[u8], _at: u64) -> U256 {
+		const MULTIPLIER: u64 = 1000;
+
+		let k = input.len() / self.pair_len;
+		if k == 0 {
+			return U256::zero();
+		}
+		let discount = self.discount.get(k - 1)
+			.or_else(|| self.discount.last())
+			.cloned()
+			.unwrap_or(MULTIPLIER);
+
+		U256::from(k) * U256::from(self.base) * U256::from(discount) / U256::from(MULTIPLIER)
 	}
 }
 
@@ -85,17 +151,11 @@ impl Pricer for Linear {
 /// alt_bn128 constant operations (add and mul) pricing model.
 struct AltBn128ConstOperations {
 	price: usize,
-	eip1108_transition_at: u64,
-	eip1108_transition_price: usize,
 }
 
 impl Pricer for AltBn128ConstOperations {
-	fn cost(&self, _input: &[u8], at: u64) -> U256 {
-		if at >= self.eip1108_transition_at {
-			self.eip1108_transition_price.into()
-		} else {
-			self.price.into()
-		}
+	fn cost(&self, _input: &[u8], _at: u64) -> U256 {
+		self.price.into()
 	}
 }
 
@@ -109,24 +169,31 @@ struct AltBn128PairingPrice {
 /// alt_bn128_pairing pricing model. This computes a price using a base cost and a cost per pair.
 struct AltBn128PairingPricer {
 	price: AltBn128PairingPrice,
-	eip1108_transition_at: u64,
-	eip1108_transition_price: AltBn128PairingPrice,
 }
 
 impl Pricer for AltBn128PairingPricer {
-	fn cost(&self, input: &[u8], at: u64) -> U256 {
-		let price = if at >= self.eip1108_transition_at {
-			self.eip1108_transition_price
-		} else {
-			self.price
-		};
-
-		U256::from(price.base) + U256::from(price.pair) * U256::from(input.len() / 192)
+	fn cost(&self, input: &[u8], _at: u64) -> U256 {
+		U256::from(self.price.base) + U256::from(self.price.pair) * U256::from(input.len() / 192)
 	}
 }
 
-impl Pricer for ModexpPricer {
-	fn cost(&self, input: &[u8], _at: u64) -> U256 {
+/// Operand sizes of a modular exponentiation call, as used by the modexp pricing models.
+enum ModexpSizes {
+	/// Both the base and the modulus are empty.
+	Empty,
+	/// One of the operands is too large to be priced.
+	Oversized,
+	/// Length of the larger of base and modulus, length of the exponent and the index of the
+	/// highest set bit in its first 32 bytes.
+	Sized {
+		max_len: u64,
+		exp_len: u64,
+		exp_bit_index: u64,
+	},
+}
+
+impl ModexpSizes {
+	fn read(input: &[u8]) -> Self {
 		let mut reader = input.chain(io::repeat(0));
 		let mut buf = [0; 32];
 
@@ -140,16 +207,15 @@ impl Pricer for ModexpPricer {
 		let mod_len = read_len();
 
 		if mod_len.is_zero() && base_len.is_zero() {
-			return U256::zero()
+			return ModexpSizes::Empty
 		}
 
 		let max_len = U256::from(u32::max_value() / 2);
 		if base_len > max_len || mod_len > max_len || exp_len > max_len {
-			return U256::max_value();
+			return ModexpSizes::Oversized;
 		}
 		let (base_len, exp_len, mod_len) = (base_len.low_u64(), exp_len.low_u64(), mod_len.low_u64());
 
-		let m = max(mod_len, base_len);
 		// read fist 32-byte word of the exponent.
 		let exp_low = if base_len + 96 >= input.len() as u64 { U256::zero() } else {
 			let mut buf = [0; 32];
@@ -159,7 +225,31 @@ impl Pricer for ModexpPricer {
 			U256::from_big_endian(&buf[..])
 		};
 
-		let adjusted_exp_len = Self::adjusted_exp_len(exp_len, exp_low);
+		ModexpSizes::Sized {
+			max_len: max(mod_len, base_len),
+			exp_len,
+			exp_bit_index: if exp_low.is_zero() { 0 } else { (255 - exp_low.leading_zeros()) as u64 },
+		}
+	}
+
+	/// Adjusted exponent length, charging `word_multiplier` for every exponent byte past the first 32.
+	fn adjusted_exp_len(exp_len: u64, exp_bit_index: u64, word_multiplier: u64) -> u64 {
+		if exp_len <= 32 {
+			exp_bit_index
+		} else {
+			word_multiplier * (exp_len - 32) + exp_bit_index
+		}
+	}
+}
+
+impl Pricer for ModexpPricer {
+	fn cost(&self, input: &[u8], _at: u64) -> U256 {
+		let (m, adjusted_exp_len) = match ModexpSizes::read(input) {
+			ModexpSizes::Empty => return U256::zero(),
+			ModexpSizes::Oversized => return U256::max_value(),
+			ModexpSizes::Sized { max_len, exp_len, exp_bit_index } =>
+				(max_len, ModexpSizes::adjusted_exp_len(exp_len, exp_bit_index, 8)),
+		};
 
 		let (gas, overflow) = Self::mult_complexity(m).overflowing_mul(max(adjusted_exp_len, 1));
 		if overflow {
@@ -170,15 +260,6 @@ impl Pricer for ModexpPricer {
 }
 
 impl ModexpPricer {
-	fn adjusted_exp_len(len: u64, exp_low: U256) -> u64 {
-		let bit_index = if exp_low.is_zero() { 0 } else { (255 - exp_low.leading_zeros()) as u64 };
-		if len <= 32 {
-			bit_index
-		} else {
-			8 * (len - 32) + bit_index
-		}
-	}
-
 	fn mult_complexity(x: u64) -> u64 {
 		match x {
 			x if x <= 64 => x * x,
@@ -188,6 +269,143 @@ impl ModexpPricer {
 	}
 }
 
+/// Modular exponentiation pricing model introduced by EIP-2565.
+struct Modexp2565Pricer {
+	divisor: u64,
+	min_price: u64,
+}
+
+impl Pricer for Modexp2565Pricer {
+	fn cost(&self, input: &[u8], _at: u64) -> U256 {
+		let (m, adjusted_exp_len) = match ModexpSizes::read(input) {
+			ModexpSizes::Empty => return self.min_price.into(),
+			ModexpSizes::Oversized => return U256::max_value(),
+			ModexpSizes::Sized { max_len, exp_len, exp_bit_index } =>
+				(max_len, ModexpSizes::adjusted_exp_len(exp_len, exp_bit_index, 8)),
+		};
+
+		let (gas, overflow) = Self::mult_complexity(m).overflowing_mul(max(adjusted_exp_len, 1));
+		if overflow {
+			return U256::max_value();
+		}
+		max(gas / self.divisor, self.min_price).into()
+	}
+}
+
+impl Modexp2565Pricer {
+	/// Default divisor of the EIP-2565 formula.
+	const DEFAULT_DIVISOR: u64 = 3;
+	/// Default minimum price of the EIP-2565 formula.
+	const DEFAULT_MIN_PRICE: u64 = 200;
+
+	fn mult_complexity(x: u64) -> u64 {
+		let words = (x + 7) / 8;
+		words * words
+	}
+}
+
+/// Modular exponentiation pricing model introduced by EIP-7883.
+struct Modexp7883Pricer {
+	min_price: u64,
+}
+
+impl Pricer for Modexp7883Pricer {
+	fn cost(&self, input: &[u8], _at: u64) -> U256 {
+		let (m, adjusted_exp_len) = match ModexpSizes::read(input) {
+			ModexpSizes::Empty => return self.min_price.into(),
+			ModexpSizes::Oversized => return U256::max_value(),
+			ModexpSizes::Sized { max_len, exp_len, exp_bit_index } =>
+				(max_len, ModexpSizes::adjusted_exp_len(exp_len, exp_bit_index, 16)),
+		};
+
+		let (gas, overflow) = Self::mult_complexity(m).overflowing_mul(max(adjusted_exp_len, 1));
+		if overflow {
+			return U256::max_value();
+		}
+		max(gas, self.min_price).into()
+	}
+}
+
+impl Modexp7883Pricer {
+	fn mult_complexity(x: u64) -> u64 {
+		if x <= 32 {
+			16
+		} else {
+			let words = (x + 7) / 8;
+			2 * words * words
+		}
+	}
+}
+
+/// Block number or timestamp from which a builtin, or one of its pricings, is active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Activation {
+	/// Active from the given block number.
+	Block(u64),
+	/// Active from the given block timestamp.
+	Timestamp(u64),
+}
+
+impl fmt::Display for Activation {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Activation::Block(block) => write!(f, "block {}", block),
+			Activation::Timestamp(ts) => write!(f, "timestamp {}", ts),
+		}
+	}
+}
+
+impl Activation {
+	/// Whether the activation point is reached by a block with the given number and timestamp.
+	fn is_reached(&self, at: u64, timestamp: u64) -> bool {
+		match *self {
+			Activation::Block(block) => at >= block,
+			Activation::Timestamp(ts) => timestamp >= ts,
+		}
+	}
+
+	/// Whether both activations are measured in the same unit.
+	fn same_kind(&self, other: &Activation) -> bool {
+		match (self, other) {
+			(Activation::Block(_), Activation::Block(_)) |
+			(Activation::Timestamp(_), Activation::Timestamp(_)) => true,
+			_ => false,
+		}
+	}
+
+	/// Whether this activation strictly precedes `other`, which must be of the same kind.
+	fn is_before(&self, other: &Activation) -> bool {
+		match (*self, *other) {
+			(Activation::Block(a), Activation::Block(b)) |
+			(Activation::Timestamp(a), Activation::Timestamp(b)) => a < b,
+			_ => false,
+		}
+	}
+
+	/// Whether this activation point is reached by every block that reaches `other`.
+	fn is_reached_by(&self, other: &Activation) -> bool {
+		match (*self, *other) {
+			(Activation::Block(0), _) | (Activation::Timestamp(0), _) => true,
+			(this, other) => this.same_kind(&other) && !other.is_before(&this),
+		}
+	}
+
+	fn from_json(
+		name: &str,
+		block: Option<ethjson::uint::U64>,
+		timestamp: Option<ethjson::uint::U64>,
+	) -> Result<Option<Self>, EthcoreError> {
+		match (block, timestamp) {
+			(Some(_), Some(_)) => Err(EthcoreError::Msg(format!(
+				"builtin {}: `activate_at` and `activate_at_timestamp` are mutually exclusive", name
+			))),
+			(Some(block), None) => Ok(Some(Activation::Block(block.into()))),
+			(None, Some(ts)) => Ok(Some(Activation::Timestamp(ts.into()))),
+			(None, None) => Ok(None),
+		}
+	}
+}
+
 /// Pricing scheme, execution definition, and activation block for a built-in contract.
 ///
 /// Call `cost` to compute cost for the given input, `execute` to execute the contract
@@ -195,25 +413,193 @@ impl ModexpPricer {
 ///
 /// Unless `is_active` is true,
 pub struct Builtin {
-	pricer: Box<dyn Pricer>,
+	spec: ethjson::spec::Builtin,
+	pricer: Vec<(Activation, Box<dyn Pricer>)>,
 	native: Box<dyn Implementation>,
-	activate_at: u64,
+	activate_at: Activation,
+	deactivate_at: Option<u64>,
+	max_input_length: Option<usize>,
+	fallback_to_code: bool,
+	metered: bool,
 }
 
 impl Builtin {
 	/// Simple forwarder for cost.
-	pub fn cost(&self, input: &[u8], at: u64) -> U256 {
-		self.pricer.cost(input, at)
+	///
+	/// Uses the last pricing in the schedule activated by the block with the given number and timestamp.
+	pub fn cost(&self, input: &[u8], at: u64, timestamp: u64) -> U256 {
+		if self.exceeds_max_input_length(input) {
+			return U256::max_value();
+		}
+		self.pricer_at(at, timestamp).map_or_else(U256::zero, |pricer| pricer.cost(input, at))
+	}
+
+	fn pricer_at(&self, at: u64, timestamp: u64) -> Option<&dyn Pricer> {
+		self.pricer.iter().rev()
+			.find(|(activation, _)| activation.is_reached(at, timestamp))
+			.map(|(_, pricer)| &**pricer)
 	}
 
 	/// Simple forwarder for execute.
+	///
+	/// Fails without running the contract if the input exceeds the configured maximum length.
 	pub fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+		if self.exceeds_max_input_length(input) {
+			trace!(target: "builtin", "input length {} exceeds the maximum of {:?}", input.len(), self.max_input_length);
+			return Err("input length exceeds the builtin maximum");
+		}
 		self.native.execute(input, output)
 	}
 
-	/// Whether the builtin is activated at the given block number.
-	pub fn is_active(&self, at: u64) -> bool {
-		at >= self.activate_at
+	/// Like `execute`, but also returns the gas actually used by the call at the block with the
+	/// given number and timestamp if the builtin is `metered` and its implementation can price the
+	/// call more precisely than `cost`. Callers charge `cost` as a cap and refund the difference;
+	/// `None` means no refund.
+	pub fn execute_metered(&self, input: &[u8], output: &mut BytesRef, at: u64, timestamp: u64) -> Result<Option<U256>, &'static str> {
+		if self.exceeds_max_input_length(input) {
+			trace!(target: "builtin", "input length {} exceeds the maximum of {:?}", input.len(), self.max_input_length);
+			return Err("input length exceeds the builtin maximum");
+		}
+		match self.pricer_at(at, timestamp) {
+			Some(pricer) if self.metered => self.native.execute_metered(input, output, pricer, at),
+			_ => self.native.execute(input, output).map(|()| None),
+		}
+	}
+
+	fn exceeds_max_input_length(&self, input: &[u8]) -> bool {
+		self.max_input_length.map_or(false, |max| input.len() > max)
+	}
+
+	/// Specification the builtin was loaded from.
+	pub fn spec(&self) -> &ethjson::spec::Builtin {
+		&self.spec
+	}
+
+	/// Whether a failing call should be executed by the code deployed at the builtin address instead.
+	pub fn fallback_to_code(&self) -> bool {
+		self.fallback_to_code
+	}
+
+	/// Whether calls are charged the gas reported by the implementation, capped by `cost`.
+	pub fn metered(&self) -> bool {
+		self.metered
+	}
+
+	/// Whether the builtin is activated, and not yet deactivated, at the block with the given
+	/// number and timestamp.
+	pub fn is_active(&self, at: u64, timestamp: u64) -> bool {
+		self.activate_at.is_reached(at, timestamp) && self.deactivate_at.map_or(true, |deactivate_at| at < deactivate_at)
+	}
+}
+
+/// Pricing of an alt_bn128 builtin after the legacy `eip1108_transition`, taken from its
+/// `eip1108_transition_*` fields.
+fn eip1108_pricing(name: &str, pricing: &ethjson::spec::PricingInner) -> Result<ethjson::spec::PricingInner, EthcoreError> {
+	match *pricing {
+		ethjson::spec::PricingInner::AltBn128ConstOperations(ref p) => {
+			Ok(ethjson::spec::PricingInner::AltBn128ConstOperations(ethjson::spec::builtin::AltBn128ConstOperations {
+				price: p.eip1108_transition_price,
+				eip1108_transition_price: p.eip1108_transition_price,
+			}))
+		}
+		ethjson::spec::PricingInner::AltBn128Pairing(ref p) => {
+			Ok(ethjson::spec::PricingInner::AltBn128Pairing(ethjson::spec::builtin::AltBn128Pairing {
+				base: p.eip1108_transition_base,
+				pair: p.eip1108_transition_pair,
+				eip1108_transition_base: p.eip1108_transition_base,
+				eip1108_transition_pair: p.eip1108_transition_pair,
+			}))
+		}
+		_ => Err(EthcoreError::Msg(format!("builtin {}: eip1108_transition is only supported by alt_bn128 pricings", name))),
+	}
+}
+
+/// Builds the pricer for a single pricing variant.
+fn pricer_from_json(pricing: ethjson::spec::PricingInner) -> Box<dyn Pricer> {
+	match pricing {
+		ethjson::spec::PricingInner::Blake2F { gas_per_round, max_rounds } => {
+			Box::new(Blake2FPricer { gas_per_round, max_rounds })
+		},
+		ethjson::spec::PricingInner::Linear(linear) => {
+			Box::new(Linear {
+				base: linear.base,
+				word: linear.word,
+			})
+		}
+		ethjson::spec::PricingInner::Modexp(exp) => {
+			Box::new(ModexpPricer {
+				divisor: if exp.divisor == 0 {
+					warn!(target: "builtin", "Zero modexp divisor specified. Falling back to default.");
+					10
+				} else {
+					exp.divisor
+				}
+			})
+		}
+		ethjson::spec::PricingInner::Modexp2565(exp) => {
+			Box::new(Modexp2565Pricer {
+				divisor: if exp.divisor == 0 {
+					warn!(target: "builtin", "Zero modexp divisor specified. Falling back to default.");
+					Modexp2565Pricer::DEFAULT_DIVISOR
+				} else {
+					exp.divisor
+				},
+				min_price: exp.min_price.unwrap_or(Modexp2565Pricer::DEFAULT_MIN_PRICE),
+			})
+		}
+		ethjson::spec::PricingInner::Modexp7883(exp) => {
+			Box::new(Modexp7883Pricer {
+				min_price: exp.min_price,
+			})
+		}
+		ethjson::spec::PricingInner::AltBn128Pairing(pricer) => {
+			Box::new(AltBn128PairingPricer {
+				price: AltBn128PairingPrice {
+					base: pricer.base,
+					pair: pricer.pair,
+				},
+			})
+		}
+		ethjson::spec::PricingInner::AltBn128ConstOperations(pricer) => {
+			Box::new(AltBn128ConstOperations {
+				price: pricer.price,
+			})
+		}
+		ethjson::spec::PricingInner::Bls12G1Add { price } |
+		ethjson::spec::PricingInner::Bls12G1Mul { price } |
+		ethjson::spec::PricingInner::Bls12G2Add { price } |
+		ethjson::spec::PricingInner::Bls12G2Mul { price } |
+		ethjson::spec::PricingInner::Bls12MapFpToG1 { price } |
+		ethjson::spec::PricingInner::Bls12MapFp2ToG2 { price } |
+		ethjson::spec::PricingInner::Secp256r1Verify { price } |
+		ethjson::spec::PricingInner::Const { price } => {
+			Box::new(ConstPricer(price))
+		}
+		ethjson::spec::PricingInner::Bls12G1MultiExp(pricer) => {
+			Box::new(Bls12MultiExpPricer {
+				base: pricer.base,
+				discount: pricer.discount,
+				// 128 bytes for the point and 32 bytes for the scalar
+				pair_len: 160,
+			})
+		}
+		ethjson::spec::PricingInner::PointEvaluation(pricer) => {
+			Box::new(ConstPricer(pricer.price))
+		}
+		ethjson::spec::PricingInner::Bls12Pairing(pricer) => {
+			Box::new(Bls12PairingPricer {
+				base: pricer.base,
+				pair: pricer.pair,
+			})
+		}
+		ethjson::spec::PricingInner::Bls12G2MultiExp(pricer) => {
+			Box::new(Bls12MultiExpPricer {
+				base: pricer.base,
+				discount: pricer.discount,
+				// 256 bytes for the point and 32 bytes for the scalar
+				pair_len: 288,
+			})
+		}
 	}
 }
 
@@ -221,58 +607,127 @@ impl TryFrom<ethjson::spec::Builtin> for Builtin {
 	type Error = EthcoreError;
 
 	fn try_from(b: ethjson::spec::Builtin) -> Result<Self, Self::Error> {
-		let pricer: Box<dyn Pricer> = match b.pricing {
-			ethjson::spec::Pricing::Blake2F { gas_per_round } => {
-				Box::new(gas_per_round)
-			},
-			ethjson::spec::Pricing::Linear(linear) => {
-				Box::new(Linear {
-					base: linear.base,
-					word: linear.word,
-				})
-			}
-			ethjson::spec::Pricing::Modexp(exp) => {
-				Box::new(ModexpPricer {
-					divisor: if exp.divisor == 0 {
-						warn!(target: "builtin", "Zero modexp divisor specified. Falling back to default.");
-						10
-					} else {
-						exp.divisor
+		b.validate().map_err(|e| EthcoreError::Msg(format!("builtin {}: {}", b.name, e)))?;
+		let spec = b.clone();
+		let activate_at = Activation::from_json(&b.name, b.activate_at, b.activate_at_timestamp)?;
+
+		let pricer: Vec<(Activation, Box<dyn Pricer>)> = match b.pricing {
+			ethjson::spec::Pricing::Single(pricing) => {
+				let activation = activate_at.unwrap_or(Activation::Block(0));
+				match b.eip1108_transition {
+					// the legacy EIP-1108 fields are the same as an explicit two-stage schedule
+					Some(transition) => {
+						warn!(
+							target: "builtin",
+							"Builtin {}: eip1108_transition is deprecated, use a multi-pricing schedule activating the EIP-1108 pricing at block {} instead",
+							b.name, transition.0,
+						);
+						let transition = Activation::Block(transition.into());
+						let repriced = pricer_from_json(eip1108_pricing(&b.name, &pricing)?);
+						if activation.same_kind(&transition) && !activation.is_before(&transition) {
+							vec![(activation, repriced)]
+						} else {
+							vec![(activation, pricer_from_json(pricing)), (transition, repriced)]
+						}
 					}
-				})
+					None => vec![(activation, pricer_from_json(pricing))],
+				}
 			}
-			ethjson::spec::Pricing::AltBn128Pairing(pricer) => {
-				Box::new(AltBn128PairingPricer {
-					price: AltBn128PairingPrice {
-						base: pricer.base,
-						pair: pricer.pair,
-					},
-					eip1108_transition_at: b.eip1108_transition.map_or(u64::max_value(), Into::into),
-					eip1108_transition_price: AltBn128PairingPrice {
-						base: pricer.eip1108_transition_base,
-						pair: pricer.eip1108_transition_pair,
-					},
-				})
+			ethjson::spec::Pricing::Multi(_) if b.eip1108_transition.is_some() => {
+				return Err(EthcoreError::Msg(format!(
+					"builtin {}: eip1108_transition can't be combined with a multi-pricing schedule", b.name,
+				)));
 			}
-			ethjson::spec::Pricing::AltBn128ConstOperations(pricer) => {
-				Box::new(AltBn128ConstOperations {
-						price: pricer.price,
-						eip1108_transition_price: pricer.eip1108_transition_price,
-						eip1108_transition_at: b.eip1108_transition.map_or(u64::max_value(), Into::into)
-				})
+			ethjson::spec::Pricing::Multi(pricings) => {
+				if pricings.is_empty() {
+					return Err(EthcoreError::Msg(format!("builtin {}: empty multi-pricing schedule", b.name)));
+				}
+
+				let mut pricer: Vec<(Activation, Box<dyn Pricer>)> = Vec::with_capacity(pricings.len());
+				for p in pricings {
+					let activation = Activation::from_json(&b.name, p.activate_at, p.activate_at_timestamp)?
+						.unwrap_or(Activation::Block(0));
+					let previous = pricer.iter().rev().map(|(a, _)| *a).find(|a| a.same_kind(&activation));
+					if let Some(previous) = previous {
+						if !previous.is_before(&activation) {
+							return Err(EthcoreError::Msg(format!(
+								"builtin {}: multi-pricing activations must be strictly increasing, found {} followed by {}",
+								b.name, previous, activation,
+							)));
+						}
+					}
+					pricer.push((activation, pricer_from_json(p.price)));
+				}
+				pricer
 			}
 		};
 
+		let activate_at = activate_at.or_else(|| pricer.first().map(|(activation, _)| *activation)).unwrap_or(Activation::Block(0));
+		// a call to an active builtin without a pricing would cost nothing
+		if !pricer.iter().any(|(activation, _)| activation.is_reached_by(&activate_at)) {
+			return Err(EthcoreError::Msg(format!(
+				"builtin {}: no pricing is active at its activation, {}", b.name, activate_at,
+			)));
+		}
+		let deactivate_at: Option<u64> = b.deactivate_at.map(Into::into);
+		if let Some(deactivate_at) = deactivate_at {
+			if let Activation::Block(activate_at) = activate_at {
+				if deactivate_at <= activate_at {
+					return Err(EthcoreError::Msg(format!(
+						"builtin {}: deactivate_at block {} must be after activate_at block {}", b.name, deactivate_at, activate_at,
+					)));
+				}
+			}
+			for (activation, _) in &pricer {
+				if let Activation::Block(block) = activation {
+					if *block >= deactivate_at {
+						warn!(target: "builtin", "Builtin {}: pricing activated at block {} is never used, the builtin is deactivated at block {}", b.name, block, deactivate_at);
+					}
+				}
+			}
+		}
+
 		let native = ethereum_builtin(&b.name)?;
 		Ok(Builtin {
+			spec,
+			activate_at,
+			deactivate_at,
 			pricer,
 			native,
-			activate_at: b.activate_at.map_or(0, Into::into),
+			max_input_length: b.max_input_length.map(|len| len as usize),
+			fallback_to_code: b.fallback_to_code,
+			metered: b.metered,
 		})
 	}
 }
 
+/// Names of all natively implemented built-in contracts.
+const BUILTIN_NAMES: &[&str] = &[
+	"identity",
+	"ecrecover",
+	"sha256",
+	"ripemd160",
+	"modexp",
+	"alt_bn128_add",
+	"alt_bn128_mul",
+	"alt_bn128_pairing",
+	"blake2_f",
+	"bls12_381_g1_add",
+	"bls12_381_g1_mul",
+	"bls12_381_g1_multiexp",
+	"bls12_381_g2_add",
+	"bls12_381_g2_mul",
+	"bls12_381_g2_multiexp",
+	"bls12_381_pairing",
+	"bls12_381_fp_to_g1",
+	"bls12_381_fp2_to_g2",
+	"p256_verify",
+	"kzg_point_evaluation",
+];
+
 /// Ethereum built-in factory.
+///
+/// Fails with a descriptive error if there is no native implementation for `name`.
 fn ethereum_builtin(name: &str) -> Result<Box<dyn Implementation>, EthcoreError> {
 	let implementation = match name {
 		"identity" => Box::new(Identity) as Box<dyn Implementation>,
@@ -284,11 +739,50 @@ fn ethereum_builtin(name: &str) -> Result<Box<dyn Implementation>, EthcoreError>
 		"alt_bn128_mul" => Box::new(Bn128Mul) as Box<dyn Implementation>,
 		"alt_bn128_pairing" => Box::new(Bn128Pairing) as Box<dyn Implementation>,
 		"blake2_f" => Box::new(Blake2F) as Box<dyn Implementation>,
-		_ => return Err(EthcoreError::Msg(format!("invalid builtin name: {}", name))),
+		#[cfg(feature = "bls12-381")]
+		"bls12_381_g1_add" => Box::new(Bls12G1Add) as Box<dyn Implementation>,
+		#[cfg(feature = "bls12-381")]
+		"bls12_381_g1_mul" => Box::new(Bls12G1Mul) as Box<dyn Implementation>,
+		#[cfg(feature = "bls12-381")]
+		"bls12_381_g1_multiexp" => Box::new(Bls12G1MultiExp) as Box<dyn Implementation>,
+		#[cfg(feature = "bls12-381")]
+		"bls12_381_g2_add" => Box::new(Bls12G2Add) as Box<dyn Implementation>,
+		#[cfg(feature = "bls12-381")]
+		"bls12_381_g2_mul" => Box::new(Bls12G2Mul) as Box<dyn Implementation>,
+		#[cfg(feature = "bls12-381")]
+		"bls12_381_g2_multiexp" => Box::new(Bls12G2MultiExp) as Box<dyn Implementation>,
+		#[cfg(feature = "bls12-381")]
+		"bls12_381_pairing" => Box::new(Bls12Pairing) as Box<dyn Implementation>,
+		#[cfg(feature = "bls12-381")]
+		"bls12_381_fp_to_g1" => Box::new(Bls12MapFpToG1) as Box<dyn Implementation>,
+		#[cfg(feature = "bls12-381")]
+		"bls12_381_fp2_to_g2" => Box::new(Bls12MapFp2ToG2) as Box<dyn Implementation>,
+		#[cfg(not(feature = "bls12-381"))]
+		"bls12_381_g1_add" | "bls12_381_g1_mul" | "bls12_381_g1_multiexp" |
+		"bls12_381_g2_add" | "bls12_381_g2_mul" | "bls12_381_g2_multiexp" |
+		"bls12_381_pairing" | "bls12_381_fp_to_g1" | "bls12_381_fp2_to_g2" => {
+			return Err(missing_feature(name, "bls12-381"))
+		}
+		"p256_verify" => Box::new(P256Verify) as Box<dyn Implementation>,
+		#[cfg(feature = "kzg")]
+		"kzg_point_evaluation" => Box::new(KzgPointEvaluation) as Box<dyn Implementation>,
+		#[cfg(not(feature = "kzg"))]
+		"kzg_point_evaluation" => return Err(missing_feature(name, "kzg")),
+		_ => return Err(EthcoreError::Msg(format!(
+			"unknown builtin name `{}`, supported builtins are: {}", name, BUILTIN_NAMES.join(", ")
+		))),
 	};
 	Ok(implementation)
 }
 
+/// Error for a known builtin whose native implementation was not compiled in.
+#[cfg(not(all(feature = "bls12-381", feature = "kzg")))]
+fn missing_feature(name: &str, feature: &str) -> EthcoreError {
+	EthcoreError::Msg(format!(
+		"builtin `{}` requires ethcore-builtin to be built with the `{}` feature", name, feature
+	))
+}
+
 // Ethereum builtins:
 //
 // - The identity function
@@ -300,6 +794,9 @@ fn ethereum_builtin(name: &str) -> Result<Box<dyn Implementation>, EthcoreError>
 // - alt_bn128_mul
 // - alt_bn128_pairing
 // - blake2_f (The Blake2 compression function F, EIP-152)
+// - bls12_381_* (BLS12-381 curve operations, EIP-2537)
+// - p256_verify (secp256r1 signature verification, RIP-7212)
+// - kzg_point_evaluation (KZG proof verification, EIP-4844)
 
 #[derive(Debug)]
 struct Identity;
@@ -328,6 +825,49 @@ struct Bn128Pairing;
 #[derive(Debug)]
 struct Blake2F;
 
+#[cfg(feature = "bls12-381")]
+#[derive(Debug)]
+struct Bls12G1Add;
+
+#[cfg(feature = "bls12-381")]
+#[derive(Debug)]
+struct Bls12G1Mul;
+
+#[cfg(feature = "bls12-381")]
+#[derive(Debug)]
+struct Bls12G1MultiExp;
+
+#[cfg(feature = "bls12-381")]
+#[derive(Debug)]
+struct Bls12G2Add;
+
+#[cfg(feature = "bls12-381")]
+#[derive(Debug)]
+struct Bls12G2Mul;
+
+#[cfg(feature = "bls12-381")]
+#[derive(Debug)]
+struct Bls12G2MultiExp;
+
+#[cfg(feature = "bls12-381")]
+#[derive(Debug)]
+struct Bls12Pairing;
+
+#[cfg(feature = "bls12-381")]
+#[derive(Debug)]
+struct Bls12MapFpToG1;
+
+#[cfg(feature = "bls12-381")]
+#[derive(Debug)]
+struct Bls12MapFp2ToG2;
+
+#[derive(Debug)]
+struct P256Verify;
+
+#[cfg(feature = "kzg")]
+#[derive(Debug)]
+struct KzgPointEvaluation;
+
 impl Implementation for Identity {
 	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
 		output.write(0, input);
@@ -605,6 +1145,23 @@ impl Implementation for Bn128Mul {
 	}
 }
 
+/// The part of a `(point, ...)` pairs input that has to be priced: pairs whose point is all zeros,
+/// i.e. the point at infinity, contribute nothing to the result and are neither checked against
+/// the curve nor the subgroup, so they are dropped. A trailing partial pair is kept, and so is the
+/// first pair if all of them are dropped and `keep_one` is set.
+fn priced_pairs(input: &[u8], pair_len: usize, point_len: usize, keep_one: bool) -> Vec<u8> {
+	let full = input.len() - input.len() % pair_len;
+	let mut priced: Vec<u8> = input[..full].chunks(pair_len)
+		.filter(|pair| pair[..point_len].iter().any(|b| *b != 0))
+		.flat_map(|pair| pair.iter().cloned())
+		.collect();
+	if priced.is_empty() && keep_one && full > 0 {
+		priced.extend_from_slice(&input[..pair_len]);
+	}
+	priced.extend_from_slice(&input[full..]);
+	priced
+}
+
 impl Implementation for Bn128Pairing {
 	/// Can fail if:
 	///     - input length is not a multiple of 192
@@ -621,6 +1178,12 @@ impl Implementation for Bn128Pairing {
 		}
 		Ok(())
 	}
+
+	/// Pairs of two points at infinity are not charged.
+	fn execute_metered(&self, input: &[u8], output: &mut BytesRef, pricer: &dyn Pricer, at: u64) -> Result<Option<U256>, &'static str> {
+		self.execute(input, output)?;
+		Ok(Some(pricer.cost(&priced_pairs(input, 192, 192, false), at)))
+	}
 }
 
 impl Bn128Pairing {
@@ -683,29 +1246,315 @@ impl Bn128Pairing {
 	}
 }
 
+/// Writes the result of an EIP-2537 operation to `output`.
+///
+/// Malformed inputs, including points that are not on the curve or not in the correct subgroup,
+/// are reported as errors so that the call fails and consumes all supplied gas.
+#[cfg(feature = "bls12-381")]
+fn write_bls12_result<R: AsRef<[u8]>, E: fmt::Debug>(
+	name: &str,
+	result: Result<R, E>,
+	output: &mut BytesRef,
+) -> Result<(), &'static str> {
+	match result {
+		Ok(bytes) => {
+			output.write(0, bytes.as_ref());
+			Ok(())
+		}
+		Err(e) => {
+			trace!(target: "builtin", "{} error: {:?}", name, e);
+			Err("BLS12-381 precompile called with invalid input")
+		}
+	}
+}
+
+#[cfg(feature = "bls12-381")]
+impl Implementation for Bls12G1Add {
+	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+		write_bls12_result("Bls12G1Add", EIP2537Executor::g1_add(input), output)
+	}
+}
+
+#[cfg(feature = "bls12-381")]
+impl Implementation for Bls12G1Mul {
+	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+		write_bls12_result("Bls12G1Mul", EIP2537Executor::g1_mul(input), output)
+	}
+}
+
+#[cfg(feature = "bls12-381")]
+impl Implementation for Bls12G1MultiExp {
+	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+		write_bls12_result("Bls12G1MultiExp", EIP2537Executor::g1_multiexp(input), output)
+	}
+
+	/// Pairs with the point at infinity are not charged, and the discount applies to the rest.
+	fn execute_metered(&self, input: &[u8], output: &mut BytesRef, pricer: &dyn Pricer, at: u64) -> Result<Option<U256>, &'static str> {
+		self.execute(input, output)?;
+		Ok(Some(pricer.cost(&priced_pairs(input, 160, 128, true), at)))
+	}
+}
+
+#[cfg(feature = "bls12-381")]
+impl Implementation for Bls12G2Add {
+	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+		write_bls12_result("Bls12G2Add", EIP2537Executor::g2_add(input), output)
+	}
+}
+
+#[cfg(feature = "bls12-381")]
+impl Implementation for Bls12G2Mul {
+	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+		write_bls12_result("Bls12G2Mul", EIP2537Executor::g2_mul(input), output)
+	}
+}
+
+#[cfg(feature = "bls12-381")]
+impl Implementation for Bls12G2MultiExp {
+	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+		write_bls12_result("Bls12G2MultiExp", EIP2537Executor::g2_multiexp(input), output)
+	}
+
+	/// Pairs with the point at infinity are not charged, and the discount applies to the rest.
+	fn execute_metered(&self, input: &[u8], output: &mut BytesRef, pricer: &dyn Pricer, at: u64) -> Result<Option<U256>, &'static str> {
+		self.execute(input, output)?;
+		Ok(Some(pricer.cost(&priced_pairs(input, 288, 256, true), at)))
+	}
+}
+
+#[cfg(feature = "bls12-381")]
+impl Implementation for Bls12Pairing {
+	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+		write_bls12_result("Bls12Pairing", EIP2537Executor::pair(input), output)
+	}
+
+	/// Pairs of two points at infinity are not charged.
+	fn execute_metered(&self, input: &[u8], output: &mut BytesRef, pricer: &dyn Pricer, at: u64) -> Result<Option<U256>, &'static str> {
+		self.execute(input, output)?;
+		Ok(Some(pricer.cost(&priced_pairs(input, 384, 384, false), at)))
+	}
+}
+
+#[cfg(feature = "bls12-381")]
+impl Implementation for Bls12MapFpToG1 {
+	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+		write_bls12_result("Bls12MapFpToG1", EIP2537Executor::map_fp_to_g1(input), output)
+	}
+}
+
+#[cfg(feature = "bls12-381")]
+impl Implementation for Bls12MapFp2ToG2 {
+	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+		write_bls12_result("Bls12MapFp2ToG2", EIP2537Executor::map_fp2_to_g2(input), output)
+	}
+}
+
+impl Implementation for P256Verify {
+	/// Verifies a secp256r1 signature over a prehashed message. Any malformed input, including
+	/// out-of-range signature scalars and points off the curve, yields empty output.
+	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+		use p256::{EncodedPoint, FieldBytes, ecdsa::{Signature, VerifyingKey, signature::hazmat::PrehashVerifier}};
+
+		if input.len() != 160 {
+			return Ok(());
+		}
+
+		let field_bytes = |bytes: &[u8]| {
+			let mut field_bytes = FieldBytes::default();
+			field_bytes.copy_from_slice(bytes);
+			field_bytes
+		};
+		let hash = &input[0..32];
+		let r = field_bytes(&input[32..64]);
+		let s = field_bytes(&input[64..96]);
+		let x = field_bytes(&input[96..128]);
+		let y = field_bytes(&input[128..160]);
+
+		let signature = match Signature::from_scalars(r, s) {
+			Ok(signature) => signature,
+			Err(_) => return Ok(()),
+		};
+		let key = match VerifyingKey::from_encoded_point(&EncodedPoint::from_affine_coordinates(&x, &y, false)) {
+			Ok(key) => key,
+			Err(_) => return Ok(()),
+		};
+
+		if key.verify_prehash(hash, &signature).is_ok() {
+			let mut result = [0u8; 32];
+			result[31] = 1;
+			output.write(0, &result);
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(feature = "kzg")]
+impl KzgPointEvaluation {
+	/// Version byte of KZG commitment hashes.
+	const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+	/// Number of field elements in a blob.
+	const FIELD_ELEMENTS_PER_BLOB: u64 = 4096;
+	/// Modulus of the BLS12-381 scalar field.
+	const BLS_MODULUS: [u8; 32] = [
+		0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+		0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+	];
+}
+
+#[cfg(feature = "kzg")]
+impl Implementation for KzgPointEvaluation {
+	/// Verifies that the blob committed to by the versioned hash evaluates to `y` at `z`, using the
+	/// trusted setup of the Ethereum KZG ceremony.
+	fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+		use c_kzg::{Bytes32, Bytes48, KzgProof, ethereum_kzg_settings};
+
+		if input.len() != 192 {
+			return Err("input length for KZG point evaluation precompile should be exactly 192 bytes");
+		}
+
+		let versioned_hash = &input[0..32];
+		let commitment = &input[96..144];
+		let mut expected_hash = [0u8; 32];
+		expected_hash.copy_from_slice(&*digest::sha256(commitment));
+		expected_hash[0] = Self::VERSIONED_HASH_VERSION_KZG;
+		if versioned_hash != &expected_hash[..] {
+			return Err("versioned hash does not match the KZG commitment");
+		}
+
+		let z = Bytes32::from_slice(&input[32..64]).expect("slice is 32 bytes long; qed");
+		let y = Bytes32::from_slice(&input[64..96]).expect("slice is 32 bytes long; qed");
+		let commitment = Bytes48::from_slice(commitment).expect("slice is 48 bytes long; qed");
+		let proof = Bytes48::from_slice(&input[144..192]).expect("slice is 48 bytes long; qed");
+
+		match KzgProof::verify_kzg_proof(&commitment, &z, &y, &proof, ethereum_kzg_settings()) {
+			Ok(true) => {
+				let mut result = [0u8; 64];
+				U256::from(Self::FIELD_ELEMENTS_PER_BLOB).to_big_endian(&mut result[0..32]);
+				result[32..64].copy_from_slice(&Self::BLS_MODULUS);
+				output.write(0, &result);
+				Ok(())
+			},
+			Ok(false) => Err("KZG proof verification failed"),
+			Err(e) => {
+				trace!(target: "builtin", "KZG point evaluation failed: {:?}", e);
+				Err("invalid KZG point evaluation input")
+			},
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use std::convert::TryFrom;
+	use common_types::errors::EthcoreError;
 	use ethereum_types::U256;
-	use ethjson::uint::Uint;
+	use ethjson::uint::U64;
 	use num::{BigUint, Zero, One};
 	use parity_bytes::BytesRef;
 	use hex_literal::hex;
-	use super::{Builtin, Linear, ethereum_builtin, Pricer, ModexpPricer, modexp as me};
+	use super::{Activation, BUILTIN_NAMES, Blake2FPricer, Bls12MultiExpPricer, Bls12PairingPricer, Builtin, Implementation, Linear, ethereum_builtin, Pricer, priced_pairs, pricer_from_json, ModexpPricer, Modexp2565Pricer, Modexp7883Pricer, modexp as me};
+
+	fn test_spec(name: &str) -> ethjson::spec::Builtin {
+		ethjson::spec::Builtin {
+			name: name.to_owned(),
+			pricing: ethjson::spec::Pricing::Single(ethjson::spec::PricingInner::Linear(ethjson::spec::Linear { base: 0, word: 0 })),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		}
+	}
+
+	#[test]
+	fn blake2f_cost() {
+		let f = Builtin {
+			spec: test_spec("blake2_f"),
+			pricer: vec![(Activation::Block(0), Box::new(Blake2FPricer { gas_per_round: 123, max_rounds: None }) as Box<dyn Pricer>)],
+			native: ethereum_builtin("blake2_f").expect("known builtin"),
+			activate_at: Activation::Block(0),
+			deactivate_at: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+		};
+		// 5 rounds
+		let input = hex!("0000000548c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b61626300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000001");
+		let mut output = [0u8; 64];
+		f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).unwrap();
+
+		assert_eq!(f.cost(&input[..], 0, 0), U256::from(123*5));
+	}
+
+	#[test]
+	fn execute_metered() {
+		struct Metered;
+
+		impl Implementation for Metered {
+			fn execute(&self, input: &[u8], output: &mut BytesRef) -> Result<(), &'static str> {
+				output.write(0, input);
+				Ok(())
+			}
+
+			fn execute_metered(&self, input: &[u8], output: &mut BytesRef, pricer: &dyn Pricer, at: u64) -> Result<Option<U256>, &'static str> {
+				self.execute(input, output).map(|()| Some(pricer.cost(&input[1..], at)))
+			}
+		}
 
-	#[test]
-	fn blake2f_cost() {
-		let f = Builtin {
-			pricer: Box::new(123),
-			native: ethereum_builtin("blake2_f").expect("known builtin"),
-			activate_at: 0,
+		let metered = Builtin {
+			spec: test_spec("identity"),
+			pricer: vec![(Activation::Block(0), Box::new(Linear { base: 100, word: 10 }) as Box<dyn Pricer>)],
+			native: Box::new(Metered),
+			activate_at: Activation::Block(0),
+			deactivate_at: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: true,
 		};
-		// 5 rounds
-		let input = hex!("0000000548c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b61626300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000001");
-		let mut output = [0u8; 64];
-		f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).unwrap();
+		let mut output = Vec::new();
+		assert_eq!(metered.execute_metered(&[1; 33], &mut BytesRef::Flexible(&mut output), 0, 0), Ok(Some(U256::from(110))));
+		assert_eq!(output, vec![1; 33]);
+
+		// implementations are only asked for the gas used if the spec opts in
+		let unmetered = Builtin { metered: false, ..metered };
+		let mut output = Vec::new();
+		assert_eq!(unmetered.execute_metered(&[1; 33], &mut BytesRef::Flexible(&mut output), 0, 0), Ok(None));
+		assert_eq!(output, vec![1; 33]);
+
+		// existing builtins charge their upfront cost
+		let identity = Builtin {
+			spec: test_spec("identity"),
+			pricer: vec![(Activation::Block(0), Box::new(Linear { base: 100, word: 0 }) as Box<dyn Pricer>)],
+			native: ethereum_builtin("identity").expect("known builtin"),
+			activate_at: Activation::Block(0),
+			deactivate_at: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: true,
+		};
+		let mut output = Vec::new();
+		assert_eq!(identity.execute_metered(&[1, 2, 3], &mut BytesRef::Flexible(&mut output), 0, 0), Ok(None));
+		assert_eq!(output, vec![1, 2, 3]);
+	}
+
+	#[test]
+	fn blake2f_cost_on_short_input() {
+		let pricer: Box<dyn Pricer> = Box::new(Blake2FPricer { gas_per_round: 123, max_rounds: None });
+		assert_eq!(pricer.cost(&[], 0), U256::zero());
+		assert_eq!(pricer.cost(&[0, 0, 5], 0), U256::zero());
+		assert_eq!(pricer.cost(&[0, 0, 0, 5], 0), U256::from(123 * 5));
+	}
 
-		assert_eq!(f.cost(&input[..], 0), U256::from(123*5));
+	#[test]
+	fn blake2f_cost_with_max_rounds() {
+		let pricer: Box<dyn Pricer> = Box::new(Blake2FPricer { gas_per_round: 123, max_rounds: Some(5) });
+		assert_eq!(pricer.cost(&[0, 0, 0, 5], 0), U256::from(123 * 5));
+		assert_eq!(pricer.cost(&[0, 0, 0, 6], 0), U256::max_value());
+		assert_eq!(pricer.cost(&[0xff, 0xff, 0xff, 0xff], 0), U256::max_value());
 	}
 
 	#[test]
@@ -950,16 +1799,21 @@ mod tests {
 	fn modexp() {
 
 		let f = Builtin {
-			pricer: Box::new(ModexpPricer { divisor: 20 }),
+			spec: test_spec("modexp"),
+			pricer: vec![(Activation::Block(0), Box::new(ModexpPricer { divisor: 20 }) as Box<dyn Pricer>)],
 			native: ethereum_builtin("modexp").expect("known builtin"),
-			activate_at: 0,
+			activate_at: Activation::Block(0),
+			deactivate_at: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
 		};
 
 		// test for potential gas cost multiplication overflow
 		{
 			let input = hex!("0000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000003b27bafd00000000000000000000000000000000000000000000000000000000503c8ac3");
 			let expected_cost = U256::max_value();
-			assert_eq!(f.cost(&input[..], 0), expected_cost.into());
+			assert_eq!(f.cost(&input[..], 0, 0), expected_cost.into());
 		}
 
 		// test for potential exp len overflow
@@ -976,7 +1830,7 @@ mod tests {
 
 			f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).expect("Builtin should fail");
 			assert_eq!(output, expected);
-			assert_eq!(f.cost(&input[..], 0), expected_cost.into());
+			assert_eq!(f.cost(&input[..], 0, 0), expected_cost.into());
 		}
 
 		// fermat's little theorem example.
@@ -996,7 +1850,7 @@ mod tests {
 
 			f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).expect("Builtin should not fail");
 			assert_eq!(output, expected);
-			assert_eq!(f.cost(&input[..], 0), expected_cost.into());
+			assert_eq!(f.cost(&input[..], 0, 0), expected_cost.into());
 		}
 
 		// second example from EIP: zero base.
@@ -1015,7 +1869,7 @@ mod tests {
 
 			f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).expect("Builtin should not fail");
 			assert_eq!(output, expected);
-			assert_eq!(f.cost(&input[..], 0), expected_cost.into());
+			assert_eq!(f.cost(&input[..], 0, 0), expected_cost.into());
 		}
 
 		// another example from EIP: zero-padding
@@ -1035,7 +1889,7 @@ mod tests {
 
 			f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).expect("Builtin should not fail");
 			assert_eq!(output, expected);
-			assert_eq!(f.cost(&input[..], 0), expected_cost.into());
+			assert_eq!(f.cost(&input[..], 0, 0), expected_cost.into());
 		}
 
 		// zero-length modulus.
@@ -1053,7 +1907,7 @@ mod tests {
 
 			f.execute(&input[..], &mut BytesRef::Flexible(&mut output)).expect("Builtin should not fail");
 			assert_eq!(output.len(), 0); // shouldn't have written any output.
-			assert_eq!(f.cost(&input[..], 0), expected_cost.into());
+			assert_eq!(f.cost(&input[..], 0, 0), expected_cost.into());
 		}
 	}
 
@@ -1061,9 +1915,14 @@ mod tests {
 	fn bn128_add() {
 
 		let f = Builtin {
-			pricer: Box::new(Linear { base: 0, word: 0 }),
+			spec: test_spec("alt_bn128_add"),
+			pricer: vec![(Activation::Block(0), Box::new(Linear { base: 0, word: 0 }) as Box<dyn Pricer>)],
 			native: ethereum_builtin("alt_bn128_add").expect("known builtin"),
-			activate_at: 0,
+			activate_at: Activation::Block(0),
+			deactivate_at: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
 		};
 
 		// zero-points additions
@@ -1120,9 +1979,14 @@ mod tests {
 	fn bn128_mul() {
 
 		let f = Builtin {
-			pricer: Box::new(Linear { base: 0, word: 0 }),
+			spec: test_spec("alt_bn128_mul"),
+			pricer: vec![(Activation::Block(0), Box::new(Linear { base: 0, word: 0 }) as Box<dyn Pricer>)],
 			native: ethereum_builtin("alt_bn128_mul").expect("known builtin"),
-			activate_at: 0,
+			activate_at: Activation::Block(0),
+			deactivate_at: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
 		};
 
 		// zero-point multiplication
@@ -1160,9 +2024,14 @@ mod tests {
 
 	fn builtin_pairing() -> Builtin {
 		Builtin {
-			pricer: Box::new(Linear { base: 0, word: 0 }),
+			spec: test_spec("alt_bn128_pairing"),
+			pricer: vec![(Activation::Block(0), Box::new(Linear { base: 0, word: 0 }) as Box<dyn Pricer>)],
 			native: ethereum_builtin("alt_bn128_pairing").expect("known builtin"),
-			activate_at: 0,
+			activate_at: Activation::Block(0),
+			deactivate_at: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
 		}
 	}
 
@@ -1236,33 +2105,77 @@ mod tests {
 		let _ = ethereum_builtin("foo").unwrap();
 	}
 
+	#[test]
+	fn unknown_builtin_error_lists_supported_names() {
+		let err = ethereum_builtin("alt_bn128_pariring").err().expect("unknown builtin").to_string();
+		assert!(err.contains("alt_bn128_pariring"));
+		for name in BUILTIN_NAMES {
+			assert!(err.contains(name));
+		}
+	}
+
+	#[test]
+	fn all_builtin_names_resolve() {
+		for name in BUILTIN_NAMES {
+			match ethereum_builtin(name) {
+				Ok(_) => {},
+				Err(e) => assert!(e.to_string().contains("feature"), "{} should be a known builtin", name),
+			}
+		}
+	}
+
+	#[cfg(not(feature = "kzg"))]
+	#[test]
+	fn kzg_point_evaluation_requires_feature() {
+		let err = ethereum_builtin("kzg_point_evaluation").err().expect("feature is disabled").to_string();
+		assert!(err.contains("`kzg` feature"));
+	}
+
+	#[cfg(not(feature = "bls12-381"))]
+	#[test]
+	fn bls12_381_requires_feature() {
+		let err = ethereum_builtin("bls12_381_pairing").err().expect("feature is disabled").to_string();
+		assert!(err.contains("bls12_381_pairing"));
+		assert!(err.contains("`bls12-381` feature"));
+	}
+
 	#[test]
 	fn is_active() {
 		let pricer = Box::new(Linear { base: 10, word: 20} );
 		let b = Builtin {
-			pricer: pricer as Box<dyn Pricer>,
+			spec: test_spec("identity"),
+			pricer: vec![(Activation::Block(0), pricer as Box<dyn Pricer>)],
 			native: ethereum_builtin("identity").expect("known builtin"),
-			activate_at: 100_000,
+			activate_at: Activation::Block(100_000),
+			deactivate_at: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
 		};
 
-		assert!(!b.is_active(99_999));
-		assert!(b.is_active(100_000));
-		assert!(b.is_active(100_001));
+		assert!(!b.is_active(99_999, 0));
+		assert!(b.is_active(100_000, 0));
+		assert!(b.is_active(100_001, 0));
 	}
 
 	#[test]
 	fn from_named_linear() {
 		let pricer = Box::new(Linear { base: 10, word: 20 });
 		let b = Builtin {
-			pricer: pricer as Box<dyn Pricer>,
+			spec: test_spec("identity"),
+			pricer: vec![(Activation::Block(0), pricer as Box<dyn Pricer>)],
 			native: ethereum_builtin("identity").expect("known builtin"),
-			activate_at: 1,
+			activate_at: Activation::Block(1),
+			deactivate_at: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
 		};
 
-		assert_eq!(b.cost(&[0; 0], 0), U256::from(10));
-		assert_eq!(b.cost(&[0; 1], 0), U256::from(30));
-		assert_eq!(b.cost(&[0; 32], 0), U256::from(30));
-		assert_eq!(b.cost(&[0; 33], 0), U256::from(50));
+		assert_eq!(b.cost(&[0; 0], 0, 0), U256::from(10));
+		assert_eq!(b.cost(&[0; 1], 0, 0), U256::from(30));
+		assert_eq!(b.cost(&[0; 32], 0, 0), U256::from(30));
+		assert_eq!(b.cost(&[0; 33], 0, 0), U256::from(50));
 
 		let i = [0u8, 1, 2, 3];
 		let mut o = [255u8; 4];
@@ -1274,18 +2187,24 @@ mod tests {
 	fn from_json() {
 		let b = Builtin::try_from(ethjson::spec::Builtin {
 			name: "identity".to_owned(),
-			pricing: ethjson::spec::Pricing::Linear(ethjson::spec::Linear {
+			pricing: ethjson::spec::Pricing::Single(ethjson::spec::PricingInner::Linear(ethjson::spec::Linear {
 				base: 10,
 				word: 20,
-			}),
+			})),
 			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
 			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
 		}).expect("known builtin");
 
-		assert_eq!(b.cost(&[0; 0], 0), U256::from(10));
-		assert_eq!(b.cost(&[0; 1], 0), U256::from(30));
-		assert_eq!(b.cost(&[0; 32], 0), U256::from(30));
-		assert_eq!(b.cost(&[0; 33], 0), U256::from(50));
+		assert_eq!(b.cost(&[0; 0], 0, 0), U256::from(10));
+		assert_eq!(b.cost(&[0; 1], 0, 0), U256::from(30));
+		assert_eq!(b.cost(&[0; 32], 0, 0), U256::from(30));
+		assert_eq!(b.cost(&[0; 33], 0, 0), U256::from(50));
 
 		let i = [0u8, 1, 2, 3];
 		let mut o = [255u8; 4];
@@ -1293,53 +2212,882 @@ mod tests {
 		assert_eq!(i, o);
 	}
 
+	#[test]
+	fn priced_pairs_drop_points_at_infinity() {
+		let pair = |point: u8, scalar: u8| [&[point; 4][..], &[scalar; 2][..]].concat();
+		let input = [pair(0, 1), pair(1, 0), pair(0, 0), pair(2, 2)].concat();
+		assert_eq!(priced_pairs(&input, 6, 4, false), [pair(1, 0), pair(2, 2)].concat());
+
+		// a trailing partial pair is priced as is
+		let input = [pair(0, 1), vec![0; 3]].concat();
+		assert_eq!(priced_pairs(&input, 6, 4, false), vec![0; 3]);
+
+		// at least one pair can be kept
+		let input = [pair(0, 1), pair(0, 2)].concat();
+		assert_eq!(priced_pairs(&input, 6, 4, false), Vec::<u8>::new());
+		assert_eq!(priced_pairs(&input, 6, 4, true), pair(0, 1));
+	}
+
+	#[test]
+	fn bn128_pairing_metered() {
+		let b = Builtin::try_from(ethjson::spec::Builtin::new(
+			"alt_bn128_pairing",
+			ethjson::spec::PricingInner::AltBn128Pairing(ethjson::spec::builtin::AltBn128Pairing {
+				base: 45_000,
+				pair: 34_000,
+				eip1108_transition_base: 45_000,
+				eip1108_transition_pair: 34_000,
+			}),
+		).metered()).expect("known builtin");
+
+		// the generators of G1 and G2, followed by two pairs of points at infinity
+		let generators = hex!("
+			0000000000000000000000000000000000000000000000000000000000000001
+			0000000000000000000000000000000000000000000000000000000000000002
+			198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2
+			1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed
+			090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b
+			12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa"
+		);
+		let input = [&generators[..], &[0u8; 192 * 2][..]].concat();
+		assert_eq!(b.cost(&input, 0, 0), U256::from(147_000));
+
+		let mut output = Vec::new();
+		assert_eq!(b.execute_metered(&input, &mut BytesRef::Flexible(&mut output), 0, 0), Ok(Some(U256::from(79_000))));
+		assert_eq!(output, vec![0u8; 32]);
+
+		let mut output = Vec::new();
+		assert_eq!(b.execute_metered(&[0u8; 192 * 2], &mut BytesRef::Flexible(&mut output), 0, 0), Ok(Some(U256::from(45_000))));
+		let mut one = [0u8; 32];
+		one[31] = 1;
+		assert_eq!(output, one.to_vec());
+	}
+
 	#[test]
 	fn bn128_pairing_eip1108_transition() {
 		let b = Builtin::try_from(ethjson::spec::Builtin {
 			name: "alt_bn128_pairing".to_owned(),
-			pricing: ethjson::spec::Pricing::AltBn128Pairing(ethjson::spec::builtin::AltBn128Pairing {
+			pricing: ethjson::spec::Pricing::Single(ethjson::spec::PricingInner::AltBn128Pairing(ethjson::spec::builtin::AltBn128Pairing {
 				base: 100_000,
 				pair: 80_000,
 				eip1108_transition_base: 45_000,
 				eip1108_transition_pair: 34_000,
-			}),
-			activate_at: Some(Uint(U256::from(10))),
-			eip1108_transition: Some(Uint(U256::from(20))),
+			})),
+			activate_at: Some(U64(10)),
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: Some(U64(20)),
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
 		}).expect("known builtin");
 
-		assert_eq!(b.cost(&[0; 192 * 3], 10), U256::from(340_000), "80 000 * 3 + 100 000 == 340 000");
-		assert_eq!(b.cost(&[0; 192 * 7], 20), U256::from(283_000), "34 000 * 7 + 45 000 == 283 000");
+		assert_eq!(b.cost(&[0; 192 * 3], 10, 0), U256::from(340_000), "80 000 * 3 + 100 000 == 340 000");
+		assert_eq!(b.cost(&[0; 192 * 7], 20, 0), U256::from(283_000), "34 000 * 7 + 45 000 == 283 000");
 	}
 
 	#[test]
 	fn bn128_add_eip1108_transition() {
 		let b = Builtin::try_from(ethjson::spec::Builtin {
 			name: "alt_bn128_add".to_owned(),
-			pricing: ethjson::spec::Pricing::AltBn128ConstOperations(ethjson::spec::builtin::AltBn128ConstOperations {
+			pricing: ethjson::spec::Pricing::Single(ethjson::spec::PricingInner::AltBn128ConstOperations(ethjson::spec::builtin::AltBn128ConstOperations {
 				price: 500,
 				eip1108_transition_price: 150,
-			}),
-			activate_at: Some(Uint(U256::from(10))),
-			eip1108_transition: Some(Uint(U256::from(20))),
+			})),
+			activate_at: Some(U64(10)),
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: Some(U64(20)),
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
 		}).expect("known builtin");
 
-		assert_eq!(b.cost(&[0; 192], 10), U256::from(500));
-		assert_eq!(b.cost(&[0; 10], 20), U256::from(150), "after istanbul hardfork gas cost for add should be 150");
+		assert_eq!(b.cost(&[0; 192], 10, 0), U256::from(500));
+		assert_eq!(b.cost(&[0; 10], 20, 0), U256::from(150), "after istanbul hardfork gas cost for add should be 150");
 	}
 
 	#[test]
 	fn bn128_mul_eip1108_transition() {
 		let b = Builtin::try_from(ethjson::spec::Builtin {
 			name: "alt_bn128_mul".to_owned(),
-			pricing: ethjson::spec::Pricing::AltBn128ConstOperations(ethjson::spec::builtin::AltBn128ConstOperations {
+			pricing: ethjson::spec::Pricing::Single(ethjson::spec::PricingInner::AltBn128ConstOperations(ethjson::spec::builtin::AltBn128ConstOperations {
 				price: 40_000,
 				eip1108_transition_price: 6000,
-			}),
-			activate_at: Some(Uint(U256::from(10))),
-			eip1108_transition: Some(Uint(U256::from(20))),
+			})),
+			activate_at: Some(U64(10)),
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: Some(U64(20)),
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		}).expect("known builtin");
+
+		assert_eq!(b.cost(&[0; 192], 10, 0), U256::from(40_000));
+		assert_eq!(b.cost(&[0; 10], 20, 0), U256::from(6_000), "after istanbul hardfork gas cost for mul should be 6 000");
+	}
+
+	#[test]
+	fn eip1108_transition_matches_multi_pricing() {
+		let legacy_pricing = ethjson::spec::PricingInner::AltBn128Pairing(ethjson::spec::builtin::AltBn128Pairing {
+			base: 100_000,
+			pair: 80_000,
+			eip1108_transition_base: 45_000,
+			eip1108_transition_pair: 34_000,
+		});
+		let legacy = Builtin::try_from(ethjson::spec::Builtin {
+			name: "alt_bn128_pairing".to_owned(),
+			pricing: ethjson::spec::Pricing::Single(legacy_pricing.clone()),
+			activate_at: Some(U64(10)),
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: Some(U64(20)),
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		}).expect("known builtin");
+
+		let multi = Builtin::try_from(ethjson::spec::Builtin {
+			name: "alt_bn128_pairing".to_owned(),
+			pricing: ethjson::spec::Pricing::Multi(vec![
+				ethjson::spec::PricingAt {
+					activate_at: Some(U64(10)),
+					activate_at_timestamp: None,
+					price: ethjson::spec::PricingInner::AltBn128Pairing(ethjson::spec::builtin::AltBn128Pairing {
+						base: 100_000,
+						pair: 80_000,
+						eip1108_transition_base: 0,
+						eip1108_transition_pair: 0,
+					}),
+				},
+				ethjson::spec::PricingAt {
+					activate_at: Some(U64(20)),
+					activate_at_timestamp: None,
+					price: ethjson::spec::PricingInner::AltBn128Pairing(ethjson::spec::builtin::AltBn128Pairing {
+						base: 45_000,
+						pair: 34_000,
+						eip1108_transition_base: 0,
+						eip1108_transition_pair: 0,
+					}),
+				},
+			]),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		}).expect("known builtin");
+
+		for block in &[10, 19, 20, 21, 1_000_000] {
+			for pairs in &[0, 1, 3] {
+				let input = vec![0u8; 192 * pairs];
+				assert_eq!(legacy.cost(&input, *block, 0), multi.cost(&input, *block, 0), "block {}, {} pairs", block, pairs);
+			}
+		}
+		assert_eq!(legacy.cost(&[0; 192], 19, 0), U256::from(180_000));
+		assert_eq!(legacy.cost(&[0; 192], 20, 0), U256::from(79_000));
+
+		// the legacy fields are ambiguous within a multi-pricing schedule
+		let err = Builtin::try_from(ethjson::spec::Builtin {
+			name: "alt_bn128_pairing".to_owned(),
+			pricing: ethjson::spec::Pricing::Multi(vec![ethjson::spec::PricingAt {
+				activate_at: Some(U64(10)),
+				activate_at_timestamp: None,
+				price: legacy_pricing,
+			}]),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: Some(U64(20)),
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		}).err().expect("legacy fields combined with multi-pricing").to_string();
+		assert!(err.contains("eip1108_transition can't be combined with a multi-pricing schedule"));
+
+		// only alt_bn128 pricings have EIP-1108 prices
+		assert!(Builtin::try_from(ethjson::spec::Builtin {
+			name: "identity".to_owned(),
+			pricing: ethjson::spec::Pricing::Single(ethjson::spec::PricingInner::Linear(ethjson::spec::Linear { base: 15, word: 3 })),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: Some(U64(20)),
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		}).is_err());
+	}
+
+	#[test]
+	fn bls12_g1_add_multi_pricing() {
+		let b = Builtin::try_from(ethjson::spec::Builtin {
+			name: "identity".to_owned(),
+			pricing: ethjson::spec::Pricing::Multi(vec![
+				ethjson::spec::PricingAt {
+					activate_at: Some(U64(10)),
+					activate_at_timestamp: None,
+					price: ethjson::spec::PricingInner::Bls12G1Add { price: 600 },
+				},
+				ethjson::spec::PricingAt {
+					activate_at: Some(U64(20)),
+					activate_at_timestamp: None,
+					price: ethjson::spec::PricingInner::Bls12G1Add { price: 500 },
+				},
+			]),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		}).expect("known builtin");
+
+		assert!(!b.is_active(9, 0));
+		assert!(b.is_active(10, 0));
+		assert_eq!(b.cost(&[0; 256], 10, 0), U256::from(600));
+		assert_eq!(b.cost(&[0; 256], 19, 0), U256::from(600));
+		assert_eq!(b.cost(&[0; 256], 20, 0), U256::from(500));
+	}
+
+	#[test]
+	fn is_active_at_timestamp() {
+		let b = Builtin::try_from(ethjson::spec::Builtin {
+			name: "identity".to_owned(),
+			pricing: ethjson::spec::Pricing::Single(ethjson::spec::PricingInner::Linear(ethjson::spec::Linear {
+				base: 10,
+				word: 20,
+			})),
+			activate_at: None,
+			activate_at_timestamp: Some(U64(1_000)),
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		}).expect("known builtin");
+
+		assert!(!b.is_active(100_000, 999));
+		assert!(b.is_active(0, 1_000));
+		assert!(b.is_active(0, 1_001));
+		assert_eq!(b.cost(&[0; 0], 0, 0), U256::zero());
+		assert_eq!(b.cost(&[0; 0], 0, 1_000), U256::from(10));
+	}
+
+	#[test]
+	fn multi_pricing_at_timestamp() {
+		let b = Builtin::try_from(ethjson::spec::Builtin {
+			name: "identity".to_owned(),
+			pricing: ethjson::spec::Pricing::Multi(vec![
+				ethjson::spec::PricingAt {
+					activate_at: Some(U64(10)),
+					activate_at_timestamp: None,
+					price: ethjson::spec::PricingInner::Bls12G1Add { price: 600 },
+				},
+				ethjson::spec::PricingAt {
+					activate_at: None,
+					activate_at_timestamp: Some(U64(1_000)),
+					price: ethjson::spec::PricingInner::Bls12G1Add { price: 500 },
+				},
+			]),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		}).expect("known builtin");
+
+		assert!(b.is_active(10, 0));
+		assert_eq!(b.cost(&[0; 256], 10, 999), U256::from(600));
+		assert_eq!(b.cost(&[0; 256], 10, 1_000), U256::from(500));
+	}
+
+	#[test]
+	fn block_and_timestamp_activation_conflict() {
+		let res = Builtin::try_from(ethjson::spec::Builtin {
+			name: "identity".to_owned(),
+			pricing: ethjson::spec::Pricing::Multi(vec![
+				ethjson::spec::PricingAt {
+					activate_at: Some(U64(10)),
+					activate_at_timestamp: Some(U64(1_000)),
+					price: ethjson::spec::PricingInner::Bls12G1Add { price: 600 },
+				},
+			]),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		});
+		assert!(res.is_err());
+
+		let res = Builtin::try_from(ethjson::spec::Builtin {
+			name: "identity".to_owned(),
+			pricing: ethjson::spec::Pricing::Single(ethjson::spec::PricingInner::Linear(ethjson::spec::Linear {
+				base: 10,
+				word: 20,
+			})),
+			activate_at: Some(U64(10)),
+			activate_at_timestamp: Some(U64(1_000)),
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		});
+		assert!(res.is_err());
+	}
+
+	#[test]
+	fn bls12_multiexp_cost() {
+		let pricer = Bls12MultiExpPricer {
+			base: 12000,
+			discount: vec![1000, 900, 800],
+			pair_len: 160,
+		};
+
+		assert_eq!(pricer.cost(&[], 0), U256::zero());
+		assert_eq!(pricer.cost(&[0; 160], 0), U256::from(12000));
+		assert_eq!(pricer.cost(&[0; 320], 0), U256::from(2 * 12000 * 900 / 1000));
+		assert_eq!(pricer.cost(&[0; 480], 0), U256::from(3 * 12000 * 800 / 1000));
+		// past the end of the table the last discount applies
+		assert_eq!(pricer.cost(&[0; 1600], 0), U256::from(10 * 12000 * 800 / 1000));
+	}
+
+	fn modexp_input(base_len: usize, exp: &[u8], mod_len: usize) -> Vec<u8> {
+		let mut input = vec![0u8; 96];
+		U256::from(base_len).to_big_endian(&mut input[0..32]);
+		U256::from(exp.len()).to_big_endian(&mut input[32..64]);
+		U256::from(mod_len).to_big_endian(&mut input[64..96]);
+		input.extend(vec![0xffu8; base_len]);
+		input.extend_from_slice(exp);
+		input.extend(vec![0xffu8; mod_len]);
+		input
+	}
+
+	fn p256_input(hash: &[u8], r: &[u8], s: &[u8]) -> Vec<u8> {
+		// public key of the P-256 test key from RFC 6979, appendix A.2.5
+		let x = hex!("60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6");
+		let y = hex!("7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299");
+		[hash, r, s, &x[..], &y[..]].concat()
+	}
+
+	#[test]
+	fn p256_verify() {
+		let f = ethereum_builtin("p256_verify").expect("known builtin");
+		let mut success = [0u8; 32];
+		success[31] = 1;
+
+		// SHA-256 signatures of "sample" and "test" from RFC 6979, appendix A.2.5
+		let sample = hex!("af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf");
+		let sample_r = hex!("efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716");
+		let sample_s = hex!("f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8");
+		let test = hex!("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08");
+		let test_r = hex!("f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367");
+		let test_s = hex!("019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083");
+
+		for input in &[p256_input(&sample, &sample_r, &sample_s), p256_input(&test, &test_r, &test_s)] {
+			let mut output = Vec::new();
+			f.execute(&input[..], &mut BytesRef::Flexible(&mut output)).expect("Builtin should not fail");
+			assert_eq!(&output[..], &success[..]);
+		}
+
+		let n = hex!("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551");
+		let mut off_curve = p256_input(&sample, &sample_r, &sample_s);
+		off_curve[159] ^= 1;
+		let failures = vec![
+			("wrong message", p256_input(&test, &sample_r, &sample_s)),
+			("zero r", p256_input(&sample, &[0u8; 32], &sample_s)),
+			("r equal to the group order", p256_input(&sample, &n, &sample_s)),
+			("s above the group order", p256_input(&sample, &sample_r, &[0xffu8; 32])),
+			("point not on the curve", off_curve),
+			("short input", p256_input(&sample, &sample_r, &sample_s)[..159].to_vec()),
+			("long input", [&p256_input(&sample, &sample_r, &sample_s)[..], &[0u8][..]].concat()),
+		];
+
+		for (name, input) in failures {
+			let mut output = Vec::new();
+			f.execute(&input[..], &mut BytesRef::Flexible(&mut output)).expect("Builtin should not fail");
+			assert!(output.is_empty(), "{}", name);
+		}
+	}
+
+	#[cfg(feature = "kzg")]
+	#[test]
+	fn kzg_point_evaluation() {
+		let f = ethereum_builtin("kzg_point_evaluation").expect("known builtin");
+
+		// the commitment to the zero polynomial and its proof are both the point at infinity, so
+		// the evaluation at any point is zero
+		let versioned_hash = hex!("010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014");
+		let z = hex!("0000000000000000000000000000000000000000000000000000000000000002");
+		let mut infinity = [0u8; 48];
+		infinity[0] = 0xc0;
+		let input = [&versioned_hash[..], &z[..], &[0u8; 32][..], &infinity[..], &infinity[..]].concat();
+
+		let mut output = Vec::new();
+		f.execute(&input[..], &mut BytesRef::Flexible(&mut output)).expect("Builtin should not fail");
+		assert_eq!(output, hex!("000000000000000000000000000000000000000000000000000000000000100073eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001").to_vec());
+
+		// wrong evaluation
+		let mut wrong_y = input.clone();
+		wrong_y[95] = 1;
+		assert!(f.execute(&wrong_y[..], &mut BytesRef::Flexible(&mut Vec::new())).is_err());
+
+		// versioned hash with the wrong version
+		let mut wrong_version = input.clone();
+		wrong_version[0] = 0x02;
+		assert!(f.execute(&wrong_version[..], &mut BytesRef::Flexible(&mut Vec::new())).is_err());
+
+		// evaluation point outside of the scalar field
+		let mut wrong_z = input.clone();
+		wrong_z[32..64].copy_from_slice(&[0xffu8; 32]);
+		assert!(f.execute(&wrong_z[..], &mut BytesRef::Flexible(&mut Vec::new())).is_err());
+
+		// invalid input length
+		assert!(f.execute(&input[..191], &mut BytesRef::Flexible(&mut Vec::new())).is_err());
+	}
+
+	#[test]
+	fn modexp_eip2565_cost() {
+		let pricer = Modexp2565Pricer { divisor: 3, min_price: 200 };
+		let exp = hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e");
+
+		// Test vectors from https://eips.ethereum.org/EIPS/eip-2565#test-cases
+		let vectors: &[(&str, Vec<u8>, u64)] = &[
+			("eip_example1", modexp_input(1, &exp, 32), 1360),
+			("eip_example2", modexp_input(0, &exp, 32), 1360),
+			("nagydani-1-square", modexp_input(64, &[0x02], 64), 200),
+			("nagydani-1-qube", modexp_input(64, &[0x03], 64), 200),
+			("nagydani-1-pow0x10001", modexp_input(64, &[0x01, 0x00, 0x01], 64), 341),
+			("nagydani-2-square", modexp_input(128, &[0x02], 128), 200),
+			("nagydani-2-qube", modexp_input(128, &[0x03], 128), 200),
+			("nagydani-2-pow0x10001", modexp_input(128, &[0x01, 0x00, 0x01], 128), 1365),
+			("nagydani-3-square", modexp_input(256, &[0x02], 256), 341),
+			("nagydani-3-qube", modexp_input(256, &[0x03], 256), 341),
+			("nagydani-3-pow0x10001", modexp_input(256, &[0x01, 0x00, 0x01], 256), 5461),
+			("nagydani-4-square", modexp_input(512, &[0x02], 512), 1365),
+			("nagydani-4-qube", modexp_input(512, &[0x03], 512), 1365),
+			("nagydani-4-pow0x10001", modexp_input(512, &[0x01, 0x00, 0x01], 512), 21845),
+			("nagydani-5-square", modexp_input(1024, &[0x02], 1024), 5461),
+			("nagydani-5-qube", modexp_input(1024, &[0x03], 1024), 5461),
+			("nagydani-5-pow0x10001", modexp_input(1024, &[0x01, 0x00, 0x01], 1024), 87381),
+		];
+
+		for (name, input, expected) in vectors {
+			assert_eq!(pricer.cost(input, 0), U256::from(*expected), "{}", name);
+		}
+
+		// empty base and modulus are charged the minimum price
+		assert_eq!(pricer.cost(&[0u8; 96], 0), U256::from(200));
+	}
+
+	#[test]
+	fn modexp_eip7883_cost() {
+		let pricer = Modexp7883Pricer { min_price: 500 };
+		let exp = hex!("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e");
+
+		let vectors: &[(&str, Vec<u8>, u64)] = &[
+			("eip_example1", modexp_input(1, &exp, 32), 4080),
+			("eip_example2", modexp_input(0, &exp, 32), 4080),
+			("nagydani-1-square", modexp_input(64, &[0x02], 64), 500),
+			("nagydani-1-pow0x10001", modexp_input(64, &[0x01, 0x00, 0x01], 64), 2048),
+			("nagydani-3-square", modexp_input(256, &[0x02], 256), 2048),
+			("nagydani-5-pow0x10001", modexp_input(1024, &[0x01, 0x00, 0x01], 1024), 524288),
+		];
+
+		for (name, input, expected) in vectors {
+			assert_eq!(pricer.cost(input, 0), U256::from(*expected), "{}", name);
+		}
+
+		// exponent bytes past the first 32 are charged 16 iterations each
+		let mut long_exp = vec![0u8; 33];
+		long_exp[32] = 1;
+		assert_eq!(pricer.cost(&modexp_input(64, &long_exp, 64), 0), U256::from(128 * 16));
+
+		// empty base and modulus are charged the minimum price
+		assert_eq!(pricer.cost(&[0u8; 96], 0), U256::from(500));
+	}
+
+	#[test]
+	fn modexp_eip2565_activation() {
+		let b = Builtin::try_from(ethjson::spec::Builtin {
+			name: "modexp".to_owned(),
+			pricing: ethjson::spec::Pricing::Multi(vec![
+				ethjson::spec::PricingAt {
+					activate_at: Some(U64(0)),
+					activate_at_timestamp: None,
+					price: ethjson::spec::PricingInner::Modexp(ethjson::spec::builtin::Modexp { divisor: 20 }),
+				},
+				ethjson::spec::PricingAt {
+					activate_at: Some(U64(100)),
+					activate_at_timestamp: None,
+					price: ethjson::spec::PricingInner::Modexp2565(ethjson::spec::builtin::Modexp2565 {
+						divisor: 3,
+						min_price: None,
+					}),
+				},
+			]),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		}).expect("known builtin");
+
+		let input = modexp_input(64, &[0x02], 64);
+		assert_eq!(b.cost(&input, 99, 0), U256::from(4096 / 20));
+		assert_eq!(b.cost(&input, 100, 0), U256::from(200));
+	}
+
+	#[test]
+	fn const_cost() {
+		let pricer = pricer_from_json(ethjson::spec::PricingInner::Const { price: 3450 });
+		assert_eq!(pricer.cost(&[], 0), U256::from(3450));
+		assert_eq!(pricer.cost(&[0u8; 160], 0), U256::from(3450));
+		assert_eq!(pricer.cost(&[0u8; 4096], 100), U256::from(3450));
+	}
+
+	#[test]
+	fn modexp_eip2565_defaults() {
+		let pricer = pricer_from_json(ethjson::spec::PricingInner::Modexp2565(ethjson::spec::builtin::Modexp2565 {
+			divisor: 0,
+			min_price: None,
+		}));
+
+		// a zero divisor falls back to 3, and the minimum price to 200
+		assert_eq!(pricer.cost(&modexp_input(64, &[0x02], 64), 0), U256::from(200));
+		assert_eq!(pricer.cost(&modexp_input(256, &[0x02], 256), 0), U256::from(341));
+	}
+
+	#[cfg(feature = "bls12-381")]
+	#[test]
+	fn bls12_381_g1_add() {
+		let f = ethereum_builtin("bls12_381_g1_add").expect("known builtin");
+
+		// adding two points at infinity yields the point at infinity
+		let input = [0u8; 256];
+		let mut output = [0xffu8; 128];
+		f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).expect("Builtin should not fail");
+		assert_eq!(&output[..], &[0u8; 128][..]);
+
+		// invalid input length
+		let mut output = [0u8; 128];
+		assert!(f.execute(&input[..255], &mut BytesRef::Fixed(&mut output[..])).is_err());
+
+		// coordinates larger than the field modulus
+		let input = [0xffu8; 256];
+		assert!(f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).is_err());
+	}
+
+	#[cfg(feature = "bls12-381")]
+	#[test]
+	fn bls12_381_g1_mul() {
+		let f = ethereum_builtin("bls12_381_g1_mul").expect("known builtin");
+
+		// any multiple of the point at infinity is the point at infinity
+		let mut input = [0u8; 160];
+		input[159] = 7;
+		let mut output = [0xffu8; 128];
+		f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).expect("Builtin should not fail");
+		assert_eq!(&output[..], &[0u8; 128][..]);
+
+		let mut output = [0u8; 128];
+		assert!(f.execute(&input[..128], &mut BytesRef::Fixed(&mut output[..])).is_err());
+	}
+
+	#[cfg(feature = "bls12-381")]
+	#[test]
+	fn bls12_381_g1_multiexp() {
+		let f = ethereum_builtin("bls12_381_g1_multiexp").expect("known builtin");
+
+		let input = [0u8; 320];
+		let mut output = [0xffu8; 128];
+		f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).expect("Builtin should not fail");
+		assert_eq!(&output[..], &[0u8; 128][..]);
+
+		// empty input is invalid
+		let mut output = [0u8; 128];
+		assert!(f.execute(&[], &mut BytesRef::Fixed(&mut output[..])).is_err());
+	}
+
+	#[cfg(feature = "bls12-381")]
+	#[test]
+	fn bls12_381_g1_multiexp_metered() {
+		let f = ethereum_builtin("bls12_381_g1_multiexp").expect("known builtin");
+		let pricer = Bls12MultiExpPricer { base: 12000, discount: vec![1000, 949, 848], pair_len: 160 };
+
+		// points at infinity are not charged, but a call pays for at least one pair
+		let input = [0u8; 160 * 3];
+		assert_eq!(pricer.cost(&input, 0), U256::from(30_528));
+		let mut output = Vec::new();
+		assert_eq!(f.execute_metered(&input, &mut BytesRef::Flexible(&mut output), &pricer, 0), Ok(Some(U256::from(12000))));
+		assert_eq!(output, vec![0u8; 128]);
+	}
+
+	#[cfg(feature = "bls12-381")]
+	#[test]
+	fn bls12_381_g2_add() {
+		let f = ethereum_builtin("bls12_381_g2_add").expect("known builtin");
+
+		let input = [0u8; 512];
+		let mut output = [0xffu8; 256];
+		f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).expect("Builtin should not fail");
+		assert_eq!(&output[..], &[0u8; 256][..]);
+
+		let mut output = [0u8; 256];
+		assert!(f.execute(&input[..511], &mut BytesRef::Fixed(&mut output[..])).is_err());
+	}
+
+	#[cfg(feature = "bls12-381")]
+	#[test]
+	fn bls12_381_g2_mul_and_multiexp() {
+		for name in &["bls12_381_g2_mul", "bls12_381_g2_multiexp"] {
+			let f = ethereum_builtin(name).expect("known builtin");
+
+			let mut input = [0u8; 288];
+			input[287] = 3;
+			let mut output = [0xffu8; 256];
+			f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).expect("Builtin should not fail");
+			assert_eq!(&output[..], &[0u8; 256][..], "{}", name);
+
+			let mut output = [0u8; 256];
+			assert!(f.execute(&input[..287], &mut BytesRef::Fixed(&mut output[..])).is_err(), "{}", name);
+		}
+	}
+
+	#[cfg(feature = "bls12-381")]
+	#[test]
+	fn bls12_381_pairing() {
+		let f = ethereum_builtin("bls12_381_pairing").expect("known builtin");
+
+		// the pairing of points at infinity is the identity
+		let input = [0u8; 384];
+		let mut output = [0u8; 32];
+		f.execute(&input[..], &mut BytesRef::Fixed(&mut output[..])).expect("Builtin should not fail");
+		assert_eq!(output, hex!("0000000000000000000000000000000000000000000000000000000000000001"));
+
+		assert!(f.execute(&[], &mut BytesRef::Fixed(&mut output[..])).is_err());
+		assert!(f.execute(&input[..383], &mut BytesRef::Fixed(&mut output[..])).is_err());
+	}
+
+	#[cfg(feature = "bls12-381")]
+	#[test]
+	fn bls12_381_map_to_curve_invalid_input() {
+		let f = ethereum_builtin("bls12_381_fp_to_g1").expect("known builtin");
+		let mut output = [0u8; 128];
+		// field element larger than the modulus
+		assert!(f.execute(&[0xffu8; 64], &mut BytesRef::Fixed(&mut output[..])).is_err());
+		assert!(f.execute(&[0u8; 63], &mut BytesRef::Fixed(&mut output[..])).is_err());
+
+		let f = ethereum_builtin("bls12_381_fp2_to_g2").expect("known builtin");
+		let mut output = [0u8; 256];
+		assert!(f.execute(&[0xffu8; 128], &mut BytesRef::Fixed(&mut output[..])).is_err());
+		assert!(f.execute(&[0u8; 127], &mut BytesRef::Fixed(&mut output[..])).is_err());
+	}
+
+	#[cfg(feature = "bls12-381")]
+	#[test]
+	fn bls12_381_spec_with_multi_pricing() {
+		let s = r#"{
+			"name": "bls12_381_g1_add",
+			"pricing": [
+				{ "activate_at": 100, "price": { "bls12_g1_add": { "price": 600 } } },
+				{ "activate_at": 200, "price": { "bls12_g1_add": { "price": 500 } } }
+			]
+		}"#;
+		let b = Builtin::try_from(serde_json::from_str::<ethjson::spec::Builtin>(s).unwrap()).expect("known builtin");
+
+		assert!(!b.is_active(99, 0));
+		assert_eq!(b.cost(&[0; 256], 100, 0), U256::from(600));
+		assert_eq!(b.cost(&[0; 256], 200, 0), U256::from(500));
+
+		let mut output = [0xffu8; 128];
+		b.execute(&[0u8; 256], &mut BytesRef::Fixed(&mut output[..])).expect("Builtin should not fail");
+		assert_eq!(&output[..], &[0u8; 128][..]);
+	}
+
+	fn multi_pricing_builtin(activations: &[u64]) -> ethjson::spec::Builtin {
+		ethjson::spec::Builtin {
+			name: "identity".to_owned(),
+			pricing: ethjson::spec::Pricing::Multi(activations.iter().map(|at| ethjson::spec::PricingAt {
+				activate_at: Some(U64(*at)),
+				activate_at_timestamp: None,
+				price: ethjson::spec::PricingInner::Linear(ethjson::spec::Linear { base: 10, word: 0 }),
+			}).collect()),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		}
+	}
+
+	#[test]
+	fn multi_pricing_out_of_order() {
+		let err = Builtin::try_from(multi_pricing_builtin(&[0, 20, 10])).err().expect("out of order").to_string();
+		assert!(err.contains("identity"));
+		assert!(err.contains("pricing 2 activated at block 10 is not after the preceding pricing activated at block 20"));
+	}
+
+	#[test]
+	fn multi_pricing_duplicate() {
+		let err = Builtin::try_from(multi_pricing_builtin(&[0, 10, 10])).err().expect("duplicate").to_string();
+		assert!(err.contains("identity"));
+		assert!(err.contains("more than one pricing is activated at block 10"));
+	}
+
+	#[test]
+	fn multi_pricing_empty() {
+		let err = Builtin::try_from(multi_pricing_builtin(&[])).err().expect("empty").to_string();
+		assert!(err.contains("identity"));
+	}
+
+	#[test]
+	fn multi_pricing_increasing() {
+		assert!(Builtin::try_from(multi_pricing_builtin(&[0, 10, 20])).is_ok());
+	}
+
+	#[test]
+	fn multi_pricing_after_activation() {
+		let mut b = multi_pricing_builtin(&[10, 20]);
+		b.activate_at = Some(U64(5));
+		let err = Builtin::try_from(b).err().expect("free before the first pricing").to_string();
+		assert!(err.contains("identity"));
+		assert!(err.contains("no pricing is active at its activation, block 5"));
+
+		let mut b = multi_pricing_builtin(&[10, 20]);
+		b.activate_at = Some(U64(10));
+		assert!(Builtin::try_from(b).is_ok());
+
+		// a pricing activated at the genesis covers any activation
+		let mut b = multi_pricing_builtin(&[0, 20]);
+		b.activate_at_timestamp = Some(U64(1000));
+		assert!(Builtin::try_from(b).is_ok());
+	}
+
+	#[test]
+	fn bls12_pairing_cost() {
+		let pricer = Bls12PairingPricer { base: 115000, pair: 23000 };
+		assert_eq!(pricer.cost(&[0; 384], 0), U256::from(138_000));
+		assert_eq!(pricer.cost(&[0; 384 * 3], 0), U256::from(184_000));
+	}
+
+	#[test]
+	fn max_input_length() {
+		let b = Builtin::try_from(ethjson::spec::Builtin {
+			name: "alt_bn128_pairing".to_owned(),
+			pricing: ethjson::spec::Pricing::Single(ethjson::spec::PricingInner::AltBn128Pairing(ethjson::spec::builtin::AltBn128Pairing {
+				base: 100_000,
+				pair: 80_000,
+				eip1108_transition_base: 45_000,
+				eip1108_transition_pair: 34_000,
+			})),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: Some(192 * 2),
+			fallback_to_code: false,
+			metered: false,
+			info: None,
 		}).expect("known builtin");
 
-		assert_eq!(b.cost(&[0; 192], 10), U256::from(40_000));
-		assert_eq!(b.cost(&[0; 10], 20), U256::from(6_000), "after istanbul hardfork gas cost for mul should be 6 000");
+		// oversized input fails before any pairing work happens
+		let mut output = [0u8; 32];
+		assert_eq!(b.cost(&[0; 192 * 3], 0, 0), U256::max_value());
+		assert!(b.execute(&[0; 192 * 3], &mut BytesRef::Fixed(&mut output[..])).is_err());
+
+		// input within the limit still succeeds
+		assert_eq!(b.cost(&[0; 192 * 2], 0, 0), U256::from(260_000));
+		b.execute(&[0; 192 * 2], &mut BytesRef::Fixed(&mut output[..])).expect("Builtin should not fail");
+		assert_eq!(output, hex!("0000000000000000000000000000000000000000000000000000000000000001"));
+	}
+
+	fn deactivated_builtin(activate_at: u64, deactivate_at: u64, pricing: ethjson::spec::Pricing) -> Result<Builtin, EthcoreError> {
+		Builtin::try_from(ethjson::spec::Builtin {
+			name: "identity".to_owned(),
+			pricing,
+			activate_at: Some(U64(activate_at)),
+			activate_at_timestamp: None,
+			deactivate_at: Some(U64(deactivate_at)),
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		})
+	}
+
+	#[test]
+	fn deactivate_at() {
+		let pricing = ethjson::spec::Pricing::Single(ethjson::spec::PricingInner::Linear(ethjson::spec::Linear {
+			base: 10,
+			word: 20,
+		}));
+		let b = deactivated_builtin(10, 20, pricing).expect("known builtin");
+
+		assert!(!b.is_active(9, 0));
+		assert!(b.is_active(10, 0));
+		assert!(b.is_active(19, 0));
+		assert!(!b.is_active(20, 0));
+		assert!(!b.is_active(21, 0));
+	}
+
+	#[test]
+	fn deactivate_at_not_after_activate_at() {
+		let pricing = ethjson::spec::Pricing::Single(ethjson::spec::PricingInner::Linear(ethjson::spec::Linear {
+			base: 10,
+			word: 20,
+		}));
+		assert!(deactivated_builtin(10, 10, pricing.clone()).is_err());
+		assert!(deactivated_builtin(10, 5, pricing).is_err());
+	}
+
+	#[test]
+	fn deactivate_at_with_later_pricing() {
+		let pricing = ethjson::spec::Pricing::Multi(vec![
+			ethjson::spec::PricingAt {
+				activate_at: Some(U64(10)),
+				activate_at_timestamp: None,
+				price: ethjson::spec::PricingInner::Bls12G1Add { price: 600 },
+			},
+			ethjson::spec::PricingAt {
+				activate_at: Some(U64(30)),
+				activate_at_timestamp: None,
+				price: ethjson::spec::PricingInner::Bls12G1Add { price: 500 },
+			},
+		]);
+		let b = deactivated_builtin(10, 20, pricing).expect("pricings past deactivation are ignored");
+
+		assert!(b.is_active(19, 0));
+		assert!(!b.is_active(30, 0));
+		assert_eq!(b.cost(&[0; 256], 19, 0), U256::from(600));
 	}
 }
@@ -32,6 +32,7 @@ use common_types::{
 	call_analytics::CallAnalytics,
 	chain_notify::{NewBlocks, ChainMessageType},
 	client_types::Mode,
+	database_stats::DatabaseStats,
 	encoded,
 	engines::{epoch::Transition as EpochTransition, machine::Executed},
 	errors::{EthcoreError, EthcoreResult},
@@ -54,6 +55,8 @@ use stats;
 use trace::{
 	FlatTrace,
 	localized::LocalizedTrace,
+	StructLog,
+	StructLoggerConfig,
 	VMTrace,
 };
 use common_types::{
@@ -276,6 +279,12 @@ pub trait BlockChainClient:
 	/// If `after` is set the list starts with the following item.
 	fn list_storage(&self, id: BlockId, account: &Address, after: Option<&H256>, count: Option<u64>) -> Option<Vec<H256>>;
 
+	/// Get a page of at most `count` storage keys in the block `id`, if fat DB is in operation,
+	/// otherwise `None`. The walk resumes right after the storage trie path `resume_at`, and the
+	/// path of the last returned key is handed back when more keys follow. Returns `Missing` if the
+	/// state of the block is not available (anymore).
+	fn list_storage_from_path(&self, id: BlockId, account: &Address, resume_at: Option<&H256>, count: u64) -> Option<StateResult<(Vec<H256>, Option<H256>)>>;
+
 	/// Get transaction with given hash.
 	fn transaction(&self, id: TransactionId) -> Option<LocalizedTransaction>;
 
@@ -315,6 +324,9 @@ pub trait BlockChainClient:
 	/// Replays a given transaction for inspection.
 	fn replay(&self, t: TransactionId, analytics: CallAnalytics) -> Result<Executed<FlatTrace, VMTrace>, CallError>;
 
+	/// Replays a given transaction, logging the state of the VM before every instruction.
+	fn replay_struct_logs(&self, t: TransactionId, config: StructLoggerConfig) -> Result<Executed<FlatTrace, Vec<StructLog>>, CallError>;
+
 	/// Replays all the transactions in a given block for inspection.
 	fn replay_block_transactions(&self, block: BlockId, analytics: CallAnalytics) -> Result<Box<dyn Iterator<Item = (H256, Executed<FlatTrace, VMTrace>)>>, CallError>;
 
@@ -359,6 +371,27 @@ pub trait BlockChainClient:
 		corpus.into()
 	}
 
+	/// Sorted list of priority fees recent transactions would pay on top of `base_fee`.
+	fn priority_fee_corpus(&self, sample_size: usize, base_fee: U256) -> stats::Corpus<U256> {
+		let mut h = self.chain_info().best_block_hash;
+		let mut corpus = Vec::new();
+		for _ in 0..sample_size {
+			let block = match self.block(BlockId::Hash(h)) {
+				Some(block) => block,
+				None => break,
+			};
+
+			if block.number() == 0 {
+				break;
+			}
+			for t in block.transactions() {
+				corpus.push(t.effective_priority_fee(Some(base_fee)))
+			}
+			h = block.parent_hash().clone();
+		}
+		corpus.into()
+	}
+
 	/// Get the preferred chain ID to sign on
 	fn signing_chain_id(&self) -> Option<u64>;
 
@@ -387,6 +420,13 @@ pub trait BlockChainClient:
 	/// Returns information about pruning/data availability.
 	fn pruning_info(&self) -> PruningInfo;
 
+	/// Returns size statistics of the database columns and the status of the manual compaction.
+	fn database_stats(&self) -> DatabaseStats;
+
+	/// Start a manual compaction of the given database column, or of all columns, on a background thread.
+	/// Fails if a compaction is already running.
+	fn compact_database(&self, column: Option<u32>) -> Result<(), String>;
+
 	/// Schedule state-altering transaction to be executed on the next pending block.
 	fn transact_contract(&self, address: Address, data: Bytes) -> Result<(), transaction::Error>;
 }
@@ -43,8 +43,10 @@ pub const COL_NODE_INFO: Option<u32> = Some(6);
 pub const COL_LIGHT_CHAIN: Option<u32> = Some(7);
 /// Column for the private transactions state.
 pub const COL_PRIVATE_TRANSACTIONS_STATE: Option<u32> = Some(8);
+/// Column for the index of blocks by log address and topic.
+pub const COL_LOG_INDEX: Option<u32> = Some(9);
 /// Number of columns in DB
-pub const NUM_COLUMNS: Option<u32> = Some(9);
+pub const NUM_COLUMNS: Option<u32> = Some(10);
 
 /// Modes for updating caches.
 #[derive(Clone, Copy)]
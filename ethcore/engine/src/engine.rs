@@ -346,8 +346,8 @@ pub trait Engine: Sync + Send {
 
 	/// Attempt to get a handle to a built-in contract.
 	/// Only returns references to activated built-ins.
-	fn builtin(&self, a: &Address, block_number: BlockNumber) -> Option<&Builtin> {
-		self.machine().builtin(a, block_number)
+	fn builtin(&self, a: &Address, block_number: BlockNumber, timestamp: u64) -> Option<&Builtin> {
+		self.machine().builtin(a, block_number, timestamp)
 	}
 
 	/// Some intrinsic operation parameters; by default they take their value from the `spec()`'s `engine_params`.
@@ -491,6 +491,8 @@ struct EpochVerifier {
 	empty_steps_transition: u64,
 	/// First block for which a 2/3 quorum (instead of 1/2) is required.
 	two_thirds_majority_transition: BlockNumber,
+	/// First block whose header carries a base fee.
+	eip1559_transition: BlockNumber,
 }
 
 impl engine::EpochVerifier for EpochVerifier {
@@ -507,7 +509,10 @@ impl engine::EpochVerifier for EpochVerifier {
 		let mut finality_checker = RollingFinality::blank(signers, self.two_thirds_majority_transition);
 		let mut finalized = Vec::new();
 
-		let headers: Vec<Header> = Rlp::new(proof).as_list().ok()?;
+		let headers = Rlp::new(proof).iter()
+			.map(|header| Header::decode_rlp(&header, self.eip1559_transition))
+			.collect::<Result<Vec<_>, _>>()
+			.ok()?;
 
 		{
 			let mut push_header = |parent_header: &Header, header: Option<&Header>| {
@@ -1606,6 +1611,7 @@ impl Engine for AuthorityRound {
 					subchain_validators: list,
 					empty_steps_transition: self.empty_steps_transition,
 					two_thirds_majority_transition: self.two_thirds_majority_transition,
+					eip1559_transition: self.machine.params().eip1559_transition,
 				});
 
 				match finalize {
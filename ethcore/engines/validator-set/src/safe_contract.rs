@@ -69,7 +69,7 @@ impl engine::StateDependentProof for StateProof {
 	}
 
 	fn check_proof(&self, machine: &Machine, proof: &[u8]) -> Result<(), String> {
-		let (header, state_items) = decode_first_proof(&Rlp::new(proof))
+		let (header, state_items) = decode_first_proof(&Rlp::new(proof), machine.params().eip1559_transition)
 			.map_err(|e| format!("proof incorrectly encoded: {}", e))?;
 		if &header != &self.header {
 			return Err("wrong header in proof".into());
@@ -121,6 +121,7 @@ fn check_first_proof(machine: &Machine, contract_address: Address, old_header: H
 			Arc::new(last_hashes)
 		},
 		gas_used: 0.into(),
+		base_fee: None,
 	};
 
 	// check state proof using given machine.
@@ -152,8 +153,8 @@ fn check_first_proof(machine: &Machine, contract_address: Address, old_header: H
 	}
 }
 
-fn decode_first_proof(rlp: &Rlp) -> Result<(Header, Vec<DBValue>), EthcoreError> {
-	let header = rlp.val_at(0)?;
+fn decode_first_proof(rlp: &Rlp, eip1559_transition: BlockNumber) -> Result<(Header, Vec<DBValue>), EthcoreError> {
+	let header = Header::decode_rlp(&rlp.at(0)?, eip1559_transition)?;
 	let state_items = rlp.at(1)?.iter().map(|x| {
 		let mut val = DBValue::new();
 		val.append_slice(x.data()?);
@@ -172,8 +173,8 @@ fn encode_proof(header: &Header, receipts: &[Receipt]) -> Bytes {
 	stream.drain()
 }
 
-fn decode_proof(rlp: &Rlp) -> Result<(Header, Vec<Receipt>), EthcoreError> {
-	Ok((rlp.val_at(0)?, rlp.list_at(1)?))
+fn decode_proof(rlp: &Rlp, eip1559_transition: BlockNumber) -> Result<(Header, Vec<Receipt>), EthcoreError> {
+	Ok((Header::decode_rlp(&rlp.at(0)?, eip1559_transition)?, rlp.list_at(1)?))
 }
 
 // given a provider and caller, generate proof. this will just be a state proof
@@ -363,7 +364,7 @@ impl ValidatorSet for ValidatorSafeContract {
 		if first {
 			trace!(target: "engine", "Recovering initial epoch set");
 
-			let (old_header, state_items) = decode_first_proof(&rlp)?;
+			let (old_header, state_items) = decode_first_proof(&rlp, machine.params().eip1559_transition)?;
 			let number = old_header.number();
 			let old_hash = old_header.hash();
 			let addresses = check_first_proof(machine, self.contract_address, old_header, &state_items)
@@ -374,7 +375,7 @@ impl ValidatorSet for ValidatorSafeContract {
 
 			Ok((SimpleList::new(addresses), Some(old_hash)))
 		} else {
-			let (old_header, receipts) = decode_proof(&rlp)?;
+			let (old_header, receipts) = decode_proof(&rlp, machine.params().eip1559_transition)?;
 
 			// ensure receipts match header.
 			// TODO: optimize? these were just decoded.
@@ -554,7 +555,7 @@ mod tests {
 		let sync_client = generate_dummy_client_with_spec_and_data(spec::new_validator_safe_contract, 0, 0, &[]);
 		sync_client.engine().register_client(Arc::downgrade(&sync_client) as _);
 		for i in 1..4 {
-			sync_client.import_block(Unverified::from_rlp(client.block(BlockId::Number(i)).unwrap().into_inner()).unwrap()).unwrap();
+			sync_client.import_block(Unverified::from_rlp(client.block(BlockId::Number(i)).unwrap().into_inner(), sync_client.engine().params().eip1559_transition).unwrap()).unwrap();
 		}
 		sync_client.flush_queue();
 		assert_eq!(sync_client.chain_info().best_block_number, 3);
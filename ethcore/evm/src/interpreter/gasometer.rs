@@ -15,7 +15,7 @@
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::cmp;
-use ethereum_types::{BigEndianHash, U256};
+use ethereum_types::{Address, BigEndianHash, U256};
 use super::u256_to_address;
 
 use {evm, vm};
@@ -141,25 +141,42 @@ impl<Gas: evm::CostType> Gasometer<Gas> {
 						schedule.sstore_reset_gas
 					}
 				};
+				let gas = if schedule.eip2929 && !ext.al_contains_storage_key(&address) {
+					gas + schedule.cold_sload_cost
+				} else {
+					gas
+				};
 				Request::Gas(Gas::from(gas))
 			},
 			instructions::SLOAD => {
-				Request::Gas(Gas::from(schedule.sload_gas))
+				let key = BigEndianHash::from_uint(stack.peek(0));
+				let gas = if schedule.eip2929 && !ext.al_contains_storage_key(&key) {
+					schedule.cold_sload_cost
+				} else {
+					schedule.sload_gas
+				};
+				Request::Gas(Gas::from(gas))
 			},
 			instructions::BALANCE => {
-				Request::Gas(Gas::from(schedule.balance_gas))
+				let address = u256_to_address(stack.peek(0));
+				Request::Gas(Gas::from(account_access_gas(schedule, ext, &address, schedule.balance_gas)))
 			},
 			instructions::EXTCODESIZE => {
-				Request::Gas(Gas::from(schedule.extcodesize_gas))
+				let address = u256_to_address(stack.peek(0));
+				Request::Gas(Gas::from(account_access_gas(schedule, ext, &address, schedule.extcodesize_gas)))
 			},
 			instructions::EXTCODEHASH => {
-				Request::Gas(Gas::from(schedule.extcodehash_gas))
+				let address = u256_to_address(stack.peek(0));
+				Request::Gas(Gas::from(account_access_gas(schedule, ext, &address, schedule.extcodehash_gas)))
 			},
 			instructions::SUICIDE => {
 				let mut gas = Gas::from(schedule.suicide_gas);
 
 				let is_value_transfer = !ext.origin_balance()?.is_zero();
 				let address = u256_to_address(stack.peek(0));
+				if schedule.eip2929 && !ext.al_contains_address(&address) {
+					gas = overflowing!(gas.overflow_add(schedule.cold_account_access_cost.into()));
+				}
 				if (
 					!schedule.no_empty && !ext.exists(&address)?
 				) || (
@@ -188,7 +205,9 @@ impl<Gas: evm::CostType> Gasometer<Gas> {
 				Request::GasMemCopy(default_gas, mem_needed(stack.peek(0), stack.peek(2))?, Gas::from_u256(*stack.peek(2))?)
 			},
 			instructions::EXTCODECOPY => {
-				Request::GasMemCopy(schedule.extcodecopy_base_gas.into(), mem_needed(stack.peek(1), stack.peek(3))?, Gas::from_u256(*stack.peek(3))?)
+				let address = u256_to_address(stack.peek(0));
+				let base = account_access_gas(schedule, ext, &address, schedule.extcodecopy_base_gas);
+				Request::GasMemCopy(base.into(), mem_needed(stack.peek(1), stack.peek(3))?, Gas::from_u256(*stack.peek(3))?)
 			},
 			instructions::LOG0 | instructions::LOG1 | instructions::LOG2 | instructions::LOG3 | instructions::LOG4 => {
 				let no_of_topics = instruction.log_topics().expect("log_topics always return some for LOG* instructions; qed");
@@ -199,13 +218,13 @@ impl<Gas: evm::CostType> Gasometer<Gas> {
 				Request::GasMem(gas, mem_needed(stack.peek(0), stack.peek(1))?)
 			},
 			instructions::CALL | instructions::CALLCODE => {
-				let mut gas = Gas::from(schedule.call_gas);
+				let address = u256_to_address(stack.peek(1));
+				let mut gas = Gas::from(account_access_gas(schedule, ext, &address, schedule.call_gas));
 				let mem = cmp::max(
 					mem_needed(stack.peek(5), stack.peek(6))?,
 					mem_needed(stack.peek(3), stack.peek(4))?
 				);
 
-				let address = u256_to_address(stack.peek(1));
 				let is_value_transfer = !stack.peek(2).is_zero();
 
 				if instruction == instructions::CALL && (
@@ -225,7 +244,8 @@ impl<Gas: evm::CostType> Gasometer<Gas> {
 				Request::GasMemProvide(gas, mem, Some(requested))
 			},
 			instructions::DELEGATECALL | instructions::STATICCALL => {
-				let gas = Gas::from(schedule.call_gas);
+				let address = u256_to_address(stack.peek(1));
+				let gas = Gas::from(account_access_gas(schedule, ext, &address, schedule.call_gas));
 				let mem = cmp::max(
 					mem_needed(stack.peek(4), stack.peek(5))?,
 					mem_needed(stack.peek(2), stack.peek(3))?
@@ -361,6 +381,16 @@ fn add_gas_usize<Gas: evm::CostType>(value: Gas, num: usize) -> (Gas, bool) {
 	value.overflow_add(Gas::from(num))
 }
 
+/// Price of accessing `address`: the first access in a transaction is cold under EIP-2929,
+/// any other is charged `warm_gas`.
+fn account_access_gas(schedule: &Schedule, ext: &dyn vm::Ext, address: &Address, warm_gas: usize) -> usize {
+	if schedule.eip2929 && !ext.al_contains_address(address) {
+		schedule.cold_account_access_cost
+	} else {
+		warm_gas
+	}
+}
+
 #[inline]
 fn to_word_size<Gas: evm::CostType>(value: Gas) -> (Gas, bool) {
 	let (gas, overflow) = add_gas_usize(value, 31);
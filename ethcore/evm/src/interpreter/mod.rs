@@ -364,6 +364,10 @@ impl<Cost: CostType> Interpreter<Cost> {
 				self.gasometer.as_mut().expect(GASOMETER_PROOF).current_mem_gas = requirements.memory_total_gas;
 				self.gasometer.as_mut().expect(GASOMETER_PROOF).current_gas = self.gasometer.as_mut().expect(GASOMETER_PROOF).current_gas - requirements.gas_cost;
 
+				if ext.schedule().eip2929 {
+					Self::mark_accessed(ext, instruction, &self.stack);
+				}
+
 				evm_debug!({ self.informant.before_instruction(self.reader.position, instruction, info, &self.gasometer.as_mut().expect(GASOMETER_PROOF).current_gas, &self.stack) });
 
 				// Execute instruction
@@ -500,6 +504,28 @@ impl<Cost: CostType> Interpreter<Cost> {
 		}
 	}
 
+	/// Records the account or storage slot touched by `instruction` as warm (EIP-2929).
+	/// Must be called after the gasometer has priced the access.
+	fn mark_accessed(
+		ext: &mut dyn vm::Ext,
+		instruction: Instruction,
+		stack: &dyn Stack<U256>
+	) {
+		match instruction {
+			instructions::SLOAD | instructions::SSTORE => {
+				ext.al_insert_storage_key(BigEndianHash::from_uint(stack.peek(0)));
+			},
+			instructions::BALANCE | instructions::EXTCODESIZE | instructions::EXTCODEHASH |
+			instructions::EXTCODECOPY | instructions::SUICIDE => {
+				ext.al_insert_address(u256_to_address(stack.peek(0)));
+			},
+			instructions::CALL | instructions::CALLCODE | instructions::DELEGATECALL | instructions::STATICCALL => {
+				ext.al_insert_address(u256_to_address(stack.peek(1)));
+			},
+			_ => {},
+		}
+	}
+
 	fn exec_instruction(
 		&mut self,
 		gas: Cost,
@@ -150,6 +150,36 @@ fn test_selfbalance(factory: super::Factory) {
 	assert_store(&ext, 0xff, "0000000000000000000000000000000000000000000000000000000000000401");
 }
 
+evm_test!{test_eip2929_cold_and_warm_access: test_eip2929_cold_and_warm_access_int}
+fn test_eip2929_cold_and_warm_access(factory: super::Factory) {
+	// 60 00    PUSH 00
+	// 54       SLOAD (cold)
+	// 50       POP
+	// 60 00    PUSH 00
+	// 54       SLOAD (warm)
+	// 50       POP
+	// 60 01    PUSH 01
+	// 31       BALANCE (cold)
+	// 50       POP
+	// 60 01    PUSH 01
+	// 31       BALANCE (warm)
+	// 50       POP
+	let code = hex!("60 00 54 50 60 00 54 50 60 01 31 50 60 01 31 50").to_vec();
+
+	let mut params = ActionParams::default();
+	params.gas = U256::from(100_000);
+	params.code = Some(Arc::new(code));
+	let mut ext = FakeExt::new_berlin();
+	let gas_left = {
+		let vm = factory.create(params, ext.schedule(), ext.depth());
+		test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap()
+	};
+	// 4 * PUSH1 + 4 * POP + SLOAD 2100 + 100 + BALANCE 2600 + 100
+	assert_eq!(gas_left, U256::from(100_000 - 12 - 8 - 2_200 - 2_700));
+	assert!(ext.accessed_storage_keys.contains(&H256::zero()));
+	assert!(ext.accessed_addresses.contains(&Address::from_low_u64_be(1)));
+}
+
 evm_test!{test_sender: test_sender_int}
 fn test_sender(factory: super::Factory) {
 	let address = Address::from_str("0f572e5295c57f15886f9b263e2f6d2d6c7b5ec6").unwrap();
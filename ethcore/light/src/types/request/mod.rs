@@ -670,7 +670,7 @@ pub trait ResponseLike {
 /// Header request.
 pub mod header {
 	use super::{Field, HashOrNumber, NoSuchOutput, OutputKind, Output};
-	use common_types::encoded;
+	use common_types::{encoded, BlockNumber};
 	use rlp::{Encodable, Decodable, DecoderError, RlpStream, Rlp};
 
 	/// Potentially incomplete headers request.
@@ -758,9 +758,10 @@ pub mod header {
 			let mut headers = Vec::new();
 
 			for item in rlp.iter() {
-				// check that it's a valid encoding.
+				// check that it's a valid encoding. the base fee is taken for a seal field,
+				// which doesn't change the outcome of the check.
 				// TODO: just return full headers here?
-				let _: FullHeader = item.as_val()?;
+				FullHeader::decode_rlp(&item, BlockNumber::max_value())?;
 				headers.push(encoded::Header::new(item.as_raw().to_owned()));
 			}
 
@@ -1022,7 +1023,7 @@ pub mod block_receipts {
 /// Request and response for a block body
 pub mod block_body {
 	use super::{Field, NoSuchOutput, OutputKind, Output};
-	use common_types::encoded;
+	use common_types::{encoded, BlockNumber};
 	use rlp::{Encodable, Decodable, DecoderError, RlpStream, Rlp};
 	use ethereum_types::H256;
 
@@ -1092,9 +1093,12 @@ pub mod block_body {
 			use common_types::header::Header as FullHeader;
 			use common_types::transaction::UnverifiedTransaction;
 
-			// check body validity.
+			// check body validity. the base fee of an uncle is taken for a seal field,
+			// which doesn't change the outcome of the check.
 			let _: Vec<UnverifiedTransaction> = rlp.list_at(0)?;
-			let _: Vec<FullHeader> = rlp.list_at(1)?;
+			for uncle in rlp.at(1)?.iter() {
+				FullHeader::decode_rlp(&uncle, BlockNumber::max_value())?;
+			}
 
 			Ok(Response {
 				body: encoded::Body::new(rlp.as_raw().to_owned()),
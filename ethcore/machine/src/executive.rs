@@ -244,10 +244,10 @@ impl<'a> CallCreateExecutive<'a> {
 		let static_flag = parent_static_flag || params.call_type == CallType::StaticCall;
 
 		// if destination is builtin, try to execute it
-		let kind = if let Some(builtin) = machine.builtin(&params.code_address, info.number) {
+		let kind = if let Some(builtin) = machine.builtin(&params.code_address, info.number, info.timestamp) {
 			// Engines aren't supposed to return builtins until activation, but
 			// prefer to fail rather than silently break consensus.
-			if !builtin.is_active(info.number) {
+			if !builtin.is_active(info.number, info.timestamp) {
 				panic!("Consensus failure: engine implementation prematurely enabled built-in at {}", params.code_address);
 			}
 
@@ -351,6 +351,7 @@ impl<'a> CallCreateExecutive<'a> {
 				| Err(vm::Error::Reverted)
 				| Ok(FinalizationResult { apply_state: false, .. }) => {
 					state.revert_to_checkpoint();
+					un_substate.access_list.revert();
 			},
 			Ok(_) | Err(vm::Error::Internal(_)) => {
 				state.discard_checkpoint();
@@ -403,7 +404,7 @@ impl<'a> CallCreateExecutive<'a> {
 				assert!(!self.is_create);
 
 				let mut inner = || {
-					let builtin = self.machine.builtin(&params.code_address, self.info.number).expect("Builtin is_some is checked when creating this kind in new_call_raw; qed");
+					let builtin = self.machine.builtin(&params.code_address, self.info.number, self.info.timestamp).expect("Builtin is_some is checked when creating this kind in new_call_raw; qed");
 
 					Self::check_static_flag(&params, self.static_flag, self.is_create)?;
 					state.checkpoint();
@@ -413,26 +414,31 @@ impl<'a> CallCreateExecutive<'a> {
 					let data = if let Some(ref d) = params.data { d as &[u8] } else { &default as &[u8] };
 
 					// NOTE(niklasad1): block number is used by `builtin alt_bn128 ops` to enable eip1108
-					let cost = builtin.cost(data, self.info.number);
+					let cost = builtin.cost(data, self.info.number, self.info.timestamp);
 					if cost <= params.gas {
 						let mut builtin_out_buffer = Vec::new();
 						let result = {
 							let mut builtin_output = BytesRef::Flexible(&mut builtin_out_buffer);
-							builtin.execute(data, &mut builtin_output)
+							builtin.execute_metered(data, &mut builtin_output, self.info.number, self.info.timestamp)
 						};
-						if let Err(e) = result {
-							state.revert_to_checkpoint();
-
-							Err(vm::Error::BuiltIn(e))
-						} else {
-							state.discard_checkpoint();
-
-							let out_len = builtin_out_buffer.len();
-							Ok(FinalizationResult {
-								gas_left: params.gas - cost,
-								return_data: ReturnData::new(builtin_out_buffer, 0, out_len),
-								apply_state: true,
-							})
+						match result {
+							Err(e) => {
+								state.revert_to_checkpoint();
+
+								Err(vm::Error::BuiltIn(e))
+							},
+							Ok(gas_used) => {
+								state.discard_checkpoint();
+
+								// the upfront cost caps what the builtin may charge
+								let charged = gas_used.map_or(cost, |gas_used| cmp::min(gas_used, cost));
+								let out_len = builtin_out_buffer.len();
+								Ok(FinalizationResult {
+									gas_left: params.gas - charged,
+									return_data: ReturnData::new(builtin_out_buffer, 0, out_len),
+									apply_state: true,
+								})
+							},
 						}
 					} else {
 						// just drain the whole gas
@@ -442,7 +448,22 @@ impl<'a> CallCreateExecutive<'a> {
 					}
 				};
 
-				Ok(inner())
+				let result = inner();
+
+				// a failing builtin may hand the call over to the code deployed at its address
+				let fallback_to_code = match result {
+					Err(vm::Error::BuiltIn(_)) => params.code.is_some() && self.machine
+						.builtin(&params.code_address, self.info.number, self.info.timestamp)
+						.map_or(false, |builtin| builtin.fallback_to_code()),
+					_ => false,
+				};
+				if fallback_to_code {
+					trace!(target: "executive", "Builtin at {} failed, falling back to code", params.code_address);
+					self.kind = CallCreateExecutiveKind::ExecCall(params.clone(), Substate::new());
+					return self.exec(state, substate, tracer, vm_tracer);
+				}
+
+				Ok(result)
 			},
 			CallCreateExecutiveKind::ExecCall(params, mut unconfirmed_substate) => {
 				assert!(!self.is_create);
@@ -465,6 +486,7 @@ impl<'a> CallCreateExecutive<'a> {
 					}
 				}
 
+				unconfirmed_substate.access_list = substate.access_list.sub_list();
 				let origin_info = OriginInfo::from(&params);
 				let exec = self.factory.create(params, self.schedule, self.depth);
 
@@ -497,6 +519,11 @@ impl<'a> CallCreateExecutive<'a> {
 			CallCreateExecutiveKind::ExecCreate(params, mut unconfirmed_substate) => {
 				assert!(self.is_create);
 
+				// the created address stays warm even if the creation fails
+				if self.schedule.eip2929 {
+					substate.access_list.insert_address(params.address);
+				}
+
 				{
 					let static_flag = self.static_flag;
 					let is_create = self.is_create;
@@ -516,6 +543,7 @@ impl<'a> CallCreateExecutive<'a> {
 					}
 				}
 
+				unconfirmed_substate.access_list = substate.access_list.sub_list();
 				let origin_info = OriginInfo::from(&params);
 				let exec = self.factory.create(params, self.schedule, self.depth);
 
@@ -725,7 +753,7 @@ impl<'a> CallCreateExecutive<'a> {
 					}
 				},
 				Some((_, _, Err(TrapError::Call(subparams, resume)))) => {
-					tracer.prepare_trace_call(&subparams, resume.depth + 1, resume.machine.builtin(&subparams.address, resume.info.number).is_some());
+					tracer.prepare_trace_call(&subparams, resume.depth + 1, resume.machine.builtin(&subparams.address, resume.info.number, resume.info.timestamp).is_some());
 					vm_tracer.prepare_subtrace(subparams.code.as_ref().map_or_else(|| &[] as &[u8], |d| &*d as &[u8]));
 
 					let sub_exec = CallCreateExecutive::new_call_raw(
@@ -871,10 +899,18 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 			});
 		}
 
+		// EIP-1559: the fee cap has to cover the base fee of the block
+		if let Some(base_fee) = self.info.base_fee {
+			if t.max_fee_per_gas() < base_fee {
+				return Err(ExecutionError::GasPriceLowerThanBaseFee { gas_price: t.max_fee_per_gas(), base_fee });
+			}
+		}
+
 		// TODO: we might need bigints here, or at least check overflows.
 		let balance = self.state.balance(&sender)?;
-		let gas_cost = t.gas.full_mul(t.gas_price);
-		let total_cost = U512::from(t.value) + gas_cost;
+		let gas_price = t.effective_gas_price(self.info.base_fee);
+		let gas_cost = t.gas.full_mul(gas_price);
+		let total_cost = U512::from(t.value) + t.gas.full_mul(t.max_fee_per_gas());
 
 		// avoid unaffordable transactions
 		let balance512 = U512::from(balance);
@@ -884,13 +920,34 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 
 		let mut substate = Substate::new();
 
+		// EIP-2929: the sender, the recipient and all active precompiles start out warm
+		if schedule.eip2929 {
+			substate.access_list.insert_address(sender);
+			if let Action::Call(ref address) = t.action {
+				substate.access_list.insert_address(*address);
+			}
+			let (number, timestamp) = (self.info.number, self.info.timestamp);
+			for (address, builtin) in self.machine.builtins() {
+				if builtin.is_active(number, timestamp) {
+					substate.access_list.insert_address(*address);
+				}
+			}
+			// EIP-2930: as well as everything in the transaction's access list
+			for item in t.access_list().into_iter().flatten() {
+				substate.access_list.insert_address(item.address);
+				for key in &item.storage_keys {
+					substate.access_list.insert_storage_key(item.address, *key);
+				}
+			}
+		}
+
 		// NOTE: there can be no invalid transactions from this point.
 		if !schedule.keep_unsigned_nonce || !t.is_unsigned() {
 			self.state.inc_nonce(&sender)?;
 		}
 		self.state.sub_balance(
 			&sender,
-			&U256::try_from(gas_cost).expect("Total cost (value + gas_cost) is lower than max allowed balance (U256); gas_cost has to fit U256; qed"),
+			&U256::try_from(gas_cost).expect("Total cost (value + gas * max_fee_per_gas) is lower than max allowed balance (U256); gas_cost has to fit U256; qed"),
 			&mut cleanup_mode(&mut substate, &schedule)
 		)?;
 
@@ -904,7 +961,7 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 					sender: sender.clone(),
 					origin: sender.clone(),
 					gas: init_gas,
-					gas_price: t.gas_price,
+					gas_price,
 					value: ActionValue::Transfer(t.value),
 					code: Some(Arc::new(t.data.clone())),
 					code_version: schedule.latest_version,
@@ -926,7 +983,7 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 					sender: sender.clone(),
 					origin: sender.clone(),
 					gas: init_gas,
-					gas_price: t.gas_price,
+					gas_price,
 					value: ActionValue::Transfer(t.value),
 					code: self.state.code(address)?,
 					code_hash: self.state.code_hash(address)?,
@@ -960,7 +1017,7 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 		tracer: &mut T,
 		vm_tracer: &mut V
 	) -> vm::Result<FinalizationResult> where T: Tracer, V: VMTracer {
-		tracer.prepare_trace_call(&params, self.depth, self.machine.builtin(&params.address, self.info.number).is_some());
+		tracer.prepare_trace_call(&params, self.depth, self.machine.builtin(&params.address, self.info.number, self.info.timestamp).is_some());
 		vm_tracer.prepare_subtrace(params.code.as_ref().map_or_else(|| &[] as &[u8], |d| &*d as &[u8]));
 
 		let gas = params.gas;
@@ -1154,8 +1211,10 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 		let gas_left = gas_left_prerefund + refunded;
 
 		let gas_used = t.gas.saturating_sub(gas_left);
-		let (refund_value, overflow_1) = gas_left.overflowing_mul(t.gas_price);
-		let (fees_value, overflow_2) = gas_used.overflowing_mul(t.gas_price);
+		let gas_price = t.effective_gas_price(self.info.base_fee);
+		let (refund_value, overflow_1) = gas_left.overflowing_mul(gas_price);
+		// EIP-1559: the base fee is burnt, only the priority fee goes to the author
+		let (fees_value, overflow_2) = gas_used.overflowing_mul(t.effective_priority_fee(self.info.base_fee));
 		if overflow_1 || overflow_2 {
 			return Err(ExecutionError::TransactionMalformed("U256 Overflow".to_string()));
 		}
@@ -1177,7 +1236,7 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 		}
 
 		// perform garbage-collection
-		let min_balance = if schedule.kill_dust != CleanDustMode::Off { Some(U256::from(schedule.tx_gas).overflowing_mul(t.gas_price).0) } else { None };
+		let min_balance = if schedule.kill_dust != CleanDustMode::Off { Some(U256::from(schedule.tx_gas).overflowing_mul(gas_price).0) } else { None };
 		self.state.kill_garbage(&substate.touched, schedule.kill_empty, &min_balance, schedule.kill_dust == CleanDustMode::WithCodeAndStorage)?;
 
 		match result {
@@ -1231,7 +1290,7 @@ mod tests {
 	use account_state::CleanupMode;
 	use common_types::{
 		errors::ExecutionError,
-		transaction::{Action, Transaction},
+		transaction::{Action, EIP1559Tx, Transaction},
 	};
 	use ethkey::{Generator, Random};
 	use evm::{Factory, VMType, evm_test, evm_test_ignore};
@@ -1252,6 +1311,8 @@ mod tests {
 			new_frontier_test_machine,
 			new_byzantium_test_machine,
 			new_constantinople_test_machine,
+			new_istanbul_test_machine,
+			new_berlin_test_machine,
 			new_kovan_wasm_test_machine,
 		},
 	};
@@ -1459,6 +1520,255 @@ mod tests {
 		}]);
 	}
 
+	#[test]
+	fn test_blake2_f_invalid_input_consumes_all_gas() {
+		let sender = Address::from_str("4444444444444444444444444444444444444444").unwrap();
+		let address = Address::from_str("0000000000000000000000000000000000000009").unwrap();
+
+		let mut params = ActionParams::default();
+		params.address = address.clone();
+		params.code_address = address.clone();
+		params.sender = sender.clone();
+		params.origin = sender.clone();
+		params.gas = U256::from(100_000);
+		// one byte short of the 213 bytes expected by EIP-152
+		params.data = Some(vec![0u8; 212]);
+		params.value = ActionValue::Transfer(U256::zero());
+		params.call_type = CallType::Call;
+		let mut state = get_temp_state();
+		let info = EnvInfo::default();
+		let machine = new_istanbul_test_machine();
+		let schedule = machine.schedule(info.number);
+		let mut substate = Substate::new();
+
+		let result = {
+			let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+			ex.call(params, &mut substate, &mut NoopTracer, &mut NoopVMTracer)
+		};
+
+		match result {
+			Err(vm::Error::BuiltIn(msg)) => assert_eq!(msg, "input length for Blake2 F precompile should be exactly 213 bytes"),
+			other => panic!("expected builtin error, got {:?}", other),
+		}
+	}
+
+	#[test]
+	fn test_eip2929_cold_and_warm_access() {
+		// 60 00 54 50 (x2): SLOAD slot 0, cold then warm
+		// 60 00 (x5) 60 04 61 ffff f1 50: CALL the identity precompile, which starts out warm
+		let code = "600054506000545060006000600060006000600461fffff150".from_hex().unwrap();
+		let contract = Address::from_str("1337000000000000000000000000000000000000").unwrap();
+		let keypair = Random.generate().unwrap();
+		let t = Transaction {
+			action: Action::Call(contract),
+			value: U256::zero(),
+			data: vec![],
+			gas: U256::from(100_000),
+			gas_price: U256::zero(),
+			nonce: U256::zero()
+		}.sign(keypair.secret(), None);
+
+		let mut state = get_temp_state();
+		state.init_code(&contract, code).unwrap();
+		let mut info = EnvInfo::default();
+		info.gas_limit = U256::from(100_000);
+		let machine = new_berlin_test_machine();
+		let schedule = machine.schedule(info.number);
+
+		let executed = {
+			let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+			ex.transact(&t, TransactOptions::with_no_tracing()).unwrap()
+		};
+
+		// 21000 + SLOAD 2100 + 100 + CALL 100 + identity 15 + 9 * PUSH + 3 * POP
+		assert_eq!(executed.gas_used, U256::from(21_000 + 2_200 + 115 + 27 + 6));
+	}
+
+	#[test]
+	fn test_eip2929_reverted_call_unwarms_accesses() {
+		// 60 00 (x5) 61 1338 61 ffff f1 50: CALL 0x1338
+		// 61 beef 31 50: BALANCE of 0xbeef, which is cold again after the call reverted
+		let code = "6000600060006000600061133861fffff15061beef3150".from_hex().unwrap();
+		// 61 beef 31 50 60 00 60 00 fd: BALANCE of 0xbeef, then REVERT
+		let sub_code = "61beef315060006000fd".from_hex().unwrap();
+		let contract = Address::from_str("1337000000000000000000000000000000000000").unwrap();
+		let sub_contract = Address::from_low_u64_be(0x1338);
+		let keypair = Random.generate().unwrap();
+		let t = Transaction {
+			action: Action::Call(contract),
+			value: U256::zero(),
+			data: vec![],
+			gas: U256::from(100_000),
+			gas_price: U256::zero(),
+			nonce: U256::zero()
+		}.sign(keypair.secret(), None);
+
+		let mut state = get_temp_state();
+		state.init_code(&contract, code).unwrap();
+		state.init_code(&sub_contract, sub_code).unwrap();
+		let mut info = EnvInfo::default();
+		info.gas_limit = U256::from(100_000);
+		let machine = new_berlin_test_machine();
+		let schedule = machine.schedule(info.number);
+
+		let executed = {
+			let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+			ex.transact(&t, TransactOptions::with_no_tracing()).unwrap()
+		};
+
+		// 21000 + cold CALL 2600 + cold BALANCE 2600 + 3 * PUSH + POP in the sub-call
+		// + cold BALANCE 2600 + 8 * PUSH + 2 * POP in the caller
+		assert_eq!(executed.gas_used, U256::from(21_000 + 2_600 + 2_600 + 6 + 3 + 2 + 2_600 + 24 + 4));
+	}
+
+	fn make_builtin_machine(builtin_address: Address, builtin: ethjson::spec::Builtin) -> Machine {
+		use std::collections::BTreeMap;
+		use common_types::engines::params::CommonParams;
+		use ethcore_builtin::Builtin;
+
+		let spec = ethjson::spec::Spec::load(&include_bytes!("../../res/ethereum/byzantium_test.json")[..]).unwrap();
+		let mut builtins = BTreeMap::new();
+		builtins.insert(builtin_address, Builtin::try_from(builtin).unwrap());
+		Machine::regular(CommonParams::from(spec.params), builtins)
+	}
+
+	fn make_fallback_machine(builtin_address: Address, activate_at: u64) -> Machine {
+		make_builtin_machine(builtin_address, ethjson::spec::Builtin {
+			name: "alt_bn128_pairing".to_owned(),
+			pricing: ethjson::spec::Pricing::Single(ethjson::spec::PricingInner::Linear(ethjson::spec::Linear {
+				base: 100,
+				word: 0,
+			})),
+			activate_at: Some(ethjson::uint::U64(activate_at)),
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: true,
+			metered: false,
+			info: None,
+		})
+	}
+
+	fn call_builtin_with_fallback(activate_at: u64, code: Option<Bytes>, data: Bytes) -> (vm::Result<FinalizationResult>, H256) {
+		let sender = Address::from_str("4444444444444444444444444444444444444444").unwrap();
+		let address = Address::from_str("0000000000000000000000000000000000000008").unwrap();
+
+		let mut params = ActionParams::default();
+		params.address = address.clone();
+		params.code_address = address.clone();
+		params.sender = sender.clone();
+		params.origin = sender.clone();
+		params.gas = U256::from(100_000);
+		params.code = code.map(Arc::new);
+		params.data = Some(data);
+		params.value = ActionValue::Transfer(U256::zero());
+		params.call_type = CallType::Call;
+		let mut state = get_temp_state();
+		let info = EnvInfo::default();
+		let machine = make_fallback_machine(address, activate_at);
+		let schedule = machine.schedule(info.number);
+		let mut substate = Substate::new();
+
+		let result = {
+			let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+			ex.call(params, &mut substate, &mut NoopTracer, &mut NoopVMTracer)
+		};
+		(result, state.storage_at(&address, &H256::zero()).unwrap())
+	}
+
+	#[test]
+	fn test_builtin_fallback_to_code() {
+		// code: stores 1 at key 0
+		//
+		// 60 01 - push 1
+		// 60 00 - push 0
+		// 55 - sstore
+		let code = "6001600055".from_hex().unwrap();
+		let stored = H256::from_low_u64_be(1);
+		// not a multiple of 192 bytes, rejected by the pairing builtin
+		let malformed = vec![0u8; 10];
+
+		// builtin not yet active, the deployed code runs
+		let (result, storage) = call_builtin_with_fallback(100, Some(code.clone()), vec![]);
+		assert!(result.is_ok());
+		assert_eq!(storage, stored);
+
+		// builtin active and succeeding, the deployed code is not run
+		let (result, storage) = call_builtin_with_fallback(0, Some(code.clone()), vec![]);
+		assert_eq!(result.unwrap().gas_left, U256::from(100_000 - 100));
+		assert_eq!(storage, H256::zero());
+
+		// builtin active and failing, the deployed code runs instead
+		let (result, storage) = call_builtin_with_fallback(0, Some(code), malformed.clone());
+		assert!(result.is_ok());
+		assert_eq!(storage, stored);
+
+		// builtin active and failing without code to fall back to
+		let (result, storage) = call_builtin_with_fallback(0, None, malformed);
+		match result {
+			Err(vm::Error::BuiltIn(_)) => {},
+			other => panic!("expected builtin error, got {:?}", other),
+		}
+		assert_eq!(storage, H256::zero());
+	}
+
+	#[test]
+	fn test_metered_builtin_refunds_unused_gas() {
+		let address = Address::from_str("0000000000000000000000000000000000000008").unwrap();
+		let machine = make_builtin_machine(address, ethjson::spec::Builtin::new(
+			"alt_bn128_pairing",
+			ethjson::spec::PricingInner::AltBn128Pairing(ethjson::spec::builtin::AltBn128Pairing {
+				base: 45_000,
+				pair: 34_000,
+				eip1108_transition_base: 45_000,
+				eip1108_transition_pair: 34_000,
+			}),
+		).metered());
+
+		// two pairs of points at infinity: priced at 45 000 + 2 * 34 000 upfront, charged the base only
+		let keypair = Random.generate().unwrap();
+		let t = Transaction {
+			action: Action::Call(address),
+			value: U256::zero(),
+			data: vec![0u8; 384],
+			gas: U256::from(200_000),
+			gas_price: U256::zero(),
+			nonce: U256::zero()
+		}.sign(keypair.secret(), None);
+
+		let mut state = get_temp_state();
+		let mut info = EnvInfo::default();
+		info.gas_limit = U256::from(200_000);
+		let schedule = machine.schedule(info.number);
+
+		let executed = {
+			let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+			ex.transact(&t, TransactOptions::with_tracing()).unwrap()
+		};
+
+		// 21 000 + 384 zero bytes * 4 + 45 000
+		assert_eq!(executed.gas_used, U256::from(67_536));
+		let mut success = vec![0u8; 32];
+		success[31] = 1;
+		assert_eq!(executed.trace, vec![FlatTrace {
+			action: trace::Action::Call(trace::Call {
+				from: t.sender(),
+				to: address,
+				value: 0.into(),
+				gas: 177_464.into(),
+				input: vec![0u8; 384],
+				call_type: CallType::Call,
+			}),
+			result: trace::Res::Call(trace::CallResult {
+				gas_used: 45_000.into(),
+				output: success,
+			}),
+			subtraces: 0,
+			trace_address: Default::default(),
+		}]);
+	}
+
 	#[test]
 	// Tracing is not suported in JIT
 	fn test_call_to_create() {
@@ -2098,6 +2408,74 @@ mod tests {
 		}
 	}
 
+	fn eip1559_transfer(max_fee_per_gas: u64, max_priority_fee_per_gas: u64) -> SignedTransaction {
+		let keypair = Random.generate().unwrap();
+		EIP1559Tx {
+			transaction: Transaction {
+				action: Action::Call(Address::from_low_u64_be(0x1337)),
+				value: U256::zero(),
+				data: vec![],
+				gas: U256::from(100_000),
+				gas_price: U256::from(max_fee_per_gas),
+				nonce: U256::zero(),
+			},
+			chain_id: 1,
+			max_priority_fee_per_gas: U256::from(max_priority_fee_per_gas),
+			access_list: vec![],
+		}.sign(keypair.secret())
+	}
+
+	#[test]
+	fn burns_base_fee_and_pays_priority_fee_to_author() {
+		let t = eip1559_transfer(15, 2);
+		let sender = t.sender();
+		let author = Address::from_low_u64_be(0xa);
+
+		let mut state = get_temp_state();
+		state.add_balance(&sender, &U256::from(1_500_000), CleanupMode::NoEmpty).unwrap();
+		let mut info = EnvInfo::default();
+		info.author = author;
+		info.gas_limit = U256::from(100_000);
+		info.base_fee = Some(U256::from(10));
+		let machine = make_frontier_machine(0);
+		let schedule = machine.schedule(info.number);
+
+		let executed = {
+			let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+			ex.transact(&t, TransactOptions::with_no_tracing()).unwrap()
+		};
+
+		assert_eq!(executed.gas_used, U256::from(21_000));
+		// the sender pays base fee plus priority fee, the author only gets the priority fee
+		assert_eq!(state.balance(&sender).unwrap(), U256::from(1_500_000 - 21_000 * 12));
+		assert_eq!(state.balance(&author).unwrap(), U256::from(21_000 * 2));
+	}
+
+	#[test]
+	fn rejects_max_fee_lower_than_base_fee() {
+		let t = eip1559_transfer(9, 2);
+		let sender = t.sender();
+
+		let mut state = get_temp_state();
+		state.add_balance(&sender, &U256::from(1_500_000), CleanupMode::NoEmpty).unwrap();
+		let mut info = EnvInfo::default();
+		info.gas_limit = U256::from(100_000);
+		info.base_fee = Some(U256::from(10));
+		let machine = make_frontier_machine(0);
+		let schedule = machine.schedule(info.number);
+
+		let res = {
+			let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+			ex.transact(&t, TransactOptions::with_no_tracing())
+		};
+
+		match res {
+			Err(ExecutionError::GasPriceLowerThanBaseFee { gas_price, base_fee })
+				if gas_price == U256::from(9) && base_fee == U256::from(10) => (),
+			_ => assert!(false, "Expected gas price lower than base fee error. {:?}", res)
+		}
+	}
+
 	evm_test!{test_keccak: test_keccak_int}
 	fn test_keccak(factory: Factory) {
 		let code = "6064640fffffffff20600055".from_hex().unwrap();
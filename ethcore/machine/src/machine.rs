@@ -32,7 +32,7 @@ use common_types::{
 		params::CommonParams,
 	},
 	errors::{EngineError, EthcoreError as Error},
-	transaction::{self, SYSTEM_ADDRESS, UNSIGNED_SENDER, UnverifiedTransaction, SignedTransaction},
+	transaction::{self, SYSTEM_ADDRESS, UNSIGNED_SENDER, TypedTxId, UnverifiedTransaction, SignedTransaction},
 };
 use vm::{CallType, ActionParams, ActionValue, ParamsType};
 use vm::{EnvInfo, Schedule};
@@ -217,9 +217,12 @@ impl Machine {
 	/// Populate a header's fields based on its parent's header.
 	/// Usually implements the chain scoring rule based on weight.
 	/// The gas floor target must not be lower than the engine's minimum gas limit.
+	/// The targets bound the gas limit of the block, also from the EIP-1559 transition on,
+	/// where the gas target is the gas limit divided by the elasticity multiplier.
 	pub fn populate_from_parent(&self, header: &mut Header, parent: &Header, gas_floor_target: U256, gas_ceil_target: U256) {
 		header.set_difficulty(parent.difficulty().clone());
-		let gas_limit = parent.gas_limit().clone();
+		header.set_base_fee(self.calc_base_fee(parent));
+		let gas_limit = self.parent_gas_limit(header.number(), parent);
 		assert!(!gas_limit.is_zero(), "Gas limit should be > 0");
 
 		if let Some(ref ethash_params) = self.ethash_extensions {
@@ -269,6 +272,45 @@ impl Machine {
 		&self.params
 	}
 
+	/// The gas limit of `parent` that bounds the gas limit of block `number`. At the
+	/// EIP-1559 transition it is scaled by the elasticity multiplier, so that the gas
+	/// target of the first EIP-1559 block equals the gas limit of its parent.
+	pub fn parent_gas_limit(&self, number: BlockNumber, parent: &Header) -> U256 {
+		if number == self.params().eip1559_transition {
+			parent.gas_limit().saturating_mul(self.params().eip1559_elasticity_multiplier)
+		} else {
+			*parent.gas_limit()
+		}
+	}
+
+	/// Calculates the base fee of the child of `parent` (EIP-1559), `None` before the transition.
+	pub fn calc_base_fee(&self, parent: &Header) -> Option<U256> {
+		let params = self.params();
+		let number = parent.number() + 1;
+		if number < params.eip1559_transition {
+			return None;
+		}
+		let base_fee = match parent.base_fee() {
+			Some(base_fee) if number > params.eip1559_transition => base_fee,
+			_ => return Some(params.eip1559_base_fee_initial_value),
+		};
+
+		let gas_target = *parent.gas_limit() / params.eip1559_elasticity_multiplier;
+		let gas_used = *parent.gas_used();
+		if gas_target.is_zero() || gas_used == gas_target {
+			return Some(base_fee);
+		}
+
+		let denominator = params.eip1559_base_fee_max_change_denominator;
+		Some(if gas_used > gas_target {
+			let delta = base_fee.saturating_mul(gas_used - gas_target) / gas_target / denominator;
+			base_fee.saturating_add(cmp::max(delta, U256::one()))
+		} else {
+			let delta = base_fee.saturating_mul(gas_target - gas_used) / gas_target / denominator;
+			base_fee.saturating_sub(delta)
+		})
+	}
+
 	/// Get the EVM schedule for the given block number.
 	pub fn schedule(&self, block_number: BlockNumber) -> Schedule {
 		let mut schedule = match self.ethash_extensions {
@@ -298,10 +340,10 @@ impl Machine {
 	/// Only returns references to activated built-ins.
 	// TODO: builtin contract routing - to do this properly, it will require removing the built-in configuration-reading logic
 	// from Spec into here and removing the Spec::builtins field.
-	pub fn builtin(&self, a: &Address, block_number: BlockNumber) -> Option<&Builtin> {
+	pub fn builtin(&self, a: &Address, block_number: BlockNumber, timestamp: u64) -> Option<&Builtin> {
 		self.builtins()
 			.get(a)
-			.and_then(|b| if b.is_active(block_number) { Some(b) } else { None })
+			.and_then(|b| if b.is_active(block_number, timestamp) { Some(b) } else { None })
 	}
 
 	/// Some intrinsic operation parameters; by default they take their value from the `spec()`'s `engine_params`.
@@ -345,6 +387,18 @@ impl Machine {
 		};
 		t.verify_basic(check_low_s, chain_id, false)?;
 
+		match t.tx_type() {
+			TypedTxId::AccessList if header.number() < self.params().eip2930_transition =>
+				return Err(transaction::Error::TransactionTypeNotEnabled),
+			TypedTxId::EIP1559Transaction if header.number() < self.params().eip1559_transition =>
+				return Err(transaction::Error::TransactionTypeNotEnabled),
+			_ => {},
+		}
+
+		if t.max_priority_fee_per_gas() > t.max_fee_per_gas() {
+			return Err(transaction::Error::MaxPriorityFeeHigherThanMaxFee);
+		}
+
 		Ok(())
 	}
 
@@ -489,4 +543,34 @@ mod tests {
 		machine.populate_from_parent(&mut header, &parent, U256::from(150_000), U256::from(150_002));
 		assert_eq!(*header.gas_limit(), U256::from(150_002));
 	}
+
+	#[test]
+	fn eip1559_base_fee_follows_parent_gas_used() {
+		let mut params = spec::new_homestead_test().params().clone();
+		params.eip1559_transition = 1;
+		let machine = Machine::regular(params, Default::default());
+
+		let mut parent = Header::new();
+		parent.set_gas_limit(U256::from(20_000_000));
+		assert_eq!(machine.calc_base_fee(&parent), Some(U256::from(1_000_000_000)));
+		assert_eq!(machine.parent_gas_limit(1, &parent), U256::from(40_000_000));
+		assert_eq!(machine.parent_gas_limit(2, &parent), U256::from(20_000_000));
+
+		parent.set_number(1);
+		parent.set_base_fee(Some(U256::from(1_000_000_000)));
+		// at the gas target the base fee does not change
+		parent.set_gas_used(U256::from(10_000_000));
+		assert_eq!(machine.calc_base_fee(&parent), Some(U256::from(1_000_000_000)));
+		// full blocks increase it by 1/8
+		parent.set_gas_used(U256::from(20_000_000));
+		assert_eq!(machine.calc_base_fee(&parent), Some(U256::from(1_125_000_000)));
+		// empty blocks decrease it by 1/8
+		parent.set_gas_used(U256::zero());
+		assert_eq!(machine.calc_base_fee(&parent), Some(U256::from(875_000_000)));
+
+		let mut before = Header::new();
+		before.set_number(0);
+		let machine = Machine::regular(spec::new_homestead_test().params().clone(), Default::default());
+		assert_eq!(machine.calc_base_fee(&before), None);
+	}
 }
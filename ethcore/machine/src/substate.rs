@@ -17,9 +17,88 @@
 //! Execution environment substate.
 
 use std::collections::HashSet;
-use ethereum_types::Address;
+use std::sync::Arc;
+use ethereum_types::{Address, H256};
+use parking_lot::Mutex;
 use common_types::log_entry::LogEntry;
 
+/// Addresses and storage keys accessed during a transaction (EIP-2929).
+///
+/// The accessed entries are shared by the access lists of all call frames of a transaction.
+/// Each frame journals the entries it added itself, so reverting a frame only touches those.
+#[derive(Debug, Default)]
+pub struct AccessList {
+	accessed: Arc<Mutex<Accessed>>,
+	journal: Vec<AccessedEntry>,
+}
+
+#[derive(Debug, Default)]
+struct Accessed {
+	addresses: HashSet<Address>,
+	storage_keys: HashSet<(Address, H256)>,
+}
+
+#[derive(Debug)]
+enum AccessedEntry {
+	Address(Address),
+	StorageKey(Address, H256),
+}
+
+impl AccessList {
+	/// Creates the access list of a sub-call. It sees everything accessed so far, but starts with
+	/// an empty journal.
+	pub fn sub_list(&self) -> Self {
+		AccessList {
+			accessed: self.accessed.clone(),
+			journal: Vec::new(),
+		}
+	}
+
+	/// Checks whether `address` has been accessed.
+	pub fn contains_address(&self, address: &Address) -> bool {
+		self.accessed.lock().addresses.contains(address)
+	}
+
+	/// Marks `address` as accessed.
+	pub fn insert_address(&mut self, address: Address) {
+		if self.accessed.lock().addresses.insert(address) {
+			self.journal.push(AccessedEntry::Address(address));
+		}
+	}
+
+	/// Checks whether storage `key` of `address` has been accessed.
+	pub fn contains_storage_key(&self, address: &Address, key: &H256) -> bool {
+		self.accessed.lock().storage_keys.contains(&(*address, *key))
+	}
+
+	/// Marks storage `key` of `address` as accessed.
+	pub fn insert_storage_key(&mut self, address: Address, key: H256) {
+		if self.accessed.lock().storage_keys.insert((address, key)) {
+			self.journal.push(AccessedEntry::StorageKey(address, key));
+		}
+	}
+
+	/// Keeps the entries added by the sub-call access list `sub`, as if they were added by this frame.
+	pub fn accrue(&mut self, sub: AccessList) {
+		if self.journal.is_empty() {
+			self.journal = sub.journal;
+		} else {
+			self.journal.extend(sub.journal);
+		}
+	}
+
+	/// Removes the entries added by this frame (and by the sub-calls it accrued).
+	pub fn revert(self) {
+		let mut accessed = self.accessed.lock();
+		for entry in self.journal {
+			match entry {
+				AccessedEntry::Address(address) => { accessed.addresses.remove(&address); },
+				AccessedEntry::StorageKey(address, key) => { accessed.storage_keys.remove(&(address, key)); },
+			}
+		}
+	}
+}
+
 /// State changes which should be applied in finalize,
 /// after transaction is fully executed.
 #[derive(Debug, Default)]
@@ -38,6 +117,9 @@ pub struct Substate {
 
 	/// Created contracts.
 	pub contracts_created: Vec<Address>,
+
+	/// Addresses and storage keys accessed during the transaction (EIP-2929).
+	pub access_list: AccessList,
 }
 
 impl Substate {
@@ -53,12 +135,13 @@ impl Substate {
 		self.logs.extend(s.logs);
 		self.sstore_clears_refund += s.sstore_clears_refund;
 		self.contracts_created.extend(s.contracts_created);
+		self.access_list.accrue(s.access_list);
 	}
 }
 
 #[cfg(test)]
 mod tests {
-	use ethereum_types::Address;
+	use ethereum_types::{Address, H256};
 	use common_types::log_entry::LogEntry;
 	use super::Substate;
 
@@ -94,4 +177,47 @@ mod tests {
 		assert_eq!(sub_state.sstore_clears_refund, (15000 * 12).into());
 		assert_eq!(sub_state.suicides.len(), 1);
 	}
+
+	#[test]
+	fn access_list_revert() {
+		let (a, b) = (Address::from_low_u64_be(1), Address::from_low_u64_be(2));
+		let mut parent = Substate::new();
+		parent.access_list.insert_address(a);
+		parent.access_list.insert_storage_key(a, H256::zero());
+
+		let mut child = Substate::new();
+		child.access_list = parent.access_list.sub_list();
+		assert!(child.access_list.contains_address(&a));
+		child.access_list.insert_address(a);
+		child.access_list.insert_address(b);
+		child.access_list.insert_storage_key(b, H256::zero());
+		assert!(parent.access_list.contains_address(&b));
+
+		child.access_list.revert();
+		assert!(parent.access_list.contains_address(&a));
+		assert!(parent.access_list.contains_storage_key(&a, &H256::zero()));
+		assert!(!parent.access_list.contains_address(&b));
+		assert!(!parent.access_list.contains_storage_key(&b, &H256::zero()));
+	}
+
+	#[test]
+	fn access_list_accrue() {
+		let (a, b) = (Address::from_low_u64_be(1), Address::from_low_u64_be(2));
+		let mut grandparent = Substate::new();
+		let mut parent = Substate::new();
+		parent.access_list = grandparent.access_list.sub_list();
+		parent.access_list.insert_address(a);
+
+		let mut child = Substate::new();
+		child.access_list = parent.access_list.sub_list();
+		child.access_list.insert_address(b);
+		parent.accrue(child);
+
+		// entries kept by an accrued sub-call are undone together with the frame that accrued them
+		parent.access_list.revert();
+		assert!(!grandparent.access_list.contains_address(&a));
+		assert!(!grandparent.access_list.contains_address(&b));
+		grandparent.access_list.insert_address(b);
+		assert!(grandparent.access_list.contains_address(&b));
+	}
 }
@@ -57,6 +57,9 @@ pub fn new_constantinople_fix_test_machine() -> Machine { load_machine(include_b
 /// Create a new Foundation Istanbul era spec.
 pub fn new_istanbul_test_machine() -> Machine { load_machine(include_bytes!("../../res/ethereum/istanbul_test.json")) }
 
+/// Create a new Foundation Berlin era spec.
+pub fn new_berlin_test_machine() -> Machine { load_machine(include_bytes!("../../res/ethereum/berlin_test.json")) }
+
 /// Create a new Musicoin-MCIP3-era spec.
 pub fn new_mcip3_test_machine() -> Machine { load_machine(include_bytes!("../../res/ethereum/mcip3_test.json")) }
 
@@ -77,6 +77,13 @@ impl pool::ScoredTransaction for VerifiedPrivateTransaction {
 		&self.transaction.gas_price
 	}
 
+	fn effective_priority_fee(&self, block_base_fee: Option<U256>) -> U256 {
+		match block_base_fee {
+			Some(_) => self.transaction.effective_priority_fee(block_base_fee),
+			None => self.transaction.max_priority_fee_per_gas(),
+		}
+	}
+
 	/// Gets transaction nonce.
 	fn nonce(&self) -> U256 {
 		self.transaction.nonce
@@ -133,7 +140,7 @@ impl Default for VerificationStore {
 			verification_pool: RwLock::new(
 				txpool::Pool::new(
 					txpool::NoopListener,
-					pool::scoring::NonceAndGasPrice(pool::PrioritizationStrategy::GasPriceOnly),
+					pool::scoring::NonceAndGasPrice::new(pool::PrioritizationStrategy::GasPriceOnly),
 					pool::Options {
 						max_count: MAX_QUEUE_LEN,
 						max_per_sender: MAX_QUEUE_LEN / 10,
@@ -147,6 +154,7 @@ impl Default for VerificationStore {
 				block_gas_limit: 8_000_000.into(),
 				tx_gas_limit: U256::max_value(),
 				no_early_reject: false,
+				max_nonce_gap: U256::max_value(),
 			},
 		}
 	}
@@ -141,6 +141,9 @@ impl ClientService {
 			client: client.clone(),
 		};
 		let snapshot = Arc::new(SnapshotService::new(snapshot_params)?);
+		if let Some(manifest) = snapshot.manifest() {
+			client.note_snapshot_block(manifest.block_number);
+		}
 
 		let private_keys = Arc::new(ethcore_private_tx::SecretStoreKeys::new(
 			client.clone(),
@@ -20,11 +20,14 @@ use snapshot::test_helpers::AbridgedBlock;
 
 use bytes::Bytes;
 use ethereum_types::{H256, U256, Address};
+use keccak_hash::keccak;
 use common_types::{
 	transaction::{Action, Transaction},
 	block::Block,
+	header::Header,
 	view,
 	views::BlockView,
+	BlockNumber,
 };
 
 fn encode_block(b: &Block) -> Bytes {
@@ -38,7 +41,7 @@ fn empty_block_abridging() {
 	let encoded = encode_block(&b);
 
 	let abridged = AbridgedBlock::from_block_view(&view!(BlockView, &encoded));
-	assert_eq!(abridged.to_block(H256::zero(), 0, receipts_root).unwrap(), b);
+	assert_eq!(abridged.to_block(H256::zero(), 0, receipts_root, BlockNumber::max_value()).unwrap(), b);
 }
 
 #[test]
@@ -49,7 +52,7 @@ fn wrong_number() {
 	let encoded = encode_block(&b);
 
 	let abridged = AbridgedBlock::from_block_view(&view!(BlockView, &encoded));
-	assert_eq!(abridged.to_block(H256::zero(), 2, receipts_root).unwrap(), b);
+	assert_eq!(abridged.to_block(H256::zero(), 2, receipts_root, BlockNumber::max_value()).unwrap(), b);
 }
 
 #[test]
@@ -85,5 +88,23 @@ fn with_transactions() {
 	let encoded = encode_block(&b);
 
 	let abridged = AbridgedBlock::from_block_view(&view!(BlockView, &encoded[..]));
-	assert_eq!(abridged.to_block(H256::zero(), 0, receipts_root).unwrap(), b);
+	assert_eq!(abridged.to_block(H256::zero(), 0, receipts_root, BlockNumber::max_value()).unwrap(), b);
+}
+
+#[test]
+fn with_base_fee() {
+	let mut b = Block::default();
+	let mut uncle = Header::new();
+	uncle.set_number(4);
+	uncle.set_base_fee(Some(U256::from(875_000_000)));
+	b.uncles.push(uncle);
+	b.header.set_uncles_hash(keccak(::rlp::encode_list::<Header, _>(&b.uncles)));
+	b.header.set_number(5);
+	b.header.set_base_fee(Some(U256::from(1_000_000_000)));
+
+	let receipts_root = b.header.receipts_root().clone();
+	let encoded = encode_block(&b);
+
+	let abridged = AbridgedBlock::from_block_view(&view!(BlockView, &encoded));
+	assert_eq!(abridged.to_block(H256::zero(), 5, receipts_root, 4).unwrap(), b);
 }
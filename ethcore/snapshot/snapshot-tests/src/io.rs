@@ -19,7 +19,7 @@
 use tempdir::TempDir;
 use keccak_hash::keccak;
 
-use common_types::snapshot::ManifestData;
+use common_types::snapshot::{Compression, ManifestData};
 use snapshot::io::{
 	SnapshotWriter,SnapshotReader,
 	PackedWriter, PackedReader, LooseWriter, LooseReader,
@@ -57,6 +57,9 @@ fn packed_write_and_read() {
 		state_root: keccak(b"notarealroot"),
 		block_number: 12345678987654321,
 		block_hash: keccak(b"notarealblock"),
+		compression: Compression::Snappy,
+		state_checksums: Vec::new(),
+		block_checksums: Vec::new(),
 	};
 
 	writer.finish(manifest.clone()).unwrap();
@@ -96,6 +99,9 @@ fn loose_write_and_read() {
 		state_root: keccak(b"notarealroot"),
 		block_number: 12345678987654321,
 		block_hash: keccak(b"notarealblock)"),
+		compression: Compression::Snappy,
+		state_checksums: Vec::new(),
+		block_checksums: Vec::new(),
 	};
 
 	writer.finish(manifest.clone()).unwrap();
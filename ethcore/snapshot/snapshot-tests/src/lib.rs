@@ -31,7 +31,7 @@ mod helpers;
 
 #[test]
 fn manifest_rlp() {
-    use common_types::snapshot::ManifestData;
+    use common_types::snapshot::{Compression, ManifestData};
     let manifest = ManifestData {
         version: 2,
         block_hashes: Vec::new(),
@@ -39,7 +39,34 @@ fn manifest_rlp() {
         block_number: 1234567,
         state_root: Default::default(),
         block_hash: Default::default(),
+        compression: Compression::Snappy,
+        state_checksums: Vec::new(),
+        block_checksums: Vec::new(),
     };
     let raw = manifest.clone().into_rlp();
     assert_eq!(ManifestData::from_rlp(&raw).unwrap(), manifest);
 }
+
+#[test]
+fn manifest_rlp_with_checksums() {
+    use common_types::snapshot::{ChunkChecksum, Compression, ManifestData};
+    let checksum = ChunkChecksum { len: 1024, checksum: ethereum_types::H256::repeat_byte(1) };
+    let manifest = ManifestData {
+        version: 3,
+        block_hashes: vec![ethereum_types::H256::repeat_byte(2)],
+        state_hashes: vec![ethereum_types::H256::repeat_byte(3)],
+        block_number: 1234567,
+        state_root: Default::default(),
+        block_hash: Default::default(),
+        compression: Compression::Zstd,
+        state_checksums: vec![checksum],
+        block_checksums: vec![checksum],
+    };
+    let raw = manifest.clone().into_rlp();
+    assert_eq!(ManifestData::from_rlp(&raw).unwrap(), manifest);
+
+    let mut missing_checksum = manifest;
+    missing_checksum.block_checksums.clear();
+    let raw = missing_checksum.into_rlp();
+    assert!(ManifestData::from_rlp(&raw).is_err());
+}
@@ -21,7 +21,7 @@ use tempdir::TempDir;
 use common_types::{
 	errors::{EthcoreError as Error, SnapshotError},
 	engines::ForkChoice,
-	snapshot::{Progress, ManifestData},
+	snapshot::{Compression, Progress, ManifestData},
 };
 use blockchain::generator::{BlockGenerator, BlockBuilder};
 use blockchain::{BlockChain, ExtrasInsert};
@@ -84,6 +84,9 @@ fn chunk_and_restore(amount: u64) {
 		state_root: KECCAK_NULL_RLP,
 		block_number: amount,
 		block_hash: best_hash,
+		compression: Compression::Snappy,
+		state_checksums: Vec::new(),
+		block_checksums: Vec::new(),
 	};
 
 	writer.into_inner().finish(manifest.clone()).unwrap();
@@ -146,6 +149,9 @@ fn checks_flag() {
 		state_root: KECCAK_NULL_RLP,
 		block_number: 102,
 		block_hash: H256::zero(),
+		compression: Compression::Snappy,
+		state_checksums: Vec::new(),
+		block_checksums: Vec::new(),
 	};
 
 	let mut rebuilder = SNAPSHOT_MODE.rebuilder(chain, db.clone(), &manifest).unwrap();
@@ -16,26 +16,30 @@
 
 //! Tests for the snapshot service.
 
-use std::fs;
+use std::{fs, io};
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 
 use tempdir::TempDir;
 use blockchain::BlockProvider;
 use ethcore::client::{Client, ClientConfig};
-use client_traits::{BlockInfo, ImportBlock};
+use client_traits::{BlockInfo, ChainInfo, ImportBlock};
 use common_types::{
+	errors::{EthcoreError, SnapshotError},
 	io_message::ClientIoMessage,
 	ids::BlockId,
 	snapshot::Progress,
 	verification::Unverified,
-	snapshot::{ManifestData, RestorationStatus},
+	snapshot::{Compression, ManifestData, RestorationStatus},
 };
 use snapshot::{
-	chunk_state, chunk_secondary, SnapshotService,
-	io::{PackedReader, PackedWriter, SnapshotReader, SnapshotWriter},
+	chunk_state, chunk_secondary, Checkpoint, SnapshotClient, SnapshotService, SNAPSHOT_SUBPARTS,
+	io::{LooseReader, LooseWriter, PackedReader, PackedWriter, SnapshotReader, SnapshotWriter},
 	service::{Service, ServiceParams, Guard, Restoration, RestorationParams},
 	PowSnapshot,
 };
+use ethereum_types::H256;
+use keccak_hash::keccak;
 use spec;
 use ethcore::{
 	miner,
@@ -80,6 +84,9 @@ fn sends_async_messages() {
 		state_root: Default::default(),
 		block_number: 0,
 		block_hash: Default::default(),
+		compression: Compression::Snappy,
+		state_checksums: Vec::new(),
+		block_checksums: Vec::new(),
 	};
 
 	service.begin_restore(manifest);
@@ -111,6 +118,9 @@ fn cannot_finish_with_invalid_chunks() {
 			state_root: H256::zero(),
 			block_number: 100000,
 			block_hash: H256::zero(),
+			compression: Compression::Snappy,
+			state_checksums: Vec::new(),
+			block_checksums: Vec::new(),
 		},
 		Algorithm::Archive,
 		restoration_db_handler(db_config).open(&tempdir.path().to_owned()).unwrap(),
@@ -230,6 +240,9 @@ fn guards_delete_folders() {
 		block_hashes: vec![],
 		block_number: 0,
 		block_hash: Default::default(),
+		compression: Compression::Snappy,
+		state_checksums: Vec::new(),
+		block_checksums: Vec::new(),
 		state_root: Default::default(),
 	};
 
@@ -299,6 +312,9 @@ fn keep_ancient_blocks() {
 		block_hashes,
 		block_number: NUM_BLOCKS,
 		block_hash: best_hash,
+		compression: Compression::Snappy,
+		state_checksums: Vec::new(),
+		block_checksums: Vec::new(),
 	};
 
 	writer.into_inner().finish(manifest.clone()).unwrap();
@@ -318,7 +334,7 @@ fn keep_ancient_blocks() {
 	for block_number in 1..50 {
 		let block_hash = bc.block_hash(block_number).unwrap();
 		let block = bc.block(&block_hash).unwrap();
-		client2.import_block(Unverified::from_rlp(block.into_inner()).unwrap()).unwrap();
+		client2.import_block(Unverified::from_rlp(block.into_inner(), spec.params().eip1559_transition).unwrap()).unwrap();
 	}
 
 	client2.flush_queue();
@@ -446,3 +462,122 @@ fn recover_aborted_recovery() {
 		_ => panic!("Snapshot restoration must be ongoing"),
 	}
 }
+
+/// Writer failing to write block chunks, interrupting the snapshot once the state is chunked.
+struct InterruptingWriter(LooseWriter);
+
+impl SnapshotWriter for InterruptingWriter {
+	fn write_state_chunk(&mut self, hash: H256, chunk: &[u8]) -> io::Result<()> {
+		self.0.write_state_chunk(hash, chunk)
+	}
+
+	fn write_block_chunk(&mut self, _hash: H256, _chunk: &[u8]) -> io::Result<()> {
+		Err(io::Error::new(io::ErrorKind::Other, "interrupted"))
+	}
+
+	fn finish(self, manifest: ManifestData) -> io::Result<()> {
+		self.0.finish(manifest)
+	}
+
+	fn resume(&mut self, block_hash: H256, compression: Compression) -> io::Result<Checkpoint> {
+		self.0.resume(block_hash, compression)
+	}
+
+	fn checkpoint(&mut self, checkpoint: &Checkpoint) -> io::Result<()> {
+		self.0.checkpoint(checkpoint)
+	}
+}
+
+#[test]
+fn resumes_interrupted_snapshot() {
+	let gas_prices = vec![1.into(), 2.into(), 3.into(), 999.into()];
+	let client = generate_dummy_client_with_spec_and_data(spec::new_null, 400, 5, &gas_prices);
+	let hash = client.chain_info().best_block_hash;
+	let tempdir = TempDir::new("").unwrap();
+
+	let uninterrupted_dir = tempdir.path().join("uninterrupted");
+	let writer = LooseWriter::new(uninterrupted_dir.clone()).unwrap();
+	client.take_snapshot(writer, BlockId::Hash(hash), &Progress::default()).unwrap();
+	let uninterrupted = LooseReader::new(uninterrupted_dir).unwrap().manifest().clone();
+
+	// interrupt the snapshot after all state parts are checkpointed
+	let dir = tempdir.path().join("resumed");
+	let writer = InterruptingWriter(LooseWriter::new(dir.clone()).unwrap());
+	assert!(client.take_snapshot(writer, BlockId::Hash(hash), &Progress::default()).is_err());
+	let checkpoint = LooseWriter::new(dir.clone()).unwrap().resume(hash, Compression::Snappy).unwrap();
+	assert_eq!(checkpoint.state_parts.len(), SNAPSHOT_SUBPARTS);
+	assert_eq!(checkpoint.blocks, None);
+
+	// resume it
+	let progress = Progress::default();
+	let writer = LooseWriter::new(dir.clone()).unwrap();
+	client.take_snapshot(writer, BlockId::Hash(hash), &progress).unwrap();
+	let resumed = LooseReader::new(dir.clone()).unwrap().manifest().clone();
+
+	assert_eq!(keccak(resumed.clone().into_rlp()), keccak(uninterrupted.into_rlp()));
+	assert_eq!(progress.state_parts(), SNAPSHOT_SUBPARTS);
+	assert!(!dir.join("CHECKPOINT").exists());
+	for hash in resumed.state_hashes.iter().chain(&resumed.block_hashes) {
+		assert!(dir.join(format!("{:x}", hash)).exists());
+	}
+}
+
+#[test]
+fn verifies_chunk_checksums_on_restoration() {
+	let gas_prices = vec![1.into(), 2.into(), 3.into(), 999.into()];
+	let client = generate_dummy_client_with_spec_and_data(spec::new_null, 400, 5, &gas_prices);
+	let spec = spec::new_null();
+	let tempdir = TempDir::new("").unwrap();
+
+	// take a zstd compressed snapshot
+	let dir = tempdir.path().join("snapshot");
+	let bc = client.chain();
+	let best_hash = bc.best_block_hash();
+	let state_db = client.state_db().journal_db().boxed_clone();
+	snapshot::take_snapshot(
+		snapshot::chunker(spec.engine.snapshot_mode()).unwrap(),
+		&bc,
+		best_hash,
+		state_db.as_hash_db(),
+		LooseWriter::new(dir.clone()).unwrap(),
+		&Progress::default(),
+		1,
+		None,
+		Compression::Zstd,
+	).unwrap();
+
+	let reader = LooseReader::new(dir).unwrap();
+	let manifest = reader.manifest().clone();
+	assert_eq!(manifest.version, 3);
+	assert_eq!(manifest.compression, Compression::Zstd);
+	assert_eq!(manifest.state_checksums.len(), manifest.state_hashes.len());
+	assert_eq!(manifest.block_checksums.len(), manifest.block_hashes.len());
+
+	let flag = AtomicBool::new(true);
+	let restoration = |manifest: ManifestData, name: &str| {
+		let db_config = DatabaseConfig::with_columns(ethcore_db::NUM_COLUMNS);
+		let db = restoration_db_handler(db_config).open(&tempdir.path().join(name)).unwrap();
+		let gb = spec.genesis_block();
+		Restoration::new(RestorationParams::new(manifest, Algorithm::Archive, db, None, &gb, Guard::benign(), &*spec.engine)).unwrap()
+	};
+
+	// a chunk not matching its checksum aborts the restoration
+	let mut tampered = manifest.clone();
+	tampered.state_checksums[0].checksum = H256::zero();
+	let bad_hash = tampered.state_hashes[0];
+	let mut bad_restoration = restoration(tampered, "bad");
+	match bad_restoration.feed_state(bad_hash, &reader.chunk(bad_hash).unwrap(), &flag) {
+		Err(EthcoreError::Snapshot(SnapshotError::ChunkChecksumMismatch(hash))) => assert_eq!(hash, bad_hash),
+		res => panic!("expected checksum mismatch, got {:?}", res),
+	}
+
+	// the untampered snapshot restores
+	let mut good_restoration = restoration(manifest.clone(), "good");
+	for hash in &manifest.state_hashes {
+		good_restoration.feed_state(*hash, &reader.chunk(*hash).unwrap(), &flag).unwrap();
+	}
+	for hash in &manifest.block_hashes {
+		good_restoration.feed_blocks(*hash, &reader.chunk(*hash).unwrap(), &*spec.engine, &flag).unwrap();
+	}
+	assert!(good_restoration.is_done());
+}
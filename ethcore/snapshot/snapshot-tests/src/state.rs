@@ -23,7 +23,7 @@ use keccak_hash::{KECCAK_NULL_RLP, keccak};
 use common_types::{
 	basic_account::BasicAccount,
 	errors::{EthcoreError as Error, SnapshotError},
-	snapshot::{ManifestData, Progress},
+	snapshot::{Compression, ManifestData, Progress},
 };
 use snapshot::{
 	test_helpers::to_fat_rlps,
@@ -73,6 +73,9 @@ fn snap_and_restore() {
 		state_root,
 		block_number: 1000,
 		block_hash: H256::zero(),
+		compression: Compression::Snappy,
+		state_checksums: Vec::new(),
+		block_checksums: Vec::new(),
 	}).unwrap();
 
 	let db_path = tempdir.path().join("db");
@@ -187,6 +190,9 @@ fn checks_flag() {
 		state_root,
 		block_number: 0,
 		block_hash: H256::zero(),
+		compression: Compression::Snappy,
+		state_checksums: Vec::new(),
+		block_checksums: Vec::new(),
 	}).unwrap();
 
 	let tempdir = TempDir::new("").unwrap();
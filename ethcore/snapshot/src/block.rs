@@ -21,6 +21,7 @@ use common_types::{
 	block::Block,
 	header::Header,
 	views::BlockView,
+	BlockNumber,
 };
 use ethereum_types::H256;
 use keccak_hash::keccak;
@@ -75,7 +76,7 @@ impl AbridgedBlock {
 		// write block values.
 		stream
 			.append_list(&block_view.transactions())
-			.append_list(&block_view.uncles());
+			.append_raw(block_view.uncles_rlp().as_raw(), 1);
 
 		// write seal fields.
 		for field in seal_fields {
@@ -88,9 +89,10 @@ impl AbridgedBlock {
 	}
 
 	/// Flesh out an abridged block view with the provided parent hash and block number.
+	/// See `Header::decode_rlp` for the meaning of `eip1559_transition`.
 	///
 	/// Will fail if contains invalid rlp.
-	pub fn to_block(&self, parent_hash: H256, number: u64, receipts_root: H256) -> Result<Block, DecoderError> {
+	pub fn to_block(&self, parent_hash: H256, number: u64, receipts_root: H256, eip1559_transition: BlockNumber) -> Result<Block, DecoderError> {
 		let rlp = Rlp::new(&self.rlp);
 
 		let mut header: Header = Default::default();
@@ -106,7 +108,9 @@ impl AbridgedBlock {
 		header.set_extra_data(rlp.val_at(7)?);
 
 		let transactions = rlp.list_at(8)?;
-		let uncles: Vec<Header> = rlp.list_at(9)?;
+		let uncles = rlp.at(9)?.iter()
+			.map(|uncle| Header::decode_rlp(&uncle, eip1559_transition))
+			.collect::<Result<Vec<_>, _>>()?;
 
 		header.set_transactions_root(ordered_trie_root(
 			rlp.at(8)?.iter().map(|r| r.as_raw())
@@ -117,8 +121,18 @@ impl AbridgedBlock {
 		uncles_rlp.append_list(&uncles);
 		header.set_uncles_hash(keccak(uncles_rlp.as_raw()));
 
+		// the base fee, if any, was written out as the last seal field
+		let mut item_count = rlp.item_count()?;
+		if number >= eip1559_transition {
+			if item_count <= HEADER_FIELDS + BLOCK_FIELDS {
+				return Err(DecoderError::RlpIncorrectListLen);
+			}
+			item_count -= 1;
+			header.set_base_fee(Some(rlp.val_at(item_count)?));
+		}
+
 		let mut seal_fields = Vec::new();
-		for i in (HEADER_FIELDS + BLOCK_FIELDS)..rlp.item_count()? {
+		for i in (HEADER_FIELDS + BLOCK_FIELDS)..item_count {
 			let seal_rlp = rlp.at(i)?;
 			seal_fields.push(seal_rlp.as_raw().to_owned());
 		}
@@ -106,7 +106,7 @@ impl SnapshotComponents for PoaSnapshot {
 		let (block, receipts) = chain.block(&block_at)
 			.and_then(|b| chain.block_receipts(&block_at).map(|r| (b, r)))
 			.ok_or_else(||SnapshotError::BlockNotFound(block_at))?;
-		let block = block.decode()?;
+		let block = block.decode(chain.eip1559_transition())?;
 
 		let parent_td = chain.block_details(block.header.parent_hash())
 			.map(|d| d.total_difficulty)
@@ -194,7 +194,7 @@ impl ChunkRebuilder {
 		use engine::ConstructedVerifier;
 
 		// decode.
-		let header: Header = transition_rlp.val_at(0)?;
+		let header = Header::decode_rlp(&transition_rlp.at(0)?, engine.params().eip1559_transition)?;
 		let epoch_data: Bytes = transition_rlp.val_at(1)?;
 
 		trace!(target: "snapshot", "verifying transition to epoch at block {}", header.number());
@@ -325,10 +325,13 @@ impl Rebuilder for ChunkRebuilder {
 			use common_types::block::Block;
 
 			let last_rlp = rlp.at(num_items - 1)?;
+			let eip1559_transition = engine.params().eip1559_transition;
 			let block = Block {
-				header: last_rlp.val_at(0)?,
+				header: Header::decode_rlp(&last_rlp.at(0)?, eip1559_transition)?,
 				transactions: last_rlp.list_at(1)?,
-				uncles: last_rlp.list_at(2)?,
+				uncles: last_rlp.at(2)?.iter()
+					.map(|uncle| Header::decode_rlp(&uncle, eip1559_transition))
+					.collect::<Result<_, _>>()?,
 			};
 			let block_data = block.rlp_bytes();
 			let receipts: Vec<Receipt> = last_rlp.list_at(3)?;
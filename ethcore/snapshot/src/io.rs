@@ -20,7 +20,7 @@
 //! Packed snapshots are written to a single file, and loose snapshots are
 //! written to multiple files in one directory.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::fs::{self, File};
 use std::path::{Path, PathBuf};
@@ -28,14 +28,108 @@ use std::path::{Path, PathBuf};
 use bytes::Bytes;
 use common_types::{
 	errors::{SnapshotError, EthcoreError},
-	snapshot::ManifestData,
+	snapshot::{ChunkChecksum, Compression, ManifestData},
 };
 use ethereum_types::H256;
 use log::trace;
-use rlp::{RlpStream, Rlp};
+use rlp::{DecoderError, RlpStream, Rlp};
 use rlp_derive::*;
 
-pub const SNAPSHOT_VERSION: u64 = 2;
+pub const SNAPSHOT_VERSION: u64 = 3;
+
+/// File the progress of a loose snapshot being written is persisted to.
+const CHECKPOINT_FILE: &str = "CHECKPOINT";
+
+/// Progress of a snapshot being written, persisted so an interrupted snapshot can be resumed
+/// rather than restarted.
+///
+/// The state is checkpointed per state part and the blocks once all of them are written; parts
+/// interrupted halfway are chunked again, which yields the very same chunks.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Checkpoint {
+	/// Hash of the block the snapshot is taken at.
+	pub block_hash: H256,
+	/// Compression of the chunks.
+	pub compression: Compression,
+	/// Chunks of the completed state parts, by part.
+	pub state_parts: BTreeMap<usize, Chunks>,
+	/// Chunks of the blocks, if all of them were written.
+	pub blocks: Option<Chunks>,
+}
+
+/// Chunks written for a completed piece of a snapshot.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Chunks {
+	/// Hashes of the chunks, in order.
+	pub hashes: Vec<H256>,
+	/// Checksums of the chunks, in order.
+	pub checksums: Vec<ChunkChecksum>,
+	/// Number of accounts or blocks in the chunks.
+	pub items: u64,
+	/// Size of the chunks in bytes.
+	pub size: u64,
+}
+
+impl Checkpoint {
+	/// Create an empty checkpoint of a snapshot at given block, with chunks compressed using `compression`.
+	pub fn new(block_hash: H256, compression: Compression) -> Self {
+		Checkpoint { block_hash, compression, ..Default::default() }
+	}
+
+	fn to_rlp(&self) -> Bytes {
+		let mut stream = RlpStream::new_list(4);
+		stream.append(&self.block_hash);
+		stream.begin_list(self.state_parts.len());
+		for (part, chunks) in &self.state_parts {
+			stream.begin_list(2).append(&(*part as u64));
+			chunks.append_to(&mut stream);
+		}
+		match self.blocks {
+			Some(ref chunks) => { stream.begin_list(1); chunks.append_to(&mut stream); },
+			None => { stream.begin_list(0); },
+		}
+		stream.append(&self.compression);
+		stream.out()
+	}
+
+	fn from_rlp(raw: &[u8]) -> Result<Self, DecoderError> {
+		let rlp = Rlp::new(raw);
+		let state_parts = rlp.at(1)?.iter()
+			.map(|part| Ok((part.val_at::<u64>(0)? as usize, Chunks::decode(&part.at(1)?)?)))
+			.collect::<Result<_, DecoderError>>()?;
+		let blocks = rlp.at(2)?;
+		let blocks = match blocks.item_count()? {
+			0 => None,
+			_ => Some(Chunks::decode(&blocks.at(0)?)?),
+		};
+
+		Ok(Checkpoint {
+			block_hash: rlp.val_at(0)?,
+			compression: rlp.val_at(3)?,
+			state_parts,
+			blocks,
+		})
+	}
+}
+
+impl Chunks {
+	fn append_to(&self, stream: &mut RlpStream) {
+		stream.begin_list(4)
+			.append_list(&self.hashes)
+			.append(&self.items)
+			.append(&self.size)
+			.append_list(&self.checksums);
+	}
+
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		Ok(Chunks {
+			hashes: rlp.list_at(0)?,
+			items: rlp.val_at(1)?,
+			size: rlp.val_at(2)?,
+			checksums: rlp.list_at(3)?,
+		})
+	}
+}
 
 // (hash, len, offset)
 #[derive(RlpEncodable, RlpDecodable)]
@@ -94,14 +188,17 @@ impl SnapshotWriter for PackedWriter {
 	fn finish(mut self, manifest: ManifestData) -> io::Result<()> {
 		// we ignore the hashes fields of the manifest under the assumption that
 		// they are consistent with ours.
-		let mut stream = RlpStream::new_list(6);
+		let mut stream = RlpStream::new_list(9);
 		stream
 			.append(&SNAPSHOT_VERSION)
 			.append_list(&self.state_hashes)
 			.append_list(&self.block_hashes)
 			.append(&manifest.state_root)
 			.append(&manifest.block_number)
-			.append(&manifest.block_hash);
+			.append(&manifest.block_hash)
+			.append(&manifest.compression)
+			.append_list(&manifest.state_checksums)
+			.append_list(&manifest.block_checksums);
 
 		let manifest_rlp = stream.out();
 
@@ -169,7 +266,44 @@ impl SnapshotWriter for LooseWriter {
 		let mut file = File::create(path)?;
 		file.write_all(&rlp[..])?;
 
-		Ok(())
+		match fs::remove_file(self.dir.join(CHECKPOINT_FILE)) {
+			Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+			res => res,
+		}
+	}
+
+	/// Resumes the snapshot if the directory holds a checkpoint of the same block and compression,
+	/// otherwise the directory is emptied.
+	fn resume(&mut self, block_hash: H256, compression: Compression) -> io::Result<Checkpoint> {
+		let path = self.dir.join(CHECKPOINT_FILE);
+		let checkpoint = match fs::read(&path) {
+			Ok(raw) => Checkpoint::from_rlp(&raw).ok(),
+			Err(ref e) if e.kind() == io::ErrorKind::NotFound => None,
+			Err(e) => return Err(e),
+		};
+
+		match checkpoint {
+			Some(checkpoint) if checkpoint.block_hash == block_hash && checkpoint.compression == compression => {
+				trace!(target: "snapshot_io", "resuming snapshot of {:?} with {} state parts done",
+					block_hash, checkpoint.state_parts.len());
+				Ok(checkpoint)
+			}
+			_ => {
+				fs::remove_dir_all(&self.dir)?;
+				fs::create_dir_all(&self.dir)?;
+				Ok(Checkpoint::new(block_hash, compression))
+			}
+		}
+	}
+
+	fn checkpoint(&mut self, checkpoint: &Checkpoint) -> io::Result<()> {
+		// write to a temporary file first, so an interruption never leaves a truncated checkpoint
+		let path = self.dir.join(CHECKPOINT_FILE);
+		let tmp_path = path.with_extension("tmp");
+		let mut file = File::create(&tmp_path)?;
+		file.write_all(&checkpoint.to_rlp())?;
+		file.sync_all()?;
+		fs::rename(&tmp_path, &path)
 	}
 }
 
@@ -196,6 +330,17 @@ pub trait SnapshotWriter {
 	/// Complete writing. The manifest's chunk lists must be consistent
 	/// with the chunks written.
 	fn finish(self, manifest: ManifestData) -> std::io::Result<()> where Self: Sized;
+
+	/// Start writing a snapshot of the given block, returning the progress made by an earlier,
+	/// interrupted attempt which is to be resumed. Writers unable to resume start from scratch.
+	fn resume(&mut self, block_hash: H256, compression: Compression) -> std::io::Result<Checkpoint> {
+		Ok(Checkpoint::new(block_hash, compression))
+	}
+
+	/// Persist the progress made so far.
+	fn checkpoint(&mut self, _checkpoint: &Checkpoint) -> std::io::Result<()> {
+		Ok(())
+	}
 }
 
 /// Packed snapshot reader.
@@ -243,7 +388,8 @@ impl PackedReader {
 
 		let rlp = Rlp::new(&manifest_buf);
 
-		let (start, version) = if rlp.item_count()? == 5 {
+		let item_count = rlp.item_count()?;
+		let (start, version) = if item_count == 5 {
 			(0, 1)
 		} else {
 			(1, rlp.val_at(0)?)
@@ -256,6 +402,12 @@ impl PackedReader {
 		let state: Vec<ChunkInfo> = rlp.list_at(0 + start)?;
 		let blocks: Vec<ChunkInfo> = rlp.list_at(1 + start)?;
 
+		let (compression, state_checksums, block_checksums) = if item_count == 8 + start {
+			(rlp.val_at(5 + start)?, rlp.list_at(6 + start)?, rlp.list_at(7 + start)?)
+		} else {
+			(Compression::Snappy, Vec::new(), Vec::new())
+		};
+
 		let manifest = ManifestData {
 			version: version,
 			state_hashes: state.iter().map(|c| c.0).collect(),
@@ -263,6 +415,9 @@ impl PackedReader {
 			state_root: rlp.val_at(2 + start)?,
 			block_number: rlp.val_at(3 + start)?,
 			block_hash: rlp.val_at(4 + start)?,
+			compression,
+			state_checksums,
+			block_checksums,
 		};
 
 		Ok(Some(PackedReader {
@@ -23,6 +23,7 @@ use std::collections::{HashMap, HashSet};
 use std::cmp;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use keccak_hash::{keccak, KECCAK_NULL_RLP, KECCAK_EMPTY};
 
@@ -35,7 +36,7 @@ use common_types::{
 	ids::BlockId,
 	header::Header,
 	errors::{SnapshotError as Error, EthcoreError},
-	snapshot::{Progress, ManifestData},
+	snapshot::{ChunkChecksum, Compression, Progress, ManifestData},
 };
 use crossbeam_utils::thread;
 use engine::Engine;
@@ -51,13 +52,14 @@ use num_cpus;
 use rand::{Rng, rngs::OsRng};
 use rlp::{RlpStream, Rlp};
 use snappy;
+use zstd;
 use state_db::StateDB;
 use trie_db::{Trie, TrieMut};
 
 pub use self::consensus::*;
 pub use self::service::{Service, Guard, Restoration, RestorationParams};
 pub use self::traits::{Broadcast, Oracle, SnapshotService, SnapshotClient, SnapshotComponents, Rebuilder};
-pub use self::io::SnapshotWriter;
+pub use self::io::{Checkpoint, Chunks, SnapshotWriter};
 pub use self::watcher::Watcher;
 use common_types::basic_account::BasicAccount;
 
@@ -90,11 +92,13 @@ const MAX_CHUNK_SIZE: usize = PREFERRED_CHUNK_SIZE / 4 * 5;
 // Minimum supported state chunk version.
 const MIN_SUPPORTED_STATE_CHUNK_VERSION: u64 = 1;
 // current state chunk version.
-const STATE_CHUNK_VERSION: u64 = 2;
+const STATE_CHUNK_VERSION: u64 = 3;
 /// number of snapshot subparts, must be a power of 2 in [1; 256]
 pub const SNAPSHOT_SUBPARTS: usize = 16;
 /// Maximum number of snapshot subparts (must be a multiple of `SNAPSHOT_SUBPARTS`)
 const MAX_SNAPSHOT_SUBPARTS: usize = 256;
+// Zstandard compression level of chunks.
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
 
 /// Configuration for the Snapshot service
 #[derive(Debug, Clone, PartialEq)]
@@ -103,6 +107,10 @@ pub struct SnapshotConfiguration {
 	pub no_periodic: bool,
 	/// Number of threads for creating snapshots
 	pub processing_threads: usize,
+	/// Maximum number of bytes of state chunks written per second, unlimited if `None`
+	pub io_budget: Option<u64>,
+	/// Compression of the chunks of created snapshots
+	pub compression: Compression,
 }
 
 impl Default for SnapshotConfiguration {
@@ -110,19 +118,27 @@ impl Default for SnapshotConfiguration {
 		SnapshotConfiguration {
 			no_periodic: false,
 			processing_threads: ::std::cmp::max(1, num_cpus::get_physical() / 2),
+			io_budget: None,
+			compression: Compression::default(),
 		}
 	}
 }
 
 /// Take a snapshot using the given blockchain, starting block hash, and database, writing into the given writer.
+///
+/// Progress is checkpointed through the writer, so a snapshot of the same block interrupted earlier
+/// is resumed. Writing state chunks is throttled to `io_budget` bytes per second, if given.
+/// Chunks are compressed with `compression`.
 pub fn take_snapshot<W: SnapshotWriter + Send>(
 	chunker: Box<dyn SnapshotComponents>,
 	chain: &BlockChain,
 	block_hash: H256,
 	state_db: &dyn HashDB<KeccakHasher, DBValue>,
-	writer: W,
+	mut writer: W,
 	p: &Progress,
 	processing_threads: usize,
+	io_budget: Option<u64>,
+	compression: Compression,
 ) -> Result<(), Error> {
 	let start_header = chain.block_header_data(&block_hash)
 		.ok_or_else(|| Error::InvalidStartingBlock(BlockId::Hash(block_hash)))?;
@@ -131,57 +147,98 @@ pub fn take_snapshot<W: SnapshotWriter + Send>(
 
 	info!("Taking snapshot starting at block {}", block_number);
 
+	let checkpoint = writer.resume(block_hash, compression)?;
+	if !checkpoint.state_parts.is_empty() || checkpoint.blocks.is_some() {
+		info!(target: "snapshot", "Resuming snapshot with {} of {} state parts done.", checkpoint.state_parts.len(), SNAPSHOT_SUBPARTS);
+	}
+
 	let version = chunker.current_version();
 	let writer = Mutex::new(writer);
-	let (state_hashes, block_hashes) = thread::scope(|scope| -> Result<(Vec<H256>, Vec<H256>), Error> {
+	let checkpoint = Mutex::new(checkpoint);
+	let (state_chunks, block_chunks) = thread::scope(|scope| -> Result<(Chunks, Chunks), Error> {
 		let writer = &writer;
-		let block_guard = scope.spawn(move |_| {
-			chunk_secondary(chunker, chain, block_hash, writer, p)
+		let checkpoint = &checkpoint;
+		let block_guard = scope.spawn(move |_| -> Result<Chunks, Error> {
+			let done = checkpoint.lock().blocks.clone();
+			if let Some(chunks) = done {
+				p.blocks.fetch_add(chunks.items as usize, Ordering::SeqCst);
+				p.size.fetch_add(chunks.size, Ordering::SeqCst);
+				return Ok(chunks);
+			}
+
+			let chunks = chunk_blocks(chunker, chain, block_hash, writer, p, compression)?;
+			let mut checkpoint = checkpoint.lock();
+			checkpoint.blocks = Some(chunks.clone());
+			writer.lock().checkpoint(&checkpoint)?;
+			Ok(chunks)
 		});
 
 		// The number of threads must be between 1 and SNAPSHOT_SUBPARTS
 		assert!(processing_threads >= 1, "Cannot use less than 1 threads for creating snapshots");
 		let num_threads: usize = cmp::min(processing_threads, SNAPSHOT_SUBPARTS);
 		info!(target: "snapshot", "Using {} threads for Snapshot creation.", num_threads);
+		// every thread gets an even share of the budget
+		let io_budget = io_budget.map(|budget| cmp::max(1, budget / num_threads as u64));
 
 		let mut state_guards = Vec::with_capacity(num_threads as usize);
 
 		for thread_idx in 0..num_threads {
-			let state_guard = scope.spawn(move |_| -> Result<Vec<H256>, Error> {
-				let mut chunk_hashes = Vec::new();
+			let state_guard = scope.spawn(move |_| -> Result<Chunks, Error> {
+				let mut thread_chunks = Chunks::default();
 
 				for part in (thread_idx..SNAPSHOT_SUBPARTS).step_by(num_threads) {
-					debug!(target: "snapshot", "Chunking part {} in thread {}", part, thread_idx);
-					let mut hashes = chunk_state(state_db, &state_root, writer, p, Some(part), thread_idx)?;
-					chunk_hashes.append(&mut hashes);
+					let done = checkpoint.lock().state_parts.get(&part).cloned();
+					let chunks = match done {
+						Some(chunks) => {
+							debug!(target: "snapshot", "Part {} already chunked", part);
+							p.accounts.fetch_add(chunks.items as usize, Ordering::SeqCst);
+							p.size.fetch_add(chunks.size, Ordering::SeqCst);
+							chunks
+						}
+						None => {
+							debug!(target: "snapshot", "Chunking part {} in thread {}", part, thread_idx);
+							let chunks = chunk_state_part(state_db, &state_root, writer, p, Some(part), thread_idx, io_budget, compression)?;
+							let mut checkpoint = checkpoint.lock();
+							checkpoint.state_parts.insert(part, chunks.clone());
+							writer.lock().checkpoint(&checkpoint)?;
+							chunks
+						}
+					};
+					p.state_parts.fetch_add(1, Ordering::SeqCst);
+					thread_chunks.hashes.extend(chunks.hashes);
+					thread_chunks.checksums.extend(chunks.checksums);
 				}
 
-				Ok(chunk_hashes)
+				Ok(thread_chunks)
 			});
 			state_guards.push(state_guard);
 		}
 
-		let block_hashes = block_guard.join().expect("Sub-thread never panics; qed")?;
-		let mut state_hashes = Vec::new();
+		let block_chunks = block_guard.join().expect("Sub-thread never panics; qed")?;
+		let mut state_chunks = Chunks::default();
 
 		for guard in state_guards {
-			let part_state_hashes = guard.join().expect("Sub-thread never panics; qed")?;
-			state_hashes.extend(part_state_hashes);
+			let part_state_chunks = guard.join().expect("Sub-thread never panics; qed")?;
+			state_chunks.hashes.extend(part_state_chunks.hashes);
+			state_chunks.checksums.extend(part_state_chunks.checksums);
 		}
 
 		debug!(target: "snapshot", "Took a snapshot of {} accounts", p.accounts.load(Ordering::SeqCst));
-		Ok((state_hashes, block_hashes))
+		Ok((state_chunks, block_chunks))
 	}).expect("Sub-thread never panics; qed")?;
 
-	info!(target: "snapshot", "produced {} state chunks and {} block chunks.", state_hashes.len(), block_hashes.len());
+	info!(target: "snapshot", "produced {} state chunks and {} block chunks.", state_chunks.hashes.len(), block_chunks.hashes.len());
 
 	let manifest_data = ManifestData {
 		version,
-		state_hashes,
-		block_hashes,
+		state_hashes: state_chunks.hashes,
+		block_hashes: block_chunks.hashes,
 		state_root,
 		block_number,
 		block_hash,
+		compression,
+		state_checksums: state_chunks.checksums,
+		block_checksums: block_chunks.checksums,
 	};
 
 	writer.into_inner().finish(manifest_data)?;
@@ -198,19 +255,34 @@ pub fn take_snapshot<W: SnapshotWriter + Send>(
 /// in the state chunks.
 /// Returns a list of chunk hashes, with the first having the blocks furthest from the genesis.
 pub fn chunk_secondary<'a>(
-	mut chunker: Box<dyn SnapshotComponents>,
+	chunker: Box<dyn SnapshotComponents>,
 	chain: &'a BlockChain,
 	start_hash: H256,
 	writer: &Mutex<dyn SnapshotWriter + 'a>,
 	progress: &'a Progress
 ) -> Result<Vec<H256>, Error> {
+	chunk_blocks(chunker, chain, start_hash, writer, progress, Compression::Snappy).map(|chunks| chunks.hashes)
+}
+
+// Same as `chunk_secondary`, but compresses the chunks with `compression` and also returns
+// the checksums of the chunks, the number of blocks and the size of the chunks.
+fn chunk_blocks<'a>(
+	mut chunker: Box<dyn SnapshotComponents>,
+	chain: &'a BlockChain,
+	start_hash: H256,
+	writer: &Mutex<dyn SnapshotWriter + 'a>,
+	progress: &'a Progress,
+	compression: Compression,
+) -> Result<Chunks, Error> {
+	let blocks_before = progress.blocks();
 	let mut chunk_hashes = Vec::new();
-	let mut snappy_buffer = vec![0; snappy::max_compressed_len(PREFERRED_CHUNK_SIZE)];
+	let mut chunk_checksums = Vec::new();
+	let mut chunks_size = 0;
+	let mut compression_buffer = vec![0; snappy::max_compressed_len(PREFERRED_CHUNK_SIZE)];
 
 	{
 		let mut chunk_sink = |raw_data: &[u8]| {
-			let compressed_size = snappy::compress_into(raw_data, &mut snappy_buffer);
-			let compressed = &snappy_buffer[..compressed_size];
+			let compressed = compress_chunk(compression, raw_data, &mut compression_buffer)?;
 			let hash = keccak(&compressed);
 			let size = compressed.len();
 
@@ -219,7 +291,9 @@ pub fn chunk_secondary<'a>(
 				hash, size, raw_data.len());
 
 			progress.size.fetch_add(size as u64, Ordering::SeqCst);
+			chunks_size += size as u64;
 			chunk_hashes.push(hash);
+			chunk_checksums.push(ChunkChecksum { len: raw_data.len() as u64, checksum: keccak(raw_data) });
 			Ok(())
 		};
 
@@ -232,18 +306,61 @@ pub fn chunk_secondary<'a>(
 		)?;
 	}
 
-	Ok(chunk_hashes)
+	Ok(Chunks {
+		hashes: chunk_hashes,
+		checksums: chunk_checksums,
+		items: (progress.blocks() - blocks_before) as u64,
+		size: chunks_size,
+	})
+}
+
+/// Compress a raw chunk with the given algorithm, using `buffer` as scratch space.
+fn compress_chunk<'b>(compression: Compression, raw: &[u8], buffer: &'b mut Bytes) -> std::io::Result<&'b [u8]> {
+	match compression {
+		Compression::Snappy => {
+			let size = snappy::compress_into(raw, buffer);
+			Ok(&buffer[..size])
+		}
+		Compression::Zstd => {
+			*buffer = zstd::block::compress(raw, ZSTD_COMPRESSION_LEVEL)?;
+			Ok(&buffer[..])
+		}
+	}
+}
+
+/// Decompress a chunk compressed with the given algorithm into `buffer`, returning the length
+/// of the decompressed data. Chunks decompressing to more than `MAX_CHUNK_SIZE` are rejected.
+pub(crate) fn decompress_chunk(compression: Compression, chunk: &[u8], buffer: &mut Bytes) -> Result<usize, EthcoreError> {
+	match compression {
+		Compression::Snappy => {
+			let expected_len = snappy::decompressed_len(chunk)?;
+			if expected_len > MAX_CHUNK_SIZE {
+				trace!(target: "snapshot", "Discarding large chunk: {} vs {}", expected_len, MAX_CHUNK_SIZE);
+				return Err(Error::ChunkTooLarge.into());
+			}
+			Ok(snappy::decompress_into(chunk, buffer)?)
+		}
+		Compression::Zstd => {
+			*buffer = zstd::block::decompress(chunk, MAX_CHUNK_SIZE)?;
+			Ok(buffer.len())
+		}
+	}
 }
 
 /// State trie chunker.
 struct StateChunker<'a> {
 	hashes: Vec<H256>,
+	checksums: Vec<ChunkChecksum>,
 	rlps: Vec<Bytes>,
 	cur_size: usize,
-	snappy_buffer: Vec<u8>,
+	compression: Compression,
+	compression_buffer: Vec<u8>,
 	writer: &'a Mutex<dyn SnapshotWriter + 'a>,
 	progress: &'a Progress,
 	thread_idx: usize,
+	accounts: u64,
+	size: u64,
+	io_budget: Option<u64>,
 }
 
 impl<'a> StateChunker<'a> {
@@ -268,8 +385,8 @@ impl<'a> StateChunker<'a> {
 
 		let raw_data = stream.out();
 
-		let compressed_size = snappy::compress_into(&raw_data, &mut self.snappy_buffer);
-		let compressed = &self.snappy_buffer[..compressed_size];
+		let compressed = compress_chunk(self.compression, &raw_data, &mut self.compression_buffer)?;
+		let compressed_size = compressed.len();
 		let hash = keccak(&compressed);
 
 		self.writer.lock().write_state_chunk(hash, compressed)?;
@@ -277,10 +394,18 @@ impl<'a> StateChunker<'a> {
 
 		self.progress.accounts.fetch_add(num_entries, Ordering::SeqCst);
 		self.progress.size.fetch_add(compressed_size as u64, Ordering::SeqCst);
+		self.accounts += num_entries as u64;
+		self.size += compressed_size as u64;
 
 		self.hashes.push(hash);
+		self.checksums.push(ChunkChecksum { len: raw_data.len() as u64, checksum: keccak(&raw_data) });
 		self.cur_size = 0;
 
+		// yield, so the snapshot doesn't saturate the IO of a busy node
+		if let Some(budget) = self.io_budget {
+			std::thread::sleep(Duration::from_millis(compressed_size as u64 * 1000 / budget));
+		}
+
 		Ok(())
 	}
 
@@ -305,16 +430,37 @@ pub fn chunk_state<'a>(
 	part: Option<usize>,
 	thread_idx: usize,
 ) -> Result<Vec<H256>, Error> {
+	chunk_state_part(db, root, writer, progress, part, thread_idx, None, Compression::Snappy).map(|chunks| chunks.hashes)
+}
+
+// Same as `chunk_state`, but compresses the chunks with `compression`, also returns the checksums
+// of the chunks, the number of accounts and the size of the chunks, and throttles writing the
+// chunks to `io_budget` bytes per second.
+fn chunk_state_part<'a>(
+	db: &dyn HashDB<KeccakHasher, DBValue>,
+	root: &H256,
+	writer: &Mutex<dyn SnapshotWriter + 'a>,
+	progress: &'a Progress,
+	part: Option<usize>,
+	thread_idx: usize,
+	io_budget: Option<u64>,
+	compression: Compression,
+) -> Result<Chunks, Error> {
 	let account_trie = TrieDB::new(&db, &root)?;
 
 	let mut chunker = StateChunker {
 		hashes: Vec::new(),
+		checksums: Vec::new(),
 		rlps: Vec::new(),
 		cur_size: 0,
-		snappy_buffer: vec![0; snappy::max_compressed_len(PREFERRED_CHUNK_SIZE)],
+		compression,
+		compression_buffer: vec![0; snappy::max_compressed_len(PREFERRED_CHUNK_SIZE)],
 		writer,
 		progress,
 		thread_idx,
+		accounts: 0,
+		size: 0,
+		io_budget,
 	};
 
 	let mut used_code = HashSet::new();
@@ -362,7 +508,12 @@ pub fn chunk_state<'a>(
 		chunker.write_chunk()?;
 	}
 
-	Ok(chunker.hashes)
+	Ok(Chunks {
+		hashes: chunker.hashes,
+		checksums: chunker.checksums,
+		items: chunker.accounts,
+		size: chunker.size,
+	})
 }
 
 /// Used to rebuild the state trie piece by piece.
@@ -550,7 +701,7 @@ pub fn verify_old_block(rng: &mut OsRng, header: &Header, engine: &dyn Engine, c
 	if always || rng.gen::<f32>() <= POW_VERIFY_RATE {
 		engine.verify_block_unordered(header)?;
 		match chain.block_header_data(header.parent_hash()) {
-			Some(parent) => engine.verify_block_family(header, &parent.decode()?).map_err(Into::into),
+			Some(parent) => engine.verify_block_family(header, &parent.decode(engine.params().eip1559_transition)?).map_err(Into::into),
 			None => Ok(()),
 		}
 	} else {
@@ -16,7 +16,7 @@
 
 //! Snapshot network service implementation.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::{self, Read, ErrorKind};
 use std::fs::{self, File};
 use std::path::PathBuf;
@@ -30,7 +30,7 @@ use common_types::{
 	io_message::ClientIoMessage,
 	errors::{EthcoreError as Error, SnapshotError, SnapshotError::UnlinkedAncientBlockChain},
 	ids::BlockId,
-	snapshot::{ManifestData, Progress, RestorationStatus},
+	snapshot::{ChunkChecksum, CreationStatus, ManifestData, Progress, RestorationStatus},
 };
 use client_traits::ChainInfo;
 use engine::Engine;
@@ -41,7 +41,6 @@ use keccak_hash::keccak;
 use kvdb::DBTransaction;
 use log::{error, info, trace, warn};
 use parking_lot::{Mutex, RwLock, RwLockReadGuard};
-use snappy;
 use trie_db::TrieError;
 
 use crate::{SnapshotClient, SnapshotWriter};
@@ -50,9 +49,10 @@ use super::{
 	StateRebuilder,
 	SnapshotService,
 	Rebuilder,
-	MAX_CHUNK_SIZE,
+	SNAPSHOT_SUBPARTS,
 	io::{SnapshotReader, LooseReader,  LooseWriter},
 	chunker,
+	decompress_chunk,
 };
 
 /// Helper for removing directories in case of error.
@@ -80,10 +80,11 @@ pub struct Restoration {
 	manifest: ManifestData,
 	state_chunks_left: HashSet<H256>,
 	block_chunks_left: HashSet<H256>,
+	checksums: HashMap<H256, ChunkChecksum>,
 	state: StateRebuilder,
 	secondary: Box<dyn Rebuilder>,
 	writer: Option<LooseWriter>,
-	snappy_buffer: Bytes,
+	decompression_buffer: Bytes,
 	final_state_root: H256,
 	guard: Guard,
 	db: Arc<dyn BlockChainDB>,
@@ -122,6 +123,10 @@ impl Restoration {
 
 		let state_chunks = manifest.state_hashes.iter().cloned().collect();
 		let block_chunks = manifest.block_hashes.iter().cloned().collect();
+		let checksums = manifest.state_hashes.iter().zip(&manifest.state_checksums)
+			.chain(manifest.block_hashes.iter().zip(&manifest.block_checksums))
+			.map(|(hash, checksum)| (*hash, *checksum))
+			.collect();
 
 		let raw_db = params.db;
 
@@ -137,10 +142,11 @@ impl Restoration {
 			manifest,
 			state_chunks_left: state_chunks,
 			block_chunks_left: block_chunks,
+			checksums,
 			state: StateRebuilder::new(raw_db.key_value().clone(), params.pruning),
 			secondary,
 			writer: params.writer,
-			snappy_buffer: Vec::new(),
+			decompression_buffer: Vec::new(),
 			final_state_root,
 			guard: params.guard,
 			db: raw_db,
@@ -150,14 +156,10 @@ impl Restoration {
 	/// Feeds a chunk of state data to the Restoration. Aborts early if `flag` becomes false.
 	pub fn feed_state(&mut self, hash: H256, chunk: &[u8], flag: &AtomicBool) -> Result<(), Error> {
 		if self.state_chunks_left.contains(&hash) {
-			let expected_len = snappy::decompressed_len(chunk)?;
-			if expected_len > MAX_CHUNK_SIZE {
-				trace!(target: "snapshot", "Discarding large chunk: {} vs {}", expected_len, MAX_CHUNK_SIZE);
-				return Err(SnapshotError::ChunkTooLarge.into());
-			}
-			let len = snappy::decompress_into(chunk, &mut self.snappy_buffer)?;
+			let len = decompress_chunk(self.manifest.compression, chunk, &mut self.decompression_buffer)?;
+			self.verify_checksum(hash, &self.decompression_buffer[..len])?;
 
-			self.state.feed(&self.snappy_buffer[..len], flag)?;
+			self.state.feed(&self.decompression_buffer[..len], flag)?;
 
 			if let Some(ref mut writer) = self.writer.as_mut() {
 				writer.write_state_chunk(hash, chunk)?;
@@ -172,14 +174,10 @@ impl Restoration {
 	/// Feeds a chunk of block data to the `Restoration`. Aborts early if `flag` becomes false.
 	pub fn feed_blocks(&mut self, hash: H256, chunk: &[u8], engine: &dyn Engine, flag: &AtomicBool) -> Result<(), Error> {
 		if self.block_chunks_left.contains(&hash) {
-			let expected_len = snappy::decompressed_len(chunk)?;
-			if expected_len > MAX_CHUNK_SIZE {
-				trace!(target: "snapshot", "Discarding large chunk: {} vs {}", expected_len, MAX_CHUNK_SIZE);
-				return Err(SnapshotError::ChunkTooLarge.into());
-			}
-			let len = snappy::decompress_into(chunk, &mut self.snappy_buffer)?;
+			let len = decompress_chunk(self.manifest.compression, chunk, &mut self.decompression_buffer)?;
+			self.verify_checksum(hash, &self.decompression_buffer[..len])?;
 
-			self.secondary.feed(&self.snappy_buffer[..len], engine, flag)?;
+			self.secondary.feed(&self.decompression_buffer[..len], engine, flag)?;
 			if let Some(ref mut writer) = self.writer.as_mut() {
 				 writer.write_block_chunk(hash, chunk)?;
 			}
@@ -190,6 +188,16 @@ impl Restoration {
 		Ok(())
 	}
 
+	// check the decompressed chunk against the checksum in the manifest, if it records one.
+	fn verify_checksum(&self, hash: H256, raw: &[u8]) -> Result<(), Error> {
+		match self.checksums.get(&hash) {
+			Some(expected) if expected.len != raw.len() as u64 || expected.checksum != keccak(raw) => {
+				Err(SnapshotError::ChunkChecksumMismatch(hash).into())
+			}
+			_ => Ok(()),
+		}
+	}
+
 	// finish up restoration.
 	fn finalize(mut self) -> Result<(), Error> {
 		if !self.is_done() { return Ok(()) }
@@ -299,12 +307,7 @@ impl<C> Service<C> where C: SnapshotClient + ChainInfo {
 			}
 		}
 
-		// delete the temporary snapshot dir if it does exist.
-		if let Err(e) = fs::remove_dir_all(service.temp_snapshot_dir()) {
-			if e.kind() != ErrorKind::NotFound {
-				return Err(e.into())
-			}
-		}
+		// the temporary snapshot dir is kept, so an interrupted snapshot can be resumed.
 
 		let reader = LooseReader::new(service.snapshot_dir()).ok();
 		*service.reader.get_mut() = reader;
@@ -502,11 +505,9 @@ impl<C> Service<C> where C: SnapshotClient + ChainInfo {
 		let temp_dir = self.temp_snapshot_dir();
 		let snapshot_dir = self.snapshot_dir();
 
-		let _ = fs::remove_dir_all(&temp_dir);
-
+		// an interrupted snapshot of the same block is resumed from what is left in `temp_dir`.
 		let writer = LooseWriter::new(temp_dir.clone())?;
 
-		let guard = Guard::new(temp_dir.clone());
 		let res = client.take_snapshot(writer, BlockId::Number(num), &self.progress);
 		self.taking_snapshot.store(false, Ordering::SeqCst);
 		if let Err(e) = res {
@@ -534,7 +535,6 @@ impl<C> Service<C> where C: SnapshotClient + ChainInfo {
 
 		*reader = Some(LooseReader::new(snapshot_dir)?);
 
-		guard.disarm();
 		Ok(())
 	}
 
@@ -884,6 +884,27 @@ impl<C: Send + Sync> SnapshotService for Service<C> {
 		}
 	}
 
+	fn creation_status(&self) -> CreationStatus {
+		if !self.taking_snapshot.load(Ordering::SeqCst) {
+			return CreationStatus::Inactive;
+		}
+
+		let p = &self.progress;
+		let accounts = p.accounts();
+		// the state parts are split evenly by account hash, so they hold similar numbers of accounts
+		let estimated_accounts = match p.state_parts() {
+			0 => None,
+			parts => Some(accounts * SNAPSHOT_SUBPARTS / parts),
+		};
+
+		CreationStatus::Ongoing {
+			accounts,
+			estimated_accounts,
+			blocks: p.blocks(),
+			size: p.size(),
+		}
+	}
+
 	fn abort_snapshot(&self) {
 		if self.taking_snapshot.load(Ordering::SeqCst) {
 			trace!(target: "snapshot", "Aborting snapshot – Snapshot under way");
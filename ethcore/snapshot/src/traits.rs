@@ -22,7 +22,7 @@ use client_traits::{BlockChainClient, BlockInfo, DatabaseRestore, BlockChainRese
 use common_types::{
 	ids::BlockId,
 	errors::{EthcoreError as Error, SnapshotError},
-	snapshot::{ManifestData, ChunkSink, Progress, RestorationStatus},
+	snapshot::{CreationStatus, ManifestData, ChunkSink, Progress, RestorationStatus},
 };
 use engine::Engine;
 use ethereum_types::H256;
@@ -66,6 +66,9 @@ pub trait SnapshotService : Sync + Send {
 	/// no-op if currently restoring.
 	fn restore_block_chunk(&self, hash: H256, chunk: Bytes);
 
+	/// Ask the snapshot service for the status of the snapshot being taken.
+	fn creation_status(&self) -> CreationStatus;
+
 	/// Abort in-progress snapshotting if there is one.
 	fn abort_snapshot(&self);
 
@@ -145,6 +145,7 @@ fn run_constructors<T: Backend>(
 			last_hashes: Default::default(),
 			gas_used: U256::zero(),
 			gas_limit: U256::max_value(),
+			base_fee: None,
 		};
 
 		let from = Address::zero();
@@ -528,6 +529,7 @@ impl Spec {
 				gas_limit: U256::max_value(),
 				last_hashes: Arc::new(Vec::new()),
 				gas_used: 0.into(),
+				base_fee: None,
 			};
 
 			let from = Address::zero();
@@ -576,6 +578,28 @@ mod tests {
 		assert!(Spec::load(&tempdir.path(), &[] as &[u8]).is_err());
 	}
 
+	#[test]
+	fn test_load_unknown_builtin() {
+		let tempdir = TempDir::new("").unwrap();
+		let spec = include_str!("../../res/null.json")
+			.replace(r#""name": "ecrecover""#, r#""name": "alt_bn128_pariring""#);
+		let err = Spec::load(&tempdir.path(), spec.as_bytes()).err().expect("unknown builtin");
+		assert!(err.to_string().contains("alt_bn128_pariring"));
+	}
+
+	#[test]
+	fn bundled_spec_builtins_resolve() {
+		let tempdir = TempDir::new("").unwrap();
+		let specs: &[&[u8]] = &[
+			include_bytes!("../../res/ethereum/foundation.json"),
+			include_bytes!("../../res/ethereum/classic.json"),
+			include_bytes!("../../res/ethereum/kovan.json"),
+		];
+		for spec in specs {
+			assert!(Spec::load(&tempdir.path(), *spec).is_ok());
+		}
+	}
+
 	#[test]
 	fn test_chain() {
 		let test_spec = crate::new_test();
@@ -21,18 +21,20 @@ use std::io::{BufRead, BufReader};
 use std::str::from_utf8;
 use std::sync::{Arc, Weak};
 use std::sync::atomic::{AtomicBool, AtomicI64, Ordering as AtomicOrdering};
+use std::thread;
 use std::time::{Duration, Instant};
 
 use ansi_term::Colour;
 use bytes::Bytes;
 use bytes::ToPretty;
 use ethereum_types::{Address, H256, H264, U256};
-use hash::keccak;
+use hash::{keccak, KECCAK_EMPTY, KECCAK_NULL_RLP};
 use hash_db::EMPTY_PREFIX;
 use itertools::Itertools;
 use kvdb::{DBTransaction, DBValue, KeyValueDB};
 use parking_lot::{Mutex, RwLock};
 use rand::rngs::OsRng;
+use rayon::prelude::*;
 use rlp::PayloadInfo;
 use rustc_hex::FromHex;
 use trie::{Trie, TrieFactory, TrieSpec};
@@ -58,6 +60,7 @@ use client::{
 	ReopenBlock, SealedBlockImporter,
 };
 use client::ancient_import::AncientVerifier;
+use client::log_index::LogIndex;
 use client_traits::{
 	AccountData,
 	BadBlocks,
@@ -83,7 +86,7 @@ use client_traits::{
 use db::{keys::BlockDetails, Readable, Writable};
 use engine::Engine;
 use ethcore_miner::pool::VerifiedTransaction;
-use ethtrie::Layout;
+use ethtrie::{self, Layout};
 use evm::Schedule;
 use executive_state;
 use io::IoChannel;
@@ -102,6 +105,7 @@ use trace::{self, Database as TraceDatabase, ImportRequest as TraceImportRequest
 use trie_vm_factories::{Factories, VmFactory};
 use types::{
 	ancestry_action::AncestryAction,
+	basic_account::BasicAccount,
 	block::PreverifiedBlock,
 	block_status::BlockStatus,
 	blockchain_info::BlockChainInfo,
@@ -109,6 +113,7 @@ use types::{
 	call_analytics::CallAnalytics,
 	chain_notify::{ChainMessageType, ChainRoute, NewBlocks},
 	client_types::{ClientReport, Mode, StateResult},
+	database_stats::{CompactionStatus, DatabaseStats},
 	encoded,
 	engines::{
 		epoch::{PendingTransition, Transition as EpochTransition},
@@ -127,6 +132,8 @@ use types::{
 	pruning_info::PruningInfo,
 	receipt::{LocalizedReceipt, Receipt},
 	snapshot::{Progress, Snapshotting},
+	state_dump::{DumpedAccount, DumpedStorageEntry, StateDumpOptions},
+	state_override::{StateOverride, StorageOverride},
 	trace_filter::Filter as TraceFilter,
 	transaction::{self, Action, CallError, LocalizedTransaction, SignedTransaction, UnverifiedTransaction},
 	verification::{Unverified, VerificationQueueInfo as BlockQueueInfo},
@@ -142,6 +149,16 @@ const MAX_ANCIENT_BLOCKS_QUEUE_SIZE: usize = 4096;
 const MAX_ANCIENT_BLOCKS_TO_IMPORT: usize = 4;
 const MAX_QUEUE_SIZE_TO_SLEEP_ON: usize = 2;
 const MIN_HISTORY_SIZE: u64 = 8;
+// Max number of ancient block bodies pruned per client tick.
+const MAX_ANCIENT_BLOCKS_TO_PRUNE: u64 = 1000;
+// Max number of ancient blocks added to the log index per client tick.
+const MAX_ANCIENT_BLOCKS_TO_INDEX: u64 = 2000;
+// Number of ancient blocks between two reports of log indexing progress.
+const LOG_INDEX_PROGRESS_INTERVAL: u64 = 100_000;
+// Number of blocks read and decoded ahead of the verification queue by `import_blocks`.
+const IMPORT_BATCH_SIZE: usize = 256;
+// How long `import_blocks` waits before retrying when the verification queue is full.
+const IMPORT_QUEUE_FULL_BACKOFF: Duration = Duration::from_millis(20);
 
 struct SleepState {
 	last_activity: Option<Instant>,
@@ -196,6 +213,8 @@ pub struct Client {
 
 	chain: RwLock<Arc<BlockChain>>,
 	tracedb: RwLock<TraceDB<BlockChain>>,
+	/// Index of blocks by log address and first topic, if enabled.
+	log_index: RwLock<Option<LogIndex>>,
 	engine: Arc<dyn Engine>,
 
 	/// Client configuration
@@ -240,6 +259,12 @@ pub struct Client {
 	/// Number of eras kept in a journal before they are pruned
 	history: u64,
 
+	/// Starting block of the most recent snapshot; ancient block pruning never goes past it
+	snapshot_block: RwLock<Option<BlockNumber>>,
+
+	/// Status of the manual database compaction
+	compaction: Arc<Mutex<CompactionStatus>>,
+
 	/// An action to be done if a mode/spec_name change happens
 	on_user_defaults_change: Mutex<Option<Box<dyn FnMut(Option<Mode>) + 'static + Send>>>,
 
@@ -271,8 +296,8 @@ impl Importer {
 			block_queue,
 			miner,
 			ancient_verifier: AncientVerifier::new(engine.clone()),
+			bad_blocks: bad_blocks::BadBlocks::new(engine.params().eip1559_transition),
 			engine,
-			bad_blocks: Default::default(),
 		})
 	}
 
@@ -575,6 +600,11 @@ impl Importer {
 			retracted: route.retracted.len()
 		});
 
+		if let Some(ref log_index) = *client.log_index.read() {
+			log_index.import(&mut batch, &chain, &route, number, hash, &receipts)
+				.expect("Low level database error when updating the log index. Some issue with disk?");
+		}
+
 		let is_canon = route.enacted.last().map_or(false, |h| h == hash);
 		state.sync_cache(&route.enacted, &route.retracted, is_canon);
 		// Final commit to the DB
@@ -625,6 +655,7 @@ impl Importer {
 							last_hashes: client.build_last_hashes(header.parent_hash()),
 							gas_used: U256::default(),
 							gas_limit: u64::max_value().into(),
+							base_fee: None,
 						};
 
 						let call = move |addr, data| {
@@ -719,6 +750,9 @@ impl Client {
 		miner: Arc<Miner>,
 		message_channel: IoChannel<ClientIoMessage<Self>>,
 	) -> Result<Arc<Client>, EthcoreError> {
+		let mut config = config;
+		config.blockchain.eip1559_transition = spec.params().eip1559_transition;
+
 		let trie_spec = match config.fat_db {
 			true => TrieSpec::Fat,
 			false => TrieSpec::Secure,
@@ -744,6 +778,10 @@ impl Client {
 		let gb = spec.genesis_block();
 		let chain = Arc::new(BlockChain::new(config.blockchain.clone(), &gb, db.clone()));
 		let tracedb = RwLock::new(TraceDB::new(config.tracing.clone(), db.clone(), chain.clone()));
+		let log_index = match config.index_logs {
+			true => Some(LogIndex::new(db.clone(), chain.best_block_number(), chain.best_block_hash())?),
+			false => None,
+		};
 
 		trace!("Cleanup journal: DB Earliest = {:?}, Latest = {:?}", state_db.journal_db().earliest_era(), state_db.journal_db().latest_era());
 
@@ -778,6 +816,7 @@ impl Client {
 			mode: Mutex::new(config.mode.clone()),
 			chain: RwLock::new(chain),
 			tracedb,
+			log_index: RwLock::new(log_index),
 			engine,
 			pruning: config.pruning.clone(),
 			db: RwLock::new(db.clone()),
@@ -793,6 +832,8 @@ impl Client {
 			last_hashes: RwLock::new(VecDeque::new()),
 			factories,
 			history,
+			snapshot_block: RwLock::new(None),
+			compaction: Default::default(),
 			on_user_defaults_change: Mutex::new(None),
 			registrar_address,
 			exit_handler: Mutex::new(None),
@@ -912,6 +953,7 @@ impl Client {
 				last_hashes: self.build_last_hashes(&header.parent_hash()),
 				gas_used: U256::default(),
 				gas_limit: header.gas_limit(),
+				base_fee: header.base_fee(self.engine.params().eip1559_transition),
 			}
 		})
 	}
@@ -1111,6 +1153,153 @@ impl Client {
 		self.tracedb.read().collect_garbage();
 	}
 
+	/// Record the starting block of a snapshot. Blocks the snapshot covers are
+	/// kept by ancient block pruning.
+	pub fn note_snapshot_block(&self, number: BlockNumber) {
+		*self.snapshot_block.write() = Some(number);
+	}
+
+	/// Walk the state of block `id` and pass every account selected by `options` to `f`,
+	/// in address hash order. Accounts are read from the state trie one at a time, so memory use
+	/// is bounded by the largest account. Preimages of addresses and storage keys are only known
+	/// to fat databases.
+	///
+	/// Fails with a readable error if the state, or part of it, has been pruned.
+	pub fn dump_state<F>(&self, id: BlockId, options: &StateDumpOptions, mut f: F) -> Result<(), String>
+		where F: FnMut(DumpedAccount) -> Result<(), String>
+	{
+		let number = self.block_number(id).ok_or("Specified block not found")?;
+		let pruned = |e: &dyn ::std::fmt::Display| format!(
+			"State of block #{} is not available ({}). Historical state can only be exported with --pruning archive.",
+			number, e,
+		);
+		let state = self.state_at(id).ok_or_else(|| pruned(&"pruned"))?;
+
+		let (root, db) = state.drop();
+		let db = &db.as_hash_db();
+		let trie = ethtrie::TrieDB::new(db, &root).map_err(|e| pruned(&e))?;
+		let mut iter = trie.iter().map_err(|e| pruned(&e))?;
+		if let Some(from) = options.from {
+			iter.seek(from.as_bytes()).map_err(|e| pruned(&e))?;
+		}
+
+		for item in iter {
+			let (key, value) = item.map_err(|e| pruned(&e))?;
+			let address_hash = H256::from_slice(&key);
+			let account: BasicAccount = ::rlp::decode(&value)
+				.map_err(|e| format!("Invalid account {:?}: {}", address_hash, e))?;
+			if !options.includes_balance(&account.balance) {
+				continue;
+			}
+
+			let address = db.get(&address_hash, EMPTY_PREFIX)
+				.filter(|preimage| preimage.len() == 20 && keccak(&preimage[..]) == address_hash)
+				.map(|preimage| Address::from_slice(&preimage));
+			let account_db = self.factories.accountdb.readonly(*db, address_hash);
+
+			let code = if !options.code {
+				None
+			} else if account.code_hash == KECCAK_EMPTY {
+				Some(Vec::new())
+			} else {
+				let code = account_db.get(&account.code_hash, EMPTY_PREFIX)
+					.ok_or_else(|| pruned(&format!("missing code of account {:?}", address_hash)))?;
+				Some(code.into_vec())
+			};
+
+			let mut storage = Vec::new();
+			if options.storage && account.storage_root != KECCAK_NULL_RLP {
+				let account_db = &&*account_db;
+				let storage_trie = ethtrie::TrieDB::new(account_db, &account.storage_root).map_err(|e| pruned(&e))?;
+				for entry in storage_trie.iter().map_err(|e| pruned(&e))? {
+					let (key, value) = entry.map_err(|e| pruned(&e))?;
+					let key_hash = H256::from_slice(&key);
+					let key = account_db.get(&key_hash, EMPTY_PREFIX)
+						.filter(|preimage| preimage.len() == 32 && keccak(&preimage[..]) == key_hash)
+						.map(|preimage| H256::from_slice(&preimage));
+					let value = ::rlp::decode(&value)
+						.map_err(|e| format!("Invalid storage entry {:?} of account {:?}: {}", key_hash, address_hash, e))?;
+					storage.push(DumpedStorageEntry { key_hash, key, value });
+				}
+			}
+
+			f(DumpedAccount {
+				address_hash,
+				address,
+				balance: account.balance,
+				nonce: account.nonce,
+				code_hash: account.code_hash,
+				code,
+				storage_root: account.storage_root,
+				storage,
+			})?;
+		}
+		Ok(())
+	}
+
+	// Prune bodies, receipts and traces of blocks older than the configured history.
+	fn prune_ancient_blocks(&self) {
+		let history = match self.config.ancient_blocks_history {
+			Some(history) => history,
+			None => return,
+		};
+
+		// same lock order as block import
+		let _import_lock = self.importer.import_lock.lock();
+		let chain = self.chain.read();
+		let mut until = chain.best_block_number().saturating_sub(history);
+		if let Some(snapshot_block) = *self.snapshot_block.read() {
+			let snapshot_blocks = match self.engine.snapshot_mode() {
+				Snapshotting::PoW { blocks, .. } => blocks,
+				_ => 0,
+			};
+			until = cmp::min(until, snapshot_block.saturating_sub(snapshot_blocks));
+		}
+		if chain.earliest_available_block().unwrap_or(1) >= until {
+			return;
+		}
+
+		let mut batch = DBTransaction::new();
+		let pruned = chain.prune_ancient_blocks(&mut batch, until, MAX_ANCIENT_BLOCKS_TO_PRUNE);
+		self.tracedb.read().prune(&mut batch, &pruned);
+		self.db.read().key_value().write(batch).expect("Low level database error when pruning ancient blocks. Some issue with disk?");
+		chain.commit();
+		debug!(target: "client", "Pruned {} ancient blocks, earliest available block is #{:?}", pruned.len(), chain.earliest_available_block());
+	}
+
+	// Add blocks imported before the log index was enabled to the index, newest first.
+	fn index_ancient_logs(&self) {
+		if self.log_index.read().as_ref().map_or(true, LogIndex::is_complete) {
+			return;
+		}
+
+		// same lock order as block import
+		let _import_lock = self.importer.import_lock.lock();
+		let chain = self.chain.read();
+		let log_index = self.log_index.read();
+		let log_index = match *log_index {
+			Some(ref log_index) => log_index,
+			None => return,
+		};
+
+		let before = log_index.lowest();
+		let indexed = log_index.index_ancient(MAX_ANCIENT_BLOCKS_TO_INDEX, |number| {
+			chain.block_hash(number).and_then(|hash| chain.block_receipts(&hash)).map(|r| r.receipts)
+		});
+		match indexed {
+			Ok(0) => trace!(target: "client", "Receipts of block #{} not available, log indexing paused", before - 1),
+			Ok(_) if log_index.is_complete() => info!(target: "client", "Log index complete"),
+			Ok(indexed) => {
+				let lowest = log_index.lowest();
+				debug!(target: "client", "Indexed logs of {} ancient blocks, lowest indexed block is #{}", indexed, lowest);
+				if before / LOG_INDEX_PROGRESS_INTERVAL != lowest / LOG_INDEX_PROGRESS_INTERVAL {
+					info!(target: "client", "Indexing logs of ancient blocks: {} blocks left", lowest);
+				}
+			},
+			Err(e) => warn!(target: "client", "Failed to index logs of ancient blocks: {}", e),
+		}
+	}
+
 	fn check_snooze(&self) {
 		let mode = self.mode.lock().clone();
 		match mode {
@@ -1263,7 +1452,7 @@ impl Client {
 				=> Some(self.chain.read().best_block_header()),
 			BlockId::Number(number) if number == self.chain.read().best_block_number()
 				=> Some(self.chain.read().best_block_header()),
-			_   => self.block_header(id).and_then(|h| h.decode().ok())
+			_   => self.block_header(id).and_then(|h| h.decode(self.engine.params().eip1559_transition).ok())
 		}
 	}
 }
@@ -1277,6 +1466,7 @@ impl DatabaseRestore for Client {
 		let mut state_db = self.state_db.write();
 		let mut chain = self.chain.write();
 		let mut tracedb = self.tracedb.write();
+		let mut log_index = self.log_index.write();
 		self.importer.miner.clear();
 		let db = self.db.write();
 		db.restore(new_db)?;
@@ -1285,6 +1475,9 @@ impl DatabaseRestore for Client {
 		*state_db = StateDB::new(journaldb::new(db.key_value().clone(), self.pruning, ::db::COL_STATE), cache_size);
 		*chain = Arc::new(BlockChain::new(self.config.blockchain.clone(), &[], db.clone()));
 		*tracedb = TraceDB::new(self.config.tracing.clone(), db.clone(), chain.clone());
+		if self.config.index_logs {
+			*log_index = Some(LogIndex::new(db.clone(), chain.best_block_number(), chain.best_block_hash())?);
+		}
 		Ok(())
 	}
 }
@@ -1300,6 +1493,7 @@ impl BlockChainReset for Client {
 		let mut blocks_to_delete = Vec::with_capacity(num as usize);
 		let mut best_block_hash = self.chain.read().best_block_hash();
 		let mut batch = DBTransaction::with_capacity(blocks_to_delete.len());
+		let mut retracted_receipts = Vec::new();
 
 		for _ in 0..num {
 			let current_header = self.chain.read().block_header_data(&best_block_hash)
@@ -1307,6 +1501,10 @@ impl BlockChainReset for Client {
 			best_block_hash = current_header.parent_hash();
 
 			let (number, hash) = (current_header.number(), current_header.hash());
+			if self.log_index.read().is_some() {
+				let receipts = self.chain.read().block_receipts(&hash).map_or_else(Vec::new, |r| r.receipts);
+				retracted_receipts.push((number, receipts));
+			}
 			batch.delete(::db::COL_HEADERS, hash.as_bytes());
 			batch.delete(::db::COL_BODIES, hash.as_bytes());
 			Writable::delete::<BlockDetails, H264>
@@ -1343,6 +1541,13 @@ impl BlockChainReset for Client {
 		// update the new best block hash
 		batch.put(::db::COL_EXTRA, b"best", best_block_hash.as_bytes());
 
+		if let Some(ref log_index) = *self.log_index.read() {
+			let (last_number, _) = blocks_to_delete.last()
+				.expect("num is > 0; blocks_to_delete can't be empty; qed");
+			log_index.update(&mut batch, &retracted_receipts, &[], last_number - 1, best_block_hash)
+				.map_err(|err| format!("could not update the log index; io error occurred: {}", err))?;
+		}
+
 		self.db.read()
 			.key_value()
 			.write(batch)
@@ -1499,6 +1704,8 @@ impl Call for Client {
 			last_hashes: self.build_last_hashes(header.parent_hash()),
 			gas_used: U256::default(),
 			gas_limit: U256::max_value(),
+			// calls are not subject to the base fee
+			base_fee: None,
 		};
 		let machine = self.engine.machine();
 
@@ -1514,6 +1721,8 @@ impl Call for Client {
 			last_hashes: self.build_last_hashes(header.parent_hash()),
 			gas_used: U256::default(),
 			gas_limit: U256::max_value(),
+			// calls are not subject to the base fee
+			base_fee: None,
 		};
 
 		let mut results = Vec::with_capacity(transactions.len());
@@ -1528,6 +1737,40 @@ impl Call for Client {
 		Ok(results)
 	}
 
+	fn apply_state_overrides(&self, state: &mut Self::State, overrides: &StateOverride) -> Result<(), CallError> {
+		let corrupt = |e| {
+			warn!(target: "client", "Failed to apply state overrides: {}", e);
+			CallError::StateCorrupt
+		};
+
+		for (address, account) in overrides {
+			if let Some(balance) = account.balance {
+				state.set_balance(address, balance).map_err(corrupt)?;
+			}
+			if let Some(nonce) = account.nonce {
+				state.set_nonce(address, nonce).map_err(corrupt)?;
+			}
+			if let Some(ref code) = account.code {
+				state.reset_code(address, code.clone()).map_err(corrupt)?;
+			}
+			match account.storage {
+				Some(StorageOverride::Full(ref storage)) => {
+					let code = state.code(address).map_err(corrupt)?.unwrap_or_default();
+					let storage = storage.iter().map(|(key, value)| (*key, *value)).collect();
+					state.patch_account(address, code, storage).map_err(corrupt)?;
+				},
+				Some(StorageOverride::Diff(ref storage)) => {
+					for (key, value) in storage {
+						state.set_storage(address, *key, *value).map_err(corrupt)?;
+					}
+				},
+				None => {},
+			}
+		}
+
+		Ok(())
+	}
+
 	fn estimate_gas(&self, t: &SignedTransaction, state: &Self::State, header: &Header) -> Result<U256, CallError> {
 		let (mut upper, max_upper, env_info) = {
 			let init = *header.gas_limit();
@@ -1541,13 +1784,14 @@ impl Call for Client {
 				last_hashes: self.build_last_hashes(header.parent_hash()),
 				gas_used: U256::default(),
 				gas_limit: max,
+				base_fee: None,
 			};
 
 			(init, max, env_info)
 		};
 
 		let sender = t.sender();
-		let options = || TransactOptions::with_tracing().dont_check_nonce();
+		let options = || TransactOptions::with_tracing().dont_check_nonce().save_output_from_contract();
 
 		let exec = |gas| {
 			let mut tx = t.as_unsigned().clone();
@@ -1567,21 +1811,34 @@ impl Call for Client {
 				.map_or(false, |r| r.exception.is_none())
 		};
 
-		if !cond(upper) {
+		// execute with the block gas limit first: a transaction failing there is reported as is,
+		// rather than as needing more gas than allowed
+		let mut executed = exec(upper);
+		let out_of_gas = executed.as_ref().map_or(true, |r| match r.exception {
+			None | Some(vm::Error::Reverted) => false,
+			Some(_) => true,
+		});
+		if out_of_gas {
 			upper = max_upper;
-			match exec(upper) {
-				Ok(v) => {
-					if let Some(exception) = v.exception {
-						return Err(CallError::Exceptional(exception))
-					}
-				},
-				Err(_e) => {
-					trace!(target: "estimate_gas", "estimate_gas failed with {}", upper);
-					let err = ExecutionError::Internal(format!("Requires higher than upper limit of {}", upper));
-					return Err(err.into())
-				}
+			executed = exec(upper);
+		}
+		let executed = match executed {
+			Ok(executed) => executed,
+			Err(_e) => {
+				trace!(target: "estimate_gas", "estimate_gas failed with {}", upper);
+				let err = ExecutionError::Internal(format!("Requires higher than upper limit of {}", upper));
+				return Err(err.into())
 			}
+		};
+		match executed.exception {
+			None => {},
+			Some(vm::Error::Reverted) => return Err(CallError::Reverted(executed.output)),
+			Some(exception) => return Err(CallError::Exceptional(exception)),
 		}
+
+		// the gas used at the upper limit isn't a lower bound, as a nested call may burn all the gas
+		// it is given and still let the transaction succeed. Every candidate is executed instead, so
+		// the gas withheld from nested calls by the all-but-one-64th rule (EIP-150) is accounted for.
 		let lower = t.gas_required(&self.engine.schedule(env_info.number)).into();
 		if cond(lower) {
 			trace!(target: "estimate_gas", "estimate_gas succeeded with {}", lower);
@@ -1607,7 +1864,7 @@ impl Call for Client {
 			Ok(upper)
 		}
 
-		// binary chop to non-excepting call with gas somewhere between 21000 and block gas limit
+		// binary chop to non-excepting call with gas somewhere between 21000 and the upper limit
 		trace!(target: "estimate_gas", "estimate_gas chopping {} .. {}", lower, upper);
 		binary_chop(lower, upper, cond)
 	}
@@ -1634,6 +1891,30 @@ impl BlockChainClient for Client {
 		Ok(self.replay_block_transactions(block, analytics)?.nth(address.index).expect(PROOF).1)
 	}
 
+	fn replay_struct_logs(&self, id: TransactionId, config: trace::StructLoggerConfig) -> Result<types::engines::machine::Executed<trace::FlatTrace, Vec<trace::StructLog>>, CallError> {
+		let address = self.transaction_address(id).ok_or_else(|| CallError::TransactionNotFound)?;
+		let block = BlockId::Hash(address.block_hash);
+		let mut env_info = self.env_info(block).ok_or_else(|| CallError::StatePruned)?;
+		let body = self.block_body(block).ok_or_else(|| CallError::StatePruned)?;
+		let mut state = self.state_at_beginning(block).ok_or_else(|| CallError::StatePruned)?;
+		let machine = self.engine.machine();
+
+		const PROOF: &'static str = "Transactions fetched from blockchain; blockchain transactions are valid; qed";
+
+		let mut txs = body.transactions().into_iter().map(|t| SignedTransaction::new(t).expect(PROOF));
+		for t in txs.by_ref().take(address.index) {
+			let x = Self::do_virtual_call(machine, &env_info, &mut state, &t, CallAnalytics::default())?;
+			env_info.gas_used = env_info.gas_used + x.gas_used;
+		}
+		let t = txs.next().expect("The transaction address contains a valid index within block; qed");
+
+		let options = TransactOptions::new(trace::NoopTracer, trace::StructLogger::new(config))
+			.dont_check_nonce()
+			.save_output_from_contract();
+		let schedule = machine.schedule(env_info.number);
+		Ok(Executive::new(&mut state, &env_info, machine, &schedule).transact_virtual(&t, options)?)
+	}
+
 	fn replay_block_transactions(&self, block: BlockId, analytics: CallAnalytics) -> Result<Box<dyn Iterator<Item = (H256, Executed)>>, CallError> {
 		let mut env_info = self.env_info(block).ok_or_else(|| CallError::StatePruned)?;
 		let body = self.block_body(block).ok_or_else(|| CallError::StatePruned)?;
@@ -1862,6 +2143,71 @@ impl BlockChainClient for Client {
 		Some(keys)
 	}
 
+	fn list_storage_from_path(&self, id: BlockId, account: &Address, resume_at: Option<&H256>, count: u64) -> Option<StateResult<(Vec<H256>, Option<H256>)>> {
+		if !self.factories.trie.is_fat() {
+			trace!(target: "fatdb", "list_storage_from_path: Not a fat DB");
+			return None;
+		}
+
+		let state = match self.state_at(id) {
+			Some(state) => state,
+			None => return self.block_header(id).map(|_| StateResult::Missing),
+		};
+
+		let root = match state.storage_root(account) {
+			Ok(Some(root)) => root,
+			Ok(None) => return Some(StateResult::Some((Vec::new(), None))),
+			Err(_) => return Some(StateResult::Missing),
+		};
+
+		let (_, db) = state.drop();
+		let account_db = &self.factories.accountdb.readonly(db.as_hash_db(), keccak(account));
+		let account_db = &account_db.as_hash_db();
+		// Walk the storage trie itself rather than the fat DB view, so the paths can be handed out
+		// and the walk resumed without hashing the keys again.
+		let trie = match ethtrie::TrieDB::new(account_db, &root) {
+			Ok(trie) => trie,
+			Err(e) => {
+				trace!(target: "fatdb", "list_storage_from_path: Couldn't open the DB: {:?}", e);
+				return Some(StateResult::Missing);
+			}
+		};
+
+		let mut iter = match trie.iter() {
+			Ok(iter) => iter,
+			Err(_) => return Some(StateResult::Missing),
+		};
+
+		if let Some(path) = resume_at {
+			if let Err(e) = iter.seek(path.as_bytes()) {
+				trace!(target: "fatdb", "list_storage_from_path: Couldn't seek the DB: {:?}", e);
+				return Some(StateResult::Missing);
+			}
+		}
+
+		let mut keys = Vec::new();
+		let mut last = None;
+		for item in iter {
+			let path = match item {
+				Ok((path, _)) => H256::from_slice(&path),
+				Err(_) => return Some(StateResult::Missing),
+			};
+			if Some(&path) == resume_at {
+				continue;
+			}
+			if keys.len() as u64 == count {
+				return Some(StateResult::Some((keys, last)));
+			}
+			match account_db.get(&keccak(&path), EMPTY_PREFIX) {
+				Some(key) => keys.push(H256::from_slice(&key)),
+				None => return Some(StateResult::Missing),
+			}
+			last = Some(path);
+		}
+
+		Some(StateResult::Some((keys, None)))
+	}
+
 	fn transaction(&self, id: TransactionId) -> Option<LocalizedTransaction> {
 		self.transaction_address(id).and_then(|address| self.chain.read().transaction(&address))
 	}
@@ -1878,6 +2224,7 @@ impl BlockChainClient for Client {
 		let hash = address.block_hash;
 		let chain = self.chain.read();
 		let number = chain.block_number(&hash)?;
+		let base_fee = chain.block_header_data(&hash)?.base_fee(self.engine.params().eip1559_transition);
 		let body = chain.block_body(&hash)?;
 		let mut receipts = chain.block_receipts(&hash)?.receipts;
 		receipts.truncate(address.index + 1);
@@ -1887,7 +2234,7 @@ impl BlockChainClient for Client {
 		let gas_used = receipts.last().map_or_else(|| 0.into(), |r| r.gas_used);
 		let no_of_logs = receipts.into_iter().map(|receipt| receipt.logs.len()).sum::<usize>();
 
-		let receipt = transaction_receipt(transaction, receipt, gas_used, no_of_logs);
+		let receipt = transaction_receipt(transaction, receipt, base_fee, gas_used, no_of_logs);
 		Some(receipt)
 	}
 
@@ -1897,6 +2244,7 @@ impl BlockChainClient for Client {
 		let chain = self.chain.read();
 		let receipts = chain.block_receipts(&hash)?;
 		let number = chain.block_number(&hash)?;
+		let base_fee = chain.block_header_data(&hash)?.base_fee(self.engine.params().eip1559_transition);
 		let body = chain.block_body(&hash)?;
 
 		let mut gas_used = 0.into();
@@ -1908,7 +2256,7 @@ impl BlockChainClient for Client {
 			.into_iter()
 			.zip(receipts.receipts)
 			.map(move |(transaction, receipt)| {
-				let result = transaction_receipt(transaction, receipt, gas_used, no_of_logs);
+				let result = transaction_receipt(transaction, receipt, base_fee, gas_used, no_of_logs);
 				gas_used = result.cumulative_gas_used;
 				no_of_logs += result.logs.len();
 				result
@@ -1981,8 +2329,25 @@ impl BlockChainClient for Client {
 				return Err(filter.to_block.clone());
 			}
 
-			chain.blocks_with_bloom(&filter.bloom_possibilities(), from, to)
-				.into_iter()
+			// The log index, when enabled, covers the address and first topic of the filter.
+			// Blocks it doesn't cover yet are found by their blooms.
+			let indexed = self.log_index.read().as_ref()
+				.and_then(|log_index| log_index.blocks(&filter, from, to).unwrap_or_else(|e| {
+					warn!(target: "client", "Failed to read the log index: {}", e);
+					None
+				}));
+			let numbers = match indexed {
+				Some(indexed) => {
+					let mut numbers = indexed.unindexed.map_or_else(Vec::new, |(from, to)| {
+						chain.blocks_with_bloom(&filter.bloom_possibilities(), from, to)
+					});
+					numbers.extend(indexed.numbers);
+					numbers
+				},
+				None => chain.blocks_with_bloom(&filter.bloom_possibilities(), from, to),
+			};
+
+			numbers.into_iter()
 				.filter_map(|n| chain.block_hash(n))
 				.collect::<Vec<H256>>()
 		} else {
@@ -2055,12 +2420,11 @@ impl BlockChainClient for Client {
 			to_address: filter.to_address.into(),
 		};
 
-		let traces = self.tracedb.read()
-			.filter(&db_filter)
-			.into_iter()
-			.skip(filter.after.unwrap_or(0))
-			.take(filter.count.unwrap_or(usize::max_value()))
-			.collect();
+		let traces = self.tracedb.read().filter_limited(
+			&db_filter,
+			filter.after.unwrap_or(0),
+			filter.count.unwrap_or(usize::max_value()),
+		);
 		Some(traces)
 	}
 
@@ -2136,19 +2500,69 @@ impl BlockChainClient for Client {
 	fn uncle_extra_info(&self, id: UncleId) -> Option<BTreeMap<String, String>> {
 		self.uncle(id)
 			.and_then(|h| {
-				h.decode().map(|dh| {
+				h.decode(self.engine.params().eip1559_transition).map(|dh| {
 					self.engine.extra_info(&dh)
 				}).ok()
 			})
 	}
 
 	fn pruning_info(&self) -> PruningInfo {
+		let chain = self.chain.read();
+		let earliest_body = chain.earliest_available_block();
 		PruningInfo {
-			earliest_chain: self.chain.read().first_block_number().unwrap_or(1),
+			earliest_chain: cmp::max(chain.first_block_number().unwrap_or(1), earliest_body.unwrap_or(1)),
 			earliest_state: self.state_db.read().journal_db().earliest_era().unwrap_or(0),
+			earliest_body,
+		}
+	}
+
+	fn database_stats(&self) -> DatabaseStats {
+		DatabaseStats {
+			columns: self.db.read().column_stats(),
+			compaction: self.compaction.lock().clone(),
 		}
 	}
 
+	fn compact_database(&self, column: Option<u32>) -> Result<(), String> {
+		let num_columns = ::db::NUM_COLUMNS.expect("client database is always opened with columns; qed");
+		let columns: Vec<u32> = match column {
+			Some(col) if col < num_columns => vec![col],
+			Some(col) => return Err(format!("Unknown database column {}", col)),
+			None => (0..num_columns).collect(),
+		};
+		let total = columns.len() as u32;
+
+		let mut status = self.compaction.lock();
+		if status.is_ongoing() {
+			return Err("A database compaction is already running".into());
+		}
+		*status = CompactionStatus::Ongoing { column: columns[0], done: 0, total };
+
+		let db = self.db.read().clone();
+		let compaction = self.compaction.clone();
+		let spawned = thread::Builder::new()
+			.name("db-compaction".into())
+			.spawn(move || {
+				for (done, col) in columns.into_iter().enumerate() {
+					*compaction.lock() = CompactionStatus::Ongoing { column: col, done: done as u32, total };
+					info!(target: "client", "Compacting database column {}", col);
+					if let Err(e) = db.compact_column(col) {
+						warn!(target: "client", "Database compaction of column {} failed: {}", col, e);
+						*compaction.lock() = CompactionStatus::Failed(e.to_string());
+						return;
+					}
+				}
+				info!(target: "client", "Database compaction finished");
+				*compaction.lock() = CompactionStatus::Inactive;
+			});
+
+		if let Err(e) = spawned {
+			*status = CompactionStatus::Failed(e.to_string());
+			return Err(format!("Unable to spawn the compaction thread: {}", e));
+		}
+		Ok(())
+	}
+
 	fn transact_contract(&self, address: Address, data: Bytes) -> Result<(), transaction::Error> {
 		let authoring_params = self.importer.miner.authoring_params();
 		let service_transaction_checker = self.importer.miner.service_transaction_checker();
@@ -2278,6 +2692,8 @@ impl Tick for Client {
 	// TODO: manage by real events.
 	fn tick(&self, prevent_sleep: bool) {
 		self.check_garbage();
+		self.prune_ancient_blocks();
+		self.index_ancient_logs();
 		if !prevent_sleep {
 			self.check_snooze();
 		}
@@ -2300,7 +2716,7 @@ impl ReopenBlock for Client {
 			for h in uncles {
 				if !block.uncles.iter().any(|header| header.hash() == h) {
 					let uncle = chain.block_header_data(&h).expect("find_uncle_hashes only returns hashes for existing headers; qed");
-					let uncle = uncle.decode().expect("decoding failure");
+					let uncle = uncle.decode(engine.params().eip1559_transition).expect("decoding failure");
 					block.push_uncle(uncle).expect("pushing up to maximum_uncle_count;
 												push_uncle is not ok only if more than maximum_uncle_count is pushed;
 												so all push_uncle are Ok;
@@ -2342,7 +2758,7 @@ impl PrepareOpenBlock for Client {
 			.into_iter()
 			.take(engine.maximum_uncle_count(open_block.header.number()))
 			.foreach(|h| {
-				open_block.push_uncle(h.decode().expect("decoding failure")).expect("pushing maximum_uncle_count;
+				open_block.push_uncle(h.decode(engine.params().eip1559_transition).expect("decoding failure")).expect("pushing maximum_uncle_count;
 												open_block was just created;
 												push_uncle is not ok only if more than maximum_uncle_count is pushed;
 												so all push_uncle are Ok;
@@ -2559,8 +2975,13 @@ impl SnapshotClient for Client {
 				None => return Err(SnapshotError::InvalidStartingBlock(at).into()),
 			},
 		};
+		if let Some(start_number) = self.block_number(BlockId::Hash(start_hash)) {
+			self.note_snapshot_block(start_number);
+		}
 
 		let processing_threads = self.config.snapshot.processing_threads;
+		let io_budget = self.config.snapshot.io_budget;
+		let compression = self.config.snapshot.compression;
 		let chunker = snapshot::chunker(self.engine.snapshot_mode()).ok_or_else(|| SnapshotError::SnapshotsUnsupported)?;
 		snapshot::take_snapshot(
 			chunker,
@@ -2570,6 +2991,8 @@ impl SnapshotClient for Client {
 			writer,
 			p,
 			processing_threads,
+			io_budget,
+			compression,
 		)?;
 		Ok(())
 	}
@@ -2627,47 +3050,32 @@ impl ImportExportBlocks for Client {
 			}
 		};
 
-		let do_import = |bytes: Vec<u8>| {
-			let block = Unverified::from_rlp(bytes).map_err(|_| "Invalid block rlp")?;
-			let number = block.header.number();
-			while self.queue_info().is_full() { std::thread::sleep(Duration::from_secs(1)); }
-			match self.import_block(block) {
-				Err(EthcoreError::Import(ImportError::AlreadyInChain)) => {
-					trace!("Skipping block #{}: already in chain.", number);
-				}
-				Err(e) => {
-					return Err(format!("Cannot import block #{}: {:?}", number, e));
-				},
-				Ok(_) => {},
-			}
-			Ok(())
-		};
-
-		match format {
-			DataFormat::Binary => {
-				loop {
-					let (mut bytes, n) = if first_read > 0 {
-						(first_bytes.clone(), first_read)
-					} else {
-						let mut bytes = vec![0; READAHEAD_BYTES];
-						let n = source.read(&mut bytes)
-							.map_err(|err| format!("Error reading from the file/stream: {:?}", err))?;
-						(bytes, n)
-					};
-					if n == 0 { break; }
-					first_read = 0;
-					let s = PayloadInfo::from(&bytes)
-						.map_err(|e| format!("Invalid RLP in the file/stream: {:?}", e))?.total();
-					bytes.resize(s, 0);
-					source.read_exact(&mut bytes[n..])
+		let mut next_block: Box<dyn FnMut() -> Result<Option<Bytes>, String> + 'a> = match format {
+			DataFormat::Binary => Box::new(move || {
+				let (mut bytes, n) = if first_read > 0 {
+					(first_bytes.clone(), first_read)
+				} else {
+					let mut bytes = vec![0; READAHEAD_BYTES];
+					let n = source.read(&mut bytes)
 						.map_err(|err| format!("Error reading from the file/stream: {:?}", err))?;
-					do_import(bytes)?;
-				}
-			}
+					(bytes, n)
+				};
+				if n == 0 { return Ok(None); }
+				first_read = 0;
+				let s = PayloadInfo::from(&bytes)
+					.map_err(|e| format!("Invalid RLP in the file/stream: {:?}", e))?.total();
+				bytes.resize(s, 0);
+				source.read_exact(&mut bytes[n..])
+					.map_err(|err| format!("Error reading from the file/stream: {:?}", err))?;
+				Ok(Some(bytes))
+			}),
 			DataFormat::Hex => {
-				for line in BufReader::new(source).lines() {
-					let s = line
-						.map_err(|err| format!("Error reading from the file/stream: {:?}", err))?;
+				let mut lines = BufReader::new(source).lines();
+				Box::new(move || {
+					let s = match lines.next() {
+						Some(line) => line.map_err(|err| format!("Error reading from the file/stream: {:?}", err))?,
+						None => return Ok(None),
+					};
 					let s = if first_read > 0 {
 						from_utf8(&first_bytes)
 							.map_err(|err| format!("Invalid UTF-8: {:?}", err))?
@@ -2676,12 +3084,56 @@ impl ImportExportBlocks for Client {
 						s
 					};
 					first_read = 0;
-					let bytes = s.from_hex()
-						.map_err(|err| format!("Invalid hex in file/stream: {:?}", err))?;
-					do_import(bytes)?;
+					s.from_hex()
+						.map(Some)
+						.map_err(|err| format!("Invalid hex in file/stream: {:?}", err))
+				})
+			}
+		};
+
+		let eip1559_transition = self.engine.params().eip1559_transition;
+		let decode = |batch: Vec<Bytes>| -> Result<Vec<Unverified>, String> {
+			batch.into_par_iter()
+				.map(|bytes| Unverified::from_rlp(bytes, eip1559_transition).map_err(|_| "Invalid block rlp".to_owned()))
+				.collect()
+		};
+
+		let enqueue = |blocks: Vec<Unverified>| -> Result<(), String> {
+			for block in blocks {
+				let number = block.header.number();
+				while self.queue_info().is_full() { thread::sleep(IMPORT_QUEUE_FULL_BACKOFF); }
+				match self.import_block(block) {
+					Err(EthcoreError::Import(ImportError::AlreadyInChain)) => {
+						trace!("Skipping block #{}: already in chain.", number);
+					}
+					Err(e) => {
+						return Err(format!("Cannot import block #{}: {:?}", number, e));
+					},
+					Ok(_) => {},
 				}
 			}
+			Ok(())
 		};
+
+		// Blocks are read in batches; each batch is decoded on the rayon pool while the
+		// previous one is handed to the verification queue, which recovers the transaction
+		// signatures in parallel ahead of execution.
+		let mut decoded = Vec::new();
+		loop {
+			let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+			while batch.len() < IMPORT_BATCH_SIZE {
+				match next_block()? {
+					Some(bytes) => batch.push(bytes),
+					None => break,
+				}
+			}
+			if batch.is_empty() && decoded.is_empty() { break; }
+
+			let current = std::mem::replace(&mut decoded, Vec::new());
+			let (next, imported) = rayon::join(|| decode(batch), || enqueue(current));
+			imported?;
+			decoded = next?;
+		}
 		self.flush_queue();
 		Ok(())
 	}
@@ -2692,6 +3144,7 @@ impl ImportExportBlocks for Client {
 fn transaction_receipt(
 	mut tx: LocalizedTransaction,
 	receipt: Receipt,
+	base_fee: Option<U256>,
 	prior_gas_used: U256,
 	prior_no_of_logs: usize,
 ) -> LocalizedReceipt {
@@ -2713,6 +3166,7 @@ fn transaction_receipt(
 		block_number: block_number,
 		cumulative_gas_used: receipt.gas_used,
 		gas_used: receipt.gas_used - prior_gas_used,
+		effective_gas_price: tx.effective_gas_price(base_fee),
 		contract_address: match tx.action {
 			Action::Call(_) => None,
 			Action::Create => Some(contract_address(CreateContractAddress::FromSenderAndNonce, &sender, &tx.nonce, &tx.data).0)
@@ -2890,7 +3344,7 @@ mod tests {
 		};
 
 		// when
-		let receipt = transaction_receipt(transaction, receipt, 5.into(), 1);
+		let receipt = transaction_receipt(transaction, receipt, None, 5.into(), 1);
 
 		// then
 		assert_eq!(receipt, LocalizedReceipt {
@@ -2905,6 +3359,7 @@ mod tests {
 			block_number: block_number,
 			cumulative_gas_used: gas_used,
 			gas_used: gas_used - 5,
+			effective_gas_price: 0.into(),
 			contract_address: None,
 			logs: vec![LocalizedLogEntry {
 				entry: logs[0].clone(),
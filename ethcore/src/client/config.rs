@@ -90,6 +90,10 @@ pub struct ClientConfig {
 	pub history: u64,
 	/// Ideal memory usage for state pruning history.
 	pub history_mem: usize,
+	/// Number of recent blocks whose bodies, receipts and traces are kept. `None` keeps all blocks.
+	pub ancient_blocks_history: Option<u64>,
+	/// Maintain an index of blocks by log address and first topic.
+	pub index_logs: bool,
 	/// Check seal valididity on block import
 	pub check_seal: bool,
 	/// Maximal number of transactions queued for verification in a separate thread.
@@ -120,6 +124,8 @@ impl Default for ClientConfig {
 			jump_table_size: 1 * mb,
 			history: 64,
 			history_mem: 32 * mb,
+			ancient_blocks_history: None,
+			index_logs: false,
 			check_seal: true,
 			transaction_verification_queue_size: 8192,
 			max_round_blocks_to_import: 12,
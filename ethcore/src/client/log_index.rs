@@ -0,0 +1,440 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Index of canonical blocks by the address and first topic of their logs.
+//!
+//! Block numbers are grouped into buckets of `BUCKET_SIZE`. For every bucket, the index keeps a
+//! bitmap of the blocks having a log with a given address, a given first topic, or both, so a
+//! log query reads a single bitmap per bucket instead of the bloom of every block.
+//!
+//! Blocks are added when they become canonical and removed when they are retracted. Blocks
+//! imported before the index was enabled are added from the newest to the oldest one by
+//! `index_ancient`; every canonical block from `lowest` up to the best block is indexed.
+
+use std::cmp;
+use std::collections::HashMap;
+use std::io;
+use std::sync::Arc;
+
+use blockchain::{BlockChain, BlockChainDB, BlockProvider};
+use db::COL_LOG_INDEX;
+use ethereum_types::{Address, H256};
+use kvdb::{DBTransaction, KeyValueDB};
+use parking_lot::RwLock;
+use rlp::{DecoderError, Rlp, RlpStream};
+use types::{
+	BlockNumber,
+	filter::Filter,
+	import_route::ImportRoute,
+	receipt::Receipt,
+};
+
+/// Number of consecutive blocks sharing a bitmap.
+const BUCKET_SIZE: u64 = 4096;
+const BITMAP_BYTES: usize = BUCKET_SIZE as usize / 8;
+const META_KEY: &[u8] = b"meta";
+
+// Indexing progress, stored under `META_KEY`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Meta {
+	// Lowest indexed block.
+	lowest: BlockNumber,
+	// Best block the index is consistent with.
+	head: BlockNumber,
+	head_hash: H256,
+}
+
+impl Meta {
+	fn read(db: &dyn KeyValueDB) -> io::Result<Option<Meta>> {
+		let value = match db.get(COL_LOG_INDEX, META_KEY)? {
+			Some(value) => value,
+			None => return Ok(None),
+		};
+		let rlp = Rlp::new(&value);
+		let decode = || -> Result<Meta, DecoderError> {
+			Ok(Meta { lowest: rlp.val_at(0)?, head: rlp.val_at(1)?, head_hash: rlp.val_at(2)? })
+		};
+		decode().map(Some).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Corrupted log index: {}", e)))
+	}
+
+	fn write(&self, batch: &mut DBTransaction) {
+		let mut stream = RlpStream::new_list(3);
+		stream.append(&self.lowest).append(&self.head).append(&self.head_hash);
+		batch.put(COL_LOG_INDEX, META_KEY, &stream.out());
+	}
+}
+
+// Bitmap key. `None` matches any address or topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Key {
+	address: Option<Address>,
+	topic: Option<H256>,
+	bucket: u64,
+}
+
+impl Key {
+	fn to_bytes(&self) -> [u8; 61] {
+		let mut key = [0u8; 61];
+		key[0] = self.address.is_some() as u8 | (self.topic.is_some() as u8) << 1;
+		if let Some(ref address) = self.address {
+			key[1..21].copy_from_slice(address.as_bytes());
+		}
+		if let Some(ref topic) = self.topic {
+			key[21..53].copy_from_slice(topic.as_bytes());
+		}
+		key[53..].copy_from_slice(&self.bucket.to_be_bytes());
+		key
+	}
+}
+
+// Bitmaps modified by a single update, written to the database at once.
+struct Changes<'a> {
+	db: &'a dyn KeyValueDB,
+	bitmaps: HashMap<Key, Vec<u8>>,
+}
+
+impl<'a> Changes<'a> {
+	fn new(db: &'a dyn KeyValueDB) -> Self {
+		Changes { db, bitmaps: HashMap::new() }
+	}
+
+	// Set or clear the bit of block `number` in every bitmap its logs belong to.
+	fn mark(&mut self, number: BlockNumber, receipts: &[Receipt], present: bool) -> io::Result<()> {
+		let bucket = number / BUCKET_SIZE;
+		let bit = (number % BUCKET_SIZE) as usize;
+		for log in receipts.iter().flat_map(|r| r.logs.iter()) {
+			let topic = log.topics.first().cloned();
+			let mut keys = vec![Key { address: Some(log.address), topic: None, bucket }];
+			if topic.is_some() {
+				keys.push(Key { address: Some(log.address), topic, bucket });
+				keys.push(Key { address: None, topic, bucket });
+			}
+			for key in keys {
+				if !self.bitmaps.contains_key(&key) {
+					let bitmap = match self.db.get(COL_LOG_INDEX, &key.to_bytes())? {
+						Some(value) => value.into_vec(),
+						None => vec![0; BITMAP_BYTES],
+					};
+					self.bitmaps.insert(key, bitmap);
+				}
+				let bitmap = self.bitmaps.get_mut(&key).expect("bitmap inserted above; qed");
+				if present {
+					bitmap[bit / 8] |= 1 << (bit % 8);
+				} else {
+					bitmap[bit / 8] &= !(1 << (bit % 8));
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn write(self, batch: &mut DBTransaction) {
+		for (key, bitmap) in self.bitmaps {
+			if bitmap.iter().all(|b| *b == 0) {
+				batch.delete(COL_LOG_INDEX, &key.to_bytes());
+			} else {
+				batch.put(COL_LOG_INDEX, &key.to_bytes(), &bitmap);
+			}
+		}
+	}
+}
+
+/// Blocks possibly matching a filter according to the index.
+#[derive(Debug, PartialEq)]
+pub struct IndexedBlocks {
+	/// Indexed blocks with matching logs, in ascending order.
+	pub numbers: Vec<BlockNumber>,
+	/// Range of the filtered blocks which are not indexed yet, below all `numbers`.
+	pub unindexed: Option<(BlockNumber, BlockNumber)>,
+}
+
+/// Index of blocks by log address and first topic.
+pub struct LogIndex {
+	db: Arc<dyn BlockChainDB>,
+	meta: RwLock<Meta>,
+}
+
+impl LogIndex {
+	/// Open the index of a chain with the given best block. An index left behind by a run
+	/// without indexing doesn't match the chain anymore; it is cleared and rebuilt.
+	pub fn new(db: Arc<dyn BlockChainDB>, best: BlockNumber, best_hash: H256) -> io::Result<Self> {
+		let meta = match Meta::read(&**db.key_value())? {
+			Some(meta) if meta.head == best && meta.head_hash == best_hash => meta,
+			existing => {
+				let mut batch = DBTransaction::new();
+				if existing.is_some() {
+					info!(target: "client", "Log index is out of date, rebuilding");
+					for (key, _) in db.key_value().iter(COL_LOG_INDEX) {
+						batch.delete(COL_LOG_INDEX, &key);
+					}
+				}
+				let meta = Meta { lowest: best + 1, head: best, head_hash: best_hash };
+				meta.write(&mut batch);
+				db.key_value().write(batch)?;
+				meta
+			},
+		};
+
+		Ok(LogIndex { db, meta: RwLock::new(meta) })
+	}
+
+	/// Lowest indexed block.
+	pub fn lowest(&self) -> BlockNumber {
+		self.meta.read().lowest
+	}
+
+	/// Whether all blocks down to the genesis are indexed.
+	pub fn is_complete(&self) -> bool {
+		self.lowest() == 0
+	}
+
+	/// Update the index with a newly imported block. `receipts` are the receipts of the
+	/// imported block, which isn't committed to the chain yet.
+	pub fn import(&self, batch: &mut DBTransaction, chain: &BlockChain, route: &ImportRoute, number: BlockNumber, hash: &H256, receipts: &[Receipt]) -> io::Result<()> {
+		if route.enacted.is_empty() {
+			return Ok(());
+		}
+
+		let known = |h: &H256| -> io::Result<(BlockNumber, Vec<Receipt>)> {
+			match (chain.block_number(h), chain.block_receipts(h)) {
+				(Some(number), Some(receipts)) => Ok((number, receipts.receipts)),
+				_ => Err(io::Error::new(io::ErrorKind::NotFound, format!("Missing receipts of block {:?}", h))),
+			}
+		};
+		let retracted = route.retracted.iter().map(|h| known(h)).collect::<io::Result<Vec<_>>>()?;
+		let enacted = route.enacted.iter()
+			.map(|h| if h == hash { Ok((number, receipts.to_vec())) } else { known(h) })
+			.collect::<io::Result<Vec<_>>>()?;
+		self.update(batch, &retracted, &enacted, number, *hash)
+	}
+
+	/// Remove the retracted blocks from the index and add the enacted ones, leaving
+	/// the index consistent with the best block `head`.
+	pub fn update(
+		&self,
+		batch: &mut DBTransaction,
+		retracted: &[(BlockNumber, Vec<Receipt>)],
+		enacted: &[(BlockNumber, Vec<Receipt>)],
+		head: BlockNumber,
+		head_hash: H256,
+	) -> io::Result<()> {
+		let mut meta = self.meta.write();
+		let mut changes = Changes::new(&**self.db.key_value());
+		for (number, receipts) in retracted {
+			changes.mark(*number, receipts, false)?;
+		}
+		for (number, receipts) in enacted {
+			changes.mark(*number, receipts, true)?;
+		}
+		changes.write(batch);
+
+		meta.head = head;
+		meta.head_hash = head_hash;
+		meta.lowest = cmp::min(meta.lowest, head + 1);
+		meta.write(batch);
+		Ok(())
+	}
+
+	/// Index up to `max_blocks` canonical blocks below the lowest indexed one, stopping at the
+	/// first block whose receipts aren't available. Progress is persisted, so indexing resumes
+	/// where it stopped after a restart. Returns the number of blocks indexed.
+	pub fn index_ancient<F>(&self, max_blocks: u64, receipts: F) -> io::Result<u64>
+		where F: Fn(BlockNumber) -> Option<Vec<Receipt>>
+	{
+		let mut meta = self.meta.write();
+		let mut lowest = meta.lowest;
+		let mut changes = Changes::new(&**self.db.key_value());
+		while lowest > 0 && meta.lowest - lowest < max_blocks {
+			// the genesis block has no transactions
+			if lowest > 1 {
+				match receipts(lowest - 1) {
+					Some(receipts) => changes.mark(lowest - 1, &receipts, true)?,
+					None => break,
+				}
+			}
+			lowest -= 1;
+		}
+
+		let indexed = meta.lowest - lowest;
+		if indexed > 0 {
+			let mut batch = DBTransaction::new();
+			changes.write(&mut batch);
+			let updated = Meta { lowest, ..*meta };
+			updated.write(&mut batch);
+			self.db.key_value().write(batch)?;
+			*meta = updated;
+		}
+		Ok(indexed)
+	}
+
+	/// Blocks between `from` and `to` which may have logs matching the address and first topic
+	/// of the filter. Returns `None` if the filter constrains neither.
+	pub fn blocks(&self, filter: &Filter, from: BlockNumber, to: BlockNumber) -> io::Result<Option<IndexedBlocks>> {
+		let addresses: Vec<Option<Address>> = match filter.address {
+			Some(ref addresses) => addresses.iter().cloned().map(Some).collect(),
+			None => vec![None],
+		};
+		let topics: Vec<Option<H256>> = match filter.topics.first() {
+			Some(&Some(ref topics)) => topics.iter().cloned().map(Some).collect(),
+			_ => vec![None],
+		};
+		if addresses == [None] && topics == [None] {
+			return Ok(None);
+		}
+
+		let lowest = self.lowest();
+		let unindexed = if from < lowest {
+			Some((from, cmp::min(to, lowest - 1)))
+		} else {
+			None
+		};
+
+		let start = cmp::max(from, lowest);
+		let mut numbers = Vec::new();
+		if start <= to {
+			for bucket in start / BUCKET_SIZE..=to / BUCKET_SIZE {
+				let mut bitmap = vec![0u8; BITMAP_BYTES];
+				for address in &addresses {
+					for topic in &topics {
+						let key = Key { address: *address, topic: *topic, bucket };
+						if let Some(value) = self.db.key_value().get(COL_LOG_INDEX, &key.to_bytes())? {
+							bitmap.iter_mut().zip(value.iter()).for_each(|(b, v)| *b |= *v);
+						}
+					}
+				}
+				let first = cmp::max(start, bucket * BUCKET_SIZE);
+				let last = cmp::min(to, bucket * BUCKET_SIZE + BUCKET_SIZE - 1);
+				numbers.extend((first..=last).filter(|n| {
+					let bit = (n % BUCKET_SIZE) as usize;
+					bitmap[bit / 8] & (1 << (bit % 8)) != 0
+				}));
+			}
+		}
+
+		Ok(Some(IndexedBlocks { numbers, unindexed }))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ethereum_types::{Address, H256};
+	use kvdb::DBTransaction;
+	use test_helpers::new_db;
+	use types::{
+		filter::Filter,
+		ids::BlockId,
+		log_entry::LogEntry,
+		receipt::{Receipt, TransactionOutcome},
+	};
+
+	use super::{IndexedBlocks, LogIndex, BUCKET_SIZE};
+
+	fn receipts(address: u64, topic: Option<u64>) -> Vec<Receipt> {
+		let log = LogEntry {
+			address: Address::from_low_u64_be(address),
+			topics: topic.into_iter().map(H256::from_low_u64_be).collect(),
+			data: Vec::new(),
+		};
+		vec![Receipt::new(TransactionOutcome::Unknown, 0.into(), vec![log])]
+	}
+
+	fn filter(address: Option<u64>, topic: Option<u64>) -> Filter {
+		Filter {
+			from_block: BlockId::Earliest,
+			to_block: BlockId::Latest,
+			address: address.map(|a| vec![Address::from_low_u64_be(a)]),
+			topics: vec![topic.map(|t| vec![H256::from_low_u64_be(t)])],
+			limit: None,
+		}
+	}
+
+	fn numbers(index: &LogIndex, filter: &Filter, from: u64, to: u64) -> Vec<u64> {
+		index.blocks(filter, from, to).unwrap().unwrap().numbers
+	}
+
+	fn update(index: &LogIndex, retracted: &[(u64, Vec<Receipt>)], enacted: &[(u64, Vec<Receipt>)], head: u64) {
+		let mut batch = DBTransaction::new();
+		index.update(&mut batch, retracted, enacted, head, H256::from_low_u64_be(head)).unwrap();
+		index.db.key_value().write(batch).unwrap();
+	}
+
+	#[test]
+	fn finds_blocks_by_address_and_topic() {
+		let index = LogIndex::new(new_db(), 0, H256::zero()).unwrap();
+		update(&index, &[], &[(1, receipts(1, Some(10))), (2, receipts(2, Some(10)))], 2);
+		update(&index, &[], &[(BUCKET_SIZE + 3, receipts(1, None))], BUCKET_SIZE + 3);
+
+		let to = BUCKET_SIZE + 3;
+		assert_eq!(numbers(&index, &filter(Some(1), None), 0, to), vec![1, BUCKET_SIZE + 3]);
+		assert_eq!(numbers(&index, &filter(Some(1), Some(10)), 0, to), vec![1]);
+		assert_eq!(numbers(&index, &filter(None, Some(10)), 0, to), vec![1, 2]);
+		assert_eq!(numbers(&index, &filter(None, Some(10)), 2, to), vec![2]);
+		assert_eq!(numbers(&index, &filter(Some(3), None), 0, to), Vec::<u64>::new());
+		assert_eq!(index.blocks(&filter(None, None), 0, to).unwrap(), None);
+	}
+
+	#[test]
+	fn reorg_replaces_retracted_blocks() {
+		let index = LogIndex::new(new_db(), 0, H256::zero()).unwrap();
+		update(&index, &[], &[(1, receipts(1, Some(10))), (2, receipts(1, Some(11)))], 2);
+
+		// blocks 2 and 3 of a heavier fork replace block 2
+		update(&index, &[(2, receipts(1, Some(11)))], &[(2, receipts(2, Some(12))), (3, receipts(1, Some(12)))], 3);
+		assert_eq!(numbers(&index, &filter(None, Some(11)), 0, 3), Vec::<u64>::new());
+		assert_eq!(numbers(&index, &filter(None, Some(12)), 0, 3), vec![2, 3]);
+		assert_eq!(numbers(&index, &filter(Some(1), None), 0, 3), vec![1, 3]);
+		assert_eq!(numbers(&index, &filter(Some(2), Some(12)), 0, 3), vec![2]);
+
+		// reorg back to a shorter fork
+		update(&index, &[(3, receipts(1, Some(12))), (2, receipts(2, Some(12)))], &[(2, receipts(1, Some(11)))], 2);
+		assert_eq!(numbers(&index, &filter(None, Some(12)), 0, 3), Vec::<u64>::new());
+		assert_eq!(numbers(&index, &filter(Some(1), None), 0, 3), vec![1, 2]);
+	}
+
+	#[test]
+	fn indexes_ancient_blocks_and_resumes() {
+		let db = new_db();
+		let index = LogIndex::new(db.clone(), 10, H256::from_low_u64_be(10)).unwrap();
+		assert_eq!(index.blocks(&filter(Some(1), None), 0, 10).unwrap(), Some(IndexedBlocks {
+			numbers: Vec::new(),
+			unindexed: Some((0, 10)),
+		}));
+
+		let ancient = |number| if number >= 4 { Some(receipts(number, None)) } else { None };
+		assert_eq!(index.index_ancient(3, ancient).unwrap(), 3);
+		assert_eq!(index.lowest(), 8);
+		assert_eq!(index.blocks(&filter(Some(9), None), 0, 10).unwrap(), Some(IndexedBlocks {
+			numbers: vec![9],
+			unindexed: Some((0, 7)),
+		}));
+
+		// progress survives a restart, indexing stops at missing receipts
+		let index = LogIndex::new(db.clone(), 10, H256::from_low_u64_be(10)).unwrap();
+		assert_eq!(index.lowest(), 8);
+		assert_eq!(index.index_ancient(100, ancient).unwrap(), 4);
+		assert_eq!(index.lowest(), 4);
+
+		let ancient = |number| Some(receipts(number, None));
+		assert_eq!(index.index_ancient(100, ancient).unwrap(), 4);
+		assert!(index.is_complete());
+		assert_eq!(numbers(&index, &filter(Some(2), None), 0, 10), vec![2]);
+
+		// the chain moved on without the index
+		let index = LogIndex::new(db, 12, H256::from_low_u64_be(12)).unwrap();
+		assert_eq!(index.lowest(), 13);
+		assert_eq!(index.blocks(&filter(Some(2), None), 0, 12).unwrap().unwrap().numbers, Vec::<u64>::new());
+	}
+}
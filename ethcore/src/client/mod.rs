@@ -20,6 +20,7 @@ mod ancient_import;
 mod bad_blocks;
 mod client;
 mod config;
+mod log_index;
 mod traits;
 
 pub use self::client::Client;
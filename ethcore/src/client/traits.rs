@@ -19,6 +19,7 @@ use ethereum_types::{H256, U256, Address};
 use types::{
 	transaction::{SignedTransaction, CallError},
 	call_analytics::CallAnalytics,
+	state_override::StateOverride,
 	errors::EthcoreError as Error,
 	errors::EthcoreResult,
 	header::Header,
@@ -43,6 +44,10 @@ pub trait Call {
 
 	/// Estimates how much gas will be necessary for a call.
 	fn estimate_gas(&self, t: &SignedTransaction, state: &Self::State, header: &Header) -> Result<U256, CallError>;
+
+	/// Applies account overrides to `state` before making calls against it.
+	/// The state must be a temporary checkout which is never committed.
+	fn apply_state_overrides(&self, state: &mut Self::State, overrides: &StateOverride) -> Result<(), CallError>;
 }
 
 /// Provides `engine` method
@@ -14,11 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::convert::TryFrom;
 use std::path::Path;
 use super::test_common::*;
 use pod::PodState;
 use trace;
 use ethjson;
+use ethjson::transaction::TransactionError;
 use test_helpers::{EvmTestClient, EvmTestError, TransactErr, TransactSuccess};
 use types::transaction::SignedTransaction;
 use vm::EnvInfo;
@@ -57,8 +59,10 @@ pub fn json_chain_test<H: FnMut(&str, HookType)>(path: &Path, json_data: &[u8],
 			let env: EnvInfo = test.env.into();
 			let pre: PodState = test.pre_state.into();
 
-			for (spec_name, states) in test.post_states {
+			let expect_exception = test.post_states.expect_exception;
+			for (spec_name, states) in test.post_states.states {
 				let total = states.len();
+				let expected_exception = expect_exception.get(&spec_name);
 				let spec = match EvmTestClient::fork_spec_from_json(&spec_name) {
 					Some(spec) => spec,
 					None => {
@@ -75,7 +79,26 @@ pub fn json_chain_test<H: FnMut(&str, HookType)>(path: &Path, json_data: &[u8],
 					}
 
 					let post_root: H256 = state.hash.into();
-					let transaction: SignedTransaction = multitransaction.select(&state.indexes).into();
+					let transaction = match SignedTransaction::try_from(multitransaction.select(&state.indexes)) {
+						Ok(transaction) => transaction,
+						Err(TransactionError::UnsupportedType(tx_type)) => {
+							println!("{}: SKIPPED (unsupported transaction type {})", info, tx_type as u8);
+							continue;
+						},
+						Err(err) => {
+							match expected_exception {
+								Some(_) => {
+									flushln!("{} ok ({})", info, err);
+								},
+								None => {
+									println!("{} !!! Invalid transaction: {}", info, err);
+									flushln!("{} fail", info);
+									failed.push(name.clone());
+								},
+							}
+							continue;
+						},
+					};
 
 					let result = || -> Result<_, EvmTestError> {
 						Ok(EvmTestClient::from_pod_state(&spec, pre.clone())?
@@ -101,8 +124,15 @@ pub fn json_chain_test<H: FnMut(&str, HookType)>(path: &Path, json_data: &[u8],
 						Ok(Err(TransactErr { error, .. })) => {
 							flushln!("{} ok ({:?})", info, error);
 						},
-						Ok(_) => {
-							flushln!("{} ok", info);
+						Ok(Ok(_)) => match expected_exception {
+							Some(exception) => {
+								println!("{} !!! Expected exception {} was not raised", info, exception);
+								flushln!("{} fail", info);
+								failed.push(name.clone());
+							},
+							None => {
+								flushln!("{} ok", info);
+							},
 						},
 					}
 				}
@@ -0,0 +1,215 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Gas price oracle based on the prices paid in recent blocks.
+
+use ethereum_types::{H256, U256};
+use parking_lot::Mutex;
+use types::{
+	BlockNumber,
+	ids::BlockId,
+	transaction::SignedTransaction,
+};
+
+use client_traits::BlockInfo;
+
+/// Gas price oracle options.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasPriceOracleOptions {
+	/// Number of most recent blocks to sample the prices from.
+	pub blocks: usize,
+	/// Percentile of the sampled prices to suggest.
+	pub percentile: usize,
+}
+
+impl Default for GasPriceOracleOptions {
+	fn default() -> Self {
+		GasPriceOracleOptions {
+			blocks: 20,
+			percentile: 60,
+		}
+	}
+}
+
+/// Suggests gas prices based on the cheapest transactions included in recent blocks.
+///
+/// The cheapest priority fee (the gas price before EIP-1559) paid in each of the most recent blocks
+/// is sampled, skipping transactions sent by the author of the block. The configured percentile
+/// of the samples is suggested on top of the base fee of the best block.
+///
+/// The suggestion is cached until the best block changes.
+#[derive(Debug)]
+pub struct GasPriceOracle {
+	options: GasPriceOracleOptions,
+	eip1559_transition: BlockNumber,
+	cache: Mutex<Option<(H256, Option<U256>)>>,
+}
+
+impl GasPriceOracle {
+	/// Creates a new oracle for a chain with given EIP-1559 transition.
+	pub fn new(options: GasPriceOracleOptions, eip1559_transition: BlockNumber) -> Self {
+		GasPriceOracle {
+			options,
+			eip1559_transition,
+			cache: Mutex::new(None),
+		}
+	}
+
+	/// Suggests the gas price for new transactions.
+	///
+	/// Returns `None` if there are no transactions in recent blocks to base the suggestion on.
+	pub fn suggested_gas_price<C: BlockInfo + ?Sized>(&self, chain: &C) -> Option<U256> {
+		let best = chain.best_block_header();
+		let best_hash = best.hash();
+
+		let mut cache = self.cache.lock();
+		if let Some((hash, price)) = *cache {
+			if hash == best_hash {
+				return price;
+			}
+		}
+
+		let mut prices = self.sample_prices(chain, best_hash);
+		prices.sort();
+		let price = match prices.len() {
+			0 => None,
+			len => Some(prices[(len - 1) * self.options.percentile / 100] + best.base_fee().unwrap_or_default()),
+		};
+
+		*cache = Some((best_hash, price));
+		price
+	}
+
+	/// Collects the cheapest priority fee paid by transactions not sent by the block author,
+	/// from every block sampled.
+	fn sample_prices<C: BlockInfo + ?Sized>(&self, chain: &C, best_hash: H256) -> Vec<U256> {
+		let mut prices = Vec::with_capacity(self.options.blocks);
+		let mut hash = best_hash;
+		for _ in 0..self.options.blocks {
+			let block = match chain.block(BlockId::Hash(hash)) {
+				Some(block) => block,
+				None => break,
+			};
+			if block.number() == 0 {
+				break;
+			}
+
+			let author = block.author();
+			let base_fee = block.header().base_fee(self.eip1559_transition);
+			let cheapest = block.transactions()
+				.into_iter()
+				.filter_map(|tx| SignedTransaction::new(tx).ok())
+				.filter(|tx| tx.sender() != author)
+				.map(|tx| tx.effective_priority_fee(base_fee))
+				.min();
+
+			prices.extend(cheapest);
+			hash = block.parent_hash();
+		}
+		prices
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use ethereum_types::Address;
+	use ethkey::{Generator, KeyPair, Random};
+	use types::transaction::{Action, Transaction};
+
+	use test_helpers::{TestBlockChainClient, EachBlockWith};
+
+	fn transaction(keypair: &KeyPair, gas_price: u64) -> SignedTransaction {
+		Transaction {
+			action: Action::Call(Address::zero()),
+			value: U256::zero(),
+			data: vec![],
+			gas: 21_000.into(),
+			gas_price: gas_price.into(),
+			nonce: U256::zero(),
+		}.sign(keypair.secret(), None)
+	}
+
+	fn add_block(client: &TestBlockChainClient, author: &KeyPair, prices: &[u64]) {
+		let transactions: Vec<_> = prices.iter()
+			.map(|price| transaction(&Random.generate().unwrap(), *price))
+			.chain(Some(transaction(author, 1)))
+			.collect();
+		let author = author.address();
+		client.add_block_with_transactions(&transactions, |mut header| {
+			header.set_author(author);
+			header
+		});
+	}
+
+	fn oracle(blocks: usize, percentile: usize) -> GasPriceOracle {
+		GasPriceOracle::new(GasPriceOracleOptions { blocks, percentile }, BlockNumber::max_value())
+	}
+
+	#[test]
+	fn should_suggest_percentile_of_cheapest_prices_in_recent_blocks() {
+		// given
+		let client = TestBlockChainClient::new();
+		let author = Random.generate().unwrap();
+		for price in &[50, 10, 40, 20, 30] {
+			add_block(&client, &author, &[*price, 100]);
+		}
+
+		// then
+		assert_eq!(oracle(20, 60).suggested_gas_price(&client), Some(30.into()));
+		assert_eq!(oracle(20, 100).suggested_gas_price(&client), Some(50.into()));
+		assert_eq!(oracle(2, 60).suggested_gas_price(&client), Some(20.into()));
+	}
+
+	#[test]
+	fn should_ignore_transactions_sent_by_block_author() {
+		// given
+		let client = TestBlockChainClient::new();
+		let author = Random.generate().unwrap();
+		add_block(&client, &author, &[70]);
+		add_block(&client, &author, &[]);
+
+		// then
+		assert_eq!(oracle(20, 0).suggested_gas_price(&client), Some(70.into()));
+	}
+
+	#[test]
+	fn should_not_suggest_anything_without_transactions() {
+		// given
+		let client = TestBlockChainClient::new();
+		client.add_blocks(5, EachBlockWith::Nothing);
+
+		// then
+		assert_eq!(oracle(20, 60).suggested_gas_price(&client), None);
+	}
+
+	#[test]
+	fn should_update_suggestion_when_best_block_changes() {
+		// given
+		let client = TestBlockChainClient::new();
+		let author = Random.generate().unwrap();
+		let oracle = oracle(1, 60);
+		add_block(&client, &author, &[10]);
+		assert_eq!(oracle.suggested_gas_price(&client), Some(10.into()));
+
+		// when
+		add_block(&client, &author, &[20]);
+
+		// then
+		assert_eq!(oracle.suggested_gas_price(&client), Some(20.into()));
+	}
+}
@@ -17,6 +17,7 @@
 use std::cmp;
 use std::time::{Instant, Duration};
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use ansi_term::Colour;
@@ -24,7 +25,9 @@ use bytes::Bytes;
 use call_contract::CallContract;
 use ethcore_miner::gas_pricer::GasPricer;
 use ethcore_miner::local_accounts::LocalAccounts;
-use ethcore_miner::pool::{self, TransactionQueue, VerifiedTransaction, QueueStatus, PrioritizationStrategy, TxStatus};
+use ethcore_miner::pool::{
+	self, TransactionQueue, VerifiedTransaction, QueueStatus, PrioritizationStrategy, TxStatus, PoolEvent,
+};
 use ethcore_miner::service_transaction_checker::ServiceTransactionChecker;
 #[cfg(feature = "work-notify")]
 use ethcore_miner::work_notify::NotifyWork;
@@ -32,6 +35,7 @@ use ethereum_types::{H256, U256, Address};
 use futures::sync::mpsc;
 use io::IoChannel;
 use miner::filter_options::{FilterOptions, FilterOperator};
+use miner::gas_price_oracle::{GasPriceOracle, GasPriceOracleOptions};
 use miner::pool_client::{PoolClient, CachedNonceClient, NonceCache};
 use miner::{self, MinerService};
 use parking_lot::{Mutex, RwLock};
@@ -58,7 +62,7 @@ use using_queue::{UsingQueue, GetAction};
 
 use block::{ClosedBlock, SealedBlock};
 use client::{BlockProducer, SealedBlockImporter, Client};
-use client_traits::{BlockChain, ChainInfo, EngineClient, Nonce, TransactionInfo};
+use client_traits::{BlockChain, BlockInfo, ChainInfo, EngineClient, Nonce, TransactionInfo};
 use engine::{Engine, signer::EngineSigner};
 use machine::executive::contract_address;
 use spec::Spec;
@@ -148,16 +152,22 @@ pub struct MinerOptions {
 
 	/// Strategy to use for prioritizing transactions in the queue.
 	pub tx_queue_strategy: PrioritizationStrategy,
+	/// Gas price bump, in basis points, required to replace a non-local transaction in the queue.
+	pub tx_queue_price_bump: u32,
 	/// Simple senders penalization.
 	pub tx_queue_penalization: Penalization,
 	/// Do we want to mark transactions received locally (e.g. RPC) as local if we don't have the sending account?
 	pub tx_queue_no_unfamiliar_locals: bool,
 	/// Do we refuse to accept service transactions even if sender is certified.
 	pub refuse_service_transactions: bool,
+	/// Senders whose transactions are treated like local ones and always included first.
+	pub tx_queue_priority_senders: HashSet<Address>,
 	/// Transaction pool limits.
 	pub pool_limits: pool::Options,
 	/// Initial transaction verification options.
 	pub pool_verification_options: pool::verifier::Options,
+	/// Gas price oracle options.
+	pub gas_price_oracle: GasPriceOracleOptions,
 }
 
 impl Default for MinerOptions {
@@ -174,9 +184,11 @@ impl Default for MinerOptions {
 			enable_resubmission: true,
 			infinite_pending_block: false,
 			tx_queue_strategy: PrioritizationStrategy::GasPriceOnly,
+			tx_queue_price_bump: pool::scoring::DEFAULT_PRICE_BUMP,
 			tx_queue_penalization: Penalization::Disabled,
 			tx_queue_no_unfamiliar_locals: false,
 			refuse_service_transactions: false,
+			tx_queue_priority_senders: HashSet::new(),
 			pool_limits: pool::Options {
 				max_count: 8_192,
 				max_per_sender: 81,
@@ -187,7 +199,9 @@ impl Default for MinerOptions {
 				block_gas_limit: U256::max_value(),
 				tx_gas_limit: U256::max_value(),
 				no_early_reject: false,
+				max_nonce_gap: 81.into(),
 			},
+			gas_price_oracle: Default::default(),
 		}
 	}
 }
@@ -195,7 +209,9 @@ impl Default for MinerOptions {
 /// Configurable parameters of block authoring.
 #[derive(Debug, Default, Clone)]
 pub struct AuthoringParams {
-	/// Lower and upper bound of block gas limit that we are targeting
+	/// Lower and upper bound of block gas limit that we are targeting.
+	/// From the EIP-1559 transition on these still bound the gas limit, not the gas target,
+	/// which is the gas limit divided by the elasticity multiplier.
 	pub gas_range_target: (U256, U256),
 	/// Block author
 	pub author: Address,
@@ -251,6 +267,8 @@ pub struct Miner {
 	listeners: RwLock<Vec<Box<dyn NotifyWork>>>,
 	nonce_cache: NonceCache,
 	gas_pricer: Mutex<GasPricer>,
+	gas_price_oracle: GasPriceOracle,
+	not_allowed_transactions: RwLock<HashSet<H256>>,
 	options: MinerOptions,
 	// TODO [ToDr] Arc is only required because of price updater
 	transaction_queue: Arc<TransactionQueue>,
@@ -275,6 +293,13 @@ impl Miner {
 		receiver
 	}
 
+	/// Set a callback to be notified about the bodies of transactions added to the pool.
+	pub fn pending_transaction_bodies_receiver(&self) -> mpsc::UnboundedReceiver<Arc<Vec<Arc<VerifiedTransaction>>>> {
+		let (sender, receiver) = mpsc::unbounded();
+		self.transaction_queue.add_pending_transactions_listener(sender);
+		receiver
+	}
+
 	/// Set a callback to be notified about imported transactions' hashes.
 	pub fn full_transactions_receiver(&self) -> mpsc::UnboundedReceiver<Arc<Vec<(H256, TxStatus)>>> {
 		let (sender, receiver) = mpsc::unbounded();
@@ -282,6 +307,13 @@ impl Miner {
 		receiver
 	}
 
+	/// Set a callback to be notified about every event of the transaction pool.
+	pub fn pool_events_receiver(&self) -> mpsc::UnboundedReceiver<Arc<Vec<PoolEvent>>> {
+		let (sender, receiver) = mpsc::unbounded();
+		self.transaction_queue.add_pool_events_listener(sender);
+		receiver
+	}
+
 	/// Creates new instance of miner Arc.
 	pub fn new<A: LocalAccounts + 'static>(
 		options: MinerOptions,
@@ -291,9 +323,13 @@ impl Miner {
 	) -> Self {
 		let limits = options.pool_limits.clone();
 		let verifier_options = options.pool_verification_options.clone();
-		let tx_queue_strategy = options.tx_queue_strategy;
+		let tx_queue_scoring = pool::scoring::NonceAndGasPrice::new(options.tx_queue_strategy)
+			.with_price_bump(options.tx_queue_price_bump);
 		let nonce_cache_size = cmp::max(4096, limits.max_count / 4);
 		let refuse_service_transactions = options.refuse_service_transactions;
+		let gas_price_oracle = GasPriceOracle::new(options.gas_price_oracle.clone(), spec.params().eip1559_transition);
+		let transaction_queue = TransactionQueue::with_scoring(limits, verifier_options, tx_queue_scoring);
+		transaction_queue.set_priority_senders(options.tx_queue_priority_senders.clone());
 
 		Miner {
 			sealing: Mutex::new(SealingWork {
@@ -308,9 +344,11 @@ impl Miner {
 			#[cfg(feature = "work-notify")]
 			listeners: RwLock::new(vec![]),
 			gas_pricer: Mutex::new(gas_pricer),
+			gas_price_oracle,
+			not_allowed_transactions: RwLock::new(HashSet::new()),
 			nonce_cache: NonceCache::new(nonce_cache_size),
 			options,
-			transaction_queue: Arc::new(TransactionQueue::new(limits, verifier_options, tx_queue_strategy)),
+			transaction_queue: Arc::new(transaction_queue),
 			accounts: Arc::new(accounts),
 			engine: spec.engine.clone(),
 			io_channel: RwLock::new(None),
@@ -333,6 +371,7 @@ impl Miner {
 				block_gas_limit: U256::max_value(),
 				tx_gas_limit: U256::max_value(),
 				no_early_reject: false,
+				max_nonce_gap: U256::max_value(),
 			},
 			reseal_min_period: Duration::from_secs(0),
 			..Default::default()
@@ -357,6 +396,22 @@ impl Miner {
 		});
 	}
 
+	/// Imports the local transactions journaled at `path`, silently dropping the ones which are
+	/// no longer valid (e.g. already mined), and keeps journaling pending local transactions there.
+	pub fn set_local_transactions_journal<C: miner::BlockChainClient>(&self, chain: &C, path: PathBuf) {
+		let journal = pool::journal::Journal::new(path);
+		match journal.read() {
+			Ok(transactions) => for pending in transactions {
+				let hash = pending.hash();
+				if let Err(e) = self.import_own_transaction(chain, pending) {
+					debug!(target: "own_tx", "Dropping journaled transaction {:?}: {}", hash, e);
+				}
+			},
+			Err(e) => warn!(target: "own_tx", "Error reading local transactions journal: {}", e),
+		}
+		self.transaction_queue.set_local_transactions_journal(journal);
+	}
+
 	/// Clear all pending block states
 	pub fn clear(&self) {
 		self.sealing.lock().queue.reset();
@@ -555,6 +610,10 @@ impl Miner {
 						break;
 					}
 				},
+				// The fee cap of the transaction is too low for this block, it may still be included later on.
+				Err(Error::Execution(ExecutionError::GasPriceLowerThanBaseFee { gas_price, base_fee })) => {
+					debug!(target: "miner", "Skipping adding transaction to block because of base fee: {:?} (max fee: {:?}, base fee: {:?})", hash, gas_price, base_fee);
+				},
 				// Invalid nonce error can happen only if previous transaction is skipped because of gas limit.
 				// If there is erroneous state of transaction queue it will be fixed when next block is imported.
 				Err(Error::Execution(ExecutionError::InvalidNonce { expected, got })) => {
@@ -562,6 +621,7 @@ impl Miner {
 				},
 				// already have transaction - ignore
 				Err(Error::Transaction(transaction::Error::AlreadyImported)) => {},
+				// The transaction permission contract may allow the transaction later on, keep it in the queue.
 				Err(Error::Transaction(transaction::Error::NotAllowed)) => {
 					not_allowed_transactions.insert(hash);
 					debug!(target: "miner", "Skipping non-allowed transaction for sender {:?}", hash);
@@ -590,9 +650,9 @@ impl Miner {
 
 		{
 			self.transaction_queue.remove(invalid_transactions.iter(), true);
-			self.transaction_queue.remove(not_allowed_transactions.iter(), false);
 			self.transaction_queue.penalize(senders_to_penalize.iter());
 		}
+		*self.not_allowed_transactions.write() = not_allowed_transactions;
 
 		Some((block, original_work_hash))
 	}
@@ -683,7 +743,7 @@ impl Miner {
 
 		let parent_header = match chain.block_header(BlockId::Hash(*block.header.parent_hash())) {
 			Some(h) => {
-				match h.decode() {
+				match h.decode(self.engine.params().eip1559_transition) {
 					Ok(decoded_hdr) => decoded_hdr,
 					Err(e) => {
 						error!(target: "miner", "seal_block_internally: Block #{}, Could not decode header from parent block (hash={}): {:?}", block_number, block.header.parent_hash(), e);
@@ -934,6 +994,10 @@ impl miner::MinerService for Miner {
 		self.transaction_queue.current_worst_gas_price() * 110u32 / 100
 	}
 
+	fn suggested_gas_price<C: BlockInfo>(&self, chain: &C) -> Option<U256> {
+		self.gas_price_oracle.suggested_gas_price(chain)
+	}
+
 	fn sensible_gas_limit(&self) -> U256 {
 		self.params.read().gas_range_target.0 / 5
 	}
@@ -1048,6 +1112,14 @@ impl miner::MinerService for Miner {
 		self.transaction_queue.all_transaction_hashes()
 	}
 
+	fn not_allowed_transactions(&self) -> HashSet<H256> {
+		self.not_allowed_transactions.read().clone()
+	}
+
+	fn set_priority_senders(&self, senders: HashSet<Address>) {
+		self.transaction_queue.set_priority_senders(senders);
+	}
+
 	fn pending_transaction_hashes<C>(&self, chain: &C) -> BTreeSet<H256> where
 		C: ChainInfo + Sync,
 	{
@@ -1386,9 +1458,17 @@ impl miner::MinerService for Miner {
 		}
 
 		// First update gas limit in transaction queue and minimal gas price.
-		let gas_limit = *chain.best_block_header().gas_limit();
+		let best_block_header = chain.best_block_header();
+		let gas_limit = *best_block_header.gas_limit();
 		self.update_transaction_queue_limits(gas_limit);
 
+		// Rescore pending transactions by the tip they pay on top of the new base fee.
+		if has_new_best_block {
+			if let Some(base_fee) = best_block_header.base_fee() {
+				self.transaction_queue.set_block_base_fee(base_fee);
+			}
+		}
+
 		// Then import all transactions from retracted blocks.
 		let client = self.pool_client(chain);
 		{
@@ -1504,7 +1584,7 @@ mod tests {
 
 	use super::*;
 	use accounts::AccountProvider;
-	use ethkey::{Generator, Random};
+	use ethkey::{Generator, KeyPair, Random};
 	use hash::keccak;
 	use rustc_hex::FromHex;
 
@@ -1568,15 +1648,19 @@ mod tests {
 				infinite_pending_block: false,
 				tx_queue_penalization: Penalization::Disabled,
 				tx_queue_strategy: PrioritizationStrategy::GasPriceOnly,
+				tx_queue_price_bump: pool::scoring::DEFAULT_PRICE_BUMP,
 				tx_queue_no_unfamiliar_locals: false,
 				refuse_service_transactions: false,
+				tx_queue_priority_senders: HashSet::new(),
 				pool_limits: Default::default(),
 				pool_verification_options: pool::verifier::Options {
 					minimal_gas_price: 0.into(),
 					block_gas_limit: U256::max_value(),
 					tx_gas_limit: U256::max_value(),
 					no_early_reject: false,
+					max_nonce_gap: U256::max_value(),
 				},
+				gas_price_oracle: Default::default(),
 			},
 			GasPricer::new_fixed(0u64.into()),
 			&spec::new_test(),
@@ -1761,6 +1845,40 @@ mod tests {
 		assert_eq!(miner.prepare_pending_block(&client), BlockPreparationStatus::NotPrepared);
 	}
 
+	#[test]
+	fn should_include_transactions_of_priority_senders_first() {
+		// given
+		let client = TestBlockChainClient::new_with_spec(spec::new_null());
+		let miner = Miner::new(
+			MinerOptions {
+				pool_verification_options: pool::verifier::Options {
+					minimal_gas_price: 10.into(),
+					..miner().options.pool_verification_options
+				},
+				..miner().options
+			},
+			GasPricer::new_fixed(10u64.into()),
+			&spec::new_null(),
+			HashSet::new(),
+		);
+		let stranger = KeyPair::from_secret_slice(keccak("").as_bytes()).unwrap();
+		client.set_balance(stranger.address(), U256::from(1_000_000_000_000u64));
+		let expensive = Transaction {
+			gas_price: 20.into(),
+			..transaction().as_unsigned().clone()
+		}.sign(stranger.secret(), Some(TEST_CHAIN_ID));
+		let free = transaction();
+		miner.set_priority_senders(HashSet::from_iter(vec![free.sender()]));
+
+		// when
+		let res = miner.import_external_transactions(&client, vec![expensive.clone().into(), free.clone().into()]);
+
+		// then
+		assert_eq!(res, vec![Ok(()), Ok(())]);
+		let (block, _) = miner.prepare_block(&client).unwrap();
+		assert_eq!(block.transactions, vec![free, expensive]);
+	}
+
 	#[test]
 	fn should_not_seal_unless_enabled() {
 		let miner = miner();
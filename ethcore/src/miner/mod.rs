@@ -21,17 +21,19 @@
 
 mod miner;
 mod filter_options;
+mod gas_price_oracle;
 pub mod pool_client;
 #[cfg(feature = "stratum")]
 pub mod stratum;
 
 pub use self::miner::{Miner, MinerOptions, Penalization, PendingSet, AuthoringParams, Author};
 pub use self::filter_options::FilterOptions;
+pub use self::gas_price_oracle::{GasPriceOracle, GasPriceOracleOptions};
 pub use ethcore_miner::local_accounts::LocalAccounts;
 pub use ethcore_miner::pool::PendingOrdering;
 
 use std::sync::Arc;
-use std::collections::{BTreeSet, BTreeMap};
+use std::collections::{BTreeSet, BTreeMap, HashSet};
 
 use bytes::Bytes;
 use ethcore_miner::pool::{VerifiedTransaction, QueueStatus, local_transactions};
@@ -47,7 +49,7 @@ use types::{
 
 use call_contract::CallContract;
 use registrar::RegistrarClient;
-use client_traits::{BlockChain, ChainInfo, AccountData, Nonce, ScheduleInfo};
+use client_traits::{BlockChain, BlockInfo, ChainInfo, AccountData, Nonce, ScheduleInfo};
 use account_state::state::StateInfo;
 
 use crate::{
@@ -202,6 +204,15 @@ pub trait MinerService : Send + Sync {
 	/// Get a list of all transaction hashes in the pool (some of them might not be ready for inclusion yet).
 	fn queued_transaction_hashes(&self) -> Vec<H256>;
 
+	/// Get hashes of queued transactions which were skipped when preparing the last block,
+	/// because the transaction permission contract didn't allow them.
+	///
+	/// Such transactions are kept in the pool, as the contract may allow them later on.
+	fn not_allowed_transactions(&self) -> HashSet<H256>;
+
+	/// Set the senders whose transactions are treated like local ones and always included first.
+	fn set_priority_senders(&self, senders: HashSet<Address>);
+
 	/// Get a list of local transactions with statuses.
 	fn local_transactions(&self) -> BTreeMap<H256, local_transactions::Status>;
 
@@ -215,6 +226,11 @@ pub trait MinerService : Send + Sync {
 	/// Suggested gas price.
 	fn sensible_gas_price(&self) -> U256;
 
+	/// Gas price suggested by the gas price oracle, based on the prices paid in recent blocks.
+	///
+	/// Returns `None` if there are no transactions in recent blocks to base the suggestion on.
+	fn suggested_gas_price<C: BlockInfo>(&self, chain: &C) -> Option<U256>;
+
 	/// Suggested gas limit.
 	fn sensible_gas_limit(&self) -> U256;
 
@@ -28,8 +28,9 @@ use ethcore_miner::work_notify::NotifyWork;
 #[cfg(feature = "work-notify")]
 use ethcore_stratum::PushWorkHandler;
 use ethcore_stratum::{
-	JobDispatcher, Stratum as StratumService, Error as StratumServiceError,
+	JobDispatcher, Stratum as StratumService, Error as StratumServiceError, Share,
 };
+pub use ethcore_stratum::DifficultyConfig;
 use miner::{Miner, MinerService};
 use parking_lot::Mutex;
 use rlp::encode;
@@ -45,6 +46,8 @@ pub struct Options {
 	pub port: u16,
 	/// Secret for peers
 	pub secret: Option<H256>,
+	/// Per-worker difficulty adjustment, if enabled
+	pub difficulty: Option<DifficultyConfig>,
 }
 
 fn clean_0x(s: &str) -> &str {
@@ -132,6 +135,25 @@ impl JobDispatcher for StratumJobDispatcher {
 		}))
 	}
 
+	fn share(&self, payload: &[String]) -> Option<Share> {
+		let payload = SubmitPayload::from_args(payload.to_vec()).ok()?;
+		self.with_core(|client, miner| {
+			// shares for anything but the current work package are left to the seal check
+			let (pow_hash, _number, _timestamp, block_difficulty) = miner.work_package(&*client)?;
+			if pow_hash != payload.pow_hash {
+				return None;
+			}
+			// NOTE: ProgPoW shares are not supported
+			let difficulty = ethash::boundary_to_difficulty(&H256(ethash::quick_get_difficulty(
+				&payload.pow_hash.0,
+				payload.nonce.to_low_u64_be(),
+				&payload.mix_hash.0,
+				false,
+			)));
+			Some(Share { difficulty, block_difficulty })
+		})
+	}
+
 	fn submit(&self, payload: Vec<String>) -> Result<(), StratumServiceError> {
 		let payload = SubmitPayload::from_args(payload).map_err(|e|
 			StratumServiceError::Dispatch(e.to_string())
@@ -241,6 +263,7 @@ impl Stratum {
 			&SocketAddr::new(options.listen_addr.parse::<IpAddr>()?, options.port),
 			dispatcher.clone(),
 			options.secret.clone(),
+			options.difficulty.clone(),
 		)?;
 
 		Ok(Stratum { dispatcher, service })
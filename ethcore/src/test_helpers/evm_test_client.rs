@@ -110,8 +110,9 @@ impl<'a> EvmTestClient<'a> {
 			ForkSpec::Constantinople => Some(spec::new_constantinople_test()),
 			ForkSpec::ConstantinopleFix => Some(spec::new_constantinople_fix_test()),
 			ForkSpec::Istanbul => Some(spec::new_istanbul_test()),
+			ForkSpec::Berlin => Some(spec::new_berlin_test()),
 			ForkSpec::EIP158ToByzantiumAt5 => Some(spec::new_transition_test()),
-			ForkSpec::FrontierToHomesteadAt5 | ForkSpec::HomesteadToDaoAt5 | ForkSpec::HomesteadToEIP150At5 => None,
+			ForkSpec::London | ForkSpec::FrontierToHomesteadAt5 | ForkSpec::HomesteadToDaoAt5 | ForkSpec::HomesteadToEIP150At5 => None,
 		}
 	}
 
@@ -223,6 +224,7 @@ impl<'a> EvmTestClient<'a> {
 			last_hashes: Arc::new([H256::zero(); 256].to_vec()),
 			gas_used: 0.into(),
 			gas_limit: *genesis.gas_limit(),
+			base_fee: genesis.base_fee(),
 		};
 		self.call_envinfo(params, tracer, vm_tracer, info)
 	}
@@ -48,6 +48,7 @@ use types::{
 	filter::Filter,
 	trace_filter::Filter as TraceFilter,
 	call_analytics::CallAnalytics,
+	state_override::StateOverride,
 	header::Header,
 	log_entry::LocalizedLogEntry,
 	pruning_info::PruningInfo,
@@ -56,6 +57,7 @@ use types::{
 	views::BlockView,
 	verification::Unverified,
 	client_types::{Mode, StateResult},
+	database_stats::{CompactionStatus, DatabaseStats},
 	blockchain_info::BlockChainInfo,
 	block_status::BlockStatus,
 	verification::VerificationQueueInfo as BlockQueueInfo,
@@ -81,7 +83,7 @@ use miner::{self, Miner, MinerService};
 use spec::{Spec, self};
 use account_state::state::StateInfo;
 use state_db::StateDB;
-use trace::LocalizedTrace;
+use trace::{FlatTrace, LocalizedTrace, StructLog, StructLoggerConfig};
 
 /// Test client.
 pub struct TestBlockChainClient {
@@ -107,6 +109,8 @@ pub struct TestBlockChainClient {
 	pub code: RwLock<HashMap<Address, Bytes>>,
 	/// Execution result.
 	pub execution_result: RwLock<Option<Result<Executed, CallError>>>,
+	/// State overrides applied by the last call.
+	pub state_overrides: RwLock<Option<StateOverride>>,
 	/// Transaction receipts.
 	pub receipts: RwLock<HashMap<TransactionId, LocalizedReceipt>>,
 	/// Logs
@@ -129,6 +133,8 @@ pub struct TestBlockChainClient {
 	pub traces: RwLock<Option<Vec<LocalizedTrace>>>,
 	/// Pruning history size to report.
 	pub history: RwLock<Option<u64>>,
+	/// Database statistics to report.
+	pub database_stats: RwLock<DatabaseStats>,
 	/// Is disabled
 	pub disabled: AtomicBool,
 }
@@ -188,6 +194,7 @@ impl TestBlockChainClient {
 			storage: RwLock::new(HashMap::new()),
 			code: RwLock::new(HashMap::new()),
 			execution_result: RwLock::new(None),
+			state_overrides: RwLock::new(None),
 			receipts: RwLock::new(HashMap::new()),
 			logs: RwLock::new(Vec::new()),
 			queue_size: AtomicUsize::new(0),
@@ -198,6 +205,7 @@ impl TestBlockChainClient {
 			first_block: RwLock::new(None),
 			traces: RwLock::new(None),
 			history: RwLock::new(None),
+			database_stats: RwLock::new(Default::default()),
 			disabled: AtomicBool::new(false),
 			error_on_logs: RwLock::new(None),
 		};
@@ -319,11 +327,37 @@ impl TestBlockChainClient {
 			_ => ::rlp::EMPTY_LIST_RLP.to_vec()
 		};
 
+		self.import_test_block(header, &txs, uncles.as_raw());
+	}
+
+	/// Add a block with given transactions to test client.
+	pub fn add_block_with_transactions<F>(&self, transactions: &[SignedTransaction], hook: F)
+		where F: Fn(Header) -> Header
+	{
+		let n = self.numbers.read().len();
+
+		let mut header = Header::new();
+		header.set_difficulty(From::from(n));
+		header.set_parent_hash(self.last_hash.read().clone());
+		header.set_number(n as BlockNumber);
+		header.set_gas_limit(U256::from(1_000_000));
+		header.set_extra_data(self.extra_data.clone());
+
+		header = hook(header);
+
+		let mut txs = RlpStream::new_list(transactions.len());
+		for tx in transactions {
+			txs.append(tx);
+		}
+		self.import_test_block(header, &txs.out(), &::rlp::EMPTY_LIST_RLP);
+	}
+
+	fn import_test_block(&self, header: Header, txs: &[u8], uncles: &[u8]) {
 		let mut rlp = RlpStream::new_list(3);
 		rlp.append(&header);
-		rlp.append_raw(&txs, 1);
-		rlp.append_raw(uncles.as_raw(), 1);
-		let unverified = Unverified::from_rlp(rlp.out()).unwrap();
+		rlp.append_raw(txs, 1);
+		rlp.append_raw(uncles, 1);
+		let unverified = Unverified::from_rlp(rlp.out(), self.spec.params().eip1559_transition).unwrap();
 		self.import_block(unverified).unwrap();
 	}
 
@@ -337,7 +371,9 @@ impl TestBlockChainClient {
 	/// Make a bad block by setting invalid parent hash.
 	pub fn corrupt_block_parent(&self, n: BlockNumber) {
 		let hash = self.block_hash(BlockId::Number(n)).unwrap();
-		let mut header: Header = self.block_header(BlockId::Number(n)).unwrap().decode().expect("decoding failed");
+		let mut header: Header = self.block_header(BlockId::Number(n)).unwrap()
+			.decode(self.spec.params().eip1559_transition)
+			.expect("decoding failed");
 		header.set_parent_hash(H256::from_low_u64_be(42));
 		let mut rlp = RlpStream::new_list(3);
 		rlp.append(&header);
@@ -387,6 +423,31 @@ impl TestBlockChainClient {
 		self.insert_transaction_with_gas_price_to_queue(U256::from(20_000_000_000u64))
 	}
 
+	/// Inserts an EIP-2930 transaction to miners transactions queue.
+	/// The client's spec has to have EIP-2930 enabled.
+	pub fn insert_access_list_transaction_to_queue(&self) -> H256 {
+		let keypair = Random.generate().unwrap();
+		let tx = transaction::AccessListTx {
+			transaction: Transaction {
+				action: Action::Create,
+				value: U256::from(100),
+				data: "3331600055".from_hex().unwrap(),
+				gas: U256::from(100_000),
+				gas_price: U256::from(20_000_000_000u64),
+				nonce: U256::zero()
+			},
+			chain_id: self.spec.chain_id(),
+			access_list: vec![],
+		};
+		let signed_tx = tx.sign(keypair.secret());
+		self.set_balance(signed_tx.sender(), 10_000_000_000_000_000_000u64.into());
+		let hash = signed_tx.hash();
+		let res = self.miner.import_external_transactions(self, vec![signed_tx.into()]);
+		let res = res.into_iter().next().unwrap();
+		assert!(res.is_ok());
+		hash
+	}
+
 	/// Set reported history size.
 	pub fn set_history(&self, h: Option<u64>) {
 		*self.history.write() = h;
@@ -514,7 +575,7 @@ impl BlockInfo for TestBlockChainClient {
 	fn best_block_header(&self) -> Header {
 		self.block_header(BlockId::Hash(self.chain_info().best_block_hash))
 			.expect("Best block always has header.")
-			.decode()
+			.decode(self.spec.params().eip1559_transition)
 			.expect("decoding failed")
 	}
 
@@ -550,8 +611,13 @@ impl RegistrarClient for TestBlockChainClient {
 }
 
 impl TransactionInfo for TestBlockChainClient {
-	fn transaction_block(&self, _id: TransactionId) -> Option<H256> {
-		None	// Simple default.
+	fn transaction_block(&self, id: TransactionId) -> Option<H256> {
+		match id {
+			TransactionId::Hash(hash) => self.blocks.read().iter()
+				.find(|&(_, block)| view!(BlockView, block).transaction_hashes().contains(&hash))
+				.map(|(block_hash, _)| *block_hash),
+			_ => None,
+		}
 	}
 }
 
@@ -568,7 +634,7 @@ impl ImportBlock for TestBlockChainClient {
 		if number > 0 {
 			match self.blocks.read().get(header.parent_hash()) {
 				Some(parent) => {
-					let parent = view!(BlockView, parent).header();
+					let parent = view!(BlockView, parent).header_view();
 					if parent.number() != (header.number() - 1) {
 						panic!("Unexpected block parent");
 					}
@@ -593,7 +659,7 @@ impl ImportBlock for TestBlockChainClient {
 				while n > 0 && self.numbers.read()[&n] != parent_hash {
 					*self.numbers.write().get_mut(&n).unwrap() = parent_hash.clone();
 					n -= 1;
-					parent_hash = view!(BlockView, &self.blocks.read()[&parent_hash]).header().parent_hash().clone();
+					parent_hash = view!(BlockView, &self.blocks.read()[&parent_hash]).header_view().parent_hash();
 				}
 			}
 		}
@@ -627,6 +693,11 @@ impl Call for TestBlockChainClient {
 	fn estimate_gas(&self, _t: &SignedTransaction, _state: &Self::State, _header: &Header) -> Result<U256, CallError> {
 		Ok(21000.into())
 	}
+
+	fn apply_state_overrides(&self, _state: &mut Self::State, overrides: &StateOverride) -> Result<(), CallError> {
+		*self.state_overrides.write() = Some(overrides.clone());
+		Ok(())
+	}
 }
 
 /// NewType wrapper around `()` to impersonate `State` in trait impls. State will not be used by
@@ -655,7 +726,7 @@ impl StateClient for TestBlockChainClient {
 
 impl EngineInfo for TestBlockChainClient {
 	fn engine(&self) -> &dyn Engine {
-		unimplemented!()
+		&*self.spec.engine
 	}
 }
 
@@ -677,6 +748,22 @@ impl BlockChainClient for TestBlockChainClient {
 		self.execution_result.read().clone().unwrap()
 	}
 
+	fn replay_struct_logs(&self, _id: TransactionId, _config: StructLoggerConfig) -> Result<types::engines::machine::Executed<FlatTrace, Vec<StructLog>>, CallError> {
+		self.execution_result.read().clone().unwrap().map(|executed| types::engines::machine::Executed {
+			exception: executed.exception,
+			gas: executed.gas,
+			gas_used: executed.gas_used,
+			refunded: executed.refunded,
+			cumulative_gas_used: executed.cumulative_gas_used,
+			logs: executed.logs,
+			contracts_created: executed.contracts_created,
+			output: executed.output,
+			trace: executed.trace,
+			vm_trace: Some(vec![]),
+			state_diff: executed.state_diff,
+		})
+	}
+
 	fn queue_info(&self) -> BlockQueueInfo {
 		BlockQueueInfo {
 			verified_queue_size: self.queue_size.load(AtomicOrder::Relaxed),
@@ -738,6 +825,9 @@ impl BlockChainClient for TestBlockChainClient {
 	fn list_storage(&self, _id: BlockId, _account: &Address, _after: Option<&H256>, _count: Option<u64>) -> Option<Vec<H256>> {
 		None
 	}
+	fn list_storage_from_path(&self, _id: BlockId, _account: &Address, _resume_at: Option<&H256>, _count: u64) -> Option<StateResult<(Vec<H256>, Option<H256>)>> {
+		None
+	}
 	fn transaction(&self, _id: TransactionId) -> Option<LocalizedTransaction> {
 		None	// Simple default.
 	}
@@ -765,6 +855,11 @@ impl BlockChainClient for TestBlockChainClient {
 		}
 
 		let mut logs = self.logs.read().clone();
+		if let (BlockId::Hash(from), BlockId::Hash(to)) = (&filter.from_block, &filter.to_block) {
+			if from == to {
+				logs.retain(|log| log.block_hash == *from);
+			}
+		}
 		let len = logs.len();
 		Ok(match filter.limit {
 			Some(limit) if limit <= len => logs.split_off(len - limit),
@@ -798,7 +893,7 @@ impl BlockChainClient for TestBlockChainClient {
 
 	fn block_extra_info(&self, id: BlockId) -> Option<BTreeMap<String, String>> {
 		self.block(id)
-			.map(|block| block.view().header())
+			.map(|block| block.decode_header(self.spec.params().eip1559_transition))
 			.map(|header| self.spec.engine.extra_info(&header))
 	}
 
@@ -872,8 +967,12 @@ impl BlockChainClient for TestBlockChainClient {
 	fn clear_queue(&self) {
 	}
 
-	fn filter_traces(&self, _filter: TraceFilter) -> Option<Vec<LocalizedTrace>> {
-		self.traces.read().clone()
+	fn filter_traces(&self, filter: TraceFilter) -> Option<Vec<LocalizedTrace>> {
+		self.traces.read().clone().map(|traces| traces.into_iter()
+			.skip(filter.after.unwrap_or(0))
+			.take(filter.count.unwrap_or(usize::max_value()))
+			.collect()
+		)
 	}
 
 	fn trace(&self, _trace: TraceId) -> Option<LocalizedTrace> {
@@ -909,7 +1008,21 @@ impl BlockChainClient for TestBlockChainClient {
 		PruningInfo {
 			earliest_chain: 1,
 			earliest_state: self.history.read().as_ref().map(|x| best_num - x).unwrap_or(0),
+			earliest_body: None,
+		}
+	}
+
+	fn database_stats(&self) -> DatabaseStats {
+		self.database_stats.read().clone()
+	}
+
+	fn compact_database(&self, column: Option<u32>) -> Result<(), String> {
+		let mut stats = self.database_stats.write();
+		if stats.compaction.is_ongoing() {
+			return Err("A database compaction is already running".into());
 		}
+		stats.compaction = CompactionStatus::Ongoing { column: column.unwrap_or(0), done: 0, total: 1 };
+		Ok(())
 	}
 
 	fn transact_contract(&self, address: Address, data: Bytes) -> Result<(), transaction::Error> {
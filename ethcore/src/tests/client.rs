@@ -18,7 +18,9 @@ use std::str::{FromStr, from_utf8};
 use std::sync::Arc;
 
 use account_state::state::StateInfo;
-use ethereum_types::{U256, Address};
+use block::{OpenBlock, SealedBlock};
+use blockchain::BlockChainDB;
+use ethereum_types::{U256, H256, Address};
 use ethkey::KeyPair;
 use hash::keccak;
 use io::IoChannel;
@@ -26,8 +28,10 @@ use tempdir::TempDir;
 use types::{
 	data_format::DataFormat,
 	ids::BlockId,
-	transaction::{PendingTransaction, Transaction, Action, Condition},
+	call_analytics::CallAnalytics,
+	transaction::{PendingTransaction, Transaction, Action, Condition, CallError, SignedTransaction},
 	filter::Filter,
+	header::Header,
 	verification::Unverified,
 	view,
 	views::BlockView,
@@ -39,6 +43,7 @@ use client_traits::{
 	ImportExportBlocks, Tick, ImportBlock
 };
 use spec;
+use state_db::StateDB;
 use stats;
 use machine::executive::{Executive, TransactOptions};
 use miner::{Miner, PendingOrdering, MinerService};
@@ -46,7 +51,7 @@ use account_state::{State, CleanupMode, backend};
 use test_helpers::{
 	self,
 	generate_dummy_client, push_blocks_to_client, get_test_client_with_blocks, get_good_dummy_block_seq,
-	generate_dummy_client_with_data, get_good_dummy_block, get_bad_state_dummy_block
+	generate_dummy_client_with_data, generate_dummy_client_with_spec, get_good_dummy_block, get_bad_state_dummy_block
 };
 use rustc_hex::ToHex;
 use registrar::RegistrarClient;
@@ -105,7 +110,7 @@ fn imports_good_block() {
 		IoChannel::disconnected(),
 	).unwrap();
 	let good_block = get_good_dummy_block();
-	if client.import_block(Unverified::from_rlp(good_block).unwrap()).is_err() {
+	if client.import_block(Unverified::from_rlp(good_block, spec.params().eip1559_transition).unwrap()).is_err() {
 		panic!("error importing block being good by definition");
 	}
 	client.flush_queue();
@@ -144,7 +149,7 @@ fn returns_chain_info() {
 	let client = get_test_client_with_blocks(vec![dummy_block.clone()]);
 	let block = view!(BlockView, &dummy_block);
 	let info = client.chain_info();
-	assert_eq!(info.best_block_hash, block.header().hash());
+	assert_eq!(info.best_block_hash, block.hash());
 }
 
 #[test]
@@ -180,7 +185,7 @@ fn returns_block_body() {
 	let dummy_block = get_good_dummy_block();
 	let client = get_test_client_with_blocks(vec![dummy_block.clone()]);
 	let block = view!(BlockView, &dummy_block);
-	let body = client.block_body(BlockId::Hash(block.header().hash())).unwrap();
+	let body = client.block_body(BlockId::Hash(block.hash())).unwrap();
 	let body = body.rlp();
 	assert_eq!(body.item_count().unwrap(), 2);
 	assert_eq!(body.at(0).unwrap().as_raw()[..], block.rlp().at(1).as_raw()[..]);
@@ -299,6 +304,40 @@ fn change_history_size() {
 	assert_eq!(client.state().balance(&address).unwrap(), 100.into());
 }
 
+#[test]
+fn prunes_ancient_blocks_on_tick() {
+	let test_spec = spec::new_null();
+	let mut config = ClientConfig::default();
+	config.ancient_blocks_history = Some(5);
+	let client = Client::new(
+		config,
+		&test_spec,
+		test_helpers::new_db(),
+		Arc::new(Miner::new_for_tests(&test_spec, None)),
+		IoChannel::disconnected(),
+	).unwrap();
+
+	for _ in 0..20 {
+		let b = client.prepare_open_block(Address::zero(), (3141562.into(), 31415620.into()), vec![]).unwrap();
+		let b = b.close_and_lock().unwrap().seal(&*test_spec.engine, vec![]).unwrap();
+		client.import_sealed_block(b).unwrap();
+	}
+
+	// blocks included in the most recent snapshot are kept
+	client.note_snapshot_block(12);
+	client.tick(true);
+	assert_eq!(client.pruning_info().earliest_body, None);
+
+	client.note_snapshot_block(20_000);
+	client.tick(true);
+	assert_eq!(client.pruning_info().earliest_body, Some(15));
+	assert_eq!(client.pruning_info().earliest_chain, 15);
+	assert!(client.block_body(BlockId::Number(0)).is_some());
+	assert!(client.block_body(BlockId::Number(14)).is_none());
+	assert!(client.block_header(BlockId::Number(14)).is_some());
+	assert!(client.block_body(BlockId::Number(15)).is_some());
+}
+
 #[test]
 fn does_not_propagate_delayed_transactions() {
 	let key = KeyPair::from_secret(keccak("test").into()).unwrap();
@@ -373,6 +412,323 @@ fn transaction_proof() {
 	assert_eq!(state.balance(&address).unwrap(), 95.into());
 }
 
+#[test]
+fn dumps_state_account_by_account() {
+	use types::state_dump::StateDumpOptions;
+
+	let client = generate_dummy_client(0);
+	let contract = Address::from_low_u64_be(0x42);
+	let test_spec = spec::new_test();
+	let mut b = client.prepare_open_block(Address::zero(), (3141562.into(), 31415620.into()), vec![]).unwrap();
+	{
+		let state = b.block_mut().state_mut();
+		state.add_balance(&contract, &1000.into(), CleanupMode::NoEmpty).unwrap();
+		state.init_code(&contract, vec![0x60, 0x00]).unwrap();
+		state.set_storage(&contract, H256::from_low_u64_be(1), H256::from_low_u64_be(2)).unwrap();
+		state.commit().unwrap();
+	}
+	let b = b.close_and_lock().unwrap().seal(&*test_spec.engine, vec![]).unwrap();
+	client.import_sealed_block(b).unwrap();
+
+	let dump = |options: &StateDumpOptions| {
+		let mut accounts = Vec::new();
+		client.dump_state(BlockId::Latest, options, |account| { accounts.push(account); Ok(()) }).unwrap();
+		accounts
+	};
+
+	let all = dump(&Default::default());
+	assert!(all.windows(2).all(|w| w[0].address_hash < w[1].address_hash));
+	let account = all.iter().find(|a| a.address_hash == keccak(&contract)).unwrap();
+	assert_eq!(account.address, None); // not a fat db
+	assert_eq!(account.balance, 1000.into());
+	assert_eq!(account.code, Some(vec![0x60, 0x00]));
+	assert_eq!(account.storage.len(), 1);
+	assert_eq!(account.storage[0].key_hash, keccak(H256::from_low_u64_be(1)));
+	assert_eq!(account.storage[0].value, 2.into());
+
+	let filtered = dump(&StateDumpOptions { min_balance: Some(1000.into()), code: false, storage: false, ..Default::default() });
+	assert!(filtered.iter().all(|a| a.balance >= 1000.into() && a.code.is_none() && a.storage.is_empty()));
+	assert!(filtered.iter().any(|a| a.address_hash == keccak(&contract)));
+
+	let resumed = dump(&StateDumpOptions { from: Some(all[1].address_hash), ..Default::default() });
+	assert_eq!(resumed, &all[1..]);
+
+	assert!(client.dump_state(BlockId::Number(10), &Default::default(), |_| Ok(())).is_err());
+}
+
+#[test]
+fn applies_state_overrides_without_touching_canonical_state() {
+	use client::Call;
+	use types::state_override::{AccountOverride, StateOverride, StorageOverride};
+
+	let client = generate_dummy_client(0);
+	let address = Address::from_low_u64_be(0x42);
+	let replaced = Address::from_low_u64_be(0x43);
+	let key = H256::from_low_u64_be(1);
+
+	let mut overrides = StateOverride::new();
+	overrides.insert(address, AccountOverride {
+		balance: Some(100.into()),
+		nonce: Some(5.into()),
+		code: Some(vec![0x60, 0x00]),
+		storage: Some(StorageOverride::Diff(vec![(key, H256::from_low_u64_be(2))].into_iter().collect())),
+	});
+	overrides.insert(replaced, AccountOverride {
+		storage: Some(StorageOverride::Full(vec![(key, H256::from_low_u64_be(3))].into_iter().collect())),
+		..Default::default()
+	});
+
+	let (mut state, _) = client.latest_state_and_header();
+	client.apply_state_overrides(&mut state, &overrides).unwrap();
+
+	assert_eq!(state.balance(&address).unwrap(), 100.into());
+	assert_eq!(state.nonce(&address).unwrap(), 5.into());
+	assert_eq!(state.code(&address).unwrap().map(|code| (*code).clone()), Some(vec![0x60, 0x00]));
+	assert_eq!(state.storage_at(&address, &key).unwrap(), H256::from_low_u64_be(2));
+	assert_eq!(state.storage_at(&replaced, &key).unwrap(), H256::from_low_u64_be(3));
+
+	let (canonical, _) = client.latest_state_and_header();
+	assert_eq!(canonical.balance(&address).unwrap(), 0.into());
+	assert_eq!(canonical.code(&address).unwrap(), None);
+	assert_eq!(canonical.storage_at(&replaced, &key).unwrap(), H256::zero());
+}
+
+/// Client on a chain with EIP-150 and the latest state with `code` deployed at the returned address.
+fn client_with_code(code: &str) -> (Arc<Client>, State<StateDB>, Header, Address) {
+	use client::Call;
+	use rustc_hex::FromHex;
+	use types::state_override::{AccountOverride, StateOverride};
+
+	let client = test_helpers::generate_dummy_client_with_spec(spec::new_byzantium_test);
+	let contract = Address::from_low_u64_be(0x42);
+	let mut overrides = StateOverride::new();
+	overrides.insert(contract, AccountOverride {
+		code: Some(code.from_hex().unwrap()),
+		..Default::default()
+	});
+
+	let (mut state, header) = client.latest_state_and_header();
+	client.apply_state_overrides(&mut state, &overrides).unwrap();
+	(client, state, header, contract)
+}
+
+fn call_transaction(contract: Address, gas: U256, data: Vec<u8>) -> SignedTransaction {
+	Transaction {
+		nonce: 0.into(),
+		gas_price: 0.into(),
+		gas,
+		action: Action::Call(contract),
+		value: 0.into(),
+		data,
+	}.fake_sign(Address::from_low_u64_be(0x10))
+}
+
+#[test]
+fn estimate_gas_reports_revert_reason() {
+	use client::Call;
+
+	// stores `Error("nope")` in memory and reverts with it
+	let (client, state, header, contract) = client_with_code(
+		"7f08c379a000000000000000000000000000000000000000000000000000000000600052\
+		6020600452\
+		6004602452\
+		7f6e6f706500000000000000000000000000000000000000000000000000000000604452\
+		60646000fd"
+	);
+
+	match client.estimate_gas(&call_transaction(contract, 0.into(), vec![]), &state, &header) {
+		Err(CallError::Reverted(output)) => {
+			assert_eq!(output.len(), 100);
+			assert_eq!(&output[..4], &[0x08, 0xc3, 0x79, 0xa0]);
+			assert_eq!(&output[68..72], b"nope");
+		},
+		other => panic!("expected a revert, got {:?}", other),
+	}
+}
+
+#[test]
+fn estimate_gas_accounts_for_gas_withheld_from_nested_calls() {
+	use client::Call;
+
+	// calls itself with the depth in the calldata decremented, reverting if the nested call fails
+	let (client, state, header, contract) = client_with_code(
+		"60003580156023576001900360005260006000602060006000305af1602357600080fd5b00"
+	);
+	let mut depth = vec![0u8; 32];
+	depth[31] = 10;
+
+	let estimate = client.estimate_gas(&call_transaction(contract, 0.into(), depth.clone()), &state, &header).unwrap();
+
+	let analytics = CallAnalytics::default();
+	let executed = client.call(&call_transaction(contract, estimate, depth.clone()), analytics, &mut state.clone(), &header).unwrap();
+	assert_eq!(executed.exception, None);
+	// the gas withheld from the nested calls is needed, but never used
+	assert!(executed.gas_used < estimate);
+
+	let executed = client.call(&call_transaction(contract, estimate - 1, depth), analytics, &mut state.clone(), &header).unwrap();
+	assert!(executed.exception.is_some());
+}
+
+// Client indexing logs, with block #1 deploying a contract which logs the call value as its topic.
+fn client_with_log_contract(db: Arc<dyn BlockChainDB>, index_logs: bool) -> (Arc<Client>, spec::Spec, Address) {
+	use rustc_hex::FromHex;
+
+	let test_spec = spec::new_test();
+	let mut config = ClientConfig::default();
+	config.index_logs = index_logs;
+	let client = Client::new(
+		config,
+		&test_spec,
+		db,
+		Arc::new(Miner::new_for_tests(&test_spec, None)),
+		IoChannel::disconnected(),
+	).unwrap();
+
+	let contract = Address::from_low_u64_be(0x42);
+	if client.chain_info().best_block_number == 0 {
+		let mut b = client.prepare_open_block(Address::zero(), (3141562.into(), 31415620.into()), vec![]).unwrap();
+		{
+			let state = b.block_mut().state_mut();
+			state.add_balance(&Address::from_low_u64_be(0x10), &1_000_000.into(), CleanupMode::NoEmpty).unwrap();
+			// CALLVALUE PUSH1 0 PUSH1 0 LOG1 STOP
+			state.init_code(&contract, "3460006000a100".from_hex().unwrap()).unwrap();
+			state.commit().unwrap();
+		}
+		let b = b.close_and_lock().unwrap().seal(&*test_spec.engine, vec![]).unwrap();
+		client.import_sealed_block(b).unwrap();
+	}
+	(client, test_spec, contract)
+}
+
+// Seal a block on top of `parent`, calling `contract` once for every value.
+fn seal_block_with_calls(client: &Client, test_spec: &spec::Spec, parent: H256, contract: Address, values: &[u64]) -> SealedBlock {
+	let sender = Address::from_low_u64_be(0x10);
+	let parent = client.block_header(BlockId::Hash(parent)).unwrap().decode(test_spec.params().eip1559_transition).unwrap();
+	let state = client.state_at(BlockId::Hash(parent.hash())).unwrap();
+	let nonce = state.nonce(&sender).unwrap();
+	let mut b = OpenBlock::new(
+		&*test_spec.engine,
+		Default::default(),
+		false,
+		state.drop().1,
+		&parent,
+		Arc::new(vec![parent.hash()]),
+		Address::zero(),
+		(3141562.into(), 31415620.into()),
+		vec![],
+		false,
+	).unwrap();
+	b.set_timestamp(parent.timestamp() + 10);
+	for (i, value) in values.iter().enumerate() {
+		b.push_transaction(Transaction {
+			nonce: nonce + U256::from(i),
+			gas_price: 0.into(),
+			gas: 100_000.into(),
+			action: Action::Call(contract),
+			value: (*value).into(),
+			data: vec![],
+		}.fake_sign(sender), None).unwrap();
+	}
+	b.close_and_lock().unwrap().seal(&*test_spec.engine, vec![]).unwrap()
+}
+
+// Block numbers and topics of the logs matching an address and first topic.
+fn logged(client: &Client, address: Option<Address>, topic: Option<u64>) -> Vec<(u64, H256)> {
+	client.logs(Filter {
+		from_block: BlockId::Earliest,
+		to_block: BlockId::Latest,
+		address: address.map(|a| vec![a]),
+		topics: vec![topic.map(|t| vec![H256::from_low_u64_be(t)])],
+		limit: None,
+	}).unwrap().into_iter().map(|log| (log.block_number, log.entry.topics[0])).collect()
+}
+
+#[test]
+fn log_index_follows_reorgs() {
+	let (client, test_spec, contract) = client_with_log_contract(test_helpers::new_db(), true);
+	let topic = H256::from_low_u64_be;
+	let block1 = client.chain_info().best_block_hash;
+
+	let a2 = seal_block_with_calls(&client, &test_spec, block1, contract, &[1, 2]);
+	client.import_sealed_block(a2).unwrap();
+	assert_eq!(logged(&client, Some(contract), None), vec![(2, topic(1)), (2, topic(2))]);
+	assert_eq!(logged(&client, None, Some(2)), vec![(2, topic(2))]);
+	assert_eq!(logged(&client, Some(contract), Some(3)), vec![]);
+	assert_eq!(logged(&client, Some(Address::from_low_u64_be(0x43)), None), vec![]);
+
+	// a fork of the same difficulty doesn't replace the best block
+	let b2 = seal_block_with_calls(&client, &test_spec, block1, contract, &[3]);
+	let b2_hash = b2.header.hash();
+	client.import_sealed_block(b2).unwrap();
+	assert_eq!(logged(&client, Some(contract), Some(3)), vec![]);
+
+	// extending it does, retracting the logs of the old block
+	let b3 = seal_block_with_calls(&client, &test_spec, b2_hash, contract, &[1]);
+	client.import_sealed_block(b3).unwrap();
+	assert_eq!(client.chain_info().best_block_number, 3);
+	assert_eq!(logged(&client, Some(contract), None), vec![(2, topic(3)), (3, topic(1))]);
+	assert_eq!(logged(&client, None, Some(1)), vec![(3, topic(1))]);
+	assert_eq!(logged(&client, None, Some(2)), vec![]);
+	assert_eq!(logged(&client, Some(contract), Some(3)), vec![(2, topic(3))]);
+
+	// filters on neither the address nor the first topic use blooms
+	assert_eq!(logged(&client, None, None), vec![(2, topic(3)), (3, topic(1))]);
+}
+
+#[test]
+fn log_index_backfills_ancient_blocks() {
+	let db = test_helpers::new_db();
+	let topic = H256::from_low_u64_be;
+	{
+		let (client, test_spec, contract) = client_with_log_contract(db.clone(), false);
+		for value in 1..4 {
+			let best = client.chain_info().best_block_hash;
+			let b = seal_block_with_calls(&client, &test_spec, best, contract, &[value]);
+			client.import_sealed_block(b).unwrap();
+		}
+	}
+
+	let (client, test_spec, contract) = client_with_log_contract(db.clone(), true);
+	let best = client.chain_info().best_block_hash;
+	let b = seal_block_with_calls(&client, &test_spec, best, contract, &[2]);
+	client.import_sealed_block(b).unwrap();
+
+	let expected = vec![(2, topic(1)), (3, topic(2)), (4, topic(3)), (5, topic(2))];
+	assert_eq!(logged(&client, Some(contract), None), expected);
+	assert_eq!(logged(&client, None, Some(2)), vec![(3, topic(2)), (5, topic(2))]);
+
+	client.tick(true);
+	assert_eq!(logged(&client, Some(contract), None), expected);
+	assert_eq!(logged(&client, None, Some(2)), vec![(3, topic(2)), (5, topic(2))]);
+
+	// the completed index is kept across restarts
+	drop(client);
+	let (client, _, contract) = client_with_log_contract(db, true);
+	assert_eq!(logged(&client, Some(contract), None), expected);
+}
+
+#[test]
+fn reset_blockchain_updates_log_index() {
+	let db = test_helpers::new_db();
+	let (client, test_spec, contract) = client_with_log_contract(db.clone(), true);
+	for value in 1..4 {
+		let best = client.chain_info().best_block_hash;
+		let b = seal_block_with_calls(&client, &test_spec, best, contract, &[value]);
+		client.import_sealed_block(b).unwrap();
+	}
+
+	assert!(client.reset(2).is_ok());
+	drop(client);
+
+	// the index is consistent with the new best block
+	let (client, test_spec, contract) = client_with_log_contract(db, true);
+	assert_eq!(client.chain_info().best_block_number, 2);
+	let best = client.chain_info().best_block_hash;
+	let b = seal_block_with_calls(&client, &test_spec, best, contract, &[4]);
+	client.import_sealed_block(b).unwrap();
+	assert_eq!(logged(&client, Some(contract), None), vec![(2, H256::from_low_u64_be(1)), (3, H256::from_low_u64_be(4))]);
+}
+
 #[test]
 fn reset_blockchain() {
 	let client = get_test_client_with_blocks(get_good_dummy_block_seq(19));
@@ -468,3 +824,17 @@ fn import_export_binary() {
 	assert!(client.block_header(BlockId::Number(17)).is_some());
 	assert!(client.block_header(BlockId::Number(16)).is_some());
 }
+
+#[test]
+fn import_blocks_spanning_several_batches() {
+	let source = generate_dummy_client_with_data(600, 2, &[1.into()]);
+	let mut out = Vec::new();
+	source.export_blocks(Box::new(&mut out), BlockId::Number(1), BlockId::Latest, Some(DataFormat::Binary)).unwrap();
+
+	let client = generate_dummy_client_with_spec(spec::new_null);
+	client.import_blocks(Box::new(&*out), Some(DataFormat::Binary)).unwrap();
+
+	assert_eq!(client.chain_info().best_block_number, 600);
+	assert_eq!(client.chain_info().best_block_hash, source.chain_info().best_block_hash);
+	assert_eq!(client.block(BlockId::Number(300)).unwrap().transactions_count(), 2);
+}
@@ -0,0 +1,247 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Migration of state between journal strategies.
+//!
+//! Only the state reachable from a single root is carried over: every account trie node, storage
+//! trie node and contract code is copied into the destination `JournalDB` as canonical data, and
+//! the destination journal is then started at the block the root belongs to. Historical states
+//! are dropped.
+
+use std::io;
+
+use account_db::AccountDB;
+use common_types::{
+	basic_account::BasicAccount,
+	errors::EthcoreError,
+};
+use ethereum_types::H256;
+use ethtrie::TrieDB;
+use hash_db::{AsHashDB, HashDB, Prefix, EMPTY_PREFIX};
+use journaldb::JournalDB;
+use keccak_hash::KECCAK_EMPTY;
+use keccak_hasher::KeccakHasher;
+use kvdb::{DBTransaction, DBValue};
+use log::trace;
+use parking_lot::Mutex;
+use trie_db::Trie;
+
+/// Number of state nodes written to the destination in a single transaction.
+const NODES_PER_BATCH: usize = 16 * 1024;
+
+// Read-only `HashDB` remembering every value fetched through it. It is only ever handed out as a
+// shared reference to `TrieDB`, which never writes, so the mutating methods can't be reached.
+struct RecordingDB<'db> {
+	db: &'db dyn HashDB<KeccakHasher, DBValue>,
+	recorded: Mutex<Vec<(H256, DBValue)>>,
+}
+
+impl<'db> AsHashDB<KeccakHasher, DBValue> for RecordingDB<'db> {
+	fn as_hash_db(&self) -> &dyn HashDB<KeccakHasher, DBValue> { self }
+	fn as_hash_db_mut(&mut self) -> &mut dyn HashDB<KeccakHasher, DBValue> { self }
+}
+
+impl<'db> HashDB<KeccakHasher, DBValue> for RecordingDB<'db> {
+	fn get(&self, key: &H256, prefix: Prefix) -> Option<DBValue> {
+		let value = self.db.get(key, prefix);
+		if let Some(ref value) = value {
+			self.recorded.lock().push((*key, value.clone()));
+		}
+		value
+	}
+
+	fn contains(&self, key: &H256, prefix: Prefix) -> bool {
+		self.db.contains(key, prefix)
+	}
+
+	fn insert(&mut self, _prefix: Prefix, _value: &[u8]) -> H256 {
+		unreachable!("RecordingDB is only read through a shared reference; qed")
+	}
+
+	fn emplace(&mut self, _key: H256, _prefix: Prefix, _value: DBValue) {
+		unreachable!("RecordingDB is only read through a shared reference; qed")
+	}
+
+	fn remove(&mut self, _key: &H256, _prefix: Prefix) {
+		unreachable!("RecordingDB is only read through a shared reference; qed")
+	}
+}
+
+// Walk the whole state under `root`, fetching every trie node, storage node and code once.
+// `on_item` is called after every account and storage entry with the number of accounts visited.
+fn walk_state(
+	db: &dyn HashDB<KeccakHasher, DBValue>,
+	root: &H256,
+	on_item: &mut dyn FnMut(u64) -> Result<(), EthcoreError>,
+) -> Result<u64, EthcoreError> {
+	let db = &db;
+	let trie = TrieDB::new(db, root).map_err(|e| *e)?;
+	let mut accounts = 0;
+	for item in trie.iter().map_err(|e| *e)? {
+		let (key, value) = item.map_err(|e| *e)?;
+		let account: BasicAccount = rlp::decode(&value)?;
+		let address_hash = H256::from_slice(&key);
+
+		let account_db = AccountDB::from_hash(*db, address_hash);
+		let account_db = &(&account_db as &dyn HashDB<_, _>);
+		let storage = TrieDB::new(account_db, &account.storage_root).map_err(|e| *e)?;
+		for entry in storage.iter().map_err(|e| *e)? {
+			entry.map_err(|e| *e)?;
+			on_item(accounts)?;
+		}
+
+		if account.code_hash != KECCAK_EMPTY && account_db.get(&account.code_hash, EMPTY_PREFIX).is_none() {
+			return Err(EthcoreError::Msg(format!("Missing code {:?} of account {:?}", account.code_hash, address_hash)));
+		}
+
+		accounts += 1;
+		on_item(accounts)?;
+	}
+	Ok(accounts)
+}
+
+// Move the recorded nodes into `dest` as canonical data, bypassing the journal.
+fn flush(recorded: &mut Vec<(H256, DBValue)>, dest: &mut dyn JournalDB) -> io::Result<u64> {
+	let count = recorded.len() as u64;
+	for (key, value) in recorded.drain(..) {
+		dest.emplace(key, EMPTY_PREFIX, value);
+	}
+	let mut batch = DBTransaction::new();
+	dest.inject(&mut batch)?;
+	dest.backing().write(batch)?;
+	Ok(count)
+}
+
+/// Copy the state under `root` from `source` into `dest`. The nodes are injected directly into
+/// the destination's backing database, so the copy can safely be repeated if interrupted.
+///
+/// `progress` is called after every written batch with the number of accounts and nodes copied
+/// so far. Returns the total number of nodes written.
+pub fn copy_state<F>(
+	source: &dyn JournalDB,
+	dest: &mut dyn JournalDB,
+	root: &H256,
+	mut progress: F,
+) -> Result<u64, EthcoreError> where F: FnMut(u64, u64) {
+	let recorder = RecordingDB { db: source.as_hash_db(), recorded: Mutex::new(Vec::new()) };
+	let mut nodes = 0;
+	let accounts = walk_state(&recorder, root, &mut |accounts| {
+		let mut recorded = recorder.recorded.lock();
+		if recorded.len() >= NODES_PER_BATCH {
+			nodes += flush(&mut recorded, dest)?;
+			progress(accounts, nodes);
+		}
+		Ok(())
+	})?;
+	nodes += flush(&mut recorder.recorded.lock(), dest)?;
+	progress(accounts, nodes);
+	trace!(target: "migration", "Copied {} state nodes of {} accounts", nodes, accounts);
+	Ok(nodes)
+}
+
+/// Start the journal of a freshly copied `dest` at block `era` with hash `id`, making `dest`
+/// a valid, non-empty state database whose history begins at that block.
+pub fn finalize(dest: &mut dyn JournalDB, era: u64, id: &H256) -> io::Result<()> {
+	let mut batch = DBTransaction::new();
+	dest.journal_under(&mut batch, era, id)?;
+	dest.backing().write(batch)
+}
+
+/// Check that the complete state under `root` is available in `db`.
+/// Returns the number of accounts found.
+pub fn verify_state(db: &dyn JournalDB, root: &H256) -> Result<u64, EthcoreError> {
+	walk_state(db.as_hash_db(), root, &mut |_| Ok(()))
+}
+
+#[cfg(test)]
+mod tests {
+	use account_db::AccountDBMut;
+	use common_types::basic_account::BasicAccount;
+	use ethcore::test_helpers::new_db;
+	use ethereum_types::{Address, H256, U256};
+	use ethtrie::TrieDBMut;
+	use hash_db::{AsHashDB, HashDB, EMPTY_PREFIX};
+	use journaldb::{self, Algorithm};
+	use keccak_hash::keccak;
+	use keccak_hasher::KeccakHasher;
+	use kvdb::DBValue;
+	use trie_db::TrieMut;
+
+	use super::{copy_state, finalize, verify_state};
+
+	fn archive_state(db: &mut dyn HashDB<KeccakHasher, DBValue>) -> H256 {
+		let mut root = H256::zero();
+		let mut accounts = Vec::new();
+		for i in 1..100u64 {
+			let address_hash = keccak(Address::from_low_u64_be(i));
+			let mut account_db = AccountDBMut::from_hash(db, address_hash);
+			let code_hash = account_db.insert(EMPTY_PREFIX, &i.to_be_bytes());
+			let mut storage_root = H256::zero();
+			{
+				let mut storage = TrieDBMut::new(&mut account_db, &mut storage_root);
+				for j in 0..i {
+					storage.insert(keccak(j.to_be_bytes()).as_bytes(), &rlp::encode(&j)).unwrap();
+				}
+			}
+			accounts.push((address_hash, BasicAccount {
+				nonce: U256::zero(),
+				balance: U256::from(i),
+				storage_root,
+				code_hash,
+				code_version: U256::zero(),
+			}));
+		}
+		{
+			let mut trie = TrieDBMut::new(db, &mut root);
+			for (address_hash, account) in accounts {
+				trie.insert(address_hash.as_bytes(), &rlp::encode(&account)).unwrap();
+			}
+		}
+		root
+	}
+
+	#[test]
+	fn copies_state_into_overlay_recent() {
+		let mut source = journaldb::new(new_db().key_value().clone(), Algorithm::Archive, ethcore_db::COL_STATE);
+		let root = archive_state(source.as_hash_db_mut());
+		let db = new_db();
+		let mut dest = journaldb::new(db.key_value().clone(), Algorithm::OverlayRecent, ethcore_db::COL_STATE);
+		assert!(verify_state(&*dest, &root).is_err());
+
+		let mut copied_accounts = 0;
+		let nodes = copy_state(&*source, &mut *dest, &root, |accounts, _| copied_accounts = accounts).unwrap();
+		assert!(nodes > 0);
+		assert_eq!(copied_accounts, 99);
+
+		finalize(&mut *dest, 1000, &H256::from_low_u64_be(1000)).unwrap();
+		let dest = journaldb::new(db.key_value().clone(), Algorithm::OverlayRecent, ethcore_db::COL_STATE);
+		assert!(!dest.is_empty());
+		assert_eq!(dest.latest_era(), Some(1000));
+		assert_eq!(verify_state(&*dest, &root).unwrap(), 99);
+	}
+
+	#[test]
+	fn copy_is_repeatable() {
+		let mut source = journaldb::new(new_db().key_value().clone(), Algorithm::Archive, ethcore_db::COL_STATE);
+		let root = archive_state(source.as_hash_db_mut());
+		let db = new_db();
+		let mut dest = journaldb::new(db.key_value().clone(), Algorithm::OverlayRecent, ethcore_db::COL_STATE);
+		let first = copy_state(&*source, &mut *dest, &root, |_, _| {}).unwrap();
+		let second = copy_state(&*source, &mut *dest, &root, |_, _| {}).unwrap();
+		assert_eq!(first, second);
+		assert_eq!(verify_state(&*dest, &root).unwrap(), 99);
+	}
+}
@@ -125,6 +125,8 @@ pub struct SyncConfig {
 	pub light_subprotocol_name: [u8; 3],
 	/// Fork block to check
 	pub fork_block: Option<(BlockNumber, H256)>,
+	/// Number of the first block whose header carries a base fee (EIP-1559)
+	pub eip1559_transition: BlockNumber,
 	/// Enable snapshot sync
 	pub warp_sync: WarpSync,
 	/// Enable light client server.
@@ -140,6 +142,7 @@ impl Default for SyncConfig {
 			subprotocol_name: ETH_PROTOCOL,
 			light_subprotocol_name: LIGHT_PROTOCOL,
 			fork_block: None,
+			eip1559_transition: BlockNumber::max_value(),
 			warp_sync: WarpSync::Disabled,
 			serve_light: false,
 		}
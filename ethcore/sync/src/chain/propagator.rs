@@ -27,7 +27,7 @@ use network::client_version::ClientCapabilities;
 use network::PeerId;
 use rand::RngCore;
 use rlp::{Encodable, RlpStream};
-use common_types::{blockchain_info::BlockChainInfo, transaction::SignedTransaction, BlockNumber};
+use common_types::{blockchain_info::BlockChainInfo, transaction::{SignedTransaction, TypedTxId}, BlockNumber};
 
 use super::sync_packet::SyncPacket::{
 	NewBlockHashesPacket,
@@ -39,6 +39,7 @@ use super::sync_packet::SyncPacket::{
 use super::{
 	random,
 	ChainSync,
+	ETH_PROTOCOL_VERSION_65,
 	MAX_TRANSACTION_PACKET_SIZE,
 	MAX_PEER_LAG_PROPAGATION,
 	MAX_PEERS_PROPAGATION,
@@ -112,8 +113,10 @@ impl SyncPropagator {
 			return 0;
 		}
 
-		let (transactions, service_transactions): (Vec<_>, Vec<_>) = transactions.iter()
+		let (typed_transactions, transactions): (Vec<_>, Vec<_>) = transactions.iter()
 			.map(|tx| tx.signed())
+			.partition(|tx| tx.tx_type() != TypedTxId::Legacy);
+		let (transactions, service_transactions): (Vec<_>, Vec<_>) = transactions.into_iter()
 			.partition(|tx| !tx.gas_price.is_zero());
 
 		// usual transactions could be propagated to all peers
@@ -125,6 +128,17 @@ impl SyncPropagator {
 			);
 		}
 
+		// peers before eth/65 can't decode typed transactions
+		if !typed_transactions.is_empty() {
+			let typed_transactions_peers = SyncPropagator::select_peers_for_transactions(sync, |peer_id| {
+				sync.peers.get(peer_id).map_or(false, |peer| peer.protocol_version >= ETH_PROTOCOL_VERSION_65.0)
+			});
+			let typed_transactions_affected_peers = SyncPropagator::propagate_transactions_to_peers(
+				sync, io, typed_transactions_peers, typed_transactions, &mut should_continue
+			);
+			affected_peers.extend(&typed_transactions_affected_peers);
+		}
+
 		// most of times service_transactions will be empty
 		// => there's no need to merge packets
 		if !service_transactions.is_empty() {
@@ -338,7 +352,7 @@ mod tests {
 
 	use crate::{
 		api::SyncConfig,
-		chain::{ChainSync, ForkConfirmation, PeerAsking, PeerInfo},
+		chain::{ChainSync, ForkConfirmation, PeerAsking, PeerInfo, ETH_PROTOCOL_VERSION_65},
 		tests::{helpers::TestIo, snapshot::TestSnapshotService},
 	};
 
@@ -608,6 +622,31 @@ mod tests {
 		assert_eq!(io.packets.len(), 2);
 	}
 
+	#[test]
+	fn should_propagate_typed_transaction_to_eth65_peers_only() {
+		let mut client = TestBlockChainClient::new_with_spec(spec::new_berlin_test());
+		client.insert_access_list_transaction_to_queue();
+		let block_hash = client.block_hash_delta_minus(1);
+		let mut sync = ChainSync::new(SyncConfig::default(), &client, None);
+		let queue = RwLock::new(VecDeque::new());
+		let ss = TestSnapshotService::new();
+		let mut io = TestIo::new(&mut client, &ss, &queue, None, None);
+
+		// when peer#1 speaks eth/63
+		insert_dummy_peer(&mut sync, 1, block_hash);
+		// and peer#2 negotiated eth/65
+		insert_dummy_peer(&mut sync, 2, block_hash);
+		sync.peers.get_mut(&2).unwrap().protocol_version = ETH_PROTOCOL_VERSION_65.0;
+
+		// and new typed transaction is propagated to peers
+		let peer_count = SyncPropagator::propagate_new_transactions(&mut sync, &mut io, || true);
+
+		// only peer#2 is receiving it
+		assert_eq!(1, peer_count);
+		assert!(io.packets.iter().any(|p| p.packet_id == 0x02 && p.recipient == 2)); // TRANSACTIONS_PACKET
+		assert_eq!(io.packets.len(), 1);
+	}
+
 	#[test]
 	fn should_propagate_service_transaction_is_sent_as_separate_message() {
 		let mut client = TestBlockChainClient::new();
@@ -238,15 +238,32 @@ impl SyncSupplier {
 			return Ok(None);
 		}
 		count = cmp::min(count, MAX_BODIES_TO_SEND);
+		let earliest_body = io.chain().pruning_info().earliest_body;
 		let mut added = 0usize;
 		let mut data = Bytes::new();
 		for i in 0..count {
-			if let Some(body) = io.chain().block_body(BlockId::Hash(r.val_at::<H256>(i)?)) {
-				data.append(&mut body.into_inner());
-				added += 1;
-				// Check that the packet won't be oversized
-				if data.len() > payload_soft_limit {
-					break;
+			let hash = r.val_at::<H256>(i)?;
+			match io.chain().block_body(BlockId::Hash(hash)) {
+				Some(body) => {
+					data.append(&mut body.into_inner());
+					added += 1;
+					// Check that the packet won't be oversized
+					if data.len() > payload_soft_limit {
+						break;
+					}
+				}
+				None => {
+					let pruned = earliest_body.map_or(false, |earliest| {
+						io.chain().block_number(BlockId::Hash(hash)).map_or(false, |number| number < earliest)
+					});
+					if pruned {
+						// stop at the first pruned body instead of answering with gaps
+						if added == 0 {
+							trace!(target: "sync", "{} -> GetBlockBodies: refusing to serve pruned bodies", peer_id);
+							return Ok(None);
+						}
+						break;
+					}
 				}
 			}
 		}
@@ -438,14 +455,14 @@ mod test {
 		}
 
 		fn to_header_vec(rlp: RlpResponseResult) -> Vec<SyncHeader> {
-			Rlp::new(&rlp.unwrap().unwrap().1.out()).iter().map(|r| SyncHeader::from_rlp(r.as_raw().to_vec()).unwrap()).collect()
+			Rlp::new(&rlp.unwrap().unwrap().1.out()).iter().map(|r| SyncHeader::from_rlp(r.as_raw().to_vec(), BlockNumber::max_value()).unwrap()).collect()
 		}
 
 		let mut client = TestBlockChainClient::new();
 		client.add_blocks(100, EachBlockWith::Nothing);
 		let blocks: Vec<_> = (0 .. 100)
 			.map(|i| (&client as &dyn BlockChainClient).block(BlockId::Number(i as BlockNumber)).map(|b| b.into_inner()).unwrap()).collect();
-		let headers: Vec<_> = blocks.iter().map(|b| SyncHeader::from_rlp(Rlp::new(b).at(0).unwrap().as_raw().to_vec()).unwrap()).collect();
+		let headers: Vec<_> = blocks.iter().map(|b| SyncHeader::from_rlp(Rlp::new(b).at(0).unwrap().as_raw().to_vec(), BlockNumber::max_value()).unwrap()).collect();
 		let hashes: Vec<_> = headers.iter().map(|h| h.header.hash()).collect();
 
 		let queue = RwLock::new(VecDeque::new());
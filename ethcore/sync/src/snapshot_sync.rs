@@ -173,7 +173,7 @@ mod test {
 
 	use bytes::Bytes;
 	use keccak_hash::keccak;
-	use common_types::snapshot::ManifestData;
+	use common_types::snapshot::{Compression, ManifestData};
 
 	fn is_empty(snapshot: &Snapshot) -> bool {
 		snapshot.pending_block_chunks.is_empty() &&
@@ -193,6 +193,9 @@ mod test {
 			state_root: H256::zero(),
 			block_number: 42,
 			block_hash: H256::zero(),
+			compression: Compression::Snappy,
+			state_checksums: Vec::new(),
+			block_checksums: Vec::new(),
 		};
 		let mhash = keccak(manifest.clone().into_rlp());
 		(manifest, mhash, state_chunks, block_chunks)
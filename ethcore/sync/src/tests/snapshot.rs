@@ -30,7 +30,7 @@ use parking_lot::Mutex;
 use snapshot::SnapshotService;
 use common_types::{
 	BlockNumber,
-	snapshot::{ManifestData, RestorationStatus},
+	snapshot::{Compression, CreationStatus, ManifestData, RestorationStatus},
 };
 
 #[derive(Default)]
@@ -59,6 +59,9 @@ impl TestSnapshotService {
 			state_root: H256::zero(),
 			block_number: block_number,
 			block_hash: block_hash,
+			compression: Compression::Snappy,
+			state_checksums: Vec::new(),
+			block_checksums: Vec::new(),
 		};
 		let mut chunks: HashMap<H256, Bytes> = state_chunks.into_iter().map(|data| (keccak(&data), data)).collect();
 		chunks.extend(block_chunks.into_iter().map(|data| (keccak(&data), data)));
@@ -123,6 +126,10 @@ impl SnapshotService for TestSnapshotService {
 		self.block_restoration_chunks.lock().clear();
 	}
 
+	fn creation_status(&self) -> CreationStatus {
+		CreationStatus::Inactive
+	}
+
 	fn abort_snapshot(&self) {}
 
 	fn restore_state_chunk(&self, hash: H256, chunk: Bytes) {
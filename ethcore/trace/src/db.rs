@@ -16,6 +16,7 @@
 
 //! Trace database.
 use std::collections::HashMap;
+use std::iter;
 use std::sync::Arc;
 
 use ethcore_blockchain::{BlockProvider, BlockChainDB, TransactionAddress};
@@ -24,7 +25,7 @@ use ethcore_db::{
 	cache_manager::CacheManager,
 	Key, Writable, Readable, CacheUpdatePolicy,
 };
-use ethereum_types::{H256, H264};
+use ethereum_types::{Bloom, H256, H264};
 use kvdb::DBTransaction;
 use parity_util_mem::MallocSizeOfExt;
 use parking_lot::RwLock;
@@ -150,6 +151,19 @@ impl<T> TraceDB<T> where T: DatabaseExtras {
 		});
 	}
 
+	/// Deletes the traces of the given blocks and clears their trace blooms.
+	pub fn prune(&self, batch: &mut DBTransaction, blocks: &[(BlockNumber, H256)]) {
+		let mut traces = self.traces.write();
+		let empty_bloom = Bloom::default();
+		for (number, hash) in blocks {
+			Writable::delete::<FlatBlockTraces, H264>(batch, db::COL_TRACE, hash);
+			traces.remove(hash);
+			self.db.trace_blooms()
+				.insert_blooms(*number, iter::once(&empty_bloom))
+				.expect("Low level database error. Some issue with disk?");
+		}
+	}
+
 	/// Returns traces for block with hash.
 	fn traces(&self, block_hash: &H256) -> Option<FlatBlockTraces> {
 		let result = self.db.key_value().read_with_cache(db::COL_TRACE, &self.traces, block_hash);
@@ -347,6 +361,10 @@ impl<T> TraceDatabase for TraceDB<T> where T: DatabaseExtras {
 	}
 
 	fn filter(&self, filter: &Filter) -> Vec<LocalizedTrace> {
+		self.filter_limited(filter, 0, usize::max_value())
+	}
+
+	fn filter_limited(&self, filter: &Filter, after: usize, count: usize) -> Vec<LocalizedTrace> {
 		let possibilities = filter.bloom_possibilities();
 		let numbers = self.db.trace_blooms()
 			.filter(filter.range.start as u64, filter.range.end as u64, &possibilities)
@@ -361,6 +379,8 @@ impl<T> TraceDatabase for TraceDB<T> where T: DatabaseExtras {
 					.expect("Expected to find a trace. Db is probably corrupted.");
 				self.matching_block_traces(filter, traces, hash, number)
 			})
+			.skip(after)
+			.take(count)
 			.collect()
 	}
 }
@@ -598,6 +618,14 @@ mod tests {
 		assert_eq!(traces[0], create_simple_localized_trace(1, block_1.clone(), tx_1.clone()));
 		assert_eq!(traces[1], create_simple_localized_trace(2, block_2.clone(), tx_2.clone()));
 
+		let traces = tracedb.filter_limited(&filter, 0, 1);
+		assert_eq!(traces, vec![create_simple_localized_trace(1, block_1.clone(), tx_1.clone())]);
+
+		let traces = tracedb.filter_limited(&filter, 1, 5);
+		assert_eq!(traces, vec![create_simple_localized_trace(2, block_2.clone(), tx_2.clone())]);
+
+		assert!(tracedb.filter_limited(&filter, 2, 5).is_empty());
+
 		assert!(tracedb.block_traces(0).is_some(), "Genesis trace should be always present.");
 
 		let traces = tracedb.block_traces(1).unwrap();
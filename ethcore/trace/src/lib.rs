@@ -27,6 +27,7 @@ mod db;
 mod executive_tracer;
 mod import;
 mod noop_tracer;
+mod struct_logger;
 mod types;
 
 pub use crate::{
@@ -36,6 +37,7 @@ pub use crate::{
 	executive_tracer::{ExecutiveTracer, ExecutiveVMTracer},
 	import::ImportRequest,
 	noop_tracer::{NoopTracer, NoopVMTracer},
+	struct_logger::{StructLog, StructLogger, StructLoggerConfig},
 	types::{
 		Tracing,
 		error::Error as TraceError,
@@ -129,4 +131,8 @@ pub trait Database {
 
 	/// Filter traces matching given filter.
 	fn filter(&self, filter: &Filter) -> Vec<LocalizedTrace>;
+
+	/// Filter traces matching given filter, skipping the first `after` matches and returning at
+	/// most `count` of them. Blocks after the last returned trace are not read.
+	fn filter_limited(&self, filter: &Filter, after: usize, count: usize) -> Vec<LocalizedTrace>;
 }
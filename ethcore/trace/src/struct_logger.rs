@@ -0,0 +1,263 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Opcode level tracer capturing the state of the VM before every instruction.
+
+use std::collections::BTreeMap;
+use ethereum_types::{H256, U256, BigEndianHash};
+use evm::Instruction;
+use crate::VMTracer;
+
+/// Parts of the VM state left out of every step, to keep large traces small.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StructLoggerConfig {
+	/// Don't capture the memory.
+	pub disable_memory: bool,
+	/// Don't capture the stack.
+	pub disable_stack: bool,
+	/// Don't capture the storage.
+	pub disable_storage: bool,
+}
+
+/// State of the VM before executing a single instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructLog {
+	/// Program counter.
+	pub pc: usize,
+	/// The instruction.
+	pub instruction: u8,
+	/// Gas left before executing the instruction.
+	pub gas: U256,
+	/// Gas cost of the instruction, zero if it failed before the cost was known.
+	pub gas_cost: U256,
+	/// Depth of the call frame, starting at 1.
+	pub depth: usize,
+	/// Stack, bottom first.
+	pub stack: Option<Vec<U256>>,
+	/// Memory of the call frame.
+	pub memory: Option<Vec<u8>>,
+	/// Storage slots read or written by the call frame so far, including this instruction.
+	pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+impl StructLog {
+	/// Mnemonic of the instruction, `None` if it is undefined.
+	pub fn op_name(&self) -> Option<&'static str> {
+		Instruction::from_u8(self.instruction).map(|instruction| instruction.info().name)
+	}
+}
+
+/// State of a single call frame.
+#[derive(Default)]
+struct Frame {
+	stack: Vec<U256>,
+	memory: Vec<u8>,
+	storage: BTreeMap<H256, H256>,
+	/// Index of the log of the instruction being executed, with the storage slot it reads.
+	current: Option<(usize, Option<H256>)>,
+}
+
+/// VM tracer building geth style struct logs while the transaction executes, so every step is
+/// captured once.
+pub struct StructLogger {
+	config: StructLoggerConfig,
+	frames: Vec<Frame>,
+	logs: Vec<StructLog>,
+}
+
+impl StructLogger {
+	/// Create a new top-level instance.
+	pub fn new(config: StructLoggerConfig) -> Self {
+		StructLogger {
+			config,
+			frames: vec![],
+			logs: vec![],
+		}
+	}
+
+	fn frame(&mut self) -> &mut Frame {
+		self.frames.last_mut().expect("instructions are only executed within a call frame; qed")
+	}
+}
+
+impl VMTracer for StructLogger {
+	type Output = Vec<StructLog>;
+
+	fn trace_next_instruction(&mut self, pc: usize, instruction: u8, current_gas: U256) -> bool {
+		let config = self.config;
+		let depth = self.frames.len();
+		let index = self.logs.len();
+		let frame = self.frame();
+		frame.current = Some((index, None));
+		let log = StructLog {
+			pc,
+			instruction,
+			gas: current_gas,
+			gas_cost: U256::zero(),
+			depth,
+			stack: if config.disable_stack { None } else { Some(frame.stack.clone()) },
+			memory: if config.disable_memory { None } else { Some(frame.memory.clone()) },
+			storage: if config.disable_storage { None } else { Some(frame.storage.clone()) },
+		};
+		self.logs.push(log);
+		true
+	}
+
+	fn trace_prepare_execute(&mut self, _pc: usize, instruction: u8, gas_cost: U256, _mem_written: Option<(usize, usize)>, store_written: Option<(U256, U256)>) {
+		let frame = self.frames.last_mut().expect("instructions are only executed within a call frame; qed");
+		let (index, ref mut sload) = *frame.current.as_mut().expect("trace_next_instruction is called before trace_prepare_execute; qed");
+		let log = &mut self.logs[index];
+		log.gas_cost = gas_cost;
+
+		let written = match store_written {
+			Some((key, value)) => Some((BigEndianHash::from_uint(&key), BigEndianHash::from_uint(&value))),
+			None => {
+				// the value read is only known once the instruction is executed
+				if instruction == Instruction::SLOAD as u8 {
+					*sload = frame.stack.last().map(BigEndianHash::from_uint);
+				}
+				None
+			}
+		};
+		if let Some((key, value)) = written {
+			frame.storage.insert(key, value);
+			if let Some(ref mut storage) = log.storage {
+				storage.insert(key, value);
+			}
+		}
+	}
+
+	fn trace_failed(&mut self) {
+		self.frame().current = None;
+	}
+
+	fn trace_executed(&mut self, _gas_used: U256, stack_push: &[U256], mem: &[u8]) {
+		let config = self.config;
+		let frame = self.frames.last_mut().expect("instructions are only executed within a call frame; qed");
+		let (index, sload) = frame.current.take().expect("trace_next_instruction is called before trace_executed; qed");
+		let log = &mut self.logs[index];
+
+		let args = Instruction::from_u8(log.instruction)
+			.expect("only valid instructions are executed; qed")
+			.info()
+			.args;
+		let len = frame.stack.len().saturating_sub(args);
+		frame.stack.truncate(len);
+		frame.stack.extend_from_slice(stack_push);
+
+		if !config.disable_memory {
+			frame.memory.clear();
+			frame.memory.extend_from_slice(mem);
+		}
+
+		if let (Some(key), Some(value)) = (sload, stack_push.last()) {
+			let value = BigEndianHash::from_uint(value);
+			frame.storage.insert(key, value);
+			if let Some(ref mut storage) = log.storage {
+				storage.insert(key, value);
+			}
+		}
+	}
+
+	fn prepare_subtrace(&mut self, _code: &[u8]) {
+		self.frames.push(Frame::default());
+	}
+
+	fn done_subtrace(&mut self) {
+		self.frames.pop();
+	}
+
+	fn drain(self) -> Option<Vec<StructLog>> { Some(self.logs) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn h256(value: u64) -> H256 {
+		BigEndianHash::from_uint(&U256::from(value))
+	}
+
+	#[test]
+	fn should_capture_state_before_each_instruction() {
+		let mut logger = StructLogger::new(StructLoggerConfig::default());
+		logger.prepare_subtrace(&[]);
+
+		// PUSH1 0x2a
+		assert!(logger.trace_next_instruction(0, Instruction::PUSH1 as u8, 100.into()));
+		logger.trace_prepare_execute(0, Instruction::PUSH1 as u8, 3.into(), None, None);
+		logger.trace_executed(97.into(), &[0x2a.into()], &[]);
+		// PUSH1 0x01
+		logger.trace_next_instruction(2, Instruction::PUSH1 as u8, 97.into());
+		logger.trace_prepare_execute(2, Instruction::PUSH1 as u8, 3.into(), None, None);
+		logger.trace_executed(94.into(), &[0x01.into()], &[]);
+		// SSTORE
+		logger.trace_next_instruction(4, Instruction::SSTORE as u8, 94.into());
+		logger.trace_prepare_execute(4, Instruction::SSTORE as u8, 20_000.into(), None, Some((0x01.into(), 0x2a.into())));
+		logger.trace_executed(0.into(), &[], &[]);
+		// PUSH1 0x01
+		logger.trace_next_instruction(5, Instruction::PUSH1 as u8, 94.into());
+		logger.trace_prepare_execute(5, Instruction::PUSH1 as u8, 3.into(), None, None);
+		logger.trace_executed(91.into(), &[0x01.into()], &[]);
+		// SLOAD
+		logger.trace_next_instruction(7, Instruction::SLOAD as u8, 91.into());
+		logger.trace_prepare_execute(7, Instruction::SLOAD as u8, 800.into(), None, None);
+		logger.trace_executed(0.into(), &[0x2a.into()], &[]);
+		logger.done_subtrace();
+
+		let logs = logger.drain().unwrap();
+		assert_eq!(logs.len(), 5);
+		assert_eq!(logs[0].op_name(), Some("PUSH1"));
+		assert_eq!(logs[0].stack, Some(vec![]));
+		assert_eq!(logs[0].gas, 100.into());
+		assert_eq!(logs[0].gas_cost, 3.into());
+		assert_eq!(logs[0].depth, 1);
+		assert_eq!(logs[2].stack, Some(vec![0x2a.into(), 0x01.into()]));
+		assert_eq!(logs[2].storage, Some(vec![(h256(1), h256(0x2a))].into_iter().collect()));
+		assert_eq!(logs[3].stack, Some(vec![]));
+		assert_eq!(logs[4].op_name(), Some("SLOAD"));
+		assert_eq!(logs[4].storage, Some(vec![(h256(1), h256(0x2a))].into_iter().collect()));
+	}
+
+	#[test]
+	fn should_trace_sub_calls_with_their_own_frame() {
+		let mut logger = StructLogger::new(StructLoggerConfig { disable_memory: true, disable_stack: false, disable_storage: true });
+		logger.prepare_subtrace(&[]);
+
+		logger.trace_next_instruction(0, Instruction::MSIZE as u8, 100.into());
+		logger.trace_prepare_execute(0, Instruction::MSIZE as u8, 2.into(), None, None);
+		logger.trace_executed(98.into(), &[0.into()], &[0; 32]);
+		logger.trace_next_instruction(1, Instruction::CALL as u8, 98.into());
+		logger.trace_prepare_execute(1, Instruction::CALL as u8, 40.into(), None, None);
+
+		logger.prepare_subtrace(&[]);
+		logger.trace_next_instruction(0, Instruction::STOP as u8, 50.into());
+		logger.trace_prepare_execute(0, Instruction::STOP as u8, 0.into(), None, None);
+		logger.trace_executed(50.into(), &[], &[]);
+		logger.done_subtrace();
+
+		logger.trace_executed(58.into(), &[1.into()], &[]);
+		logger.trace_next_instruction(2, Instruction::STOP as u8, 58.into());
+		logger.done_subtrace();
+
+		let logs = logger.drain().unwrap();
+		assert_eq!(logs.iter().map(|log| log.depth).collect::<Vec<_>>(), vec![1, 1, 2, 1]);
+		assert_eq!(logs[1].stack, Some(vec![0.into()]));
+		assert_eq!(logs[2].stack, Some(vec![]));
+		assert_eq!(logs[3].stack, Some(vec![1.into()]));
+		assert!(logs.iter().all(|log| log.memory.is_none() && log.storage.is_none()));
+	}
+}
@@ -26,7 +26,7 @@ use parity_util_mem::MallocSizeOf;
 
 use BlockNumber;
 use header::Header;
-use rlp::{Rlp, RlpStream, Decodable, DecoderError};
+use rlp::{Rlp, RlpStream, DecoderError};
 use transaction::{UnverifiedTransaction, SignedTransaction};
 
 /// A block, encoded as it is on the block chain.
@@ -51,8 +51,9 @@ impl Block {
 	}
 }
 
-impl Decodable for Block {
-	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+impl Block {
+	/// Decode a block, see `Header::decode_rlp` for the meaning of `eip1559_transition`.
+	pub fn decode_rlp(rlp: &Rlp, eip1559_transition: BlockNumber) -> Result<Self, DecoderError> {
 		if rlp.as_raw().len() != rlp.payload_info()?.total() {
 			return Err(DecoderError::RlpIsTooBig);
 		}
@@ -60,9 +61,9 @@ impl Decodable for Block {
 			return Err(DecoderError::RlpIncorrectListLen);
 		}
 		Ok(Block {
-			header: rlp.val_at(0)?,
+			header: Header::decode_rlp(&rlp.at(0)?, eip1559_transition)?,
 			transactions: rlp.list_at(1)?,
-			uncles: rlp.list_at(2)?,
+			uncles: rlp.at(2)?.iter().map(|uncle| Header::decode_rlp(&uncle, eip1559_transition)).collect::<Result<_, _>>()?,
 		})
 	}
 }
@@ -116,3 +117,34 @@ pub struct BranchBecomingCanonChainData {
 	/// Hashes of the blocks which were invalidated.
 	pub retracted: Vec<H256>,
 }
+
+#[cfg(test)]
+mod tests {
+	use ethereum_types::{H256, U256};
+	use rlp::{self, Rlp};
+	use header::Header;
+	use views::BlockView;
+	use super::Block;
+
+	#[test]
+	fn decode_and_encode_block_with_base_fee() {
+		let mut header = Header::new();
+		header.set_number(10);
+		header.set_seal(vec![rlp::encode(&H256::zero()), rlp::encode(&0u64)]);
+		header.set_base_fee(Some(U256::from(1_000_000_000)));
+		let mut uncle = header.clone();
+		uncle.set_number(9);
+		uncle.set_base_fee(Some(U256::from(875_000_000)));
+		let block = Block { header: header.clone(), transactions: vec![], uncles: vec![uncle.clone()] };
+		let encoded = block.rlp_bytes();
+
+		let decoded = Block::decode_rlp(&Rlp::new(&encoded), 9).expect("error decoding block");
+		assert_eq!(decoded, block);
+		assert_eq!(decoded.rlp_bytes(), encoded);
+
+		let view = view!(BlockView, &encoded);
+		assert_eq!(view.header(9), header);
+		assert_eq!(view.uncles(9), vec![uncle.clone()]);
+		assert_eq!(view.uncle_at(0, 9), Some(uncle));
+	}
+}
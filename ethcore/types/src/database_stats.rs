@@ -0,0 +1,73 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Statistics of the key-value database and status of manual compactions.
+
+/// Size statistics of a single database column.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnStats {
+	/// Column index.
+	pub column: u32,
+	/// Estimated size of the live data, in bytes.
+	pub size: u64,
+	/// Estimated number of keys.
+	pub estimated_keys: u64,
+	/// Estimated number of bytes compaction needs to rewrite.
+	pub pending_compaction_bytes: u64,
+}
+
+/// Status of a manual database compaction.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompactionStatus {
+	/// No compaction is running.
+	Inactive,
+	/// A compaction is running.
+	Ongoing {
+		/// Column currently being compacted.
+		column: u32,
+		/// Number of columns already compacted.
+		done: u32,
+		/// Total number of columns to compact.
+		total: u32,
+	},
+	/// The last compaction failed.
+	Failed(String),
+}
+
+impl Default for CompactionStatus {
+	fn default() -> Self {
+		CompactionStatus::Inactive
+	}
+}
+
+impl CompactionStatus {
+	/// Whether a compaction is running.
+	pub fn is_ongoing(&self) -> bool {
+		match *self {
+			CompactionStatus::Ongoing { .. } => true,
+			_ => false,
+		}
+	}
+}
+
+/// Statistics of the key-value database.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DatabaseStats {
+	/// Per-column statistics. Empty if the database backend doesn't report them.
+	pub columns: Vec<ColumnStats>,
+	/// Status of the manual compaction.
+	pub compaction: CompactionStatus,
+}
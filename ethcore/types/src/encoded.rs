@@ -43,9 +43,9 @@ impl Header {
 	/// panics further down the line.
 	pub fn new(encoded: Vec<u8>) -> Self { Header(encoded) }
 
-	/// Upgrade this encoded view to a fully owned `Header` object.
-	pub fn decode(&self) -> Result<FullHeader, rlp::DecoderError> {
-		rlp::decode(&self.0)
+	/// Upgrade this encoded view to a fully owned `Header` object, see `Header::decode_rlp`.
+	pub fn decode(&self, eip1559_transition: BlockNumber) -> Result<FullHeader, rlp::DecoderError> {
+		FullHeader::decode_rlp(&self.rlp(), eip1559_transition)
 	}
 
 	/// Get a borrowed header view onto the data.
@@ -106,6 +106,15 @@ impl Header {
 
 	/// Engine-specific seal fields.
 	pub fn seal(&self) -> Vec<Vec<u8>> { self.view().seal() }
+
+	/// Base fee per gas, the last field of headers from the EIP-1559 transition on.
+	pub fn base_fee(&self, eip1559_transition: BlockNumber) -> Option<U256> {
+		if self.number() < eip1559_transition {
+			return None;
+		}
+		let rlp = self.rlp();
+		rlp.item_count().ok().and_then(|count| rlp.val_at(count - 1).ok())
+	}
 }
 
 /// Owning block body view.
@@ -121,9 +130,9 @@ impl Body {
 	#[inline]
 	pub fn view(&self) -> BodyView { view!(BodyView, &self.0) }
 
-	/// Fully decode this block body.
-	pub fn decode(&self) -> (Vec<UnverifiedTransaction>, Vec<FullHeader>) {
-		(self.view().transactions(), self.view().uncles())
+	/// Fully decode this block body, see `Header::decode_rlp` for the meaning of `eip1559_transition`.
+	pub fn decode(&self, eip1559_transition: BlockNumber) -> (Vec<UnverifiedTransaction>, Vec<FullHeader>) {
+		(self.view().transactions(), self.view().uncles(eip1559_transition))
 	}
 
 	/// Get the RLP of this block body.
@@ -156,8 +165,8 @@ impl Body {
 	/// Get raw rlp of uncle headers
 	pub fn uncles_rlp(&self) -> Rlp { self.view().uncles_rlp().rlp }
 
-	/// Decode uncle headers.
-	pub fn uncles(&self) -> Vec<FullHeader> { self.view().uncles() }
+	/// Decode uncle headers, see `Header::decode_rlp`.
+	pub fn uncles(&self, eip1559_transition: BlockNumber) -> Vec<FullHeader> { self.view().uncles(eip1559_transition) }
 
 	/// Number of uncles.
 	pub fn uncles_count(&self) -> usize { self.view().uncles_count() }
@@ -195,10 +204,15 @@ impl Block {
 	pub fn header_view(&self) -> HeaderView { self.view().header_view() }
 
 	/// Decode to a full block.
-	pub fn decode(&self) -> Result<FullBlock, rlp::DecoderError> { rlp::decode(&self.0) }
+	pub fn decode(&self, eip1559_transition: BlockNumber) -> Result<FullBlock, rlp::DecoderError> {
+		FullBlock::decode_rlp(&self.rlp(), eip1559_transition)
+	}
 
 	/// Decode the header.
-	pub fn decode_header(&self) -> FullHeader { self.view().rlp().val_at(0) }
+	pub fn decode_header(&self, eip1559_transition: BlockNumber) -> FullHeader {
+		FullHeader::decode_rlp(&self.view().header_rlp().rlp, eip1559_transition)
+			.expect("encoded block contains a valid header; qed")
+	}
 
 	/// Clone the encoded header.
 	pub fn header(&self) -> Header { Header(self.view().rlp().at(0).as_raw().to_vec()) }
@@ -280,8 +294,8 @@ impl Block {
 	/// The hash of each transaction in the block.
 	pub fn transaction_hashes(&self) -> Vec<H256> { self.view().transaction_hashes() }
 
-	/// Decode uncle headers.
-	pub fn uncles(&self) -> Vec<FullHeader> { self.view().uncles() }
+	/// Decode uncle headers, see `Header::decode_rlp`.
+	pub fn uncles(&self, eip1559_transition: BlockNumber) -> Vec<FullHeader> { self.view().uncles(eip1559_transition) }
 
 	/// Number of uncles.
 	pub fn uncles_count(&self) -> usize { self.view().uncles_count() }
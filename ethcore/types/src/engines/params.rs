@@ -100,6 +100,18 @@ pub struct CommonParams {
 	pub eip1884_transition: BlockNumber,
 	/// Number of first block where EIP-2028 rules begin.
 	pub eip2028_transition: BlockNumber,
+	/// Number of first block where EIP-2929 rules begin: https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2929.md
+	pub eip2929_transition: BlockNumber,
+	/// Number of first block where EIP-2930 rules begin: https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2930.md
+	pub eip2930_transition: BlockNumber,
+	/// Number of first block where EIP-1559 rules begin: https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1559.md
+	pub eip1559_transition: BlockNumber,
+	/// Bound divisor of the EIP-1559 base fee change per block.
+	pub eip1559_base_fee_max_change_denominator: U256,
+	/// Ratio of the EIP-1559 block gas limit to the block gas target.
+	pub eip1559_elasticity_multiplier: U256,
+	/// Base fee of the first EIP-1559 block.
+	pub eip1559_base_fee_initial_value: U256,
 	/// Number of first block where dust cleanup rules (EIP-168 and EIP169) begin.
 	pub dust_protection_transition: BlockNumber,
 	/// Nonce cap increase per block. Nonce cap is only checked if dust protection is enabled.
@@ -185,6 +197,11 @@ impl CommonParams {
 		if block_number >= self.eip2028_transition {
 			schedule.tx_data_non_zero_gas = 16;
 		}
+		if block_number >= self.eip2929_transition {
+			schedule.eip2929 = true;
+			schedule.apply_eip2929_costs();
+		}
+		schedule.eip1559 = block_number >= self.eip1559_transition;
 		if block_number >= self.eip210_transition {
 			schedule.blockhash_gas = 800;
 		}
@@ -322,6 +339,30 @@ impl From<ethjson::spec::Params> for CommonParams {
 				BlockNumber::max_value,
 				Into::into,
 			),
+			eip2929_transition: p.eip2929_transition.map_or_else(
+				BlockNumber::max_value,
+				Into::into,
+			),
+			eip2930_transition: p.eip2930_transition.map_or_else(
+				BlockNumber::max_value,
+				Into::into,
+			),
+			eip1559_transition: p.eip1559_transition.map_or_else(
+				BlockNumber::max_value,
+				Into::into,
+			),
+			eip1559_base_fee_max_change_denominator: p.eip1559_base_fee_max_change_denominator.map_or_else(
+				|| U256::from(8),
+				Into::into,
+			),
+			eip1559_elasticity_multiplier: p.eip1559_elasticity_multiplier.map_or_else(
+				|| U256::from(2),
+				Into::into,
+			),
+			eip1559_base_fee_initial_value: p.eip1559_base_fee_initial_value.map_or_else(
+				|| U256::from(1_000_000_000),
+				Into::into,
+			),
 			dust_protection_transition: p.dust_protection_transition.map_or_else(
 				BlockNumber::max_value,
 				Into::into,
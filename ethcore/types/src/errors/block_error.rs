@@ -89,6 +89,9 @@ pub enum BlockError {
 	/// Gas limit header field is invalid.
 	#[display(fmt = "Invalid gas limit: {}", _0)]
 	InvalidGasLimit(OutOfBounds<U256>),
+	/// Base fee header field is invalid (EIP-1559).
+	#[display(fmt = "Invalid base fee in header: {}", _0)]
+	InvalidBaseFee(Mismatch<U256>),
 	/// Receipts trie root header field is invalid.
 	#[display(fmt = "Invalid receipts trie root in header: {}", _0)]
 	InvalidReceiptsRoot(Mismatch<H256>),
@@ -60,6 +60,8 @@ pub enum SnapshotError {
 	ChunkTooSmall,
 	/// Oversized chunk
 	ChunkTooLarge,
+	/// Chunk doesn't match the checksum recorded in the manifest.
+	ChunkChecksumMismatch(H256),
 	/// Snapshots not supported by the consensus engine.
 	SnapshotsUnsupported,
 	/// Aborted snapshot
@@ -106,6 +108,7 @@ impl fmt::Display for SnapshotError {
 			VersionNotSupported(ref ver) => write!(f, "Snapshot version {} is not supprted.", ver),
 			ChunkTooSmall => write!(f, "Chunk size is too small."),
 			ChunkTooLarge => write!(f, "Chunk size is too large."),
+			ChunkChecksumMismatch(ref hash) => write!(f, "Chunk {:#x} doesn't match the checksum in the manifest.", hash),
 			SnapshotsUnsupported => write!(f, "Snapshots unsupported by consensus engine."),
 			SnapshotAborted => write!(f, "Snapshot was aborted."),
 			BadEpochProof(i) => write!(f, "Bad epoch proof for transition to epoch {}", i),
@@ -20,7 +20,7 @@ use hash::{KECCAK_NULL_RLP, KECCAK_EMPTY_LIST_RLP, keccak};
 use parity_util_mem::MallocSizeOf;
 use ethereum_types::{H256, U256, Address, Bloom};
 use bytes::Bytes;
-use rlp::{Rlp, RlpStream, Encodable, DecoderError, Decodable};
+use rlp::{Rlp, RlpStream, Encodable, DecoderError};
 use BlockNumber;
 
 /// Semantic boolean for when a seal/signature is included.
@@ -82,6 +82,8 @@ pub struct Header {
 	difficulty: U256,
 	/// Vector of post-RLP-encoded fields.
 	seal: Vec<Bytes>,
+	/// Base fee per gas, present from the EIP-1559 transition on.
+	base_fee_per_gas: Option<U256>,
 
 	/// Memoized hash of that header and the seal.
 	hash: Option<H256>,
@@ -108,7 +110,8 @@ impl PartialEq for Header {
 		self.gas_used == c.gas_used &&
 		self.gas_limit == c.gas_limit &&
 		self.difficulty == c.difficulty &&
-		self.seal == c.seal
+		self.seal == c.seal &&
+		self.base_fee_per_gas == c.base_fee_per_gas
 	}
 }
 
@@ -132,6 +135,7 @@ impl Default for Header {
 
 			difficulty: U256::default(),
 			seal: vec![],
+			base_fee_per_gas: None,
 			hash: None,
 		}
 	}
@@ -183,6 +187,9 @@ impl Header {
 	/// Get the seal field of the header.
 	pub fn seal(&self) -> &[Bytes] { &self.seal }
 
+	/// Get the base fee per gas field of the header, `None` before the EIP-1559 transition.
+	pub fn base_fee(&self) -> Option<U256> { self.base_fee_per_gas }
+
 	/// Get the seal field with RLP-decoded values as bytes.
 	pub fn decode_seal<'a, T: ::std::iter::FromIterator<&'a [u8]>>(&'a self) -> Result<T, DecoderError> {
 		self.seal.iter().map(|rlp| {
@@ -260,6 +267,11 @@ impl Header {
 		change_field(&mut self.hash, &mut self.seal, a)
 	}
 
+	/// Set the base fee per gas field of the header.
+	pub fn set_base_fee(&mut self, a: Option<U256>) {
+		change_field(&mut self.hash, &mut self.base_fee_per_gas, a)
+	}
+
 	/// Get & memoize the hash of this header (keccak of the RLP with seal).
 	pub fn compute_hash(&mut self) -> H256 {
 		let hash = self.hash();
@@ -291,10 +303,11 @@ impl Header {
 
 	/// Place this header into an RLP stream `s`, optionally `with_seal`.
 	fn stream_rlp(&self, s: &mut RlpStream, with_seal: Seal) {
+		let fields = if self.base_fee_per_gas.is_some() { 14 } else { 13 };
 		if let Seal::With = with_seal {
-			s.begin_list(13 + self.seal.len());
+			s.begin_list(fields + self.seal.len());
 		} else {
-			s.begin_list(13);
+			s.begin_list(fields);
 		}
 
 		s.append(&self.parent_hash);
@@ -316,19 +329,16 @@ impl Header {
 				s.append_raw(b, 1);
 			}
 		}
-	}
-}
 
-/// Alter value of given field, reset memoised hash if changed.
-fn change_field<T>(hash: &mut Option<H256>, field: &mut T, value: T) where T: PartialEq<T> {
-	if field != &value {
-		*field = value;
-		*hash = None;
+		// the base fee follows the seal fields
+		if let Some(ref base_fee) = self.base_fee_per_gas {
+			s.append(base_fee);
+		}
 	}
-}
 
-impl Decodable for Header {
-	fn decode(r: &Rlp) -> Result<Self, DecoderError> {
+	/// Decode a header, taking the base fee from the last field if the header is at or after
+	/// `eip1559_transition`. Every other field past the extra data is a seal field.
+	pub fn decode_rlp(r: &Rlp, eip1559_transition: BlockNumber) -> Result<Self, DecoderError> {
 		let mut blockheader = Header {
 			parent_hash: r.val_at(0)?,
 			uncles_hash: r.val_at(1)?,
@@ -344,10 +354,20 @@ impl Decodable for Header {
 			timestamp: r.val_at(11)?,
 			extra_data: r.val_at(12)?,
 			seal: vec![],
+			base_fee_per_gas: None,
 			hash: keccak(r.as_raw()).into(),
 		};
 
-		for i in 13..r.item_count()? {
+		let mut item_count = r.item_count()?;
+		if blockheader.number >= eip1559_transition {
+			if item_count < 14 {
+				return Err(DecoderError::RlpIncorrectListLen);
+			}
+			item_count -= 1;
+			blockheader.base_fee_per_gas = Some(r.val_at(item_count)?);
+		}
+
+		for i in 13..item_count {
 			blockheader.seal.push(r.at(i)?.as_raw().to_vec())
 		}
 
@@ -355,6 +375,14 @@ impl Decodable for Header {
 	}
 }
 
+/// Alter value of given field, reset memoised hash if changed.
+fn change_field<T>(hash: &mut Option<H256>, field: &mut T, value: T) where T: PartialEq<T> {
+	if field != &value {
+		*field = value;
+		*hash = None;
+	}
+}
+
 impl Encodable for Header {
 	fn rlp_append(&self, s: &mut RlpStream) {
 		self.stream_rlp(s, Seal::With);
@@ -371,7 +399,9 @@ impl ExtendedHeader {
 #[cfg(test)]
 mod tests {
 	use rustc_hex::FromHex;
-	use rlp;
+	use ethereum_types::{H256, U256};
+	use rlp::{self, Rlp};
+	use BlockNumber;
 	use super::Header;
 
 	#[test]
@@ -383,7 +413,7 @@ mod tests {
 		let nonce = "88ab4e252a7e8c2a23".from_hex().unwrap();
 		let nonce_decoded = "ab4e252a7e8c2a23".from_hex().unwrap();
 
-		let header: Header = rlp::decode(&header_rlp).expect("error decoding header");
+		let header = Header::decode_rlp(&Rlp::new(&header_rlp), BlockNumber::max_value()).expect("error decoding header");
 		let seal_fields = header.seal.clone();
 		assert_eq!(seal_fields.len(), 2);
 		assert_eq!(seal_fields[0], mix_hash);
@@ -400,12 +430,32 @@ mod tests {
 		// that's rlp of block header created with ethash engine.
 		let header_rlp = "f901f9a0d405da4e66f1445d455195229624e133f5baafe72b5cf7b3c36c12c8146e98b7a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347948888f1f195afa192cfee860698584c030f4c9db1a05fb2b4bfdef7b314451cb138a534d225c922fc0e5fbe25e451142732c3e25c25a088d2ec6b9860aae1a2c3b299f72b6a5d70d7f7ba4722c78f2c49ba96273c2158a007c6fdfa8eea7e86b81f5b0fc0f78f90cc19f4aa60d323151e0cac660199e9a1b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008302008003832fefba82524d84568e932a80a0a0349d8c3df71f1a48a9df7d03fd5f14aeee7d91332c009ecaff0a71ead405bd88ab4e252a7e8c2a23".from_hex().unwrap();
 
-		let header: Header = rlp::decode(&header_rlp).expect("error decoding header");
+		let header = Header::decode_rlp(&Rlp::new(&header_rlp), BlockNumber::max_value()).expect("error decoding header");
 		let encoded_header = rlp::encode(&header);
 
 		assert_eq!(header_rlp, encoded_header);
 	}
 
+	#[test]
+	fn decode_and_encode_header_with_base_fee() {
+		let mut header = Header::new();
+		header.set_number(10);
+		header.set_seal(vec![rlp::encode(&H256::zero()), rlp::encode(&0u64)]);
+		header.set_base_fee(Some(U256::from(1_000_000_000)));
+		let encoded = rlp::encode(&header);
+
+		let decoded = Header::decode_rlp(&Rlp::new(&encoded), 10).expect("error decoding header");
+		assert_eq!(decoded.base_fee(), Some(U256::from(1_000_000_000)));
+		assert_eq!(decoded.seal().len(), 2);
+		assert_eq!(decoded.hash(), header.hash());
+		assert_eq!(rlp::encode(&decoded), encoded);
+
+		// before the transition the base fee is taken for a seal field
+		let legacy = Header::decode_rlp(&Rlp::new(&encoded), 11).expect("error decoding header");
+		assert_eq!(legacy.base_fee(), None);
+		assert_eq!(legacy.seal().len(), 3);
+	}
+
 	#[test]
 	fn reject_header_with_large_timestamp() {
 		// that's rlp of block header created with ethash engine.
@@ -413,7 +463,7 @@ mod tests {
 		let header_rlp = "f901f9a0d405da4e66f1445d455195229624e133f5baafe72b5cf7b3c36c12c8146e98b7a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347948888f1f195afa192cfee860698584c030f4c9db1a05fb2b4bfdef7b314451cb138a534d225c922fc0e5fbe25e451142732c3e25c25a088d2ec6b9860aae1a2c3b299f72b6a5d70d7f7ba4722c78f2c49ba96273c2158a007c6fdfa8eea7e86b81f5b0fc0f78f90cc19f4aa60d323151e0cac660199e9a1b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008302008003832fefba82524d891000000000000000000080a0a0349d8c3df71f1a48a9df7d03fd5f14aeee7d91332c009ecaff0a71ead405bd88ab4e252a7e8c2a23".from_hex().unwrap();
 
 		// This should fail decoding timestamp
-		let header: Result<Header, _> = rlp::decode(&header_rlp);
+		let header = Header::decode_rlp(&Rlp::new(&header_rlp), BlockNumber::max_value());
 		assert_eq!(header.unwrap_err(), rlp::DecoderError::RlpIsTooBig);
 	}
 }
@@ -56,6 +56,8 @@ pub mod views;
 
 #[cfg(test)]
 extern crate rustc_hex;
+#[cfg(test)]
+extern crate serde_json;
 
 pub mod account_diff;
 pub mod ancestry_action;
@@ -66,6 +68,7 @@ pub mod blockchain_info;
 pub mod call_analytics;
 pub mod chain_notify;
 pub mod client_types;
+pub mod database_stats;
 pub mod encoded;
 pub mod engines;
 pub mod errors;
@@ -80,11 +83,13 @@ pub mod receipt;
 pub mod security_level;
 pub mod snapshot;
 pub mod state_diff;
+pub mod state_override;
 pub mod trace_filter;
 pub mod transaction;
 pub mod tree_route;
 pub mod verification;
 pub mod data_format;
+pub mod state_dump;
 
 /// Type for block number.
 pub type BlockNumber = u64;
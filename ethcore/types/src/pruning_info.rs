@@ -27,4 +27,6 @@ pub struct PruningInfo {
 	pub earliest_chain: u64,
 	/// The first block where state requests may be served.
 	pub earliest_state: u64,
+	/// The first block whose body, receipts and traces are kept, if ancient blocks were pruned.
+	pub earliest_body: Option<u64>,
 }
@@ -146,6 +146,8 @@ pub struct LocalizedReceipt {
 	pub cumulative_gas_used: U256,
 	/// The gas used in the execution of the transaction. Note the difference of meaning to `Receipt::gas_used`.
 	pub gas_used: U256,
+	/// The price per unit of gas actually paid by the sender.
+	pub effective_gas_price: U256,
 	/// Contract address.
 	pub contract_address: Option<Address>,
 	/// Logs
@@ -19,7 +19,7 @@
 use std::sync::atomic::{AtomicBool, AtomicUsize, AtomicU64, Ordering};
 
 use ethereum_types::H256;
-use rlp::{Rlp, RlpStream, DecoderError};
+use rlp::{Rlp, RlpStream, DecoderError, Encodable, Decodable};
 use bytes::Bytes;
 
 /// Modes of snapshotting
@@ -45,6 +45,8 @@ pub struct Progress {
 	pub accounts: AtomicUsize,
 	/// Number of blocks processed so far
 	pub blocks: AtomicUsize,
+	/// Number of state parts completed so far
+	pub state_parts: AtomicUsize,
 	/// Size in bytes of a all compressed chunks processed so far
 	pub size: AtomicU64,
 	/// Signals that the snapshotting process is completed
@@ -58,6 +60,7 @@ impl Progress {
 	pub fn reset(&self) {
 		self.accounts.store(0, Ordering::Release);
 		self.blocks.store(0, Ordering::Release);
+		self.state_parts.store(0, Ordering::Release);
 		self.size.store(0, Ordering::Release);
 		self.abort.store(false, Ordering::Release);
 
@@ -72,6 +75,9 @@ impl Progress {
 	/// Get the number of blocks snapshotted thus far.
 	pub fn blocks(&self) -> usize { self.blocks.load(Ordering::Acquire) }
 
+	/// Get the number of state parts snapshotted thus far.
+	pub fn state_parts(&self) -> usize { self.state_parts.load(Ordering::Acquire) }
+
 	/// Get the written size of the snapshot in bytes.
 	pub fn size(&self) -> u64 { self.size.load(Ordering::Acquire) }
 
@@ -79,7 +85,55 @@ impl Progress {
 	pub fn done(&self) -> bool  { self.done.load(Ordering::Acquire) }
 }
 
+/// Compression algorithm of snapshot chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+	/// Snappy, the only algorithm supported before version 3 manifests.
+	Snappy,
+	/// Zstandard.
+	Zstd,
+}
+
+impl Default for Compression {
+	fn default() -> Self {
+		Compression::Snappy
+	}
+}
+
+impl Encodable for Compression {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		let id: u8 = match *self {
+			Compression::Snappy => 0,
+			Compression::Zstd => 1,
+		};
+		s.append(&id);
+	}
+}
+
+impl Decodable for Compression {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		match rlp.as_val::<u8>()? {
+			0 => Ok(Compression::Snappy),
+			1 => Ok(Compression::Zstd),
+			_ => Err(DecoderError::Custom("Unknown snapshot chunk compression.")),
+		}
+	}
+}
+
+/// Length and checksum of the decompressed data of a snapshot chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct ChunkChecksum {
+	/// Length of the decompressed chunk.
+	pub len: u64,
+	/// Keccak hash of the decompressed chunk.
+	pub checksum: H256,
+}
+
 /// Manifest data.
+///
+/// Manifests of version 3 and later also record the compression of the chunks and the checksum
+/// of every chunk; older manifests are snappy compressed and carry no checksums. Version 3 was
+/// already in use by proof-of-authority snapshots before, so such manifests may lack them too.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ManifestData {
 	/// Snapshot format version.
@@ -94,18 +148,30 @@ pub struct ManifestData {
 	pub block_number: u64,
 	/// Block hash this snapshot was taken at.
 	pub block_hash: H256,
+	/// Compression algorithm of the chunks.
+	pub compression: Compression,
+	/// Checksums of the state chunks, in the order of `state_hashes`. Empty if not recorded.
+	pub state_checksums: Vec<ChunkChecksum>,
+	/// Checksums of the block chunks, in the order of `block_hashes`. Empty if not recorded.
+	pub block_checksums: Vec<ChunkChecksum>,
 }
 
 impl ManifestData {
 	/// Encode the manifest data to rlp.
 	pub fn into_rlp(self) -> Bytes {
-		let mut stream = RlpStream::new_list(6);
+		let with_checksums = self.version >= 3;
+		let mut stream = RlpStream::new_list(if with_checksums { 9 } else { 6 });
 		stream.append(&self.version);
 		stream.append_list(&self.state_hashes);
 		stream.append_list(&self.block_hashes);
 		stream.append(&self.state_root);
 		stream.append(&self.block_number);
 		stream.append(&self.block_hash);
+		if with_checksums {
+			stream.append(&self.compression);
+			stream.append_list(&self.state_checksums);
+			stream.append_list(&self.block_checksums);
+		}
 
 		stream.out()
 	}
@@ -113,7 +179,8 @@ impl ManifestData {
 	/// Try to restore manifest data from raw bytes, interpreted as RLP.
 	pub fn from_rlp(raw: &[u8]) -> Result<Self, DecoderError> {
 		let decoder = Rlp::new(raw);
-		let (start, version) = if decoder.item_count()? == 5 {
+		let item_count = decoder.item_count()?;
+		let (start, version) = if item_count == 5 {
 			(0, 1)
 		} else {
 			(1, decoder.val_at(0)?)
@@ -125,6 +192,18 @@ impl ManifestData {
 		let block_number: u64 = decoder.val_at(start + 3)?;
 		let block_hash: H256 = decoder.val_at(start + 4)?;
 
+		let (compression, state_checksums, block_checksums) = if item_count == start + 8 {
+			let state_checksums: Vec<ChunkChecksum> = decoder.list_at(start + 6)?;
+			let block_checksums: Vec<ChunkChecksum> = decoder.list_at(start + 7)?;
+			let missing = state_checksums.is_empty() && block_checksums.is_empty();
+			if !missing && (state_checksums.len() != state_hashes.len() || block_checksums.len() != block_hashes.len()) {
+				return Err(DecoderError::Custom("Number of chunk checksums doesn't match the number of chunks."));
+			}
+			(decoder.val_at(start + 5)?, state_checksums, block_checksums)
+		} else {
+			(Compression::Snappy, Vec::new(), Vec::new())
+		};
+
 		Ok(ManifestData {
 			version,
 			state_hashes,
@@ -132,6 +211,9 @@ impl ManifestData {
 			state_root,
 			block_number,
 			block_hash,
+			compression,
+			state_checksums,
+			block_checksums,
 		})
 	}
 }
@@ -139,6 +221,24 @@ impl ManifestData {
 /// A sink for produced chunks.
 pub type ChunkSink<'a> = dyn FnMut(&[u8]) -> std::io::Result<()> + 'a;
 
+/// Statuses for snapshot creation.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum CreationStatus {
+	/// No snapshot is being taken.
+	Inactive,
+	/// Ongoing snapshot creation.
+	Ongoing {
+		/// Number of accounts snapshotted so far.
+		accounts: usize,
+		/// Estimated total number of accounts, once at least one state part is completed.
+		estimated_accounts: Option<usize>,
+		/// Number of blocks snapshotted so far.
+		blocks: usize,
+		/// Size in bytes of the chunks written so far.
+		size: u64,
+	},
+}
+
 /// Statuses for snapshot restoration.
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum RestorationStatus {
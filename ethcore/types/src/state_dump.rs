@@ -0,0 +1,255 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types for dumping the state of a block account by account.
+
+use std::str::FromStr;
+
+use bytes::Bytes;
+use ethereum_types::{Address, H256, U256};
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+
+/// Format of a state dump.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StateDumpFormat {
+	/// One JSON object per account and line.
+	Json,
+	/// Concatenated RLP-encoded accounts.
+	Binary,
+}
+
+impl Default for StateDumpFormat {
+	fn default() -> Self {
+		StateDumpFormat::Json
+	}
+}
+
+impl FromStr for StateDumpFormat {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"json" | "jsonl" => Ok(StateDumpFormat::Json),
+			"binary" | "bin" | "rlp" => Ok(StateDumpFormat::Binary),
+			x => Err(format!("Invalid state format: {}", x))
+		}
+	}
+}
+
+/// Selection of the accounts and data included in a state dump.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateDumpOptions {
+	/// Address hash of the first account to dump. Accounts are dumped in address hash order.
+	pub from: Option<H256>,
+	/// Skip accounts with a balance lower than this.
+	pub min_balance: Option<U256>,
+	/// Skip accounts with a balance greater than this.
+	pub max_balance: Option<U256>,
+	/// Include contract code.
+	pub code: bool,
+	/// Include storage entries.
+	pub storage: bool,
+}
+
+impl Default for StateDumpOptions {
+	fn default() -> Self {
+		StateDumpOptions {
+			from: None,
+			min_balance: None,
+			max_balance: None,
+			code: true,
+			storage: true,
+		}
+	}
+}
+
+impl StateDumpOptions {
+	/// Whether an account with the given balance passes the balance filters.
+	pub fn includes_balance(&self, balance: &U256) -> bool {
+		self.min_balance.map_or(true, |min| *balance >= min) && self.max_balance.map_or(true, |max| *balance <= max)
+	}
+}
+
+/// Storage entry of a dumped account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DumpedStorageEntry {
+	/// Hash of the storage key, as found in the storage trie.
+	pub key_hash: H256,
+	/// The storage key, if its preimage is known.
+	pub key: Option<H256>,
+	/// Stored value.
+	pub value: U256,
+}
+
+/// An account of a state dump.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DumpedAccount {
+	/// Hash of the address, as found in the state trie.
+	pub address_hash: H256,
+	/// The address, if its preimage is known.
+	pub address: Option<Address>,
+	/// Balance of the account.
+	pub balance: U256,
+	/// Nonce of the account.
+	pub nonce: U256,
+	/// Hash of the account code.
+	pub code_hash: H256,
+	/// Account code, unless excluded from the dump.
+	pub code: Option<Bytes>,
+	/// Root of the storage trie.
+	pub storage_root: H256,
+	/// Storage entries in key hash order. Empty if storage is excluded from the dump.
+	pub storage: Vec<DumpedStorageEntry>,
+}
+
+// Optional values are encoded as an empty string when missing.
+fn append_optional<T: Encodable>(stream: &mut RlpStream, value: &Option<T>) {
+	match *value {
+		Some(ref value) => stream.append(value),
+		None => stream.append_empty_data(),
+	};
+}
+
+fn decode_optional<T: Decodable>(rlp: &Rlp) -> Result<Option<T>, DecoderError> {
+	if rlp.is_empty() {
+		Ok(None)
+	} else {
+		rlp.as_val().map(Some)
+	}
+}
+
+impl Encodable for DumpedStorageEntry {
+	fn rlp_append(&self, stream: &mut RlpStream) {
+		stream.begin_list(3);
+		stream.append(&self.key_hash);
+		append_optional(stream, &self.key);
+		stream.append(&self.value);
+	}
+}
+
+impl Decodable for DumpedStorageEntry {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		if rlp.item_count()? != 3 {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+
+		Ok(DumpedStorageEntry {
+			key_hash: rlp.val_at(0)?,
+			key: decode_optional(&rlp.at(1)?)?,
+			value: rlp.val_at(2)?,
+		})
+	}
+}
+
+impl Encodable for DumpedAccount {
+	fn rlp_append(&self, stream: &mut RlpStream) {
+		stream.begin_list(8);
+		stream.append(&self.address_hash);
+		append_optional(stream, &self.address);
+		stream.append(&self.balance);
+		stream.append(&self.nonce);
+		stream.append(&self.code_hash);
+		// code is a list so that excluded code can be told apart from empty code.
+		match self.code {
+			Some(ref code) => stream.begin_list(1).append(code),
+			None => stream.begin_list(0),
+		};
+		stream.append(&self.storage_root);
+		stream.append_list(&self.storage);
+	}
+}
+
+impl Decodable for DumpedAccount {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		if rlp.item_count()? != 8 {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+
+		let code = rlp.at(5)?;
+		Ok(DumpedAccount {
+			address_hash: rlp.val_at(0)?,
+			address: decode_optional(&rlp.at(1)?)?,
+			balance: rlp.val_at(2)?,
+			nonce: rlp.val_at(3)?,
+			code_hash: rlp.val_at(4)?,
+			code: match code.item_count()? {
+				0 => None,
+				1 => Some(code.val_at(0)?),
+				_ => return Err(DecoderError::RlpIncorrectListLen),
+			},
+			storage_root: rlp.val_at(6)?,
+			storage: rlp.list_at(7)?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ethereum_types::{Address, H256, U256};
+	use hash::{keccak, KECCAK_EMPTY, KECCAK_NULL_RLP};
+
+	use super::{DumpedAccount, DumpedStorageEntry, StateDumpFormat, StateDumpOptions};
+
+	#[test]
+	fn dumped_account_rlp_roundtrip() {
+		let address = Address::from_low_u64_be(1);
+		let with_storage = DumpedAccount {
+			address_hash: keccak(&address),
+			address: Some(address),
+			balance: U256::from(100),
+			nonce: U256::from(1),
+			code_hash: keccak(&[0x60u8, 0x00]),
+			code: Some(vec![0x60, 0x00]),
+			storage_root: H256::from_low_u64_be(2),
+			storage: vec![
+				DumpedStorageEntry { key_hash: keccak(H256::zero()), key: Some(H256::zero()), value: U256::from(7) },
+				DumpedStorageEntry { key_hash: H256::from_low_u64_be(3), key: None, value: U256::zero() },
+			],
+		};
+		let unknown = DumpedAccount {
+			address_hash: H256::from_low_u64_be(4),
+			address: None,
+			balance: U256::zero(),
+			nonce: U256::zero(),
+			code_hash: KECCAK_EMPTY,
+			code: None,
+			storage_root: KECCAK_NULL_RLP,
+			storage: Vec::new(),
+		};
+		let empty_code = DumpedAccount { code: Some(Vec::new()), ..unknown.clone() };
+
+		for account in &[with_storage, unknown, empty_code] {
+			assert_eq!(&rlp::decode::<DumpedAccount>(&rlp::encode(account)).unwrap(), account);
+		}
+	}
+
+	#[test]
+	fn balance_filters() {
+		let options = StateDumpOptions { min_balance: Some(U256::from(10)), max_balance: Some(U256::from(20)), ..Default::default() };
+		assert!(!options.includes_balance(&U256::from(9)));
+		assert!(options.includes_balance(&U256::from(10)));
+		assert!(options.includes_balance(&U256::from(20)));
+		assert!(!options.includes_balance(&U256::from(21)));
+		assert!(StateDumpOptions::default().includes_balance(&U256::max_value()));
+	}
+
+	#[test]
+	fn parse_format() {
+		assert_eq!("json".parse(), Ok(StateDumpFormat::Json));
+		assert_eq!("binary".parse(), Ok(StateDumpFormat::Binary));
+		assert!("msgpack".parse::<StateDumpFormat>().is_err());
+	}
+}
@@ -0,0 +1,47 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Account state overrides for virtual calls
+
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use ethereum_types::{Address, H256, U256};
+
+/// Overrides of the storage of a single account.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorageOverride {
+	/// Replace the whole storage with the given slots.
+	Full(BTreeMap<H256, H256>),
+	/// Replace only the given slots, keeping the rest of the storage.
+	Diff(BTreeMap<H256, H256>),
+}
+
+/// Overrides of a single account, applied before a call is executed.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AccountOverride {
+	/// Balance of the account.
+	pub balance: Option<U256>,
+	/// Nonce of the account.
+	pub nonce: Option<U256>,
+	/// Code of the account.
+	pub code: Option<Bytes>,
+	/// Storage of the account.
+	pub storage: Option<StorageOverride>,
+}
+
+/// Account overrides by address.
+pub type StateOverride = BTreeMap<Address, AccountOverride>;
@@ -16,6 +16,7 @@
 
 use std::{fmt, error};
 
+use bytes::Bytes;
 use ethereum_types::U256;
 use ethkey;
 use rlp;
@@ -32,6 +33,18 @@ pub enum Error {
 	Old,
 	/// Transaction was not imported to the queue because limit has been reached.
 	LimitReached,
+	/// Transaction was not imported to the queue because its sender already has too many transactions there.
+	SenderLimitReached {
+		/// Maximal number of transactions from a single sender
+		limit: usize,
+	},
+	/// Transaction's nonce is too far ahead of the sender's current nonce.
+	NonceTooHigh {
+		/// Maximal accepted nonce
+		limit: U256,
+		/// Transaction nonce
+		got: U256,
+	},
 	/// Transaction's gas price is below threshold.
 	InsufficientGasPrice {
 		/// Minimal expected gas price
@@ -86,6 +99,10 @@ pub enum Error {
 	TooBig,
 	/// Invalid RLP encoding
 	InvalidRlp(String),
+	/// Transaction type is not active yet
+	TransactionTypeNotEnabled,
+	/// Maximum priority fee per gas is higher than the maximum fee per gas (EIP-1559)
+	MaxPriorityFeeHigherThanMaxFee,
 }
 
 impl From<ethkey::Error> for Error {
@@ -111,6 +128,10 @@ impl fmt::Display for Error {
 						prev, new
 				),
 			LimitReached => "Transaction limit reached".into(),
+			SenderLimitReached { limit } =>
+				format!("Transaction limit per sender reached. Limit={}", limit),
+			NonceTooHigh { limit, got } =>
+				format!("Nonce too far in the future. Max={}, Given={}", limit, got),
 			InsufficientGasPrice { minimal, got } =>
 				format!("Insufficient gas price. Min={}, Given={}", minimal, got),
 			InsufficientGas { minimal, got } =>
@@ -129,6 +150,8 @@ impl fmt::Display for Error {
 			NotAllowed => "Sender does not have permissions to execute this type of transaction".into(),
 			TooBig => "Transaction too big".into(),
 			InvalidRlp(ref err) => format!("Transaction has invalid RLP structure: {}.", err),
+			TransactionTypeNotEnabled => "Transaction type is not enabled for the current block".into(),
+			MaxPriorityFeeHigherThanMaxFee => "Max priority fee per gas is higher than max fee per gas".into(),
 		};
 
 		f.write_fmt(format_args!("Transaction error ({})", msg))
@@ -150,6 +173,8 @@ pub enum CallError {
 	StatePruned,
 	/// Couldn't find an amount of gas that didn't result in an exception.
 	Exceptional(vm::Error),
+	/// Execution reverted even with the maximum amount of gas, with the returned data.
+	Reverted(Bytes),
 	/// Corrupt state.
 	StateCorrupt,
 	/// Error executing.
@@ -169,6 +194,7 @@ impl fmt::Display for CallError {
 			TransactionNotFound => "Transaction couldn't be found in the chain".into(),
 			StatePruned => "Couldn't find the transaction block's state in the chain".into(),
 			Exceptional(ref e) => format!("An exception ({}) happened in the execution", e),
+			Reverted(_) => "Execution reverted".into(),
 			StateCorrupt => "Stored state found to be corrupted.".into(),
 			Execution(ref e) => format!("{}", e),
 		};
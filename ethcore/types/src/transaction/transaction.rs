@@ -16,6 +16,8 @@
 
 //! Transaction data structure.
 
+use std::cmp;
+use std::convert::TryFrom;
 use std::ops::Deref;
 
 use ethereum_types::{H256, H160, Address, U256, BigEndianHash};
@@ -83,6 +85,66 @@ pub enum Condition {
 	Timestamp(u64),
 }
 
+/// Transaction type byte of EIP-2930 transactions.
+pub const ACCESS_LIST_TX_TYPE: u8 = 0x01;
+
+/// Transaction type byte of EIP-1559 transactions.
+pub const EIP1559_TX_TYPE: u8 = 0x02;
+
+/// Transaction type as defined by EIP-2718.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypedTxId {
+	/// Untyped transaction encoded as an RLP list.
+	Legacy,
+	/// EIP-2930 transaction with an access list.
+	AccessList,
+	/// EIP-1559 transaction with a priority fee and a fee cap.
+	EIP1559Transaction,
+}
+
+impl TypedTxId {
+	/// The type as returned over RPC, `0` for legacy transactions.
+	pub fn to_u8(&self) -> u8 {
+		match *self {
+			TypedTxId::Legacy => 0x00,
+			TypedTxId::AccessList => ACCESS_LIST_TX_TYPE,
+			TypedTxId::EIP1559Transaction => EIP1559_TX_TYPE,
+		}
+	}
+}
+
+/// An account and the storage keys of it a transaction declares to access (EIP-2930).
+#[derive(Debug, Default, Clone, PartialEq, Eq, MallocSizeOf)]
+pub struct AccessListItem {
+	/// Accessed account.
+	pub address: Address,
+	/// Accessed storage keys of the account.
+	pub storage_keys: Vec<H256>,
+}
+
+impl rlp::Decodable for AccessListItem {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		if rlp.item_count()? != 2 {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+		Ok(AccessListItem {
+			address: rlp.val_at(0)?,
+			storage_keys: rlp.list_at(1)?,
+		})
+	}
+}
+
+impl rlp::Encodable for AccessListItem {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(2);
+		s.append(&self.address);
+		s.append_list(&self.storage_keys);
+	}
+}
+
+/// Access list of an EIP-2930 transaction.
+pub type AccessList = Vec<AccessListItem>;
+
 /// Replay protection logic for v part of transaction's signature
 pub mod signature {
 	/// Adds chain id into v
@@ -138,13 +200,161 @@ impl Transaction {
 	}
 }
 
-impl From<ethjson::transaction::Transaction> for SignedTransaction {
-	fn from(t: ethjson::transaction::Transaction) -> Self {
+/// Appends the payload of an EIP-2930 transaction, or of an EIP-1559 transaction if the priority
+/// fee is given, with the signature if given, into RLP stream.
+fn rlp_append_typed_payload(
+	s: &mut RlpStream,
+	tx: &Transaction,
+	chain_id: u64,
+	max_priority_fee_per_gas: Option<&U256>,
+	access_list: &AccessList,
+	signature: Option<(u64, &U256, &U256)>,
+) {
+	let fields = if max_priority_fee_per_gas.is_none() { 8 } else { 9 };
+	s.begin_list(if signature.is_none() { fields } else { fields + 3 });
+	s.append(&chain_id);
+	s.append(&tx.nonce);
+	if let Some(max_priority_fee_per_gas) = max_priority_fee_per_gas {
+		s.append(max_priority_fee_per_gas);
+	}
+	s.append(&tx.gas_price);
+	s.append(&tx.gas);
+	s.append(&tx.action);
+	s.append(&tx.value);
+	s.append(&tx.data);
+	s.append_list(access_list);
+	if let Some((v, r, sig_s)) = signature {
+		s.append(&v);
+		s.append(r);
+		s.append(sig_s);
+	}
+}
+
+/// The type byte of a typed transaction, depending on whether it carries a priority fee.
+fn typed_tx_type(max_priority_fee_per_gas: Option<&U256>) -> u8 {
+	match max_priority_fee_per_gas {
+		Some(_) => EIP1559_TX_TYPE,
+		None => ACCESS_LIST_TX_TYPE,
+	}
+}
+
+/// The message hash of a typed transaction: keccak of the type byte followed by the unsigned payload.
+fn typed_tx_hash(
+	tx: &Transaction,
+	chain_id: u64,
+	max_priority_fee_per_gas: Option<&U256>,
+	access_list: &AccessList,
+) -> H256 {
+	let mut stream = RlpStream::new();
+	rlp_append_typed_payload(&mut stream, tx, chain_id, max_priority_fee_per_gas, access_list, None);
+	keccak(&[&[typed_tx_type(max_priority_fee_per_gas)][..], stream.as_raw()].concat())
+}
+
+/// An EIP-2930 transaction: the legacy fields, an explicit chain ID and an access list.
+#[derive(Debug, Clone, PartialEq, Eq, MallocSizeOf)]
+pub struct AccessListTx {
+	/// Legacy transaction fields.
+	pub transaction: Transaction,
+	/// Chain the transaction is valid on.
+	pub chain_id: u64,
+	/// Accounts and storage keys the transaction declares to access.
+	pub access_list: AccessList,
+}
+
+impl AccessListTx {
+	/// The message hash of the transaction.
+	pub fn hash(&self) -> H256 {
+		typed_tx_hash(&self.transaction, self.chain_id, None, &self.access_list)
+	}
+
+	/// Signs the transaction as coming from `sender`.
+	pub fn sign(self, secret: &Secret) -> SignedTransaction {
+		let sig = ::ethkey::sign(secret, &self.hash())
+			.expect("data is valid and context has signing capabilities; qed");
+		SignedTransaction::new(self.with_signature(sig))
+			.expect("secret is valid so it's recoverable")
+	}
+
+	/// Signs the transaction with signature.
+	pub fn with_signature(self, sig: Signature) -> UnverifiedTransaction {
+		UnverifiedTransaction {
+			unsigned: self.transaction,
+			access_list: Some((self.chain_id, self.access_list)),
+			max_priority_fee_per_gas: None,
+			r: sig.r().into(),
+			s: sig.s().into(),
+			v: sig.v() as u64,
+			hash: H256::zero(),
+		}.compute_hash()
+	}
+}
+
+/// An EIP-1559 transaction. The `gas_price` of the legacy fields is the fee cap (`max_fee_per_gas`).
+#[derive(Debug, Clone, PartialEq, Eq, MallocSizeOf)]
+pub struct EIP1559Tx {
+	/// Legacy transaction fields, with `gas_price` being the maximum fee per gas.
+	pub transaction: Transaction,
+	/// Chain the transaction is valid on.
+	pub chain_id: u64,
+	/// Maximum fee per gas paid to the block author on top of the base fee.
+	pub max_priority_fee_per_gas: U256,
+	/// Accounts and storage keys the transaction declares to access.
+	pub access_list: AccessList,
+}
+
+impl EIP1559Tx {
+	/// The message hash of the transaction.
+	pub fn hash(&self) -> H256 {
+		typed_tx_hash(&self.transaction, self.chain_id, Some(&self.max_priority_fee_per_gas), &self.access_list)
+	}
+
+	/// Signs the transaction as coming from `sender`.
+	pub fn sign(self, secret: &Secret) -> SignedTransaction {
+		let sig = ::ethkey::sign(secret, &self.hash())
+			.expect("data is valid and context has signing capabilities; qed");
+		SignedTransaction::new(self.with_signature(sig))
+			.expect("secret is valid so it's recoverable")
+	}
+
+	/// Signs the transaction with signature.
+	pub fn with_signature(self, sig: Signature) -> UnverifiedTransaction {
+		UnverifiedTransaction {
+			unsigned: self.transaction,
+			access_list: Some((self.chain_id, self.access_list)),
+			max_priority_fee_per_gas: Some(self.max_priority_fee_per_gas),
+			r: sig.r().into(),
+			s: sig.s().into(),
+			v: sig.v() as u64,
+			hash: H256::zero(),
+		}.compute_hash()
+	}
+}
+
+/// Chain ID typed transactions of the JSON tests are signed for.
+const JSON_TESTS_CHAIN_ID: u64 = 1;
+
+fn access_list_from_json(list: ethjson::transaction::AccessList) -> AccessList {
+	list.into_iter()
+		.map(|(address, storage_keys)| AccessListItem {
+			address: address.into(),
+			storage_keys: storage_keys.into_iter().map(Into::into).collect(),
+		})
+		.collect()
+}
+
+impl TryFrom<ethjson::transaction::Transaction> for SignedTransaction {
+	type Error = ethjson::transaction::TransactionError;
+
+	fn try_from(t: ethjson::transaction::Transaction) -> Result<Self, Self::Error> {
+		t.validate()?;
+		let tx_type = t.tx_type();
+		let gas_price = t.fee_cap().into();
+		let chain_id: Option<u64> = t.eip155_chain_id().map(Into::into);
 		let to: Option<ethjson::hash::Address> = t.to.into();
 		let secret = t.secret.map(|s| Secret::from(s.0));
 		let tx = Transaction {
 			nonce: t.nonce.into(),
-			gas_price: t.gas_price.into(),
+			gas_price,
 			gas: t.gas_limit.into(),
 			action: match to {
 				Some(to) => Action::Call(to.into()),
@@ -153,20 +363,50 @@ impl From<ethjson::transaction::Transaction> for SignedTransaction {
 			value: t.value.into(),
 			data: t.data.into(),
 		};
-		match secret {
-			Some(s) => tx.sign(&s, None),
-			None => tx.null_sign(1),
-		}
+		let access_list = t.access_list.map(access_list_from_json).unwrap_or_default();
+		Ok(match (secret, tx_type) {
+			(_, ethjson::transaction::TxType::Blob) => return Err(ethjson::transaction::TransactionError::UnsupportedType(tx_type)),
+			(Some(s), ethjson::transaction::TxType::EIP1559) => EIP1559Tx {
+				transaction: tx,
+				chain_id: chain_id.unwrap_or(JSON_TESTS_CHAIN_ID),
+				max_priority_fee_per_gas: t.max_priority_fee_per_gas.unwrap_or_default().into(),
+				access_list,
+			}.sign(&s),
+			(Some(s), ethjson::transaction::TxType::AccessList) => AccessListTx {
+				transaction: tx,
+				chain_id: chain_id.unwrap_or(JSON_TESTS_CHAIN_ID),
+				access_list,
+			}.sign(&s),
+			(Some(s), ethjson::transaction::TxType::Legacy) => tx.sign(&s, chain_id),
+			(None, _) => tx.null_sign(1),
+		})
 	}
 }
 
-impl From<ethjson::transaction::Transaction> for UnverifiedTransaction {
-	fn from(t: ethjson::transaction::Transaction) -> Self {
+impl TryFrom<ethjson::transaction::Transaction> for UnverifiedTransaction {
+	type Error = ethjson::transaction::TransactionError;
+
+	fn try_from(t: ethjson::transaction::Transaction) -> Result<Self, Self::Error> {
+		t.validate()?;
+		let tx_type = t.tx_type();
+		let gas_price = t.fee_cap().into();
+		let chain_id = t.eip155_chain_id().map_or(JSON_TESTS_CHAIN_ID, Into::into);
+		let (access_list, max_priority_fee_per_gas) = match tx_type {
+			ethjson::transaction::TxType::Legacy => (None, None),
+			ethjson::transaction::TxType::AccessList => (t.access_list, None),
+			ethjson::transaction::TxType::EIP1559 => (
+				Some(t.access_list.unwrap_or_default()),
+				Some(t.max_priority_fee_per_gas.unwrap_or_default().into()),
+			),
+			ethjson::transaction::TxType::Blob => return Err(ethjson::transaction::TransactionError::UnsupportedType(tx_type)),
+		};
 		let to: Option<ethjson::hash::Address> = t.to.into();
-		UnverifiedTransaction {
+		Ok(UnverifiedTransaction {
+			access_list: access_list.map(|list| (chain_id, access_list_from_json(list))),
+			max_priority_fee_per_gas,
 			unsigned: Transaction {
 				nonce: t.nonce.into(),
-				gas_price: t.gas_price.into(),
+				gas_price,
 				gas: t.gas_limit.into(),
 				action: match to {
 					Some(to) => Action::Call(to.into()),
@@ -179,7 +419,7 @@ impl From<ethjson::transaction::Transaction> for UnverifiedTransaction {
 			s: t.s.into(),
 			v: t.v.into(),
 			hash: H256::zero(),
-		}.compute_hash()
+		}.compute_hash())
 	}
 }
 
@@ -203,6 +443,8 @@ impl Transaction {
 	pub fn with_signature(self, sig: Signature, chain_id: Option<u64>) -> UnverifiedTransaction {
 		UnverifiedTransaction {
 			unsigned: self,
+			access_list: None,
+			max_priority_fee_per_gas: None,
 			r: sig.r().into(),
 			s: sig.s().into(),
 			v: signature::add_chain_replay_protection(sig.v() as u64, chain_id),
@@ -215,6 +457,8 @@ impl Transaction {
 	pub fn invalid_sign(self) -> UnverifiedTransaction {
 		UnverifiedTransaction {
 			unsigned: self,
+			access_list: None,
+			max_priority_fee_per_gas: None,
 			r: U256::one(),
 			s: U256::one(),
 			v: 0,
@@ -227,6 +471,8 @@ impl Transaction {
 		SignedTransaction {
 			transaction: UnverifiedTransaction {
 				unsigned: self,
+				access_list: None,
+			max_priority_fee_per_gas: None,
 				r: U256::one(),
 				s: U256::one(),
 				v: 0,
@@ -242,6 +488,8 @@ impl Transaction {
 		SignedTransaction {
 			transaction: UnverifiedTransaction {
 				unsigned: self,
+				access_list: None,
+			max_priority_fee_per_gas: None,
 				r: U256::zero(),
 				s: U256::zero(),
 				v: chain_id,
@@ -258,6 +506,10 @@ impl Transaction {
 pub struct UnverifiedTransaction {
 	/// Plain Transaction.
 	unsigned: Transaction,
+	/// Chain ID and access list of a typed transaction, `None` for legacy transactions.
+	access_list: Option<(u64, AccessList)>,
+	/// Maximum priority fee per gas of an EIP-1559 transaction, whose `gas_price` is the fee cap.
+	max_priority_fee_per_gas: Option<U256>,
 	/// The V field of the signature; the LS bit described which half of the curve our point falls
 	/// in. The MS bits describe which chain this transaction is for. If 27/28, its for all chains.
 	v: u64,
@@ -279,11 +531,17 @@ impl Deref for UnverifiedTransaction {
 
 impl rlp::Decodable for UnverifiedTransaction {
 	fn decode(d: &Rlp) -> Result<Self, DecoderError> {
+		// typed transactions are embedded in lists as RLP strings (EIP-2718)
+		if d.is_data() {
+			return Self::decode_typed(d.data()?);
+		}
 		if d.item_count()? != 9 {
 			return Err(DecoderError::RlpIncorrectListLen);
 		}
 		let hash = keccak(d.as_raw());
 		Ok(UnverifiedTransaction {
+			access_list: None,
+			max_priority_fee_per_gas: None,
 			unsigned: Transaction {
 				nonce: d.val_at(0)?,
 				gas_price: d.val_at(1)?,
@@ -301,17 +559,147 @@ impl rlp::Decodable for UnverifiedTransaction {
 }
 
 impl rlp::Encodable for UnverifiedTransaction {
-	fn rlp_append(&self, s: &mut RlpStream) { self.rlp_append_sealed_transaction(s) }
+	fn rlp_append(&self, s: &mut RlpStream) {
+		match self.access_list {
+			None => self.rlp_append_sealed_transaction(s),
+			Some(_) => { s.append(&self.encode()); },
+		}
+	}
 }
 
 impl UnverifiedTransaction {
 	/// Used to compute hash of created transactions
 	fn compute_hash(mut self) -> UnverifiedTransaction {
-		let hash = keccak(&*self.rlp_bytes());
+		let hash = keccak(&self.encode());
 		self.hash = hash;
 		self
 	}
 
+	/// Decodes the type byte and payload of a typed transaction.
+	fn decode_typed(bytes: &[u8]) -> Result<Self, DecoderError> {
+		match bytes.split_first() {
+			Some((&ACCESS_LIST_TX_TYPE, payload)) => {
+				let d = Rlp::new(payload);
+				if d.item_count()? != 11 {
+					return Err(DecoderError::RlpIncorrectListLen);
+				}
+				Ok(UnverifiedTransaction {
+					access_list: Some((d.val_at(0)?, d.list_at(7)?)),
+					unsigned: Transaction {
+						nonce: d.val_at(1)?,
+						gas_price: d.val_at(2)?,
+						gas: d.val_at(3)?,
+						action: d.val_at(4)?,
+						value: d.val_at(5)?,
+						data: d.val_at(6)?,
+					},
+					v: d.val_at(8)?,
+					r: d.val_at(9)?,
+					s: d.val_at(10)?,
+					hash: keccak(bytes),
+				})
+			},
+			Some((&EIP1559_TX_TYPE, payload)) => {
+				let d = Rlp::new(payload);
+				if d.item_count()? != 12 {
+					return Err(DecoderError::RlpIncorrectListLen);
+				}
+				Ok(UnverifiedTransaction {
+					access_list: Some((d.val_at(0)?, d.list_at(8)?)),
+					max_priority_fee_per_gas: Some(d.val_at(2)?),
+					unsigned: Transaction {
+						nonce: d.val_at(1)?,
+						gas_price: d.val_at(3)?,
+						gas: d.val_at(4)?,
+						action: d.val_at(5)?,
+						value: d.val_at(6)?,
+						data: d.val_at(7)?,
+					},
+					v: d.val_at(9)?,
+					r: d.val_at(10)?,
+					s: d.val_at(11)?,
+					hash: keccak(bytes),
+				})
+			},
+			Some(_) => Err(DecoderError::Custom("Unknown transaction type")),
+			None => Err(DecoderError::RlpIsTooShort),
+		}
+	}
+
+	/// Decodes a transaction as submitted by `eth_sendRawTransaction`: an RLP list for
+	/// legacy transactions, the type byte followed by the RLP payload for typed ones.
+	pub fn decode(bytes: &[u8]) -> Result<Self, DecoderError> {
+		match bytes.first() {
+			Some(&first) if first >= 0xc0 => rlp::decode(bytes),
+			_ => Self::decode_typed(bytes),
+		}
+	}
+
+	/// Encodes the transaction the way it is hashed, see `decode`.
+	pub fn encode(&self) -> Bytes {
+		match self.access_list {
+			None => {
+				let mut stream = RlpStream::new();
+				self.rlp_append_sealed_transaction(&mut stream);
+				stream.out()
+			},
+			Some((chain_id, ref access_list)) => {
+				let max_priority_fee_per_gas = self.max_priority_fee_per_gas.as_ref();
+				let mut stream = RlpStream::new();
+				rlp_append_typed_payload(
+					&mut stream,
+					&self.unsigned,
+					chain_id,
+					max_priority_fee_per_gas,
+					access_list,
+					Some((self.v, &self.r, &self.s)),
+				);
+				[&[typed_tx_type(max_priority_fee_per_gas)][..], stream.as_raw()].concat()
+			},
+		}
+	}
+
+	/// Type of the transaction.
+	pub fn tx_type(&self) -> TypedTxId {
+		match (&self.access_list, &self.max_priority_fee_per_gas) {
+			(None, _) => TypedTxId::Legacy,
+			(Some(_), None) => TypedTxId::AccessList,
+			(Some(_), Some(_)) => TypedTxId::EIP1559Transaction,
+		}
+	}
+
+	/// Access list of a typed transaction, `None` for legacy transactions.
+	pub fn access_list(&self) -> Option<&AccessList> {
+		self.access_list.as_ref().map(|&(_, ref access_list)| access_list)
+	}
+
+	/// Maximum fee per gas the sender is willing to pay: the fee cap of an EIP-1559
+	/// transaction, the gas price otherwise.
+	pub fn max_fee_per_gas(&self) -> U256 {
+		self.gas_price
+	}
+
+	/// Maximum fee per gas paid to the block author on top of the base fee. Transactions
+	/// without a priority fee offer their whole gas price.
+	pub fn max_priority_fee_per_gas(&self) -> U256 {
+		self.max_priority_fee_per_gas.unwrap_or(self.gas_price)
+	}
+
+	/// The price per gas actually paid by the sender in a block with the given base fee.
+	pub fn effective_gas_price(&self, base_fee: Option<U256>) -> U256 {
+		match (self.max_priority_fee_per_gas, base_fee) {
+			(Some(max_priority_fee_per_gas), Some(base_fee)) =>
+				cmp::min(self.gas_price, base_fee.saturating_add(max_priority_fee_per_gas)),
+			_ => self.gas_price,
+		}
+	}
+
+	/// The price per gas paid to the block author in a block with the given base fee,
+	/// the rest of the effective gas price is burnt.
+	pub fn effective_priority_fee(&self, base_fee: Option<U256>) -> U256 {
+		self.effective_gas_price(base_fee).saturating_sub(base_fee.unwrap_or_default())
+	}
+
 	/// Checks if the signature is empty.
 	pub fn is_unsigned(&self) -> bool {
 		self.r.is_zero() && self.s.is_zero()
@@ -345,13 +733,25 @@ impl UnverifiedTransaction {
 	}
 
 	/// Returns standardized `v` value (0, 1 or 4 (invalid))
-	pub fn standard_v(&self) -> u8 { signature::check_replay_protection(self.v) }
+	pub fn standard_v(&self) -> u8 {
+		match self.access_list {
+			// typed transactions carry the y-parity of the signature
+			Some(_) => match self.v {
+				v @ 0 | v @ 1 => v as u8,
+				_ => 4,
+			},
+			None => signature::check_replay_protection(self.v),
+		}
+	}
 
 	/// The `v` value that appears in the RLP.
 	pub fn original_v(&self) -> u64 { self.v }
 
 	/// The chain ID, or `None` if this is a global transaction.
 	pub fn chain_id(&self) -> Option<u64> {
+		if let Some((chain_id, _)) = self.access_list {
+			return Some(chain_id);
+		}
 		match self.v {
 			v if self.is_unsigned() => Some(v),
 			v if v >= 35 => Some((v - 35) / 2),
@@ -382,7 +782,16 @@ impl UnverifiedTransaction {
 
 	/// Recovers the public key of the sender.
 	pub fn recover_public(&self) -> Result<Public, ethkey::Error> {
-		Ok(recover(&self.signature(), &self.unsigned.hash(self.chain_id()))?)
+		let message = match self.access_list {
+			Some((chain_id, ref access_list)) => typed_tx_hash(
+				&self.unsigned,
+				chain_id,
+				self.max_priority_fee_per_gas.as_ref(),
+				access_list,
+			),
+			None => self.unsigned.hash(self.chain_id()),
+		};
+		Ok(recover(&self.signature(), &message)?)
 	}
 
 	/// Verify basic signature params. Does not attempt sender recovery.
@@ -421,7 +830,7 @@ pub struct SignedTransaction {
 }
 
 impl rlp::Encodable for SignedTransaction {
-	fn rlp_append(&self, s: &mut RlpStream) { self.transaction.rlp_append_sealed_transaction(s) }
+	fn rlp_append(&self, s: &mut RlpStream) { self.transaction.rlp_append(s) }
 }
 
 impl Deref for SignedTransaction {
@@ -555,7 +964,7 @@ impl From<SignedTransaction> for PendingTransaction {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use ethereum_types::{U256, Address};
+	use ethereum_types::{U256, H256, Address};
 	use hash::keccak;
 	use std::str::FromStr;
 
@@ -661,6 +1070,212 @@ mod tests {
 		assert_eq!(t.chain_id(), Some(69));
 	}
 
+	#[test]
+	fn access_list_transaction_roundtrip() {
+		use ethkey::{Random, Generator};
+
+		let key = Random.generate().unwrap();
+		let t = AccessListTx {
+			transaction: Transaction {
+				action: Action::Call(Address::from_low_u64_be(0x1337)),
+				nonce: U256::from(42),
+				gas_price: U256::from(3000),
+				gas: U256::from(50_000),
+				value: U256::from(1),
+				data: b"Hello!".to_vec()
+			},
+			chain_id: 69,
+			access_list: vec![AccessListItem {
+				address: Address::from_low_u64_be(0x1337),
+				storage_keys: vec![H256::zero(), H256::from_low_u64_be(1)],
+			}],
+		}.sign(&key.secret());
+		assert_eq!(Address::from(keccak(key.public())), t.sender());
+		assert_eq!(t.tx_type(), TypedTxId::AccessList);
+		assert_eq!(t.chain_id(), Some(69));
+
+		let raw = t.encode();
+		assert_eq!(raw[0], ACCESS_LIST_TX_TYPE);
+		assert_eq!(t.hash(), keccak(&raw));
+
+		let decoded = UnverifiedTransaction::decode(&raw).unwrap();
+		assert_eq!(decoded, *t);
+		assert_eq!(SignedTransaction::new(decoded).unwrap().sender(), t.sender());
+
+		// within a list the typed transaction is embedded as an RLP string
+		let embedded: UnverifiedTransaction = rlp::decode(&rlp::encode(&*t)).unwrap();
+		assert_eq!(embedded, *t);
+	}
+
+	#[test]
+	fn access_list_transaction_from_json() {
+		let json = include_str!("../../../res/access_list_transaction.json");
+		let tx: ethjson::transaction::Transaction = ::serde_json::from_str(json).unwrap();
+		let t = SignedTransaction::try_from(tx).unwrap();
+
+		assert_eq!(t.tx_type(), TypedTxId::AccessList);
+		assert_eq!(t.chain_id(), Some(1));
+		assert_eq!(t.sender(), Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap());
+		assert_eq!(t.access_list(), Some(&vec![
+			AccessListItem {
+				address: Address::from_str("095e7baea6a6c7c4c2dfeb977efac326af552d87").unwrap(),
+				storage_keys: vec![H256::zero(), H256::from_low_u64_be(1)],
+			},
+			AccessListItem {
+				address: Address::from_low_u64_be(4),
+				storage_keys: vec![],
+			},
+		]));
+	}
+
+	#[test]
+	fn eip1559_transaction_from_json() {
+		let json = r#"{
+			"data" : "0x",
+			"gasLimit" : "0xf388",
+			"maxFeePerGas" : "0x07d0",
+			"maxPriorityFeePerGas" : "0x64",
+			"nonce" : "0x00",
+			"to" : "0x095e7baea6a6c7c4c2dfeb977efac326af552d87",
+			"value" : "0x00",
+			"secretKey" : "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8"
+		}"#;
+		let tx: ethjson::transaction::Transaction = ::serde_json::from_str(json).unwrap();
+		let t = SignedTransaction::try_from(tx).unwrap();
+
+		assert_eq!(t.tx_type(), TypedTxId::EIP1559Transaction);
+		assert_eq!(t.chain_id(), Some(1));
+		assert_eq!(t.sender(), Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap());
+		assert_eq!(t.max_fee_per_gas(), U256::from(2000));
+		assert_eq!(t.max_priority_fee_per_gas(), U256::from(100));
+		assert_eq!(t.access_list(), Some(&vec![]));
+	}
+
+	#[test]
+	fn eip155_transaction_from_json() {
+		let signed = |chain_id: &str| {
+			let json = format!(r#"{{
+				"data" : "0x",
+				"gasLimit" : "0x5208",
+				"gasPrice" : "0x04a817c800",
+				"nonce" : "0x09",
+				"to" : "0x3535353535353535353535353535353535353535",
+				"value" : "0x0de0b6b3a7640000",
+				{}
+				"secretKey" : "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8"
+			}}"#, chain_id);
+			let tx: ethjson::transaction::Transaction = ::serde_json::from_str(&json).unwrap();
+			SignedTransaction::try_from(tx).unwrap()
+		};
+
+		let mainnet = signed(r#""chainId" : "0x01","#);
+		assert_eq!(mainnet.chain_id(), Some(1));
+		assert!(mainnet.original_v() == 37 || mainnet.original_v() == 38);
+		assert_eq!(mainnet.sender(), Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap());
+
+		let unprotected = signed("");
+		assert_eq!(unprotected.chain_id(), None);
+		assert!(unprotected.original_v() == 27 || unprotected.original_v() == 28);
+		assert_eq!(unprotected.sender(), mainnet.sender());
+	}
+
+	#[test]
+	fn blob_transaction_from_json_is_unsupported() {
+		use ethjson::transaction::{TransactionError, TxType};
+
+		let tx = |hashes: &str| {
+			let json = format!(r#"{{
+				"data" : "0x",
+				"gasLimit" : "0xf388",
+				"maxFeePerGas" : "0x07d0",
+				"maxPriorityFeePerGas" : "0x64",
+				"maxFeePerBlobGas" : "0x0a",
+				"blobVersionedHashes" : [{}],
+				"nonce" : "0x00",
+				"to" : "0x095e7baea6a6c7c4c2dfeb977efac326af552d87",
+				"value" : "0x00",
+				"secretKey" : "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8"
+			}}"#, hashes);
+			::serde_json::from_str::<ethjson::transaction::Transaction>(&json).unwrap()
+		};
+		let hash = r#""0x0100000000000000000000000000000000000000000000000000000000000001""#;
+
+		assert_eq!(SignedTransaction::try_from(tx(hash)).unwrap_err(), TransactionError::UnsupportedType(TxType::Blob));
+		assert_eq!(UnverifiedTransaction::try_from(tx(hash)).unwrap_err(), TransactionError::UnsupportedType(TxType::Blob));
+		assert_eq!(SignedTransaction::try_from(tx("")).unwrap_err(), TransactionError::MissingBlobVersionedHashes);
+	}
+
+	#[test]
+	fn eip1559_transaction_roundtrip() {
+		use ethkey::{Random, Generator};
+
+		let key = Random.generate().unwrap();
+		let t = EIP1559Tx {
+			transaction: Transaction {
+				action: Action::Call(Address::from_low_u64_be(0x1337)),
+				nonce: U256::from(42),
+				gas_price: U256::from(3000),
+				gas: U256::from(50_000),
+				value: U256::from(1),
+				data: b"Hello!".to_vec()
+			},
+			chain_id: 69,
+			max_priority_fee_per_gas: U256::from(100),
+			access_list: vec![],
+		}.sign(&key.secret());
+		assert_eq!(Address::from(keccak(key.public())), t.sender());
+		assert_eq!(t.tx_type(), TypedTxId::EIP1559Transaction);
+		assert_eq!(t.max_priority_fee_per_gas(), U256::from(100));
+		assert_eq!(t.max_fee_per_gas(), U256::from(3000));
+
+		let raw = t.encode();
+		assert_eq!(raw[0], EIP1559_TX_TYPE);
+		assert_eq!(t.hash(), keccak(&raw));
+
+		let decoded = UnverifiedTransaction::decode(&raw).unwrap();
+		assert_eq!(decoded, *t);
+		assert_eq!(SignedTransaction::new(decoded).unwrap().sender(), t.sender());
+
+		let embedded: UnverifiedTransaction = rlp::decode(&rlp::encode(&*t)).unwrap();
+		assert_eq!(embedded, *t);
+	}
+
+	#[test]
+	fn effective_gas_price() {
+		let tx = Transaction {
+			action: Action::Create,
+			nonce: U256::zero(),
+			gas_price: U256::from(3000),
+			gas: U256::from(50_000),
+			value: U256::zero(),
+			data: vec![],
+		};
+		let legacy = tx.clone().fake_sign(Address::zero());
+		assert_eq!(legacy.effective_gas_price(None), U256::from(3000));
+		assert_eq!(legacy.effective_gas_price(Some(U256::from(1000))), U256::from(3000));
+		assert_eq!(legacy.effective_priority_fee(Some(U256::from(1000))), U256::from(2000));
+
+		let eip1559 = EIP1559Tx {
+			transaction: tx,
+			chain_id: 1,
+			max_priority_fee_per_gas: U256::from(100),
+			access_list: vec![],
+		}.with_signature(Signature::default());
+		assert_eq!(eip1559.effective_gas_price(Some(U256::from(1000))), U256::from(1100));
+		assert_eq!(eip1559.effective_priority_fee(Some(U256::from(1000))), U256::from(100));
+		// the fee cap limits the priority fee
+		assert_eq!(eip1559.effective_gas_price(Some(U256::from(2950))), U256::from(3000));
+		assert_eq!(eip1559.effective_priority_fee(Some(U256::from(2950))), U256::from(50));
+	}
+
+	#[test]
+	fn unknown_transaction_type_rejected() {
+		assert_eq!(
+			UnverifiedTransaction::decode(&[0x7f, 0xc0]),
+			Err(DecoderError::Custom("Unknown transaction type"))
+		);
+	}
+
 	#[test]
 	fn should_agree_with_vitalik() {
 		use rustc_hex::FromHex;
@@ -19,6 +19,7 @@
 use crate::{
 	header::Header,
 	transaction::UnverifiedTransaction,
+	BlockNumber,
 };
 use bytes::Bytes;
 use parity_util_mem::MallocSizeOf;
@@ -70,14 +71,17 @@ pub struct Unverified {
 }
 
 impl Unverified {
-	/// Create an `Unverified` from raw bytes.
-	pub fn from_rlp(bytes: Bytes) -> Result<Self, rlp::DecoderError> {
+	/// Create an `Unverified` from raw bytes, see `Header::decode_rlp` for the meaning of
+	/// `eip1559_transition`.
+	pub fn from_rlp(bytes: Bytes, eip1559_transition: BlockNumber) -> Result<Self, rlp::DecoderError> {
 		use rlp::Rlp;
 		let (header, transactions, uncles) = {
 			let rlp = Rlp::new(&bytes);
-			let header = rlp.val_at(0)?;
+			let header = Header::decode_rlp(&rlp.at(0)?, eip1559_transition)?;
 			let transactions = rlp.list_at(1)?;
-			let uncles = rlp.list_at(2)?;
+			let uncles = rlp.at(2)?.iter()
+				.map(|uncle| Header::decode_rlp(&uncle, eip1559_transition))
+				.collect::<Result<_, _>>()?;
 			(header, transactions, uncles)
 		};
 
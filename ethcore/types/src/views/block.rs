@@ -23,6 +23,7 @@ use header::Header;
 use transaction::{UnverifiedTransaction, LocalizedTransaction};
 use views::{TransactionView, HeaderView};
 use super::ViewRlp;
+use BlockNumber;
 
 /// View onto block rlp.
 pub struct BlockView<'a> {
@@ -62,9 +63,9 @@ impl<'a> BlockView<'a> {
 		&self.rlp
 	}
 
-	/// Create new Header object from header rlp.
-	pub fn header(&self) -> Header {
-		self.rlp.val_at(0)
+	/// Create new Header object from header rlp, see `Header::decode_rlp`.
+	pub fn header(&self, eip1559_transition: BlockNumber) -> Header {
+		self.header_rlp().decode_with(|rlp| Header::decode_rlp(rlp, eip1559_transition))
 	}
 
 	/// Return header rlp.
@@ -143,9 +144,9 @@ impl<'a> BlockView<'a> {
 		self.rlp.at(2)
 	}
 
-	/// Return list of uncles of given block.
-	pub fn uncles(&self) -> Vec<Header> {
-		self.rlp.list_at(2)
+	/// Return list of uncles of given block, see `Header::decode_rlp`.
+	pub fn uncles(&self, eip1559_transition: BlockNumber) -> Vec<Header> {
+		self.uncles_rlp().iter().map(|rlp| rlp.decode_with(|rlp| Header::decode_rlp(rlp, eip1559_transition))).collect()
 	}
 
 	/// Return number of uncles in given block, without deserializing them.
@@ -163,9 +164,9 @@ impl<'a> BlockView<'a> {
 		self.uncles_rlp().iter().map(|rlp| keccak(rlp.as_raw())).collect()
 	}
 
-	/// Return nth uncle.
-	pub fn uncle_at(&self, index: usize) -> Option<Header> {
-		self.uncles_rlp().iter().nth(index).map(|rlp| rlp.as_val())
+	/// Return nth uncle, see `Header::decode_rlp`.
+	pub fn uncle_at(&self, index: usize, eip1559_transition: BlockNumber) -> Option<Header> {
+		self.uncles_rlp().iter().nth(index).map(|rlp| rlp.decode_with(|rlp| Header::decode_rlp(rlp, eip1559_transition)))
 	}
 
 	/// Return nth uncle rlp.
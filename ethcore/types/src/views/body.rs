@@ -117,9 +117,9 @@ impl<'a> BodyView<'a> {
 		self.rlp.at(1)
 	}
 
-	/// Return list of uncles of given block.
-	pub fn uncles(&self) -> Vec<Header> {
-		self.rlp.list_at(1)
+	/// Return list of uncles of given block, see `Header::decode_rlp`.
+	pub fn uncles(&self, eip1559_transition: BlockNumber) -> Vec<Header> {
+		self.uncles_rlp().iter().map(|rlp| rlp.decode_with(|rlp| Header::decode_rlp(rlp, eip1559_transition))).collect()
 	}
 
 	/// Return number of uncles in given block, without deserializing them.
@@ -137,9 +137,9 @@ impl<'a> BodyView<'a> {
 		self.uncles_rlp().iter().map(|rlp| keccak(rlp.as_raw())).collect()
 	}
 
-	/// Return nth uncle.
-	pub fn uncle_at(&self, index: usize) -> Option<Header> {
-		self.uncles_rlp().iter().nth(index).map(|rlp| rlp.as_val())
+	/// Return nth uncle, see `Header::decode_rlp`.
+	pub fn uncle_at(&self, index: usize, eip1559_transition: BlockNumber) -> Option<Header> {
+		self.uncles_rlp().iter().nth(index).map(|rlp| rlp.decode_with(|rlp| Header::decode_rlp(rlp, eip1559_transition)))
 	}
 
 	/// Return nth uncle rlp.
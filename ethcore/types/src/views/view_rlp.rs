@@ -82,6 +82,11 @@ impl<'a, 'view> ViewRlp<'a> where 'a : 'view {
 		self.expect_valid_rlp(self.rlp.val_at(index))
 	}
 
+	/// Returns the value decoded by `decode`, panics if rlp not valid
+	pub fn decode_with<T, F>(&self, decode: F) -> T where F: FnOnce(&Rlp<'a>) -> Result<T, DecoderError> {
+		self.expect_valid_rlp(decode(&self.rlp))
+	}
+
 	/// Returns decoded list of values, panics if rlp is invalid
 	pub fn list_at<T>(&self, index: usize) -> Vec<T> where T: Decodable {
 		self.expect_valid_rlp(self.rlp.list_at(index))
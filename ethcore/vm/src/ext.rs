@@ -160,6 +160,18 @@ pub trait Ext {
 	/// Decrements sstore refunds counter.
 	fn sub_sstore_refund(&mut self, value: usize);
 
+	/// Check whether an account has already been accessed in this transaction (EIP-2929).
+	fn al_contains_address(&self, address: &Address) -> bool;
+
+	/// Mark an account as accessed in this transaction (EIP-2929).
+	fn al_insert_address(&mut self, address: Address);
+
+	/// Check whether a storage key of the current contract has already been accessed in this transaction (EIP-2929).
+	fn al_contains_storage_key(&self, key: &H256) -> bool;
+
+	/// Mark a storage key of the current contract as accessed in this transaction (EIP-2929).
+	fn al_insert_storage_key(&mut self, key: H256);
+
 	/// Decide if any more operations should be traced. Passthrough for the VM trace.
 	fn trace_next_instruction(&mut self, _pc: usize, _instruction: u8, _current_gas: U256) -> bool { false }
 
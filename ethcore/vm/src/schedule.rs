@@ -134,6 +134,20 @@ pub struct Schedule {
 	pub eip1283: bool,
 	/// Enable EIP-1706 rules
 	pub eip1706: bool,
+	/// Enable EIP-2929 rules
+	pub eip2929: bool,
+	/// Gas cost of the first access to a storage slot in a transaction (EIP-2929)
+	pub cold_sload_cost: usize,
+	/// Gas cost of the first access to an account in a transaction (EIP-2929)
+	pub cold_account_access_cost: usize,
+	/// Gas cost of a repeated access to an account or a storage slot (EIP-2929)
+	pub warm_storage_read_cost: usize,
+	/// Additional cost for every address in a transaction's access list (EIP-2930)
+	pub tx_access_list_address_gas: usize,
+	/// Additional cost for every storage key in a transaction's access list (EIP-2930)
+	pub tx_access_list_storage_key_gas: usize,
+	/// Enable EIP-1559 rules
+	pub eip1559: bool,
 	/// VM execution does not increase null signed address nonce if this field is true.
 	pub keep_unsigned_nonce: bool,
 	/// Latest VM version for contract creation transaction.
@@ -276,6 +290,13 @@ impl Schedule {
 			kill_dust: CleanDustMode::Off,
 			eip1283: false,
 			eip1706: false,
+			eip2929: false,
+			eip1559: false,
+			cold_sload_cost: 2100,
+			cold_account_access_cost: 2600,
+			warm_storage_read_cost: 100,
+			tx_access_list_address_gas: 2400,
+			tx_access_list_storage_key_gas: 1900,
 			keep_unsigned_nonce: false,
 			latest_version: U256::zero(),
 			versions: HashMap::new(),
@@ -312,6 +333,28 @@ impl Schedule {
 		schedule
 	}
 
+	/// Schedule for the Berlin fork of the Ethereum main net.
+	pub fn new_berlin() -> Schedule {
+		let mut schedule = Self::new_istanbul();
+		schedule.eip1283 = true; // EIP 2200
+		schedule.eip1706 = true; // EIP 2200
+		schedule.eip2929 = true;
+		schedule.apply_eip2929_costs();
+		schedule
+	}
+
+	/// Reprices state access opcodes for EIP-2929. The warm cost is the base price,
+	/// cold accesses are charged the difference on top of it.
+	pub fn apply_eip2929_costs(&mut self) {
+		self.sload_gas = self.warm_storage_read_cost;
+		self.call_gas = self.warm_storage_read_cost;
+		self.balance_gas = self.warm_storage_read_cost;
+		self.extcodesize_gas = self.warm_storage_read_cost;
+		self.extcodecopy_base_gas = self.warm_storage_read_cost;
+		self.extcodehash_gas = self.warm_storage_read_cost;
+		self.sstore_reset_gas = 5000 - self.cold_sload_cost;
+	}
+
 	fn new(efcd: bool, hdc: bool, tcg: usize) -> Schedule {
 		Schedule {
 			exceptional_failed_code_deposit: efcd,
@@ -367,6 +410,13 @@ impl Schedule {
 			kill_dust: CleanDustMode::Off,
 			eip1283: false,
 			eip1706: false,
+			eip2929: false,
+			eip1559: false,
+			cold_sload_cost: 2100,
+			cold_account_access_cost: 2600,
+			warm_storage_read_cost: 100,
+			tx_access_list_address_gas: 2400,
+			tx_access_list_storage_key_gas: 1900,
 			keep_unsigned_nonce: false,
 			latest_version: U256::zero(),
 			versions: HashMap::new(),
@@ -67,6 +67,8 @@ pub struct FakeExt {
 	pub balances: HashMap<Address, U256>,
 	pub tracing: bool,
 	pub is_static: bool,
+	pub accessed_addresses: HashSet<Address>,
+	pub accessed_storage_keys: HashSet<H256>,
 
 	chain_id: u64,
 }
@@ -107,6 +109,13 @@ impl FakeExt {
 		ext
 	}
 
+	/// New fake externalities with Berlin schedule rules
+	pub fn new_berlin() -> Self {
+		let mut ext = FakeExt::default();
+		ext.schedule = Schedule::new_berlin();
+		ext
+	}
+
 	/// Alter fake externalities to allow wasm
 	pub fn with_wasm(mut self) -> Self {
 		self.schedule.wasm = Some(Default::default());
@@ -259,6 +268,22 @@ impl Ext for FakeExt {
 		self.sstore_clears -= value as i128;
 	}
 
+	fn al_contains_address(&self, address: &Address) -> bool {
+		self.accessed_addresses.contains(address)
+	}
+
+	fn al_insert_address(&mut self, address: Address) {
+		self.accessed_addresses.insert(address);
+	}
+
+	fn al_contains_storage_key(&self, key: &H256) -> bool {
+		self.accessed_storage_keys.contains(key)
+	}
+
+	fn al_insert_storage_key(&mut self, key: H256) {
+		self.accessed_storage_keys.insert(key);
+	}
+
 	fn trace_next_instruction(&mut self, _pc: usize, _instruction: u8, _gas: U256) -> bool {
 		self.tracing
 	}
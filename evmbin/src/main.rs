@@ -34,6 +34,7 @@
 
 #![warn(missing_docs)]
 
+use std::convert::TryFrom;
 use std::sync::Arc;
 use std::{fmt, fs};
 use std::path::PathBuf;
@@ -41,6 +42,7 @@ use std::path::PathBuf;
 use parity_bytes::Bytes;
 use docopt::Docopt;
 use rustc_hex::FromHex;
+use common_types::transaction::SignedTransaction;
 use ethereum_types::{U256, Address};
 use ethcore::{json_tests, test_helpers::TrieSpec};
 use spec;
@@ -157,7 +159,7 @@ fn run_state_test(args: Args) {
 		// Iterate over remaining "post" key of the 2nd level key-value pairs in the state test JSON file.
 		// Skip to next iteration if CLI option `--chain CHAIN` was parsed into `only_chain` and does not match
 		// the current key `fork_spec_name` (i.e. Constantinople, EIP150, EIP158).
-		for (fork_spec_name, states) in test.post_states {
+		for (fork_spec_name, states) in test.post_states.states {
 			if let Some(false) = only_chain.as_ref().map(|only_chain| {
 				&format!("{:?}", fork_spec_name).to_lowercase() == only_chain
 			}) {
@@ -168,7 +170,13 @@ fn run_state_test(args: Args) {
 			// (i.e. list of transactions and associated state roots hashes corresponding each chain).
 			for (tx_index, state) in states.into_iter().enumerate() {
 				let post_root = state.hash.into();
-				let transaction = multitransaction.select(&state.indexes).into();
+				let transaction = match SignedTransaction::try_from(multitransaction.select(&state.indexes)) {
+					Ok(transaction) => transaction,
+					Err(err) => {
+						println!("Skipping {} {:?} #{}: {}", state_test_name, fork_spec_name, tx_index, err);
+						continue;
+					},
+				};
 
 				// Determine the type of trie with state root to create in the database.
 				// The database is a key-value datastore implemented as a database-backend
@@ -441,6 +449,7 @@ fn die<T: fmt::Display>(msg: T) -> ! {
 
 #[cfg(test)]
 mod tests {
+	use std::convert::TryFrom;
 	use common_types::transaction;
 	use docopt::Docopt;
 	use ethcore::test_helpers::TrieSpec;
@@ -565,11 +574,11 @@ mod tests {
 		let env_info = deserialized_state_tests.add11.env.into();
 		let multitransaction = deserialized_state_tests.add11.transaction;
 
-		for (fork_spec_name, tx_states) in deserialized_state_tests.add11.post_states.iter() {
+		for (fork_spec_name, tx_states) in deserialized_state_tests.add11.post_states.states.iter() {
 			for (tx_index, tx_state) in tx_states.into_iter().enumerate() {
 				let (informant, _, res) = informant();
 				let trie_spec = TrieSpec::Secure;
-				let transaction: transaction::SignedTransaction = multitransaction.select(&tx_state.indexes).into();
+				let transaction = transaction::SignedTransaction::try_from(multitransaction.select(&tx_state.indexes)).unwrap();
 				let tx_input = TxInput {
 					state_test_name: &state_test_name,
 					tx_index,
@@ -601,11 +610,11 @@ mod tests {
 		let pre = deserialized_state_tests.create2call_precompiles.pre_state.into();
 		let env_info = deserialized_state_tests.create2call_precompiles.env.into();
 		let multitransaction = deserialized_state_tests.create2call_precompiles.transaction;
-		for (fork_spec_name, tx_states) in deserialized_state_tests.create2call_precompiles.post_states.iter() {
+		for (fork_spec_name, tx_states) in deserialized_state_tests.create2call_precompiles.post_states.states.iter() {
 			for (tx_index, tx_state) in tx_states.into_iter().enumerate() {
 				let (informant, _, _) = informant();
 				let trie_spec = TrieSpec::Secure; // TrieSpec::Fat for --std_dump_json
-				let transaction: transaction::SignedTransaction = multitransaction.select(&tx_state.indexes).into();
+				let transaction = transaction::SignedTransaction::try_from(multitransaction.select(&tx_state.indexes)).unwrap();
 				let tx_input = TxInput {
 					state_test_name: &state_test_name,
 					tx_index,
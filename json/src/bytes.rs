@@ -15,13 +15,40 @@
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
 //! Lenient bytes json deserialization for test json files.
+//!
+//! Within `with_file_references`, bytes may also be given as `{"file": "path"}`, with the hex read
+//! from the file at `path` relative to a base directory.
 
+use std::cell::RefCell;
 use std::fmt;
+use std::fs;
 use std::str::FromStr;
 use std::ops::Deref;
-use rustc_hex::FromHex;
-use serde::{Deserialize, Deserializer};
-use serde::de::{Error, Visitor};
+use std::path::{Path, PathBuf};
+use rustc_hex::{FromHex, ToHex};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{Error, MapAccess, Visitor};
+
+thread_local! {
+	/// Directory file references are resolved against, `None` if they are not allowed.
+	static FILE_REFERENCES_BASE_DIR: RefCell<Option<PathBuf>> = RefCell::new(None);
+}
+
+/// Run `f`, allowing `Bytes` deserialized by it to reference files relative to `base_dir`.
+pub fn with_file_references<T, F: FnOnce() -> T>(base_dir: &Path, f: F) -> T {
+	struct Reset(Option<PathBuf>);
+
+	impl Drop for Reset {
+		fn drop(&mut self) {
+			let previous = self.0.take();
+			FILE_REFERENCES_BASE_DIR.with(|dir| *dir.borrow_mut() = previous);
+		}
+	}
+
+	let previous = FILE_REFERENCES_BASE_DIR.with(|dir| dir.replace(Some(base_dir.to_path_buf())));
+	let _reset = Reset(previous);
+	f()
+}
 
 /// Lenient bytes json deserialization for test json files.
 #[derive(Default, Debug, PartialEq, Eq, Clone, PartialOrd, Ord)]
@@ -58,21 +85,27 @@ impl FromStr for Bytes {
 	type Err = String;
 
 	fn from_str(value: &str) -> Result<Self, Self::Err> {
-		let v = match value.len() {
-			0 => vec![],
-			2 if value.starts_with("0x") => vec![],
-			_ if value.starts_with("0x") && value.len() % 2 == 1 => {
-				let v = "0".to_owned() + &value[2..];
-				FromHex::from_hex(v.as_str()).unwrap_or_default()
-			},
-			_ if value.starts_with("0x") => FromHex::from_hex(&value[2..]).unwrap_or_default(),
-			_ => FromHex::from_hex(value).unwrap_or_default(),
+		let value = if value.starts_with("0x") { &value[2..] } else { value };
+		let v = if value.len() % 2 == 1 {
+			// left-pad odd length hex with a zero nibble
+			let v = "0".to_owned() + value;
+			FromHex::from_hex(v.as_str()).unwrap_or_default()
+		} else {
+			FromHex::from_hex(value).unwrap_or_default()
 		};
 
 		Ok(Bytes(v))
 	}
 }
 
+/// Serializes to a lowercase `0x` prefixed hex string.
+impl Serialize for Bytes {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where S: Serializer {
+		format!("0x{}", self.0.to_hex()).serialize(serializer)
+	}
+}
+
 impl<'a> Deserialize<'a> for Bytes {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 		where D: Deserializer<'a> {
@@ -96,11 +129,29 @@ impl<'a> Visitor<'a> for BytesVisitor {
 	fn visit_string<E>(self, value: String) -> Result<Self::Value, E> where E: Error {
 		self.visit_str(value.as_ref())
 	}
+
+	fn visit_map<V>(self, mut visitor: V) -> Result<Self::Value, V::Error> where V: MapAccess<'a> {
+		let mut file = None;
+		while let Some(key) = visitor.next_key::<String>()? {
+			match key.as_ref() {
+				"file" => file = Some(visitor.next_value::<String>()?),
+				other => return Err(V::Error::unknown_field(other, &["file"])),
+			}
+		}
+		let file = file.ok_or_else(|| V::Error::missing_field("file"))?;
+
+		let path = FILE_REFERENCES_BASE_DIR.with(|dir| dir.borrow().as_ref().map(|dir| dir.join(&file)))
+			.ok_or_else(|| V::Error::custom(format!("file reference {} is not allowed here", file)))?;
+		let hex = fs::read_to_string(&path)
+			.map_err(|e| V::Error::custom(format!("failed to read {}: {}", path.display(), e)))?;
+		Bytes::from_str(hex.trim()).map_err(V::Error::custom)
+	}
 }
 
 #[cfg(test)]
 mod test {
-	use super::Bytes;
+	use std::{env, fs};
+	use super::{Bytes, with_file_references};
 
 	#[test]
 	fn bytes_deserialization() {
@@ -115,9 +166,48 @@ mod test {
 		]);
 	}
 
+	#[test]
+	fn bytes_deserialization_is_lenient_about_prefix_and_length() {
+		let s = r#"["0x00ff", "00ff", "f", ""]"#;
+		let deserialized: Vec<Bytes> = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, vec![
+			Bytes(vec![0x00, 0xff]),
+			Bytes(vec![0x00, 0xff]),
+			Bytes(vec![0x0f]),
+			Bytes(vec![]),
+		]);
+	}
+
+	#[test]
+	fn bytes_serialization() {
+		let deserialized: Bytes = serde_json::from_str(r#""0xdeadbeef""#).unwrap();
+		assert_eq!(serde_json::to_string(&deserialized).unwrap(), r#""0xdeadbeef""#);
+		assert_eq!(serde_json::to_string(&Bytes(vec![])).unwrap(), r#""0x""#);
+	}
+
 	#[test]
 	fn bytes_into() {
 		let v: Vec<u8> = Bytes(vec![0xff, 0x11]).into();
 		assert_eq!(vec![0xff, 0x11], v);
 	}
+
+	#[test]
+	fn bytes_deserialization_from_file_reference() {
+		let dir = env::temp_dir().join(format!("ethjson-bytes-{}", std::process::id()));
+		fs::create_dir_all(dir.join("contracts")).unwrap();
+		fs::write(dir.join("contracts/code.hex"), "0x6000ff\n").unwrap();
+
+		let s = r#"[{"file": "contracts/code.hex"}, "0x12"]"#;
+		let deserialized: Vec<Bytes> = with_file_references(&dir, || serde_json::from_str(s)).unwrap();
+		fs::remove_dir_all(&dir).unwrap();
+
+		assert_eq!(deserialized, vec![Bytes(vec![0x60, 0x00, 0xff]), Bytes(vec![0x12])]);
+	}
+
+	#[test]
+	fn file_references_must_be_enabled() {
+		let s = r#"{"file": "code.hex"}"#;
+		let deserialized: Result<Bytes, _> = serde_json::from_str(s);
+		assert!(deserialized.unwrap_err().to_string().contains("not allowed"));
+	}
 }
@@ -21,6 +21,7 @@ use std::fmt;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::{Error, Visitor};
 use ethereum_types::{H64 as Hash64, H160 as Hash160, H256 as Hash256, H520 as Hash520, Bloom as Hash2048};
+use keccak_hash::keccak;
 
 macro_rules! impl_hash {
 	($name: ident, $inner: ident) => {
@@ -92,9 +93,69 @@ impl_hash!(H256, Hash256);
 impl_hash!(H520, Hash520);
 impl_hash!(Bloom, Hash2048);
 
+/// Address which must carry a valid EIP-55 checksum when given in mixed case. All-lowercase and
+/// all-uppercase addresses are accepted as they are.
+#[derive(Default, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone)]
+pub struct ChecksummedAddress(pub Hash160);
+
+impl ChecksummedAddress {
+	/// EIP-55 checksummed hex representation of the address, without `0x` prefix.
+	fn to_checksummed_hex(&self) -> String {
+		let hex = format!("{:x}", self.0);
+		let hash = keccak(hex.as_bytes());
+		hex.chars().enumerate().map(|(i, c)| {
+			// the nibble of the hash at the position of the character decides its case
+			let nibble = hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 }) & 0xf;
+			if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+		}).collect()
+	}
+}
+
+impl From<ChecksummedAddress> for Hash160 {
+	fn from(other: ChecksummedAddress) -> Hash160 {
+		other.0
+	}
+}
+
+impl From<Hash160> for ChecksummedAddress {
+	fn from(i: Hash160) -> Self {
+		ChecksummedAddress(i)
+	}
+}
+
+impl From<ChecksummedAddress> for Address {
+	fn from(other: ChecksummedAddress) -> Address {
+		Address(other.0)
+	}
+}
+
+impl<'a> Deserialize<'a> for ChecksummedAddress {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where D: Deserializer<'a> {
+		let value = String::deserialize(deserializer)?;
+		let hex = if value.starts_with("0x") { &value[2..] } else { &value[..] };
+		let address = ChecksummedAddress(Hash160::from_str(hex).map_err(|e| {
+			Error::custom(format!("Invalid hex value {}: {}", value, e).as_str())
+		})?);
+
+		let mixed_case = hex.chars().any(|c| c.is_ascii_lowercase()) && hex.chars().any(|c| c.is_ascii_uppercase());
+		if mixed_case && address.to_checksummed_hex() != hex {
+			return Err(Error::custom(format!("Invalid EIP-55 checksum of address {}", value).as_str()));
+		}
+		Ok(address)
+	}
+}
+
+/// Serializes with the EIP-55 checksum.
+impl Serialize for ChecksummedAddress {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+		serializer.serialize_str(&format!("0x{}", self.to_checksummed_hex()))
+	}
+}
+
 #[cfg(test)]
 mod test {
-	use super::H256;
+	use super::{Address, H256, ChecksummedAddress};
 	use std::str::FromStr;
 
 	#[test]
@@ -111,4 +172,35 @@ mod test {
 	fn hash_into() {
 		assert_eq!(ethereum_types::H256::zero(), H256(ethereum_types::H256::zero()).into());
 	}
+
+	#[test]
+	fn hash_serialization() {
+		let address = Address(ethereum_types::H160::from_low_u64_be(0xab));
+		assert_eq!(serde_json::to_string(&address).unwrap(), r#""0x00000000000000000000000000000000000000ab""#);
+
+		let hash = H256(ethereum_types::H256::zero());
+		assert_eq!(serde_json::to_string(&hash).unwrap(), format!(r#""0x{}""#, "0".repeat(64)));
+
+		let hash = H256(ethereum_types::H256::from_str("5A39ED1020C04D4D84539975B893A4E7C53EAB6C2965DB8BC3468093A31BC5AE").unwrap());
+		let serialized = serde_json::to_string(&hash).unwrap();
+		assert_eq!(serialized, r#""0x5a39ed1020c04d4d84539975b893a4e7c53eab6c2965db8bc3468093a31bc5ae""#);
+		assert_eq!(serde_json::from_str::<H256>(&serialized).unwrap(), hash);
+	}
+
+	#[test]
+	fn checksummed_address_deserialization() {
+		let address = ethereum_types::H160::from_str("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap();
+
+		let valid: ChecksummedAddress = serde_json::from_str(r#""0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed""#).unwrap();
+		assert_eq!(valid, ChecksummedAddress(address));
+		assert_eq!(serde_json::to_string(&valid).unwrap(), r#""0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed""#);
+
+		let corrupted: Result<ChecksummedAddress, _> = serde_json::from_str(r#""0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD""#);
+		assert!(corrupted.unwrap_err().to_string().contains("Invalid EIP-55 checksum"));
+
+		let lowercase: ChecksummedAddress = serde_json::from_str(r#""0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed""#).unwrap();
+		assert_eq!(lowercase, ChecksummedAddress(address));
+		let uppercase: ChecksummedAddress = serde_json::from_str(r#""0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED""#).unwrap();
+		assert_eq!(uppercase, ChecksummedAddress(address));
+	}
 }
@@ -39,8 +39,9 @@
 //! ```
 
 use std::collections::BTreeMap;
+use std::iter;
 use crate::{bytes::Bytes, hash::Address, uint::Uint};
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use super::ValidatorSet;
 
 /// Authority params deserialization.
@@ -95,6 +96,27 @@ pub struct AuthorityRoundParams {
 	pub strict_empty_steps_transition: Option<Uint>,
 	/// First block for which a 2/3 quorum (instead of 1/2) is required.
 	pub two_thirds_majority_transition: Option<Uint>,
+	/// Randomness contract addresses with their associated starting block numbers. A single
+	/// address is used starting from block 0.
+	#[serde(default, deserialize_with = "deserialize_randomness_contract_address")]
+	pub randomness_contract_address: Option<BTreeMap<Uint, Address>>,
+}
+
+/// Randomness contract address, either for all blocks or keyed by the block it starts being used at.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RandomnessContractAddress {
+	Single(Address),
+	Multi(BTreeMap<Uint, Address>),
+}
+
+fn deserialize_randomness_contract_address<'de, D>(deserializer: D) -> Result<Option<BTreeMap<Uint, Address>>, D::Error>
+	where D: Deserializer<'de>
+{
+	Ok(Option::<RandomnessContractAddress>::deserialize(deserializer)?.map(|address| match address {
+		RandomnessContractAddress::Single(address) => iter::once((Uint(0.into()), address)).collect(),
+		RandomnessContractAddress::Multi(addresses) => addresses,
+	}))
 }
 
 /// Authority engine deserialization.
@@ -107,6 +129,7 @@ pub struct AuthorityRound {
 
 #[cfg(test)]
 mod tests {
+	use std::collections::BTreeMap;
 	use super::{Address, Uint};
 	use ethereum_types::{U256, H160};
 	use crate::spec::{validator_set::ValidatorSet, authority_round::AuthorityRound};
@@ -138,6 +161,46 @@ mod tests {
 		assert_eq!(deserialized.params.immediate_transitions, None);
 		assert_eq!(deserialized.params.maximum_uncle_count_transition, Some(Uint(10_000_000.into())));
 		assert_eq!(deserialized.params.maximum_uncle_count, Some(Uint(5.into())));
+		assert_eq!(deserialized.params.randomness_contract_address, None);
+	}
+
+	#[test]
+	fn authority_round_deserialization_single_randomness_contract() {
+		let s = r#"{
+			"params": {
+				"stepDuration": "0x02",
+				"validators": {
+					"list" : ["0xc6d9d2cd449a754c494264e1809c50e34d64562b"]
+				},
+				"randomnessContractAddress": "0x2000000000000000000000000000000000000002"
+			}
+		}"#;
 
+		let deserialized: AuthorityRound = serde_json::from_str(s).unwrap();
+		let mut expected = BTreeMap::new();
+		expected.insert(Uint(0.into()), Address(H160::from_str("2000000000000000000000000000000000000002").unwrap()));
+		assert_eq!(deserialized.params.randomness_contract_address, Some(expected));
+	}
+
+	#[test]
+	fn authority_round_deserialization_randomness_contract_transitions() {
+		let s = r#"{
+			"params": {
+				"stepDuration": "0x02",
+				"validators": {
+					"list" : ["0xc6d9d2cd449a754c494264e1809c50e34d64562b"]
+				},
+				"randomnessContractAddress": {
+					"0": "0x2000000000000000000000000000000000000002",
+					"1000": "0x3000000000000000000000000000000000000003"
+				}
+			}
+		}"#;
+
+		let deserialized: AuthorityRound = serde_json::from_str(s).unwrap();
+		let mut expected = BTreeMap::new();
+		expected.insert(Uint(0.into()), Address(H160::from_str("2000000000000000000000000000000000000002").unwrap()));
+		expected.insert(Uint(1000.into()), Address(H160::from_str("3000000000000000000000000000000000000003").unwrap()));
+		assert_eq!(deserialized.params.randomness_contract_address, Some(expected));
 	}
 }
@@ -16,74 +16,335 @@
 
 //! Spec builtin deserialization.
 
-use crate::uint::Uint;
-use serde::Deserialize;
+use std::convert::TryFrom;
+use std::fmt;
+use crate::uint::{Uint, U64};
+use ethereum_types::U256;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde::de::{Error, IntoDeserializer, Unexpected, Visitor};
 
 
 /// Linear pricing.
-#[derive(Debug, PartialEq, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Linear {
 	/// Base price.
+	#[serde(deserialize_with = "price::base")]
 	pub base: usize,
 	/// Price for word.
+	#[serde(deserialize_with = "price::word")]
 	pub word: usize,
 }
 
 /// Pricing for modular exponentiation.
-#[derive(Debug, PartialEq, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Modexp {
 	/// Price divisor.
+	#[serde(deserialize_with = "price::divisor")]
 	pub divisor: usize,
 }
 
+/// Pricing for modular exponentiation as defined by EIP-2565.
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Modexp2565 {
+	/// Price divisor.
+	#[serde(deserialize_with = "price::divisor")]
+	pub divisor: u64,
+	/// Minimum price of a call, 200 if absent.
+	#[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "price::optional_min_price")]
+	pub min_price: Option<u64>,
+}
+
+/// Pricing for modular exponentiation as defined by EIP-7883.
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Modexp7883 {
+	/// Minimum price of a call.
+	#[serde(deserialize_with = "price::min_price")]
+	pub min_price: u64,
+}
+
 /// Pricing for constant alt_bn128 operations (ECADD and ECMUL)
-#[derive(Debug, PartialEq, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct AltBn128ConstOperations {
 	/// price
+	#[serde(deserialize_with = "price::price")]
 	pub price: usize,
 	/// EIP 1108 transition price
+	#[serde(deserialize_with = "price::eip1108_transition_price")]
 	pub eip1108_transition_price: usize,
 }
 
 /// Pricing for alt_bn128_pairing.
-#[derive(Debug, PartialEq, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct AltBn128Pairing {
 	/// Base price.
+	#[serde(deserialize_with = "price::base")]
 	pub base: usize,
 	/// Price per point pair.
+	#[serde(deserialize_with = "price::pair")]
 	pub pair: usize,
 	/// EIP 1108 transition base price
+	#[serde(deserialize_with = "price::eip1108_transition_base")]
 	pub eip1108_transition_base: usize,
 	/// EIP 1108 transition price per point pair
+	#[serde(deserialize_with = "price::eip1108_transition_pair")]
 	pub eip1108_transition_pair: usize,
 }
 
+/// Pricing for BLS12-381 pairing check (EIP-2537).
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Bls12Pairing {
+	/// Base price.
+	#[serde(deserialize_with = "price::base")]
+	pub base: u64,
+	/// Price per point pair.
+	#[serde(deserialize_with = "price::pair")]
+	pub pair: u64,
+}
+
+/// Pricing for the KZG point evaluation precompile (EIP-4844).
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PointEvaluation {
+	/// Fixed price of the operation.
+	#[serde(deserialize_with = "price::price")]
+	pub price: u64,
+	/// Expected input length in bytes, 192 if absent.
+	#[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "price::optional_length")]
+	pub length: Option<u64>,
+}
+
+impl PointEvaluation {
+	/// Input length of the point evaluation precompile defined by EIP-4844.
+	pub const DEFAULT_LENGTH: u64 = 192;
+
+	/// Expected input length in bytes.
+	pub fn length(&self) -> u64 {
+		self.length.unwrap_or(Self::DEFAULT_LENGTH)
+	}
+}
+
+/// Pricing for BLS12-381 multi-exponentiation (EIP-2537).
+///
+/// The price of `k` pairs is `k * base * discount / 1000`, where `discount` is looked up in the
+/// discount table at index `k - 1`, falling back to the last entry for larger `k`.
+#[derive(Debug, PartialEq, Serialize, Clone)]
+pub struct Bls12MultiExp {
+	/// Base price of a single pair.
+	pub base: u64,
+	/// Discount per number of pairs, indexed by `pairs - 1`.
+	pub discount: Vec<u64>,
+	/// Upper bound of any discount table entry.
+	pub max_discount: u64,
+}
+
+impl<'de> Deserialize<'de> for Bls12MultiExp {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where D: Deserializer<'de> {
+		#[derive(Deserialize)]
+		#[serde(deny_unknown_fields)]
+		struct Inner {
+			#[serde(deserialize_with = "price::base")]
+			base: u64,
+			#[serde(deserialize_with = "price::discount")]
+			discount: Vec<u64>,
+			#[serde(deserialize_with = "price::max_discount")]
+			max_discount: u64,
+		}
+
+		let Inner { base, discount, max_discount } = Inner::deserialize(deserializer)?;
+		if discount.is_empty() {
+			return Err(Error::custom("discount table must not be empty"));
+		}
+		if let Some(d) = discount.iter().find(|d| **d > max_discount) {
+			return Err(Error::custom(format!("discount {} exceeds max_discount {}", d, max_discount)));
+		}
+
+		Ok(Bls12MultiExp { base, discount, max_discount })
+	}
+}
+
 /// Pricing variants.
-#[derive(Debug, PartialEq, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "snake_case")]
-pub enum Pricing {
+pub enum PricingInner {
 	/// Pricing for Blake2 compression function: each call costs the same amount per round.
 	Blake2F {
 		/// Price per round of Blake2 compression function.
+		#[serde(deserialize_with = "price::gas_per_round")]
 		gas_per_round: u64,
+		/// Maximum number of rounds a call may request, unlimited if absent.
+		#[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "price::optional_max_rounds")]
+		max_rounds: Option<u64>,
 	},
 	/// Linear pricing.
 	Linear(Linear),
+	/// Constant pricing, every call costs the same regardless of the input.
+	Const {
+		/// Price of a call.
+		#[serde(deserialize_with = "price::price")]
+		price: u64,
+	},
 	/// Pricing for modular exponentiation.
 	Modexp(Modexp),
+	/// Pricing for modular exponentiation as defined by EIP-2565.
+	Modexp2565(Modexp2565),
+	/// Pricing for modular exponentiation as defined by EIP-7883.
+	Modexp7883(Modexp7883),
 	/// Pricing for alt_bn128_pairing exponentiation.
 	AltBn128Pairing(AltBn128Pairing),
 	/// Pricing for constant alt_bn128 operations
 	AltBn128ConstOperations(AltBn128ConstOperations),
+	/// Pricing for BLS12-381 G1 point addition (EIP-2537).
+	Bls12G1Add {
+		/// Fixed price of the operation.
+		#[serde(deserialize_with = "price::price")]
+		price: u64,
+	},
+	/// Pricing for BLS12-381 G1 scalar multiplication (EIP-2537).
+	Bls12G1Mul {
+		/// Fixed price of the operation.
+		#[serde(deserialize_with = "price::price")]
+		price: u64,
+	},
+	/// Pricing for BLS12-381 G1 multi-exponentiation (EIP-2537).
+	#[serde(rename = "bls12_g1_multiexp")]
+	Bls12G1MultiExp(Bls12MultiExp),
+	/// Pricing for BLS12-381 G2 point addition (EIP-2537).
+	Bls12G2Add {
+		/// Fixed price of the operation.
+		#[serde(deserialize_with = "price::price")]
+		price: u64,
+	},
+	/// Pricing for BLS12-381 G2 scalar multiplication (EIP-2537).
+	Bls12G2Mul {
+		/// Fixed price of the operation.
+		#[serde(deserialize_with = "price::price")]
+		price: u64,
+	},
+	/// Pricing for BLS12-381 G2 multi-exponentiation (EIP-2537).
+	#[serde(rename = "bls12_g2_multiexp")]
+	Bls12G2MultiExp(Bls12MultiExp),
+	/// Pricing for BLS12-381 pairing check (EIP-2537).
+	Bls12Pairing(Bls12Pairing),
+	/// Pricing for mapping a BLS12-381 base field element to G1 (EIP-2537).
+	Bls12MapFpToG1 {
+		/// Fixed price of the operation.
+		#[serde(deserialize_with = "price::price")]
+		price: u64,
+	},
+	/// Pricing for mapping a BLS12-381 extension field element to G2 (EIP-2537).
+	Bls12MapFp2ToG2 {
+		/// Fixed price of the operation.
+		#[serde(deserialize_with = "price::price")]
+		price: u64,
+	},
+	/// Pricing for the KZG point evaluation precompile (EIP-4844).
+	PointEvaluation(PointEvaluation),
+	/// Pricing for secp256r1 signature verification (RIP-7212).
+	Secp256r1Verify {
+		/// Fixed price of the operation.
+		#[serde(deserialize_with = "price::price")]
+		price: u64,
+	},
+}
+
+impl PricingInner {
+	/// Splits an alt_bn128 pricing into its pricings before and after EIP-1108, with the
+	/// `eip1108_transition_*` fields cleared.
+	fn eip1108_split(&self) -> Option<(PricingInner, PricingInner)> {
+		match *self {
+			PricingInner::AltBn128ConstOperations(ref p) => Some((
+				PricingInner::AltBn128ConstOperations(AltBn128ConstOperations {
+					price: p.price,
+					eip1108_transition_price: 0,
+				}),
+				PricingInner::AltBn128ConstOperations(AltBn128ConstOperations {
+					price: p.eip1108_transition_price,
+					eip1108_transition_price: 0,
+				}),
+			)),
+			PricingInner::AltBn128Pairing(ref p) => Some((
+				PricingInner::AltBn128Pairing(AltBn128Pairing {
+					base: p.base,
+					pair: p.pair,
+					eip1108_transition_base: 0,
+					eip1108_transition_pair: 0,
+				}),
+				PricingInner::AltBn128Pairing(AltBn128Pairing {
+					base: p.eip1108_transition_base,
+					pair: p.eip1108_transition_pair,
+					eip1108_transition_base: 0,
+					eip1108_transition_pair: 0,
+				}),
+			)),
+			_ => None,
+		}
+	}
+}
+
+/// Pricing that takes effect from a given block or timestamp.
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PricingAt {
+	/// Activation block.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub activate_at: Option<U64>,
+	/// Activation timestamp, alternative to `activate_at`.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub activate_at_timestamp: Option<U64>,
+	/// Pricing used from the activation block onwards.
+	pub price: PricingInner,
+}
+
+/// Builtin pricing, either a single pricing or a non-empty schedule of repricings.
+#[derive(Debug, PartialEq, Serialize, Clone)]
+#[serde(untagged)]
+pub enum Pricing {
+	/// Single pricing.
+	Single(PricingInner),
+	/// Multiple pricings, each taking effect from its activation block.
+	Multi(Vec<PricingAt>),
+}
+
+impl<'de> Deserialize<'de> for Pricing {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where D: Deserializer<'de> {
+		// dispatch on the shape by hand, an untagged enum would hide the errors of the pricing itself
+		let value = serde_json::Value::deserialize(deserializer)?;
+		if !value.is_array() {
+			return serde_json::from_value(value).map(Pricing::Single).map_err(Error::custom);
+		}
+
+		let pricings: Vec<PricingAt> = serde_json::from_value(value).map_err(Error::custom)?;
+		if pricings.is_empty() {
+			return Err(Error::custom("multi-pricing schedule must not be empty"));
+		}
+		Ok(Pricing::Multi(pricings))
+	}
+}
+
+impl From<PricingInner> for Pricing {
+	fn from(pricing: PricingInner) -> Self {
+		Pricing::Single(pricing)
+	}
+}
+
+impl From<Vec<PricingAt>> for Pricing {
+	fn from(pricings: Vec<PricingAt>) -> Self {
+		Pricing::Multi(pricings)
+	}
 }
 
 /// Spec builtin.
-#[derive(Debug, PartialEq, Deserialize, Clone)]
+#[derive(Debug, PartialEq, Deserialize, Serialize, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Builtin {
 	/// Builtin name.
@@ -91,14 +352,361 @@ pub struct Builtin {
 	/// Builtin pricing.
 	pub pricing: Pricing,
 	/// Activation block.
-	pub activate_at: Option<Uint>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub activate_at: Option<U64>,
+	/// Activation timestamp, alternative to `activate_at`.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub activate_at_timestamp: Option<U64>,
+	/// Block from which the builtin no longer exists.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub deactivate_at: Option<U64>,
 	/// EIP 1108
-	pub eip1108_transition: Option<Uint>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub eip1108_transition: Option<U64>,
+	/// Maximum accepted input length in bytes, unlimited if absent.
+	#[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "price::optional_max_input_length")]
+	pub max_input_length: Option<u64>,
+	/// Whether a failing call falls back to the code deployed at the builtin address.
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub fallback_to_code: bool,
+	/// Whether calls are charged the gas the builtin reports once it has parsed its input, capped
+	/// by the upfront price.
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub metered: bool,
+	/// Free-form description of the builtin, e.g. why it is part of the spec.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub info: Option<String>,
+}
+
+impl Default for Builtin {
+	/// An unnamed builtin which costs nothing, active from genesis.
+	fn default() -> Self {
+		Builtin {
+			name: String::new(),
+			pricing: Pricing::Single(PricingInner::Linear(Linear { base: 0, word: 0 })),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		}
+	}
+}
+
+impl Builtin {
+	/// Builtin with the given name and pricing, active from genesis.
+	pub fn new<N: Into<String>, P: Into<Pricing>>(name: N, pricing: P) -> Self {
+		Builtin {
+			name: name.into(),
+			pricing: pricing.into(),
+			..Default::default()
+		}
+	}
+
+	/// Activates the builtin at the given block.
+	pub fn activate_at(mut self, block: u64) -> Self {
+		self.activate_at = Some(U64(block));
+		self
+	}
+
+	/// Activates the builtin at the given timestamp.
+	pub fn activate_at_timestamp(mut self, timestamp: u64) -> Self {
+		self.activate_at_timestamp = Some(U64(timestamp));
+		self
+	}
+
+	/// Removes the builtin from the given block onwards.
+	pub fn deactivate_at(mut self, block: u64) -> Self {
+		self.deactivate_at = Some(U64(block));
+		self
+	}
+
+	/// Limits the accepted input length in bytes.
+	pub fn max_input_length(mut self, length: u64) -> Self {
+		self.max_input_length = Some(length);
+		self
+	}
+
+	/// Falls back to the code deployed at the builtin address when a call fails.
+	pub fn fallback_to_code(mut self) -> Self {
+		self.fallback_to_code = true;
+		self
+	}
+
+	/// Charges calls the gas reported by the builtin instead of the upfront price when lower.
+	pub fn metered(mut self) -> Self {
+		self.metered = true;
+		self
+	}
+
+	/// Attaches a free-form description.
+	pub fn info<I: Into<String>>(mut self, info: I) -> Self {
+		self.info = Some(info.into());
+		self
+	}
+
+	/// Pricing in effect at the given block: the `Single` pricing once `activate_at` is reached,
+	/// or the `Multi` pricing with the latest activation at or before the block, whatever the order
+	/// of the list. Pricings activated by timestamp are not considered.
+	pub fn pricing_at(&self, block: u64) -> Option<&PricingInner> {
+		let block = U64(block);
+		let reached = |activate_at: Option<U64>, activate_at_timestamp: Option<U64>| {
+			match (activate_at, activate_at_timestamp) {
+				(Some(activate_at), _) => activate_at <= block,
+				(None, Some(_)) => false,
+				(None, None) => true,
+			}
+		};
+
+		match self.pricing {
+			Pricing::Single(ref pricing) => Some(pricing).filter(|_| reached(self.activate_at, self.activate_at_timestamp)),
+			Pricing::Multi(ref pricings) => pricings.iter()
+				.filter(|p| reached(p.activate_at, p.activate_at_timestamp))
+				.max_by_key(|p| p.activate_at)
+				.map(|p| &p.price),
+		}
+	}
+
+	/// Pricings paired with their activation block, in ascending block order. Pricings without an
+	/// activation block, i.e. active from genesis or activated by timestamp, come first. Pricings
+	/// activated at the same block keep their relative order.
+	pub fn schedule(&self) -> Vec<(Option<U64>, &PricingInner)> {
+		let mut schedule = match self.pricing {
+			Pricing::Single(ref pricing) => vec![(self.activate_at, pricing)],
+			Pricing::Multi(ref pricings) => pricings.iter().map(|p| (p.activate_at, &p.price)).collect(),
+		};
+		schedule.sort_by_key(|&(activate_at, _)| activate_at);
+		schedule
+	}
+
+	/// Rewrites the legacy `eip1108_transition` into the equivalent multi-pricing schedule: the
+	/// original pricing from the builtin activation, followed by the `eip1108_transition_*`
+	/// pricing from the transition block. The legacy fields of the result are cleared. Builtins
+	/// without the legacy fields, or whose pricing doesn't support them, are returned unchanged.
+	pub fn normalized(self) -> Builtin {
+		let transition = match self.eip1108_transition {
+			Some(transition) => transition,
+			None => return self,
+		};
+		let (before, after) = match self.pricing {
+			Pricing::Single(ref pricing) => match pricing.eip1108_split() {
+				Some(split) => split,
+				None => return self,
+			},
+			Pricing::Multi(_) => return self,
+		};
+
+		// a builtin activated at or after the transition never uses the original pricing
+		let activated_after = match (self.activate_at, self.activate_at_timestamp) {
+			(Some(activate_at), _) => activate_at >= transition,
+			(None, Some(_)) => false,
+			(None, None) => transition == U64::default(),
+		};
+		let mut pricings = vec![PricingAt {
+			activate_at: self.activate_at,
+			activate_at_timestamp: self.activate_at_timestamp,
+			price: if activated_after { after.clone() } else { before },
+		}];
+		if !activated_after {
+			pricings.push(PricingAt { activate_at: Some(transition), activate_at_timestamp: None, price: after });
+		}
+
+		Builtin {
+			pricing: Pricing::Multi(pricings),
+			activate_at: None,
+			activate_at_timestamp: None,
+			eip1108_transition: None,
+			..self
+		}
+	}
+
+	/// Checks that the block activations of a multi-pricing schedule are strictly increasing, which
+	/// rules out both out of order and duplicate activations.
+	pub fn validate(&self) -> Result<(), BuiltinError> {
+		if let Pricing::Multi(ref pricings) = self.pricing {
+			let mut previous: Option<U64> = None;
+			for (index, pricing) in pricings.iter().enumerate() {
+				// pricings activated by timestamp are ordered independently of block activations
+				let current = match (pricing.activate_at, pricing.activate_at_timestamp) {
+					(Some(block), _) => block,
+					(None, Some(_)) => continue,
+					(None, None) => U64::default(),
+				};
+				if let Some(previous) = previous {
+					if current == previous {
+						return Err(BuiltinError::DuplicateActivation { block: current });
+					}
+					if current < previous {
+						return Err(BuiltinError::UnsortedPricing { index, previous, current });
+					}
+				}
+				previous = Some(current);
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Invalid builtin specification.
+#[derive(Debug, PartialEq, Clone)]
+pub enum BuiltinError {
+	/// The multi-pricing entry at `index` is not activated after the preceding entry.
+	UnsortedPricing {
+		/// Index of the offending entry.
+		index: usize,
+		/// Activation block of the preceding entry.
+		previous: U64,
+		/// Activation block of the offending entry.
+		current: U64,
+	},
+	/// Two multi-pricing entries are activated at the same block.
+	DuplicateActivation {
+		/// The shared activation block.
+		block: U64,
+	},
+	/// An integer field does not fit into 64 bits.
+	PriceOverflow {
+		/// Name of the offending field.
+		field: &'static str,
+		/// The value given in the spec.
+		value: Uint,
+	},
+}
+
+impl fmt::Display for BuiltinError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			BuiltinError::UnsortedPricing { index, ref previous, ref current } => write!(
+				f, "pricing {} activated at block {} is not after the preceding pricing activated at block {}",
+				index, current.0, previous.0,
+			),
+			BuiltinError::DuplicateActivation { ref block } => write!(
+				f, "more than one pricing is activated at block {}", block.0,
+			),
+			BuiltinError::PriceOverflow { field, ref value } => write!(
+				f, "{} of {} exceeds the maximum of {}", field, value.0, u64::max_value(),
+			),
+		}
+	}
+}
+
+impl std::error::Error for BuiltinError {}
+
+/// An integer field as written in the spec, before checking that it fits into 64 bits.
+struct PriceValue(U256);
+
+impl<'de> Deserialize<'de> for PriceValue {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where D: Deserializer<'de> {
+		deserializer.deserialize_any(PriceVisitor)
+	}
+}
+
+struct PriceVisitor;
+
+impl<'de> Visitor<'de> for PriceVisitor {
+	type Value = PriceValue;
+
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(formatter, "a hex encoded or decimal uint")
+	}
+
+	fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> where E: Error {
+		Ok(PriceValue(value.into()))
+	}
+
+	fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> where E: Error {
+		// serde_json hands out integers above `u64::max_value()` as floats
+		if value < 0.0 || value.fract() != 0.0 {
+			return Err(Error::invalid_type(Unexpected::Float(value), &self));
+		}
+		U256::from_dec_str(&format!("{:.0}", value))
+			.map(PriceValue)
+			.map_err(|_| Error::invalid_type(Unexpected::Float(value), &self))
+	}
+
+	fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> where E: Error {
+		Uint::deserialize(value.into_deserializer()).map(|value| PriceValue(value.0))
+	}
+}
+
+/// Narrows an integer field to `T`, naming `field` if the value does not fit.
+fn checked_price<T, E>(field: &'static str, value: PriceValue) -> Result<T, E>
+	where T: TryFrom<u64>, E: Error
+{
+	let overflow = || Error::custom(BuiltinError::PriceOverflow { field, value: Uint(value.0) });
+	if value.0 > U256::from(u64::max_value()) {
+		return Err(overflow());
+	}
+	T::try_from(value.0.low_u64()).map_err(|_| overflow())
+}
+
+/// Deserializers for the integer fields of builtins, accepting JSON numbers as well as hex or
+/// decimal strings.
+mod price {
+	use std::convert::TryFrom;
+	use serde::{Deserialize, Deserializer};
+	use super::{checked_price, PriceValue};
+
+	macro_rules! price_fields {
+		($($field:ident),*) => {$(
+			pub fn $field<'de, D, T>(d: D) -> Result<T, D::Error>
+				where D: Deserializer<'de>, T: TryFrom<u64>
+			{
+				checked_price(stringify!($field), PriceValue::deserialize(d)?)
+			}
+		)*}
+	}
+
+	macro_rules! optional_price_fields {
+		($($name:ident => $field:ident),*) => {$(
+			pub fn $name<'de, D, T>(d: D) -> Result<Option<T>, D::Error>
+				where D: Deserializer<'de>, T: TryFrom<u64>
+			{
+				Option::<PriceValue>::deserialize(d)?
+					.map(|value| checked_price(stringify!($field), value))
+					.transpose()
+			}
+		)*}
+	}
+
+	price_fields!(
+		base, word, divisor, min_price, price, pair, max_discount, gas_per_round,
+		eip1108_transition_price, eip1108_transition_base, eip1108_transition_pair
+	);
+
+	optional_price_fields!(
+		optional_min_price => min_price,
+		optional_length => length,
+		optional_max_rounds => max_rounds,
+		optional_max_input_length => max_input_length
+	);
+
+	pub fn discount<'de, D, T>(d: D) -> Result<Vec<T>, D::Error>
+		where D: Deserializer<'de>, T: TryFrom<u64>
+	{
+		Vec::<PriceValue>::deserialize(d)?
+			.into_iter()
+			.map(|value| checked_price("discount", value))
+			.collect()
+	}
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{Builtin, Modexp, Linear, Pricing, Uint};
+	use super::{
+		AltBn128ConstOperations, AltBn128Pairing, Bls12MultiExp, Bls12Pairing, Builtin, BuiltinError, PointEvaluation, Modexp, Modexp2565, Modexp7883, Linear, Pricing, PricingAt,
+		PricingInner, Uint, U64, U256
+	};
+
+	fn assert_round_trip(builtin: Builtin) {
+		let serialized = serde_json::to_string(&builtin).unwrap();
+		let deserialized: Builtin = serde_json::from_str(&serialized).unwrap();
+		assert_eq!(deserialized, builtin);
+	}
 
 	#[test]
 	fn builtin_deserialization() {
@@ -108,7 +716,7 @@ mod tests {
 		}"#;
 		let deserialized: Builtin = serde_json::from_str(s).unwrap();
 		assert_eq!(deserialized.name, "ecrecover");
-		assert_eq!(deserialized.pricing, Pricing::Linear(Linear { base: 3000, word: 0 }));
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::Linear(Linear { base: 3000, word: 0 })));
 		assert!(deserialized.activate_at.is_none());
 	}
 
@@ -121,10 +729,21 @@ mod tests {
 		}"#;
 		let deserialized: Builtin = serde_json::from_str(s).unwrap();
 		assert_eq!(deserialized.name, "blake2_f");
-		assert_eq!(deserialized.pricing, Pricing::Blake2F { gas_per_round: 123 });
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::Blake2F { gas_per_round: 123, max_rounds: None }));
 		assert!(deserialized.activate_at.is_some());
 	}
 
+	#[test]
+	fn deserialization_blake2_f_max_rounds() {
+		let legacy = r#"{ "blake2_f": { "gas_per_round": 1 } }"#;
+		let deserialized: PricingInner = serde_json::from_str(legacy).unwrap();
+		assert_eq!(deserialized, PricingInner::Blake2F { gas_per_round: 1, max_rounds: None });
+
+		let capped = r#"{ "blake2_f": { "gas_per_round": 1, "max_rounds": 4294967295 } }"#;
+		let deserialized: PricingInner = serde_json::from_str(capped).unwrap();
+		assert_eq!(deserialized, PricingInner::Blake2F { gas_per_round: 1, max_rounds: Some(4294967295) });
+	}
+
 	#[test]
 	fn activate_at() {
 		let s = r#"{
@@ -135,7 +754,898 @@ mod tests {
 
 		let deserialized: Builtin = serde_json::from_str(s).unwrap();
 		assert_eq!(deserialized.name, "late_start");
-		assert_eq!(deserialized.pricing, Pricing::Modexp(Modexp { divisor: 5 }));
-		assert_eq!(deserialized.activate_at, Some(Uint(100000.into())));
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::Modexp(Modexp { divisor: 5 })));
+		assert_eq!(deserialized.activate_at, Some(U64(100000)));
+	}
+
+	#[test]
+	fn deserialization_bls12_g1_add_builtin() {
+		let s = r#"{
+			"name": "bls12_g1_add",
+			"activate_at": "0xffffff",
+			"pricing": { "bls12_g1_add": { "price": 600 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.name, "bls12_g1_add");
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::Bls12G1Add { price: 600 }));
+		assert!(deserialized.activate_at.is_some());
+	}
+
+	#[test]
+	fn deserialization_multi_pricing() {
+		let s = r#"{
+			"name": "bls12_g1_add",
+			"pricing": [
+				{ "activate_at": 0, "price": { "bls12_g1_add": { "price": 600 } } },
+				{ "activate_at": 100, "price": { "bls12_g1_add": { "price": 500 } } }
+			]
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.pricing, Pricing::Multi(vec![
+			PricingAt {
+				activate_at: Some(U64(0)),
+				activate_at_timestamp: None,
+				price: PricingInner::Bls12G1Add { price: 600 },
+			},
+			PricingAt {
+				activate_at: Some(U64(100)),
+				activate_at_timestamp: None,
+				price: PricingInner::Bls12G1Add { price: 500 },
+			},
+		]));
+	}
+
+	#[test]
+	fn activate_at_timestamp() {
+		let s = r#"{
+			"name": "late_start",
+			"activate_at_timestamp": "0x5e0be100",
+			"pricing": { "modexp": { "divisor": 5 } }
+		}"#;
+
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.activate_at, None);
+		assert_eq!(deserialized.activate_at_timestamp, Some(U64(0x5e0be100)));
+	}
+
+	#[test]
+	fn deserialization_multi_pricing_timestamp() {
+		let s = r#"{
+			"name": "bls12_g1_add",
+			"pricing": [
+				{ "activate_at": 0, "price": { "bls12_g1_add": { "price": 600 } } },
+				{ "activate_at_timestamp": 1577836800, "price": { "bls12_g1_add": { "price": 500 } } }
+			]
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.pricing, Pricing::Multi(vec![
+			PricingAt {
+				activate_at: Some(U64(0)),
+				activate_at_timestamp: None,
+				price: PricingInner::Bls12G1Add { price: 600 },
+			},
+			PricingAt {
+				activate_at: None,
+				activate_at_timestamp: Some(U64(1577836800)),
+				price: PricingInner::Bls12G1Add { price: 500 },
+			},
+		]));
+	}
+
+	#[test]
+	fn deserialization_bls12_g1_mul_builtin() {
+		let s = r#"{
+			"name": "bls12_g1_mul",
+			"pricing": [
+				{ "activate_at": "0xffffff", "price": { "bls12_g1_mul": { "price": 12000 } } }
+			]
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.name, "bls12_g1_mul");
+		assert_eq!(deserialized.pricing, Pricing::Multi(vec![
+			PricingAt {
+				activate_at: Some(U64(0xffffff)),
+				activate_at_timestamp: None,
+				price: PricingInner::Bls12G1Mul { price: 12000 },
+			},
+		]));
+
+		let s = r#"{
+			"name": "bls12_g1_mul",
+			"pricing": { "bls12_g1_mul": { "price": 12000, "word": 1 } }
+		}"#;
+		assert!(serde_json::from_str::<Builtin>(s).is_err());
+	}
+
+	#[test]
+	fn single_pricing_round_trip() {
+		assert_round_trip(Builtin {
+			name: "ecrecover".to_owned(),
+			pricing: Pricing::Single(PricingInner::Linear(Linear { base: 3000, word: 0 })),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		});
+		assert_round_trip(Builtin {
+			name: "blake2_f".to_owned(),
+			pricing: Pricing::Single(PricingInner::Blake2F { gas_per_round: 1, max_rounds: Some(u32::max_value() as u64) }),
+			activate_at: Some(U64(0xffffff)),
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		});
+	}
+
+	#[test]
+	fn eip1108_round_trip() {
+		assert_round_trip(Builtin {
+			name: "alt_bn128_add".to_owned(),
+			pricing: Pricing::Single(PricingInner::AltBn128ConstOperations(AltBn128ConstOperations {
+				price: 500,
+				eip1108_transition_price: 150,
+			})),
+			activate_at: Some(U64(0)),
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: Some(U64(0x17d433)),
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		});
+		assert_round_trip(Builtin {
+			name: "alt_bn128_pairing".to_owned(),
+			pricing: Pricing::Single(PricingInner::AltBn128Pairing(AltBn128Pairing {
+				base: 100_000,
+				pair: 80_000,
+				eip1108_transition_base: 45_000,
+				eip1108_transition_pair: 34_000,
+			})),
+			activate_at: Some(U64(0)),
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: Some(U64(0x17d433)),
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		});
+	}
+
+	#[test]
+	fn multi_pricing_round_trip() {
+		assert_round_trip(Builtin {
+			name: "modexp".to_owned(),
+			pricing: Pricing::Multi(vec![
+				PricingAt {
+					activate_at: Some(U64(0)),
+					activate_at_timestamp: None,
+					price: PricingInner::Modexp(Modexp { divisor: 20 }),
+				},
+				PricingAt {
+					activate_at: None,
+					activate_at_timestamp: Some(U64(1577836800)),
+					price: PricingInner::Bls12G1Mul { price: 12000 },
+				},
+			]),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		});
+	}
+
+	#[test]
+	fn serialization_format() {
+		let builtin = Builtin {
+			name: "ecrecover".to_owned(),
+			pricing: Pricing::Single(PricingInner::Linear(Linear { base: 3000, word: 0 })),
+			activate_at: None,
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: None,
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		};
+		assert_eq!(
+			serde_json::to_string(&builtin).unwrap(),
+			r#"{"name":"ecrecover","pricing":{"linear":{"base":3000,"word":0}}}"#
+		);
+	}
+
+	#[test]
+	fn serialization_preserves_input_shape() {
+		let inputs = [
+			r#"{
+				"name": "alt_bn128_pairing",
+				"pricing": {
+					"alt_bn128_pairing": {
+						"base": 100000,
+						"pair": 80000,
+						"eip1108_transition_base": 45000,
+						"eip1108_transition_pair": 34000
+					}
+				},
+				"activate_at": "0x42ae50",
+				"eip1108_transition": "0x8a61c8"
+			}"#,
+			r#"{
+				"name": "modexp",
+				"pricing": [
+					{ "activate_at": "0x0", "price": { "modexp": { "divisor": 20 } } },
+					{ "activate_at_timestamp": "0x64373057", "price": { "modexp2565": { "divisor": 3 } } }
+				]
+			}"#,
+		];
+
+		for input in &inputs {
+			let builtin: Builtin = serde_json::from_str(input).unwrap();
+			let expected: serde_json::Value = serde_json::from_str(input).unwrap();
+			assert_eq!(serde_json::to_value(&builtin).unwrap(), expected);
+		}
+	}
+
+	#[test]
+	fn deserialization_bls12_g1_multiexp_builtin() {
+		let discount: Vec<u64> = (0..128).map(|i| 1000 - 3 * i).collect();
+		let s = format!(r#"{{
+			"name": "bls12_g1_multiexp",
+			"pricing": {{ "bls12_g1_multiexp": {{ "base": 12000, "discount": {:?}, "max_discount": 1000 }} }}
+		}}"#, discount);
+		let deserialized: Builtin = serde_json::from_str(&s).unwrap();
+		assert_eq!(deserialized.name, "bls12_g1_multiexp");
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::Bls12G1MultiExp(Bls12MultiExp {
+			base: 12000,
+			discount,
+			max_discount: 1000,
+		})));
+	}
+
+	#[test]
+	fn bls12_multiexp_rejects_invalid_discount_table() {
+		let empty = r#"{ "bls12_g1_multiexp": { "base": 12000, "discount": [], "max_discount": 1000 } }"#;
+		assert!(serde_json::from_str::<PricingInner>(empty).is_err());
+
+		let above_max = r#"{ "bls12_g1_multiexp": { "base": 12000, "discount": [1000, 1001], "max_discount": 1000 } }"#;
+		assert!(serde_json::from_str::<PricingInner>(above_max).is_err());
+	}
+
+	#[test]
+	fn deserialization_modexp2565() {
+		let s = r#"{
+			"name": "modexp",
+			"pricing": [
+				{ "activate_at": 0, "price": { "modexp": { "divisor": 20 } } },
+				{ "activate_at": "0xbad420", "price": { "modexp2565": { "divisor": 3, "min_price": 200 } } }
+			]
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.pricing, Pricing::Multi(vec![
+			PricingAt {
+				activate_at: Some(U64(0)),
+				activate_at_timestamp: None,
+				price: PricingInner::Modexp(Modexp { divisor: 20 }),
+			},
+			PricingAt {
+				activate_at: Some(U64(0xbad420)),
+				activate_at_timestamp: None,
+				price: PricingInner::Modexp2565(Modexp2565 { divisor: 3, min_price: Some(200) }),
+			},
+		]));
+	}
+
+	#[test]
+	fn pricing_at() {
+		let single: Builtin = serde_json::from_str(r#"{
+			"name": "ecrecover",
+			"activate_at": 100,
+			"pricing": { "linear": { "base": 3000, "word": 0 } }
+		}"#).unwrap();
+		assert_eq!(single.pricing_at(99), None);
+		assert_eq!(single.pricing_at(100), Some(&PricingInner::Linear(Linear { base: 3000, word: 0 })));
+		assert_eq!(single.pricing_at(u64::max_value()), Some(&PricingInner::Linear(Linear { base: 3000, word: 0 })));
+
+		let multi: Builtin = serde_json::from_str(r#"{
+			"name": "modexp",
+			"pricing": [
+				{ "activate_at": 10, "price": { "modexp": { "divisor": 20 } } },
+				{ "activate_at": 20, "price": { "modexp2565": { "divisor": 3 } } },
+				{ "activate_at": 30, "price": { "modexp7883": { "min_price": 500 } } }
+			]
+		}"#).unwrap();
+		assert_eq!(multi.pricing_at(0), None);
+		assert_eq!(multi.pricing_at(9), None);
+		assert_eq!(multi.pricing_at(10), Some(&PricingInner::Modexp(Modexp { divisor: 20 })));
+		assert_eq!(multi.pricing_at(25), Some(&PricingInner::Modexp2565(Modexp2565 { divisor: 3, min_price: None })));
+		assert_eq!(multi.pricing_at(30), Some(&PricingInner::Modexp7883(Modexp7883 { min_price: 500 })));
+
+		// the latest activation wins, not the last entry of the list
+		let unsorted: Builtin = serde_json::from_str(r#"{
+			"name": "modexp",
+			"pricing": [
+				{ "activate_at": 30, "price": { "modexp7883": { "min_price": 500 } } },
+				{ "activate_at": 10, "price": { "modexp": { "divisor": 20 } } },
+				{ "activate_at": 20, "price": { "modexp2565": { "divisor": 3 } } }
+			]
+		}"#).unwrap();
+		for block in &[0, 10, 25, 30, u64::max_value()] {
+			assert_eq!(unsorted.pricing_at(*block), multi.pricing_at(*block));
+		}
+	}
+
+	#[test]
+	fn deserialization_hex_prices() {
+		let number: PricingInner = serde_json::from_str(r#"{ "linear": { "base": 3000, "word": 0 } }"#).unwrap();
+		let hex: PricingInner = serde_json::from_str(r#"{ "linear": { "base": "0xBB8", "word": "0x0" } }"#).unwrap();
+		assert_eq!(number, PricingInner::Linear(Linear { base: 3000, word: 0 }));
+		assert_eq!(hex, number);
+
+		let number: PricingInner = serde_json::from_str(r#"{ "modexp": { "divisor": 20 } }"#).unwrap();
+		let hex: PricingInner = serde_json::from_str(r#"{ "modexp": { "divisor": "0x14" } }"#).unwrap();
+		assert_eq!(number, PricingInner::Modexp(Modexp { divisor: 20 }));
+		assert_eq!(hex, number);
+
+		let number: PricingInner = serde_json::from_str(r#"{ "blake2_f": { "gas_per_round": 1 } }"#).unwrap();
+		let hex: PricingInner = serde_json::from_str(r#"{ "blake2_f": { "gas_per_round": "0x1" } }"#).unwrap();
+		assert_eq!(number, PricingInner::Blake2F { gas_per_round: 1, max_rounds: None });
+		assert_eq!(hex, number);
+
+		// serialized back as JSON numbers
+		assert_eq!(serde_json::to_string(&hex).unwrap(), r#"{"blake2_f":{"gas_per_round":1}}"#);
+	}
+
+	#[test]
+	fn deserialization_price_overflow() {
+		let expected = BuiltinError::PriceOverflow { field: "gas_per_round", value: Uint(U256::from(u64::max_value()) + 1) };
+		assert_eq!(expected.to_string(), "gas_per_round of 18446744073709551616 exceeds the maximum of 18446744073709551615");
+
+		let number = r#"{ "name": "blake2_f", "pricing": { "blake2_f": { "gas_per_round": 18446744073709551616 } } }"#;
+		let err = serde_json::from_str::<Builtin>(number).unwrap_err();
+		assert!(err.to_string().starts_with(&expected.to_string()), "unexpected error: {}", err);
+
+		let hex = r#"{ "name": "blake2_f", "pricing": { "blake2_f": { "gas_per_round": "0x10000000000000000" } } }"#;
+		let err = serde_json::from_str::<Builtin>(hex).unwrap_err();
+		assert!(err.to_string().starts_with(&expected.to_string()), "unexpected error: {}", err);
+
+		let max = r#"{ "blake2_f": { "gas_per_round": 18446744073709551615 } }"#;
+		let pricing: PricingInner = serde_json::from_str(max).unwrap();
+		assert_eq!(pricing, PricingInner::Blake2F { gas_per_round: u64::max_value(), max_rounds: None });
+
+		let discount = r#"{ "bls12_g1_multiexp": { "base": 12000, "discount": [1000, "0x10000000000000000"], "max_discount": 1000 } }"#;
+		let err = serde_json::from_str::<PricingInner>(discount).unwrap_err();
+		assert!(err.to_string().starts_with("discount of 18446744073709551616"), "unexpected error: {}", err);
+	}
+
+	#[test]
+	fn schedule() {
+		let single: Builtin = serde_json::from_str(r#"{
+			"name": "ecrecover",
+			"activate_at": 100,
+			"pricing": { "linear": { "base": 3000, "word": 0 } }
+		}"#).unwrap();
+		assert_eq!(single.schedule(), vec![(Some(U64(100)), &PricingInner::Linear(Linear { base: 3000, word: 0 }))]);
+
+		let unsorted: Builtin = serde_json::from_str(r#"{
+			"name": "modexp",
+			"pricing": [
+				{ "activate_at": 30, "price": { "modexp7883": { "min_price": 500 } } },
+				{ "activate_at": 10, "price": { "modexp": { "divisor": 20 } } },
+				{ "activate_at": 30, "price": { "modexp2565": { "divisor": 3 } } },
+				{ "price": { "modexp": { "divisor": 10 } } }
+			]
+		}"#).unwrap();
+		assert_eq!(unsorted.schedule(), vec![
+			(None, &PricingInner::Modexp(Modexp { divisor: 10 })),
+			(Some(U64(10)), &PricingInner::Modexp(Modexp { divisor: 20 })),
+			(Some(U64(30)), &PricingInner::Modexp7883(Modexp7883 { min_price: 500 })),
+			(Some(U64(30)), &PricingInner::Modexp2565(Modexp2565 { divisor: 3, min_price: None })),
+		]);
+	}
+
+	#[test]
+	fn deserialization_empty_multi_pricing() {
+		let s = r#"{"name":"x","pricing":[]}"#;
+		let err = serde_json::from_str::<Builtin>(s).unwrap_err();
+		assert!(err.to_string().contains("multi-pricing schedule must not be empty"));
+	}
+
+	#[test]
+	fn validate_multi_pricing_order() {
+		let in_order = r#"{
+			"name": "modexp",
+			"pricing": [
+				{ "price": { "modexp": { "divisor": 20 } } },
+				{ "activate_at_timestamp": 100, "price": { "modexp2565": { "divisor": 3 } } },
+				{ "activate_at": 10, "price": { "modexp": { "divisor": 10 } } },
+				{ "activate_at": 20, "price": { "modexp2565": { "divisor": 3 } } }
+			]
+		}"#;
+		let builtin: Builtin = serde_json::from_str(in_order).unwrap();
+		assert_eq!(builtin.validate(), Ok(()));
+
+		let swapped = r#"{
+			"name": "modexp",
+			"pricing": [
+				{ "activate_at": 0, "price": { "modexp": { "divisor": 20 } } },
+				{ "activate_at": 20, "price": { "modexp2565": { "divisor": 3 } } },
+				{ "activate_at": 10, "price": { "modexp": { "divisor": 10 } } }
+			]
+		}"#;
+		let builtin: Builtin = serde_json::from_str(swapped).unwrap();
+		let err = builtin.validate().unwrap_err();
+		assert_eq!(err, BuiltinError::UnsortedPricing { index: 2, previous: U64(20), current: U64(10) });
+		assert_eq!(err.to_string(), "pricing 2 activated at block 10 is not after the preceding pricing activated at block 20");
+	}
+
+	#[test]
+	fn validate_duplicate_activation() {
+		let s = r#"{
+			"name": "modexp",
+			"pricing": [
+				{ "activate_at": 0, "price": { "modexp": { "divisor": 20 } } },
+				{ "activate_at": 500, "price": { "modexp": { "divisor": 10 } } },
+				{ "activate_at": "0x1f4", "price": { "modexp2565": { "divisor": 3 } } }
+			]
+		}"#;
+		let builtin: Builtin = serde_json::from_str(s).unwrap();
+		let err = builtin.validate().unwrap_err();
+		assert_eq!(err, BuiltinError::DuplicateActivation { block: U64(500) });
+		assert_eq!(err.to_string(), "more than one pricing is activated at block 500");
+	}
+
+	#[test]
+	fn deserialization_const() {
+		let s = r#"{
+			"name": "p256_verify",
+			"pricing": { "const": { "price": 3000 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::Const { price: 3000 }));
+
+		let s = r#"[
+			{ "activate_at": 0, "price": { "linear": { "base": 3000, "word": 0 } } },
+			{ "activate_at": 10, "price": { "const": { "price": 3450 } } }
+		]"#;
+		let deserialized: Pricing = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, Pricing::Multi(vec![
+			PricingAt {
+				activate_at: Some(U64(0)),
+				activate_at_timestamp: None,
+				price: PricingInner::Linear(Linear { base: 3000, word: 0 }),
+			},
+			PricingAt {
+				activate_at: Some(U64(10)),
+				activate_at_timestamp: None,
+				price: PricingInner::Const { price: 3450 },
+			},
+		]));
+		assert_eq!(serde_json::to_string(&PricingInner::Const { price: 3000 }).unwrap(), r#"{"const":{"price":3000}}"#);
+
+		let s = r#"{ "const": { "prcie": 3000 } }"#;
+		assert!(serde_json::from_str::<PricingInner>(s).is_err());
+		let s = r#"{ "const": { "price": 3000, "word": 0 } }"#;
+		assert!(serde_json::from_str::<PricingInner>(s).is_err());
+	}
+
+	#[test]
+	fn deserialization_modexp7883() {
+		let s = r#"{
+			"name": "modexp",
+			"pricing": [
+				{ "activate_at": 0, "price": { "modexp": { "divisor": 20 } } },
+				{ "activate_at": "0xbad420", "price": { "modexp2565": { "divisor": 3, "min_price": 200 } } },
+				{ "activate_at": "0xc0ffee", "price": { "modexp7883": { "min_price": 500 } } }
+			]
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.pricing, Pricing::Multi(vec![
+			PricingAt {
+				activate_at: Some(U64(0)),
+				activate_at_timestamp: None,
+				price: PricingInner::Modexp(Modexp { divisor: 20 }),
+			},
+			PricingAt {
+				activate_at: Some(U64(0xbad420)),
+				activate_at_timestamp: None,
+				price: PricingInner::Modexp2565(Modexp2565 { divisor: 3, min_price: Some(200) }),
+			},
+			PricingAt {
+				activate_at: Some(U64(0xc0ffee)),
+				activate_at_timestamp: None,
+				price: PricingInner::Modexp7883(Modexp7883 { min_price: 500 }),
+			},
+		]));
+
+		match deserialized.pricing {
+			Pricing::Multi(ref pricings) => assert!(pricings.windows(2).all(|w| w[0].activate_at < w[1].activate_at)),
+			_ => unreachable!(),
+		}
+		assert_round_trip(deserialized);
+	}
+
+	#[test]
+	fn deserialization_modexp2565_without_min_price() {
+		let s = r#"{ "modexp2565": { "divisor": 3 } }"#;
+		let deserialized: PricingInner = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, PricingInner::Modexp2565(Modexp2565 { divisor: 3, min_price: None }));
+		assert_eq!(serde_json::to_string(&deserialized).unwrap(), r#"{"modexp2565":{"divisor":3}}"#);
+	}
+
+	#[test]
+	fn deserialization_bls12_g2_add_builtin() {
+		let s = r#"{
+			"name": "bls12_g2_add",
+			"pricing": { "bls12_g2_add": { "price": 4500 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.name, "bls12_g2_add");
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::Bls12G2Add { price: 4500 }));
+
+		let s = r#"[
+			{ "activate_at": 0, "price": { "bls12_g2_add": { "price": 4500 } } },
+			{ "activate_at": 10, "price": { "bls12_g2_add": { "price": 600 } } }
+		]"#;
+		let deserialized: Pricing = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, Pricing::Multi(vec![
+			PricingAt {
+				activate_at: Some(U64(0)),
+				activate_at_timestamp: None,
+				price: PricingInner::Bls12G2Add { price: 4500 },
+			},
+			PricingAt {
+				activate_at: Some(U64(10)),
+				activate_at_timestamp: None,
+				price: PricingInner::Bls12G2Add { price: 600 },
+			},
+		]));
+
+		let s = r#"{ "bls12_g2_add": { "price": 4500, "pair": 0 } }"#;
+		assert!(serde_json::from_str::<PricingInner>(s).is_err());
+	}
+
+	#[test]
+	fn deserialization_bls12_g2_mul_builtin() {
+		let s = r#"{
+			"name": "bls12_381_g2_mul",
+			"activate_at": "0xffffff",
+			"pricing": { "bls12_g2_mul": { "price": 55000 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.name, "bls12_381_g2_mul");
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::Bls12G2Mul { price: 55000 }));
+		assert_eq!(deserialized.activate_at, Some(U64(0xffffff)));
+	}
+
+	#[test]
+	fn deserialization_bls12_g2_multiexp_builtin() {
+		let discount: Vec<u64> = (0..150).map(|i| 1000 - 5 * i).collect();
+		let s = format!(r#"{{
+			"name": "bls12_381_g2_multiexp",
+			"pricing": {{ "bls12_g2_multiexp": {{ "base": 55000, "discount": {:?}, "max_discount": 1000 }} }}
+		}}"#, discount);
+		let deserialized: Builtin = serde_json::from_str(&s).unwrap();
+		match deserialized.pricing {
+			Pricing::Single(PricingInner::Bls12G2MultiExp(pricing)) => {
+				assert_eq!(pricing.base, 55000);
+				assert_eq!(pricing.discount.len(), 150);
+				assert_eq!(pricing.discount, discount);
+			}
+			other => panic!("unexpected pricing: {:?}", other),
+		}
+
+		let empty = r#"{ "bls12_g2_multiexp": { "base": 55000, "discount": [], "max_discount": 1000 } }"#;
+		assert!(serde_json::from_str::<PricingInner>(empty).is_err());
+	}
+
+	#[test]
+	fn deserialization_bls12_pairing_builtin() {
+		let s = r#"{
+			"name": "bls12_381_pairing",
+			"pricing": { "bls12_pairing": { "base": 115000, "pair": 23000 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.name, "bls12_381_pairing");
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::Bls12Pairing(Bls12Pairing {
+			base: 115000,
+			pair: 23000,
+		})));
+
+		let s = r#"{ "bls12_pairing": { "base": 115000, "pair": 23000, "eip1108_transition_base": 0 } }"#;
+		assert!(serde_json::from_str::<PricingInner>(s).is_err());
+	}
+
+	#[test]
+	fn deserialization_max_input_length() {
+		let s = r#"{
+			"name": "alt_bn128_pairing",
+			"max_input_length": 1920,
+			"pricing": { "linear": { "base": 3000, "word": 0 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.max_input_length, Some(1920));
+	}
+
+	#[test]
+	fn deserialization_bls12_map_to_curve_builtins() {
+		let s = r#"{
+			"name": "bls12_381_fp_to_g1",
+			"pricing": { "bls12_map_fp_to_g1": { "price": 5500 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.name, "bls12_381_fp_to_g1");
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::Bls12MapFpToG1 { price: 5500 }));
+
+		let s = r#"{
+			"name": "bls12_381_fp2_to_g2",
+			"pricing": { "bls12_map_fp2_to_g2": { "price": 75000 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.name, "bls12_381_fp2_to_g2");
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::Bls12MapFp2ToG2 { price: 75000 }));
+	}
+
+	#[test]
+	fn deserialization_info() {
+		let without_info: Builtin = serde_json::from_str(r#"{
+			"name": "ecrecover",
+			"pricing": { "linear": { "base": 3000, "word": 0 } }
+		}"#).unwrap();
+		assert_eq!(without_info.info, None);
+
+		let with_info: Builtin = serde_json::from_str(r#"{
+			"name": "x",
+			"info": "foo",
+			"pricing": { "linear": { "base": 3000, "word": 0 } }
+		}"#).unwrap();
+		assert_eq!(with_info.info, Some("foo".to_owned()));
+		assert_eq!(with_info.pricing, Pricing::Single(PricingInner::Linear(Linear { base: 3000, word: 0 })));
+
+		let serialized = serde_json::to_string(&with_info).unwrap();
+		assert_eq!(serialized, r#"{"name":"x","pricing":{"linear":{"base":3000,"word":0}},"info":"foo"}"#);
+	}
+
+	#[test]
+	fn deserialization_fallback_to_code() {
+		let s = r#"{
+			"name": "alt_bn128_pairing",
+			"activate_at": 100,
+			"fallback_to_code": true,
+			"pricing": { "linear": { "base": 3000, "word": 0 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert!(deserialized.fallback_to_code);
+
+		let s = r#"{
+			"name": "alt_bn128_pairing",
+			"pricing": { "linear": { "base": 3000, "word": 0 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert!(!deserialized.fallback_to_code);
+	}
+
+	#[test]
+	fn deserialization_metered() {
+		let s = r#"{
+			"name": "alt_bn128_pairing",
+			"metered": true,
+			"pricing": { "linear": { "base": 3000, "word": 0 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert!(deserialized.metered);
+		assert_eq!(deserialized, Builtin::new("alt_bn128_pairing", PricingInner::Linear(Linear { base: 3000, word: 0 })).metered());
+
+		let serialized = serde_json::to_string(&Builtin::new("x", PricingInner::Linear(Linear { base: 3000, word: 0 }))).unwrap();
+		assert!(!serialized.contains("metered"));
+	}
+
+	#[test]
+	fn deactivate_at() {
+		let s = r#"{
+			"name": "identity",
+			"activate_at": 100,
+			"deactivate_at": "0x1000",
+			"pricing": { "linear": { "base": 15, "word": 3 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.activate_at, Some(U64(100)));
+		assert_eq!(deserialized.deactivate_at, Some(U64(0x1000)));
+	}
+
+	#[test]
+	fn deserialization_point_evaluation_builtin() {
+		let s = r#"{
+			"name": "point_evaluation",
+			"pricing": { "point_evaluation": { "price": 50000 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		let expected = PointEvaluation { price: 50000, length: None };
+		assert_eq!(expected.length(), 192);
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::PointEvaluation(expected)));
+
+		let s = r#"{ "point_evaluation": { "price": 50000, "length": 256 } }"#;
+		let deserialized: PricingInner = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, PricingInner::PointEvaluation(PointEvaluation { price: 50000, length: Some(256) }));
+	}
+
+	#[test]
+	fn deserialization_secp256r1_verify_builtin() {
+		let s = r#"{
+			"name": "p256_verify",
+			"pricing": { "secp256r1_verify": { "price": 3450 } }
+		}"#;
+		let deserialized: Builtin = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.pricing, Pricing::Single(PricingInner::Secp256r1Verify { price: 3450 }));
+
+		let s = r#"[{ "activate_at": 10, "price": { "secp256r1_verify": { "price": 3450 } } }]"#;
+		let deserialized: Pricing = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, Pricing::Multi(vec![PricingAt {
+			activate_at: Some(U64(10)),
+			activate_at_timestamp: None,
+			price: PricingInner::Secp256r1Verify { price: 3450 },
+		}]));
+
+		let s = r#"{ "secp256r1_verify": { "price": 3450, "word": 0 } }"#;
+		assert!(serde_json::from_str::<PricingInner>(s).is_err());
+	}
+
+	#[test]
+	fn normalized_eip1108_pairing() {
+		let legacy: Builtin = serde_json::from_str(r#"{
+			"name": "alt_bn128_pairing",
+			"activate_at": "0x10",
+			"eip1108_transition": "0x20",
+			"pricing": {
+				"alt_bn128_pairing": {
+					"base": 100000,
+					"pair": 80000,
+					"eip1108_transition_base": 45000,
+					"eip1108_transition_pair": 34000
+				}
+			}
+		}"#).unwrap();
+		let multi: Builtin = serde_json::from_str(r#"{
+			"name": "alt_bn128_pairing",
+			"pricing": [
+				{
+					"activate_at": "0x10",
+					"price": { "alt_bn128_pairing": { "base": 100000, "pair": 80000, "eip1108_transition_base": 0, "eip1108_transition_pair": 0 } }
+				},
+				{
+					"activate_at": "0x20",
+					"price": { "alt_bn128_pairing": { "base": 45000, "pair": 34000, "eip1108_transition_base": 0, "eip1108_transition_pair": 0 } }
+				}
+			]
+		}"#).unwrap();
+
+		let normalized = legacy.clone().normalized();
+		assert_eq!(normalized, multi);
+		assert_eq!(normalized.validate(), Ok(()));
+		for block in &[0x10, 0x1f, 0x20, 0x1000] {
+			assert_eq!(normalized.pricing_at(*block), multi.pricing_at(*block));
+		}
+		assert_eq!(normalized.clone().normalized(), normalized);
+	}
+
+	#[test]
+	fn normalized_eip1108_activated_after_transition() {
+		let pricing = PricingInner::AltBn128ConstOperations(AltBn128ConstOperations {
+			price: 500,
+			eip1108_transition_price: 150,
+		});
+		let legacy = Builtin {
+			name: "alt_bn128_add".to_owned(),
+			pricing: Pricing::Single(pricing),
+			activate_at: Some(U64(0x20)),
+			activate_at_timestamp: None,
+			deactivate_at: None,
+			eip1108_transition: Some(U64(0x10)),
+			max_input_length: None,
+			fallback_to_code: false,
+			metered: false,
+			info: None,
+		};
+
+		assert_eq!(legacy.normalized().pricing, Pricing::Multi(vec![PricingAt {
+			activate_at: Some(U64(0x20)),
+			activate_at_timestamp: None,
+			price: PricingInner::AltBn128ConstOperations(AltBn128ConstOperations {
+				price: 150,
+				eip1108_transition_price: 0,
+			}),
+		}]));
+	}
+
+	#[test]
+	fn normalized_without_legacy_fields_is_unchanged() {
+		let builtin: Builtin = serde_json::from_str(r#"{
+			"name": "ecrecover",
+			"activate_at": "0x10",
+			"pricing": { "linear": { "base": 3000, "word": 0 } }
+		}"#).unwrap();
+		assert_eq!(builtin.clone().normalized(), builtin);
+	}
+
+	#[test]
+	fn builtin_new() {
+		let built = Builtin::new("ecrecover", PricingInner::Linear(Linear { base: 3000, word: 0 })).activate_at(0);
+		let deserialized: Builtin = serde_json::from_str(r#"{
+			"name": "ecrecover",
+			"activate_at": 0,
+			"pricing": { "linear": { "base": 3000, "word": 0 } }
+		}"#).unwrap();
+		assert_eq!(built, deserialized);
+		assert_eq!(Builtin::new("", PricingInner::Linear(Linear { base: 0, word: 0 })), Builtin::default());
+	}
+
+	#[test]
+	fn builtin_new_alt_bn128_pairing() {
+		let built = Builtin::new("alt_bn128_pairing", vec![
+			PricingAt {
+				activate_at: Some(U64(4_370_000)),
+				activate_at_timestamp: None,
+				price: PricingInner::AltBn128Pairing(AltBn128Pairing {
+					base: 100_000,
+					pair: 80_000,
+					eip1108_transition_base: 0,
+					eip1108_transition_pair: 0,
+				}),
+			},
+			PricingAt {
+				activate_at: Some(U64(9_069_000)),
+				activate_at_timestamp: None,
+				price: PricingInner::AltBn128Pairing(AltBn128Pairing {
+					base: 45_000,
+					pair: 34_000,
+					eip1108_transition_base: 0,
+					eip1108_transition_pair: 0,
+				}),
+			},
+		]).deactivate_at(20_000_000).info("EIP-197 and EIP-1108");
+
+		let deserialized: Builtin = serde_json::from_str(r#"{
+			"name": "alt_bn128_pairing",
+			"deactivate_at": 20000000,
+			"info": "EIP-197 and EIP-1108",
+			"pricing": [
+				{
+					"activate_at": 4370000,
+					"price": { "alt_bn128_pairing": { "base": 100000, "pair": 80000, "eip1108_transition_base": 0, "eip1108_transition_pair": 0 } }
+				},
+				{
+					"activate_at": 9069000,
+					"price": { "alt_bn128_pairing": { "base": 45000, "pair": 34000, "eip1108_transition_base": 0, "eip1108_transition_pair": 0 } }
+				}
+			]
+		}"#).unwrap();
+		assert_eq!(built, deserialized);
+		assert_round_trip(built);
+	}
+
+	#[test]
+	fn activation_must_fit_into_64_bits() {
+		let builtin = |activate_at: &str| serde_json::from_str::<Builtin>(&format!(r#"{{
+			"name": "ecrecover",
+			"pricing": {{ "linear": {{ "base": 3000, "word": 0 }} }},
+			"activate_at": {}
+		}}"#, activate_at));
+
+		assert_eq!(builtin(r#""0xffffffffffffffff""#).unwrap().activate_at, Some(U64(u64::max_value())));
+		assert!(builtin(r#""0x10000000000000000""#).is_err());
+		assert!(builtin("18446744073709551616").is_err());
 	}
 }
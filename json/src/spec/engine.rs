@@ -41,6 +41,7 @@ pub enum Engine {
 
 #[cfg(test)]
 mod tests {
+	use std::num::NonZeroU64;
 	use super::Engine;
 
 	#[test]
@@ -143,7 +144,10 @@ mod tests {
 		}"#;
 		let deserialized: Engine = serde_json::from_str(s).unwrap();
 		match deserialized {
-			Engine::Clique(_) => {}, // Clique is unit tested in its own file.
+			Engine::Clique(clique) => {
+				assert_eq!(clique.params.period, Some(15));
+				assert_eq!(clique.params.epoch, NonZeroU64::new(30000));
+			},
 			_ => panic!(),
 		};
 	}
@@ -252,6 +252,74 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn ethash_deserialization_progpow_transition() {
+		let s = r#"{
+			"params": {
+				"difficultyBoundDivisor": "0x0800",
+				"minimumDifficulty": "0x020000",
+				"progpowTransition": "0x2710"
+			}
+		}"#;
+
+		let deserialized: Ethash = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.params.progpow_transition, Some(Uint(U256::from(10000))));
+		assert_eq!(deserialized.params.homestead_transition, None);
+	}
+
+	#[test]
+	fn ethash_deserialization_single_block_reward() {
+		let s = r#"{
+			"params": {
+				"difficultyBoundDivisor": "0x0800",
+				"minimumDifficulty": "0x020000",
+				"blockReward": "0x4563918244f40000",
+				"difficultyBombDelays": {
+					"0x42ae50": "0x2dc6c0"
+				}
+			}
+		}"#;
+
+		let deserialized: Ethash = serde_json::from_str(s).unwrap();
+		assert_eq!(
+			deserialized.params.block_reward,
+			Some(BlockReward::Single(Uint(U256::from(5_000_000_000_000_000_000u64))))
+		);
+		assert_eq!(deserialized.params.difficulty_bomb_delays, Some(vec![
+			(Uint(U256::from(4370000)), Uint(U256::from(3000000))),
+		].into_iter().collect()));
+	}
+
+	#[test]
+	fn ethash_deserialization_block_reward_schedule() {
+		let s = r#"{
+			"params": {
+				"difficultyBoundDivisor": "0x0800",
+				"minimumDifficulty": "0x020000",
+				"blockReward": {
+					"0x0": "0x4563918244f40000",
+					"0x42ae50": "0x29a2241af62c0000",
+					"0x6f1580": "0x1bc16d674ec80000"
+				},
+				"difficultyBombDelays": {
+					"0x42ae50": "0x2dc6c0",
+					"0x6f1580": "0x1e8480"
+				}
+			}
+		}"#;
+
+		let deserialized: Ethash = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.params.block_reward, Some(BlockReward::Multi(vec![
+			(Uint(U256::from(0)), Uint(U256::from(5_000_000_000_000_000_000u64))),
+			(Uint(U256::from(4370000)), Uint(U256::from(3_000_000_000_000_000_000u64))),
+			(Uint(U256::from(7280000)), Uint(U256::from(2_000_000_000_000_000_000u64))),
+		].into_iter().collect())));
+		assert_eq!(deserialized.params.difficulty_bomb_delays, Some(vec![
+			(Uint(U256::from(4370000)), Uint(U256::from(3000000))),
+			(Uint(U256::from(7280000)), Uint(U256::from(2000000))),
+		].into_iter().collect()));
+	}
+
 	#[test]
 	#[should_panic(expected = "a non-zero value")]
 	fn test_zero_value_divisor() {
@@ -34,7 +34,7 @@ pub mod hardcoded_sync;
 pub mod clique;
 
 pub use self::account::Account;
-pub use self::builtin::{Builtin, Pricing, Linear};
+pub use self::builtin::{Builtin, BuiltinError, Pricing, PricingAt, PricingInner, Linear};
 pub use self::genesis::Genesis;
 pub use self::params::Params;
 pub use self::spec::{Spec, ForkSpec};
@@ -42,7 +42,7 @@ pub use self::seal::{Seal, Ethereum, AuthorityRoundSeal, TendermintSeal};
 pub use self::engine::Engine;
 pub use self::state::{State, HashOrMap};
 pub use self::ethash::{Ethash, EthashParams, BlockReward};
-pub use self::validator_set::ValidatorSet;
+pub use self::validator_set::{ValidatorSet, ValidatorSetError};
 pub use self::basic_authority::{BasicAuthority, BasicAuthorityParams};
 pub use self::authority_round::{AuthorityRound, AuthorityRoundParams};
 pub use self::clique::{Clique, CliqueParams};
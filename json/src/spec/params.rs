@@ -107,6 +107,30 @@ pub struct Params {
 	/// See `CommonParams` docs.
 	pub eip2028_transition: Option<Uint>,
 	/// See `CommonParams` docs.
+	pub eip2929_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	pub eip2930_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	pub eip1559_transition: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(default, deserialize_with="uint::validate_optional_non_zero")]
+	pub eip1559_base_fee_max_change_denominator: Option<Uint>,
+	/// See `CommonParams` docs.
+	#[serde(default, deserialize_with="uint::validate_optional_non_zero")]
+	pub eip1559_elasticity_multiplier: Option<Uint>,
+	/// See `CommonParams` docs.
+	pub eip1559_base_fee_initial_value: Option<Uint>,
+	/// EIP-3529 (reduction in refunds) activation block height.
+	pub eip3529_transition: Option<Uint>,
+	/// EIP-3855 (PUSH0 instruction) activation block height.
+	pub eip3855_transition: Option<Uint>,
+	/// EIP-3860 (limit and meter initcode) activation block height.
+	pub eip3860_transition: Option<Uint>,
+	/// EIP-1153 (transient storage opcodes) activation block height.
+	pub eip1153_transition: Option<Uint>,
+	/// EIP-4844 (shard blob transactions) activation block height.
+	pub eip4844_transition: Option<Uint>,
+	/// See `CommonParams` docs.
 	pub dust_protection_transition: Option<Uint>,
 	/// See `CommonParams` docs.
 	pub nonce_cap_increment: Option<Uint>,
@@ -127,6 +151,8 @@ pub struct Params {
 	pub max_transaction_size: Option<Uint>,
 	/// See main EthashParams docs.
 	pub max_code_size_transition: Option<Uint>,
+	/// EIP-3860 maximum size of contract init code, 49152 bytes if not set.
+	pub max_init_code_size: Option<Uint>,
 	/// Transaction permission contract address.
 	pub transaction_permission_contract: Option<Address>,
 	/// Block at which the transaction permission contract should start being used.
@@ -170,6 +196,108 @@ mod tests {
 		assert_eq!(deserialized.gas_limit_bound_divisor, Uint(U256::from(0x20)));
 		assert_eq!(deserialized.max_code_size, Some(Uint(U256::from(0x1000))));
 		assert_eq!(deserialized.wasm_activation_transition, Some(Uint(U256::from(0x1010))));
+		assert_eq!(deserialized.eip2929_transition, None);
+		assert_eq!(deserialized.eip2930_transition, None);
+		assert_eq!(deserialized.eip1559_transition, None);
+		assert_eq!(deserialized.eip1559_base_fee_max_change_denominator, None);
+		assert_eq!(deserialized.eip1559_elasticity_multiplier, None);
+		assert_eq!(deserialized.eip1559_base_fee_initial_value, None);
+		assert_eq!(deserialized.eip3529_transition, None);
+		assert_eq!(deserialized.eip3855_transition, None);
+		assert_eq!(deserialized.eip3860_transition, None);
+		assert_eq!(deserialized.eip1153_transition, None);
+		assert_eq!(deserialized.eip4844_transition, None);
+		assert_eq!(deserialized.max_init_code_size, None);
+	}
+
+	#[test]
+	fn params_eip1559_deserialization() {
+		let s = r#"{
+			"maximumExtraDataSize": "0x20",
+			"networkID": "0x1",
+			"minGasLimit": "0x1388",
+			"gasLimitBoundDivisor": "0x400",
+			"eip1559Transition": "0xc5d488",
+			"eip1559BaseFeeMaxChangeDenominator": "0x8",
+			"eip1559ElasticityMultiplier": "0x2",
+			"eip1559BaseFeeInitialValue": "0x3b9aca00"
+		}"#;
+
+		let deserialized: Params = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.eip1559_transition, Some(Uint(U256::from(0xc5d488))));
+		assert_eq!(deserialized.eip1559_base_fee_max_change_denominator, Some(Uint(U256::from(8))));
+		assert_eq!(deserialized.eip1559_elasticity_multiplier, Some(Uint(U256::from(2))));
+		assert_eq!(deserialized.eip1559_base_fee_initial_value, Some(Uint(U256::from(1_000_000_000))));
+	}
+
+	#[test]
+	fn params_berlin_deserialization() {
+		let s = r#"{
+			"maximumExtraDataSize": "0x20",
+			"networkID": "0x1",
+			"minGasLimit": "0x1388",
+			"gasLimitBoundDivisor": "0x400",
+			"eip2929Transition": "0xbad420",
+			"eip2930Transition": "0xbad420"
+		}"#;
+
+		let deserialized: Params = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.eip2929_transition, Some(Uint(U256::from(0xbad420))));
+		assert_eq!(deserialized.eip2930_transition, Some(Uint(U256::from(0xbad420))));
+		assert_eq!(deserialized.eip1559_transition, None);
+	}
+
+	#[test]
+	fn params_eip3529_deserialization() {
+		let s = r#"{
+			"maximumExtraDataSize": "0x20",
+			"networkID": "0x1",
+			"minGasLimit": "0x1388",
+			"gasLimitBoundDivisor": "0x400",
+			"eip1559Transition": "0xc5d488",
+			"eip3529Transition": "0xc5d488"
+		}"#;
+
+		let deserialized: Params = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.eip1559_transition, Some(Uint(U256::from(0xc5d488))));
+		assert_eq!(deserialized.eip3529_transition, Some(Uint(U256::from(0xc5d488))));
+	}
+
+	#[test]
+	fn params_shanghai_cancun_deserialization() {
+		let s = r#"{
+			"maximumExtraDataSize": "0x20",
+			"networkID": "0x1",
+			"minGasLimit": "0x1388",
+			"gasLimitBoundDivisor": "0x400",
+			"eip3855Transition": "0x1000",
+			"eip3860Transition": "0x1000",
+			"eip1153Transition": "0x1000",
+			"eip4844Transition": "0x1000"
+		}"#;
+
+		let deserialized: Params = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.eip3855_transition, Some(Uint(U256::from(0x1000))));
+		assert_eq!(deserialized.eip3860_transition, Some(Uint(U256::from(0x1000))));
+		assert_eq!(deserialized.eip1153_transition, Some(Uint(U256::from(0x1000))));
+		assert_eq!(deserialized.eip4844_transition, Some(Uint(U256::from(0x1000))));
+		assert_eq!(deserialized.eip3529_transition, None);
+	}
+
+	#[test]
+	fn params_code_size_limits_deserialization() {
+		let s = r#"{
+			"maximumExtraDataSize": "0x20",
+			"networkID": "0x1",
+			"minGasLimit": "0x1388",
+			"gasLimitBoundDivisor": "0x400",
+			"maxCodeSize": "0x6000",
+			"maxInitCodeSize": "0xc000"
+		}"#;
+
+		let deserialized: Params = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.max_code_size, Some(Uint(U256::from(0x6000))));
+		assert_eq!(deserialized.max_init_code_size, Some(Uint(U256::from(0xc000))));
 	}
 
 	#[test]
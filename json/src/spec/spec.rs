@@ -17,6 +17,8 @@
 //! Spec deserialization.
 
 use std::io::Read;
+use std::path::Path;
+use crate::bytes::with_file_references;
 use crate::spec::{Params, Genesis, Engine, State, HardcodedSync};
 use serde::Deserialize;
 use serde_json::Error;
@@ -40,6 +42,10 @@ pub enum ForkSpec {
 	ConstantinopleFix,
 	/// Istanbul (To be announced)
 	Istanbul,
+	/// Berlin (To be announced)
+	Berlin,
+	/// London (To be announced)
+	London,
 	/// Byzantium transition test-net
 	EIP158ToByzantiumAt5,
 	/// Homestead transition test-net
@@ -78,6 +84,12 @@ impl Spec {
 	pub fn load<R>(reader: R) -> Result<Self, Error> where R: Read {
 		serde_json::from_reader(reader)
 	}
+
+	/// Loads spec from json, reading bytes given as `{"file": "path"}` from files relative to
+	/// `base_dir`.
+	pub fn load_with_file_references<R>(reader: R, base_dir: &Path) -> Result<Self, Error> where R: Read {
+		with_file_references(base_dir, || serde_json::from_reader(reader))
+	}
 }
 
 #[cfg(test)]
@@ -22,13 +22,13 @@ use crate::{
 	hash::{Address, H256},
 	spec::{Account, Builtin}
 };
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+use serde::de::Error;
 
 /// Recent JSON tests can be either a map or a hash (represented by a string).
 /// See https://github.com/ethereum/tests/issues/637
 #[cfg_attr(any(test, feature = "test-helpers"), derive(Clone))]
-#[derive(Debug, PartialEq, Deserialize)]
-#[serde(untagged)]
+#[derive(Debug, PartialEq)]
 pub enum HashOrMap {
 	/// When the `postState` is large, tests sometimes just include the state root of the last
 	/// successful block here.
@@ -37,6 +37,19 @@ pub enum HashOrMap {
 	Map(BTreeMap<Address, Account>),
 }
 
+impl<'de> Deserialize<'de> for HashOrMap {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where D: Deserializer<'de> {
+		// dispatch on the shape by hand, an untagged enum would hide the errors of the accounts
+		let value = serde_json::Value::deserialize(deserializer)?;
+		if value.is_string() {
+			serde_json::from_value(value).map(HashOrMap::Hash).map_err(Error::custom)
+		} else {
+			serde_json::from_value(value).map(HashOrMap::Map).map_err(Error::custom)
+		}
+	}
+}
+
 /// Blockchain state deserializer.
 #[cfg_attr(any(test, feature = "test-helpers"), derive(Clone))]
 #[derive(Debug, PartialEq, Deserialize)]
@@ -17,8 +17,10 @@
 //! Validator set deserialization.
 
 use std::collections::BTreeMap;
+use std::fmt;
 use crate::{hash::Address, uint::Uint};
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
+use serde::de::{Error, MapAccess, Visitor};
 
 /// Different ways of specifying validators.
 #[derive(Debug, PartialEq, Deserialize)]
@@ -32,13 +34,86 @@ pub enum ValidatorSet {
 	/// Address of a contract that indicates the list of authorities and enables reporting of theor misbehaviour using transactions.
 	Contract(Address),
 	/// A map of starting blocks for each validator set.
+	#[serde(deserialize_with = "deserialize_multi")]
 	Multi(BTreeMap<Uint, ValidatorSet>),
 }
 
+impl ValidatorSet {
+	/// Checks that every multi-set, including nested ones, has a set starting at block 0, so there
+	/// always is an initial set. Duplicate starting blocks are already rejected when deserializing.
+	pub fn validate(&self) -> Result<(), ValidatorSetError> {
+		if let ValidatorSet::Multi(ref sets) = *self {
+			if !sets.contains_key(&Uint(0.into())) {
+				return Err(ValidatorSetError::MissingInitialSet {
+					first_block: sets.keys().next().cloned(),
+				});
+			}
+			for set in sets.values() {
+				set.validate()?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Invalid validator set specification.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ValidatorSetError {
+	/// A multi-set has no set starting at block 0.
+	MissingInitialSet {
+		/// Starting block of the earliest set, if there are any.
+		first_block: Option<Uint>,
+	},
+}
+
+impl fmt::Display for ValidatorSetError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			ValidatorSetError::MissingInitialSet { first_block: Some(ref block) } => write!(
+				f, "multi validator set has no set starting at block 0, the earliest one starts at block {}", block.0,
+			),
+			ValidatorSetError::MissingInitialSet { first_block: None } => write!(
+				f, "multi validator set is empty, a set starting at block 0 is required",
+			),
+		}
+	}
+}
+
+impl std::error::Error for ValidatorSetError {}
+
+/// Deserializes the sets of a multi-set, rejecting duplicate starting blocks instead of
+/// silently keeping the last one.
+fn deserialize_multi<'de, D>(deserializer: D) -> Result<BTreeMap<Uint, ValidatorSet>, D::Error>
+	where D: Deserializer<'de>
+{
+	struct MultiVisitor;
+
+	impl<'de> Visitor<'de> for MultiVisitor {
+		type Value = BTreeMap<Uint, ValidatorSet>;
+
+		fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+			write!(formatter, "a map of starting blocks to validator sets")
+		}
+
+		fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error> where A: MapAccess<'de> {
+			let mut sets = BTreeMap::new();
+			while let Some((block, set)) = map.next_entry::<Uint, ValidatorSet>()? {
+				if sets.contains_key(&block) {
+					return Err(A::Error::custom(format!("duplicate validator set starting at block {}", block.0)));
+				}
+				sets.insert(block, set);
+			}
+			Ok(sets)
+		}
+	}
+
+	deserializer.deserialize_map(MultiVisitor)
+}
+
 #[cfg(test)]
 mod tests {
 	use std::str::FromStr;
-	use super::{Address, Uint, ValidatorSet};
+	use super::{Address, Uint, ValidatorSet, ValidatorSetError};
 	use ethereum_types::{H160, U256};
 
 	#[test]
@@ -73,4 +148,51 @@ mod tests {
 			_ => assert!(false),
 		}
 	}
+
+	#[test]
+	fn multi_validator_set_validation() {
+		let s = r#"{
+			"multi": {
+				"0": { "list": ["0xc6d9d2cd449a754c494264e1809c50e34d64562b"] },
+				"10": { "multi": {
+					"0": { "safeContract": "0xc6d9d2cd449a754c494264e1809c50e34d64562b" },
+					"15": { "contract": "0xc6d9d2cd449a754c494264e1809c50e34d64562b" }
+				} }
+			}
+		}"#;
+
+		let deserialized: ValidatorSet = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.validate(), Ok(()));
+	}
+
+	#[test]
+	fn multi_validator_set_without_initial_set_is_invalid() {
+		let s = r#"{
+			"multi": {
+				"10": { "list": ["0xc6d9d2cd449a754c494264e1809c50e34d64562b"] },
+				"20": { "contract": "0xc6d9d2cd449a754c494264e1809c50e34d64562b" }
+			}
+		}"#;
+
+		let deserialized: ValidatorSet = serde_json::from_str(s).unwrap();
+		let err = deserialized.validate().unwrap_err();
+		assert_eq!(err, ValidatorSetError::MissingInitialSet { first_block: Some(Uint(U256::from(10))) });
+		assert_eq!(
+			err.to_string(),
+			"multi validator set has no set starting at block 0, the earliest one starts at block 10",
+		);
+	}
+
+	#[test]
+	fn multi_validator_set_with_duplicate_blocks_is_rejected() {
+		let s = r#"{
+			"multi": {
+				"0": { "list": ["0xc6d9d2cd449a754c494264e1809c50e34d64562b"] },
+				"0x0": { "contract": "0xc6d9d2cd449a754c494264e1809c50e34d64562b" }
+			}
+		}"#;
+
+		let err = serde_json::from_str::<ValidatorSet>(s).unwrap_err();
+		assert!(err.to_string().contains("duplicate validator set starting at block 0"));
+	}
 }
@@ -27,7 +27,7 @@ use crate::{
 	maybe::MaybeEmpty,
 	uint::Uint,
 	spec::{ForkSpec, State as AccountState},
-	transaction::Transaction,
+	transaction::{AccessList, Transaction, deserialize_access_list},
 	vm::Env
 };
 
@@ -41,7 +41,7 @@ pub struct State {
 	pub pre_state: AccountState,
 	/// Post state.
 	#[serde(rename = "post")]
-	pub post_states: BTreeMap<ForkSpec, Vec<PostStateResult>>,
+	pub post_states: PostStates,
 	/// Transaction.
 	pub transaction: MultiTransaction,
 }
@@ -54,8 +54,15 @@ pub struct MultiTransaction {
 	pub data: Vec<Bytes>,
 	/// Gas limit set.
 	pub gas_limit: Vec<Uint>,
-	/// Gas price.
-	pub gas_price: Uint,
+	/// Gas price, absent for EIP-1559 transactions.
+	#[serde(default)]
+	pub gas_price: Option<Uint>,
+	/// Maximum fee per gas, present for EIP-1559 transactions.
+	#[serde(default)]
+	pub max_fee_per_gas: Option<Uint>,
+	/// Maximum priority fee per gas, present for EIP-1559 transactions.
+	#[serde(default)]
+	pub max_priority_fee_per_gas: Option<Uint>,
 	/// Nonce.
 	pub nonce: Uint,
 	/// Secret key.
@@ -65,6 +72,9 @@ pub struct MultiTransaction {
 	pub to: MaybeEmpty<Address>,
 	/// Value set.
 	pub value: Vec<Uint>,
+	/// Access list, present for EIP-2930 transactions.
+	#[serde(default, deserialize_with = "deserialize_access_list")]
+	pub access_list: Option<AccessList>,
 }
 
 impl MultiTransaction {
@@ -74,19 +84,36 @@ impl MultiTransaction {
 			data: self.data[indexes.data as usize].clone(),
 			gas_limit: self.gas_limit[indexes.gas as usize],
 			gas_price: self.gas_price,
+			max_fee_per_gas: self.max_fee_per_gas,
+			max_priority_fee_per_gas: self.max_priority_fee_per_gas,
 			nonce: self.nonce,
 			to: self.to.clone(),
 			value: self.value[indexes.value as usize],
 			r: Default::default(),
 			s: Default::default(),
 			v: Default::default(),
+			chain_id: None,
 			secret: self.secret.clone(),
+			access_list: self.access_list.clone(),
+			max_fee_per_blob_gas: None,
+			blob_versioned_hashes: None,
 		}
 	}
 }
 
-/// State test indexes deserialization.
+/// State test post section deserialization.
 #[derive(Debug, PartialEq, Deserialize)]
+pub struct PostStates {
+	/// Name of the exception the transaction is expected to raise, for forks where it is invalid.
+	#[serde(rename = "expectException", default)]
+	pub expect_exception: BTreeMap<ForkSpec, String>,
+	/// Post state results by fork.
+	#[serde(flatten)]
+	pub states: BTreeMap<ForkSpec, Vec<PostStateResult>>,
+}
+
+/// State test indexes deserialization.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
 pub struct PostStateIndexes {
 	/// Index into transaction data set.
 	pub data: u64,
@@ -108,7 +135,10 @@ pub struct PostStateResult {
 #[cfg(test)]
 mod tests {
 	use serde_json;
-	use super::{MultiTransaction, State};
+	use ethereum_types::U256;
+	use crate::{transaction::TxType, uint::Uint};
+	use crate::spec::ForkSpec;
+	use super::{MultiTransaction, PostStateIndexes, PostStates, State};
 
 	#[test]
 	fn multi_transaction_deserialization() {
@@ -121,7 +151,61 @@ mod tests {
 			"to": "1000000000000000000000000000000000000000",
 			"value": [ "0x00", "0x01", "0x02" ]
 		}"#;
-		let _deserialized: MultiTransaction = serde_json::from_str(s).unwrap();
+		let deserialized: MultiTransaction = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.access_list, None);
+
+		let indexes = PostStateIndexes { data: 0, gas: 1, value: 2 };
+		let tx = deserialized.select(&indexes);
+		assert_eq!(tx.tx_type(), TxType::Legacy);
+		assert_eq!(tx.fee_cap(), Uint(U256::one()));
+	}
+
+	#[test]
+	fn multi_transaction_eip1559_deserialization() {
+		let s = r#"{
+			"data": [ "" ],
+			"gasLimit": [ "0x2dc6c0" ],
+			"maxFeePerGas": "0x0a",
+			"maxPriorityFeePerGas": "0x02",
+			"nonce": "0x00",
+			"secretKey": "45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8",
+			"to": "1000000000000000000000000000000000000000",
+			"value": [ "0x00" ]
+		}"#;
+		let deserialized: MultiTransaction = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.gas_price, None);
+
+		let indexes = PostStateIndexes { data: 0, gas: 0, value: 0 };
+		let tx = deserialized.select(&indexes);
+		assert_eq!(tx.tx_type(), TxType::EIP1559);
+		assert_eq!(tx.max_priority_fee_per_gas, Some(Uint(U256::from(2))));
+		assert_eq!(tx.fee_cap(), Uint(U256::from(10)));
+	}
+
+	#[test]
+	fn multi_transaction_with_access_list_deserialization() {
+		let s = r#"{
+			"data": [ "" ],
+			"gasLimit": [ "0x2dc6c0" ],
+			"gasPrice": "0x01",
+			"nonce": "0x00",
+			"secretKey": "45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8",
+			"to": "1000000000000000000000000000000000000000",
+			"value": [ "0x00" ],
+			"accessList": [
+				{
+					"address": "1000000000000000000000000000000000000000",
+					"storageKeys": [ "0000000000000000000000000000000000000000000000000000000000000001" ]
+				}
+			]
+		}"#;
+		let deserialized: MultiTransaction = serde_json::from_str(s).unwrap();
+		let access_list = deserialized.access_list.clone().unwrap();
+		assert_eq!(access_list.len(), 1);
+		assert_eq!(access_list[0].1.len(), 1);
+
+		let indexes = PostStateIndexes { data: 0, gas: 0, value: 0 };
+		assert_eq!(deserialized.select(&indexes).access_list, Some(access_list));
 	}
 
 	#[test]
@@ -200,4 +284,72 @@ mod tests {
 		let _deserialized: State = serde_json::from_str(s).unwrap();
 		// TODO: validate all fields
 	}
+
+	#[test]
+	fn post_states_with_expected_exception_deserialization() {
+		let s = r#"{
+			"Berlin": [
+				{
+					"hash": "3e6dacc1575c6a8c76422255eca03529bbf4c0dda75dfc110b22d6dc4152396f",
+					"indexes": { "data": 0, "gas": 0, "value": 0 }
+				}
+			],
+			"London": [
+				{
+					"hash": "99a450d8ce5b987a71346d8a0a1203711f770745c7ef326912e46761f14cd764",
+					"indexes": { "data": 0, "gas": 0, "value": 0 }
+				}
+			],
+			"expectException": {
+				"London": "TR_TipGtFeeCap"
+			}
+		}"#;
+		let deserialized: PostStates = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.states.len(), 2);
+		assert_eq!(deserialized.states[&ForkSpec::London].len(), 1);
+		assert_eq!(deserialized.expect_exception.get(&ForkSpec::London).map(String::as_str), Some("TR_TipGtFeeCap"));
+		assert_eq!(deserialized.expect_exception.get(&ForkSpec::Berlin), None);
+	}
+
+	#[test]
+	fn post_states_preserve_indexes_in_order() {
+		let s = r#"{
+			"Berlin": [
+				{
+					"hash": "3e6dacc1575c6a8c76422255eca03529bbf4c0dda75dfc110b22d6dc4152396f",
+					"indexes": { "data": 0, "gas": 0, "value": 0 }
+				},
+				{
+					"hash": "99a450d8ce5b987a71346d8a0a1203711f770745c7ef326912e46761f14cd764",
+					"indexes": { "data": 2, "gas": 0, "value": 1 }
+				},
+				{
+					"hash": "99a450d8ce5b987a71346d8a0a1203711f770745c7ef326912e46761f14cd764",
+					"indexes": { "data": 1, "gas": 3, "value": 0 }
+				}
+			]
+		}"#;
+		let deserialized: PostStates = serde_json::from_str(s).unwrap();
+		let indexes: Vec<_> = deserialized.states[&ForkSpec::Berlin].iter().map(|state| state.indexes).collect();
+		assert_eq!(indexes, vec![
+			PostStateIndexes { data: 0, gas: 0, value: 0 },
+			PostStateIndexes { data: 2, gas: 0, value: 1 },
+			PostStateIndexes { data: 1, gas: 3, value: 0 },
+		]);
+	}
+
+	#[test]
+	fn post_states_without_expected_exception_deserialization() {
+		let s = r#"{
+			"Berlin": [
+				{
+					"hash": "3e6dacc1575c6a8c76422255eca03529bbf4c0dda75dfc110b22d6dc4152396f",
+					"indexes": { "data": 0, "gas": 0, "value": 0 }
+				}
+			]
+		}"#;
+		let deserialized: PostStates = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.states.len(), 1);
+		assert!(deserialized.expect_exception.is_empty());
+	}
 }
@@ -16,8 +16,67 @@
 
 //! Transaction deserialization.
 
+use std::fmt;
 use crate::{bytes::Bytes, hash::{Address, H256}, maybe::MaybeEmpty, uint::Uint};
-use serde::Deserialize;
+use ethereum_types::U256;
+use serde::{Deserialize, Deserializer};
+
+/// Accounts and storage keys a transaction declares to access (EIP-2930).
+pub type AccessList = Vec<(Address, Vec<H256>)>;
+
+/// Access list entry deserialization.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+struct AccessListItem {
+	address: Address,
+	storage_keys: Vec<H256>,
+}
+
+/// Deserializes an optional list of `{ "address", "storageKeys" }` entries.
+pub fn deserialize_access_list<'de, D>(deserializer: D) -> Result<Option<AccessList>, D::Error>
+	where D: Deserializer<'de>
+{
+	let items: Option<Vec<AccessListItem>> = Option::deserialize(deserializer)?;
+	Ok(items.map(|items| items.into_iter().map(|item| (item.address, item.storage_keys)).collect()))
+}
+
+/// Transaction type, as implied by the fields a test transaction carries.
+/// Discriminants are the EIP-2718 transaction type bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxType {
+	/// Legacy transaction priced with `gasPrice`.
+	Legacy = 0,
+	/// EIP-2930 transaction with an access list, priced with `gasPrice`.
+	AccessList = 1,
+	/// EIP-1559 transaction priced with `maxFeePerGas` and `maxPriorityFeePerGas`.
+	EIP1559 = 2,
+	/// EIP-4844 transaction carrying blobs, additionally priced with `maxFeePerBlobGas`.
+	Blob = 3,
+}
+
+/// Invalid test transaction.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TransactionError {
+	/// A blob transaction has no versioned hashes.
+	MissingBlobVersionedHashes,
+	/// A blob transaction has no recipient; blob transactions can't create contracts.
+	MissingBlobRecipient,
+	/// The transaction is valid but its type can't be executed.
+	UnsupportedType(TxType),
+}
+
+impl fmt::Display for TransactionError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			TransactionError::MissingBlobVersionedHashes => write!(f, "blob transaction without blob versioned hashes"),
+			TransactionError::MissingBlobRecipient => write!(f, "blob transaction without a recipient"),
+			TransactionError::UnsupportedType(tx_type) => write!(f, "unsupported transaction type {}", tx_type as u8),
+		}
+	}
+}
+
+impl std::error::Error for TransactionError {}
 
 /// Unsigned transaction with signing information deserialization.
 #[derive(Debug, PartialEq, Deserialize)]
@@ -27,8 +86,15 @@ pub struct Transaction {
 	pub data: Bytes,
 	/// Gas limit.
 	pub gas_limit: Uint,
-	/// Gas price.
-	pub gas_price: Uint,
+	/// Gas price, absent for EIP-1559 transactions.
+	#[serde(default)]
+	pub gas_price: Option<Uint>,
+	/// Maximum fee per gas, present for EIP-1559 transactions.
+	#[serde(default)]
+	pub max_fee_per_gas: Option<Uint>,
+	/// Maximum priority fee per gas, present for EIP-1559 transactions.
+	#[serde(default)]
+	pub max_priority_fee_per_gas: Option<Uint>,
 	/// Nonce.
 	pub nonce: Uint,
 	/// To.
@@ -41,18 +107,76 @@ pub struct Transaction {
 	/// S.
 	#[serde(default)]
 	pub s: MaybeEmpty<Uint>,
-	/// V.
+	/// V, either 27/28 or the EIP-155 encoding `chain_id * 2 + 35/36`.
 	#[serde(default)]
 	pub v: MaybeEmpty<Uint>,
+	/// Chain id, present for replay-protected (EIP-155) transactions.
+	#[serde(default)]
+	pub chain_id: Option<Uint>,
 	/// Secret
 	#[serde(rename = "secretKey")]
 	pub secret: Option<H256>,
+	/// Access list, present for EIP-2930 transactions.
+	#[serde(default, deserialize_with = "deserialize_access_list")]
+	pub access_list: Option<AccessList>,
+	/// Maximum fee per blob gas, present for EIP-4844 transactions.
+	#[serde(default)]
+	pub max_fee_per_blob_gas: Option<Uint>,
+	/// Versioned hashes of the blobs, present for EIP-4844 transactions.
+	#[serde(default)]
+	pub blob_versioned_hashes: Option<Vec<H256>>,
+}
+
+impl Transaction {
+	/// Type of the transaction: EIP-4844 if it carries any of the blob fields, EIP-1559 if it
+	/// carries any of the EIP-1559 fee fields, EIP-2930 if it carries an access list, legacy otherwise.
+	pub fn tx_type(&self) -> TxType {
+		if self.max_fee_per_blob_gas.is_some() || self.blob_versioned_hashes.is_some() {
+			TxType::Blob
+		} else if self.max_fee_per_gas.is_some() || self.max_priority_fee_per_gas.is_some() {
+			TxType::EIP1559
+		} else if self.access_list.is_some() {
+			TxType::AccessList
+		} else {
+			TxType::Legacy
+		}
+	}
+
+	/// Chain id the transaction is replay-protected for: `chainId` if given, otherwise the one
+	/// encoded in an EIP-155 `v`. `None` for transactions without replay protection.
+	pub fn eip155_chain_id(&self) -> Option<Uint> {
+		self.chain_id.or_else(|| match self.v {
+			MaybeEmpty::Some(Uint(v)) if v >= U256::from(35) => Some(Uint((v - 35) / 2)),
+			_ => None,
+		})
+	}
+
+	/// The most the sender pays per unit of gas: `maxFeePerGas` for EIP-1559 transactions,
+	/// `gasPrice` otherwise.
+	pub fn fee_cap(&self) -> Uint {
+		self.max_fee_per_gas.or(self.gas_price).unwrap_or_default()
+	}
+
+	/// Checks the fields required by the type of the transaction: blob transactions must carry
+	/// at least one versioned hash and a recipient.
+	pub fn validate(&self) -> Result<(), TransactionError> {
+		if self.tx_type() == TxType::Blob {
+			if self.blob_versioned_hashes.as_ref().map_or(true, Vec::is_empty) {
+				return Err(TransactionError::MissingBlobVersionedHashes);
+			}
+			if self.to == MaybeEmpty::None {
+				return Err(TransactionError::MissingBlobRecipient);
+			}
+		}
+		Ok(())
+	}
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{Bytes, H256, MaybeEmpty, Transaction, Uint};
-	use ethereum_types::{H256 as Eth256, U256};
+	use std::str::FromStr;
+	use super::{Address, Bytes, H256, MaybeEmpty, Transaction, TransactionError, TxType, Uint};
+	use ethereum_types::{H160 as Eth160, H256 as Eth256, U256};
 
 	#[test]
 	fn transaction_deserialization() {
@@ -71,7 +195,9 @@ mod tests {
 		let tx: Transaction = serde_json::from_str(s).expect("JSON string is valid");
 		assert_eq!(tx.data, Bytes::new(Vec::new()));
 		assert_eq!(tx.gas_limit, Uint(U256::from(0xf388)));
-		assert_eq!(tx.gas_price, Uint(U256::from(0x09184e72a000_u64)));
+		assert_eq!(tx.gas_price, Some(Uint(U256::from(0x09184e72a000_u64))));
+		assert_eq!(tx.max_fee_per_gas, None);
+		assert_eq!(tx.max_priority_fee_per_gas, None);
 		assert_eq!(tx.nonce, Uint(U256::zero()));
 		assert_eq!(tx.to, MaybeEmpty::None);
 		assert_eq!(tx.value, Uint(U256::zero()));
@@ -79,5 +205,174 @@ mod tests {
 		assert_eq!(tx.s, Uint(U256::one()).into());
 		assert_eq!(tx.v, Uint(U256::from(2)).into());
 		assert_eq!(tx.secret, Some(H256(Eth256::zero())));
+		assert_eq!(tx.access_list, None);
+		assert_eq!(tx.tx_type(), TxType::Legacy);
+		assert_eq!(tx.fee_cap(), Uint(U256::from(0x09184e72a000_u64)));
+		assert_eq!(tx.validate(), Ok(()));
+	}
+
+	#[test]
+	fn eip155_transaction_deserialization() {
+		let s = r#"{
+			"data" : "0x",
+			"gasLimit" : "0x5208",
+			"gasPrice" : "0x04a817c800",
+			"nonce" : "0x09",
+			"to" : "0x3535353535353535353535353535353535353535",
+			"value" : "0x0de0b6b3a7640000",
+			"chainId" : "0x01",
+			"r" : "0x28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276",
+			"s" : "0x67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+			"v" : "0x25"
+		}"#;
+		let tx: Transaction = serde_json::from_str(s).expect("JSON string is valid");
+		assert_eq!(tx.chain_id, Some(Uint(U256::one())));
+		assert_eq!(tx.v, Uint(U256::from(37)).into());
+		assert_eq!(tx.eip155_chain_id(), Some(Uint(U256::one())));
+		assert_eq!(tx.secret, None);
+	}
+
+	#[test]
+	fn eip155_chain_id_from_large_v() {
+		let s = r#"{
+			"data" : "0x",
+			"gasLimit" : "0x5208",
+			"gasPrice" : "0x01",
+			"nonce" : "0x00",
+			"to" : "0x3535353535353535353535353535353535353535",
+			"value" : "0x00",
+			"r" : "0x01",
+			"s" : "0x01",
+			"v" : "0x0100000000000000000000000000000024"
+		}"#;
+		let tx: Transaction = serde_json::from_str(s).expect("JSON string is valid");
+		assert_eq!(tx.chain_id, None);
+		assert_eq!(tx.eip155_chain_id(), Some(Uint((U256::one() << 128) / 2)));
+	}
+
+	#[test]
+	fn pre_eip155_transaction_deserialization() {
+		let s = r#"{
+			"data" : "0x",
+			"gasLimit" : "0x5208",
+			"gasPrice" : "0x04a817c800",
+			"nonce" : "0x09",
+			"to" : "0x3535353535353535353535353535353535353535",
+			"value" : "0x0de0b6b3a7640000",
+			"r" : "0x01",
+			"s" : "0x01",
+			"v" : "0x1b"
+		}"#;
+		let tx: Transaction = serde_json::from_str(s).expect("JSON string is valid");
+		assert_eq!(tx.chain_id, None);
+		assert_eq!(tx.v, Uint(U256::from(27)).into());
+		assert_eq!(tx.eip155_chain_id(), None);
+	}
+
+	#[test]
+	fn access_list_transaction_deserialization() {
+		let s = r#"{
+			"data" : "0x",
+			"gasLimit" : "0xf388",
+			"gasPrice" : "0x09184e72a000",
+			"nonce" : "0x00",
+			"to" : "0x095e7baea6a6c7c4c2dfeb977efac326af552d87",
+			"value" : "0x00",
+			"secretKey": "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8",
+			"accessList": [
+				{
+					"address": "0x095e7baea6a6c7c4c2dfeb977efac326af552d87",
+					"storageKeys": [
+						"0x0000000000000000000000000000000000000000000000000000000000000000",
+						"0x0000000000000000000000000000000000000000000000000000000000000001"
+					]
+				},
+				{
+					"address": "0x0000000000000000000000000000000000000001",
+					"storageKeys": []
+				}
+			]
+		}"#;
+		let tx: Transaction = serde_json::from_str(s).expect("JSON string is valid");
+		assert_eq!(tx.access_list, Some(vec![
+			(
+				Address(Eth160::from_str("095e7baea6a6c7c4c2dfeb977efac326af552d87").unwrap()),
+				vec![H256(Eth256::zero()), H256(Eth256::from_low_u64_be(1))],
+			),
+			(Address(Eth160::from_low_u64_be(1)), vec![]),
+		]));
+		assert_eq!(tx.tx_type(), TxType::AccessList);
+	}
+
+	#[test]
+	fn eip1559_transaction_deserialization() {
+		let s = r#"{
+			"data" : "0x",
+			"gasLimit" : "0xf388",
+			"maxFeePerGas" : "0x07d0",
+			"maxPriorityFeePerGas" : "0x64",
+			"nonce" : "0x00",
+			"to" : "0x095e7baea6a6c7c4c2dfeb977efac326af552d87",
+			"value" : "0x00",
+			"secretKey": "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8",
+			"accessList": []
+		}"#;
+		let tx: Transaction = serde_json::from_str(s).expect("JSON string is valid");
+		assert_eq!(tx.gas_price, None);
+		assert_eq!(tx.max_fee_per_gas, Some(Uint(U256::from(2000))));
+		assert_eq!(tx.max_priority_fee_per_gas, Some(Uint(U256::from(100))));
+		assert_eq!(tx.access_list, Some(vec![]));
+		assert_eq!(tx.tx_type(), TxType::EIP1559);
+		assert_eq!(tx.fee_cap(), Uint(U256::from(2000)));
+	}
+
+	#[test]
+	fn blob_transaction_deserialization() {
+		let s = r#"{
+			"data" : "0x",
+			"gasLimit" : "0xf388",
+			"maxFeePerGas" : "0x07d0",
+			"maxPriorityFeePerGas" : "0x64",
+			"maxFeePerBlobGas" : "0x0a",
+			"blobVersionedHashes" : [
+				"0x0100000000000000000000000000000000000000000000000000000000000001"
+			],
+			"nonce" : "0x00",
+			"to" : "0x095e7baea6a6c7c4c2dfeb977efac326af552d87",
+			"value" : "0x00",
+			"secretKey": "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8",
+			"accessList": []
+		}"#;
+		let tx: Transaction = serde_json::from_str(s).expect("JSON string is valid");
+		assert_eq!(tx.max_fee_per_blob_gas, Some(Uint(U256::from(10))));
+		let hash = Eth256::from_str("0100000000000000000000000000000000000000000000000000000000000001").unwrap();
+		assert_eq!(tx.blob_versioned_hashes, Some(vec![H256(hash)]));
+		assert_eq!(tx.tx_type(), TxType::Blob);
+		assert_eq!(tx.tx_type() as u8, 3);
+		assert_eq!(tx.fee_cap(), Uint(U256::from(2000)));
+		assert_eq!(tx.validate(), Ok(()));
+	}
+
+	#[test]
+	fn blob_transaction_requires_versioned_hashes_and_recipient() {
+		let tx = |hashes: &str, to: &str| serde_json::from_str::<Transaction>(&format!(r#"{{
+			"data" : "0x",
+			"gasLimit" : "0xf388",
+			"maxFeePerGas" : "0x07d0",
+			"maxPriorityFeePerGas" : "0x64",
+			"maxFeePerBlobGas" : "0x0a",
+			{}
+			"nonce" : "0x00",
+			"to" : "{}",
+			"value" : "0x00"
+		}}"#, hashes, to)).expect("JSON string is valid");
+		let hashes = r#""blobVersionedHashes" : ["0x0100000000000000000000000000000000000000000000000000000000000001"],"#;
+		let to = "0x095e7baea6a6c7c4c2dfeb977efac326af552d87";
+
+		assert_eq!(tx("", to).tx_type(), TxType::Blob);
+		assert_eq!(tx("", to).validate(), Err(TransactionError::MissingBlobVersionedHashes));
+		assert_eq!(tx(r#""blobVersionedHashes" : [],"#, to).validate(), Err(TransactionError::MissingBlobVersionedHashes));
+		assert_eq!(tx(hashes, "").validate(), Err(TransactionError::MissingBlobRecipient));
+		assert_eq!(tx(hashes, to).validate(), Ok(()));
 	}
 }
@@ -16,6 +16,8 @@
 
 //! Lenient uint json deserialization for test json files.
 
+use std::borrow::Cow;
+use std::convert::TryFrom;
 use std::fmt;
 use std::str::FromStr;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -26,6 +28,33 @@ use ethereum_types::U256;
 #[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Uint(pub U256);
 
+impl Uint {
+	/// Whether the value is zero.
+	pub fn is_zero(&self) -> bool {
+		self.0.is_zero()
+	}
+
+	/// Add `other`, returning `None` on overflow.
+	pub fn checked_add(self, other: Uint) -> Option<Uint> {
+		self.0.checked_add(other.0).map(Uint)
+	}
+
+	/// The value as `u64`, `None` if it doesn't fit.
+	pub fn as_u64(&self) -> Option<u64> {
+		if self.0.bits() > 64 {
+			None
+		} else {
+			Some(self.0.low_u64())
+		}
+	}
+}
+
+impl From<u64> for Uint {
+	fn from(value: u64) -> Self {
+		Uint(value.into())
+	}
+}
+
 impl Into<U256> for Uint {
 	fn into(self) -> U256 {
 		self.0
@@ -45,10 +74,44 @@ impl Into<usize> for Uint {
 }
 
 
+/// Serializes to a `0x` prefixed hex string without leading zeros, the form chain specs use.
+///
+/// Earlier versions emitted decimal strings. Deserialization accepts both, so older dumps still load,
+/// but re-emitting them yields hex.
 impl Serialize for Uint {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 		where S: Serializer {
-		self.0.to_string().serialize(serializer)
+		format!("0x{:x}", self.0).serialize(serializer)
+	}
+}
+
+/// Lenient json deserialization of integers which must fit into 64 bits, such as block numbers.
+#[derive(Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct U64(pub u64);
+
+impl From<u64> for U64 {
+	fn from(value: u64) -> Self {
+		U64(value)
+	}
+}
+
+impl Into<u64> for U64 {
+	fn into(self) -> u64 {
+		self.0
+	}
+}
+
+impl Serialize for U64 {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where S: Serializer {
+		format!("0x{:x}", self.0).serialize(serializer)
+	}
+}
+
+impl<'a> Deserialize<'a> for U64 {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where D: Deserializer<'a> {
+		deserialize_u64_or_hex(deserializer).map(U64)
 	}
 }
 
@@ -72,16 +135,38 @@ impl<'a> Visitor<'a> for UintVisitor {
 		Ok(Uint(U256::from(value)))
 	}
 
+	fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> where E: Error {
+		if value < 0 {
+			return Err(invalid_uint(value));
+		}
+		self.visit_u64(value as u64)
+	}
+
+	fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> where E: Error {
+		Err(invalid_uint(value))
+	}
+
 	fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> where E: Error {
 		let value = match value.len() {
 			0 => U256::from(0),
 			2 if value.starts_with("0x") => U256::from(0),
-			_ if value.starts_with("0x") => U256::from_str(&value[2..]).map_err(|e| {
-				Error::custom(format!("Invalid hex value {}: {}", value, e).as_str())
-			})?,
-			_ => U256::from_dec_str(value).map_err(|e| {
-				Error::custom(format!("Invalid decimal value {}: {:?}", value, e).as_str())
-			})?
+			_ if value.starts_with("0x") => {
+				if !value[2..].chars().all(|c| c.is_ascii_hexdigit()) {
+					return Err(invalid_uint(value));
+				}
+				U256::from_str(&value[2..]).map_err(|e| {
+					Error::custom(format!("Invalid hex value {}: {}", value, e).as_str())
+				})?
+			},
+			_ => {
+				let digits = strip_separators(value)?;
+				if !digits.chars().all(|c| c.is_ascii_digit()) {
+					return Err(invalid_uint(value));
+				}
+				U256::from_dec_str(&digits).map_err(|e| {
+					Error::custom(format!("Invalid decimal value {}: {:?}", value, e).as_str())
+				})?
+			}
 		};
 
 		Ok(Uint(value))
@@ -92,11 +177,26 @@ impl<'a> Visitor<'a> for UintVisitor {
 	}
 }
 
+fn invalid_uint<E: Error, T: fmt::Display>(value: T) -> E {
+	Error::custom(format!("invalid Uint: expected non-negative integer or 0x-hex string, got \"{}\"", value))
+}
+
+/// Removes the `_` digit separators of a decimal string, each of which must sit between two digits.
+fn strip_separators<E: Error>(value: &str) -> Result<Cow<str>, E> {
+	if !value.contains('_') {
+		return Ok(Cow::Borrowed(value));
+	}
+	if value.starts_with('_') || value.ends_with('_') || value.contains("__") {
+		return Err(Error::custom(format!("Invalid digit separators in decimal value {}", value)));
+	}
+	Ok(Cow::Owned(value.replace('_', "")))
+}
+
 /// Deserialize and validate that the value is non-zero
 pub fn validate_non_zero<'de, D>(d: D) -> Result<Uint, D::Error> where D: Deserializer<'de> {
 	let value = Uint::deserialize(d)?;
 
-	if value == Uint(U256::from(0)) {
+	if value.is_zero() {
 		return Err(Error::invalid_value(Unexpected::Unsigned(0), &"a non-zero value"))
 	}
 
@@ -108,7 +208,7 @@ pub fn validate_optional_non_zero<'de, D>(d: D) -> Result<Option<Uint>, D::Error
 	let value: Option<Uint> = Option::deserialize(d)?;
 
 	if let Some(value) = value {
-		if value == Uint(U256::from(0)) {
+		if value.is_zero() {
 			return Err(Error::invalid_value(Unexpected::Unsigned(0), &"a non-zero value"))
 		}
 	}
@@ -116,9 +216,22 @@ pub fn validate_optional_non_zero<'de, D>(d: D) -> Result<Option<Uint>, D::Error
 	Ok(value)
 }
 
+/// Deserialize an integer given either as a JSON number or as a hex or decimal string, and validate
+/// that it fits into `T`.
+pub fn deserialize_u64_or_hex<'de, D, T>(d: D) -> Result<T, D::Error>
+	where D: Deserializer<'de>, T: TryFrom<u64>
+{
+	let value = Uint::deserialize(d)?;
+
+	let low = value.as_u64().ok_or_else(|| Error::custom(format!("value {} does not fit into 64 bits", value.0)))?;
+
+	T::try_from(low).map_err(|_| Error::custom(format!("value {} is out of range", value.0)))
+}
+
 #[cfg(test)]
 mod test {
-	use super::Uint;
+	use super::{Uint, U64, deserialize_u64_or_hex};
+	use serde::Deserialize;
 	use ethereum_types::U256;
 
 	#[test]
@@ -134,8 +247,109 @@ mod test {
 		]);
 	}
 
+	#[test]
+	fn uint_serialization() {
+		let uint = Uint(0x17d433.into());
+		let serialized = serde_json::to_string(&uint).unwrap();
+		assert_eq!(serialized, r#""0x17d433""#);
+		assert_eq!(serde_json::from_str::<Uint>(&serialized).unwrap(), uint);
+		assert_eq!(serde_json::to_string(&Uint::default()).unwrap(), r#""0x0""#);
+	}
+
+	#[test]
+	fn uint_serialization_round_trips_spec_balances() {
+		let spec: serde_json::Value = serde_json::from_str(include_str!("../../ethcore/res/ethereum/foundation.json")).unwrap();
+		let balances: Vec<_> = spec["accounts"].as_object().unwrap().values()
+			.filter_map(|account| account.get("balance"))
+			.collect();
+		assert!(!balances.is_empty());
+
+		for balance in balances {
+			let uint: Uint = serde_json::from_value(balance.clone()).unwrap();
+			assert_eq!(&serde_json::to_value(&uint).unwrap(), balance);
+		}
+	}
+
+	#[test]
+	fn uint_deserialization_with_separators() {
+		let s = r#"["1_000_000_000_000_000_000", "1_0", "1000", "0x1000"]"#;
+		let deserialized: Vec<Uint> = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, vec![
+			Uint(U256::from(1_000_000_000_000_000_000u64)),
+			Uint(U256::from(10)),
+			Uint(U256::from(1000)),
+			Uint(U256::from(0x1000)),
+		]);
+	}
+
+	#[test]
+	fn uint_deserialization_with_misplaced_separators() {
+		for s in &[r#""_1000""#, r#""1000_""#, r#""1__000""#, r#""_""#, r#""0x1_000""#] {
+			assert!(serde_json::from_str::<Uint>(s).is_err(), "{} should be rejected", s);
+		}
+	}
+
+	#[test]
+	fn uint_deserialization_rejects_invalid_input() {
+		for (s, got) in &[("-5", "-5"), ("1.5", "1.5"), (r#""abc""#, "abc"), (r#""-5""#, "-5"), (r#""0xzz""#, "0xzz")] {
+			let err = serde_json::from_str::<Uint>(s).unwrap_err().to_string();
+			let expected = format!("invalid Uint: expected non-negative integer or 0x-hex string, got \"{}\"", got);
+			assert!(err.starts_with(&expected), "unexpected error for {}: {}", s, err);
+		}
+	}
+
+	#[test]
+	fn u64_or_hex_deserialization() {
+		#[derive(Deserialize)]
+		struct Price {
+			#[serde(deserialize_with = "deserialize_u64_or_hex")]
+			price: usize,
+		}
+
+		let number: Price = serde_json::from_str(r#"{"price": 3000}"#).unwrap();
+		assert_eq!(number.price, 3000);
+		let hex: Price = serde_json::from_str(r#"{"price": "0xBB8"}"#).unwrap();
+		assert_eq!(hex.price, 3000);
+		assert!(serde_json::from_str::<Price>(r#"{"price": "0x10000000000000000"}"#).is_err());
+	}
+
+	#[test]
+	fn u64_deserialization() {
+		let s = r#"[0, "0x10", "16", 18446744073709551615, "0xffffffffffffffff", "18_446_744_073_709_551_615"]"#;
+		let deserialized: Vec<U64> = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, vec![U64(0), U64(16), U64(16), U64(u64::max_value()), U64(u64::max_value()), U64(u64::max_value())]);
+
+		for s in &[r#""0x10000000000000000""#, r#""18446744073709551616""#, "18446744073709551616", "-1"] {
+			assert!(serde_json::from_str::<U64>(s).is_err(), "{} should be rejected", s);
+		}
+	}
+
+	#[test]
+	fn u64_serialization() {
+		assert_eq!(serde_json::to_string(&U64(0x17d433)).unwrap(), r#""0x17d433""#);
+		assert_eq!(serde_json::to_string(&U64(0)).unwrap(), r#""0x0""#);
+		assert_eq!(serde_json::from_str::<U64>(r#""0x17d433""#).unwrap(), U64(0x17d433));
+	}
+
 	#[test]
 	fn uint_into() {
 		assert_eq!(U256::from(10), Uint(U256::from(10)).into());
 	}
+
+	#[test]
+	fn uint_as_u64() {
+		let max = U256::from(u64::max_value());
+		assert_eq!(Uint(max).as_u64(), Some(u64::max_value()));
+		assert_eq!(Uint(max + 1).as_u64(), None);
+		assert_eq!(Uint(U256::max_value()).as_u64(), None);
+		assert_eq!(Uint::from(0).as_u64(), Some(0));
+	}
+
+	#[test]
+	fn uint_helpers() {
+		assert!(Uint::default().is_zero());
+		assert!(!Uint::from(1).is_zero());
+		assert_eq!(Uint::from(1).checked_add(Uint::from(2)), Some(Uint::from(3)));
+		assert_eq!(Uint(U256::max_value()).checked_add(Uint::from(1)), None);
+	}
 }
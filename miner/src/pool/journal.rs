@@ -0,0 +1,151 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Journal of pending local transactions.
+//!
+//! The journal is rewritten and synced to disk whenever the set of pending local transactions
+//! changes, so they survive restarts and crashes and can be imported again on startup.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use rlp::{Rlp, RlpStream};
+use types::transaction::{Condition, PendingTransaction, SignedTransaction, UnverifiedTransaction};
+
+const CONDITION_NUMBER: u8 = 0;
+const CONDITION_TIMESTAMP: u8 = 1;
+
+/// File the pending local transactions are written to.
+#[derive(Debug, Clone)]
+pub struct Journal {
+	path: PathBuf,
+}
+
+impl Journal {
+	/// Create a journal written to `path`.
+	pub fn new(path: PathBuf) -> Self {
+		Journal { path }
+	}
+
+	/// Read the transactions from the journal. Entries which can't be decoded are skipped.
+	pub fn read(&self) -> io::Result<Vec<PendingTransaction>> {
+		let bytes = match fs::read(&self.path) {
+			Ok(bytes) => bytes,
+			Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+			Err(e) => return Err(e),
+		};
+
+		let rlp = Rlp::new(&bytes);
+		let entries = rlp.iter().filter_map(|entry| match decode_entry(&entry) {
+			Ok(pending) => Some(pending),
+			Err(e) => {
+				warn!(target: "own_tx", "Skipping invalid journaled transaction: {}", e);
+				None
+			}
+		});
+		Ok(entries.collect())
+	}
+
+	/// Replace the journaled transactions with `transactions`.
+	pub fn write<'a, I>(&self, transactions: I) -> io::Result<()> where
+		I: IntoIterator<Item = &'a PendingTransaction>,
+	{
+		let transactions: Vec<_> = transactions.into_iter().collect();
+		let mut stream = RlpStream::new_list(transactions.len());
+		for pending in transactions {
+			stream.begin_list(2).append(&pending.transaction);
+			match pending.condition {
+				None => { stream.begin_list(0); },
+				Some(Condition::Number(number)) => { stream.begin_list(2).append(&CONDITION_NUMBER).append(&number); },
+				Some(Condition::Timestamp(timestamp)) => { stream.begin_list(2).append(&CONDITION_TIMESTAMP).append(&timestamp); },
+			}
+		}
+
+		// write to a temporary file first, so a crash never leaves a truncated journal behind
+		let tmp_path = self.path.with_extension("tmp");
+		let mut file = File::create(&tmp_path)?;
+		file.write_all(&stream.out())?;
+		file.sync_all()?;
+		fs::rename(&tmp_path, &self.path)
+	}
+}
+
+fn decode_entry(entry: &Rlp) -> Result<PendingTransaction, String> {
+	let tx: UnverifiedTransaction = entry.val_at(0).map_err(|e| e.to_string())?;
+	let hash = tx.hash();
+	let tx = SignedTransaction::new(tx).map_err(|_| format!("bad signature on transaction {:?}", hash))?;
+
+	let condition = entry.at(1).map_err(|e| e.to_string())?;
+	let condition = match condition.item_count().map_err(|e| e.to_string())? {
+		0 => None,
+		_ => match condition.val_at::<u8>(0).map_err(|e| e.to_string())? {
+			CONDITION_NUMBER => Some(Condition::Number(condition.val_at(1).map_err(|e| e.to_string())?)),
+			CONDITION_TIMESTAMP => Some(Condition::Timestamp(condition.val_at(1).map_err(|e| e.to_string())?)),
+			other => return Err(format!("unknown condition {} on transaction {:?}", other, hash)),
+		},
+	};
+
+	Ok(PendingTransaction::new(tx, condition))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::env;
+	use pool::tests::tx::Tx;
+
+	fn journal(name: &str) -> Journal {
+		let dir = env::temp_dir().join(format!("txqueue-journal-{}", ::std::process::id()));
+		fs::create_dir_all(&dir).unwrap();
+		let path = dir.join(name);
+		let _ = fs::remove_file(&path);
+		Journal::new(path)
+	}
+
+	#[test]
+	fn should_read_nothing_without_journal() {
+		assert_eq!(journal("missing").read().unwrap(), vec![]);
+	}
+
+	#[test]
+	fn should_roundtrip_transactions_with_conditions() {
+		let journal = journal("roundtrip");
+		let (tx1, tx2, tx3) = Tx::default().signed_triple();
+		let transactions = vec![
+			PendingTransaction::new(tx1, None),
+			PendingTransaction::new(tx2, Some(Condition::Number(100))),
+			PendingTransaction::new(tx3, Some(Condition::Timestamp(1_000_000))),
+		];
+
+		journal.write(&transactions).unwrap();
+		assert_eq!(journal.read().unwrap(), transactions);
+
+		journal.write(&transactions[1..]).unwrap();
+		assert_eq!(journal.read().unwrap(), &transactions[1..]);
+	}
+
+	#[test]
+	fn should_skip_transactions_with_bad_signatures() {
+		let journal = journal("bad-signature");
+		let good = PendingTransaction::new(Tx::default().signed(), None);
+		let bad = PendingTransaction::new(Tx::default().unsigned().fake_sign(Default::default()), None);
+
+		journal.write(vec![&good, &bad]).unwrap();
+		assert_eq!(journal.read().unwrap(), vec![good]);
+	}
+}
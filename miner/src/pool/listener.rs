@@ -21,6 +21,7 @@ use std::sync::Arc;
 
 use ethereum_types::H256;
 use futures::sync::mpsc;
+use parking_lot::Mutex;
 use txpool::{self, VerifiedTransaction};
 
 use pool::VerifiedTransaction as Transaction;
@@ -79,7 +80,9 @@ impl txpool::Listener<Transaction> for Logger {
 pub struct TransactionsPoolNotifier {
 	full_listeners: Vec<mpsc::UnboundedSender<Arc<Vec<(H256, TxStatus)>>>>,
 	pending_listeners: Vec<mpsc::UnboundedSender<Arc<Vec<H256>>>>,
+	pending_transactions_listeners: Vec<mpsc::UnboundedSender<Arc<Vec<Arc<Transaction>>>>>,
 	tx_statuses: Vec<(H256, TxStatus)>,
+	added: Vec<Arc<Transaction>>,
 }
 
 impl TransactionsPoolNotifier {
@@ -93,12 +96,23 @@ impl TransactionsPoolNotifier {
 		self.pending_listeners.push(f);
 	}
 
+	/// Add new listener to receive the bodies of added transactions.
+	pub fn add_pending_transactions_listener(&mut self, f: mpsc::UnboundedSender<Arc<Vec<Arc<Transaction>>>>) {
+		self.pending_transactions_listeners.push(f);
+	}
+
 	/// Notify listeners about all currently transactions.
 	pub fn notify(&mut self) {
 		if self.tx_statuses.is_empty() {
 			return;
 		}
 
+		if !self.added.is_empty() {
+			let to_send = Arc::new(std::mem::replace(&mut self.added, Vec::new()));
+			self.pending_transactions_listeners
+				.retain(|listener| listener.unbounded_send(to_send.clone()).is_ok());
+		}
+
 		let to_pending_send: Arc<Vec<H256>> = Arc::new(
 			self.tx_statuses.clone()
 				.into_iter()
@@ -118,6 +132,7 @@ impl fmt::Debug for TransactionsPoolNotifier {
 		fmt.debug_struct("TransactionsPoolNotifier")
 			.field("full_listeners", &self.full_listeners.len())
 			.field("pending_listeners", &self.pending_listeners.len())
+			.field("pending_transactions_listeners", &self.pending_transactions_listeners.len())
 			.finish()
 	}
 }
@@ -125,6 +140,9 @@ impl fmt::Debug for TransactionsPoolNotifier {
 impl txpool::Listener<Transaction> for TransactionsPoolNotifier {
 	fn added(&mut self, tx: &Arc<Transaction>, _old: Option<&Arc<Transaction>>) {
 		self.tx_statuses.push((tx.hash.clone(), TxStatus::Added));
+		if !self.pending_transactions_listeners.is_empty() {
+			self.added.push(tx.clone());
+		}
 	}
 
 	fn rejected<H: fmt::Debug + fmt::LowerHex>(&mut self, tx: &Arc<Transaction>, _reason: &txpool::Error<H>) {
@@ -148,6 +166,112 @@ impl txpool::Listener<Transaction> for TransactionsPoolNotifier {
 	}
 }
 
+/// Event of a single transaction in the pool.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PoolEvent {
+	/// Transaction hash.
+	pub hash: H256,
+	/// What happened to the transaction.
+	pub event: TxStatus,
+	/// Why the transaction was rejected or dropped, if known.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reason: Option<String>,
+}
+
+type PoolEventsListeners = Arc<Mutex<Vec<mpsc::UnboundedSender<Arc<Vec<PoolEvent>>>>>>;
+
+/// Transactions pool notifier sending every event together with the rejection reasons.
+///
+/// Events are only recorded by the listener, they are sent out by `PoolEvents::notify`
+/// so that slow subscribers don't hold up the pool.
+#[derive(Default)]
+pub struct PoolEventsNotifier {
+	listeners: PoolEventsListeners,
+	events: Vec<PoolEvent>,
+}
+
+impl PoolEventsNotifier {
+	/// Add new listener to receive the events.
+	pub fn add_listener(&mut self, f: mpsc::UnboundedSender<Arc<Vec<PoolEvent>>>) {
+		self.listeners.lock().push(f);
+	}
+
+	/// Take the events recorded since the last call.
+	///
+	/// Returns `None` if there is nothing to send.
+	pub fn take(&mut self) -> Option<PoolEvents> {
+		if self.events.is_empty() {
+			return None;
+		}
+
+		Some(PoolEvents {
+			listeners: self.listeners.clone(),
+			events: Arc::new(std::mem::replace(&mut self.events, Vec::new())),
+		})
+	}
+
+	fn record(&mut self, hash: &H256, event: TxStatus, reason: Option<String>) {
+		if self.listeners.lock().is_empty() {
+			return;
+		}
+
+		self.events.push(PoolEvent { hash: *hash, event, reason });
+	}
+}
+
+impl fmt::Debug for PoolEventsNotifier {
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+		fmt.debug_struct("PoolEventsNotifier")
+			.field("listeners", &self.listeners.lock().len())
+			.field("events", &self.events.len())
+			.finish()
+	}
+}
+
+impl txpool::Listener<Transaction> for PoolEventsNotifier {
+	fn added(&mut self, tx: &Arc<Transaction>, old: Option<&Arc<Transaction>>) {
+		self.record(&tx.hash, TxStatus::Added, None);
+		if let Some(old) = old {
+			self.record(&old.hash, TxStatus::Dropped, Some(format!("Replaced by {:?}", tx.hash)));
+		}
+	}
+
+	fn rejected<H: fmt::Debug + fmt::LowerHex>(&mut self, tx: &Arc<Transaction>, reason: &txpool::Error<H>) {
+		self.record(&tx.hash, TxStatus::Rejected, Some(reason.to_string()));
+	}
+
+	fn dropped(&mut self, tx: &Arc<Transaction>, new: Option<&Transaction>) {
+		let reason = new.map(|new| format!("Pushed out by {:?}", new.hash));
+		self.record(&tx.hash, TxStatus::Dropped, reason);
+	}
+
+	fn invalid(&mut self, tx: &Arc<Transaction>) {
+		self.record(&tx.hash, TxStatus::Invalid, None);
+	}
+
+	fn canceled(&mut self, tx: &Arc<Transaction>) {
+		self.record(&tx.hash, TxStatus::Canceled, None);
+	}
+
+	fn culled(&mut self, tx: &Arc<Transaction>) {
+		self.record(&tx.hash, TxStatus::Culled, None);
+	}
+}
+
+/// Pool events taken out of the `PoolEventsNotifier`, to be sent after releasing the pool lock.
+pub struct PoolEvents {
+	listeners: PoolEventsListeners,
+	events: Arc<Vec<PoolEvent>>,
+}
+
+impl PoolEvents {
+	/// Send the events to all listeners, dropping the ones which are gone.
+	pub fn notify(self) {
+		let events = self.events;
+		self.listeners.lock().retain(|listener| listener.unbounded_send(events.clone()).is_ok());
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -161,10 +285,12 @@ mod tests {
 		// given
 		let (full_sender, full_receiver) = mpsc::unbounded();
 		let (pending_sender, pending_receiver) = mpsc::unbounded();
+		let (transactions_sender, transactions_receiver) = mpsc::unbounded();
 
 		let mut tx_listener = TransactionsPoolNotifier::default();
 		tx_listener.add_full_listener(full_sender);
 		tx_listener.add_pending_listener(pending_sender);
+		tx_listener.add_pending_transactions_listener(transactions_sender);
 
 		// when
 		let tx = new_tx();
@@ -174,6 +300,7 @@ mod tests {
 		tx_listener.notify();
 		let (full_res , _full_receiver)= full_receiver.into_future().wait().unwrap();
 		let (pending_res , _pending_receiver)= pending_receiver.into_future().wait().unwrap();
+		let (transactions_res, _transactions_receiver) = transactions_receiver.into_future().wait().unwrap();
 		assert_eq!(
 			full_res,
 			Some(Arc::new(vec![(serde_json::from_str::<H256>("\"0x13aff4201ac1dc49daf6a7cf07b558ed956511acbaabf9502bdacc353953766d\"").unwrap(), TxStatus::Added)]))
@@ -182,6 +309,7 @@ mod tests {
 			pending_res,
 			Some(Arc::new(vec![serde_json::from_str::<H256>("\"0x13aff4201ac1dc49daf6a7cf07b558ed956511acbaabf9502bdacc353953766d\"").unwrap()]))
 		);
+		assert_eq!(transactions_res, Some(Arc::new(vec![tx])));
 	}
 
 	fn new_tx() -> Arc<Transaction> {
@@ -21,6 +21,7 @@ use std::{fmt, sync::Arc};
 use ethereum_types::H256;
 use linked_hash_map::LinkedHashMap;
 use pool::{VerifiedTransaction as Transaction, ScoredTransaction};
+use pool::journal::Journal;
 use txpool::{self, VerifiedTransaction};
 
 /// Status of local transaction.
@@ -67,6 +68,7 @@ pub struct LocalTransactionsList {
 	transactions: LinkedHashMap<H256, Status>,
 	pending: usize,
 	in_chain: Option<Box<dyn Fn(&H256) -> bool + Send + Sync>>,
+	journal: Option<Journal>,
 }
 
 impl fmt::Debug for LocalTransactionsList {
@@ -76,6 +78,7 @@ impl fmt::Debug for LocalTransactionsList {
 			.field("transactions", &self.transactions)
 			.field("pending", &self.pending)
 			.field("in_chain", &self.in_chain.is_some())
+			.field("journal", &self.journal)
 			.finish()
 	}
 }
@@ -94,6 +97,7 @@ impl LocalTransactionsList {
 			transactions: Default::default(),
 			pending: 0,
 			in_chain: None,
+			journal: None,
 		}
 	}
 
@@ -107,6 +111,12 @@ impl LocalTransactionsList {
 		self.in_chain = checker.into().map(|f| Box::new(f) as _);
 	}
 
+	/// Set the journal pending transactions are written to, and write the current ones to it.
+	pub fn set_journal(&mut self, journal: Journal) {
+		self.journal = Some(journal);
+		self.update_journal();
+	}
+
 	/// Returns true if the transaction is already in local transactions.
 	pub fn contains(&self, hash: &H256) -> bool {
 		self.transactions.contains_key(hash)
@@ -140,6 +150,18 @@ impl LocalTransactionsList {
 		}
 	}
 
+	fn update_journal(&self) {
+		if let Some(ref journal) = self.journal {
+			let pending = self.transactions.values().filter_map(|status| match *status {
+				Status::Pending(ref tx) => Some(tx.pending()),
+				_ => None,
+			});
+			if let Err(e) = journal.write(pending) {
+				warn!(target: "own_tx", "Failed to write local transactions journal: {}", e);
+			}
+		}
+	}
+
 	fn insert(&mut self, hash: H256, status: Status) {
 		let result = self.transactions.insert(hash, status);
 		if let Some(old) = result {
@@ -169,6 +191,7 @@ impl txpool::Listener<Transaction> for LocalTransactionsList {
 				});
 			}
 		}
+		self.update_journal();
 	}
 
 	fn rejected<H: fmt::Debug + fmt::LowerHex>(&mut self, tx: &Arc<Transaction>, reason: &txpool::Error<H>) {
@@ -192,6 +215,7 @@ impl txpool::Listener<Transaction> for LocalTransactionsList {
 		}
 		self.insert(*tx.hash(), Status::Dropped(tx.clone()));
 		self.clear_old();
+		self.update_journal();
 	}
 
 	fn invalid(&mut self, tx: &Arc<Transaction>) {
@@ -202,6 +226,7 @@ impl txpool::Listener<Transaction> for LocalTransactionsList {
 		warn!(target: "own_tx", "Transaction marked invalid (hash {:?})", tx.hash());
 		self.insert(*tx.hash(), Status::Invalid(tx.clone()));
 		self.clear_old();
+		self.update_journal();
 	}
 
 	fn canceled(&mut self, tx: &Arc<Transaction>) {
@@ -212,6 +237,7 @@ impl txpool::Listener<Transaction> for LocalTransactionsList {
 		warn!(target: "own_tx", "Transaction canceled (hash {:?})", tx.hash());
 		self.insert(*tx.hash(), Status::Canceled(tx.clone()));
 		self.clear_old();
+		self.update_journal();
 	}
 
 	fn culled(&mut self, tx: &Arc<Transaction>) {
@@ -223,11 +249,11 @@ impl txpool::Listener<Transaction> for LocalTransactionsList {
 		if is_in_chain {
 			info!(target: "own_tx", "Transaction mined (hash {:?})", tx.hash());
 			self.insert(*tx.hash(), Status::Mined(tx.clone()));
-			return;
+		} else {
+			info!(target: "own_tx", "Transaction culled (hash {:?})", tx.hash());
+			self.insert(*tx.hash(), Status::Culled(tx.clone()));
 		}
-
-		info!(target: "own_tx", "Transaction culled (hash {:?})", tx.hash());
-		self.insert(*tx.hash(), Status::Culled(tx.clone()));
+		self.update_journal();
 	}
 }
 
@@ -303,6 +329,31 @@ mod tests {
 		assert!(list.contains(tx3.hash()));
 	}
 
+	#[test]
+	fn should_journal_pending_transactions() {
+		// given
+		let path = ::std::env::temp_dir().join(format!("local-transactions-journal-{}", ::std::process::id()));
+		let journal = Journal::new(path.clone());
+		let mut list = LocalTransactionsList::default();
+		let tx1 = new_tx(10);
+		let tx2 = new_tx(20);
+		list.added(&tx1, None);
+
+		// when
+		list.set_journal(journal.clone());
+		let journaled_after_set = journal.read().unwrap();
+		list.added(&tx2, None);
+		let journaled_after_added = journal.read().unwrap();
+		list.culled(&tx1);
+		let journaled_after_culled = journal.read().unwrap();
+		::std::fs::remove_file(&path).unwrap();
+
+		// then
+		assert_eq!(journaled_after_set, vec![tx1.pending().clone()]);
+		assert_eq!(journaled_after_added, vec![tx1.pending().clone(), tx2.pending().clone()]);
+		assert_eq!(journaled_after_culled, vec![tx2.pending().clone()]);
+	}
+
 	fn new_tx<T: Into<U256>>(nonce: T) -> Arc<Transaction> {
 		let keypair = Random.generate().unwrap();
 		let signed = transaction::Transaction {
@@ -26,6 +26,7 @@ mod queue;
 mod ready;
 
 pub mod client;
+pub mod journal;
 pub mod local_transactions;
 pub mod replace;
 pub mod scoring;
@@ -34,7 +35,8 @@ pub mod verifier;
 #[cfg(test)]
 mod tests;
 
-pub use self::queue::{TransactionQueue, Status as QueueStatus};
+pub use self::listener::PoolEvent;
+pub use self::queue::{TransactionQueue, Status as QueueStatus, Evictions};
 pub use self::txpool::{VerifiedTransaction as PoolVerifiedTransaction, Options};
 
 /// How to prioritize transactions in the pool
@@ -84,7 +86,7 @@ impl PendingSettings {
 }
 
 /// Transaction priority.
-#[derive(Debug, PartialEq, Eq, PartialOrd,  Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum Priority {
 	/// Regular transactions received over the network. (no priority boost)
 	Regular,
@@ -98,12 +100,17 @@ pub enum Priority {
 	/// Transactions either from a local account or
 	/// submitted over local RPC connection via `eth_sendRawTransaction`
 	Local,
+	/// Transactions from priority senders (highest priority)
+	///
+	/// Treated like local transactions, but included ahead of all other
+	/// transactions regardless of their gas price.
+	PrioritySender,
 }
 
 impl Priority {
 	fn is_local(&self) -> bool {
 		match *self {
-			Priority::Local => true,
+			Priority::Local | Priority::PrioritySender => true,
 			_ => false,
 		}
 	}
@@ -117,6 +124,10 @@ pub trait ScoredTransaction {
 	/// Gets transaction gas price.
 	fn gas_price(&self) -> &U256;
 
+	/// Gets the tip paid to the block author per gas in a block with the given base fee,
+	/// or the offered tip if the base fee is not known.
+	fn effective_priority_fee(&self, block_base_fee: Option<U256>) -> U256;
+
 	/// Gets transaction nonce.
 	fn nonce(&self) -> U256;
 }
@@ -194,6 +205,13 @@ impl ScoredTransaction for VerifiedTransaction {
 		&self.transaction.gas_price
 	}
 
+	fn effective_priority_fee(&self, block_base_fee: Option<U256>) -> U256 {
+		match block_base_fee {
+			Some(_) => self.transaction.effective_priority_fee(block_base_fee),
+			None => self.transaction.max_priority_fee_per_gas(),
+		}
+	}
+
 	/// Gets transaction nonce.
 	fn nonce(&self) -> U256 {
 		self.transaction.nonce
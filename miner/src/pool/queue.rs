@@ -16,15 +16,15 @@
 
 //! Ethereum Transaction Queue
 
-use std::{cmp, fmt};
+use std::{cmp, fmt, mem};
 use std::sync::Arc;
 use std::sync::atomic::{self, AtomicUsize};
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use ethereum_types::{H256, U256, Address};
 use futures::sync::mpsc;
 use parking_lot::RwLock;
-use txpool::{self, Verifier};
+use txpool::{self, Listener as PoolListener, Verifier};
 use types::transaction;
 
 use pool::{
@@ -33,7 +33,10 @@ use pool::{
 };
 use pool::local_transactions::LocalTransactionsList;
 
-type Listener = (LocalTransactionsList, (listener::TransactionsPoolNotifier, listener::Logger));
+type Listener = (
+	LocalTransactionsList,
+	(listener::TransactionsPoolNotifier, (listener::PoolEventsNotifier, listener::Logger)),
+);
 type Pool = txpool::Pool<pool::VerifiedTransaction, scoring::NonceAndGasPrice, Listener>;
 
 /// Max cache time in milliseconds for pending transactions.
@@ -61,6 +64,17 @@ pub struct Status {
 	pub status: txpool::LightStatus,
 	/// Current limits of the transaction pool.
 	pub limits: txpool::Options,
+	/// Transactions evicted from the full pool so far.
+	pub evictions: Evictions,
+}
+
+/// Number of transactions evicted from the full pool to make room for better paying ones, by reason.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Evictions {
+	/// Executable transactions evicted.
+	pub underpriced: usize,
+	/// Transactions waiting for a transaction with a lower nonce evicted.
+	pub future: usize,
 }
 
 impl fmt::Display for Status {
@@ -204,6 +218,9 @@ pub struct TransactionQueue {
 	options: RwLock<verifier::Options>,
 	cached_pending: RwLock<CachedPending>,
 	recently_rejected: RecentlyRejected,
+	block_base_fee: RwLock<Option<U256>>,
+	evictions: RwLock<Evictions>,
+	priority_senders: RwLock<HashSet<Address>>,
 }
 
 impl TransactionQueue {
@@ -212,14 +229,26 @@ impl TransactionQueue {
 		limits: txpool::Options,
 		verification_options: verifier::Options,
 		strategy: PrioritizationStrategy,
+	) -> Self {
+		Self::with_scoring(limits, verification_options, scoring::NonceAndGasPrice::new(strategy))
+	}
+
+	/// Create new queue with given pool limits, initial verification options and scoring.
+	pub fn with_scoring(
+		limits: txpool::Options,
+		verification_options: verifier::Options,
+		scoring: scoring::NonceAndGasPrice,
 	) -> Self {
 		let max_count = limits.max_count;
 		TransactionQueue {
 			insertion_id: Default::default(),
-			pool: RwLock::new(txpool::Pool::new(Default::default(), scoring::NonceAndGasPrice(strategy), limits)),
+			pool: RwLock::new(txpool::Pool::new(Default::default(), scoring, limits)),
 			options: RwLock::new(verification_options),
 			cached_pending: RwLock::new(CachedPending::none()),
 			recently_rejected: RecentlyRejected::new(cmp::max(MIN_REJECTED_CACHE_SIZE, max_count / 4)),
+			block_base_fee: RwLock::new(None),
+			evictions: RwLock::new(Evictions::default()),
+			priority_senders: RwLock::new(HashSet::new()),
 		}
 	}
 
@@ -230,6 +259,15 @@ impl TransactionQueue {
 		*self.options.write() = options;
 	}
 
+	/// Sets the senders whose transactions are treated like local ones and always included first.
+	///
+	/// Applies to transactions imported afterwards.
+	pub fn set_priority_senders(&self, senders: HashSet<Address>) {
+		*self.priority_senders.write() = senders;
+		// transactions rejected because of their gas price may be accepted now
+		self.recently_rejected.clear();
+	}
+
 	/// Sets the in-chain transaction checker for pool listener.
 	pub fn set_in_chain_checker<F>(&self, f: F) where
 		F: Fn(&H256) -> bool + Send + Sync + 'static
@@ -237,6 +275,11 @@ impl TransactionQueue {
 		self.pool.write().listener_mut().0.set_in_chain_checker(f)
 	}
 
+	/// Sets the journal pending local transactions are written to.
+	pub fn set_local_transactions_journal(&self, journal: pool::journal::Journal) {
+		self.pool.write().listener_mut().0.set_journal(journal)
+	}
+
 	/// Import a set of transactions to the pool.
 	///
 	/// Given blockchain and state access (Client)
@@ -268,9 +311,9 @@ impl TransactionQueue {
 			options,
 			self.insertion_id.clone(),
 			transaction_to_replace,
-		);
+		).with_priority_senders(self.priority_senders.read().clone());
 
-		let mut replace = replace::ReplaceByScoreAndReadiness::new(self.pool.read().scoring().clone(), client);
+		let mut replace = replace::ReplaceByScoreAndReadiness::new(self.pool.read().scoring().clone(), client.clone());
 
 		let results = transactions
 			.into_iter()
@@ -289,7 +332,10 @@ impl TransactionQueue {
 				let imported = verifier
 					.verify_transaction(transaction)
 					.and_then(|verified| {
-						self.pool.write().import(verified, &mut replace).map_err(convert_error)
+						let mut pool = self.pool.write();
+						check_sender_limit(&pool, &verified)?;
+						self.make_room(&mut pool, &client, &verified);
+						pool.import(verified, &mut replace).map_err(convert_error)
 					});
 
 				match imported {
@@ -304,6 +350,7 @@ impl TransactionQueue {
 
 		// Notify about imported transactions.
 		(self.pool.write().listener_mut().1).0.notify();
+		self.notify_pool_events();
 
 		if results.iter().any(|r| r.is_ok()) {
 			self.cached_pending.write().clear();
@@ -312,6 +359,44 @@ impl TransactionQueue {
 		results
 	}
 
+	/// Evicts a transaction from the full pool to make room for `new`, see `eviction_candidate`.
+	///
+	/// If there is none the pool is left as is and the import is decided by the pool limits alone.
+	fn make_room<C: client::NonceClient>(
+		&self,
+		pool: &mut Pool,
+		client: &C,
+		new: &pool::VerifiedTransaction,
+	) {
+		if pool.light_status().transaction_count < pool.options().max_count {
+			return;
+		}
+
+		// replacements don't take any additional room
+		let ready = |_tx: &pool::VerifiedTransaction| txpool::Readiness::Ready;
+		if pool.pending_from_sender(ready, &new.sender).any(|tx| tx.signed().nonce == new.signed().nonce) {
+			return;
+		}
+
+		let base_fee = *self.block_base_fee.read();
+		if let Some((worst, is_executable)) = eviction_candidate(pool, client, new, base_fee) {
+			trace!(target: "txqueue", "[{:?}] Evicting to make room for [{:?}]", worst.hash, new.hash);
+			// `remove` would report the transaction as canceled, so mute the listener
+			// and report it as dropped instead.
+			let listener = mem::replace(pool.listener_mut(), Default::default());
+			pool.remove(&worst.hash, false);
+			*pool.listener_mut() = listener;
+			pool.listener_mut().dropped(&worst, Some(new));
+
+			let mut evictions = self.evictions.write();
+			if is_executable {
+				evictions.underpriced += 1;
+			} else {
+				evictions.future += 1;
+			}
+		}
+	}
+
 	/// Returns all transactions in the queue without explicit ordering.
 	pub fn all_transactions(&self) -> Vec<Arc<pool::VerifiedTransaction>> {
 		let ready = |_tx: &pool::VerifiedTransaction| txpool::Readiness::Ready;
@@ -458,6 +543,7 @@ impl TransactionQueue {
 			let state_readiness = ready::State::new(client.clone(), stale_id, nonce_cap);
 			removed += self.pool.write().cull(Some(chunk), state_readiness);
 		}
+		self.notify_pool_events();
 		debug!(target: "txqueue", "Removed {} stalled transactions. {}", removed, self.status());
 	}
 
@@ -510,6 +596,7 @@ impl TransactionQueue {
 				.map(|hash| pool.remove(hash, is_invalid))
 				.collect::<Vec<_>>()
 		};
+		self.notify_pool_events();
 
 		if results.iter().any(Option::is_some) {
 			self.cached_pending.write().clear();
@@ -527,7 +614,17 @@ impl TransactionQueue {
 	pub fn penalize<'a, T: IntoIterator<Item = &'a Address>>(&self, senders: T) {
 		let mut pool = self.pool.write();
 		for sender in senders {
-			pool.update_scores(sender, ());
+			pool.update_scores(sender, scoring::ScoringEvent::Penalize);
+		}
+	}
+
+	/// Rescores all transactions by the tip they pay on top of the base fee of the latest block (EIP-1559).
+	pub fn set_block_base_fee(&self, block_base_fee: U256) {
+		*self.block_base_fee.write() = Some(block_base_fee);
+		let mut pool = self.pool.write();
+		let senders: Vec<_> = pool.senders().cloned().collect();
+		for sender in &senders {
+			pool.update_scores(sender, scoring::ScoringEvent::BlockBaseFee(block_base_fee));
 		}
 	}
 
@@ -545,11 +642,13 @@ impl TransactionQueue {
 		let status = pool.light_status();
 		let limits = pool.options();
 		let options = self.options.read().clone();
+		let evictions = self.evictions.read().clone();
 
 		Status {
 			options,
 			status,
 			limits,
+			evictions,
 		}
 	}
 
@@ -575,12 +674,32 @@ impl TransactionQueue {
 		(pool.listener_mut().1).0.add_pending_listener(f);
 	}
 
+	/// Add a listener to be notified about the bodies of transactions added to the pool
+	pub fn add_pending_transactions_listener(&self, f: mpsc::UnboundedSender<Arc<Vec<Arc<pool::VerifiedTransaction>>>>) {
+		let mut pool = self.pool.write();
+		(pool.listener_mut().1).0.add_pending_transactions_listener(f);
+	}
+
 	/// Add a listener to be notified about all transactions the pool
 	pub fn add_full_listener(&self, f: mpsc::UnboundedSender<Arc<Vec<(H256, TxStatus)>>>) {
 		let mut pool = self.pool.write();
 		(pool.listener_mut().1).0.add_full_listener(f);
 	}
 
+	/// Add a listener to be notified about every event of the pool, with rejection reasons.
+	pub fn add_pool_events_listener(&self, f: mpsc::UnboundedSender<Arc<Vec<pool::PoolEvent>>>) {
+		let mut pool = self.pool.write();
+		((pool.listener_mut().1).1).0.add_listener(f);
+	}
+
+	/// Sends out the pool events recorded so far, after releasing the pool lock.
+	fn notify_pool_events(&self) {
+		let events = ((self.pool.write().listener_mut().1).1).0.take();
+		if let Some(events) = events {
+			events.notify();
+		}
+	}
+
 	/// Check if pending set is cached.
 	#[cfg(test)]
 	pub fn is_pending_cached(&self) -> bool {
@@ -588,6 +707,109 @@ impl TransactionQueue {
 	}
 }
 
+/// Rejects a non-local transaction if its sender already has the maximal number of transactions
+/// in the pool and the transaction would only extend them.
+///
+/// The pool enforces the limit itself only after making room for the transaction,
+/// which could evict transactions of other senders.
+fn check_sender_limit(pool: &Pool, tx: &pool::VerifiedTransaction) -> Result<(), transaction::Error> {
+	if tx.priority.is_local() {
+		return Ok(());
+	}
+
+	let ready = |_tx: &pool::VerifiedTransaction| txpool::Readiness::Ready;
+	let mut count = 0;
+	for existing in pool.pending_from_sender(ready, &tx.sender) {
+		// replacements and transactions filling a gap are handled by the pool
+		if existing.signed().nonce >= tx.signed().nonce {
+			return Ok(());
+		}
+		count += 1;
+	}
+
+	let limit = pool.options().max_per_sender;
+	if count >= limit {
+		trace!(target: "txqueue", "[{:?}] Rejected tx, sender limit reached: {}", tx.hash, limit);
+		return Err(transaction::Error::SenderLimitReached { limit });
+	}
+
+	Ok(())
+}
+
+/// Transactions of a single sender in the pool.
+struct SenderTransactions {
+	lowest_nonce: U256,
+	last: Arc<pool::VerifiedTransaction>,
+	count: usize,
+}
+
+impl SenderTransactions {
+	/// Checks if a transaction with `nonce` would be executable after the sender's pooled ones,
+	/// given the current state nonce of the sender.
+	fn is_executable(&self, nonce: U256, state_nonce: U256) -> bool {
+		let last_nonce = self.last.signed().nonce;
+		let has_gaps = last_nonce - self.lowest_nonce + 1 != U256::from(self.count);
+		self.lowest_nonce == state_nonce && !has_gaps && nonce <= last_nonce + 1
+	}
+}
+
+/// Picks a transaction to evict from the full pool to make room for `new`.
+///
+/// Only the last transaction of every other sender is considered, so no nonce gaps are created,
+/// and local transactions are never evicted. Of those with a lower priority or effective gas price
+/// than `new` the one with the lowest is picked, the oldest one if several are priced the same,
+/// except that an executable transaction is never evicted in favour of a future one.
+///
+/// Returns the transaction with the information whether it is executable.
+fn eviction_candidate<C: client::NonceClient>(
+	pool: &Pool,
+	client: &C,
+	new: &pool::VerifiedTransaction,
+	base_fee: Option<U256>,
+) -> Option<(Arc<pool::VerifiedTransaction>, bool)> {
+	use pool::ScoredTransaction;
+
+	let price = |tx: &pool::VerifiedTransaction| (tx.priority, tx.effective_priority_fee(base_fee));
+
+	let mut senders = HashMap::<Address, SenderTransactions>::new();
+	let ready = |_tx: &pool::VerifiedTransaction| txpool::Readiness::Ready;
+	for tx in pool.unordered_pending(ready) {
+		let nonce = tx.signed().nonce;
+		let sender = senders.entry(tx.sender).or_insert_with(|| SenderTransactions {
+			lowest_nonce: nonce,
+			last: tx.clone(),
+			count: 0,
+		});
+		sender.lowest_nonce = cmp::min(sender.lowest_nonce, nonce);
+		if nonce > sender.last.signed().nonce {
+			sender.last = tx.clone();
+		}
+		sender.count += 1;
+	}
+
+	let new_nonce = new.signed().nonce;
+	let new_state_nonce = client.account_nonce(&new.sender);
+	let is_new_executable = new_nonce == new_state_nonce || senders.get(&new.sender)
+		.map_or(false, |sender| sender.is_executable(new_nonce, new_state_nonce));
+
+	let new_price = price(new);
+	let mut candidates: Vec<_> = senders
+		.into_iter()
+		.filter(|&(ref address, ref sender)| {
+			*address != new.sender && !sender.last.priority.is_local() && price(&sender.last) < new_price
+		})
+		.map(|(_, sender)| sender)
+		.collect();
+	candidates.sort_unstable_by_key(|sender| (price(&sender.last), sender.last.insertion_id()));
+
+	candidates.into_iter()
+		.map(|sender| {
+			let is_executable = sender.is_executable(sender.last.signed().nonce, client.account_nonce(&sender.last.sender));
+			(sender.last, is_executable)
+		})
+		.find(|&(_, is_executable)| is_new_executable || !is_executable)
+}
+
 fn convert_error<H: fmt::Debug + fmt::LowerHex>(err: txpool::Error<H>) -> transaction::Error {
 	use self::txpool::Error;
 
@@ -20,12 +20,16 @@
 //! Low nonces need to be included first. If there are two transactions from the same sender
 //! and with the same `nonce` only one of them can be included.
 //! We choose the one with higher gas price, but also require that gas price increment
-//! is high enough to prevent attacking miners by requiring them to reshuffle/reexecute
-//! the queue too often.
+//! is high enough (a configurable percentage, 12.5% by default) to prevent attacking miners
+//! by requiring them to reshuffle/reexecute the queue too often. Local transactions only
+//! need a higher gas price.
 //!
 //! Transactions between senders are prioritized using `gas price`. Higher `gas price`
-//! yields more profits for miners. Additionally we prioritize transactions that originate
-//! from our local node (own transactions).
+//! yields more profits for miners. Since EIP-1559 only the tip on top of the block base fee
+//! goes to the miner, so transactions are ordered by their effective tip instead; until the
+//! base fee of the next block is known the tip they offer is used.
+//! Additionally we prioritize transactions that originate from our local node (own transactions)
+//! and always put transactions of configured priority senders first.
 
 use std::cmp;
 
@@ -33,14 +37,42 @@ use ethereum_types::U256;
 use txpool::{self, scoring};
 use super::{verifier, PrioritizationStrategy, VerifiedTransaction, ScoredTransaction};
 
-/// Transaction with the same (sender, nonce) can be replaced only if
-/// `new_gas_price >= old_gas_price + old_gas_price >> SHIFT`
-const GAS_PRICE_BUMP_SHIFT: usize = 3; // 2 = 25%, 3 = 12.5%, 4 = 6.25%
+/// Default gas price bump, in basis points, required to replace a transaction with the same
+/// (sender, nonce): 12.5%.
+pub const DEFAULT_PRICE_BUMP: u32 = 1250;
 
-/// Calculate minimal gas price requirement.
+/// Calculate minimal gas price requirement: `old_gp` increased by `price_bump` basis points,
+/// but always strictly higher than `old_gp`.
 #[inline]
-fn bump_gas_price(old_gp: U256) -> U256 {
-	old_gp.saturating_add(old_gp >> GAS_PRICE_BUMP_SHIFT)
+pub fn bump_gas_price(old_gp: U256, price_bump: u32) -> U256 {
+	let bump = U256::from(price_bump);
+	// `old_gp * bump / 10_000` rounded down, without overflowing
+	let increase = (old_gp / 10_000).saturating_mul(bump)
+		.saturating_add((old_gp % 10_000) * bump / 10_000);
+	old_gp.saturating_add(cmp::max(increase, 1.into()))
+}
+
+/// Events changing the scores of the transactions of a sender.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScoringEvent {
+	/// Lower the priority of the transactions.
+	Penalize,
+	/// A block with the given base fee was imported, score transactions by their effective tip.
+	BlockBaseFee(U256),
+}
+
+/// Calculates the score of the transaction, boosted by its priority.
+///
+/// Transactions of priority senders score above any other transaction.
+fn score<P: ScoredTransaction>(tx: &P, block_base_fee: Option<U256>) -> U256 {
+	let fee = tx.effective_priority_fee(block_base_fee);
+	let boost = match tx.priority() {
+		super::Priority::PrioritySender => return (U256::one() << 255).saturating_add(fee),
+		super::Priority::Local => 15,
+		super::Priority::Retracted => 10,
+		super::Priority::Regular => 0,
+	};
+	fee << boost
 }
 
 /// Simple, gas-price based scoring for transactions.
@@ -48,9 +80,29 @@ fn bump_gas_price(old_gp: U256) -> U256 {
 /// NOTE: Currently penalization does not apply to new transactions that enter the pool.
 /// We might want to store penalization status in some persistent state.
 #[derive(Debug, Clone)]
-pub struct NonceAndGasPrice(pub PrioritizationStrategy);
+pub struct NonceAndGasPrice {
+	/// Strategy used to prioritize transactions.
+	pub strategy: PrioritizationStrategy,
+	/// Gas price bump, in basis points, required to replace a non-local transaction with the same
+	/// (sender, nonce).
+	pub price_bump: u32,
+}
 
 impl NonceAndGasPrice {
+	/// Create new scoring with given strategy and the default price bump.
+	pub fn new(strategy: PrioritizationStrategy) -> Self {
+		NonceAndGasPrice {
+			strategy,
+			price_bump: DEFAULT_PRICE_BUMP,
+		}
+	}
+
+	/// Require a gas price bump of `price_bump` basis points to replace non-local transactions.
+	pub fn with_price_bump(mut self, price_bump: u32) -> Self {
+		self.price_bump = price_bump;
+		self
+	}
+
 	/// Decide if the transaction should even be considered into the pool (if the pool is full).
 	///
 	/// Used by Verifier to quickly reject transactions that don't have any chance to get into the pool later on,
@@ -69,7 +121,7 @@ impl NonceAndGasPrice {
 
 impl<P> txpool::Scoring<P> for NonceAndGasPrice where P: ScoredTransaction + txpool::VerifiedTransaction {
 	type Score = U256;
-	type Event = ();
+	type Event = ScoringEvent;
 
 	fn compare(&self, old: &P, other: &P) -> cmp::Ordering {
 		old.nonce().cmp(&other.nonce())
@@ -83,7 +135,9 @@ impl<P> txpool::Scoring<P> for NonceAndGasPrice where P: ScoredTransaction + txp
 		let old_gp = old.gas_price();
 		let new_gp = new.gas_price();
 
-		let min_required_gp = bump_gas_price(*old_gp);
+		// local transactions are exempt from the bump
+		let price_bump = if new.priority().is_local() { 0 } else { self.price_bump };
+		let min_required_gp = bump_gas_price(*old_gp, price_bump);
 
 		match min_required_gp.cmp(&new_gp) {
 			cmp::Ordering::Greater => scoring::Choice::RejectNew,
@@ -101,17 +155,10 @@ impl<P> txpool::Scoring<P> for NonceAndGasPrice where P: ScoredTransaction + txp
 				assert!(i < txs.len());
 				assert!(i < scores.len());
 
-				scores[i] = *txs[i].transaction.gas_price();
-				let boost = match txs[i].priority() {
-					super::Priority::Local => 15,
-					super::Priority::Retracted => 10,
-					super::Priority::Regular => 0,
-				};
-				scores[i] = scores[i] << boost;
+				scores[i] = score(&*txs[i].transaction, None);
 			},
-			// We are only sending an event in case of penalization.
-			// So just lower the priority of all non-local transactions.
-			Change::Event(_) => {
+			// Lower the priority of all non-local transactions.
+			Change::Event(ScoringEvent::Penalize) => {
 				for (score, tx) in scores.iter_mut().zip(txs) {
 					// Never penalize local transactions.
 					if !tx.priority().is_local() {
@@ -119,6 +166,11 @@ impl<P> txpool::Scoring<P> for NonceAndGasPrice where P: ScoredTransaction + txp
 					}
 				}
 			},
+			Change::Event(ScoringEvent::BlockBaseFee(base_fee)) => {
+				for (score_of_tx, tx) in scores.iter_mut().zip(txs) {
+					*score_of_tx = score(&*tx.transaction, Some(base_fee));
+				}
+			},
 		}
 	}
 
@@ -138,7 +190,7 @@ mod tests {
 	#[test]
 	fn should_calculate_score_correctly() {
 		// given
-		let scoring = NonceAndGasPrice(PrioritizationStrategy::GasPriceOnly);
+		let scoring = NonceAndGasPrice::new(PrioritizationStrategy::GasPriceOnly);
 		let (tx1, tx2, tx3) = Tx::default().signed_triple();
 		let transactions = vec![tx1, tx2, tx3].into_iter().enumerate().map(|(i, tx)| {
 			let mut verified = tx.verified();
@@ -184,7 +236,47 @@ mod tests {
 		assert_eq!(scores, vec![32768.into(), 1024.into(), 1.into()]);
 
 		// Check penalization
-		scoring.update_scores(&transactions, &mut *scores, scoring::Change::Event(()));
+		scoring.update_scores(&transactions, &mut *scores, scoring::Change::Event(ScoringEvent::Penalize));
 		assert_eq!(scores, vec![32768.into(), 128.into(), 0.into()]);
+
+		// Check rescoring by the tip on top of the base fee
+		scoring.update_scores(&transactions, &mut *scores, scoring::Change::Event(ScoringEvent::BlockBaseFee(0.into())));
+		assert_eq!(scores, vec![32768.into(), 1024.into(), 1.into()]);
+		scoring.update_scores(&transactions, &mut *scores, scoring::Change::Event(ScoringEvent::BlockBaseFee(1.into())));
+		assert_eq!(scores, vec![0.into(), 0.into(), 0.into()]);
+	}
+
+	fn choose(scoring: &NonceAndGasPrice, old_gas_price: u64, new_gas_price: u64, local: bool) -> scoring::Choice {
+		let old = Tx::gas_price(old_gas_price).signed().verified();
+		let mut new = Tx::gas_price(new_gas_price).signed().verified();
+		if local {
+			new.priority = ::pool::Priority::Local;
+		}
+		scoring.choose(&old, &new)
+	}
+
+	#[test]
+	fn should_require_price_bump_to_replace() {
+		let scoring = NonceAndGasPrice::new(PrioritizationStrategy::GasPriceOnly);
+		assert_eq!(choose(&scoring, 10_000, 11_250, false), scoring::Choice::ReplaceOld);
+		assert_eq!(choose(&scoring, 10_000, 11_249, false), scoring::Choice::RejectNew);
+
+		let scoring = scoring.with_price_bump(1000);
+		assert_eq!(choose(&scoring, 10_000, 11_000, false), scoring::Choice::ReplaceOld);
+		assert_eq!(choose(&scoring, 10_000, 10_999, false), scoring::Choice::RejectNew);
+	}
+
+	#[test]
+	fn should_require_higher_price_without_price_bump() {
+		let scoring = NonceAndGasPrice::new(PrioritizationStrategy::GasPriceOnly).with_price_bump(0);
+		assert_eq!(choose(&scoring, 10_000, 10_001, false), scoring::Choice::ReplaceOld);
+		assert_eq!(choose(&scoring, 10_000, 10_000, false), scoring::Choice::RejectNew);
+	}
+
+	#[test]
+	fn should_not_require_price_bump_for_local_transactions() {
+		let scoring = NonceAndGasPrice::new(PrioritizationStrategy::GasPriceOnly);
+		assert_eq!(choose(&scoring, 10_000, 10_001, true), scoring::Choice::ReplaceOld);
+		assert_eq!(choose(&scoring, 10_000, 10_000, true), scoring::Choice::RejectNew);
 	}
 }
@@ -15,10 +15,14 @@
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
 use ethereum_types::U256;
+use futures::{Stream, sync::mpsc};
 use types::transaction::{self, PendingTransaction};
 use txpool;
 
-use pool::{verifier, TransactionQueue, PrioritizationStrategy, PendingSettings, PendingOrdering};
+use pool::{
+	verifier, TransactionQueue, PrioritizationStrategy, PendingSettings, PendingOrdering, Evictions,
+	PoolEvent, TxStatus,
+};
 
 pub mod tx;
 pub mod client;
@@ -44,6 +48,7 @@ fn new_queue() -> TransactionQueue {
 			block_gas_limit: 1_000_000.into(),
 			tx_gas_limit: 1_000_000.into(),
 			no_early_reject: false,
+			max_nonce_gap: U256::max_value(),
 		},
 		PrioritizationStrategy::GasPriceOnly,
 	)
@@ -62,6 +67,7 @@ fn should_return_correct_nonces_when_dropped_because_of_limit() {
 			block_gas_limit: 1_000_000.into(),
 			tx_gas_limit: 1_000_000.into(),
 			no_early_reject: false,
+			max_nonce_gap: U256::max_value(),
 		},
 		PrioritizationStrategy::GasPriceOnly,
 	);
@@ -73,7 +79,7 @@ fn should_return_correct_nonces_when_dropped_because_of_limit() {
 	let r1 = txq.import(TestClient::new(), vec![tx1].retracted());
 	let r2 = txq.import(TestClient::new(), vec![tx2].retracted());
 	assert_eq!(r1, vec![Ok(())]);
-	assert_eq!(r2, vec![Err(transaction::Error::LimitReached)]);
+	assert_eq!(r2, vec![Err(transaction::Error::SenderLimitReached { limit: 1 })]);
 	assert_eq!(txq.status().status.transaction_count, 1);
 
 	// then
@@ -116,6 +122,7 @@ fn should_never_drop_local_transactions_from_different_senders() {
 			block_gas_limit: 1_000_000.into(),
 			tx_gas_limit: 1_000_000.into(),
 			no_early_reject: false,
+			max_nonce_gap: U256::max_value(),
 		},
 		PrioritizationStrategy::GasPriceOnly,
 	);
@@ -255,6 +262,35 @@ fn should_import_transaction_below_min_gas_price_threshold_if_local() {
 	assert_eq!(txq.status().status.transaction_count, 1);
 }
 
+#[test]
+fn should_import_and_prioritize_transactions_of_priority_senders() {
+	// given
+	let txq = new_queue();
+	txq.set_verifier_options(verifier::Options {
+		minimal_gas_price: 3.into(),
+		..Default::default()
+	});
+	let tx = Tx::gas_price(1).signed();
+	let tx2 = Tx::gas_price(5).signed();
+	let (hash, hash2) = (tx.hash(), tx2.hash());
+	let res = txq.import(TestClient::new(), vec![tx.clone().unverified()]);
+	assert_eq!(res, vec![Err(transaction::Error::InsufficientGasPrice {
+		minimal: 3.into(),
+		got: 1.into(),
+	})]);
+
+	// when
+	txq.set_priority_senders(vec![tx.sender()].into_iter().collect());
+	let res = txq.import(TestClient::new(), vec![tx2.unverified(), tx.unverified()]);
+
+	// then
+	assert_eq!(res, vec![Ok(()), Ok(())]);
+	let top = txq.pending(TestClient::new(), PendingSettings::all_prioritized(0, 0));
+	assert_eq!(top[0].hash, hash);
+	assert_eq!(top[1].hash, hash2);
+	assert_eq!(top.len(), 2);
+}
+
 #[test]
 fn should_import_txs_from_same_sender() {
 	// given
@@ -490,6 +526,7 @@ fn should_prefer_current_transactions_when_hitting_the_limit() {
 			block_gas_limit: 1_000_000.into(),
 			tx_gas_limit: 1_000_000.into(),
 			no_early_reject: false,
+			max_nonce_gap: U256::max_value(),
 		},
 		PrioritizationStrategy::GasPriceOnly,
 	);
@@ -579,10 +616,10 @@ fn should_not_replace_same_transaction_if_the_fee_is_less_than_minimal_bump() {
 	let client = TestClient::new().with_balance(1_000_000);
 
 	// when
-	let res = txq.import(client.clone(), vec![tx, tx3].local());
+	let res = txq.import(client.clone(), vec![tx, tx3].unverified());
 	assert_eq!(res, vec![Ok(()), Ok(())]);
 
-	let res = txq.import(client.clone(), vec![tx2, tx4].local());
+	let res = txq.import(client.clone(), vec![tx2, tx4].unverified());
 
 	// then
 	assert_eq!(res, vec![Err(transaction::Error::TooCheapToReplace { prev: None, new: None }), Ok(())]);
@@ -591,6 +628,58 @@ fn should_not_replace_same_transaction_if_the_fee_is_less_than_minimal_bump() {
 	assert_eq!(txq.pending(client.clone(), PendingSettings::all_prioritized(0, 0))[1].signed().gas_price, U256::from(2));
 }
 
+#[test]
+fn should_notify_pool_events_of_replaced_transaction() {
+	// given
+	let txq = new_queue();
+	let (sender, receiver) = mpsc::unbounded();
+	txq.add_pool_events_listener(sender);
+	let (tx, tx2) = Tx::gas_price(1).signed_replacement();
+	let (tx3, tx4) = Tx::gas_price(20).signed_replacement();
+	let (hash, hash2, hash3, hash4) = (tx.hash(), tx2.hash(), tx3.hash(), tx4.hash());
+	let client = TestClient::new().with_balance(1_000_000);
+
+	// when
+	assert_eq!(txq.import(client.clone(), vec![tx, tx3].unverified()), vec![Ok(()), Ok(())]);
+	let res = txq.import(client.clone(), vec![tx2, tx4].unverified());
+	assert_eq!(res, vec![Ok(()), Err(transaction::Error::TooCheapToReplace { prev: None, new: None })]);
+	drop(txq);
+
+	// then
+	let events: Vec<PoolEvent> = receiver.wait()
+		.flat_map(|events| events.unwrap().to_vec())
+		.collect();
+	let statuses: Vec<_> = events.iter().map(|event| (event.hash, event.event)).collect();
+	assert_eq!(statuses, vec![
+		(hash, TxStatus::Added),
+		(hash3, TxStatus::Added),
+		(hash2, TxStatus::Added),
+		(hash, TxStatus::Dropped),
+		(hash4, TxStatus::Rejected),
+	]);
+	assert_eq!(events[3].reason, Some(format!("Replaced by {:?}", hash2)));
+	assert!(events[4].reason.is_some());
+}
+
+#[test]
+fn should_replace_local_transaction_with_any_higher_fee() {
+	// given
+	let txq = new_queue();
+	let (tx, tx2) = Tx::gas_price(20).signed_replacement();
+	let client = TestClient::new().with_balance(1_000_000);
+
+	// when
+	let res = txq.import(client.clone(), vec![tx].local());
+	assert_eq!(res, vec![Ok(())]);
+
+	let res = txq.import(client.clone(), vec![tx2].local());
+
+	// then
+	assert_eq!(res, vec![Ok(())]);
+	assert_eq!(txq.status().status.transaction_count, 1);
+	assert_eq!(txq.pending(client.clone(), PendingSettings::all_prioritized(0, 0))[0].signed().gas_price, U256::from(21));
+}
+
 #[test]
 fn should_return_none_when_transaction_from_given_address_does_not_exist() {
 	// given
@@ -903,6 +992,7 @@ fn should_include_local_transaction_to_a_full_pool() {
 			block_gas_limit: 1_000_000.into(),
 			tx_gas_limit: 1_000_000.into(),
 			no_early_reject: false,
+			max_nonce_gap: U256::max_value(),
 		},
 		PrioritizationStrategy::GasPriceOnly,
 	);
@@ -935,6 +1025,7 @@ fn should_avoid_verifying_transaction_already_in_pool() {
 			block_gas_limit: 1_000_000.into(),
 			tx_gas_limit: 1_000_000.into(),
 			no_early_reject: false,
+			max_nonce_gap: U256::max_value(),
 		},
 		PrioritizationStrategy::GasPriceOnly,
 	);
@@ -970,6 +1061,7 @@ fn should_avoid_reverifying_recently_rejected_transactions() {
 			block_gas_limit: 1_000_000.into(),
 			tx_gas_limit: 1_000_000.into(),
 			no_early_reject: false,
+			max_nonce_gap: U256::max_value(),
 		},
 		PrioritizationStrategy::GasPriceOnly,
 	);
@@ -1012,6 +1104,7 @@ fn should_reject_early_in_case_gas_price_is_less_than_min_effective() {
 			block_gas_limit: 1_000_000.into(),
 			tx_gas_limit: 1_000_000.into(),
 			no_early_reject: false,
+			max_nonce_gap: U256::max_value(),
 		},
 		PrioritizationStrategy::GasPriceOnly,
 	);
@@ -1051,6 +1144,7 @@ fn should_not_reject_early_in_case_gas_price_is_less_than_min_effective() {
 			block_gas_limit: 1_000_000.into(),
 			tx_gas_limit: 1_000_000.into(),
 			no_early_reject: true,
+			max_nonce_gap: U256::max_value(),
 		},
 		PrioritizationStrategy::GasPriceOnly,
 	);
@@ -1074,3 +1168,118 @@ fn should_not_reject_early_in_case_gas_price_is_less_than_min_effective() {
 	assert_eq!(txq.status().status.transaction_count, 2);
 	assert!(client.was_verification_triggered());
 }
+
+#[test]
+fn should_not_evict_other_senders_when_sender_limit_is_reached() {
+	// given
+	let txq = TransactionQueue::new(
+		txpool::Options {
+			max_count: 3,
+			max_per_sender: 2,
+			max_mem_usage: TEST_QUEUE_MAX_MEM
+		},
+		verifier::Options {
+			minimal_gas_price: 1.into(),
+			block_gas_limit: 1_000_000.into(),
+			tx_gas_limit: 1_000_000.into(),
+			no_early_reject: false,
+			max_nonce_gap: U256::max_value(),
+		},
+		PrioritizationStrategy::GasPriceOnly,
+	);
+	let other = Tx::gas_price(1).signed();
+	let other_hash = other.hash();
+	let (tx1, tx2, tx3) = Tx::gas_price(5).signed_triple();
+	let client = TestClient::new().with_balance(1_000_000);
+
+	let res = txq.import(client.clone(), vec![other, tx1, tx2].unverified());
+	assert_eq!(res, vec![Ok(()), Ok(()), Ok(())]);
+	assert_eq!(txq.status().status.transaction_count, 3);
+
+	// when
+	let res = txq.import(client, vec![tx3.unverified()]);
+
+	// then
+	assert_eq!(res, vec![Err(transaction::Error::SenderLimitReached { limit: 2 })]);
+	assert_eq!(txq.status().status.transaction_count, 3);
+	assert!(txq.find(&other_hash).is_some());
+}
+
+#[test]
+fn should_reject_non_local_transactions_with_too_high_nonce() {
+	// given
+	let txq = TransactionQueue::new(
+		txpool::Options::default(),
+		verifier::Options {
+			max_nonce_gap: 2.into(),
+			..Default::default()
+		},
+		PrioritizationStrategy::GasPriceOnly,
+	);
+	let tx1 = Tx { nonce: 125, ..Default::default() }.signed();
+	let tx2 = Tx { nonce: 126, ..Default::default() }.signed();
+	let tx3 = Tx { nonce: 126, ..Default::default() }.signed();
+
+	// when
+	let res = txq.import(TestClient::new(), vec![tx1, tx2].unverified());
+	let res2 = txq.import(TestClient::new(), vec![tx3.local()]);
+
+	// then
+	assert_eq!(res, vec![Ok(()), Err(transaction::Error::NonceTooHigh { limit: 125.into(), got: 126.into() })]);
+	assert_eq!(res2, vec![Ok(())]);
+	assert_eq!(txq.status().status.transaction_count, 2);
+}
+
+#[test]
+fn should_evict_cheapest_and_oldest_transactions_when_full() {
+	// given
+	let txq = TransactionQueue::new(
+		txpool::Options {
+			max_count: 3,
+			max_per_sender: 16,
+			max_mem_usage: TEST_QUEUE_MAX_MEM
+		},
+		verifier::Options {
+			minimal_gas_price: 1.into(),
+			block_gas_limit: 1_000_000.into(),
+			tx_gas_limit: 1_000_000.into(),
+			no_early_reject: false,
+			max_nonce_gap: U256::max_value(),
+		},
+		PrioritizationStrategy::GasPriceOnly,
+	);
+	let client = TestClient::new().with_balance(1_000_000);
+	let older = Tx::gas_price(2).signed();
+	let newer = Tx::gas_price(2).signed();
+	let future = Tx { nonce: 125, gas_price: 1, ..Default::default() }.signed();
+	let (older_hash, newer_hash, future_hash) = (older.hash(), newer.hash(), future.hash());
+	let res = txq.import(client.clone(), vec![older, newer, future].unverified());
+	assert_eq!(res, vec![Ok(()), Ok(()), Ok(())]);
+
+	// when
+	let res = txq.import(client.clone(), vec![Tx::gas_price(3).signed().unverified()]);
+
+	// then the cheapest one goes first
+	assert_eq!(res, vec![Ok(())]);
+	assert!(txq.find(&future_hash).is_none());
+	assert_eq!(txq.status().evictions, Evictions { future: 1, underpriced: 0 });
+
+	// when
+	let res = txq.import(client.clone(), vec![Tx::gas_price(3).signed().unverified()]);
+
+	// then the oldest one of the same price goes first
+	assert_eq!(res, vec![Ok(())]);
+	assert!(txq.find(&older_hash).is_none());
+	assert!(txq.find(&newer_hash).is_some());
+	assert_eq!(txq.status().evictions, Evictions { future: 1, underpriced: 1 });
+
+	// when
+	let future = Tx { nonce: 130, gas_price: 10, ..Default::default() }.signed();
+	let res = txq.import(client, vec![future.unverified()]);
+
+	// then executable transactions are not evicted in favour of future ones
+	assert!(res[0].is_err());
+	assert!(txq.find(&newer_hash).is_some());
+	assert_eq!(txq.status().status.transaction_count, 3);
+	assert_eq!(txq.status().evictions, Evictions { future: 1, underpriced: 1 });
+}
@@ -23,10 +23,11 @@
 //! stalled transactions.
 
 use std::cmp;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::atomic::{self, AtomicUsize};
 
-use ethereum_types::{U256, H256};
+use ethereum_types::{U256, H256, Address};
 use rlp::Encodable;
 use txpool;
 use types::transaction;
@@ -45,6 +46,8 @@ pub struct Options {
 	pub tx_gas_limit: U256,
 	/// Skip checks for early rejection, to make sure that local transactions are always imported.
 	pub no_early_reject: bool,
+	/// Maximal distance between the nonce of a non-local transaction and its sender's current nonce.
+	pub max_nonce_gap: U256,
 }
 
 #[cfg(test)]
@@ -55,6 +58,7 @@ impl Default for Options {
 			block_gas_limit: U256::max_value(),
 			tx_gas_limit: U256::max_value(),
 			no_early_reject: false,
+			max_nonce_gap: U256::max_value(),
 		}
 	}
 }
@@ -105,11 +109,11 @@ impl Transaction {
 		}
 	}
 
-	fn transaction(&self) -> &transaction::Transaction {
+	fn transaction(&self) -> &transaction::UnverifiedTransaction {
 		match *self {
-			Transaction::Unverified(ref tx) => &*tx,
-			Transaction::Retracted(ref tx) => &*tx,
-			Transaction::Local(ref tx) => &*tx,
+			Transaction::Unverified(ref tx) => tx,
+			Transaction::Retracted(ref tx) => tx,
+			Transaction::Local(ref tx) => tx,
 		}
 	}
 
@@ -137,6 +141,7 @@ pub struct Verifier<C, S, V> {
 	options: Options,
 	id: Arc<AtomicUsize>,
 	transaction_to_replace: Option<(S, Arc<V>)>,
+	priority_senders: HashSet<Address>,
 }
 
 impl<C, S, V> Verifier<C, S, V> {
@@ -152,8 +157,15 @@ impl<C, S, V> Verifier<C, S, V> {
 			options,
 			id,
 			transaction_to_replace,
+			priority_senders: HashSet::new(),
 		}
 	}
+
+	/// Treat transactions of given senders like local ones and give them the highest priority.
+	pub fn with_priority_senders(mut self, priority_senders: HashSet<Address>) -> Self {
+		self.priority_senders = priority_senders;
+		self
+	}
 }
 
 impl<C: Client> txpool::Verifier<Transaction> for Verifier<C, ::pool::scoring::NonceAndGasPrice, VerifiedTransaction> {
@@ -207,7 +219,8 @@ impl<C: Client> txpool::Verifier<Transaction> for Verifier<C, ::pool::scoring::N
 		//
 		// We're checking if the transaction is below configured minimal gas price
 		// or the effective minimal gas price in case the pool is full.
-		if !tx.gas_price().is_zero() && !is_own {
+		// The sender is not known yet, so there is no quick exit if any priority senders are configured.
+		if !tx.gas_price().is_zero() && !is_own && self.priority_senders.is_empty() {
 			if tx.gas_price() < &self.options.minimal_gas_price {
 				trace!(
 					target: "txqueue",
@@ -261,12 +274,14 @@ impl<C: Client> txpool::Verifier<Transaction> for Verifier<C, ::pool::scoring::N
 
 		let sender = transaction.sender();
 		let account_details = self.client.account_details(&sender);
+		let is_priority_sender = self.priority_senders.contains(&sender);
+		let is_local = is_own || account_details.is_local || is_priority_sender;
 
 		if transaction.gas_price < self.options.minimal_gas_price {
 			let transaction_type = self.client.transaction_type(&transaction);
 			if let TransactionType::Service = transaction_type {
 				debug!(target: "txqueue", "Service tx {:?} below minimal gas price accepted", hash);
-			} else if is_own || account_details.is_local {
+			} else if is_local {
 				info!(target: "own_tx", "Local tx {:?} below minimal gas price accepted", hash);
 			} else {
 				trace!(
@@ -321,7 +336,23 @@ impl<C: Client> txpool::Verifier<Transaction> for Verifier<C, ::pool::scoring::N
 			return Err(transaction::Error::Old);
 		}
 
-		let priority = match (is_own || account_details.is_local, is_retracted) {
+		let max_nonce = account_details.nonce.saturating_add(self.options.max_nonce_gap);
+		if transaction.nonce > max_nonce && !is_local {
+			debug!(
+				target: "txqueue",
+				"[{:?}] Rejected tx with nonce too far in the future ({} > {})",
+				hash,
+				transaction.nonce,
+				max_nonce,
+			);
+			return Err(transaction::Error::NonceTooHigh {
+				limit: max_nonce,
+				got: transaction.nonce,
+			});
+		}
+
+		let priority = match (is_local, is_retracted) {
+			_ if is_priority_sender => super::Priority::PrioritySender,
 			(true, _) => super::Priority::Local,
 			(false, false) => super::Priority::Regular,
 			(false, true) => super::Priority::Retracted,
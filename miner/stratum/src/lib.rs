@@ -31,7 +31,7 @@ extern crate parking_lot;
 mod traits;
 
 pub use traits::{
-	JobDispatcher, PushWorkHandler, Error, ServiceConfiguration,
+	JobDispatcher, PushWorkHandler, Error, ServiceConfiguration, Share, DifficultyConfig,
 };
 
 use jsonrpc_tcp_server::{
@@ -39,18 +39,24 @@ use jsonrpc_tcp_server::{
 	RequestContext, MetaExtractor, Dispatcher, PushMessageError,
 };
 use jsonrpc_core::{MetaIoHandler, Params, to_value, Value, Metadata, Compatibility, IoDelegate};
+use std::cmp;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
 use std::net::SocketAddr;
 use std::collections::{HashSet, HashMap};
 use hash::keccak;
-use ethereum_types::H256;
+use ethereum_types::{H256, U256};
 use parking_lot::RwLock;
 
 type RpcResult = Result<jsonrpc_core::Value, jsonrpc_core::Error>;
 
 const NOTIFY_COUNTER_INITIAL: u32 = 16;
 
+/// Maximal factor the difficulty of a session changes by in a single retarget
+const MAX_RETARGET_FACTOR: u32 = 4;
+
 /// Container which owns rpc server and stratum implementation
 pub struct Stratum {
 	/// RPC server
@@ -72,6 +78,7 @@ impl Stratum {
 		addr: &SocketAddr,
 		dispatcher: Arc<dyn JobDispatcher>,
 		secret: Option<H256>,
+		difficulty: Option<DifficultyConfig>,
 	) -> Result<Arc<Stratum>, Error> {
 
 		let implementation = Arc::new(StratumImpl {
@@ -81,12 +88,16 @@ impl Stratum {
 			workers: Arc::new(RwLock::default()),
 			secret,
 			notify_counter: RwLock::new(NOTIFY_COUNTER_INITIAL),
+			sessions: RwLock::default(),
+			difficulty,
+			extranonce_counter: AtomicUsize::new(0),
 		});
 
 		let mut delegate = IoDelegate::<StratumImpl, SocketMetadata>::new(implementation.clone());
 		delegate.add_method_with_meta("mining.subscribe", StratumImpl::subscribe);
 		delegate.add_method_with_meta("mining.authorize", StratumImpl::authorize);
 		delegate.add_method_with_meta("mining.submit", StratumImpl::submit);
+		delegate.add_method_with_meta("mining.extranonce.subscribe", StratumImpl::extranonce_subscribe);
 		let mut handler = MetaIoHandler::<SocketMetadata>::with_compatibility(Compatibility::Both);
 		handler.extend_with(delegate);
 
@@ -131,6 +142,26 @@ struct StratumImpl {
 	secret: Option<H256>,
 	/// Dispatch notify counter
 	notify_counter: RwLock<u32>,
+	/// Sessions of connected workers
+	sessions: RwLock<HashMap<SocketAddr, Session>>,
+	/// Per-session difficulty adjustment, if enabled
+	difficulty: Option<DifficultyConfig>,
+	/// Counter used to assign extranonces to sessions
+	extranonce_counter: AtomicUsize,
+}
+
+/// State of a single worker connection
+struct Session {
+	/// Difficulty shares of the session have to satisfy
+	difficulty: U256,
+	/// Shares accepted since the last retarget
+	shares: u64,
+	/// Time of the last retarget
+	retargeted_at: Instant,
+	/// Nonce prefix assigned to the session
+	extranonce: String,
+	/// Whether the worker subscribed to the extranonce, so its nonces have to start with it
+	extranonce_subscribed: bool,
 }
 
 impl StratumImpl {
@@ -140,6 +171,7 @@ impl StratumImpl {
 
 		self.subscribers.write().push(meta.addr().clone());
 		self.job_queue.write().insert(meta.addr().clone());
+		self.with_session(meta.addr(), |_| ());
 		trace!(target: "stratum", "Subscription request from {:?}", meta.addr());
 
 		Ok(match self.dispatcher.initial() {
@@ -165,36 +197,147 @@ impl StratumImpl {
 			}
 			trace!(target: "stratum", "New worker #{} registered", worker_id);
 			self.workers.write().insert(meta.addr().clone(), worker_id);
+
+			if self.difficulty.is_some() {
+				let difficulty = self.with_session(meta.addr(), |session| session.difficulty);
+				self.notify(&meta, "mining.set_difficulty", format!("[{}]", difficulty));
+			}
 			to_value(true)
 		}).map(|v| v.expect("Only true/false is returned and it's always serializable; qed"))
 	}
 
+	/// rpc method `mining.extranonce.subscribe`
+	fn extranonce_subscribe(&self, _params: Params, meta: SocketMetadata) -> RpcResult {
+		let extranonce = self.with_session(meta.addr(), |session| {
+			session.extranonce_subscribed = true;
+			session.extranonce.clone()
+		});
+		trace!(target: "stratum", "Extranonce {} subscribed by {:?}", extranonce, meta.addr());
+
+		self.notify(&meta, "mining.set_extranonce", format!("[\"{}\"]", extranonce));
+		Ok(to_value(true).expect("Only true/false is returned and it's always serializable; qed"))
+	}
+
 	/// rpc method `mining.submit`
 	fn submit(&self, params: Params, meta: SocketMetadata) -> RpcResult {
-		Ok(match params {
+		let payload = match params {
 			Params::Array(vals) => {
 				// first two elements are service messages (worker_id & job_id)
-				match self.dispatcher.submit(vals.iter().skip(2)
+				vals.iter().skip(2)
 					.filter_map(|val| match *val {
 						Value::String(ref s) => Some(s.to_owned()),
 						_ => None
 					})
-					.collect::<Vec<String>>()) {
-					Ok(()) => {
-						self.update_peers(&meta.tcp_dispatcher.expect("tcp_dispatcher is always initialized; qed"));
-						to_value(true)
-					},
-					Err(submit_err) => {
-						warn!("Error while submitting share: {:?}", submit_err);
-						to_value(false)
-					}
-				}
+					.collect::<Vec<String>>()
 			},
 			_ => {
 				trace!(target: "stratum", "Invalid submit work format {:?}", params);
-				to_value(false)
+				return Ok(to_value(false).expect("Only true/false is returned and it's always serializable; qed"));
+			}
+		};
+
+		Ok(to_value(self.submit_share(payload, &meta)).expect("Only true/false is returned and it's always serializable; qed"))
+	}
+
+	/// Checks the share against the session and, with difficulty adjustment enabled,
+	/// forwards it to the dispatcher only if it's a solution for the whole block.
+	fn submit_share(&self, payload: Vec<String>, meta: &SocketMetadata) -> bool {
+		if !self.has_session_extranonce(meta.addr(), &payload) {
+			trace!(target: "stratum", "Share from {:?} doesn't use the assigned extranonce", meta.addr());
+			return false;
+		}
+
+		if let Some(ref config) = self.difficulty {
+			if let Some(share) = self.dispatcher.share(&payload) {
+				if !self.record_share(meta, &share, config) {
+					trace!(target: "stratum", "Share from {:?} below the session difficulty", meta.addr());
+					return false;
+				}
+				if share.difficulty < share.block_difficulty {
+					return true;
+				}
 			}
-		}.expect("Only true/false is returned and it's always serializable; qed"))
+		}
+
+		match self.dispatcher.submit(payload) {
+			Ok(()) => {
+				self.update_peers(meta.tcp_dispatcher.as_ref().expect("tcp_dispatcher is always initialized; qed"));
+				true
+			},
+			Err(submit_err) => {
+				warn!("Error while submitting share: {:?}", submit_err);
+				false
+			}
+		}
+	}
+
+	/// Checks that the nonce of the share starts with the session extranonce, if the worker subscribed to it
+	fn has_session_extranonce(&self, addr: &SocketAddr, payload: &[String]) -> bool {
+		match self.sessions.read().get(addr) {
+			Some(session) if session.extranonce_subscribed => payload.first()
+				.map_or(false, |nonce| nonce.trim_start_matches("0x").starts_with(&session.extranonce)),
+			_ => true,
+		}
+	}
+
+	/// Counts the share towards the share rate of the session and retargets its difficulty when due.
+	///
+	/// Returns `false` if the share doesn't satisfy the session difficulty.
+	fn record_share(&self, meta: &SocketMetadata, share: &Share, config: &DifficultyConfig) -> bool {
+		let retargeted = self.with_session(meta.addr(), |session| {
+			if share.difficulty < session.difficulty {
+				return Err(());
+			}
+			session.shares += 1;
+
+			let elapsed = session.retargeted_at.elapsed();
+			if elapsed < config.retarget_interval {
+				return Ok(None);
+			}
+			let difficulty = retarget(session.difficulty, session.shares, elapsed, config.shares_per_minute);
+			session.shares = 0;
+			session.retargeted_at = Instant::now();
+			if difficulty == session.difficulty {
+				return Ok(None);
+			}
+			session.difficulty = difficulty;
+			Ok(Some(difficulty))
+		});
+
+		match retargeted {
+			Err(()) => false,
+			Ok(difficulty) => {
+				if let Some(difficulty) = difficulty {
+					trace!(target: "stratum", "Retargeted difficulty of {:?} to {}", meta.addr(), difficulty);
+					self.notify(meta, "mining.set_difficulty", format!("[{}]", difficulty));
+				}
+				true
+			}
+		}
+	}
+
+	fn with_session<F, R>(&self, addr: &SocketAddr, f: F) -> R where F: FnOnce(&mut Session) -> R {
+		let mut sessions = self.sessions.write();
+		let session = sessions.entry(*addr).or_insert_with(|| Session {
+			difficulty: self.difficulty.as_ref().map_or_else(U256::zero, |config| config.initial),
+			shares: 0,
+			retargeted_at: Instant::now(),
+			extranonce: format!("{:04x}", self.extranonce_counter.fetch_add(1, Ordering::SeqCst) & 0xffff),
+			extranonce_subscribed: false,
+		});
+		f(session)
+	}
+
+	/// Pushes a notification to the peer of the request
+	fn notify(&self, meta: &SocketMetadata, method: &str, params: String) {
+		let tcp_dispatcher = match meta.tcp_dispatcher {
+			Some(ref tcp_dispatcher) => tcp_dispatcher,
+			None => return,
+		};
+		let msg = format!("{{ \"id\": null, \"method\": \"{}\", \"params\": {} }}", method, params);
+		if let Err(e) = tcp_dispatcher.push_message(meta.addr(), msg) {
+			warn!(target: "stratum", "Failed to push {} to {}: {:?}", method, meta.addr(), e);
+		}
 	}
 
 	/// Helper method
@@ -238,13 +381,27 @@ impl StratumImpl {
 
 		if !hup_peers.is_empty() {
 			let mut workers = self.workers.write();
+			let mut sessions = self.sessions.write();
 			for hup_peer in hup_peers {
 				workers.remove(&hup_peer);
+				sessions.remove(&hup_peer);
 			}
 		}
 	}
 }
 
+/// Scales `difficulty` by the ratio of the observed share rate to the targeted one,
+/// changing it by at most `MAX_RETARGET_FACTOR` in either direction.
+fn retarget(difficulty: U256, shares: u64, elapsed: Duration, shares_per_minute: u32) -> U256 {
+	let elapsed_ms = cmp::max(elapsed.as_secs() * 1000 + u64::from(elapsed.subsec_millis()), 1);
+	let target = difficulty.saturating_mul(U256::from(shares) * U256::from(60_000))
+		/ (U256::from(elapsed_ms) * U256::from(cmp::max(shares_per_minute, 1)));
+
+	let min = cmp::max(difficulty / MAX_RETARGET_FACTOR, U256::one());
+	let max = difficulty.saturating_mul(MAX_RETARGET_FACTOR.into());
+	cmp::min(cmp::max(target, min), max)
+}
+
 #[derive(Clone)]
 pub struct SocketMetadata {
 	addr: SocketAddr,
@@ -297,6 +454,8 @@ mod tests {
 	use super::*;
 	use std::net::{SocketAddr, Shutdown};
 	use std::sync::Arc;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use std::time::Duration;
 
 	use tokio::{io, runtime::Runtime, timer::timeout::{self, Timeout}, net::TcpStream};
 	use jsonrpc_core::futures::{Future, future};
@@ -333,7 +492,7 @@ mod tests {
 
 	#[test]
 	fn can_be_started() {
-		let stratum = Stratum::start(&"127.0.0.1:19980".parse().unwrap(), Arc::new(VoidManager), None);
+		let stratum = Stratum::start(&"127.0.0.1:19980".parse().unwrap(), Arc::new(VoidManager), None, None);
 		assert!(stratum.is_ok());
 	}
 
@@ -342,7 +501,7 @@ mod tests {
 		let _ = ::env_logger::try_init();
 
 		let addr = "127.0.0.1:19985".parse().unwrap();
-		let stratum = Stratum::start(&addr, Arc::new(VoidManager), None).unwrap();
+		let stratum = Stratum::start(&addr, Arc::new(VoidManager), None, None).unwrap();
 		let request = r#"{"jsonrpc": "2.0", "method": "mining.subscribe", "params": [], "id": 1}"#;
 		dummy_request(&addr, request);
 		assert_eq!(1, stratum.implementation.subscribers.read().len());
@@ -387,7 +546,7 @@ mod tests {
 	#[test]
 	fn receives_initial_payload() {
 		let addr = "127.0.0.1:19975".parse().unwrap();
-		let _stratum = Stratum::start(&addr, DummyManager::new(), None).expect("There should be no error starting stratum");
+		let _stratum = Stratum::start(&addr, DummyManager::new(), None, None).expect("There should be no error starting stratum");
 		let request = r#"{"jsonrpc": "2.0", "method": "mining.subscribe", "params": [], "id": 2}"#;
 
 		let response = String::from_utf8(dummy_request(&addr, request)).unwrap();
@@ -401,7 +560,8 @@ mod tests {
 		let stratum = Stratum::start(
 			&addr,
 			Arc::new(DummyManager::build().of_initial(r#"["dummy autorize payload"]"#)),
-			None
+			None,
+			None,
 		).expect("There should be no error starting stratum");
 
 		let request = r#"{"jsonrpc": "2.0", "method": "mining.authorize", "params": ["miner1", ""], "id": 1}"#;
@@ -419,7 +579,8 @@ mod tests {
 		let stratum = Stratum::start(
 			&addr,
 			Arc::new(DummyManager::build().of_initial(r#"["dummy autorize payload"]"#)),
-			None
+			None,
+			None,
 		).expect("There should be no error starting stratum");
 
 		let mut auth_request =
@@ -471,6 +632,121 @@ mod tests {
 			response);
 	}
 
+	/// Dispatcher reading the difficulty of a share from its nonce
+	struct ShareManager {
+		block_difficulty: U256,
+		submitted: AtomicUsize,
+	}
+
+	impl ShareManager {
+		fn new(block_difficulty: u64) -> Arc<ShareManager> {
+			Arc::new(ShareManager {
+				block_difficulty: block_difficulty.into(),
+				submitted: AtomicUsize::new(0),
+			})
+		}
+	}
+
+	impl JobDispatcher for ShareManager {
+		fn share(&self, payload: &[String]) -> Option<Share> {
+			let difficulty = payload.first()?.trim_start_matches("0x").parse::<u64>().ok()?;
+			Some(Share {
+				difficulty: difficulty.into(),
+				block_difficulty: self.block_difficulty,
+			})
+		}
+
+		fn submit(&self, _payload: Vec<String>) -> Result<(), Error> {
+			self.submitted.fetch_add(1, Ordering::SeqCst);
+			Ok(())
+		}
+	}
+
+	fn scripted_session(addr: &SocketAddr, requests: &[&str]) -> Vec<String> {
+		let response = String::from_utf8(dummy_request(addr, &requests.join("\n"))).unwrap();
+		response.lines().map(str::to_owned).collect()
+	}
+
+	fn difficulty_config(initial: u64, retarget_interval: Duration) -> Option<DifficultyConfig> {
+		Some(DifficultyConfig {
+			initial: initial.into(),
+			shares_per_minute: 1,
+			retarget_interval,
+		})
+	}
+
+	#[test]
+	fn assigns_and_enforces_extranonce() {
+		let addr = "127.0.0.1:19960".parse().unwrap();
+		let manager = ShareManager::new(1);
+		let _stratum = Stratum::start(&addr, manager.clone(), None, None).unwrap();
+
+		let lines = scripted_session(&addr, &[
+			r#"{"jsonrpc": "2.0", "method": "mining.subscribe", "params": [], "id": 1}"#,
+			r#"{"jsonrpc": "2.0", "method": "mining.extranonce.subscribe", "params": [], "id": 2}"#,
+			r#"{"jsonrpc": "2.0", "method": "mining.submit", "params": ["miner1", "job1", "0x00001"], "id": 3}"#,
+			r#"{"jsonrpc": "2.0", "method": "mining.submit", "params": ["miner1", "job1", "0x10001"], "id": 4}"#,
+		]);
+
+		assert!(lines.contains(&r#"{ "id": null, "method": "mining.set_extranonce", "params": ["0000"] }"#.to_owned()));
+		assert!(lines.contains(&r#"{"jsonrpc":"2.0","result":true,"id":2}"#.to_owned()));
+		assert!(lines.contains(&r#"{"jsonrpc":"2.0","result":true,"id":3}"#.to_owned()));
+		assert!(lines.contains(&r#"{"jsonrpc":"2.0","result":false,"id":4}"#.to_owned()));
+		assert_eq!(manager.submitted.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn validates_shares_against_session_difficulty() {
+		let addr = "127.0.0.1:19965".parse().unwrap();
+		let manager = ShareManager::new(1000);
+		let config = difficulty_config(100, Duration::from_secs(3600));
+		let _stratum = Stratum::start(&addr, manager.clone(), None, config).unwrap();
+
+		let lines = scripted_session(&addr, &[
+			r#"{"jsonrpc": "2.0", "method": "mining.authorize", "params": ["miner1", ""], "id": 1}"#,
+			r#"{"jsonrpc": "2.0", "method": "mining.submit", "params": ["miner1", "job1", "50"], "id": 2}"#,
+			r#"{"jsonrpc": "2.0", "method": "mining.submit", "params": ["miner1", "job1", "500"], "id": 3}"#,
+			r#"{"jsonrpc": "2.0", "method": "mining.submit", "params": ["miner1", "job1", "5000"], "id": 4}"#,
+		]);
+
+		assert!(lines.contains(&r#"{ "id": null, "method": "mining.set_difficulty", "params": [100] }"#.to_owned()));
+		assert!(lines.contains(&r#"{"jsonrpc":"2.0","result":false,"id":2}"#.to_owned()));
+		assert!(lines.contains(&r#"{"jsonrpc":"2.0","result":true,"id":3}"#.to_owned()));
+		assert!(lines.contains(&r#"{"jsonrpc":"2.0","result":true,"id":4}"#.to_owned()));
+		// only the solution for the whole block is forwarded
+		assert_eq!(manager.submitted.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn retargets_difficulty_towards_share_rate() {
+		let addr = "127.0.0.1:19955".parse().unwrap();
+		let manager = ShareManager::new(1000);
+		let config = difficulty_config(100, Duration::from_secs(0));
+		let _stratum = Stratum::start(&addr, manager.clone(), None, config).unwrap();
+
+		let lines = scripted_session(&addr, &[
+			r#"{"jsonrpc": "2.0", "method": "mining.authorize", "params": ["miner1", ""], "id": 1}"#,
+			r#"{"jsonrpc": "2.0", "method": "mining.submit", "params": ["miner1", "job1", "150"], "id": 2}"#,
+			r#"{"jsonrpc": "2.0", "method": "mining.submit", "params": ["miner1", "job1", "300"], "id": 3}"#,
+		]);
+
+		// way above the targeted share rate, so the difficulty is raised as much as allowed
+		assert!(lines.contains(&r#"{ "id": null, "method": "mining.set_difficulty", "params": [100] }"#.to_owned()));
+		assert!(lines.contains(&r#"{ "id": null, "method": "mining.set_difficulty", "params": [400] }"#.to_owned()));
+		assert!(lines.contains(&r#"{"jsonrpc":"2.0","result":true,"id":2}"#.to_owned()));
+		assert!(lines.contains(&r#"{"jsonrpc":"2.0","result":false,"id":3}"#.to_owned()));
+	}
+
+	#[test]
+	fn retarget_is_bounded() {
+		let minute = Duration::from_secs(60);
+		assert_eq!(retarget(1000.into(), 20, minute, 20), 1000.into());
+		assert_eq!(retarget(1000.into(), 40, minute, 20), 2000.into());
+		assert_eq!(retarget(1000.into(), 1000, minute, 20), 4000.into());
+		assert_eq!(retarget(1000.into(), 0, minute, 20), 250.into());
+		assert_eq!(retarget(1.into(), 0, minute, 20), 1.into());
+	}
+
 	#[test]
 	fn jsonprc_server_is_send_and_sync() {
 		fn is_send_and_sync<T: Send + Sync>() {}
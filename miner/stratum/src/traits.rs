@@ -16,7 +16,8 @@
 
 use std;
 use std::error::Error as StdError;
-use ethereum_types::H256;
+use std::time::Duration;
+use ethereum_types::{H256, U256};
 use jsonrpc_tcp_server::PushMessageError;
 
 #[derive(Debug, Clone)]
@@ -48,10 +49,42 @@ pub trait JobDispatcher: Send + Sync {
 	fn difficulty(&self) -> Option<String> { None }
 	// json for job update given worker_id (payload manager should split job!)
 	fn job(&self) -> Option<String> { None }
+	// difficulties of a submitted solution, used to check shares when difficulty adjustment is enabled
+	fn share(&self, _payload: &[String]) -> Option<Share> { None }
 	// miner job result
 	fn submit(&self, payload: Vec<String>) -> Result<(), Error>;
 }
 
+/// Difficulties of a solution submitted by a worker
+#[derive(Debug, Clone, PartialEq)]
+pub struct Share {
+	/// Difficulty the solution satisfies
+	pub difficulty: U256,
+	/// Difficulty required to seal the block the solution is for
+	pub block_difficulty: U256,
+}
+
+/// Settings of the per-session difficulty adjustment
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifficultyConfig {
+	/// Difficulty of shares new sessions start with
+	pub initial: U256,
+	/// Number of shares per minute each session's difficulty is retargeted towards
+	pub shares_per_minute: u32,
+	/// Time after which the difficulty of a session is retargeted
+	pub retarget_interval: Duration,
+}
+
+impl Default for DifficultyConfig {
+	fn default() -> Self {
+		DifficultyConfig {
+			initial: 4_000_000_000u64.into(),
+			shares_per_minute: 20,
+			retarget_interval: Duration::from_secs(60),
+		}
+	}
+}
+
 /// Interface that can handle requests to push job for workers
 pub trait PushWorkHandler: Send + Sync {
 	/// push the same work package for all workers (`payload`: json of pow-specific set of work specification)
@@ -22,11 +22,9 @@ use std::thread::sleep;
 use std::sync::Arc;
 
 use rustc_hex::FromHex;
-use hash::{keccak, KECCAK_NULL_RLP};
-use ethereum_types::{U256, H256, Address};
-use bytes::ToPretty;
+use ethereum_types::{U256, H256};
 use rlp::PayloadInfo;
-use client_traits::{BlockChainReset, Nonce, Balance, BlockChainClient, ImportExportBlocks};
+use client_traits::{BlockChainReset, BlockChainClient, ImportExportBlocks};
 use ethcore::{
 	client::{DatabaseCompactionProfile, VMType},
 	miner::Miner,
@@ -38,15 +36,18 @@ use params::{SpecType, Pruning, Switch, tracing_switch_to_bool, fatdb_switch_to_
 use helpers::{to_client_config, execute_upgrades};
 use dir::Directories;
 use user_defaults::UserDefaults;
+use journaldb::Algorithm;
 use ethcore_private_tx;
 use db;
+use state_dump;
 use ansi_term::Colour;
 use types::{
 	ids::BlockId,
 	errors::{ImportError, EthcoreError},
-	client_types::{Mode, StateResult},
+	client_types::Mode,
 };
 use types::data_format::DataFormat;
+use types::state_dump::{StateDumpFormat, StateDumpOptions};
 use verification::queue::VerifierSettings;
 
 #[derive(Debug, PartialEq)]
@@ -55,7 +56,9 @@ pub enum BlockchainCmd {
 	Import(ImportBlockchain),
 	Export(ExportBlockchain),
 	ExportState(ExportState),
-	Reset(ResetBlockchain)
+	ImportState(ImportState),
+	Reset(ResetBlockchain),
+	MigratePruning(MigratePruning),
 }
 
 #[derive(Debug, PartialEq)]
@@ -72,6 +75,15 @@ pub struct ResetBlockchain {
 	pub num: u32,
 }
 
+#[derive(Debug, PartialEq)]
+pub struct MigratePruning {
+	pub spec: SpecType,
+	pub dirs: Directories,
+	pub cache_config: CacheConfig,
+	pub compaction: DatabaseCompactionProfile,
+	pub to: Algorithm,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct KillBlockchain {
 	pub spec: SpecType,
@@ -125,7 +137,7 @@ pub struct ExportState {
 	pub cache_config: CacheConfig,
 	pub dirs: Directories,
 	pub file_path: Option<String>,
-	pub format: Option<DataFormat>,
+	pub format: StateDumpFormat,
 	pub pruning: Pruning,
 	pub pruning_history: u64,
 	pub pruning_memory: usize,
@@ -137,9 +149,16 @@ pub struct ExportState {
 	pub code: bool,
 	pub min_balance: Option<U256>,
 	pub max_balance: Option<U256>,
+	pub from: Option<H256>,
 	pub max_round_blocks_to_import: usize,
 }
 
+#[derive(Debug, PartialEq)]
+pub struct ImportState {
+	pub file_path: Option<String>,
+	pub format: StateDumpFormat,
+}
+
 pub fn execute(cmd: BlockchainCmd) -> Result<(), String> {
 	match cmd {
 		BlockchainCmd::Kill(kill_cmd) => kill_db(kill_cmd),
@@ -152,7 +171,9 @@ pub fn execute(cmd: BlockchainCmd) -> Result<(), String> {
 		}
 		BlockchainCmd::Export(export_cmd) => execute_export(export_cmd),
 		BlockchainCmd::ExportState(export_cmd) => execute_export_state(export_cmd),
+		BlockchainCmd::ImportState(import_cmd) => execute_import_state(import_cmd),
 		BlockchainCmd::Reset(reset_cmd) => execute_reset(reset_cmd),
+		BlockchainCmd::MigratePruning(migrate_cmd) => execute_migrate_pruning(migrate_cmd),
 	}
 }
 
@@ -215,6 +236,8 @@ fn execute_import_light(cmd: ImportBlockchain) -> Result<(), String> {
 	let service = LightClientService::start(config, &spec, fetch, db, cache)
 		.map_err(|e| format!("Failed to start client: {}", e))?;
 
+	let eip1559_transition = spec.params().eip1559_transition;
+
 	// free up the spec in memory.
 	drop(spec);
 
@@ -244,7 +267,8 @@ fn execute_import_light(cmd: ImportBlockchain) -> Result<(), String> {
 	let do_import = |bytes: Vec<u8>| {
 		while client.queue_info().is_full() { sleep(Duration::from_secs(1)); }
 
-		let header: ::types::header::Header = ::rlp::Rlp::new(&bytes).val_at(0)
+		let header = ::rlp::Rlp::new(&bytes).at(0)
+			.and_then(|header| ::types::header::Header::decode_rlp(&header, eip1559_transition))
 			.map_err(|e| format!("Bad block: {}", e))?;
 
 		if client.best_block_header().number() >= header.number() { return Ok(()) }
@@ -566,75 +590,53 @@ fn execute_export_state(cmd: ExportState) -> Result<(), String> {
 	let client = service.client();
 
 	let mut out: Box<dyn io::Write> = match cmd.file_path {
-		Some(f) => Box::new(fs::File::create(&f).map_err(|_| format!("Cannot write to file given: {}", f))?),
+		Some(f) => Box::new(io::BufWriter::new(fs::File::create(&f).map_err(|_| format!("Cannot write to file given: {}", f))?)),
 		None => Box::new(io::stdout()),
 	};
 
-	let mut last: Option<Address> = None;
-	let at = cmd.at;
-	let mut i = 0usize;
-
-	out.write_fmt(format_args!("{{ \"state\": {{", )).expect("Couldn't write to stream.");
-	loop {
-		let accounts = client.list_accounts(at, last.as_ref(), 1000).ok_or("Specified block not found")?;
-		if accounts.is_empty() {
-			break;
+	let options = StateDumpOptions {
+		from: cmd.from,
+		min_balance: cmd.min_balance,
+		max_balance: cmd.max_balance,
+		code: cmd.code,
+		storage: cmd.storage,
+	};
+	let format = cmd.format;
+	let mut exported = 0usize;
+	let mut last = None;
+	let result = client.dump_state(cmd.at, &options, |account| {
+		let address_hash = account.address_hash;
+		state_dump::write_account(&mut *out, format, &account)
+			.map_err(|e| format!("Error writing account {:?}: {}", address_hash, e))?;
+		exported += 1;
+		last = Some(address_hash);
+		if exported % 10000 == 0 {
+			info!("Exported {} accounts, last {:?}", exported, address_hash);
 		}
+		Ok(())
+	}).and_then(|_| out.flush().map_err(|e| format!("Error writing state: {}", e)));
 
-		for account in accounts.into_iter() {
-			let balance = client.balance(&account, at.into()).unwrap_or_else(U256::zero);
-			if cmd.min_balance.map_or(false, |m| balance < m) || cmd.max_balance.map_or(false, |m| balance > m) {
-				last = Some(account);
-				continue; //filtered out
-			}
-
-			if i != 0 {
-				out.write(b",").expect("Write error");
-			}
-			out.write_fmt(format_args!("\n\"0x{:x}\": {{\"balance\": \"{:x}\", \"nonce\": \"{:x}\"", account, balance, client.nonce(&account, at).unwrap_or_else(U256::zero))).expect("Write error");
-			let code = match client.code(&account, at.into()) {
-				StateResult::Missing => Vec::new(),
-				StateResult::Some(t) => t.unwrap_or_else(Vec::new),
-			};
-			if !code.is_empty() {
-				out.write_fmt(format_args!(", \"code_hash\": \"0x{:x}\"", keccak(&code))).expect("Write error");
-				if cmd.code {
-					out.write_fmt(format_args!(", \"code\": \"{}\"", code.to_hex())).expect("Write error");
-				}
-			}
-			let storage_root = client.storage_root(&account, at).unwrap_or(KECCAK_NULL_RLP);
-			if storage_root != KECCAK_NULL_RLP {
-				out.write_fmt(format_args!(", \"storage_root\": \"0x{:x}\"", storage_root)).expect("Write error");
-				if cmd.storage {
-					out.write_fmt(format_args!(", \"storage\": {{")).expect("Write error");
-					let mut last_storage: Option<H256> = None;
-					loop {
-						let keys = client.list_storage(at, &account, last_storage.as_ref(), Some(1000)).ok_or("Specified block not found")?;
-						if keys.is_empty() {
-							break;
-						}
-
-						for key in keys.into_iter() {
-							if last_storage.is_some() {
-								out.write(b",").expect("Write error");
-							}
-							out.write_fmt(format_args!("\n\t\"0x{:x}\": \"0x{:x}\"", key, client.storage_at(&account, &key, at.into()).unwrap_or_else(Default::default))).expect("Write error");
-							last_storage = Some(key);
-						}
-					}
-					out.write(b"\n}").expect("Write error");
-				}
-			}
-			out.write(b"}").expect("Write error");
-			i += 1;
-			if i % 10000 == 0 {
-				info!("Account #{}", i);
-			}
-			last = Some(account);
+	if let Err(e) = result {
+		if let Some(last) = last {
+			warn!("Export interrupted after {} accounts. The last exported account is {:?}, resume with --from set to the account after it.", exported, last);
 		}
+		return Err(e);
 	}
-	out.write_fmt(format_args!("\n}}}}")).expect("Write error");
-	info!("Export completed.");
+
+	info!("Export completed: {} accounts.", exported);
+	Ok(())
+}
+
+fn execute_import_state(cmd: ImportState) -> Result<(), String> {
+	let input: Box<dyn io::BufRead> = match cmd.file_path {
+		Some(f) => Box::new(BufReader::new(fs::File::open(&f).map_err(|_| format!("Cannot open given file: {}", f))?)),
+		None => Box::new(BufReader::new(io::stdin())),
+	};
+
+	let stdout = io::stdout();
+	let mut out = stdout.lock();
+	let accounts = state_dump::write_genesis_accounts(&mut out, state_dump::read_accounts(input, cmd.format))?;
+	info!("Converted {} accounts.", accounts);
 	Ok(())
 }
 
@@ -660,6 +662,47 @@ fn execute_reset(cmd: ResetBlockchain) -> Result<(), String> {
 	Ok(())
 }
 
+fn execute_migrate_pruning(cmd: MigratePruning) -> Result<(), String> {
+	let spec = cmd.spec.spec(&cmd.dirs.cache)?;
+	let genesis_hash = spec.genesis_header().hash();
+	let genesis = spec.genesis_block();
+	let db_dirs = cmd.dirs.database(genesis_hash, None, spec.data_dir);
+	let user_defaults_path = db_dirs.user_defaults_path();
+	let mut user_defaults = UserDefaults::load(&user_defaults_path)?;
+
+	if user_defaults.pruning != Algorithm::Archive || cmd.to != Algorithm::OverlayRecent {
+		return Err(format!(
+			"Migrating the database from {} to {} pruning is not supported. Only archive databases can be migrated, and only to fast pruning.",
+			user_defaults.pruning.as_str(), cmd.to.as_str(),
+		));
+	}
+
+	let target = db_dirs.db_path(cmd.to);
+	if target.exists() {
+		return Err(format!("A {} database already exists at {}. Remove it with `parity db kill --pruning {}` first.",
+			cmd.to.as_str(), target.display(), cmd.to.as_str()));
+	}
+
+	// build the new database next to the target and only move it into place once verified,
+	// so that an interrupted migration never leaves a half-written database behind.
+	let staging = target.with_extension("migrating");
+	db::migrate_pruning(
+		&db_dirs.client_path(Algorithm::Archive),
+		&staging.join("db"),
+		&genesis,
+		&cmd.cache_config,
+		&cmd.compaction,
+	)?;
+	fs::rename(&staging, &target).map_err(|e| format!("Error moving migrated database into place: {}", e))?;
+
+	user_defaults.pruning = cmd.to;
+	user_defaults.save(&user_defaults_path)?;
+	info!("{}", Colour::Green.bold().paint(format!("Database migrated to {} pruning.", cmd.to.as_str())));
+	info!("The archive database at {} was left untouched and can be removed with `parity db kill --pruning archive`.",
+		db_dirs.db_path(Algorithm::Archive).display());
+	Ok(())
+}
+
 pub fn kill_db(cmd: KillBlockchain) -> Result<(), String> {
 	let spec = cmd.spec.spec(&cmd.dirs.cache)?;
 	let genesis_hash = spec.genesis_header().hash();
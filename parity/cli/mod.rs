@@ -78,6 +78,19 @@ usage! {
 		{
 			"Import blockchain data from a file to the given --chain database (default: mainnet)",
 
+			CMD cmd_import_state
+			{
+				"Convert a state exported with `parity export state` into the accounts section of a chain spec, written to standard output.",
+
+				ARG arg_import_state_format: (Option<String>) = None,
+				"--format=[FORMAT]",
+				"Format of the exported state. FORMAT must be either 'json' or 'binary'. (default: json)",
+
+				ARG arg_import_state_file: (Option<String>) = None,
+				"[FILE]",
+				"Path to the exported state",
+			}
+
 			ARG arg_import_format: (Option<String>) = None,
 			"--format=[FORMAT]",
 			"Import in a given format. FORMAT must be either 'hex' or 'binary'. (default: auto)",
@@ -114,7 +127,7 @@ usage! {
 
 			CMD cmd_export_state
 			{
-				"Export the blockchain state from the given --chain (default: mainnet) into a file. This command requires the chain to be synced with --fat-db on.",
+				"Export the blockchain state from the given --chain (default: mainnet) into a file, one account at a time. Addresses and storage keys are only included when the chain is synced with --fat-db on.",
 
 				FLAG flag_export_state_no_storage: (bool) = false,
 				"--no-storage",
@@ -136,9 +149,13 @@ usage! {
 				"--at=[BLOCK]",
 				"Take a snapshot at the given block, which may be an index, hash, or latest. Note that taking snapshots at non-recent blocks will only work with --pruning archive",
 
+				ARG arg_export_state_from: (Option<String>) = None,
+				"--from=[HASH]",
+				"Start at the account with the given address hash, e.g. to resume an interrupted export. Accounts are exported in address hash order.",
+
 				ARG arg_export_state_format: (Option<String>) = None,
 				"--format=[FORMAT]",
-				"Export in a given format. FORMAT must be either 'hex' or 'binary'. (default: binary)",
+				"Export in a given format. FORMAT must be either 'json' (one account per line) or 'binary' (concatenated RLP). (default: json)",
 
 				ARG arg_export_state_file: (Option<String>) = None,
 				"[FILE]",
@@ -229,6 +246,14 @@ usage! {
 				"Number of blocks to revert",
 			}
 
+			CMD cmd_db_migrate_pruning {
+				"Convert the archive database of the given --chain to fast pruning, keeping only the state of the best block. The archive database is left untouched",
+
+				ARG arg_db_migrate_pruning_to: (String) = "fast",
+				"--to=[METHOD]",
+				"Pruning method to migrate the database to. Only fast is supported.",
+			}
+
 		}
 
 		CMD cmd_export_hardcoded_sync
@@ -498,9 +523,9 @@ usage! {
 			"--jsonrpc-interface=[IP]",
 			"Specify the hostname portion of the HTTP JSON-RPC API server, IP should be an interface's IP address, or all (all interfaces) or local.",
 
-			ARG arg_jsonrpc_apis: (String) = "web3,eth,pubsub,net,parity,private,parity_pubsub,traces,rpc,parity_transactions_pool", or |c: &Config| c.rpc.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
+			ARG arg_jsonrpc_apis: (String) = "web3,eth,pubsub,net,parity,private,parity_pubsub,traces,rpc,parity_transactions_pool,txpool", or |c: &Config| c.rpc.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
 			"--jsonrpc-apis=[APIS]",
-			"Specify the APIs available through the HTTP JSON-RPC interface using a comma-delimited list of API names. Possible names are: all, safe, debug, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore. You can also disable a specific API by putting '-' in the front, example: all,-personal. 'safe' enables the following APIs: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc",
+			"Specify the APIs available through the HTTP JSON-RPC interface using a comma-delimited list of API names. Possible names are: all, safe, debug, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore, txpool. You can also disable a specific API by putting '-' in the front, example: all,-personal. 'safe' enables the following APIs: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, txpool",
 
 			ARG arg_jsonrpc_hosts: (String) = "none", or |c: &Config| c.rpc.as_ref()?.hosts.as_ref().map(|vec| vec.join(",")),
 			"--jsonrpc-hosts=[HOSTS]",
@@ -522,6 +547,22 @@ usage! {
 			"--jsonrpc-max-payload=[MB]",
 			"Specify maximum size for HTTP JSON-RPC requests in megabytes.",
 
+			ARG arg_jsonrpc_max_batch_size: (Option<usize>) = None, or |c: &Config| c.rpc.as_ref()?.max_batch_size,
+			"--jsonrpc-max-batch-size=[CALLS]",
+			"Specify maximum number of calls in a single HTTP or WebSockets JSON-RPC batch request. Larger batches are rejected with a single error response.",
+
+			ARG arg_rpc_gas_cap: (u64) = 50_000_000u64, or |c: &Config| c.rpc.as_ref()?.gas_cap.clone(),
+			"--rpc-gas-cap=[GAS]",
+			"Maximum gas eth_call and eth_estimateGas may use. Calls requesting more gas are rejected, calls without gas use the cap. Set to 0 to disable the cap.",
+
+			ARG arg_rpc_call_timeout: (u64) = 5u64, or |c: &Config| c.rpc.as_ref()?.call_timeout.clone(),
+			"--rpc-call-timeout=[S]",
+			"Stop waiting for eth_call and eth_estimateGas to finish after S seconds. Set to 0 to disable the timeout.",
+
+			FLAG flag_jsonrpc_revert_reasons: (bool) = false, or |c: &Config| c.rpc.as_ref()?.revert_reasons.clone(),
+			"--jsonrpc-revert-reasons",
+			"Add the revert reason to the receipts of failed transactions returned by eth_getTransactionReceipt. Requires replaying the transaction.",
+
 			ARG arg_poll_lifetime: (u32) = 60u32, or |c: &Config| c.rpc.as_ref()?.poll_lifetime.clone(),
 			"--poll-lifetime=[S]",
 			"Set the RPC filter lifetime to S seconds. The filter has to be polled at least every S seconds , otherwise it is removed.",
@@ -539,9 +580,9 @@ usage! {
 			"--ws-interface=[IP]",
 			"Specify the hostname portion of the WebSockets JSON-RPC server, IP should be an interface's IP address, or all (all interfaces) or local.",
 
-			ARG arg_ws_apis: (String) = "web3,eth,pubsub,net,parity,parity_pubsub,private,traces,rpc,parity_transactions_pool", or |c: &Config| c.websockets.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
+			ARG arg_ws_apis: (String) = "web3,eth,pubsub,net,parity,parity_pubsub,private,traces,rpc,parity_transactions_pool,txpool", or |c: &Config| c.websockets.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
 			"--ws-apis=[APIS]",
-			"Specify the JSON-RPC APIs available through the WebSockets interface using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore. You can also disable a specific API by putting '-' in the front, example: all,-personal. 'safe' enables the following APIs: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc",
+			"Specify the JSON-RPC APIs available through the WebSockets interface using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore, txpool. You can also disable a specific API by putting '-' in the front, example: all,-personal. 'safe' enables the following APIs: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, txpool",
 
 			ARG arg_ws_origins: (String) = "parity://*,chrome-extension://*,moz-extension://*", or |c: &Config| c.websockets.as_ref()?.origins.as_ref().map(|vec| vec.join(",")),
 			"--ws-origins=[URL]",
@@ -564,9 +605,9 @@ usage! {
 			"--ipc-path=[PATH]",
 			"Specify custom path for JSON-RPC over IPC service.",
 
-			ARG arg_ipc_apis: (String) = "web3,eth,pubsub,net,parity,parity_pubsub,parity_accounts,private,traces,rpc,parity_transactions_pool", or |c: &Config| c.ipc.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
+			ARG arg_ipc_apis: (String) = "web3,eth,pubsub,net,parity,parity_pubsub,parity_accounts,private,traces,rpc,parity_transactions_pool,txpool", or |c: &Config| c.ipc.as_ref()?.apis.as_ref().map(|vec| vec.join(",")),
 			"--ipc-apis=[APIS]",
-			"Specify custom API set available via JSON-RPC over IPC using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore. You can also disable a specific API by putting '-' in the front, example: all,-personal. 'safe' enables the following APIs: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc",
+			"Specify custom API set available via JSON-RPC over IPC using a comma-delimited list of API names. Possible names are: all, safe, web3, net, eth, pubsub, personal, signer, parity, parity_pubsub, parity_accounts, parity_set, traces, rpc, secretstore, txpool. You can also disable a specific API by putting '-' in the front, example: all,-personal. 'safe' enables the following APIs: web3, net, eth, pubsub, parity, parity_pubsub, traces, rpc, txpool",
 
 		["API and Console Options – IPFS"]
 			FLAG flag_ipfs_api: (bool) = false, or |c: &Config| c.ipfs.as_ref()?.enable.clone(),
@@ -778,14 +819,30 @@ usage! {
 			"--tx-queue-per-sender=[LIMIT]",
 			"Maximum number of transactions per sender in the queue. By default it's 1% of the entire queue, but not less than 16.",
 
+			ARG arg_tx_queue_max_nonce_gap: (Option<u64>) = None, or |c: &Config| c.mining.as_ref()?.tx_queue_max_nonce_gap.clone(),
+			"--tx-queue-max-nonce-gap=[GAP]",
+			"Maximum distance between the nonce of a transaction and its sender's current nonce for the transaction to be accepted to the queue. Local transactions are exempt. By default it's equal to the per-sender limit.",
+
 			ARG arg_tx_queue_locals: (Option<String>) = None, or |c: &Config| helpers::join_set(c.mining.as_ref()?.tx_queue_locals.as_ref()),
 			"--tx-queue-locals=[ACCOUNTS]",
 			"Specify local accounts for which transactions are prioritized in the queue. ACCOUNTS is a comma-delimited list of addresses.",
 
+			ARG arg_tx_queue_priority_senders: (Option<String>) = None, or |c: &Config| helpers::join_set(c.mining.as_ref()?.tx_queue_priority_senders.as_ref()),
+			"--tx-queue-priority-senders=[ACCOUNTS]",
+			"Specify accounts for which transactions are treated like local ones and always included first in mined blocks, regardless of their gas price. ACCOUNTS is a comma-delimited list of addresses.",
+
+			ARG arg_tx_queue_locals_journal: (Option<String>) = None, or |c: &Config| c.mining.as_ref()?.tx_queue_locals_journal.clone(),
+			"--tx-queue-locals-journal=[PATH]",
+			"Write pending local transactions to the file at PATH whenever they change, and import them again on startup. Relative paths are resolved against the database directory of the chain.",
+
 			ARG arg_tx_queue_strategy: (String) = "gas_price", or |c: &Config| c.mining.as_ref()?.tx_queue_strategy.clone(),
 			"--tx-queue-strategy=[S]",
 			"Prioritization strategy used to order transactions in the queue. S may be: gas_price - Prioritize txs with high gas price",
 
+			ARG arg_tx_queue_price_bump: (f64) = 12.5f64, or |c: &Config| c.mining.as_ref()?.tx_queue_price_bump.clone(),
+			"--tx-queue-price-bump=[PERCENT]",
+			"Minimal gas price increase, in percent, required to replace a transaction in the queue with another one from the same sender and with the same nonce. Local transactions only need a higher gas price. Set to 0 to accept any higher gas price.",
+
 			ARG arg_stratum_interface: (String) = "local", or |c: &Config| c.stratum.as_ref()?.interface.clone(),
 			"--stratum-interface=[IP]",
 			"Interface address for Stratum server.",
@@ -798,9 +855,13 @@ usage! {
 			"--min-gas-price=[STRING]",
 			"Minimum amount of Wei per GAS to be paid for a transaction to be accepted for mining. Overrides --usd-per-tx.",
 
-			ARG arg_gas_price_percentile: (usize) = 50usize, or |c: &Config| c.mining.as_ref()?.gas_price_percentile,
+			ARG arg_gas_price_percentile: (usize) = 60usize, or |c: &Config| c.mining.as_ref()?.gas_price_percentile,
 			"--gas-price-percentile=[PCT]",
-			"Set PCT percentile gas price value from last 100 blocks as default gas price when sending transactions.",
+			"Set PCT percentile of the cheapest gas prices paid in recent blocks as default gas price when sending transactions.",
+
+			ARG arg_gas_price_blocks: (usize) = 20usize, or |c: &Config| c.mining.as_ref()?.gas_price_blocks,
+			"--gas-price-blocks=[N]",
+			"Number of most recent blocks to sample the gas prices from for --gas-price-percentile.",
 
 			ARG arg_author: (Option<String>) = None, or |c: &Config| c.mining.as_ref()?.author.clone(),
 			"--author=[ADDRESS]",
@@ -830,6 +891,10 @@ usage! {
 			"--stratum-secret=[STRING]",
 			"Secret for authorizing Stratum server for peers.",
 
+			ARG arg_stratum_shares_per_minute: (Option<u32>) = None, or |c: &Config| c.stratum.as_ref()?.shares_per_minute.clone(),
+			"--stratum-shares-per-minute=[N]",
+			"Adjust the share difficulty of every Stratum worker so it submits about N shares per minute. Only solutions for whole blocks are then submitted.",
+
 			ARG arg_max_round_blocks_to_import: (usize) = 12usize, or |c: &Config| c.mining.as_ref()?.max_round_blocks_to_import.clone(),
 			"--max-round-blocks-to-import=[S]",
 			"Maximal number of blocks to import for each import round.",
@@ -869,6 +934,10 @@ usage! {
 			"--tracing=[BOOL]",
 			"Indicates if full transaction tracing should be enabled. Works only if client had been fully synced with tracing enabled. BOOL may be one of auto, on, off. auto uses last used value of this option (off if it does not exist).", // footprint option
 
+			ARG arg_tracing_max_count: (usize) = 100000usize, or |c: &Config| c.footprint.as_ref()?.tracing_max_count.clone(),
+			"--tracing-max-count=[NUM]",
+			"Maximum number of traces returned by trace_filter. Queries matching more traces are rejected, use the after and count fields to page through them.",
+
 			ARG arg_pruning: (String) = "auto", or |c: &Config| c.footprint.as_ref()?.pruning.clone(),
 			"--pruning=[METHOD]",
 			"Configure pruning of the state/storage trie. METHOD may be one of auto, archive, fast: archive - keep all state trie data. No pruning. fast - maintain journal overlay. Fast but 50MB used. auto - use the method most recently synced or default to fast if none synced.",
@@ -881,6 +950,18 @@ usage! {
 			"--pruning-memory=[MB]",
 			"The ideal amount of memory in megabytes to use to store recent states. As many states as possible will be kept within this limit, and at least --pruning-history states will always be kept.",
 
+			FLAG flag_prune_ancient_blocks: (bool) = false, or |c: &Config| c.footprint.as_ref()?.prune_ancient_blocks.clone(),
+			"--prune-ancient-blocks",
+			"Delete bodies, receipts and traces of blocks older than --ancient-blocks-history. Headers are always kept and blocks included in the most recent snapshot are never pruned.",
+
+			ARG arg_ancient_blocks_history: (u64) = 150000u64, or |c: &Config| c.footprint.as_ref()?.ancient_blocks_history.clone(),
+			"--ancient-blocks-history=[NUM]",
+			"Number of recent blocks whose bodies, receipts and traces are kept when --prune-ancient-blocks is enabled.",
+
+			FLAG flag_index_logs: (bool) = false, or |c: &Config| c.footprint.as_ref()?.index_logs.clone(),
+			"--index-logs",
+			"Maintain an index of blocks by log address and first topic to speed up log queries. Blocks imported before the index was enabled are indexed in the background.",
+
 			ARG arg_cache_size_db: (u32) = 128u32, or |c: &Config| c.footprint.as_ref()?.cache_size_db.clone(),
 			"--cache-size-db=[MB]",
 			"Override database cache size.",
@@ -927,6 +1008,14 @@ usage! {
 			"--snapshot-threads=[NUM]",
 			"Enables multiple threads for snapshots creation.",
 
+			ARG arg_snapshot_io_budget: (Option<u64>) = None, or |c: &Config| c.snapshots.as_ref()?.io_budget,
+			"--snapshot-io-budget=[MB]",
+			"Limit the rate at which state chunks are written when creating snapshots to MB megabytes per second, pausing between chunks. 0 means no limit.",
+
+			ARG arg_snapshot_compression: (String) = "snappy", or |c: &Config| c.snapshots.as_ref()?.compression.clone(),
+			"--snapshot-compression=[ALGORITHM]",
+			"Compression of the chunks of created snapshots. ALGORITHM may be one of: snappy, zstd. Nodes older than snapshot version 3 can't restore zstd snapshots.",
+
 		["Whisper Options"]
 			FLAG flag_whisper: (bool) = false, or |c: &Config| c.whisper.as_ref()?.enabled,
 			"--whisper",
@@ -1264,6 +1353,10 @@ struct Rpc {
 	server_threads: Option<usize>,
 	processing_threads: Option<usize>,
 	max_payload: Option<usize>,
+	max_batch_size: Option<usize>,
+	gas_cap: Option<u64>,
+	call_timeout: Option<u64>,
+	revert_reasons: Option<bool>,
 	keep_alive: Option<bool>,
 	experimental_rpcs: Option<bool>,
 	poll_lifetime: Option<u32>,
@@ -1361,6 +1454,7 @@ struct Mining {
 	relay_set: Option<String>,
 	min_gas_price: Option<u64>,
 	gas_price_percentile: Option<usize>,
+	gas_price_blocks: Option<usize>,
 	usd_per_tx: Option<String>,
 	usd_per_eth: Option<String>,
 	price_update_period: Option<String>,
@@ -1369,9 +1463,13 @@ struct Mining {
 	extra_data: Option<String>,
 	tx_queue_size: Option<usize>,
 	tx_queue_per_sender: Option<usize>,
+	tx_queue_max_nonce_gap: Option<u64>,
 	tx_queue_mem_limit: Option<u32>,
 	tx_queue_locals: Option<HashSet<String>>,
+	tx_queue_priority_senders: Option<HashSet<String>>,
+	tx_queue_locals_journal: Option<String>,
 	tx_queue_strategy: Option<String>,
+	tx_queue_price_bump: Option<f64>,
 	tx_queue_ban_count: Option<u16>,
 	tx_queue_ban_time: Option<u16>,
 	tx_queue_no_unfamiliar_locals: Option<bool>,
@@ -1390,15 +1488,20 @@ struct Stratum {
 	interface: Option<String>,
 	port: Option<u16>,
 	secret: Option<String>,
+	shares_per_minute: Option<u32>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct Footprint {
 	tracing: Option<String>,
+	tracing_max_count: Option<usize>,
 	pruning: Option<String>,
 	pruning_history: Option<u64>,
 	pruning_memory: Option<usize>,
+	prune_ancient_blocks: Option<bool>,
+	ancient_blocks_history: Option<u64>,
+	index_logs: Option<bool>,
 	fast_and_loose: Option<bool>,
 	cache_size: Option<u32>,
 	cache_size_db: Option<u32>,
@@ -1416,6 +1519,8 @@ struct Footprint {
 struct Snapshots {
 	disable_periodic: Option<bool>,
 	processing_threads: Option<usize>,
+	io_budget: Option<u64>,
+	compression: Option<String>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
@@ -1503,6 +1608,21 @@ mod tests {
 		assert_eq!(args.arg_export_state_min_balance, Some("123".to_string()));
 	}
 
+	#[test]
+	fn should_parse_import_with_and_without_subcommand() {
+		let args = Args::parse(&["parity", "import", "blocks.rlp"]).unwrap();
+		assert!(args.cmd_import);
+		assert!(!args.cmd_import_state);
+		assert_eq!(args.arg_import_file, Some("blocks.rlp".into()));
+
+		let args = Args::parse(&["parity", "import", "state", "--format", "binary", "state.bin"]).unwrap();
+		assert!(args.cmd_import);
+		assert!(args.cmd_import_state);
+		assert_eq!(args.arg_import_state_format, Some("binary".into()));
+		assert_eq!(args.arg_import_state_file, Some("state.bin".into()));
+		assert_eq!(args.arg_import_file, None);
+	}
+
 	#[test]
 	fn should_exit_gracefully_on_unknown_argument() {
 		let result = Args::parse(&["parity", "--please-exit-gracefully"]);
@@ -1646,6 +1766,7 @@ mod tests {
 		assert_eq!(args.arg_stratum_interface, "some_interface".to_owned());
 		assert_eq!(args.arg_stratum_port, 8007u16);
 		assert_eq!(args.arg_stratum_secret, Some("Yellow".to_owned()));
+		assert_eq!(args.arg_stratum_shares_per_minute, Some(30));
 	}
 
 	#[test]
@@ -1700,6 +1821,7 @@ mod tests {
 			cmd_wallet: false,
 			cmd_wallet_import: false,
 			cmd_import: false,
+			cmd_import_state: false,
 			cmd_export: false,
 			cmd_export_blocks: false,
 			cmd_export_state: false,
@@ -1715,12 +1837,15 @@ mod tests {
 			cmd_db: false,
 			cmd_db_kill: false,
 			cmd_db_reset: false,
+			cmd_db_migrate_pruning: false,
 			cmd_export_hardcoded_sync: false,
 
 			// Arguments
 			arg_daemon_pid_file: None,
 			arg_import_file: None,
 			arg_import_format: None,
+			arg_import_state_file: None,
+			arg_import_state_format: None,
 			arg_export_blocks_file: None,
 			arg_export_blocks_format: None,
 			arg_export_state_file: None,
@@ -1735,6 +1860,7 @@ mod tests {
 			arg_account_import_path: None,
 			arg_wallet_import_path: None,
 			arg_db_reset_num: 10,
+			arg_db_migrate_pruning_to: "fast".into(),
 
 			// -- Operating Options
 			arg_mode: "last".into(),
@@ -1819,6 +1945,10 @@ mod tests {
 			arg_jsonrpc_server_threads: None,
 			arg_jsonrpc_threads: 4,
 			arg_jsonrpc_max_payload: None,
+			arg_jsonrpc_max_batch_size: None,
+			arg_rpc_gas_cap: 50_000_000u64,
+			arg_rpc_call_timeout: 5u64,
+			flag_jsonrpc_revert_reasons: false,
 			arg_poll_lifetime: 60u32,
 			flag_jsonrpc_allow_missing_blocks: false,
 
@@ -1882,7 +2012,8 @@ mod tests {
 			arg_relay_set: "cheap".into(),
 			arg_min_gas_price: Some(0u64),
 			arg_usd_per_tx: "0.0001".into(),
-			arg_gas_price_percentile: 50usize,
+			arg_gas_price_percentile: 60usize,
+			arg_gas_price_blocks: 20usize,
 			arg_usd_per_eth: "auto".into(),
 			arg_price_update_period: "hourly".into(),
 			arg_gas_floor_target: "8000000".into(),
@@ -1892,9 +2023,13 @@ mod tests {
 			flag_tx_queue_no_early_reject: false,
 			arg_tx_queue_size: 8192usize,
 			arg_tx_queue_per_sender: None,
+			arg_tx_queue_max_nonce_gap: None,
 			arg_tx_queue_mem_limit: 4u32,
 			arg_tx_queue_locals: Some("0xdeadbeefcafe0000000000000000000000000000".into()),
+			arg_tx_queue_priority_senders: None,
+			arg_tx_queue_locals_journal: None,
 			arg_tx_queue_strategy: "gas_factor".into(),
+			arg_tx_queue_price_bump: 12.5f64,
 			arg_tx_queue_ban_count: Some(1u16),
 			arg_tx_queue_ban_time: Some(180u16),
 			flag_remove_solved: false,
@@ -1907,12 +2042,17 @@ mod tests {
 			arg_stratum_interface: "local".to_owned(),
 			arg_stratum_port: 8008u16,
 			arg_stratum_secret: None,
+			arg_stratum_shares_per_minute: None,
 
 			// -- Footprint Options
 			arg_tracing: "auto".into(),
+			arg_tracing_max_count: 100000usize,
 			arg_pruning: "auto".into(),
 			arg_pruning_history: 64u64,
 			arg_pruning_memory: 500usize,
+			flag_prune_ancient_blocks: false,
+			arg_ancient_blocks_history: 150000u64,
+			flag_index_logs: false,
 			arg_cache_size_db: 64u32,
 			arg_cache_size_blocks: 8u32,
 			arg_cache_size_queue: 50u32,
@@ -1932,12 +2072,15 @@ mod tests {
 			flag_export_state_no_storage: false,
 			arg_export_state_min_balance: None,
 			arg_export_state_max_balance: None,
+			arg_export_state_from: None,
 
 			// -- Snapshot Optons
 			arg_export_state_at: "latest".into(),
 			arg_snapshot_at: "latest".into(),
 			flag_no_periodic_snapshot: false,
 			arg_snapshot_threads: None,
+			arg_snapshot_io_budget: None,
+			arg_snapshot_compression: "snappy".into(),
 
 			// -- Light options.
 			arg_on_demand_response_time_window: Some(2),
@@ -2098,6 +2241,10 @@ mod tests {
 				server_threads: None,
 				processing_threads: None,
 				max_payload: None,
+				max_batch_size: None,
+				gas_cap: None,
+				call_timeout: None,
+				revert_reasons: None,
 				keep_alive: None,
 				experimental_rpcs: None,
 				poll_lifetime: None,
@@ -2159,6 +2306,7 @@ mod tests {
 				relay_set: None,
 				min_gas_price: None,
 				gas_price_percentile: None,
+				gas_price_blocks: None,
 				usd_per_tx: None,
 				usd_per_eth: None,
 				price_update_period: Some("hourly".into()),
@@ -2166,9 +2314,13 @@ mod tests {
 				gas_cap: None,
 				tx_queue_size: Some(8192),
 				tx_queue_per_sender: None,
+				tx_queue_max_nonce_gap: None,
 				tx_queue_mem_limit: None,
 				tx_queue_locals: None,
+				tx_queue_priority_senders: None,
+				tx_queue_locals_journal: None,
 				tx_queue_strategy: None,
+				tx_queue_price_bump: None,
 				tx_queue_ban_count: None,
 				tx_queue_ban_time: None,
 				tx_queue_no_unfamiliar_locals: None,
@@ -2184,9 +2336,13 @@ mod tests {
 			}),
 			footprint: Some(Footprint {
 				tracing: Some("on".into()),
+				tracing_max_count: None,
 				pruning: Some("fast".into()),
 				pruning_history: Some(64),
 				pruning_memory: None,
+				prune_ancient_blocks: None,
+				ancient_blocks_history: None,
+				index_logs: None,
 				fast_and_loose: None,
 				cache_size: None,
 				cache_size_db: Some(256),
@@ -2208,6 +2364,8 @@ mod tests {
 			snapshots: Some(Snapshots {
 				disable_periodic: Some(true),
 				processing_threads: None,
+				io_budget: None,
+				compression: None,
 			}),
 			misc: Some(Misc {
 				logging: Some("own_tx=trace".into()),
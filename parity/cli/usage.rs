@@ -414,17 +414,17 @@ macro_rules! usage {
 							);
 						)*
 
-						// Print the subcommand on its own only if it has no subsubcommands
-						if !subc_subc_exist {
-							let subc_usages : Vec<&str> = vec![
-								$(
-									concat!("[",$subc_flag_usage,"]"),
-								)*
-								$(
-									$subc_arg_usage,
-								)*
-							];
+						let subc_usages : Vec<&str> = vec![
+							$(
+								concat!("[",$subc_flag_usage,"]"),
+							)*
+							$(
+								$subc_arg_usage,
+							)*
+						];
 
+						// Print the subcommand on its own only if it has no subsubcommands or takes arguments
+						if !subc_subc_exist || !subc_usages.is_empty() {
 							help.push_str(&subcommands_wrapper.fill(
 								format!(
 									"parity [options] {} {}\n",
@@ -621,19 +621,27 @@ macro_rules! usage {
 							arg
 						}).collect::<Vec<Arg>>())
 						$(
-							.subcommand(
-								SubCommand::with_name(&underscore_to_hyphen!(&stringify!($subc)[4..]))
-								.about($subc_help)
-								.args(&subc_usages.get(stringify!($subc)).unwrap().iter().map(|u| Arg::from_usage(u).use_delimiter(false).allow_hyphen_values(true)).collect::<Vec<Arg>>())
+							.subcommand({
+								let this_subc_usages = subc_usages.get(stringify!($subc)).unwrap();
+								let subc = SubCommand::with_name(&underscore_to_hyphen!(&stringify!($subc)[4..]))
+									.about($subc_help)
+									.args(&this_subc_usages.iter().map(|u| Arg::from_usage(u).use_delimiter(false).allow_hyphen_values(true)).collect::<Vec<Arg>>());
+								let mut subc_subc_exist = false;
 								$(
-									.setting(AppSettings::SubcommandRequired) // prevent from running `parity account`
-									.subcommand(
+									subc_subc_exist = true;
+									let subc = subc.subcommand(
 										SubCommand::with_name(&underscore_to_hyphen!(&stringify!($subc_subc)[stringify!($subc).len()+1..]))
 										.about($subc_subc_help)
 										.args(&subc_usages.get(stringify!($subc_subc)).unwrap().iter().map(|u| Arg::from_usage(u).use_delimiter(false).allow_hyphen_values(true)).collect::<Vec<Arg>>())
-									)
+									);
 								)*
-							)
+								// prevent from running `parity account`, but keep `parity import [FILE]` working
+								if subc_subc_exist && this_subc_usages.is_empty() {
+									subc.setting(AppSettings::SubcommandRequired)
+								} else {
+									subc
+								}
+							})
 						)*
 						.get_matches_from_safe(command.iter().map(|x| OsStr::new(x.as_ref())))?;
 
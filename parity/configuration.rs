@@ -30,7 +30,7 @@ use ansi_term::Colour;
 use sync::{NetworkConfiguration, validate_node_url, self};
 use ethkey::{Secret, Public};
 use ethcore::client::VMType;
-use ethcore::miner::{stratum, MinerOptions};
+use ethcore::miner::{stratum, MinerOptions, GasPriceOracleOptions};
 use snapshot::SnapshotConfiguration;
 use miner::pool;
 use verification::queue::VerifierSettings;
@@ -38,7 +38,7 @@ use verification::queue::VerifierSettings;
 use rpc::{IpcConfiguration, HttpConfiguration, WsConfiguration};
 use parity_rpc::NetworkSettings;
 use cache::CacheConfig;
-use helpers::{to_duration, to_mode, to_block_id, to_u256, to_pending_set, to_price, geth_ipc_path, parity_ipc_path, to_bootnodes, to_addresses, to_address, to_queue_strategy, to_queue_penalization};
+use helpers::{to_duration, to_mode, to_block_id, to_u256, to_h256, to_pending_set, to_price, geth_ipc_path, parity_ipc_path, to_bootnodes, to_addresses, to_address, to_queue_strategy, to_queue_penalization, to_price_bump, to_snapshot_compression};
 use dir::helpers::{replace_home, replace_home_and_local};
 use params::{ResealPolicy, AccountsConfig, GasPricerConfig, MinerExtras, SpecType};
 use ethcore_logger::Config as LogConfig;
@@ -49,7 +49,8 @@ use secretstore::{NodeSecretKey, Configuration as SecretStoreConfiguration, Cont
 use updater::{UpdatePolicy, UpdateFilter, ReleaseTrack};
 use run::RunCmd;
 use types::data_format::DataFormat;
-use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, KillBlockchain, ExportState, ResetBlockchain};
+use types::state_dump::StateDumpFormat;
+use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, KillBlockchain, ExportState, ImportState, ResetBlockchain, MigratePruning};
 use export_hardcoded_sync::ExportHsyncCmd;
 use presale::ImportWallet;
 use account::{AccountCmd, NewAccount, ListAccounts, ImportAccounts, ImportFromGethAccounts};
@@ -195,6 +196,14 @@ impl Configuration {
 				cache_config,
 				num: self.args.arg_db_reset_num,
 			}))
+		} else if self.args.cmd_db && self.args.cmd_db_migrate_pruning {
+			Cmd::Blockchain(BlockchainCmd::MigratePruning(MigratePruning {
+				spec,
+				dirs,
+				cache_config,
+				compaction,
+				to: self.args.arg_db_migrate_pruning_to.parse()?,
+			}))
 		} else if self.args.cmd_db && self.args.cmd_db_kill {
 			Cmd::Blockchain(BlockchainCmd::Kill(KillBlockchain {
 				spec: spec,
@@ -245,6 +254,11 @@ impl Configuration {
 				password_file: self.accounts_config()?.password_files.first().map(|x| x.to_owned()),
 			};
 			Cmd::ImportPresaleWallet(presale_cmd)
+		} else if self.args.cmd_import_state {
+			Cmd::Blockchain(BlockchainCmd::ImportState(ImportState {
+				file_path: self.args.arg_import_state_file.clone(),
+				format: self.state_format(&self.args.arg_import_state_format)?,
+			}))
 		} else if self.args.cmd_import {
 			let import_cmd = ImportBlockchain {
 				spec: spec,
@@ -292,7 +306,7 @@ impl Configuration {
 					cache_config: cache_config,
 					dirs: dirs,
 					file_path: self.args.arg_export_state_file.clone(),
-					format: format,
+					format: self.state_format(&self.args.arg_export_state_format)?,
 					pruning: pruning,
 					pruning_history: pruning_history,
 					pruning_memory: self.args.arg_pruning_memory,
@@ -304,6 +318,10 @@ impl Configuration {
 					code: !self.args.flag_export_state_no_code,
 					min_balance: self.args.arg_export_state_min_balance.and_then(|s| to_u256(&s).ok()),
 					max_balance: self.args.arg_export_state_max_balance.and_then(|s| to_u256(&s).ok()),
+					from: match self.args.arg_export_state_from {
+						Some(ref from) => Some(to_h256(from)?),
+						None => None,
+					},
 					max_round_blocks_to_import: self.args.arg_max_round_blocks_to_import,
 				};
 				Cmd::Blockchain(BlockchainCmd::ExportState(export_cmd))
@@ -372,6 +390,8 @@ impl Configuration {
 				pruning: pruning,
 				pruning_history: pruning_history,
 				pruning_memory: self.args.arg_pruning_memory,
+				ancient_blocks_history: self.ancient_blocks_history(),
+				index_logs: self.args.flag_index_logs,
 				daemon: daemon,
 				logger_config: logger_config.clone(),
 				miner_options: self.miner_options()?,
@@ -391,6 +411,16 @@ impl Configuration {
 				allow_missing_blocks: self.args.flag_jsonrpc_allow_missing_blocks,
 				mode: mode,
 				tracing: tracing,
+				tracing_max_count: self.args.arg_tracing_max_count,
+				rpc_gas_cap: match self.args.arg_rpc_gas_cap {
+					0 => None,
+					cap => Some(cap),
+				},
+				rpc_call_timeout: match self.args.arg_rpc_call_timeout {
+					0 => None,
+					timeout => Some(Duration::from_secs(timeout)),
+				},
+				rpc_revert_reasons: self.args.flag_jsonrpc_revert_reasons,
 				fat_db: fat_db,
 				compaction: compaction,
 				vm_type: vm_type,
@@ -412,6 +442,7 @@ impl Configuration {
 				serve_light: !self.args.flag_no_serve_light,
 				light: self.args.flag_light,
 				no_persistent_txqueue: self.args.flag_no_persistent_txqueue,
+				tx_queue_locals_journal: self.args.arg_tx_queue_locals_journal.as_ref().map(|path| replace_home(&self.directories().base, path)),
 				no_hardcoded_sync: self.args.flag_no_hardcoded_sync,
 				max_round_blocks_to_import: self.args.arg_max_round_blocks_to_import,
 				on_demand_response_time_window: self.args.arg_on_demand_response_time_window,
@@ -458,13 +489,19 @@ impl Configuration {
 
 	fn format(&self) -> Result<Option<DataFormat>, String> {
 		match self.args.arg_import_format.clone()
-				.or(self.args.arg_export_blocks_format.clone())
-				.or(self.args.arg_export_state_format.clone()) {
+				.or(self.args.arg_export_blocks_format.clone()) {
 			Some(ref f) => Ok(Some(f.parse()?)),
 			None => Ok(None),
 		}
 	}
 
+	fn state_format(&self, format: &Option<String>) -> Result<StateDumpFormat, String> {
+		match *format {
+			Some(ref f) => f.parse(),
+			None => Ok(Default::default()),
+		}
+	}
+
 	fn cache_config(&self) -> CacheConfig {
 		match self.args.arg_cache_size.or(self.args.arg_cache) {
 			Some(size) => CacheConfig::new_with_total_cache_size(size),
@@ -551,6 +588,10 @@ impl Configuration {
 				listen_addr: self.stratum_interface(),
 				port: self.args.arg_ports_shift + self.args.arg_stratum_port,
 				secret: self.args.arg_stratum_secret.as_ref().map(|s| s.parse::<H256>().unwrap_or_else(|_| keccak(s))),
+				difficulty: self.args.arg_stratum_shares_per_minute.map(|shares_per_minute| stratum::DifficultyConfig {
+					shares_per_minute,
+					..Default::default()
+				}),
 			}))
 		} else { Ok(None) }
 	}
@@ -578,11 +619,17 @@ impl Configuration {
 
 			tx_queue_penalization: to_queue_penalization(self.args.arg_tx_time_limit)?,
 			tx_queue_strategy: to_queue_strategy(&self.args.arg_tx_queue_strategy)?,
+			tx_queue_price_bump: to_price_bump(self.args.arg_tx_queue_price_bump)?,
 			tx_queue_no_unfamiliar_locals: self.args.flag_tx_queue_no_unfamiliar_locals,
 			refuse_service_transactions: self.args.flag_refuse_service_transactions,
+			tx_queue_priority_senders: HashSet::from_iter(to_addresses(&self.args.arg_tx_queue_priority_senders)?.into_iter()),
 
 			pool_limits: self.pool_limits()?,
 			pool_verification_options: self.pool_verification_options()?,
+			gas_price_oracle: GasPriceOracleOptions {
+				blocks: self.args.arg_gas_price_blocks,
+				percentile: self.args.arg_gas_price_percentile,
+			},
 		};
 
 		Ok(options)
@@ -612,6 +659,10 @@ impl Configuration {
 				None => U256::max_value(),
 			},
 			no_early_reject: self.args.flag_tx_queue_no_early_reject,
+			max_nonce_gap: match self.args.arg_tx_queue_max_nonce_gap {
+				Some(gap) => gap.into(),
+				None => self.pool_limits()?.max_per_sender.into(),
+			},
 		})
 	}
 
@@ -893,6 +944,7 @@ impl Configuration {
 				Some(max) if max > 0 => max as usize,
 				_ => 5usize,
 			},
+			max_batch_size: self.args.arg_jsonrpc_max_batch_size,
 			keep_alive: !self.args.flag_jsonrpc_no_keep_alive,
 		};
 
@@ -914,6 +966,7 @@ impl Configuration {
 			signer_path: self.directories().signer.into(),
 			support_token_api,
 			max_connections: self.args.arg_ws_max_connections,
+			max_batch_size: self.args.arg_jsonrpc_max_batch_size,
 		};
 
 		Ok(conf)
@@ -948,10 +1001,23 @@ impl Configuration {
 				conf.processing_threads = threads;
 			}
 		}
+		conf.io_budget = match self.args.arg_snapshot_io_budget {
+			None | Some(0) => None,
+			Some(megabytes) => Some(megabytes.saturating_mul(1024 * 1024)),
+		};
+		conf.compression = to_snapshot_compression(&self.args.arg_snapshot_compression)?;
 
 		Ok(conf)
 	}
 
+	fn ancient_blocks_history(&self) -> Option<u64> {
+		if self.args.flag_prune_ancient_blocks {
+			Some(self.args.arg_ancient_blocks_history)
+		} else {
+			None
+		}
+	}
+
 	fn network_settings(&self) -> Result<NetworkSettings, String> {
 		let http_conf = self.http_config()?;
 		let net_addresses = self.net_addresses()?;
@@ -1197,16 +1263,19 @@ mod tests {
 
 	use tempdir::TempDir;
 	use ethcore::client::VMType;
-	use ethcore::miner::MinerOptions;
+	use ethcore::miner::{MinerOptions, GasPriceOracleOptions};
 	use miner::pool::PrioritizationStrategy;
 	use parity_rpc::NetworkSettings;
 	use updater::{UpdatePolicy, UpdateFilter, ReleaseTrack};
 	use types::ids::BlockId;
 	use types::data_format::DataFormat;
+	use types::snapshot::Compression;
+	use types::state_dump::StateDumpFormat;
 	use account::{AccountCmd, NewAccount, ImportAccounts, ListAccounts};
-	use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, ExportState};
+	use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, ExportState, ImportState, MigratePruning};
 	use cli::Args;
 	use dir::{Directories, default_hypervisor_path};
+	use journaldb::Algorithm;
 	use helpers::{default_network_config};
 	use params::SpecType;
 	use presale::ImportWallet;
@@ -1310,6 +1379,22 @@ mod tests {
 		})));
 	}
 
+	#[test]
+	fn test_command_db_migrate_pruning() {
+		let args = vec!["parity", "db", "migrate-pruning", "--to", "fast"];
+		let conf = parse(&args);
+		assert_eq!(conf.into_command().unwrap().cmd, Cmd::Blockchain(BlockchainCmd::MigratePruning(MigratePruning {
+			spec: Default::default(),
+			dirs: Default::default(),
+			cache_config: Default::default(),
+			compaction: Default::default(),
+			to: Algorithm::OverlayRecent,
+		})));
+
+		let args = vec!["parity", "db", "migrate-pruning", "--to", "nonsense"];
+		assert!(parse(&args).into_command().is_err());
+	}
+
 	#[test]
 	fn test_command_blockchain_export() {
 		let args = vec!["parity", "export", "blocks", "blockchain.json"];
@@ -1354,10 +1439,37 @@ mod tests {
 			code: true,
 			min_balance: None,
 			max_balance: None,
+			from: None,
 			max_round_blocks_to_import: 12,
 		})));
 	}
 
+	#[test]
+	fn test_command_state_export_binary_from() {
+		let args = vec!["parity", "export", "state", "--format", "binary", "--from", "0x0000000000000000000000000000000000000000000000000000000000000010", "state.bin"];
+		let conf = parse(&args);
+		match conf.into_command().unwrap().cmd {
+			Cmd::Blockchain(BlockchainCmd::ExportState(cmd)) => {
+				assert_eq!(cmd.format, StateDumpFormat::Binary);
+				assert_eq!(cmd.from, Some(H256::from_low_u64_be(16)));
+			},
+			_ => panic!("Should be an export state command"),
+		}
+
+		let args = vec!["parity", "export", "state", "--format", "hex", "state.bin"];
+		assert!(parse(&args).into_command().is_err());
+	}
+
+	#[test]
+	fn test_command_state_import() {
+		let args = vec!["parity", "import", "state", "state.jsonl"];
+		let conf = parse(&args);
+		assert_eq!(conf.into_command().unwrap().cmd, Cmd::Blockchain(BlockchainCmd::ImportState(ImportState {
+			file_path: Some("state.jsonl".into()),
+			format: StateDumpFormat::Json,
+		})));
+	}
+
 	#[test]
 	fn test_command_blockchain_export_with_custom_format() {
 		let args = vec!["parity", "export", "blocks", "--format", "hex", "blockchain.json"];
@@ -1396,6 +1508,7 @@ mod tests {
 			signer_path: expected.into(),
 			support_token_api: true,
 			max_connections: 100,
+			max_batch_size: None,
 		}, LogConfig {
 			color: !cfg!(windows),
 			mode: None,
@@ -1403,6 +1516,15 @@ mod tests {
 		} ));
 	}
 
+	#[test]
+	fn test_jsonrpc_max_batch_size() {
+		let args = vec!["parity", "--jsonrpc-max-batch-size", "10"];
+		let conf = parse(&args);
+
+		assert_eq!(conf.http_config().unwrap().max_batch_size, Some(10));
+		assert_eq!(conf.ws_config().unwrap().max_batch_size, Some(10));
+	}
+
 	#[test]
 	fn test_ws_max_connections() {
 		let args = vec!["parity", "--ws-max-connections", "1"];
@@ -1426,10 +1548,12 @@ mod tests {
 			pruning: Default::default(),
 			pruning_history: 64,
 			pruning_memory: 32,
+			ancient_blocks_history: None,
+			index_logs: false,
 			daemon: None,
 			logger_config: Default::default(),
 			miner_options: Default::default(),
-			gas_price_percentile: 50,
+			gas_price_percentile: 60,
 			poll_lifetime: 60,
 			ws_conf: Default::default(),
 			http_conf: Default::default(),
@@ -1453,6 +1577,10 @@ mod tests {
 			},
 			mode: Default::default(),
 			tracing: Default::default(),
+			tracing_max_count: 100000,
+			rpc_gas_cap: Some(50_000_000),
+			rpc_call_timeout: Some(Duration::from_secs(5)),
+			rpc_revert_reasons: false,
 			compaction: Default::default(),
 			vm_type: Default::default(),
 			geth_compatibility: false,
@@ -1475,6 +1603,7 @@ mod tests {
 			light: false,
 			no_hardcoded_sync: false,
 			no_persistent_txqueue: false,
+			tx_queue_locals_journal: None,
 			max_round_blocks_to_import: 12,
 			on_demand_response_time_window: None,
 			on_demand_request_backoff_start: None,
@@ -1495,11 +1624,63 @@ mod tests {
 		// when
 		let conf0 = parse(&["parity"]);
 		let conf2 = parse(&["parity", "--tx-queue-strategy", "gas_price"]);
+		let conf3 = parse(&["parity", "--tx-queue-per-sender", "32"]);
+		let conf4 = parse(&["parity", "--tx-queue-per-sender", "32", "--tx-queue-max-nonce-gap", "64"]);
+		let conf5 = parse(&["parity", "--gas-price-percentile", "40", "--gas-price-blocks", "10"]);
+		let conf6 = parse(&["parity", "--tx-queue-priority-senders", "0xdeadbeefcafe0000000000000000000000000000"]);
 
 		// then
 		assert_eq!(conf0.miner_options().unwrap(), mining_options);
 		mining_options.tx_queue_strategy = PrioritizationStrategy::GasPriceOnly;
 		assert_eq!(conf2.miner_options().unwrap(), mining_options);
+		assert_eq!(conf3.miner_options().unwrap().pool_verification_options.max_nonce_gap, 32.into());
+		assert_eq!(conf4.miner_options().unwrap().pool_verification_options.max_nonce_gap, 64.into());
+		assert_eq!(conf5.miner_options().unwrap().gas_price_oracle, GasPriceOracleOptions { blocks: 10, percentile: 40 });
+		assert_eq!(
+			conf6.miner_options().unwrap().tx_queue_priority_senders,
+			HashSet::from_iter(vec![Address::from_str("deadbeefcafe0000000000000000000000000000").unwrap()])
+		);
+	}
+
+	#[test]
+	fn should_parse_snapshot_io_budget() {
+		assert_eq!(parse(&["parity"]).snapshot_config().unwrap().io_budget, None);
+		assert_eq!(parse(&["parity", "--snapshot-io-budget", "0"]).snapshot_config().unwrap().io_budget, None);
+		assert_eq!(
+			parse(&["parity", "--snapshot-io-budget", "8"]).snapshot_config().unwrap().io_budget,
+			Some(8 * 1024 * 1024)
+		);
+	}
+
+	#[test]
+	fn should_parse_snapshot_compression() {
+		assert_eq!(parse(&["parity"]).snapshot_config().unwrap().compression, Compression::Snappy);
+		assert_eq!(
+			parse(&["parity", "--snapshot-compression", "zstd"]).snapshot_config().unwrap().compression,
+			Compression::Zstd
+		);
+		assert!(parse(&["parity", "--snapshot-compression", "lz4"]).snapshot_config().is_err());
+	}
+
+	#[test]
+	fn should_parse_ancient_blocks_history() {
+		assert_eq!(parse(&["parity"]).ancient_blocks_history(), None);
+		assert_eq!(parse(&["parity", "--ancient-blocks-history", "1000"]).ancient_blocks_history(), None);
+		assert_eq!(parse(&["parity", "--prune-ancient-blocks"]).ancient_blocks_history(), Some(150000));
+		assert_eq!(
+			parse(&["parity", "--prune-ancient-blocks", "--ancient-blocks-history", "1000"]).ancient_blocks_history(),
+			Some(1000)
+		);
+	}
+
+	#[test]
+	fn should_parse_index_logs() {
+		let index_logs = |args: &[&str]| match parse(args).into_command().unwrap().cmd {
+			Cmd::Run(c) => c.index_logs,
+			_ => panic!("Should be Cmd::Run"),
+		};
+		assert!(!index_logs(&["parity"]));
+		assert!(index_logs(&["parity", "--index-logs"]));
 	}
 
 	#[test]
@@ -1834,6 +2015,7 @@ mod tests {
 		assert_eq!(conf0.secretstore_config().unwrap().http_port, 8083);
 		assert_eq!(conf0.ipfs_config().port, 5002);
 		assert_eq!(conf0.stratum_options().unwrap().unwrap().port, 8009);
+		assert_eq!(conf0.stratum_options().unwrap().unwrap().difficulty, None);
 
 		assert_eq!(conf1.net_addresses().unwrap().0.port(), 30304);
 		assert_eq!(conf1.network_settings().unwrap().network_port, 30304);
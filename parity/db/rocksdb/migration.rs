@@ -50,10 +50,18 @@ pub const TO_V14: ChangeColumns = ChangeColumns {
 	version: 14,
 };
 
+/// The migration from v14 to v15.
+/// Adds a column for the log index.
+pub const TO_V15: ChangeColumns = ChangeColumns {
+	pre_columns: Some(9),
+	post_columns: Some(10),
+	version: 15,
+};
+
 /// Database is assumed to be at default version, when no version file is found.
 const DEFAULT_VERSION: u32 = 5;
 /// Current version of database models.
-const CURRENT_VERSION: u32 = 14;
+const CURRENT_VERSION: u32 = 15;
 /// A version of database at which blooms-db was introduced
 const BLOOMS_DB_VERSION: u32 = 13;
 /// Defines how many items are migrated to the new version of database at once.
@@ -156,6 +164,7 @@ fn consolidated_database_migrations(compaction_profile: &CompactionProfile) -> R
 	manager.add_migration(TO_V11).map_err(|_| Error::MigrationImpossible)?;
 	manager.add_migration(TO_V12).map_err(|_| Error::MigrationImpossible)?;
 	manager.add_migration(TO_V14).map_err(|_| Error::MigrationImpossible)?;
+	manager.add_migration(TO_V15).map_err(|_| Error::MigrationImpossible)?;
 	Ok(manager)
 }
 
@@ -24,6 +24,7 @@ use std::path::Path;
 use blooms_db;
 use ethcore_db::NUM_COLUMNS;
 use ethcore::client::{ClientConfig, DatabaseCompactionProfile};
+use types::database_stats::ColumnStats;
 use kvdb::KeyValueDB;
 use self::ethcore_blockchain::{BlockChainDBHandler, BlockChainDB};
 use self::kvdb_rocksdb::{Database, DatabaseConfig};
@@ -33,11 +34,14 @@ use cache::CacheConfig;
 mod blooms;
 mod migration;
 mod helpers;
+mod pruning;
 
 pub use self::migration::migrate;
+pub use self::pruning::migrate_pruning;
 
 struct AppDB {
 	key_value: Arc<dyn KeyValueDB>,
+	rocksdb: Arc<Database>,
 	blooms: blooms_db::Database,
 	trace_blooms: blooms_db::Database,
 }
@@ -54,6 +58,22 @@ impl BlockChainDB for AppDB {
 	fn trace_blooms(&self) -> &blooms_db::Database {
 		&self.trace_blooms
 	}
+
+	fn column_stats(&self) -> Vec<ColumnStats> {
+		let property = |col, name| self.rocksdb.property_int_value(Some(col), name).ok().and_then(|v| v).unwrap_or(0);
+		(0..self.rocksdb.num_columns())
+			.map(|col| ColumnStats {
+				column: col,
+				size: property(col, "rocksdb.estimate-live-data-size"),
+				estimated_keys: property(col, "rocksdb.estimate-num-keys"),
+				pending_compaction_bytes: property(col, "rocksdb.estimate-pending-compaction-bytes"),
+			})
+			.collect()
+	}
+
+	fn compact_column(&self, col: u32) -> io::Result<()> {
+		self.rocksdb.compact_range(Some(col))
+	}
 }
 
 /// Open a secret store DB using the given secret store data path. The DB path is one level beneath the data path.
@@ -111,8 +131,10 @@ pub fn open_database(client_path: &str, config: &DatabaseConfig) -> io::Result<A
 	fs::create_dir_all(&blooms_path)?;
 	fs::create_dir_all(&trace_blooms_path)?;
 
+	let rocksdb = Arc::new(Database::open(&config, client_path)?);
 	let db = AppDB {
-		key_value: Arc::new(Database::open(&config, client_path)?),
+		key_value: rocksdb.clone(),
+		rocksdb,
 		blooms: blooms_db::Database::open(blooms_path)?,
 		trace_blooms: blooms_db::Database::open(trace_blooms_path)?,
 	};
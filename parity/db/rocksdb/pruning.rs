@@ -0,0 +1,206 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Conversion of an archive database into one pruned with `OverlayRecent`.
+//!
+//! Every column except the state is copied verbatim, followed by the state of the best block.
+//! Progress is recorded in a marker file inside the destination, so an interrupted migration
+//! picks up where it left off. The source database is only ever read.
+
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use ethcore::client::DatabaseCompactionProfile;
+use ethcore_db::{COL_STATE, NUM_COLUMNS};
+use ethereum_types::H256;
+use journaldb::{self, Algorithm};
+use kvdb::DBTransaction;
+use state_db::migration;
+
+use cache::CacheConfig;
+use super::ethcore_blockchain::{BlockChain, BlockChainDB};
+use super::open_db;
+
+/// Name of the file recording migration progress.
+const MARKER_FILE: &str = "pruning_migration";
+/// Number of keys written in a single transaction while copying columns.
+const KEYS_PER_BATCH: usize = 16 * 1024;
+/// Minimal interval between two progress reports while copying state.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+// How far an earlier run of the migration got.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Stage {
+	// Columns below the given index are copied.
+	Columns(u32),
+	// All columns and the state are copied, only verification is left.
+	State,
+}
+
+// Marker contents: the best block being migrated and the completed stage.
+fn read_marker(path: &Path) -> Option<(H256, Stage)> {
+	let contents = fs::read_to_string(path).ok()?;
+	let mut parts = contents.split_whitespace();
+	let hash = H256::from_str(parts.next()?).ok()?;
+	let stage = match (parts.next()?, parts.next()) {
+		("columns", Some(n)) => Stage::Columns(n.parse().ok()?),
+		("state", None) => Stage::State,
+		_ => return None,
+	};
+	Some((hash, stage))
+}
+
+fn write_marker(path: &Path, hash: &H256, stage: Stage) -> Result<(), String> {
+	let contents = match stage {
+		Stage::Columns(n) => format!("{:x} columns {}", hash, n),
+		Stage::State => format!("{:x} state", hash),
+	};
+	fs::write(path, contents).map_err(|e| format!("Error writing migration marker: {}", e))
+}
+
+fn copy_dir_files(from: &Path, to: &Path) -> Result<(), String> {
+	fs::create_dir_all(to).map_err(|e| format!("Error creating {}: {}", to.display(), e))?;
+	let entries = fs::read_dir(from).map_err(|e| format!("Error reading {}: {}", from.display(), e))?;
+	for entry in entries {
+		let entry = entry.map_err(|e| format!("Error reading {}: {}", from.display(), e))?;
+		if entry.path().is_file() {
+			fs::copy(entry.path(), to.join(entry.file_name()))
+				.map_err(|e| format!("Error copying {}: {}", entry.path().display(), e))?;
+		}
+	}
+	Ok(())
+}
+
+fn copy_column(source: &dyn BlockChainDB, dest: &dyn BlockChainDB, col: u32) -> Result<usize, String> {
+	let mut keys = 0;
+	let mut batch = DBTransaction::new();
+	for (key, value) in source.key_value().iter(Some(col)) {
+		batch.put(Some(col), &key, &value);
+		keys += 1;
+		if keys % KEYS_PER_BATCH == 0 {
+			dest.key_value().write(batch).map_err(|e| format!("Error writing column {}: {}", col, e))?;
+			batch = DBTransaction::new();
+		}
+	}
+	dest.key_value().write(batch).map_err(|e| format!("Error writing column {}: {}", col, e))?;
+	Ok(keys)
+}
+
+/// Convert the archive client database at `source` into an `OverlayRecent` one at `dest`,
+/// keeping only the state of the best block. Restartable; `dest` holds a complete,
+/// verified database once this returns successfully.
+pub fn migrate_pruning(
+	source: &Path,
+	dest: &Path,
+	genesis: &[u8],
+	cache_config: &CacheConfig,
+	compaction: &DatabaseCompactionProfile,
+) -> Result<(), String> {
+	let open = |path: &Path| open_db(&path.to_string_lossy(), cache_config, compaction)
+		.map_err(|e| format!("Error opening database {}: {}", path.display(), e));
+
+	let source_db = open(source)?;
+	let best = BlockChain::new(Default::default(), genesis, source_db.clone()).best_block_header();
+	let (number, hash, state_root) = (best.number(), best.hash(), *best.state_root());
+
+	let marker = dest.join(MARKER_FILE);
+	let mut stage = match read_marker(&marker) {
+		Some((marker_hash, stage)) if marker_hash == hash => {
+			info!("Resuming pruning migration of block #{} ({:?})", number, stage);
+			stage
+		},
+		Some(_) => {
+			info!("Best block changed since the last migration attempt, starting over");
+			fs::remove_dir_all(dest).map_err(|e| format!("Error removing {}: {}", dest.display(), e))?;
+			Stage::Columns(0)
+		},
+		None => Stage::Columns(0),
+	};
+
+	fs::create_dir_all(dest).map_err(|e| format!("Error creating {}: {}", dest.display(), e))?;
+	if stage == Stage::Columns(0) {
+		copy_dir_files(&source.join("blooms"), &dest.join("blooms"))?;
+		copy_dir_files(&source.join("trace_blooms"), &dest.join("trace_blooms"))?;
+	}
+	let dest_db = open(dest)?;
+
+	let num_columns = NUM_COLUMNS.expect("main db has columns; qed");
+	while let Stage::Columns(col) = stage {
+		if col == num_columns {
+			let source_state = journaldb::new(source_db.key_value().clone(), Algorithm::Archive, COL_STATE);
+			let mut dest_state = journaldb::new(dest_db.key_value().clone(), Algorithm::OverlayRecent, COL_STATE);
+			let mut last_report = Instant::now();
+			info!("Copying state of block #{} ({:?})", number, state_root);
+			migration::copy_state(&*source_state, &mut *dest_state, &state_root, |accounts, nodes| {
+				if last_report.elapsed() >= PROGRESS_INTERVAL {
+					info!("Copied {} accounts, {} state nodes", accounts, nodes);
+					last_report = Instant::now();
+				}
+			}).map_err(|e| format!("Error copying state: {}", e))?;
+			migration::finalize(&mut *dest_state, number, &hash)
+				.map_err(|e| format!("Error writing state journal: {}", e))?;
+			stage = Stage::State;
+		} else {
+			if Some(col) != COL_STATE {
+				let keys = copy_column(&*source_db, &*dest_db, col)?;
+				info!("Copied column {}/{} ({} keys)", col + 1, num_columns, keys);
+			}
+			stage = Stage::Columns(col + 1);
+		}
+		write_marker(&marker, &hash, stage)?;
+	}
+
+	info!("Verifying migrated database");
+	let dest_best = BlockChain::new(Default::default(), genesis, dest_db.clone()).best_block_hash();
+	if dest_best != hash {
+		return Err(format!("Migrated database has best block {:?}, expected {:?}", dest_best, hash));
+	}
+	let dest_state = journaldb::new(dest_db.key_value().clone(), Algorithm::OverlayRecent, COL_STATE);
+	let accounts = migration::verify_state(&*dest_state, &state_root)
+		.map_err(|e| format!("Migrated state of block #{} is incomplete: {}", number, e))?;
+	info!("Verified state of block #{}: {} accounts", number, accounts);
+
+	fs::remove_file(&marker).map_err(|e| format!("Error removing migration marker: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs;
+
+	use ethereum_types::H256;
+	use tempdir::TempDir;
+
+	use super::{read_marker, write_marker, Stage};
+
+	#[test]
+	fn marker_roundtrip() {
+		let dir = TempDir::new("").unwrap();
+		let path = dir.path().join("marker");
+		let hash = H256::from_low_u64_be(42);
+		assert_eq!(read_marker(&path), None);
+
+		write_marker(&path, &hash, Stage::Columns(3)).unwrap();
+		assert_eq!(read_marker(&path), Some((hash, Stage::Columns(3))));
+
+		write_marker(&path, &hash, Stage::State).unwrap();
+		assert_eq!(read_marker(&path), Some((hash, Stage::State)));
+
+		fs::write(&path, "garbage").unwrap();
+		assert_eq!(read_marker(&path), None);
+	}
+}
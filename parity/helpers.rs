@@ -19,7 +19,7 @@ use std::io::{Write, BufReader, BufRead};
 use std::time::Duration;
 use std::fs::File;
 use std::collections::HashSet;
-use ethereum_types::{U256, Address};
+use ethereum_types::{U256, H256, Address};
 use journaldb::Algorithm;
 use ethcore::client::{VMType, DatabaseCompactionProfile, ClientConfig};
 use ethcore::miner::{PendingSet, Penalization};
@@ -36,6 +36,7 @@ use ethkey::Password;
 use types::{
 	ids::BlockId,
 	client_types::Mode,
+	snapshot::Compression,
 };
 
 pub fn to_duration(s: &str) -> Result<Duration, String> {
@@ -103,6 +104,10 @@ pub fn to_u256(s: &str) -> Result<U256, String> {
 	}
 }
 
+pub fn to_h256(s: &str) -> Result<H256, String> {
+	clean_0x(s).parse().map_err(|_| format!("Invalid hash: {}", s))
+}
+
 pub fn to_pending_set(s: &str) -> Result<PendingSet, String> {
 	match s {
 		"cheap" => Ok(PendingSet::AlwaysQueue),
@@ -119,6 +124,23 @@ pub fn to_queue_strategy(s: &str) -> Result<PrioritizationStrategy, String> {
 	}
 }
 
+pub fn to_snapshot_compression(s: &str) -> Result<Compression, String> {
+	match s {
+		"snappy" => Ok(Compression::Snappy),
+		"zstd" => Ok(Compression::Zstd),
+		other => Err(format!("Invalid snapshot compression: {}", other)),
+	}
+}
+
+/// Converts a price bump percentage to basis points.
+pub fn to_price_bump(percent: f64) -> Result<u32, String> {
+	if percent.is_finite() && percent >= 0.0 && percent <= 1_000.0 {
+		Ok((percent * 100.0).round() as u32)
+	} else {
+		Err(format!("Invalid price bump: {}. Must be a percentage between 0 and 1000.", percent))
+	}
+}
+
 pub fn to_queue_penalization(time: Option<u64>) -> Result<Penalization, String> {
 	Ok(match time {
 		Some(threshold_ms) => Penalization::Enabled {
@@ -358,7 +380,7 @@ mod tests {
 		ids::BlockId,
 		client_types::Mode,
 	};
-	use super::{to_duration, to_mode, to_block_id, to_u256, to_pending_set, to_address, to_addresses, to_price, geth_ipc_path, to_bootnodes, join_set, password_from_file};
+	use super::{to_duration, to_mode, to_block_id, to_u256, to_pending_set, to_address, to_addresses, to_price, to_price_bump, geth_ipc_path, to_bootnodes, join_set, password_from_file};
 
 	#[test]
 	fn test_to_duration() {
@@ -410,6 +432,15 @@ mod tests {
 		assert!(to_u256("u").is_err())
 	}
 
+	#[test]
+	fn test_to_price_bump() {
+		assert_eq!(to_price_bump(12.5).unwrap(), 1250);
+		assert_eq!(to_price_bump(10.0).unwrap(), 1000);
+		assert_eq!(to_price_bump(0.0).unwrap(), 0);
+		assert!(to_price_bump(-1.0).is_err());
+		assert!(to_price_bump(::std::f64::NAN).is_err());
+	}
+
 	#[test]
 	fn test_pending_set() {
 		assert_eq!(to_pending_set("cheap").unwrap(), PendingSet::AlwaysQueue);
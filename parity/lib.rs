@@ -74,6 +74,7 @@ extern crate parity_version;
 extern crate registrar;
 extern crate snapshot;
 extern crate spec;
+extern crate state_db;
 extern crate verification;
 
 #[macro_use]
@@ -113,6 +114,7 @@ mod run;
 mod secretstore;
 mod signer;
 mod snapshot_cmd;
+mod state_dump;
 mod upgrade;
 mod user_defaults;
 mod db;
@@ -44,6 +44,7 @@ pub struct HttpConfiguration {
 	pub server_threads: usize,
 	pub processing_threads: usize,
 	pub max_payload: usize,
+	pub max_batch_size: Option<usize>,
 	pub keep_alive: bool,
 }
 
@@ -59,6 +60,7 @@ impl Default for HttpConfiguration {
 			server_threads: 1,
 			processing_threads: 4,
 			max_payload: 5,
+			max_batch_size: None,
 			keep_alive: true,
 		}
 	}
@@ -97,6 +99,7 @@ pub struct WsConfiguration {
 	pub hosts: Option<Vec<String>>,
 	pub signer_path: PathBuf,
 	pub support_token_api: bool,
+	pub max_batch_size: Option<usize>,
 }
 
 impl Default for WsConfiguration {
@@ -112,6 +115,7 @@ impl Default for WsConfiguration {
 			hosts: Some(Vec::new()),
 			signer_path: replace_home(&data_dir, "$BASE/signer").into(),
 			support_token_api: true,
+			max_batch_size: None,
 		}
 	}
 }
@@ -151,11 +155,11 @@ pub fn new_ws<D: rpc_apis::Dependencies>(
 	let url = format!("{}:{}", conf.interface, conf.port);
 	let addr = url.parse().map_err(|_| format!("Invalid WebSockets listen host/port given: {}", url))?;
 
-	let full_handler = setup_apis(rpc_apis::ApiSet::All, deps);
+	let full_handler = setup_apis_with_max_batch_size(rpc_apis::ApiSet::All, deps, conf.max_batch_size);
 	let handler = {
 		let mut handler = MetaIoHandler::with_middleware((
 			rpc::WsDispatcher::new(full_handler),
-			Middleware::new(deps.stats.clone(), deps.apis.activity_notifier())
+			Middleware::new(deps.stats.clone(), deps.apis.activity_notifier()).with_max_batch_size(conf.max_batch_size)
 		));
 		let apis = conf.apis.list_apis();
 		deps.apis.extend_with_set(&mut handler, &apis);
@@ -209,7 +213,7 @@ pub fn new_http<D: rpc_apis::Dependencies>(
 	let domain = DAPPS_DOMAIN;
 	let url = format!("{}:{}", conf.interface, conf.port);
 	let addr = url.parse().map_err(|_| format!("Invalid {} listen host/port given: {}", id, url))?;
-	let handler = setup_apis(conf.apis, deps);
+	let handler = setup_apis_with_max_batch_size(conf.apis, deps, conf.max_batch_size);
 
 	let cors_domains = into_domains(conf.cors);
 	let allowed_hosts = into_domains(with_domain(conf.hosts, domain, &Some(url.clone().into())));
@@ -290,9 +294,15 @@ fn with_domain(items: Option<Vec<String>>, domain: &str, dapps_address: &Option<
 
 pub fn setup_apis<D>(apis: ApiSet, deps: &Dependencies<D>) -> MetaIoHandler<Metadata, Middleware<D::Notifier>>
 	where D: rpc_apis::Dependencies
+{
+	setup_apis_with_max_batch_size(apis, deps, None)
+}
+
+fn setup_apis_with_max_batch_size<D>(apis: ApiSet, deps: &Dependencies<D>, max_batch_size: Option<usize>) -> MetaIoHandler<Metadata, Middleware<D::Notifier>>
+	where D: rpc_apis::Dependencies
 {
 	let mut handler = MetaIoHandler::with_middleware(
-		Middleware::new(deps.stats.clone(), deps.apis.activity_notifier())
+		Middleware::new(deps.stats.clone(), deps.apis.activity_notifier()).with_max_batch_size(max_batch_size)
 	);
 	let apis = apis.list_apis();
 	deps.apis.extend_with_set(&mut handler, &apis);
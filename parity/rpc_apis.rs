@@ -18,6 +18,7 @@ use std::cmp::PartialEq;
 use std::collections::{BTreeMap, HashSet};
 use std::str::FromStr;
 use std::sync::{Arc, Weak};
+use std::time::Duration;
 
 pub use parity_rpc::signer::SignerService;
 
@@ -25,7 +26,7 @@ use account_utils::{self, AccountProvider};
 use ethcore::client::Client;
 use ethcore::miner::Miner;
 use snapshot::SnapshotService;
-use client_traits::BlockChainClient;
+use client_traits::{BlockChainClient, ChainInfo};
 use sync::SyncState;
 use ethcore_logger::RotatingLogger;
 use ethcore_private_tx::Provider as PrivateTransactionManager;
@@ -37,11 +38,11 @@ use light::{Cache as LightDataCache, TransactionQueue as LightTransactionQueue};
 use miner::external::ExternalMiner;
 use parity_rpc::dispatch::{FullDispatcher, LightDispatcher};
 use parity_rpc::informant::{ActivityNotifier, ClientNotifier};
-use parity_rpc::{Host, Metadata, NetworkSettings};
+use parity_rpc::{Host, Metadata, NetworkSettings, PubSubStats};
 use parity_rpc::v1::traits::TransactionsPool;
 use parity_runtime::Executor;
 use parking_lot::{Mutex, RwLock};
-use sync::{LightSync, ManageNetwork, SyncProvider};
+use sync::{LightSync, LightSyncInfo, ManageNetwork, SyncProvider};
 use updater::Updater;
 
 #[derive(Debug, PartialEq, Clone, Eq, Hash)]
@@ -79,6 +80,8 @@ pub enum Api {
 	Debug,
 	/// Parity Transactions pool PubSub
 	ParityTransactionsPool,
+	/// Geth-compatible transaction pool inspection (Safe)
+	TxPool,
 	/// Deprecated api
 	Deprecated,
 }
@@ -104,6 +107,7 @@ impl FromStr for Api {
 			"secretstore" => Ok(SecretStore),
 			"signer" => Ok(Signer),
 			"traces" => Ok(Traces),
+			"txpool" => Ok(TxPool),
 			"web3" => Ok(Web3),
 			"parity_transactions_pool" => Ok(ParityTransactionsPool),
 			"shh" | "shh_pubsub" => Ok(Deprecated),
@@ -187,6 +191,7 @@ fn to_modules(apis: &HashSet<Api>) -> BTreeMap<String, String> {
 			Api::SecretStore => ("secretstore", "1.0"),
 			Api::Signer => ("signer", "1.0"),
 			Api::Traces => ("traces", "1.0"),
+			Api::TxPool => ("txpool", "1.0"),
 			Api::Web3 => ("web3", "1.0"),
 			Api::ParityTransactionsPool => ("parity_transactions_pool", "1.0"),
 			Api::Deprecated => {
@@ -249,10 +254,14 @@ pub struct FullDependencies {
 	pub geth_compatibility: bool,
 	pub experimental_rpcs: bool,
 	pub ws_address: Option<Host>,
+	pub pubsub_stats: Arc<PubSubStats>,
 	pub fetch: FetchClient,
 	pub executor: Executor,
-	pub gas_price_percentile: usize,
 	pub poll_lifetime: u32,
+	pub tracing_max_count: usize,
+	pub rpc_gas_cap: Option<u64>,
+	pub rpc_call_timeout: Option<Duration>,
+	pub rpc_revert_reasons: bool,
 	pub allow_missing_blocks: bool,
 	pub no_ancient_blocks: bool,
 }
@@ -275,7 +284,6 @@ impl FullDependencies {
 			self.client.clone(),
 			self.miner.clone(),
 			nonces.clone(),
-			self.gas_price_percentile,
 		);
 		let account_signer = Arc::new(dispatch::Signer::new(self.accounts.clone())) as _;
 		let accounts = account_utils::accounts_list(self.accounts.clone());
@@ -303,10 +311,12 @@ impl FullDependencies {
 							pending_nonce_from_queue: self.geth_compatibility,
 							allow_pending_receipt_query: !self.geth_compatibility,
 							send_block_number_in_get_work: !self.geth_compatibility,
-							gas_price_percentile: self.gas_price_percentile,
 							allow_missing_blocks: self.allow_missing_blocks,
 							allow_experimental_rpcs: self.experimental_rpcs,
-							no_ancient_blocks: self.no_ancient_blocks
+							no_ancient_blocks: self.no_ancient_blocks,
+							gas_cap: self.rpc_gas_cap.map(Into::into),
+							call_timeout: self.rpc_call_timeout,
+							revert_reasons: self.rpc_revert_reasons,
 						}
 					);
 					handler.extend_with(client.to_delegate());
@@ -325,9 +335,15 @@ impl FullDependencies {
 				Api::EthPubSub => {
 					if !for_generic_pubsub {
 						let pool_receiver = self.miner.pending_transactions_receiver();
-						let mut client =
-							EthPubSubClient::new(self.client.clone(), self.executor.clone(), pool_receiver);
+						let mut client = EthPubSubClient::new(
+							self.client.clone(),
+							self.executor.clone(),
+							pool_receiver,
+							self.pubsub_stats.clone(),
+						);
+						client.add_transactions_notifier(self.miner.pending_transaction_bodies_receiver());
 						let weak_client = Arc::downgrade(&self.client);
+						let weak_sync = Arc::downgrade(&self.sync);
 
 						client.add_sync_notifier(self.sync.sync_notification(), move |state| {
 							let client = weak_client.upgrade()?;
@@ -335,10 +351,22 @@ impl FullDependencies {
 
 							let is_syncing_state = match state { SyncState::Idle | SyncState::NewBlocks => false, _ => true };
 							let is_verifying = queue_info.unverified_queue_size + queue_info.verified_queue_size > 3;
-
-							Some(PubSubSyncStatus {
-								syncing: is_verifying || is_syncing_state,
-							})
+							let syncing = is_verifying || is_syncing_state;
+
+							let status = if syncing {
+								let status = weak_sync.upgrade()?.status();
+								Some(SyncInfo {
+									starting_block: status.start_block_number.into(),
+									current_block: client.chain_info().best_block_number.into(),
+									highest_block: status.highest_block_number.unwrap_or(status.start_block_number).into(),
+									warp_chunks_amount: None,
+									warp_chunks_processed: None,
+								})
+							} else {
+								None
+							};
+
+							Some(PubSubSyncStatus { syncing, status })
 						});
 
 						if let Some(h) = client.handler().upgrade() {
@@ -354,6 +382,9 @@ impl FullDependencies {
 						handler.extend_with(TransactionsPoolClient::to_delegate(client));
 					}
 				}
+				Api::TxPool => {
+					handler.extend_with(TxPoolClient::new(self.client.clone(), self.miner.clone()).to_delegate());
+				}
 				Api::Personal => {
 					#[cfg(feature = "accounts")]
 					handler.extend_with(
@@ -391,6 +422,7 @@ impl FullDependencies {
 							self.settings.clone(),
 							signer,
 							self.ws_address.clone(),
+							self.pubsub_stats.clone(),
 							self.snapshot.clone().into(),
 						).to_delegate(),
 					);
@@ -408,9 +440,9 @@ impl FullDependencies {
 							.retain(ApiSet::PubSub)
 							.list_apis();
 						self.extend_api(&mut rpc, &apis, true);
-						handler.extend_with(
-							PubSubClient::new(rpc, self.executor.clone()).to_delegate(),
-						);
+						let mut client = PubSubClient::new(rpc, self.executor.clone());
+						client.add_pool_events_notifier(self.miner.pool_events_receiver());
+						handler.extend_with(client.to_delegate());
 					}
 				}
 				Api::ParityAccounts => {
@@ -424,18 +456,21 @@ impl FullDependencies {
 							&self.miner,
 							&self.updater,
 							&self.net_service,
+							self.sync.clone(),
+							self.snapshot.clone().into(),
 							self.fetch.clone(),
 						).to_delegate(),
 					);
 					#[cfg(feature = "accounts")]
 					handler.extend_with(
 						ParitySetAccountsClient::new(
+							&self.client,
 							&self.accounts,
 							&self.miner,
 						).to_delegate(),
 					);
 				}
-				Api::Traces => handler.extend_with(TracesClient::new(&self.client).to_delegate()),
+				Api::Traces => handler.extend_with(TracesClient::new(&self.client, self.tracing_max_count).to_delegate()),
 				Api::Rpc => {
 					let modules = to_modules(&apis);
 					handler.extend_with(RpcClient::new(modules).to_delegate());
@@ -493,6 +528,7 @@ pub struct LightDependencies<T> {
 	pub cache: Arc<Mutex<LightDataCache>>,
 	pub transaction_queue: Arc<RwLock<LightTransactionQueue>>,
 	pub ws_address: Option<Host>,
+	pub pubsub_stats: Arc<PubSubStats>,
 	pub fetch: FetchClient,
 	pub geth_compatibility: bool,
 	pub experimental_rpcs: bool,
@@ -564,10 +600,12 @@ impl<C: LightChainClient + 'static> LightDependencies<C> {
 						self.cache.clone(),
 						self.executor.clone(),
 						self.gas_price_percentile,
-						receiver
+						receiver,
+						self.pubsub_stats.clone(),
 					);
 
 					let weak_client = Arc::downgrade(&self.client);
+					let weak_sync = Arc::downgrade(&self.sync);
 
 					client.add_sync_notifier(self.sync.sync_notification(), move |state| {
 						let client = weak_client.upgrade()?;
@@ -575,10 +613,22 @@ impl<C: LightChainClient + 'static> LightDependencies<C> {
 
 						let is_syncing_state = match state { SyncState::Idle | SyncState::NewBlocks => false, _ => true };
 						let is_verifying = queue_info.unverified_queue_size + queue_info.verified_queue_size > 3;
+						let syncing = is_verifying || is_syncing_state;
+
+						let status = if syncing {
+							let sync = weak_sync.upgrade()?;
+							Some(SyncInfo {
+								starting_block: sync.start_block().into(),
+								current_block: client.chain_info().best_block_number.into(),
+								highest_block: sync.highest_block().unwrap_or_else(|| sync.start_block()).into(),
+								warp_chunks_amount: None,
+								warp_chunks_processed: None,
+							})
+						} else {
+							None
+						};
 
-						Some(PubSubSyncStatus {
-							syncing: is_verifying || is_syncing_state,
-						})
+						Some(PubSubSyncStatus { syncing, status })
 					});
 
 					self.client.add_listener(client.handler() as Weak<_>);
@@ -591,6 +641,9 @@ impl<C: LightChainClient + 'static> LightDependencies<C> {
 						handler.extend_with(TransactionsPoolClient::to_delegate(client));
 					}
 				}
+				Api::TxPool => {
+					warn!(target: "rpc", "Txpool API is not available in light client mode.")
+				}
 				Api::Personal => {
 					#[cfg(feature = "accounts")]
 					handler.extend_with(
@@ -624,6 +677,7 @@ impl<C: LightChainClient + 'static> LightDependencies<C> {
 							self.settings.clone(),
 							signer,
 							self.ws_address.clone(),
+							self.pubsub_stats.clone(),
 							self.gas_price_percentile,
 						).to_delegate(),
 					);
@@ -707,6 +761,7 @@ impl ApiSet {
 			Api::Parity,
 			Api::Rpc,
 			Api::Private,
+			Api::TxPool,
 		]
 			.into_iter()
 			.cloned()
@@ -778,6 +833,7 @@ mod test {
 		assert_eq!(Api::SecretStore, "secretstore".parse().unwrap());
 		assert_eq!(Api::Private, "private".parse().unwrap());
 		assert_eq!(Api::ParityTransactionsPool, "parity_transactions_pool".parse().unwrap());
+		assert_eq!(Api::TxPool, "txpool".parse().unwrap());
 		assert!("rp".parse::<Api>().is_err());
 	}
 
@@ -808,6 +864,7 @@ mod test {
 			Api::Rpc,
 			Api::Private,
 			Api::ParityTransactionsPool,
+			Api::TxPool,
 		].into_iter()
 		.collect();
 		assert_eq!(ApiSet::UnsafeContext.list_apis(), expected);
@@ -827,6 +884,7 @@ mod test {
 			Api::Rpc,
 			Api::Private,
 			Api::ParityTransactionsPool,
+			Api::TxPool,
 			// semi-safe
 			Api::ParityAccounts,
 		].into_iter()
@@ -856,6 +914,7 @@ mod test {
 					Api::Private,
 					Api::Debug,
 					Api::ParityTransactionsPool,
+					Api::TxPool,
 				].into_iter()
 				.collect()
 			)
@@ -883,6 +942,7 @@ mod test {
 					Api::Private,
 					Api::Debug,
 					Api::ParityTransactionsPool,
+					Api::TxPool,
 				].into_iter()
 				.collect()
 			)
@@ -905,6 +965,7 @@ mod test {
 					Api::Rpc,
 					Api::Private,
 					Api::ParityTransactionsPool,
+					Api::TxPool,
 				].into_iter()
 				.collect()
 			)
@@ -92,6 +92,10 @@ pub struct RunCmd {
 	pub pruning: Pruning,
 	pub pruning_history: u64,
 	pub pruning_memory: usize,
+	/// Some if bodies, receipts and traces of ancient blocks should be pruned. Contains the number of blocks to keep.
+	pub ancient_blocks_history: Option<u64>,
+	/// Whether to maintain an index of blocks by log address and first topic.
+	pub index_logs: bool,
 	/// Some if execution should be daemonized. Contains pid_file path.
 	pub daemon: Option<String>,
 	pub logger_config: LogConfig,
@@ -111,6 +115,10 @@ pub struct RunCmd {
 	pub update_policy: UpdatePolicy,
 	pub mode: Option<Mode>,
 	pub tracing: Switch,
+	pub tracing_max_count: usize,
+	pub rpc_gas_cap: Option<u64>,
+	pub rpc_call_timeout: Option<Duration>,
+	pub rpc_revert_reasons: bool,
 	pub fat_db: Switch,
 	pub compaction: DatabaseCompactionProfile,
 	pub vm_type: VMType,
@@ -133,6 +141,7 @@ pub struct RunCmd {
 	pub serve_light: bool,
 	pub light: bool,
 	pub no_persistent_txqueue: bool,
+	pub tx_queue_locals_journal: Option<String>,
 	pub no_hardcoded_sync: bool,
 	pub max_round_blocks_to_import: usize,
 	pub on_demand_response_time_window: Option<u64>,
@@ -314,6 +323,7 @@ fn execute_light_impl<Cr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq
 		cache: cache.clone(),
 		transaction_queue: txq,
 		ws_address: cmd.ws_conf.address(),
+		pubsub_stats: Default::default(),
 		fetch,
 		geth_compatibility: cmd.geth_compatibility,
 		experimental_rpcs: cmd.experimental_rpcs,
@@ -443,6 +453,7 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 	}
 
 	sync_config.fork_block = spec.fork_block();
+	sync_config.eip1559_transition = spec.params().eip1559_transition;
 	let snapshot_supported =
 		if let Snapshotting::Unsupported = spec.engine.snapshot_mode() {
 			false
@@ -536,6 +547,8 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 	client_config.queue.verifier_settings = cmd.verifier_settings;
 	client_config.transaction_verification_queue_size = ::std::cmp::max(2048, txpool_size / 4);
 	client_config.snapshot = cmd.snapshot_conf.clone();
+	client_config.ancient_blocks_history = cmd.ancient_blocks_history;
+	client_config.index_logs = cmd.index_logs;
 
 	// set up bootnodes
 	let mut net_conf = cmd.net_conf;
@@ -616,6 +629,10 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 			Err(e) => warn!("Error loading cached pending transactions from disk: {}", e),
 		}
 
+		if let Some(ref path) = cmd.tx_queue_locals_journal {
+			miner.set_local_transactions_journal(&*client, db_dirs.db_root_path().join(path));
+		}
+
 		Arc::new(store)
 	};
 
@@ -719,11 +736,15 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 		geth_compatibility: cmd.geth_compatibility,
 		experimental_rpcs: cmd.experimental_rpcs,
 		ws_address: cmd.ws_conf.address(),
+		pubsub_stats: Default::default(),
 		fetch: fetch.clone(),
 		executor: runtime.executor(),
 		private_tx_service: Some(private_tx_service.clone()),
-		gas_price_percentile: cmd.gas_price_percentile,
 		poll_lifetime: cmd.poll_lifetime,
+		tracing_max_count: cmd.tracing_max_count,
+		rpc_gas_cap: cmd.rpc_gas_cap,
+		rpc_call_timeout: cmd.rpc_call_timeout,
+		rpc_revert_reasons: cmd.rpc_revert_reasons,
 		allow_missing_blocks: cmd.allow_missing_blocks,
 		no_ancient_blocks: !cmd.download_old_blocks,
 	});
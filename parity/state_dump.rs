@@ -0,0 +1,323 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Encoding of `parity export state` dumps and their conversion into a chain spec.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Read, Write};
+
+use ethereum_types::{Address, BigEndianHash, H256, U256};
+use hash::{KECCAK_EMPTY, KECCAK_NULL_RLP};
+use rlp::{self, PayloadInfo};
+use rustc_hex::{FromHex, ToHex};
+use serde_json;
+use types::state_dump::{DumpedAccount, DumpedStorageEntry, StateDumpFormat};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonStorageEntry {
+	key_hash: H256,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	key: Option<H256>,
+	value: U256,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonAccount {
+	address_hash: H256,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	address: Option<Address>,
+	balance: U256,
+	nonce: U256,
+	code_hash: H256,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	code: Option<String>,
+	storage_root: H256,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	storage: Vec<JsonStorageEntry>,
+}
+
+impl<'a> From<&'a DumpedAccount> for JsonAccount {
+	fn from(account: &'a DumpedAccount) -> Self {
+		JsonAccount {
+			address_hash: account.address_hash,
+			address: account.address,
+			balance: account.balance,
+			nonce: account.nonce,
+			code_hash: account.code_hash,
+			code: account.code.as_ref().map(|code| format!("0x{}", code.to_hex())),
+			storage_root: account.storage_root,
+			storage: account.storage.iter().map(|entry| JsonStorageEntry {
+				key_hash: entry.key_hash,
+				key: entry.key,
+				value: entry.value,
+			}).collect(),
+		}
+	}
+}
+
+impl JsonAccount {
+	fn into_dumped(self) -> Result<DumpedAccount, String> {
+		let code = match self.code {
+			Some(code) => Some(code.trim_start_matches("0x").from_hex()
+				.map_err(|e| format!("Invalid code of account {:?}: {}", self.address_hash, e))?),
+			None => None,
+		};
+		Ok(DumpedAccount {
+			address_hash: self.address_hash,
+			address: self.address,
+			balance: self.balance,
+			nonce: self.nonce,
+			code_hash: self.code_hash,
+			code,
+			storage_root: self.storage_root,
+			storage: self.storage.into_iter().map(|entry| DumpedStorageEntry {
+				key_hash: entry.key_hash,
+				key: entry.key,
+				value: entry.value,
+			}).collect(),
+		})
+	}
+}
+
+/// Append `account` to a state dump in the given format.
+pub fn write_account(out: &mut dyn Write, format: StateDumpFormat, account: &DumpedAccount) -> io::Result<()> {
+	match format {
+		StateDumpFormat::Json => {
+			serde_json::to_writer(&mut *out, &JsonAccount::from(account))?;
+			out.write_all(b"\n")
+		},
+		StateDumpFormat::Binary => out.write_all(&rlp::encode(account)),
+	}
+}
+
+// Read the next RLP-encoded account, `None` at the end of the input.
+fn read_binary_account(input: &mut dyn BufRead) -> Result<Option<DumpedAccount>, String> {
+	if input.fill_buf().map_err(|e| format!("Error reading state: {}", e))?.is_empty() {
+		return Ok(None);
+	}
+
+	// accounts are lists, the first byte tells how many length bytes follow.
+	let mut item = vec![0u8; 1];
+	input.read_exact(&mut item).map_err(|e| format!("Error reading state: {}", e))?;
+	let header_len = 1 + (item[0] as usize).saturating_sub(0xf7);
+	item.resize(header_len, 0);
+	input.read_exact(&mut item[1..]).map_err(|e| format!("Error reading state: {}", e))?;
+	let total = PayloadInfo::from(&item).map_err(|e| format!("Invalid account: {}", e))?.total();
+	item.resize(total, 0);
+	input.read_exact(&mut item[header_len..]).map_err(|e| format!("Truncated state dump: {}", e))?;
+	rlp::decode(&item).map(Some).map_err(|e| format!("Invalid account: {}", e))
+}
+
+/// Iterate over the accounts of a state dump written by `write_account`.
+pub fn read_accounts<'a>(mut input: Box<dyn BufRead + 'a>, format: StateDumpFormat) -> impl Iterator<Item = Result<DumpedAccount, String>> + 'a {
+	let mut line = String::new();
+	let mut failed = false;
+	::std::iter::from_fn(move || {
+		if failed {
+			return None;
+		}
+		let next = match format {
+			StateDumpFormat::Json => loop {
+				line.clear();
+				match input.read_line(&mut line) {
+					Ok(0) => break Ok(None),
+					Ok(_) if line.trim().is_empty() => continue,
+					Ok(_) => break serde_json::from_str::<JsonAccount>(&line)
+						.map_err(|e| format!("Invalid account: {}", e))
+						.and_then(JsonAccount::into_dumped)
+						.map(Some),
+					Err(e) => break Err(format!("Error reading state: {}", e)),
+				}
+			},
+			StateDumpFormat::Binary => read_binary_account(&mut *input),
+		};
+		failed = next.is_err();
+		next.transpose()
+	})
+}
+
+#[derive(Serialize)]
+struct GenesisAccount {
+	balance: U256,
+	nonce: U256,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	code: Option<String>,
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	storage: BTreeMap<H256, H256>,
+}
+
+fn genesis_account(account: DumpedAccount) -> Result<(Address, GenesisAccount), String> {
+	let address_hash = account.address_hash;
+	let address = account.address.ok_or_else(|| format!(
+		"Address of account {:?} is unknown. Export the state from a node synced with --fat-db on.", address_hash,
+	))?;
+
+	let code = match account.code {
+		Some(ref code) if code.is_empty() => None,
+		Some(code) => Some(format!("0x{}", code.to_hex())),
+		None if account.code_hash == KECCAK_EMPTY => None,
+		None => return Err(format!("Code of account {:?} is missing. Export the state without --no-code.", address)),
+	};
+
+	if account.storage.is_empty() && account.storage_root != KECCAK_NULL_RLP {
+		return Err(format!("Storage of account {:?} is missing. Export the state without --no-storage.", address));
+	}
+	let mut storage = BTreeMap::new();
+	for entry in account.storage {
+		let key = entry.key.ok_or_else(|| format!(
+			"Storage key {:?} of account {:?} is unknown. Export the state from a node synced with --fat-db on.",
+			entry.key_hash, address,
+		))?;
+		storage.insert(key, H256::from_uint(&entry.value));
+	}
+
+	Ok((address, GenesisAccount { balance: account.balance, nonce: account.nonce, code, storage }))
+}
+
+/// Write a chain spec fragment holding an `accounts` section with every account of a state
+/// dump, one account at a time. Fails unless the dump contains the addresses, storage keys and
+/// code needed to recreate each account. Returns the number of accounts written.
+pub fn write_genesis_accounts<I>(out: &mut dyn Write, accounts: I) -> Result<usize, String>
+	where I: Iterator<Item = Result<DumpedAccount, String>>
+{
+	let write_err = |e: io::Error| format!("Error writing accounts: {}", e);
+	let json_err = |e: serde_json::Error| format!("Error writing accounts: {}", e);
+
+	out.write_all(b"{\"accounts\": {").map_err(write_err)?;
+	let mut count = 0;
+	for account in accounts {
+		let (address, account) = genesis_account(account?)?;
+		if count != 0 {
+			out.write_all(b",").map_err(write_err)?;
+		}
+		out.write_all(b"\n\t").map_err(write_err)?;
+		serde_json::to_writer(&mut *out, &address).map_err(json_err)?;
+		out.write_all(b": ").map_err(write_err)?;
+		serde_json::to_writer(&mut *out, &account).map_err(json_err)?;
+		count += 1;
+	}
+	out.write_all(b"\n}}\n").map_err(write_err)?;
+	out.flush().map_err(write_err)?;
+	Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Cursor;
+
+	use ethereum_types::{Address, H256, U256};
+	use hash::{keccak, KECCAK_EMPTY, KECCAK_NULL_RLP};
+	use serde_json::{self, Value};
+	use types::state_dump::{DumpedAccount, DumpedStorageEntry, StateDumpFormat};
+
+	use super::{read_accounts, write_account, write_genesis_accounts};
+
+	fn accounts() -> Vec<DumpedAccount> {
+		let contract = Address::from_low_u64_be(1);
+		let user = Address::from_low_u64_be(2);
+		vec![
+			DumpedAccount {
+				address_hash: keccak(&contract),
+				address: Some(contract),
+				balance: U256::from(1000),
+				nonce: U256::from(1),
+				code_hash: keccak(&[0x60u8, 0x00]),
+				code: Some(vec![0x60, 0x00]),
+				storage_root: H256::from_low_u64_be(99),
+				storage: vec![DumpedStorageEntry { key_hash: keccak(H256::zero()), key: Some(H256::zero()), value: U256::from(5) }],
+			},
+			DumpedAccount {
+				address_hash: keccak(&user),
+				address: Some(user),
+				balance: U256::from(7),
+				nonce: U256::zero(),
+				code_hash: KECCAK_EMPTY,
+				code: Some(Vec::new()),
+				storage_root: KECCAK_NULL_RLP,
+				storage: Vec::new(),
+			},
+		]
+	}
+
+	fn roundtrip(format: StateDumpFormat) {
+		let mut out = Vec::new();
+		for account in &accounts() {
+			write_account(&mut out, format, account).unwrap();
+		}
+		let read = read_accounts(Box::new(Cursor::new(out)), format).collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(read, accounts());
+	}
+
+	#[test]
+	fn json_roundtrip() {
+		roundtrip(StateDumpFormat::Json);
+	}
+
+	#[test]
+	fn binary_roundtrip() {
+		roundtrip(StateDumpFormat::Binary);
+	}
+
+	#[test]
+	fn truncated_binary_dump_is_an_error() {
+		let mut out = Vec::new();
+		write_account(&mut out, StateDumpFormat::Binary, &accounts()[0]).unwrap();
+		out.pop();
+		let mut read = read_accounts(Box::new(Cursor::new(out)), StateDumpFormat::Binary);
+		assert!(read.next().unwrap().is_err());
+		assert!(read.next().is_none());
+	}
+
+	#[test]
+	fn converts_accounts_to_genesis() {
+		let mut out = Vec::new();
+		let count = write_genesis_accounts(&mut out, accounts().into_iter().map(Ok)).unwrap();
+		assert_eq!(count, 2);
+
+		let spec: Value = serde_json::from_slice(&out).unwrap();
+		let accounts = &spec["accounts"];
+		let contract = &accounts["0x0000000000000000000000000000000000000001"];
+		assert_eq!(contract["balance"], "0x3e8");
+		assert_eq!(contract["nonce"], "0x1");
+		assert_eq!(contract["code"], "0x6000");
+		assert_eq!(
+			contract["storage"]["0x0000000000000000000000000000000000000000000000000000000000000000"],
+			"0x0000000000000000000000000000000000000000000000000000000000000005"
+		);
+		let user = &accounts["0x0000000000000000000000000000000000000002"];
+		assert_eq!(user["balance"], "0x7");
+		assert!(user.get("code").is_none());
+		assert!(user.get("storage").is_none());
+	}
+
+	#[test]
+	fn genesis_requires_preimages_and_data() {
+		let mut no_address = accounts();
+		no_address[1].address = None;
+		let mut no_code = accounts();
+		no_code[0].code = None;
+		let mut no_storage = accounts();
+		no_storage[0].storage.clear();
+		let mut no_key = accounts();
+		no_key[0].storage[0].key = None;
+
+		for accounts in vec![no_address, no_code, no_storage, no_key] {
+			assert!(write_genesis_accounts(&mut Vec::new(), accounts.into_iter().map(Ok)).is_err());
+		}
+	}
+}
@@ -75,6 +75,7 @@ extern crate ethcore_network as network;
 extern crate ethcore_private_tx;
 extern crate ethcore_sync as sync;
 extern crate ethereum_types;
+extern crate ethjson;
 extern crate ethkey;
 extern crate ethstore;
 extern crate fetch;
@@ -109,8 +110,6 @@ extern crate rand_xorshift;
 #[cfg(test)]
 extern crate engine;
 
-#[cfg(test)]
-extern crate ethjson;
 #[cfg(test)]
 extern crate transaction_pool as txpool;
 
@@ -133,6 +132,15 @@ extern crate spec;
 #[cfg(test)]
 extern crate verification;
 
+#[cfg(test)]
+extern crate hash_db;
+#[cfg(test)]
+extern crate journaldb;
+#[cfg(test)]
+extern crate patricia_trie_ethereum as ethtrie;
+#[cfg(test)]
+extern crate trie_db as trie;
+
 pub extern crate jsonrpc_ws_server as ws;
 
 mod authcodes;
@@ -150,7 +158,7 @@ pub use http::{
 	AccessControlAllowOrigin, Host, DomainsValidation, cors::AccessControlAllowHeaders
 };
 
-pub use v1::{NetworkSettings, Metadata, Origin, informant, dispatch, signer};
+pub use v1::{NetworkSettings, Metadata, Origin, PubSubStats, informant, dispatch, signer};
 pub use v1::block_import::{is_major_importing_or_waiting};
 pub use v1::PubSubSyncStatus;
 pub use v1::extractors::{RpcExtractor, WsExtractor, WsStats, WsDispatcher};
@@ -37,7 +37,6 @@ pub struct FullDispatcher<C, M> {
 	client: Arc<C>,
 	miner: Arc<M>,
 	nonces: Arc<Mutex<nonce::Reservations>>,
-	gas_price_percentile: usize,
 }
 
 impl<C, M> FullDispatcher<C, M> {
@@ -46,13 +45,11 @@ impl<C, M> FullDispatcher<C, M> {
 		client: Arc<C>,
 		miner: Arc<M>,
 		nonces: Arc<Mutex<nonce::Reservations>>,
-		gas_price_percentile: usize,
 	) -> Self {
 		FullDispatcher {
 			client,
 			miner,
 			nonces,
-			gas_price_percentile,
 		}
 	}
 }
@@ -63,7 +60,6 @@ impl<C, M> Clone for FullDispatcher<C, M> {
 			client: self.client.clone(),
 			miner: self.miner.clone(),
 			nonces: self.nonces.clone(),
-			gas_price_percentile: self.gas_price_percentile,
 		}
 	}
 }
@@ -106,7 +102,7 @@ impl<C: miner::BlockChainClient + BlockChainClient, M: MinerService> Dispatcher
 			to: request.to,
 			nonce,
 			gas_price: request.gas_price.unwrap_or_else(|| {
-				default_gas_price(&*self.client, &*self.miner, self.gas_price_percentile)
+				default_gas_price(&*self.client, &*self.miner)
 			}),
 			gas: request.gas.unwrap_or_else(|| self.miner.sensible_gas_limit()),
 			value: request.value.unwrap_or_else(|| 0.into()),
@@ -70,6 +70,7 @@ pub use self::full::FullDispatcher;
 pub use self::signing::Signer;
 pub use v1::helpers::nonce::Reservations;
 
+use std::cmp;
 use std::fmt::Debug;
 use std::ops::Deref;
 use std::sync::Arc;
@@ -346,11 +347,17 @@ pub fn eth_data_hash(mut data: Bytes) -> H256 {
 }
 
 /// Extract the default gas price from a client and miner.
-pub fn default_gas_price<C, M>(client: &C, miner: &M, percentile: usize) -> U256 where
+///
+/// Falls back to the miner's sensible gas price (but not below the base fee) if the gas price
+/// oracle has no recent transactions to base the suggestion on.
+pub fn default_gas_price<C, M>(client: &C, miner: &M) -> U256 where
 	C: BlockChainClient,
 	M: MinerService,
 {
-	client.gas_price_corpus(100).percentile(percentile).cloned().unwrap_or_else(|| miner.sensible_gas_price())
+	miner.suggested_gas_price(client).unwrap_or_else(|| {
+		let base_fee = client.best_block_header().base_fee().unwrap_or_default();
+		cmp::max(miner.sensible_gas_price(), base_fee)
+	})
 }
 
 /// Convert RPC confirmation payload to signer confirmation payload.
@@ -17,7 +17,9 @@
 //! RPC Error codes and error objects
 
 use std::fmt;
+use std::time::Duration;
 
+use ethereum_types::U256;
 use jsonrpc_core::{futures, Result as RpcResult, Error, ErrorCode, Value};
 use rlp::DecoderError;
 use types::transaction::Error as TransactionError;
@@ -57,6 +59,7 @@ mod codes {
 	pub const REQUEST_REJECTED: i64 = -32040;
 	pub const REQUEST_REJECTED_LIMIT: i64 = -32041;
 	pub const REQUEST_NOT_FOUND: i64 = -32042;
+	pub const CONTINUATION_TOKEN_EXPIRED: i64 = -32043;
 	pub const ENCRYPTION_ERROR: i64 = -32055;
 	pub const ENCODING_ERROR: i64 = -32058;
 	pub const FETCH_ERROR: i64 = -32060;
@@ -123,6 +126,30 @@ pub fn request_rejected_param_limit(limit: u64, items_desc: &str) -> Error {
 	}
 }
 
+pub fn batch_too_large(calls: usize, limit: usize) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::REQUEST_REJECTED_LIMIT),
+		message: format!("Batch of {} calls exceeds the limit of {} calls.", calls, limit),
+		data: None,
+	}
+}
+
+pub fn gas_cap_exceeded(gas: U256, cap: U256) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::REQUEST_REJECTED_LIMIT),
+		message: format!("Gas {} exceeds the RPC gas cap of {}.", gas, cap),
+		data: None,
+	}
+}
+
+pub fn call_timeout(timeout: Duration) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::EXECUTION_ERROR),
+		message: format!("Call execution exceeded the timeout of {}ms.", timeout.as_millis()),
+		data: None,
+	}
+}
+
 pub fn account<T: fmt::Debug>(error: &str, details: T) -> Error {
 	Error {
 		code: ErrorCode::ServerError(codes::ACCOUNT_ERROR),
@@ -174,6 +201,22 @@ pub fn state_pruned() -> Error {
 	}
 }
 
+pub fn compaction_refused<T: fmt::Display>(reason: T) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::UNSUPPORTED_REQUEST),
+		message: "Database compaction can't be started.".into(),
+		data: Some(Value::String(reason.to_string())),
+	}
+}
+
+pub fn continuation_token_expired() -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::CONTINUATION_TOKEN_EXPIRED),
+		message: "Continuation token expired: the state of the queried block has been pruned.".into(),
+		data: None,
+	}
+}
+
 pub fn state_corrupt() -> Error {
 	internal("State corrupt", "")
 }
@@ -258,6 +301,15 @@ pub fn cannot_submit_block(err: EthcoreError) -> Error {
 	}
 }
 
+pub fn ancient_block_pruned(earliest_body: u64) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::UNSUPPORTED_REQUEST),
+		message: format!("Bodies, receipts and traces of blocks before #{} have been pruned. \
+			Run without --prune-ancient-blocks to keep them.", earliest_body),
+		data: None,
+	}
+}
+
 pub fn check_block_number_existence<'a, T, C>(
 	client: &'a C,
 	num: BlockNumber,
@@ -272,6 +324,11 @@ pub fn check_block_number_existence<'a, T, C>(
 				// tried to fetch block number and got nothing even though the block number is
 				// less than the latest block number
 				if block_number < client.chain_info().best_block_number && !options.allow_missing_blocks {
+					if let Some(earliest_body) = client.pruning_info().earliest_body {
+						if block_number != 0 && block_number < earliest_body {
+							return Err(ancient_block_pruned(earliest_body));
+						}
+					}
 					return Err(unavailable_block(options.no_ancient_blocks, false));
 				}
 			}
@@ -429,6 +486,12 @@ pub fn transaction_message(error: &TransactionError) -> String {
 		LimitReached => {
 			"There are too many transactions in the queue. Your transaction was dropped due to limit. Try increasing the fee.".into()
 		}
+		SenderLimitReached { limit } => {
+			format!("There are too many transactions from your account in the queue (limit: {}). Wait for some of them to be mined or replace one of them.", limit)
+		}
+		NonceTooHigh { limit, got } => {
+			format!("Transaction nonce is too far in the future (maximal: {}, got: {}). Send the transactions with lower nonces first.", limit, got)
+		}
 		InsufficientGas { minimal, got } => {
 			format!("Transaction gas is too low. There is not enough gas to cover minimal cost of the transaction (minimal: {}, got: {}). Try increasing supplied gas.", minimal, got)
 		}
@@ -450,6 +513,8 @@ pub fn transaction_message(error: &TransactionError) -> String {
 		NotAllowed => "Transaction is not permitted.".into(),
 		TooBig => "Transaction is too big, see chain specification for the limit.".into(),
 		InvalidRlp(ref descr) => format!("Invalid RLP data: {}", descr),
+		TransactionTypeNotEnabled => "Transaction type is not enabled yet, see chain specification for the transition.".into(),
+		MaxPriorityFeeHigherThanMaxFee => "Max priority fee per gas is higher than max fee per gas.".into(),
 	}
 }
 
@@ -470,6 +535,22 @@ pub fn transaction<T: Into<EthcoreError>>(error: T) -> Error {
 	}
 }
 
+pub fn transaction_mined() -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::TRANSACTION_ERROR),
+		message: "Transaction is already mined.".into(),
+		data: None,
+	}
+}
+
+pub fn transaction_not_queued() -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::TRANSACTION_ERROR),
+		message: "Transaction is not in the queue.".into(),
+		data: None,
+	}
+}
+
 pub fn decode<T: Into<EthcoreError>>(error: T) -> Error {
 	match error.into() {
 		EthcoreError::Decoder(ref dec_err) => rlp(dec_err.clone()),
@@ -494,23 +575,58 @@ pub fn call(error: CallError) -> Error {
 		CallError::StatePruned => state_pruned(),
 		CallError::StateCorrupt => state_corrupt(),
 		CallError::Exceptional(e) => exceptional(e),
+		CallError::Reverted(output) => reverted(&output),
 		CallError::Execution(e) => execution(e),
 		CallError::TransactionNotFound => internal("{}, this should not be the case with eth_call, most likely a bug.", CallError::TransactionNotFound),
 	}
 }
 
-pub fn vm(error: &VMError, output: &[u8]) -> Error {
+/// Decodes the reason of a revert with `Error(string)` data, as emitted by `revert("reason")` and
+/// failing `require`s.
+pub fn revert_reason(output: &[u8]) -> Option<String> {
+	const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+	if output.len() < 4 + 64 || output[..4] != ERROR_SELECTOR {
+		return None;
+	}
+	let data = &output[4..];
+	let word = |offset: usize| data.get(offset..offset + 32).map(U256::from_big_endian);
+	let offset = word(0)?;
+	if offset > U256::from(data.len()) {
+		return None;
+	}
+	let len = word(offset.as_usize())?;
+	let start = offset.as_usize() + 32;
+	if len > U256::from(data.len() - start) {
+		return None;
+	}
+	String::from_utf8(data[start..start + len.as_usize()].to_vec()).ok()
+}
+
+pub fn reverted(output: &[u8]) -> Error {
 	use rustc_hex::ToHex;
 
-	let data = match error {
-		&VMError::Reverted => format!("{} 0x{}", VMError::Reverted, output.to_hex()),
-		error => format!("{}", error),
+	let message = match revert_reason(output) {
+		Some(reason) => format!("execution reverted: {}", reason),
+		None => "execution reverted".into(),
 	};
 
+	Error {
+		code: ErrorCode::ServerError(codes::EXECUTION_ERROR),
+		message,
+		data: Some(Value::String(format!("0x{}", output.to_hex()))),
+	}
+}
+
+pub fn vm(error: &VMError, output: &[u8]) -> Error {
+	if let VMError::Reverted = *error {
+		return reverted(output);
+	}
+
 	Error {
 		code: ErrorCode::ServerError(codes::EXECUTION_ERROR),
 		message: "VM execution error.".into(),
-		data: Some(Value::String(data)),
+		data: Some(Value::String(format!("{}", error))),
 	}
 }
 
@@ -622,3 +738,34 @@ pub fn invalid_input() -> Error {
 		data: None
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rustc_hex::FromHex;
+
+	#[test]
+	fn should_decode_revert_reason() {
+		let output: Vec<u8> = "08c379a0\
+			0000000000000000000000000000000000000000000000000000000000000020\
+			0000000000000000000000000000000000000000000000000000000000000004\
+			6e6f706500000000000000000000000000000000000000000000000000000000".from_hex().unwrap();
+
+		let error = reverted(&output);
+		assert_eq!(error.code, ErrorCode::ServerError(codes::EXECUTION_ERROR));
+		assert_eq!(error.message, "execution reverted: nope");
+		assert_eq!(error.data, Some(Value::String(format!("0x{}", output.to_hex()))));
+	}
+
+	#[test]
+	fn should_not_decode_malformed_revert_data() {
+		let truncated: Vec<u8> = "08c379a0\
+			0000000000000000000000000000000000000000000000000000000000000020\
+			00000000000000000000000000000000000000000000000000000000000000ff\
+			6e6f706500000000000000000000000000000000000000000000000000000000".from_hex().unwrap();
+
+		assert_eq!(reverted(&truncated).message, "execution reverted");
+		assert_eq!(reverted(&[0xde, 0xad]).message, "execution reverted");
+		assert_eq!(reverted(&[]).data, Some(Value::String("0x".into())));
+	}
+}
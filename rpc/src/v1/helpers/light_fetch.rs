@@ -324,6 +324,7 @@ where
 	/// using `gas_price_percentile` if the estimated percentile is zero the `DEFAULT_GAS_PRICE` is returned
 	pub fn gas_price(&self) -> impl Future<Item = U256, Error = Error> + Send {
 		let gas_price_percentile = self.gas_price_percentile;
+		let base_fee = self.client.best_block_header().base_fee(self.client.engine().params().eip1559_transition);
 
 		dispatch::light::fetch_gas_price_corpus(
 			self.sync.clone(),
@@ -332,8 +333,10 @@ where
 			self.cache.clone(),
 		)
 		.map(move |corp| {
-			corp.percentile(gas_price_percentile)
-				.map_or_else(|| DEFAULT_GAS_PRICE.into(), |percentile| *percentile)
+			let gas_price = corp.percentile(gas_price_percentile)
+				.map_or_else(|| DEFAULT_GAS_PRICE.into(), |percentile| *percentile);
+			// the corpus only holds offered gas prices, so never quote below the base fee.
+			base_fee.map_or(gas_price, |base_fee| cmp::max(gas_price, base_fee))
 		})
 	}
 
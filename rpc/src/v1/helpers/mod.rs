@@ -33,6 +33,7 @@ pub mod nonce;
 pub mod secretstore;
 
 mod network_settings;
+mod notification_queue;
 mod poll_filter;
 mod poll_manager;
 mod requests;
@@ -44,6 +45,7 @@ mod signature;
 pub use self::dispatch::{Dispatcher, FullDispatcher, LightDispatcher};
 pub use self::signature::verify_signature;
 pub use self::network_settings::NetworkSettings;
+pub use self::notification_queue::{NotificationQueue, PubSubStats, DEFAULT_QUEUE_SIZE};
 pub use self::poll_manager::PollManager;
 pub use self::poll_filter::{PollFilter, SyncPollFilter, limit_logs};
 pub use self::requests::{
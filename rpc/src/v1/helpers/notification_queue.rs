@@ -0,0 +1,199 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bounded notification queues of pub-sub subscribers.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use jsonrpc_core::futures::{future::{self, Either, Loop}, Future};
+use jsonrpc_pubsub::{typed::Sink, SubscriptionId};
+use parity_runtime::Executor;
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use v1::types::pubsub::SubscriptionStats;
+
+/// Number of notifications kept for a subscriber that can't keep up before the oldest get dropped.
+pub const DEFAULT_QUEUE_SIZE: usize = 1024;
+
+/// Delivery counters of a single subscription.
+#[derive(Debug)]
+struct QueueStats {
+	kind: &'static str,
+	delivered: AtomicUsize,
+	dropped: AtomicUsize,
+	queued: AtomicUsize,
+}
+
+impl QueueStats {
+	fn new(kind: &'static str) -> Self {
+		QueueStats {
+			kind,
+			delivered: AtomicUsize::new(0),
+			dropped: AtomicUsize::new(0),
+			queued: AtomicUsize::new(0),
+		}
+	}
+}
+
+/// Delivery counters of all live subscriptions, by subscription id.
+#[derive(Debug, Default)]
+pub struct PubSubStats {
+	subscriptions: RwLock<BTreeMap<String, Arc<QueueStats>>>,
+}
+
+impl PubSubStats {
+	fn register(&self, id: &SubscriptionId, kind: &'static str) -> Arc<QueueStats> {
+		let stats = Arc::new(QueueStats::new(kind));
+		self.subscriptions.write().insert(id_string(id), stats.clone());
+		stats
+	}
+
+	/// Stop tracking the subscription with given id.
+	pub fn remove(&self, id: &SubscriptionId) {
+		self.subscriptions.write().remove(&id_string(id));
+	}
+
+	/// Counters of all live subscriptions.
+	pub fn subscriptions(&self) -> Vec<SubscriptionStats> {
+		self.subscriptions.read().iter().map(|(id, stats)| SubscriptionStats {
+			id: id.clone(),
+			kind: stats.kind.into(),
+			delivered: stats.delivered.load(Ordering::Relaxed),
+			dropped: stats.dropped.load(Ordering::Relaxed),
+			queued: stats.queued.load(Ordering::Relaxed),
+		}).collect()
+	}
+}
+
+fn id_string(id: &SubscriptionId) -> String {
+	match *id {
+		SubscriptionId::String(ref id) => id.clone(),
+		SubscriptionId::Number(id) => format!("{}", id),
+	}
+}
+
+struct State<T> {
+	items: VecDeque<T>,
+	sending: bool,
+}
+
+impl<T> State<T> {
+	/// Queue `value`, dropping the oldest item if already at `capacity`. Returns whether an item was dropped.
+	fn push(&mut self, value: T, capacity: usize) -> bool {
+		let dropped = self.items.len() >= capacity && self.items.pop_front().is_some();
+		self.items.push_back(value);
+		dropped
+	}
+}
+
+struct Inner<T> {
+	sink: Sink<T>,
+	capacity: usize,
+	state: Mutex<State<T>>,
+	stats: Arc<QueueStats>,
+}
+
+/// Subscriber sink buffering at most `capacity` notifications, dropping the oldest ones when
+/// the subscriber can't keep up instead of buffering without bounds.
+pub struct NotificationQueue<T> {
+	inner: Arc<Inner<T>>,
+}
+
+impl<T> Clone for NotificationQueue<T> {
+	fn clone(&self) -> Self {
+		NotificationQueue { inner: self.inner.clone() }
+	}
+}
+
+impl<T: Serialize + Send + 'static> NotificationQueue<T> {
+	/// Create a new queue for the subscription with given id and register its counters.
+	pub fn new(sink: Sink<T>, id: &SubscriptionId, kind: &'static str, capacity: usize, stats: &PubSubStats) -> Self {
+		NotificationQueue {
+			inner: Arc::new(Inner {
+				sink,
+				capacity,
+				state: Mutex::new(State { items: VecDeque::new(), sending: false }),
+				stats: stats.register(id, kind),
+			}),
+		}
+	}
+
+	/// Queue a notification, delivering it in order after the already queued ones.
+	pub fn notify(&self, executor: &Executor, value: T) {
+		let mut state = self.inner.state.lock();
+		if state.push(value, self.inner.capacity) {
+			self.inner.stats.dropped.fetch_add(1, Ordering::Relaxed);
+		}
+		self.inner.stats.queued.store(state.items.len(), Ordering::Relaxed);
+
+		if !state.sending {
+			state.sending = true;
+			executor.spawn(self.clone().deliver());
+		}
+	}
+
+	/// Send the queued notifications one by one, waiting for the transport to accept each.
+	fn deliver(self) -> impl Future<Item = (), Error = ()> {
+		future::loop_fn(self, |queue| {
+			let next = {
+				let mut state = queue.inner.state.lock();
+				let next = state.items.pop_front();
+				queue.inner.stats.queued.store(state.items.len(), Ordering::Relaxed);
+				if next.is_none() {
+					state.sending = false;
+				}
+				next
+			};
+
+			match next {
+				None => Either::A(future::ok(Loop::Break(()))),
+				Some(value) => Either::B(queue.inner.sink.notify(Ok(value)).then(move |res| match res {
+					Ok(_) => {
+						queue.inner.stats.delivered.fetch_add(1, Ordering::Relaxed);
+						Ok(Loop::Continue(queue))
+					},
+					Err(e) => {
+						warn!(target: "rpc", "Unable to send notification: {}", e);
+						let mut state = queue.inner.state.lock();
+						state.items.clear();
+						state.sending = false;
+						queue.inner.stats.queued.store(0, Ordering::Relaxed);
+						Ok(Loop::Break(()))
+					},
+				})),
+			}
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::VecDeque;
+	use super::State;
+
+	#[test]
+	fn should_drop_oldest_notifications_when_full() {
+		let mut state = State { items: VecDeque::new(), sending: false };
+
+		assert!(!state.push(1, 2));
+		assert!(!state.push(2, 2));
+		assert!(state.push(3, 2));
+		assert!(state.push(4, 2));
+		assert_eq!(state.items, vec![3, 4]);
+	}
+}
@@ -107,24 +107,28 @@ impl<T> Subscribers<T> {
 	}
 }
 
-impl<T> Subscribers<Sink<T>> {
-	/// Assigns id and adds a subscriber to the list.
-	pub fn push(&mut self, sub: Subscriber<T>) {
+impl<T> Subscribers<T> {
+	/// Assigns id and adds a subscriber to the list, building the entry from its sink and id.
+	pub fn push_with<S, F>(&mut self, sub: Subscriber<S>, f: F) where
+		F: FnOnce(Sink<S>, &SubscriptionId) -> T,
+	{
 		let id = self.next_id();
-		if let Ok(sink) = sub.assign_id(SubscriptionId::String(id.as_string())) {
+		let subscription_id = SubscriptionId::String(id.as_string());
+		if let Ok(sink) = sub.assign_id(subscription_id.clone()) {
 			debug!(target: "pubsub", "Adding subscription id={:?}", id);
-			self.subscriptions.insert(id, sink);
+			let val = f(sink, &subscription_id);
+			self.subscriptions.insert(id, val);
 		}
 	}
 }
 
-impl<T, V> Subscribers<(Sink<T>, V)> {
+impl<T> Subscribers<Sink<T>> {
 	/// Assigns id and adds a subscriber to the list.
-	pub fn push(&mut self, sub: Subscriber<T>, val: V) {
+	pub fn push(&mut self, sub: Subscriber<T>) {
 		let id = self.next_id();
 		if let Ok(sink) = sub.assign_id(SubscriptionId::String(id.as_string())) {
 			debug!(target: "pubsub", "Adding subscription id={:?}", id);
-			self.subscriptions.insert(id, (sink, val));
+			self.subscriptions.insert(id, sink);
 		}
 	}
 }
@@ -19,12 +19,19 @@
 use std::sync::Arc;
 
 use client_traits::BlockChainClient;
+use ethereum_types::H256;
+use types::call_analytics::CallAnalytics;
 use types::header::Header;
+use types::ids::TransactionId;
 use types::transaction::LocalizedTransaction;
 
 use jsonrpc_core::Result;
+use v1::helpers::errors;
 use v1::traits::Debug;
-use v1::types::{Block, Bytes, RichBlock, BlockTransactions, Transaction};
+use v1::types::{
+	Block, Bytes, RichBlock, BlockTransactions, Transaction,
+	CallFrame, DebugTrace, DebugTraceOptions, StructLogsTrace, CALL_TRACER,
+};
 
 /// Debug rpc implementation.
 pub struct DebugClient<C> {
@@ -81,6 +88,7 @@ impl<C: BlockChainClient + 'static> Debug for DebugClient<C> {
 					),
 					transactions_root: cast(block.header.transactions_root()),
 					extra_data: block.header.extra_data().clone().into(),
+					base_fee_per_gas: block.header.base_fee(),
 				},
 				extra_info: vec![
 					("reason".to_owned(), reason),
@@ -90,6 +98,28 @@ impl<C: BlockChainClient + 'static> Debug for DebugClient<C> {
 			}
 		}).collect())
 	}
+
+	fn trace_transaction(&self, transaction_hash: H256, options: Option<DebugTraceOptions>) -> Result<DebugTrace> {
+		let options = options.unwrap_or_default();
+		let id = TransactionId::Hash(transaction_hash);
+		match options.tracer {
+			None => self.client.replay_struct_logs(id, options.struct_logger_config())
+				.map(|executed| DebugTrace::StructLogs(StructLogsTrace::from(executed)))
+				.map_err(errors::call),
+			Some(ref tracer) if tracer == CALL_TRACER => {
+				let analytics = CallAnalytics {
+					transaction_tracing: true,
+					vm_tracing: false,
+					state_diffing: false,
+				};
+				let executed = self.client.replay(id, analytics).map_err(errors::call)?;
+				CallFrame::from_executed(executed)
+					.map(DebugTrace::Call)
+					.ok_or_else(|| errors::internal("Transaction replayed without a trace", ""))
+			},
+			Some(ref tracer) => Err(errors::invalid_params("tracer", format!("unsupported tracer {}, only {} is available", tracer, CALL_TRACER))),
+		}
+	}
 }
 
 fn serialize<T: ::serde::Serialize>(t: &T) -> String {
@@ -16,11 +16,11 @@
 
 //! Eth rpc implementation.
 
+use std::cmp;
 use std::thread;
 use std::time::{Instant, Duration, SystemTime, UNIX_EPOCH};
-use std::sync::Arc;
+use std::sync::{Arc, mpsc};
 
-use rlp::Rlp;
 use ethereum_types::{Address, H64, H160, H256, U64, U256, BigEndianHash};
 use parking_lot::Mutex;
 
@@ -33,14 +33,16 @@ use snapshot::SnapshotService;
 use hash::keccak;
 use miner::external::ExternalMinerService;
 use sync::SyncProvider;
+use vm;
 use types::{
 	BlockNumber as EthBlockNumber,
+	call_analytics::CallAnalytics,
 	client_types::StateResult,
 	encoded,
 	header::Header,
 	ids::{BlockId, TransactionId, UncleId},
 	filter::Filter as EthcoreFilter,
-	transaction::{SignedTransaction, LocalizedTransaction},
+	transaction::{SignedTransaction, LocalizedTransaction, UnverifiedTransaction},
 	snapshot::RestorationStatus,
 };
 
@@ -54,12 +56,15 @@ use v1::traits::Eth;
 use v1::types::{
 	RichBlock, Block, BlockTransactions, BlockNumber, Bytes, SyncStatus, SyncInfo,
 	Transaction, CallRequest, Index, Filter, Log, Receipt, Work, EthAccount, StorageProof,
-	block_number_to_id
+	FeeHistory, StateOverrides, block_number_to_id, into_state_override
 };
 use v1::metadata::Metadata;
 
 const EXTRA_INFO_PROOF: &str = "Object exists in blockchain (fetched earlier), extra_info is always available if object exists; qed";
 
+/// Maximum number of blocks `eth_feeHistory` reports on.
+const MAX_FEE_HISTORY_BLOCKS: u64 = 1024;
+
 /// Eth RPC options
 #[derive(Copy, Clone)]
 pub struct EthClientOptions {
@@ -69,8 +74,6 @@ pub struct EthClientOptions {
 	pub allow_pending_receipt_query: bool,
 	/// Send additional block number when asking for work
 	pub send_block_number_in_get_work: bool,
-	/// Gas Price Percentile used as default gas price.
-	pub gas_price_percentile: usize,
 	/// Return 'null' instead of an error if ancient block sync is still in
 	/// progress and the block information requested could not be found.
 	pub allow_missing_blocks: bool,
@@ -78,6 +81,12 @@ pub struct EthClientOptions {
 	pub allow_experimental_rpcs: bool,
 	/// flag for ancient block sync
 	pub no_ancient_blocks: bool,
+	/// Maximum gas `eth_call` and `eth_estimateGas` may use.
+	pub gas_cap: Option<U256>,
+	/// How long to wait for `eth_call` and `eth_estimateGas` to finish.
+	pub call_timeout: Option<Duration>,
+	/// Replay failed transactions to add their revert reason to receipts.
+	pub revert_reasons: bool,
 }
 
 impl EthClientOptions {
@@ -96,10 +105,12 @@ impl Default for EthClientOptions {
 			pending_nonce_from_queue: false,
 			allow_pending_receipt_query: true,
 			send_block_number_in_get_work: true,
-			gas_price_percentile: 50,
 			allow_missing_blocks: false,
 			allow_experimental_rpcs: false,
 			no_ancient_blocks: false,
+			gas_cap: None,
+			call_timeout: None,
+			revert_reasons: false,
 		}
 	}
 }
@@ -156,6 +167,52 @@ enum PendingTransactionId {
 	Location(PendingOrBlock, usize)
 }
 
+/// A block `eth_feeHistory` reports on, with the priority fee and gas used of each transaction.
+struct FeeHistoryBlock {
+	header: Header,
+	rewards: Vec<(U256, U256)>,
+}
+
+impl FeeHistoryBlock {
+	fn new<R>(header: Header, transactions: &[UnverifiedTransaction], gas_used: R) -> Self where
+		R: IntoIterator<Item = U256>,
+	{
+		let rewards = transactions.iter()
+			.zip(gas_used)
+			.map(|(tx, gas_used)| (tx.effective_priority_fee(header.base_fee()), gas_used))
+			.collect();
+
+		FeeHistoryBlock { header, rewards }
+	}
+
+	fn gas_used_ratio(&self) -> f64 {
+		match self.header.gas_limit().is_zero() {
+			true => 0.0,
+			false => self.header.gas_used().low_u64() as f64 / self.header.gas_limit().low_u64() as f64,
+		}
+	}
+
+	/// The priority fees paid at the given percentiles of the gas used in the block.
+	fn rewards(mut self, percentiles: &[f64]) -> Vec<U256> {
+		if self.rewards.is_empty() {
+			return vec![U256::zero(); percentiles.len()];
+		}
+
+		self.rewards.sort_by(|a, b| a.0.cmp(&b.0));
+		let block_gas_used = self.header.gas_used().low_u64() as f64;
+		let mut index = 0;
+		let mut gas_used = self.rewards[0].1;
+		percentiles.iter().map(|percentile| {
+			let threshold = block_gas_used * percentile / 100.0;
+			while (gas_used.low_u64() as f64) < threshold && index < self.rewards.len() - 1 {
+				index += 1;
+				gas_used += self.rewards[index].1;
+			}
+			self.rewards[index].0
+		}).collect()
+	}
+}
+
 pub fn base_logs<C, M, T: StateInfo + 'static> (client: &C, miner: &M, filter: Filter) -> BoxFuture<Vec<Log>> where
 	C: miner::BlockChainClient + BlockChainClient + StateClient<State=T> + Call<State=T>,
 	M: MinerService<State=T> {
@@ -266,6 +323,7 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T> EthClient<C, SN, S, M, EM> where
 		match (block, difficulty) {
 			(Some(block), Some(total_difficulty)) => {
 				let view = block.header_view();
+				let header = block.decode_header(self.client.engine().params().eip1559_transition);
 				Ok(Some(RichBlock {
 					inner: Block {
 						hash: match is_pending {
@@ -293,17 +351,23 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T> EthClient<C, SN, S, M, EM> where
 						timestamp: view.timestamp().into(),
 						difficulty: view.difficulty(),
 						total_difficulty: Some(total_difficulty),
-						seal_fields: view.seal().into_iter().map(Into::into).collect(),
+						seal_fields: header.seal().iter().cloned().map(Into::into).collect(),
 						uncles: block.uncle_hashes(),
 						transactions: match include_txs {
 							true => BlockTransactions::Full(block.view().localized_transactions().into_iter().map(Transaction::from_localized).collect()),
 							false => BlockTransactions::Hashes(block.transaction_hashes()),
 						},
 						extra_data: Bytes::new(view.extra_data()),
+						base_fee_per_gas: header.base_fee(),
 					},
 					extra_info: extra.expect(EXTRA_INFO_PROOF),
 				}))
 			},
+			// the header is known, but the body was pruned
+			(None, Some(_)) if !self.options.allow_missing_blocks => match self.client.pruning_info().earliest_body {
+				Some(earliest_body) => Err(errors::ancient_block_pruned(earliest_body)),
+				None => Ok(None),
+			},
 			_ => Ok(None)
 		}
 	}
@@ -391,7 +455,7 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T> EthClient<C, SN, S, M, EM> where
 				let uncle_id = UncleId { block: block_id, position };
 
 				let uncle = match client.uncle(uncle_id) {
-					Some(hdr) => match hdr.decode() {
+					Some(hdr) => match hdr.decode(client.engine().params().eip1559_transition) {
 						Ok(h) => h,
 						Err(e) => return Err(errors::decode(e))
 					},
@@ -435,12 +499,22 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T> EthClient<C, SN, S, M, EM> where
 				seal_fields: uncle.seal().iter().cloned().map(Into::into).collect(),
 				uncles: vec![],
 				transactions: BlockTransactions::Hashes(vec![]),
+				base_fee_per_gas: uncle.base_fee(),
 			},
 			extra_info: extra,
 		};
 		Ok(Some(block))
 	}
 
+	fn fee_history_block(&self, number: EthBlockNumber) -> Option<FeeHistoryBlock> {
+		let id = BlockId::Number(number);
+		let block = self.client.block(id)?;
+		let header = block.decode_header(self.client.engine().params().eip1559_transition);
+		let receipts = self.client.localized_block_receipts(id).unwrap_or_default();
+
+		Some(FeeHistoryBlock::new(header, &block.transactions(), receipts.into_iter().map(|r| r.gas_used)))
+	}
+
 	/// Get state for the given block number. Returns either the State or a block from which state
 	/// can be retrieved.
 	/// Note: When passing `BlockNumber::Pending` we fall back to the state of the current best block
@@ -467,6 +541,28 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T> EthClient<C, SN, S, M, EM> where
 		}
 	}
 
+	/// Reject calls requesting more gas than the cap, and give calls without gas the cap.
+	fn apply_gas_cap(&self, request: &mut helpers::CallRequest) -> Result<()> {
+		if let Some(cap) = self.options.gas_cap {
+			match request.gas {
+				Some(gas) if gas > cap => return Err(errors::gas_cap_exceeded(gas, cap)),
+				Some(_) => {},
+				None => request.gas = Some(cap),
+			}
+		}
+		Ok(())
+	}
+
+	/// Replay a failed transaction to decode the reason it reverted with. Failures other than
+	/// reverts, or transactions whose state is pruned, have no reason.
+	fn revert_reason(&self, hash: H256) -> Option<String> {
+		let executed = self.client.replay(TransactionId::Hash(hash), CallAnalytics::default()).ok()?;
+		match executed.exception {
+			Some(vm::Error::Reverted) => errors::revert_reason(&executed.output),
+			_ => None,
+		}
+	}
+
 	/// Get the state and header of best pending block. On failure, fall back to the best imported
 	/// blocks state&header.
 	fn pending_state_and_header_with_fallback(&self) -> (T, Header) {
@@ -486,6 +582,32 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T> EthClient<C, SN, S, M, EM> where
 	}
 }
 
+/// Runs `f` on its own thread and waits at most `timeout` for the result. The EVM can't be
+/// interrupted, so a call that times out keeps running in the background until it runs out of gas.
+fn execute_with_timeout<R, F>(timeout: Option<Duration>, f: F) -> Result<R> where
+	R: Send + 'static,
+	F: FnOnce() -> Result<R> + Send + 'static,
+{
+	let timeout = match timeout {
+		Some(timeout) => timeout,
+		None => return f(),
+	};
+
+	let (tx, rx) = mpsc::channel();
+	thread::Builder::new()
+		.name("rpc-call".into())
+		.spawn(move || {
+			let _ = tx.send(f());
+		})
+		.map_err(|e| errors::internal("Unable to spawn call thread", e))?;
+
+	match rx.recv_timeout(timeout) {
+		Ok(result) => result,
+		Err(mpsc::RecvTimeoutError::Timeout) => Err(errors::call_timeout(timeout)),
+		Err(mpsc::RecvTimeoutError::Disconnected) => Err(errors::internal("Call thread panicked", "")),
+	}
+}
+
 pub fn pending_logs<M>(miner: &M, best_block: EthBlockNumber, filter: &EthcoreFilter) -> Vec<Log> where M: MinerService {
 	let receipts = miner.pending_receipts(best_block).unwrap_or_default();
 
@@ -534,7 +656,7 @@ fn check_known<C>(client: &C, number: BlockNumber) -> Result<()> where C: BlockC
 
 const MAX_QUEUE_SIZE_TO_MINE_ON: usize = 4;	// because uncles go back 6.
 
-impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<C, SN, S, M, EM> where
+impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + Send + 'static> Eth for EthClient<C, SN, S, M, EM> where
 	C: miner::BlockChainClient + StateClient<State=T> + ProvingBlockChainClient + Call<State=T> + EngineInfo + 'static,
 	SN: SnapshotService + 'static,
 	S: SyncProvider + 'static,
@@ -602,7 +724,75 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 	}
 
 	fn gas_price(&self) -> BoxFuture<U256> {
-		Box::new(future::ok(default_gas_price(&*self.client, &*self.miner, self.options.gas_price_percentile)))
+		Box::new(future::ok(default_gas_price(&*self.client, &*self.miner)))
+	}
+
+	fn fee_history(&self, block_count: U256, newest_block: BlockNumber, reward_percentiles: Option<Vec<f64>>) -> BoxFuture<FeeHistory> {
+		let percentiles = reward_percentiles.unwrap_or_default();
+		if percentiles.iter().any(|p| !p.is_finite() || *p < 0.0 || *p > 100.0) || percentiles.windows(2).any(|w| w[0] > w[1]) {
+			return Box::new(future::err(errors::invalid_params("rewardPercentiles", "expected ascending values between 0 and 100")));
+		}
+
+		let block_count = cmp::min(block_count, MAX_FEE_HISTORY_BLOCKS.into()).low_u64();
+		if block_count == 0 {
+			return Box::new(future::ok(FeeHistory::default()));
+		}
+
+		let best_block = self.client.chain_info().best_block_number;
+		let mut blocks = Vec::new();
+		let newest = match newest_block {
+			BlockNumber::Pending => match self.miner.pending_block(best_block) {
+				Some(block) => {
+					let receipts = self.miner.pending_receipts(best_block).unwrap_or_default();
+					let gas_used = receipts.into_iter().map(|r| r.gas_used);
+					blocks.push(FeeHistoryBlock::new(block.header, &block.transactions, gas_used));
+					best_block
+				},
+				None => best_block,
+			},
+			BlockNumber::Latest => best_block,
+			BlockNumber::Earliest => 0,
+			BlockNumber::Num(number) => number,
+			BlockNumber::Hash { hash, .. } => match self.client.block_number(BlockId::Hash(hash)) {
+				Some(number) => number,
+				None => return Box::new(future::err(errors::unknown_block())),
+			},
+		};
+		if newest > best_block {
+			return Box::new(future::err(errors::unknown_block()));
+		}
+
+		// walk back from the newest block, stopping early at the genesis or at a missing block
+		let mut number = newest;
+		while (blocks.len() as u64) < block_count {
+			match self.fee_history_block(number) {
+				Some(block) => blocks.push(block),
+				None => break,
+			}
+			if number == 0 {
+				break;
+			}
+			number -= 1;
+		}
+		blocks.reverse();
+
+		let next_base_fee = match blocks.last() {
+			Some(block) => self.client.engine().machine().calc_base_fee(&block.header).unwrap_or_default(),
+			None => return Box::new(future::err(errors::unknown_block())),
+		};
+
+		let mut history = FeeHistory {
+			oldest_block: blocks[0].header.number().into(),
+			base_fee_per_gas: blocks.iter().map(|b| b.header.base_fee().unwrap_or_default()).collect(),
+			gas_used_ratio: blocks.iter().map(FeeHistoryBlock::gas_used_ratio).collect(),
+			reward: Vec::new(),
+		};
+		history.base_fee_per_gas.push(next_base_fee);
+		if !percentiles.is_empty() {
+			history.reward = blocks.into_iter().map(|b| b.rewards(&percentiles)).collect();
+		}
+
+		Box::new(future::ok(history))
 	}
 
 	fn accounts(&self) -> Result<Vec<H160>> {
@@ -629,8 +819,6 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 	}
 
 	fn proof(&self, address: H160, values: Vec<H256>, num: Option<BlockNumber>) -> BoxFuture<EthAccount> {
-		try_bf!(errors::require_experimental(self.options.allow_experimental_rpcs, "1186"));
-
 		let key1 = keccak(address);
 
 		let num = num.unwrap_or_default();
@@ -646,29 +834,31 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 		};
 
 		try_bf!(check_known(&*self.client, num.clone()));
-		let res = match self.client.prove_account(key1, id) {
-			Some((proof, account)) => Ok(EthAccount {
-				address,
-				balance: account.balance,
-				nonce: account.nonce,
-				code_hash: account.code_hash,
-				storage_hash: account.storage_root,
-				account_proof: proof.into_iter().map(Bytes::new).collect(),
-				storage_proof: values.into_iter().filter_map(|storage_index| {
-					let key2: H256 = storage_index;
-					self.client.prove_storage(key1, keccak(key2), id)
-					    .map(|(storage_proof, storage_value)| StorageProof {
-							key: key2.into_uint(),
-							value: storage_value.into_uint(),
-							proof: storage_proof.into_iter().map(Bytes::new).collect()
-						})
-					})
-					.collect::<Vec<StorageProof>>()
-			}),
-			None => Err(errors::state_pruned()),
+		// a missing account or storage slot is proven absent by the trie nodes on its path, so
+		// failing to build a proof means the state of the block is gone.
+		let (account_proof, account) = match self.client.prove_account(key1, id) {
+			Some(proof) => proof,
+			None => return Box::new(future::err(errors::state_pruned())),
 		};
-
-		Box::new(future::done(res))
+		let storage_proof = values.into_iter().map(|key2| {
+			self.client.prove_storage(key1, keccak(key2), id)
+				.map(|(storage_proof, storage_value)| StorageProof {
+					key: key2.into_uint(),
+					value: storage_value.into_uint(),
+					proof: storage_proof.into_iter().map(Bytes::new).collect(),
+				})
+				.ok_or_else(errors::state_pruned)
+		}).collect::<Result<Vec<StorageProof>>>();
+
+		Box::new(future::done(storage_proof.map(|storage_proof| EthAccount {
+			address,
+			balance: account.balance,
+			nonce: account.nonce,
+			code_hash: account.code_hash,
+			storage_hash: account.storage_root,
+			account_proof: account_proof.into_iter().map(Bytes::new).collect(),
+			storage_proof,
+		})))
 	}
 
 	fn storage_at(&self, address: H160, position: U256, num: Option<BlockNumber>) -> BoxFuture<H256> {
@@ -831,8 +1021,14 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 			}
 		}
 
-		let receipt = self.client.transaction_receipt(TransactionId::Hash(hash));
-		let result = Ok(receipt.map(Into::into))
+		let receipt = self.client.transaction_receipt(TransactionId::Hash(hash)).map(|receipt| {
+			let mut receipt: Receipt = receipt.into();
+			if self.options.revert_reasons && receipt.status_code == Some(0.into()) {
+				receipt.revert_reason = self.revert_reason(hash);
+			}
+			receipt
+		});
+		let result = Ok(receipt)
 			.and_then(errors::check_block_gap(&*self.client, self.options));
 		Box::new(future::done(result))
 	}
@@ -941,7 +1137,7 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 	}
 
 	fn send_raw_transaction(&self, raw: Bytes) -> Result<H256> {
-		Rlp::new(&raw.into_vec()).as_val()
+		UnverifiedTransaction::decode(&raw.into_vec())
 			.map_err(errors::rlp)
 			.and_then(|tx| SignedTransaction::new(tx).map_err(errors::transaction))
 			.and_then(|signed_transaction| {
@@ -959,8 +1155,10 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 		self.send_raw_transaction(raw)
 	}
 
-	fn call(&self, request: CallRequest, num: Option<BlockNumber>) -> BoxFuture<Bytes> {
-		let request = CallRequest::into(request);
+	fn call(&self, request: CallRequest, num: Option<BlockNumber>, overrides: Option<StateOverrides>) -> BoxFuture<Bytes> {
+		let overrides = try_bf!(overrides.map(into_state_override).transpose());
+		let mut request = CallRequest::into(request);
+		try_bf!(self.apply_gas_cap(&mut request));
 		let signed = try_bf!(fake_sign::sign_call(request));
 
 		let num = num.unwrap_or_default();
@@ -981,16 +1179,21 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 				let state = try_bf!(self.client.state_at(id).ok_or_else(errors::state_pruned));
 				let header = try_bf!(
 					self.client.block_header(id).ok_or_else(errors::state_pruned)
-						.and_then(|h| h.decode().map_err(errors::decode))
+						.and_then(|h| h.decode(self.client.engine().params().eip1559_transition).map_err(errors::decode))
 				);
 
 				(state, header)
 			};
 
-		let result = self.client.call(&signed, Default::default(), &mut state, &header);
+		let client = self.client.clone();
+		let result = execute_with_timeout(self.options.call_timeout, move || {
+			if let Some(overrides) = overrides {
+				client.apply_state_overrides(&mut state, &overrides).map_err(errors::call)?;
+			}
+			client.call(&signed, Default::default(), &mut state, &header).map_err(errors::call)
+		});
 
 		Box::new(future::done(result
-			.map_err(errors::call)
 			.and_then(|executed| {
 				match executed.exception {
 					Some(ref exception) => Err(errors::vm(exception, &executed.output)),
@@ -1002,7 +1205,8 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 	}
 
 	fn estimate_gas(&self, request: CallRequest, num: Option<BlockNumber>) -> BoxFuture<U256> {
-		let request = CallRequest::into(request);
+		let mut request = CallRequest::into(request);
+		try_bf!(self.apply_gas_cap(&mut request));
 		let signed = try_bf!(fake_sign::sign_call(request));
 		let num = num.unwrap_or_default();
 
@@ -1021,13 +1225,20 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 								.ok_or_else(errors::state_pruned));
 			let header = try_bf!(self.client.block_header(id)
 								 .ok_or_else(errors::state_pruned)
-								 .and_then(|h| h.decode().map_err(errors::decode)));
+								 .and_then(|h| h.decode(self.client.engine().params().eip1559_transition).map_err(errors::decode)));
 			(state, header)
 		};
 
-		Box::new(future::done(self.client.estimate_gas(&signed, &state, &header)
-			.map_err(errors::call)
-		))
+		let client = self.client.clone();
+		let gas_cap = self.options.gas_cap;
+		let result = execute_with_timeout(self.options.call_timeout, move || {
+			client.estimate_gas(&signed, &state, &header).map_err(errors::call)
+		});
+
+		Box::new(future::done(result.and_then(|gas| match gas_cap {
+			Some(cap) if gas > cap => Err(errors::gas_cap_exceeded(gas, cap)),
+			_ => Ok(gas),
+		})))
 	}
 
 	fn compile_lll(&self, _: String) -> Result<Bytes> {
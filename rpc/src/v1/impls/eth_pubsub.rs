@@ -17,25 +17,27 @@
 //! Eth PUB-SUB rpc implementation.
 
 use std::sync::{Arc, Weak};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 
 use jsonrpc_core::{BoxFuture, Result, Error};
 use jsonrpc_core::futures::{self, Future, IntoFuture, Stream, sync::mpsc};
 use jsonrpc_pubsub::typed::{Sink, Subscriber};
 use jsonrpc_pubsub::SubscriptionId;
 
-use v1::helpers::{errors, limit_logs, Subscribers, };
+use v1::helpers::{errors, limit_logs, NotificationQueue, PubSubStats, Subscribers, DEFAULT_QUEUE_SIZE};
 use v1::helpers::light_fetch::LightFetch;
 use v1::metadata::Metadata;
 use v1::traits::EthPubSub;
-use v1::types::{pubsub, RichHeader, Log};
+use v1::types::{pubsub, Header, RichHeader, Log, Transaction};
 
 use sync::{SyncState, Notification};
 use client_traits::{BlockChainClient, ChainNotify};
+use ethcore::client::EngineInfo;
 use ethereum_types::H256;
 use light::cache::Cache;
 use light::client::{LightChainClient, LightChainNotify};
 use light::on_demand::OnDemandRequester;
+use miner::pool::VerifiedTransaction;
 use parity_runtime::Executor;
 use parking_lot::{RwLock, Mutex};
 
@@ -46,17 +48,27 @@ use types::{
 	ids::BlockId,
 	encoded,
 	filter::Filter as EthFilter,
+	log_entry::LocalizedLogEntry,
+	BlockNumber,
 };
 
-type Client = Sink<pubsub::Result>;
+type Client = NotificationQueue<pubsub::Result>;
+
+/// Number of recently notified blocks kept to replay their logs to resubscribing clients.
+const RECENT_BLOCKS: usize = 32;
+
+/// Sync status for a sync state, `None` once it can't be computed anymore.
+type SyncStatusFn = dyn Fn(SyncState) -> Option<pubsub::PubSubSyncStatus> + Send + Sync;
 
 /// Eth PubSub implementation.
 pub struct EthPubSubClient<C> {
 	handler: Arc<ChainNotificationHandler<C>>,
 	heads_subscribers: Arc<RwLock<Subscribers<Client>>>,
 	logs_subscribers: Arc<RwLock<Subscribers<(Client, EthFilter)>>>,
-	transactions_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	transactions_subscribers: Arc<RwLock<Subscribers<(Client, bool)>>>,
 	sync_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	full_transactions: bool,
+	stats: Arc<PubSubStats>,
 }
 
 impl<C> EthPubSubClient<C>
@@ -66,17 +78,31 @@ impl<C> EthPubSubClient<C>
 	/// adds a sync notification channel to the pubsub client
 	pub fn add_sync_notifier<F>(&mut self, receiver: Notification<SyncState>, f: F)
 		where
-			F: 'static + Fn(SyncState) -> Option<pubsub::PubSubSyncStatus> + Send
+			F: 'static + Fn(SyncState) -> Option<pubsub::PubSubSyncStatus> + Send + Sync
 	{
+		*self.handler.sync_status.write() = Some(Box::new(f));
 		let weak_handler = Arc::downgrade(&self.handler);
 
 		self.handler.executor.spawn(
 			receiver.for_each(move |state| {
-				if let Some(status) = f(state) {
-					if let Some(handler) = weak_handler.upgrade() {
-						handler.notify_syncing(status);
-						return Ok(())
-					}
+				match weak_handler.upgrade() {
+					Some(ref handler) if handler.update_sync_status(Some(state)) => Ok(()),
+					_ => Err(()),
+				}
+			})
+		)
+	}
+
+	/// adds a channel of transactions added to the pool, enabling full transaction subscriptions
+	pub fn add_transactions_notifier(&mut self, receiver: mpsc::UnboundedReceiver<Arc<Vec<Arc<VerifiedTransaction>>>>) {
+		let weak_handler = Arc::downgrade(&self.handler);
+		self.full_transactions = true;
+
+		self.handler.executor.spawn(
+			receiver.for_each(move |transactions| {
+				if let Some(handler) = weak_handler.upgrade() {
+					handler.notify_new_transaction_bodies(&transactions);
+					return Ok(())
 				}
 				Err(())
 			})
@@ -89,7 +115,12 @@ impl<C> EthPubSubClient<C>
 		C: 'static + Send + Sync {
 
 	/// Creates new `EthPubSubClient`.
-	pub fn new(client: Arc<C>, executor: Executor, pool_receiver: mpsc::UnboundedReceiver<Arc<Vec<H256>>>) -> Self {
+	pub fn new(
+		client: Arc<C>,
+		executor: Executor,
+		pool_receiver: mpsc::UnboundedReceiver<Arc<Vec<H256>>>,
+		stats: Arc<PubSubStats>,
+	) -> Self {
 		let heads_subscribers = Arc::new(RwLock::new(Subscribers::default()));
 		let logs_subscribers = Arc::new(RwLock::new(Subscribers::default()));
 		let transactions_subscribers = Arc::new(RwLock::new(Subscribers::default()));
@@ -102,6 +133,9 @@ impl<C> EthPubSubClient<C>
 			logs_subscribers: logs_subscribers.clone(),
 			transactions_subscribers: transactions_subscribers.clone(),
 			sync_subscribers: sync_subscribers.clone(),
+			sync_status: RwLock::new(None),
+			last_sync_status: Mutex::new((None, None)),
+			recent_blocks: Mutex::new(VecDeque::new()),
 		});
 		let handler2 = Arc::downgrade(&handler);
 
@@ -121,9 +155,15 @@ impl<C> EthPubSubClient<C>
 			heads_subscribers,
 			logs_subscribers,
 			transactions_subscribers,
+			full_transactions: false,
+			stats,
 		}
 	}
 
+	fn queue(&self, sink: Sink<pubsub::Result>, id: &SubscriptionId, kind: &'static str) -> Client {
+		NotificationQueue::new(sink, id, kind, DEFAULT_QUEUE_SIZE, &self.stats)
+	}
+
 	/// Returns a chain notification handler.
 	pub fn handler(&self) -> Weak<ChainNotificationHandler<C>> {
 		Arc::downgrade(&self.handler)
@@ -143,7 +183,8 @@ where
 		cache: Arc<Mutex<Cache>>,
 		executor: Executor,
 		gas_price_percentile: usize,
-		pool_receiver: mpsc::UnboundedReceiver<Arc<Vec<H256>>>
+		pool_receiver: mpsc::UnboundedReceiver<Arc<Vec<H256>>>,
+		stats: Arc<PubSubStats>,
 	) -> Self {
 		let fetch = LightFetch {
 			client,
@@ -152,34 +193,40 @@ where
 			cache,
 			gas_price_percentile,
 		};
-		EthPubSubClient::new(Arc::new(fetch), executor, pool_receiver)
+		EthPubSubClient::new(Arc::new(fetch), executor, pool_receiver, stats)
 	}
 }
 
+/// Logs of a block in the order they were notified to the logs subscribers.
+struct NotifiedBlock {
+	hash: H256,
+	route_type: ChainRouteType,
+	logs: Vec<LocalizedLogEntry>,
+}
+
 /// PubSub Notification handler.
 pub struct ChainNotificationHandler<C> {
 	client: Arc<C>,
 	executor: Executor,
 	heads_subscribers: Arc<RwLock<Subscribers<Client>>>,
 	logs_subscribers: Arc<RwLock<Subscribers<(Client, EthFilter)>>>,
-	transactions_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	transactions_subscribers: Arc<RwLock<Subscribers<(Client, bool)>>>,
 	sync_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	sync_status: RwLock<Option<Box<SyncStatusFn>>>,
+	last_sync_status: Mutex<(Option<SyncState>, Option<pubsub::PubSubSyncStatus>)>,
+	recent_blocks: Mutex<VecDeque<NotifiedBlock>>,
 }
 
 impl<C> ChainNotificationHandler<C> {
 	fn notify(executor: &Executor, subscriber: &Client, result: pubsub::Result) {
-		executor.spawn(subscriber
-			.notify(Ok(result))
-			.map(|_| ())
-			.map_err(|e| warn!(target: "rpc", "Unable to send notification: {}", e))
-		);
+		subscriber.notify(executor, result);
 	}
 
-	fn notify_heads(&self, headers: &[(encoded::Header, BTreeMap<String, String>)]) {
+	fn notify_heads(&self, headers: &[(encoded::Header, BTreeMap<String, String>)], eip1559_transition: BlockNumber) {
 		for subscriber in self.heads_subscribers.read().values() {
 			for &(ref header, ref extra_info) in headers {
 				Self::notify(&self.executor, subscriber, pubsub::Result::Header(Box::new(RichHeader {
-					inner: header.into(),
+					inner: Header::new(header, eip1559_transition),
 					extra_info: extra_info.clone(),
 				})));
 			}
@@ -192,6 +239,42 @@ impl<C> ChainNotificationHandler<C> {
 		}
 	}
 
+	/// Recompute the sync status, for a new sync state or the last known one, and notify the
+	/// subscribers if it changed, so they get the start, progress and end of a sync.
+	/// Returns `false` once the status can't be computed anymore.
+	fn update_sync_status(&self, state: Option<SyncState>) -> bool {
+		let sync_status = self.sync_status.read();
+		let sync_status = match *sync_status {
+			Some(ref sync_status) => sync_status,
+			None => return true,
+		};
+
+		let mut last = self.last_sync_status.lock();
+		let state = match state.or(last.0) {
+			Some(state) => state,
+			None => return true,
+		};
+		let status = match sync_status(state) {
+			Some(status) => status,
+			None => return false,
+		};
+
+		last.0 = Some(state);
+		if last.1.as_ref() != Some(&status) {
+			last.1 = Some(status.clone());
+			self.notify_syncing(status);
+		}
+		true
+	}
+
+	/// Notify sync subscribers about the progress of an ongoing sync.
+	fn notify_sync_progress(&self) {
+		let syncing = self.last_sync_status.lock().1.as_ref().map_or(false, |status| status.syncing);
+		if syncing && !self.sync_subscribers.read().is_empty() {
+			self.update_sync_status(None);
+		}
+	}
+
 	fn notify_logs<F, T, Ex>(&self, enacted: &[(H256, Ex)], logs: F) where
 		F: Fn(EthFilter, &Ex) -> T,
 		Ex: Send,
@@ -225,14 +308,74 @@ impl<C> ChainNotificationHandler<C> {
 		}
 	}
 
+	/// Notify a logs subscriber about the matching logs of the given blocks, in order. Logs of
+	/// retracted blocks are marked as removed and are never dropped by the filter limit, which
+	/// only applies to the logs of enacted blocks.
+	fn notify_blocks_logs<'a, I>(executor: &Executor, subscriber: &Client, filter: &EthFilter, blocks: I) where
+		I: IntoIterator<Item = &'a NotifiedBlock>,
+	{
+		let logs = blocks.into_iter()
+			.flat_map(|block| block.logs.iter().map(move |log| (log, &block.route_type)))
+			.filter(|&(log, _)| filter.matches(&log.entry))
+			.map(|(log, route_type)| {
+				let mut log: Log = log.clone().into();
+				if let ChainRouteType::Retracted = *route_type {
+					log.log_type = "removed".into();
+					log.removed = true;
+				}
+				log
+			})
+			.collect::<Vec<_>>();
+
+		let enacted = logs.iter().filter(|log| !log.removed).count();
+		let mut skip = filter.limit.map_or(0, |limit| enacted.saturating_sub(limit));
+		for log in logs {
+			if !log.removed && skip > 0 {
+				skip -= 1;
+				continue;
+			}
+			Self::notify(executor, subscriber, pubsub::Result::Log(Box::new(log)));
+		}
+	}
+
+	/// Notify the logs subscribers about the logs of a new chain route and remember the blocks
+	/// for replaying them later.
+	fn notify_route_logs(&self, blocks: Vec<NotifiedBlock>) {
+		// Subscribers are added with this lock held, so they can't miss any blocks.
+		let mut recent_blocks = self.recent_blocks.lock();
+		for &(ref subscriber, ref filter) in self.logs_subscribers.read().values() {
+			Self::notify_blocks_logs(&self.executor, subscriber, filter, &blocks);
+		}
+
+		recent_blocks.extend(blocks);
+		let excess = recent_blocks.len().saturating_sub(RECENT_BLOCKS);
+		recent_blocks.drain(..excess);
+	}
+
 	/// Notify all subscribers about new transaction hashes.
 	fn notify_new_transactions(&self, hashes: &[H256]) {
-		for subscriber in self.transactions_subscribers.read().values() {
+		for &(ref subscriber, full) in self.transactions_subscribers.read().values() {
+			if full {
+				continue;
+			}
 			for hash in hashes {
 				Self::notify(&self.executor, subscriber, pubsub::Result::TransactionHash(*hash));
 			}
 		}
 	}
+
+	/// Notify full transaction subscribers about transactions added to the pool.
+	fn notify_new_transaction_bodies(&self, transactions: &[Arc<VerifiedTransaction>]) {
+		for &(ref subscriber, full) in self.transactions_subscribers.read().values() {
+			if !full {
+				continue;
+			}
+			for transaction in transactions {
+				let transaction = Transaction::from_pending(transaction.pending().clone());
+				Self::notify(&self.executor, subscriber, pubsub::Result::Transaction(Box::new(transaction)));
+			}
+		}
+	}
 }
 
 /// A light client wrapper struct.
@@ -242,6 +385,9 @@ pub trait LightClient: Send + Sync {
 
 	/// Fetch logs.
 	fn logs(&self, filter: EthFilter) -> BoxFuture<Vec<Log>>;
+
+	/// Block number from which headers carry the base fee (EIP-1559).
+	fn eip1559_transition(&self) -> BlockNumber;
 }
 
 impl<S, OD> LightClient for LightFetch<S, OD>
@@ -256,6 +402,10 @@ where
 	fn logs(&self, filter: EthFilter) -> BoxFuture<Vec<Log>> {
 		Box::new(LightFetch::logs(self, filter)) as BoxFuture<_>
 	}
+
+	fn eip1559_transition(&self) -> BlockNumber {
+		self.client.engine().params().eip1559_transition
+	}
 }
 
 impl<C: LightClient> LightChainNotify for ChainNotificationHandler<C> {
@@ -266,14 +416,32 @@ impl<C: LightClient> LightChainNotify for ChainNotificationHandler<C> {
 			.map(|header| (header, Default::default()))
 			.collect::<Vec<_>>();
 
-		self.notify_heads(&headers);
+		self.notify_sync_progress();
+		self.notify_heads(&headers, self.client.eip1559_transition());
 		self.notify_logs(&enacted.iter().map(|h| (*h, ())).collect::<Vec<_>>(), |filter, _| self.client.logs(filter))
 	}
 }
 
-impl<C: BlockChainClient> ChainNotify for ChainNotificationHandler<C> {
-	fn new_blocks(&self, new_blocks: NewBlocks) {
-		if self.heads_subscribers.read().is_empty() && self.logs_subscribers.read().is_empty() { return }
+impl<C: BlockChainClient + EngineInfo> ChainNotificationHandler<C> {
+	/// All logs of a block, retracted blocks have them in reverse order.
+	fn notified_block(&self, hash: H256, route_type: ChainRouteType) -> NotifiedBlock {
+		let filter = EthFilter {
+			from_block: BlockId::Hash(hash),
+			to_block: BlockId::Hash(hash),
+			address: None,
+			topics: Vec::new(),
+			limit: None,
+		};
+		let mut logs = self.client.logs(filter).unwrap_or_default();
+		if let ChainRouteType::Retracted = route_type {
+			logs.reverse();
+		}
+
+		NotifiedBlock { hash, route_type, logs }
+	}
+
+	fn notify_new_heads(&self, new_blocks: &NewBlocks) {
+		if self.heads_subscribers.read().is_empty() { return }
 		const EXTRA_INFO_PROOF: &str = "Object exists in in blockchain (fetched earlier), extra_info is always available if object exists; qed";
 		let headers = new_blocks.route.route()
 			.iter()
@@ -289,22 +457,28 @@ impl<C: BlockChainClient> ChainNotify for ChainNotificationHandler<C> {
 			})
 			.collect::<Vec<_>>();
 
-		// Headers
-		self.notify_heads(&headers);
-
-		// We notify logs enacting and retracting as the order in route.
-		self.notify_logs(new_blocks.route.route(), |filter, ex| {
-			match ex {
-				ChainRouteType::Enacted =>
-					Ok(self.client.logs(filter).unwrap_or_default().into_iter().map(Into::into).collect()),
-				ChainRouteType::Retracted =>
-					Ok(self.client.logs(filter).unwrap_or_default().into_iter().map(Into::into).map(|mut log: Log| {
-						log.log_type = "removed".into();
-						log.removed = true;
-						log
-					}).collect()),
-			}
-		});
+		self.notify_heads(&headers, self.client.engine().params().eip1559_transition);
+	}
+}
+
+impl<C: BlockChainClient + EngineInfo> ChainNotify for ChainNotificationHandler<C> {
+	fn new_blocks(&self, new_blocks: NewBlocks) {
+		self.notify_sync_progress();
+		self.notify_new_heads(&new_blocks);
+
+		// Nobody is going to replay the logs of a sync.
+		if new_blocks.has_more_blocks_to_import && self.logs_subscribers.read().is_empty() {
+			self.recent_blocks.lock().clear();
+			return;
+		}
+
+		// The route has retracted blocks newest first followed by enacted blocks oldest first,
+		// so the removed logs of a reorg are notified before the logs replacing them.
+		let blocks = new_blocks.route.route()
+			.iter()
+			.map(|&(hash, ref route_type)| self.notified_block(hash, route_type.clone()))
+			.collect();
+		self.notify_route_logs(blocks);
 	}
 }
 
@@ -320,11 +494,11 @@ impl<C: Send + Sync + 'static> EthPubSub for EthPubSubClient<C> {
 	) {
 		let error = match (kind, params) {
 			(pubsub::Kind::NewHeads, None) => {
-				self.heads_subscribers.write().push(subscriber);
+				self.heads_subscribers.write().push_with(subscriber, |sink, id| self.queue(sink, id, "newHeads"));
 				return;
 			},
 			(pubsub::Kind::Syncing, None) => {
-				self.sync_subscribers.write().push(subscriber);
+				self.sync_subscribers.write().push_with(subscriber, |sink, id| self.queue(sink, id, "syncing"));
 				return;
 			},
 			(pubsub::Kind::NewHeads, _) => {
@@ -333,21 +507,53 @@ impl<C: Send + Sync + 'static> EthPubSub for EthPubSubClient<C> {
 			(pubsub::Kind::Logs, Some(pubsub::Params::Logs(filter))) => {
 				match filter.try_into() {
 					Ok(filter) => {
-						self.logs_subscribers.write().push(subscriber, filter);
+						self.logs_subscribers.write().push_with(subscriber, |sink, id| (self.queue(sink, id, "logs"), filter));
 						return;
 					},
 					Err(err) => err,
 				}
 			},
+			(pubsub::Kind::Logs, Some(pubsub::Params::ReplayLogs(filter, hash))) => {
+				let recent_blocks = self.handler.recent_blocks.lock();
+				let position = recent_blocks.iter().rposition(|block| match block.route_type {
+					ChainRouteType::Enacted => block.hash == hash,
+					ChainRouteType::Retracted => false,
+				});
+				match (filter.try_into(), position) {
+					(Ok(filter), Some(position)) => {
+						self.logs_subscribers.write().push_with(subscriber, |sink, id| {
+							let queue = self.queue(sink, id, "logs");
+							let replayed = recent_blocks.iter().skip(position + 1);
+							ChainNotificationHandler::<C>::notify_blocks_logs(&self.handler.executor, &queue, &filter, replayed);
+							(queue, filter)
+						});
+						return;
+					},
+					(Ok(_), None) => {
+						errors::invalid_params("logs", "Block to replay from is not among the recently notified ones.")
+					},
+					(Err(err), _) => err,
+				}
+			},
 			(pubsub::Kind::Logs, _) => {
 				errors::invalid_params("logs", "Expected a filter object.")
 			},
-			(pubsub::Kind::NewPendingTransactions, None) => {
-				self.transactions_subscribers.write().push(subscriber);
+			(pubsub::Kind::NewPendingTransactions, None) |
+			(pubsub::Kind::NewPendingTransactions, Some(pubsub::Params::FullTransactions(false))) => {
+				self.transactions_subscribers.write()
+					.push_with(subscriber, |sink, id| (self.queue(sink, id, "newPendingTransactions"), false));
 				return;
 			},
+			(pubsub::Kind::NewPendingTransactions, Some(pubsub::Params::FullTransactions(true))) => {
+				if self.full_transactions {
+					self.transactions_subscribers.write()
+						.push_with(subscriber, |sink, id| (self.queue(sink, id, "newPendingTransactions"), true));
+					return;
+				}
+				errors::unsupported("Full pending transactions are not available on this node.", None)
+			},
 			(pubsub::Kind::NewPendingTransactions, _) => {
-				errors::invalid_params("newPendingTransactions", "Expected no parameters.")
+				errors::invalid_params("newPendingTransactions", "Expected no parameters or a boolean.")
 			},
 			_ => {
 				errors::unimplemented(None)
@@ -362,6 +568,7 @@ impl<C: Send + Sync + 'static> EthPubSub for EthPubSubClient<C> {
 		let res2 = self.logs_subscribers.write().remove(&id).is_some();
 		let res3 = self.transactions_subscribers.write().remove(&id).is_some();
 		let res4 = self.sync_subscribers.write().remove(&id).is_some();
+		self.stats.remove(&id);
 
 		Ok(res || res2 || res3 || res4)
 	}
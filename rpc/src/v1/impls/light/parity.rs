@@ -32,7 +32,7 @@ use ethcore_logger::RotatingLogger;
 use jsonrpc_core::{Result, BoxFuture};
 use jsonrpc_core::futures::{future, Future};
 use light::on_demand::OnDemandRequester;
-use v1::helpers::{self, errors, ipfs, NetworkSettings, verify_signature};
+use v1::helpers::{self, errors, ipfs, NetworkSettings, PubSubStats, verify_signature};
 use v1::helpers::external_signer::{SignerService, SigningQueue};
 use v1::helpers::dispatch::LightDispatcher;
 use v1::helpers::light_fetch::{LightFetch, light_all_transactions};
@@ -42,11 +42,12 @@ use v1::types::{
 	Bytes, CallRequest,
 	Peers, Transaction, RpcSettings, Histogram,
 	TransactionStats, LocalTransactionStatus,
-	LightBlockNumber, ChainStatus, Receipt,
+	LightBlockNumber, ChainStatus, SnapshotStatus, DatabaseStats, Receipt,
 	BlockNumber, ConsensusCapability, VersionInfo,
 	OperationsInfo, Header, RichHeader, RecoveredAccount,
-	Log, Filter,
+	Log, Filter, BuiltinInfo, StorageKeysPage,
 };
+use v1::types::pubsub::SubscriptionStats;
 use Host;
 use v1::helpers::errors::light_unimplemented;
 use v1::types::block_number_to_id;
@@ -62,6 +63,7 @@ where
 	settings: Arc<NetworkSettings>,
 	signer: Option<Arc<SignerService>>,
 	ws_address: Option<Host>,
+	pubsub_stats: Arc<PubSubStats>,
 	gas_price_percentile: usize,
 }
 
@@ -77,6 +79,7 @@ where
 		settings: Arc<NetworkSettings>,
 		signer: Option<Arc<SignerService>>,
 		ws_address: Option<Host>,
+		pubsub_stats: Arc<PubSubStats>,
 		gas_price_percentile: usize,
 	) -> Self {
 		ParityClient {
@@ -85,6 +88,7 @@ where
 			settings,
 			signer,
 			ws_address,
+			pubsub_stats,
 			gas_price_percentile,
 		}
 	}
@@ -207,6 +211,10 @@ where
 		Err(errors::light_unimplemented(None))
 	}
 
+	fn list_storage_keys_paged(&self, _: H160, _: u64, _: Option<Bytes>, _: Option<BlockNumber>) -> Result<Option<StorageKeysPage>> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn encrypt_message(&self, key: H512, phrase: Bytes) -> Result<Bytes> {
 		ecies::encrypt(&key, &DEFAULT_MAC, &phrase.0)
 			.map_err(errors::encryption)
@@ -284,6 +292,10 @@ where
 			.ok_or_else(errors::ws_disabled)
 	}
 
+	fn ws_stats(&self) -> Result<Vec<SubscriptionStats>> {
+		Ok(self.pubsub_stats.subscriptions())
+	}
+
 	fn next_nonce(&self, address: H160) -> BoxFuture<U256> {
 		Box::new(self.light_dispatch.next_nonce(address))
 	}
@@ -323,6 +335,27 @@ where
 		})
 	}
 
+	fn snapshot_status(&self) -> Result<SnapshotStatus> {
+		// light clients don't take snapshots
+		Ok(SnapshotStatus::default())
+	}
+
+	fn db_stats(&self) -> Result<DatabaseStats> {
+		Err(light_unimplemented(None))
+	}
+
+	fn chain_spec_builtins(&self) -> Result<BTreeMap<H160, BuiltinInfo>> {
+		let chain_info = self.light_dispatch.client.chain_info();
+		let (number, timestamp) = (chain_info.best_block_number, chain_info.best_block_timestamp);
+
+		Ok(self.light_dispatch.client.engine().machine().builtins().iter()
+			.map(|(address, builtin)| {
+				let active = builtin.is_active(number, timestamp);
+				(*address, BuiltinInfo::new(builtin.spec(), active, number, timestamp))
+			})
+			.collect())
+	}
+
 	fn node_kind(&self) -> Result<::v1::types::NodeKind> {
 		use ::v1::types::{NodeKind, Availability, Capability};
 
@@ -337,7 +370,7 @@ where
 
 		let engine = self.light_dispatch.client.engine().clone();
 		let from_encoded = move |encoded: encoded::Header| {
-			let header = encoded.decode().map_err(errors::decode)?;
+			let header = encoded.decode(engine.params().eip1559_transition).map_err(errors::decode)?;
 			let extra_info = engine.extra_info(&header);
 			Ok(RichHeader {
 				inner: Header {
@@ -358,6 +391,7 @@ where
 					difficulty: *header.difficulty(),
 					seal_fields: header.seal().iter().cloned().map(Into::into).collect(),
 					extra_data: Bytes::new(header.extra_data().clone()),
+					base_fee_per_gas: header.base_fee(),
 				},
 				extra_info,
 			})
@@ -87,6 +87,10 @@ impl<F: Fetch> ParitySet for ParitySetClient<F> {
 		Err(errors::light_unimplemented(None))
 	}
 
+	fn set_priority_senders(&self, _senders: Vec<H160>) -> Result<bool> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn add_reserved_peer(&self, peer: String) -> Result<bool> {
 		match self.net.add_reserved_peer(peer) {
 			Ok(()) => Ok(true),
@@ -153,4 +157,8 @@ impl<F: Fetch> ParitySet for ParitySetClient<F> {
 	fn remove_transaction(&self, _hash: H256) -> Result<Option<Transaction>> {
 		Err(errors::light_unimplemented(None))
 	}
+
+	fn db_compact(&self, _column: Option<u32>) -> Result<bool> {
+		Err(errors::light_unimplemented(None))
+	}
 }
@@ -37,6 +37,7 @@ mod signing;
 mod signing_unsafe;
 mod traces;
 mod transactions_pool;
+mod txpool;
 mod web3;
 
 pub mod light;
@@ -64,4 +65,5 @@ pub use self::signer::SignerClient;
 pub use self::signing::SigningQueueClient;
 pub use self::signing_unsafe::SigningUnsafeClient;
 pub use self::traces::TracesClient;
+pub use self::txpool::TxPoolClient;
 pub use self::web3::Web3Client;
@@ -20,7 +20,7 @@ use std::collections::BTreeMap;
 
 use crypto::DEFAULT_MAC;
 use ethereum_types::{H64, H160, H256, H512, U64, U256};
-use ethcore::client::Call;
+use ethcore::client::{Call, EngineInfo};
 use client_traits::{BlockChainClient, StateClient};
 use ethcore::miner::{self, MinerService, FilterOptions};
 use snapshot::SnapshotService;
@@ -32,6 +32,7 @@ use jsonrpc_core::futures::future;
 use jsonrpc_core::{BoxFuture, Result};
 use sync::{SyncProvider, ManageNetwork};
 use types::{
+	client_types::StateResult,
 	ids::BlockId,
 	verification::Unverified,
 	snapshot::RestorationStatus,
@@ -39,19 +40,20 @@ use types::{
 use updater::{Service as UpdateService};
 use version::version_data;
 
-use v1::helpers::{self, errors, fake_sign, ipfs, NetworkSettings, verify_signature};
+use v1::helpers::{self, errors, fake_sign, ipfs, NetworkSettings, PubSubStats, verify_signature};
 use v1::helpers::external_signer::{SigningQueue, SignerService};
 use v1::metadata::Metadata;
 use v1::traits::Parity;
 use v1::types::{
 	Bytes, CallRequest,
 	Peers, Transaction, RpcSettings, Histogram,
-	TransactionStats, LocalTransactionStatus,
+	TransactionStats, LocalTransactionStatus, PendingTransactionStatus,
 	BlockNumber, ConsensusCapability, VersionInfo,
-	OperationsInfo, ChainStatus, Log, Filter,
-	RichHeader, Receipt, RecoveredAccount,
+	OperationsInfo, ChainStatus, SnapshotStatus, DatabaseStats, Log, Filter,
+	Header, RichHeader, Receipt, RecoveredAccount, BuiltinInfo, StorageKeysPage,
 	block_number_to_id
 };
+use v1::types::pubsub::SubscriptionStats;
 use Host;
 
 /// Parity implementation.
@@ -65,6 +67,7 @@ pub struct ParityClient<C, M, U> {
 	settings: Arc<NetworkSettings>,
 	signer: Option<Arc<SignerService>>,
 	ws_address: Option<Host>,
+	pubsub_stats: Arc<PubSubStats>,
 	snapshot: Option<Arc<dyn SnapshotService>>,
 }
 
@@ -82,6 +85,7 @@ impl<C, M, U> ParityClient<C, M, U> where
 		settings: Arc<NetworkSettings>,
 		signer: Option<Arc<SignerService>>,
 		ws_address: Option<Host>,
+		pubsub_stats: Arc<PubSubStats>,
 		snapshot: Option<Arc<dyn SnapshotService>>,
 	) -> Self {
 		ParityClient {
@@ -94,6 +98,7 @@ impl<C, M, U> ParityClient<C, M, U> where
 			settings,
 			signer,
 			ws_address,
+			pubsub_stats,
 			snapshot,
 		}
 	}
@@ -101,7 +106,7 @@ impl<C, M, U> ParityClient<C, M, U> where
 
 impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 	S: StateInfo + 'static,
-	C: miner::BlockChainClient + BlockChainClient + StateClient<State=S> + Call<State=S> + 'static,
+	C: miner::BlockChainClient + BlockChainClient + StateClient<State=S> + Call<State=S> + EngineInfo + 'static,
 	M: MinerService<State=S> + 'static,
 	U: UpdateService + 'static,
 {
@@ -237,6 +242,39 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 			.map(|a| a.into_iter().map(Into::into).collect()))
 	}
 
+	fn list_storage_keys_paged(&self, address: H160, count: u64, token: Option<Bytes>, block_number: Option<BlockNumber>) -> Result<Option<StorageKeysPage>> {
+		let number = match block_number.unwrap_or_default() {
+			BlockNumber::Pending => {
+				warn!("BlockNumber::Pending is unsupported");
+				return Ok(None);
+			},
+
+			num => block_number_to_id(num)
+		};
+
+		if count == 0 {
+			return Err(errors::invalid_params("count", "must be greater than zero"));
+		}
+
+		let resume_at = match token {
+			Some(ref token) if token.0.len() != H256::len_bytes() => {
+				return Err(errors::invalid_params("token", "unknown continuation token"));
+			},
+			Some(token) => Some(H256::from_slice(&token.0)),
+			None => None,
+		};
+
+		match self.client.list_storage_from_path(number, &address, resume_at.as_ref(), count) {
+			None => Ok(None),
+			Some(StateResult::Some((keys, next))) => Ok(Some(StorageKeysPage {
+				keys,
+				next: next.map(|path| path.as_bytes().to_vec().into()),
+			})),
+			Some(StateResult::Missing) if resume_at.is_some() => Err(errors::continuation_token_expired()),
+			Some(StateResult::Missing) => Err(errors::state_pruned()),
+		}
+	}
+
 	fn encrypt_message(&self, key: H512, phrase: Bytes) -> Result<Bytes> {
 		ecies::encrypt(&key, &DEFAULT_MAC, &phrase.0)
 			.map_err(errors::encryption)
@@ -250,10 +288,17 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 			filter,
 			miner::PendingOrdering::Priority,
 		);
+		let not_allowed_transactions = self.miner.not_allowed_transactions();
 
 		Ok(ready_transactions
 			.into_iter()
-			.map(|t| Transaction::from_pending(t.pending().clone()))
+			.map(|t| {
+				let mut transaction = Transaction::from_pending(t.pending().clone());
+				if not_allowed_transactions.contains(&transaction.hash) {
+					transaction.status = Some(PendingTransactionStatus::NotAllowed);
+				}
+				transaction
+			})
 			.collect()
 		)
 	}
@@ -298,6 +343,10 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 			.ok_or_else(errors::ws_disabled)
 	}
 
+	fn ws_stats(&self) -> Result<Vec<SubscriptionStats>> {
+		Ok(self.pubsub_stats.subscriptions())
+	}
+
 	fn next_nonce(&self, address: H160) -> BoxFuture<U256> {
 		Box::new(future::ok(self.miner.next_nonce(&*self.client, &address)))
 	}
@@ -333,6 +382,26 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 		})
 	}
 
+	fn snapshot_status(&self) -> Result<SnapshotStatus> {
+		Ok(self.snapshot.as_ref().map(|s| s.creation_status().into()).unwrap_or_default())
+	}
+
+	fn db_stats(&self) -> Result<DatabaseStats> {
+		Ok(self.client.database_stats().into())
+	}
+
+	fn chain_spec_builtins(&self) -> Result<BTreeMap<H160, BuiltinInfo>> {
+		let chain_info = self.client.chain_info();
+		let (number, timestamp) = (chain_info.best_block_number, chain_info.best_block_timestamp);
+
+		Ok(self.client.engine().machine().builtins().iter()
+			.map(|(address, builtin)| {
+				let active = builtin.is_active(number, timestamp);
+				(*address, BuiltinInfo::new(builtin.spec(), active, number, timestamp))
+			})
+			.collect())
+	}
+
 	fn node_kind(&self) -> Result<::v1::types::NodeKind> {
 		use ::v1::types::{NodeKind, Availability, Capability};
 
@@ -368,7 +437,7 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 		};
 
 		Box::new(future::ok(RichHeader {
-			inner: header.into(),
+			inner: Header::new(&header, self.client.engine().params().eip1559_transition),
 			extra_info: extra.unwrap_or_default(),
 		}))
 	}
@@ -426,7 +495,8 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 			};
 
 			let state = self.client.state_at(id).ok_or_else(errors::state_pruned)?;
-			let header = self.client.block_header(id).ok_or_else(errors::state_pruned)?.decode().map_err(errors::decode)?;
+			let header = self.client.block_header(id).ok_or_else(errors::state_pruned)?
+				.decode(self.client.engine().params().eip1559_transition).map_err(errors::decode)?;
 
 			(state, header)
 		};
@@ -479,7 +549,7 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 
 	fn submit_raw_block(&self, block: Bytes) -> Result<H256> {
 		let result = self.client.import_block(
-			Unverified::from_rlp(block.into_vec()).map_err(errors::rlp)?
+			Unverified::from_rlp(block.into_vec(), self.client.engine().params().eip1559_transition).map_err(errors::rlp)?
 		);
 		Ok(result.map_err(errors::cannot_submit_block)?)
 	}
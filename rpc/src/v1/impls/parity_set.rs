@@ -21,12 +21,15 @@ use std::time::Duration;
 
 use client_traits::BlockChainClient;
 use types::client_types::Mode;
+use types::ids::TransactionId;
+use types::snapshot::RestorationStatus;
 use ethcore::miner::{self, MinerService};
 use ethereum_types::{H160, H256, U256};
 use ethkey;
 use fetch::{self, Fetch};
 use hash::keccak_buffer;
-use sync::ManageNetwork;
+use snapshot::SnapshotService;
+use sync::{ManageNetwork, SyncProvider};
 use updater::{Service as UpdateService};
 
 use jsonrpc_core::{BoxFuture, Result};
@@ -39,24 +42,33 @@ use v1::types::{Bytes, ReleaseInfo, Transaction};
 pub mod accounts {
 	use super::*;
 	use accounts::AccountProvider;
+	use miner::pool::scoring::{bump_gas_price, DEFAULT_PRICE_BUMP};
+	use types::transaction::{Action, PendingTransaction, SignedTransaction, Transaction as EthTransaction};
 	use v1::traits::ParitySetAccounts;
 	use v1::helpers::deprecated::DeprecationNotice;
+	use v1::helpers::dispatch::FullDispatcher;
 	use v1::helpers::engine_signer::EngineSigner;
 
+	/// Gas of a plain value transfer, used by transactions cancelling other ones.
+	const CANCEL_TRANSACTION_GAS: u64 = 21_000;
+
 	/// Parity-specific account-touching RPC interfaces.
-	pub struct ParitySetAccountsClient<M> {
+	pub struct ParitySetAccountsClient<C, M> {
+		client: Arc<C>,
 		miner: Arc<M>,
 		accounts: Arc<AccountProvider>,
 		deprecation_notice: DeprecationNotice,
 	}
 
-	impl<M> ParitySetAccountsClient<M> {
+	impl<C, M> ParitySetAccountsClient<C, M> {
 		/// Creates new ParitySetAccountsClient
 		pub fn new(
+			client: &Arc<C>,
 			accounts: &Arc<AccountProvider>,
 			miner: &Arc<M>,
 		) -> Self {
 			ParitySetAccountsClient {
+				client: client.clone(),
 				accounts: accounts.clone(),
 				miner: miner.clone(),
 				deprecation_notice: Default::default(),
@@ -64,7 +76,10 @@ pub mod accounts {
 		}
 	}
 
-	impl<M: MinerService + 'static> ParitySetAccounts for ParitySetAccountsClient<M> {
+	impl<C, M> ParitySetAccounts for ParitySetAccountsClient<C, M> where
+		C: miner::BlockChainClient + BlockChainClient + 'static,
+		M: MinerService + 'static,
+	{
 		fn set_engine_signer(&self, address: H160, password: String) -> Result<bool> {
 			self.deprecation_notice.print(
 				"parity_setEngineSigner",
@@ -79,6 +94,29 @@ pub mod accounts {
 			self.miner.set_author(miner::Author::Sealer(signer));
 			Ok(true)
 		}
+
+		fn cancel_transaction(&self, hash: H256) -> Result<H256> {
+			if self.client.transaction_block(TransactionId::Hash(hash)).is_some() {
+				return Err(errors::transaction_mined());
+			}
+			let original = self.miner.transaction(&hash).ok_or_else(errors::transaction_not_queued)?;
+			let sender = original.signed().sender();
+
+			let cancel = EthTransaction {
+				nonce: original.signed().nonce,
+				action: Action::Call(sender),
+				gas: CANCEL_TRANSACTION_GAS.into(),
+				gas_price: bump_gas_price(original.signed().gas_price, DEFAULT_PRICE_BUMP),
+				value: 0.into(),
+				data: vec![],
+			};
+			let chain_id = self.client.signing_chain_id();
+			let signature = self.accounts.sign(sender, None, cancel.hash(chain_id)).map_err(errors::signing)?;
+			let signed = SignedTransaction::new(cancel.with_signature(signature, chain_id))
+				.expect("Transaction was signed by AccountsProvider; it never produces invalid signatures; qed");
+
+			FullDispatcher::dispatch_transaction(&*self.client, &*self.miner, PendingTransaction::new(signed, None), true)
+		}
 	}
 }
 
@@ -88,6 +126,8 @@ pub struct ParitySetClient<C, M, U, F = fetch::Client> {
 	miner: Arc<M>,
 	updater: Arc<U>,
 	net: Arc<dyn ManageNetwork>,
+	sync: Arc<dyn SyncProvider>,
+	snapshot: Option<Arc<dyn SnapshotService>>,
 	fetch: F,
 }
 
@@ -100,6 +140,8 @@ impl<C, M, U, F> ParitySetClient<C, M, U, F>
 		miner: &Arc<M>,
 		updater: &Arc<U>,
 		net: &Arc<dyn ManageNetwork>,
+		sync: Arc<dyn SyncProvider>,
+		snapshot: Option<Arc<dyn SnapshotService>>,
 		fetch: F,
 	) -> Self {
 		ParitySetClient {
@@ -107,6 +149,8 @@ impl<C, M, U, F> ParitySetClient<C, M, U, F>
 			miner: miner.clone(),
 			updater: updater.clone(),
 			net: net.clone(),
+			sync,
+			snapshot,
 			fetch,
 		}
 	}
@@ -136,6 +180,11 @@ impl<C, M, U, F> ParitySet for ParitySetClient<C, M, U, F> where
 		Ok(false)
 	}
 
+	fn set_priority_senders(&self, senders: Vec<H160>) -> Result<bool> {
+		self.miner.set_priority_senders(senders.into_iter().collect());
+		Ok(true)
+	}
+
 	fn set_gas_floor_target(&self, target: U256) -> Result<bool> {
 		let mut range = self.miner.authoring_params().gas_range_target;
 		range.0 = target;
@@ -242,8 +291,24 @@ impl<C, M, U, F> ParitySet for ParitySetClient<C, M, U, F> where
 	}
 
 	fn remove_transaction(&self, hash: H256) -> Result<Option<Transaction>> {
+		if self.client.transaction_block(TransactionId::Hash(hash)).is_some() {
+			return Err(errors::transaction_mined());
+		}
+
 		Ok(self.miner.remove_transaction(&hash)
 			.map(|t| Transaction::from_pending(t.pending().clone()))
 		)
 	}
+
+	fn db_compact(&self, column: Option<u32>) -> Result<bool> {
+		if let Some(RestorationStatus::Ongoing { .. }) = self.snapshot.as_ref().map(|s| s.status()) {
+			return Err(errors::compaction_refused("a snapshot restoration is in progress"));
+		}
+		if self.sync.status().is_snapshot_syncing() {
+			return Err(errors::compaction_refused("a warp sync is in progress"));
+		}
+
+		self.client.compact_database(column).map_err(errors::compaction_refused)?;
+		Ok(true)
+	}
 }
@@ -22,18 +22,26 @@ use parking_lot::RwLock;
 
 use jsonrpc_core::{self as core, Result, MetaIoHandler};
 use jsonrpc_core::futures::{future, Future, Stream, Sink};
-use jsonrpc_pubsub::typed::Subscriber;
+use jsonrpc_core::futures::sync::mpsc;
+use jsonrpc_pubsub::typed::{self, Subscriber};
 use jsonrpc_pubsub::SubscriptionId;
 use tokio_timer;
 
+use miner::pool::PoolEvent;
 use parity_runtime::Executor;
-use v1::helpers::GenericPollManager;
+use v1::helpers::{GenericPollManager, Subscribers};
 use v1::metadata::Metadata;
 use v1::traits::PubSub;
 
+/// Name of the subscription to the transaction pool events.
+const TRANSACTIONS_POOL_SUBSCRIPTION: &str = "transactionsPool";
+
+type PoolEventsSubscribers = Arc<RwLock<Subscribers<typed::Sink<core::Value>>>>;
+
 /// Parity PubSub implementation.
 pub struct PubSubClient<S: core::Middleware<Metadata>> {
 	poll_manager: Arc<RwLock<GenericPollManager<S>>>,
+	pool_events_subscribers: Option<PoolEventsSubscribers>,
 	executor: Executor,
 }
 
@@ -62,9 +70,39 @@ impl<S: core::Middleware<Metadata>> PubSubClient<S> {
 
 		PubSubClient {
 			poll_manager,
+			pool_events_subscribers: None,
 			executor,
 		}
 	}
+
+	/// Serves the `transactionsPool` subscription with events from given receiver.
+	pub fn add_pool_events_notifier(&mut self, receiver: mpsc::UnboundedReceiver<Arc<Vec<PoolEvent>>>) {
+		let subscribers: PoolEventsSubscribers = Default::default();
+		let weak_subscribers = Arc::downgrade(&subscribers);
+		let executor = self.executor.clone();
+
+		self.executor.spawn(receiver
+			.for_each(move |events| {
+				let subscribers = match weak_subscribers.upgrade() {
+					Some(subscribers) => subscribers,
+					None => return Err(()),
+				};
+				for subscriber in subscribers.read().values() {
+					for event in events.iter() {
+						let event = serde_json::to_value(event).expect("PoolEvent serialization is infallible; qed");
+						executor.spawn(subscriber
+							.notify(Ok(event))
+							.map(|_| ())
+							.map_err(|e| warn!(target: "rpc", "Unable to send notification: {}", e))
+						);
+					}
+				}
+				Ok(())
+			})
+		);
+
+		self.pool_events_subscribers = Some(subscribers);
+	}
 }
 
 impl PubSubClient<core::NoopMiddleware> {
@@ -81,6 +119,11 @@ impl<S: core::Middleware<Metadata>> PubSub for PubSubClient<S> {
 	type Metadata = Metadata;
 
 	fn parity_subscribe(&self, mut meta: Metadata, subscriber: Subscriber<core::Value>, method: String, params: Option<core::Params>) {
+		if let (TRANSACTIONS_POOL_SUBSCRIPTION, Some(subscribers)) = (&*method, &self.pool_events_subscribers) {
+			subscribers.write().push(subscriber);
+			return;
+		}
+
 		let params = params.unwrap_or_else(|| core::Params::Array(vec![]));
 		// Make sure to get rid of PubSub session otherwise it will never be dropped.
 		meta.session = None;
@@ -100,6 +143,12 @@ impl<S: core::Middleware<Metadata>> PubSub for PubSubClient<S> {
 	}
 
 	fn parity_unsubscribe(&self, _: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+		if let Some(ref subscribers) = self.pool_events_subscribers {
+			if subscribers.write().remove(&id).is_some() {
+				return Ok(true);
+			}
+		}
+
 		let res = self.poll_manager.write().unsubscribe(&id);
 		Ok(res)
 	}
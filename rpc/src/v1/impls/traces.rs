@@ -19,13 +19,14 @@
 use std::sync::Arc;
 
 use account_state::state::StateInfo;
-use ethcore::client::Call;
+use ethcore::client::{Call, EngineInfo};
 use client_traits::{BlockChainClient, StateClient};
 use ethereum_types::H256;
 use rlp::Rlp;
 use types::{
 	call_analytics::CallAnalytics,
 	ids::{BlockId, TransactionId, TraceId},
+	trace_filter::Filter,
 	transaction::SignedTransaction,
 };
 
@@ -47,26 +48,44 @@ fn to_call_analytics(flags: TraceOptions) -> CallAnalytics {
 /// Traces api implementation.
 pub struct TracesClient<C> {
 	client: Arc<C>,
+	max_count: usize,
 }
 
 impl<C> TracesClient<C> {
-	/// Creates new Traces client.
-	pub fn new(client: &Arc<C>) -> Self {
+	/// Creates new Traces client, `trace_filter` returning at most `max_count` traces.
+	pub fn new(client: &Arc<C>, max_count: usize) -> Self {
 		TracesClient {
 			client: client.clone(),
+			max_count,
 		}
 	}
 }
 
 impl<C, S> Traces for TracesClient<C> where
 	S: StateInfo + 'static,
-	C: BlockChainClient + StateClient<State=S> + Call<State=S> + 'static
+	C: BlockChainClient + StateClient<State=S> + Call<State=S> + EngineInfo + 'static
 {
 	type Metadata = Metadata;
 
 	fn filter(&self, filter: TraceFilter) -> Result<Option<Vec<LocalizedTrace>>> {
-		Ok(self.client.filter_traces(filter.into())
-			.map(|traces| traces.into_iter().map(LocalizedTrace::from).collect()))
+		let mut filter: Filter = filter.into();
+		match filter.count {
+			Some(count) if count > self.max_count => {
+				return Err(errors::request_rejected_param_limit(self.max_count as u64, "traces"));
+			},
+			Some(_) => {},
+			// fetch a single trace more than allowed to find out whether the limit is exceeded
+			None => filter.count = Some(self.max_count.saturating_add(1)),
+		}
+
+		let traces = match self.client.filter_traces(filter) {
+			Some(traces) => traces,
+			None => return Ok(None),
+		};
+		if traces.len() > self.max_count {
+			return Err(errors::request_rejected_param_limit(self.max_count as u64, "traces"));
+		}
+		Ok(Some(traces.into_iter().map(LocalizedTrace::from).collect()))
 	}
 
 	fn block_traces(&self, block_number: BlockNumber) -> Result<Option<Vec<LocalizedTrace>>> {
@@ -112,7 +131,7 @@ impl<C, S> Traces for TracesClient<C> where
 		let mut state = self.client.state_at(id).ok_or_else(errors::state_pruned)?;
 		let header = self.client.block_header(id).ok_or_else(errors::state_pruned)?;
 
-		self.client.call(&signed, to_call_analytics(flags), &mut state, &header.decode().map_err(errors::decode)?)
+		self.client.call(&signed, to_call_analytics(flags), &mut state, &header.decode(self.client.engine().params().eip1559_transition).map_err(errors::decode)?)
 			.map(TraceResults::from)
 			.map_err(errors::call)
 	}
@@ -140,7 +159,7 @@ impl<C, S> Traces for TracesClient<C> where
 		let mut state = self.client.state_at(id).ok_or_else(errors::state_pruned)?;
 		let header = self.client.block_header(id).ok_or_else(errors::state_pruned)?;
 
-		self.client.call_many(&requests, &mut state, &header.decode().map_err(errors::decode)?)
+		self.client.call_many(&requests, &mut state, &header.decode(self.client.engine().params().eip1559_transition).map_err(errors::decode)?)
 			.map(|results| results.into_iter().map(TraceResults::from).collect())
 			.map_err(errors::call)
 	}
@@ -163,7 +182,7 @@ impl<C, S> Traces for TracesClient<C> where
 		let mut state = self.client.state_at(id).ok_or_else(errors::state_pruned)?;
 		let header = self.client.block_header(id).ok_or_else(errors::state_pruned)?;
 
-		self.client.call(&signed, to_call_analytics(flags), &mut state, &header.decode().map_err(errors::decode)?)
+		self.client.call(&signed, to_call_analytics(flags), &mut state, &header.decode(self.client.engine().params().eip1559_transition).map_err(errors::decode)?)
 			.map(TraceResults::from)
 			.map_err(errors::call)
 	}
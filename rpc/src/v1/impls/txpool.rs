@@ -0,0 +1,137 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Geth-compatible txpool RPC implementation.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use client_traits::Nonce;
+use ethcore::miner::MinerService;
+use ethereum_types::Address;
+use miner::pool::VerifiedTransaction;
+use types::transaction::{Action, SignedTransaction};
+
+use jsonrpc_core::Result;
+use v1::traits::TxPool;
+use v1::types::{Transaction, TxPoolContent, TxPoolStatus};
+
+/// Transactions in the pool by sender, ordered by nonce.
+type BySender = BTreeMap<Address, Vec<Arc<VerifiedTransaction>>>;
+
+/// Txpool rpc implementation.
+pub struct TxPoolClient<C, M> {
+	client: Arc<C>,
+	miner: Arc<M>,
+}
+
+impl<C, M> TxPoolClient<C, M> {
+	/// Creates new txpool client.
+	pub fn new(client: Arc<C>, miner: Arc<M>) -> Self {
+		TxPoolClient {
+			client,
+			miner,
+		}
+	}
+}
+
+impl<C: Nonce, M: MinerService> TxPoolClient<C, M> {
+	/// Splits the transactions in the pool into pending ones, executable with the current state
+	/// nonces of their senders, and queued ones, waiting for a transaction with a lower nonce.
+	///
+	/// The pool is traversed once and the state nonce of every sender is looked up only once.
+	fn split(&self) -> (BySender, BySender) {
+		let mut by_sender = HashMap::<Address, Vec<_>>::new();
+		for tx in self.miner.queued_transactions() {
+			by_sender.entry(tx.signed().sender()).or_insert_with(Vec::new).push(tx);
+		}
+
+		let mut pending = BySender::new();
+		let mut queued = BySender::new();
+		for (sender, mut transactions) in by_sender {
+			transactions.sort_unstable_by_key(|tx| tx.signed().nonce);
+			let mut next_nonce = self.client.latest_nonce(&sender);
+			for tx in transactions {
+				let nonce = tx.signed().nonce;
+				if nonce < next_nonce {
+					// already mined, but not culled from the pool yet
+					continue;
+				}
+
+				if nonce == next_nonce {
+					next_nonce = next_nonce + 1;
+					pending.entry(sender).or_insert_with(Vec::new).push(tx);
+				} else {
+					queued.entry(sender).or_insert_with(Vec::new).push(tx);
+				}
+			}
+		}
+
+		(pending, queued)
+	}
+
+	fn content_with<T, F>(&self, f: F) -> TxPoolContent<T> where
+		F: Fn(&VerifiedTransaction) -> T,
+	{
+		let by_nonce = |transactions: BySender| -> BTreeMap<Address, BTreeMap<String, T>> {
+			transactions
+				.into_iter()
+				.map(|(sender, transactions)| (sender, transactions
+					.into_iter()
+					.map(|tx| (tx.signed().nonce.to_string(), f(&tx)))
+					.collect()
+				))
+				.collect()
+		};
+
+		let (pending, queued) = self.split();
+		TxPoolContent {
+			pending: by_nonce(pending),
+			queued: by_nonce(queued),
+		}
+	}
+}
+
+impl<C, M> TxPool for TxPoolClient<C, M> where
+	C: Nonce + Send + Sync + 'static,
+	M: MinerService + 'static,
+{
+	fn status(&self) -> Result<TxPoolStatus> {
+		let count = |transactions: BySender| transactions.values().map(Vec::len).sum::<usize>().into();
+		let (pending, queued) = self.split();
+		Ok(TxPoolStatus {
+			pending: count(pending),
+			queued: count(queued),
+		})
+	}
+
+	fn content(&self) -> Result<TxPoolContent<Transaction>> {
+		Ok(self.content_with(|tx| Transaction::from_pending(tx.pending().clone())))
+	}
+
+	fn inspect(&self) -> Result<TxPoolContent<String>> {
+		Ok(self.content_with(|tx| summary(tx.signed())))
+	}
+}
+
+/// One-line summary of a transaction, in the format used by geth.
+fn summary(tx: &SignedTransaction) -> String {
+	let recipient = match tx.action {
+		Action::Create => "contract creation".to_owned(),
+		Action::Call(ref address) => format!("{:#x}", address),
+	};
+	format!("{}: {} wei + {} gas × {} wei", recipient, tx.value, tx.gas, tx.gas_price)
+}
@@ -22,9 +22,10 @@ use std::sync::atomic::{self, AtomicUsize};
 use std::time;
 use parity_runtime;
 use jsonrpc_core as core;
-use jsonrpc_core::futures::future::Either;
+use jsonrpc_core::futures::future::{self, Either};
 use order_stat;
 use parking_lot::RwLock;
+use v1::helpers::errors;
 
 pub use self::parity_runtime::Executor;
 
@@ -187,6 +188,7 @@ pub trait ActivityNotifier: Send + Sync + 'static {
 pub struct Middleware<T: ActivityNotifier = ClientNotifier> {
 	stats: Arc<RpcStats>,
 	notifier: T,
+	max_batch_size: Option<usize>,
 }
 
 impl<T: ActivityNotifier> Middleware<T> {
@@ -195,8 +197,15 @@ impl<T: ActivityNotifier> Middleware<T> {
 		Middleware {
 			stats,
 			notifier,
+			max_batch_size: None,
 		}
 	}
+
+	/// Reject batches with more than `max_batch_size` calls with a single error response.
+	pub fn with_max_batch_size(mut self, max_batch_size: Option<usize>) -> Self {
+		self.max_batch_size = max_batch_size;
+		self
+	}
 }
 
 impl<M: core::Metadata, T: ActivityNotifier> core::Middleware<M> for Middleware<T> {
@@ -212,6 +221,13 @@ impl<M: core::Metadata, T: ActivityNotifier> core::Middleware<M> for Middleware<
 		self.notifier.active();
 		self.stats.count_request();
 
+		if let (Some(max), core::Request::Batch(ref calls)) = (self.max_batch_size, &request) {
+			if calls.len() > max {
+				let error = errors::batch_too_large(calls.len(), max);
+				return Either::A(Box::new(future::ok(Some(core::Response::from(error, Some(core::Version::V2))))));
+			}
+		}
+
 		let id = match request {
 			core::Request::Single(core::Call::MethodCall(ref call)) => Some(call.id.clone()),
 			_ => None,
@@ -246,7 +262,15 @@ impl ActivityNotifier for ClientNotifier {
 #[cfg(test)]
 mod tests {
 
-	use super::{RateCalculator, StatsCalculator, RpcStats};
+	use std::sync::Arc;
+	use jsonrpc_core::{MetaIoHandler, Params, Value};
+	use super::{RateCalculator, StatsCalculator, RpcStats, Middleware, ActivityNotifier};
+
+	struct TestNotifier;
+
+	impl ActivityNotifier for TestNotifier {
+		fn active(&self) {}
+	}
 
 	#[test]
 	fn should_calculate_rate() {
@@ -312,4 +336,24 @@ mod tests {
 	fn is_sync<F: Send + Sync>(x: F) {
 		drop(x)
 	}
+
+	#[test]
+	fn should_limit_batch_size() {
+		// given
+		let middleware = Middleware::new(Arc::new(RpcStats::default()), TestNotifier).with_max_batch_size(Some(2));
+		let mut io = MetaIoHandler::<(), _>::with_middleware(middleware);
+		io.add_method("hello", |_: Params| Ok(Value::String("world".into())));
+		let call = |id| format!(r#"{{"jsonrpc":"2.0","method":"hello","params":[],"id":{}}}"#, id);
+		let batch = |size| format!("[{}]", (0..size).map(call).collect::<Vec<_>>().join(","));
+
+		// when
+		let below = io.handle_request_sync(&batch(1), ());
+		let at = io.handle_request_sync(&batch(2), ());
+		let above = io.handle_request_sync(&batch(3), ());
+
+		// then
+		assert_eq!(below, Some(r#"[{"jsonrpc":"2.0","result":"world","id":0}]"#.into()));
+		assert_eq!(at, Some(r#"[{"jsonrpc":"2.0","result":"world","id":0},{"jsonrpc":"2.0","result":"world","id":1}]"#.into()));
+		assert_eq!(above, Some(r#"{"jsonrpc":"2.0","error":{"code":-32041,"message":"Batch of 3 calls exceeds the limit of 2 calls."},"id":null}"#.into()));
+	}
 }
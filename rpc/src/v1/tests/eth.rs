@@ -25,14 +25,19 @@ use ethcore::miner::Miner;
 use spec::{Genesis, Spec, self};
 use ethcore::test_helpers;
 use verification::VerifierType;
-use ethereum_types::{Address, H256, U256};
+use ethereum_types::{Address, BigEndianHash, H160, H256, U256};
+use ethtrie::TrieDB;
+use hash::keccak;
+use hash_db::{HashDB, EMPTY_PREFIX};
 use ethjson::test_helpers::blockchain::BlockChain;
 use ethjson::spec::ForkSpec;
 use io::IoChannel;
 use miner::external::ExternalMiner;
 use parity_runtime::Runtime;
 use parking_lot::Mutex;
+use trie::Trie;
 use types::{
+	basic_account::BasicAccount,
 	ids::BlockId,
 	verification::Unverified,
 };
@@ -44,6 +49,7 @@ use v1::impls::{EthClient, EthClientOptions, SigningUnsafeClient};
 use v1::metadata::Metadata;
 use v1::tests::helpers::{TestSnapshotService, TestSyncProvider, Config};
 use v1::traits::{Eth, EthSigning};
+use v1::types::Bytes;
 
 fn account_provider() -> Arc<AccountProvider> {
 	Arc::new(AccountProvider::transient_provider())
@@ -94,8 +100,9 @@ impl EthTester {
 			Self::from_spec(make_spec(chain))
 		};
 
+		let eip1559_transition = tester.client.engine().params().eip1559_transition;
 		for b in chain.blocks_rlp() {
-			if let Ok(block) = Unverified::from_rlp(b) {
+			if let Ok(block) = Unverified::from_rlp(b, eip1559_transition) {
 				let _ = tester.client.import_block(block);
 				tester.client.flush_queue();
 			}
@@ -141,16 +148,18 @@ impl EthTester {
 				pending_nonce_from_queue: false,
 				allow_pending_receipt_query: true,
 				send_block_number_in_get_work: true,
-				gas_price_percentile: 50,
 				allow_experimental_rpcs: true,
 				allow_missing_blocks: false,
-				no_ancient_blocks: false
+				no_ancient_blocks: false,
+				gas_cap: None,
+				call_timeout: None,
+				revert_reasons: false,
 			},
 		);
 
 		let reservations = Arc::new(Mutex::new(nonce::Reservations::new(runtime.executor())));
 
-		let dispatcher = FullDispatcher::new(client.clone(), miner_service.clone(), reservations, 50);
+		let dispatcher = FullDispatcher::new(client.clone(), miner_service.clone(), reservations);
 		let signer = Arc::new(dispatch::Signer::new(account_provider.clone())) as _;
 		let eth_sign = SigningUnsafeClient::new(
 			&signer,
@@ -230,6 +239,68 @@ fn eth_get_proof() {
 	assert!(tester.handler.handle_request_sync(req_new_acc).unwrap().to_string().ends_with(res_new_acc.as_str()));
 }
 
+/// Looks up `key` in the trie with the given `root`, using only the nodes of `proof`.
+fn verify_proof(root: &H256, key: &H256, proof: &[Bytes]) -> Option<Vec<u8>> {
+	let mut db = journaldb::new_memory_db();
+	for node in proof {
+		db.insert(EMPTY_PREFIX, &node.0);
+	}
+	TrieDB::new(&db, root)
+		.and_then(|trie| trie.get(key.as_bytes()))
+		.expect("proof contains every node on the path to the key")
+		.map(|value| value.to_vec())
+}
+
+fn get_proof(tester: &EthTester, address: &H160, keys: &[H256]) -> serde_json::Value {
+	let request = format!(
+		r#"{{"jsonrpc":"2.0","method":"eth_getProof","params":[{},{},"latest"],"id":1}}"#,
+		serde_json::to_string(address).unwrap(),
+		serde_json::to_string(keys).unwrap(),
+	);
+	let response: serde_json::Value = serde_json::from_str(&tester.handler.handle_request_sync(&request).unwrap()).unwrap();
+	response["result"].clone()
+}
+
+#[test]
+fn eth_get_proof_verifies_against_state_root() {
+	let chain = extract_chain!("BlockchainTests/ValidBlocks/bcWalletTest/wallet2outOf3txs");
+	let tester = EthTester::from_chain(&chain);
+	let state_root = *tester.client.best_block_header().state_root();
+
+	for (address, expected) in chain.post_state.clone() {
+		let address = address.0;
+		let storage: Vec<(H256, U256)> = expected.storage.unwrap_or_default().into_iter()
+			.map(|(key, value)| (BigEndianHash::from_uint(&key.0), value.0))
+			.collect();
+		let keys: Vec<H256> = storage.iter().map(|(key, _)| *key).collect();
+		let proof = get_proof(&tester, &address, &keys);
+
+		let account_proof: Vec<Bytes> = serde_json::from_value(proof["accountProof"].clone()).unwrap();
+		let account: BasicAccount = rlp::decode(&verify_proof(&state_root, &keccak(address), &account_proof).unwrap()).unwrap();
+		assert_eq!(serde_json::from_value::<U256>(proof["balance"].clone()).unwrap(), account.balance);
+		assert_eq!(serde_json::from_value::<U256>(proof["nonce"].clone()).unwrap(), account.nonce);
+		assert_eq!(serde_json::from_value::<H256>(proof["codeHash"].clone()).unwrap(), account.code_hash);
+		assert_eq!(serde_json::from_value::<H256>(proof["storageHash"].clone()).unwrap(), account.storage_root);
+
+		let storage_proofs = proof["storageProof"].as_array().unwrap();
+		assert_eq!(storage_proofs.len(), keys.len());
+		for ((key, expected_value), storage_proof) in storage.iter().zip(storage_proofs) {
+			let nodes: Vec<Bytes> = serde_json::from_value(storage_proof["proof"].clone()).unwrap();
+			let value = verify_proof(&account.storage_root, &keccak(key), &nodes)
+				.map_or_else(U256::zero, |value| rlp::decode(&value).unwrap());
+			assert_eq!(serde_json::from_value::<U256>(storage_proof["value"].clone()).unwrap(), value);
+			assert_eq!(*expected_value, value);
+		}
+	}
+
+	// the proof of a non-existent account shows its absence
+	let address = H160::repeat_byte(0xaa);
+	let proof = get_proof(&tester, &address, &[H256::zero()]);
+	let account_proof: Vec<Bytes> = serde_json::from_value(proof["accountProof"].clone()).unwrap();
+	assert_eq!(verify_proof(&state_root, &keccak(address), &account_proof), None);
+	assert_eq!(proof["storageProof"][0]["value"], "0x0");
+}
+
 #[test]
 fn eth_block_number() {
 	let chain = extract_chain!("BlockchainTests/ValidBlocks/bcGasPricerTest/RPC_API_Test");
@@ -474,7 +545,8 @@ fn verify_transaction_counts(name: String, chain: BlockChain) {
 	let tester = EthTester::from_chain(&chain);
 
 	let mut id = 1;
-	for b in chain.blocks_rlp().into_iter().filter_map(|b| Unverified::from_rlp(b).ok()) {
+	let eip1559_transition = tester.client.engine().params().eip1559_transition;
+	for b in chain.blocks_rlp().into_iter().filter_map(|b| Unverified::from_rlp(b, eip1559_transition).ok()) {
 		let count = b.transactions.len();
 
 		let hash = b.header.hash();
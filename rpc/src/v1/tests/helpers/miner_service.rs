@@ -17,14 +17,14 @@
 //! Test implementation of miner service.
 
 use std::sync::Arc;
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use bytes::Bytes;
-use client_traits::{Nonce, StateClient};
+use client_traits::{BlockInfo, Nonce, StateClient};
 use engine::{Engine, signer::EngineSigner};
 use ethcore::block::SealedBlock;
 use ethcore::client::{PrepareOpenBlock, EngineInfo};
-use ethcore::miner::{self, MinerService, AuthoringParams, FilterOptions};
+use ethcore::miner::{self, MinerService, AuthoringParams, FilterOptions, GasPriceOracle};
 use ethcore::test_helpers::TestState;
 use ethereum_types::{H256, U256, Address};
 use miner::pool::local_transactions::Status as LocalTransactionStatus;
@@ -49,6 +49,10 @@ pub struct TestMinerService {
 	pub pending_transactions: Mutex<HashMap<H256, SignedTransaction>>,
 	/// Pre-existed local transactions
 	pub local_transactions: Mutex<BTreeMap<H256, LocalTransactionStatus>>,
+	/// Pending transactions not allowed by the transaction permission contract
+	pub not_allowed_transactions: Mutex<HashSet<H256>>,
+	/// Priority senders
+	pub priority_senders: RwLock<HashSet<Address>>,
 	/// Pre-existed pending receipts
 	pub pending_receipts: Mutex<Vec<RichReceipt>>,
 	/// Next nonces.
@@ -57,6 +61,8 @@ pub struct TestMinerService {
 	pub min_gas_price: RwLock<Option<U256>>,
 	/// Signer (if any)
 	pub signer: RwLock<Option<Box<dyn EngineSigner>>>,
+	/// Gas price oracle
+	pub gas_price_oracle: GasPriceOracle,
 
 	authoring_params: RwLock<AuthoringParams>,
 }
@@ -67,6 +73,8 @@ impl Default for TestMinerService {
 			imported_transactions: Default::default(),
 			pending_transactions: Default::default(),
 			local_transactions: Default::default(),
+			not_allowed_transactions: Default::default(),
+			priority_senders: Default::default(),
 			pending_receipts: Default::default(),
 			next_nonces: Default::default(),
 			min_gas_price: RwLock::new(Some(0.into())),
@@ -76,6 +84,7 @@ impl Default for TestMinerService {
 				extra_data: vec![1, 2, 3, 4],
 			}),
 			signer: RwLock::new(None),
+			gas_price_oracle: GasPriceOracle::new(Default::default(), BlockNumber::max_value()),
 		}
 	}
 }
@@ -247,6 +256,14 @@ impl MinerService for TestMinerService {
 		self.pending_transactions.lock().keys().cloned().map(|hash| hash).collect()
 	}
 
+	fn not_allowed_transactions(&self) -> HashSet<H256> {
+		self.not_allowed_transactions.lock().clone()
+	}
+
+	fn set_priority_senders(&self, senders: HashSet<Address>) {
+		*self.priority_senders.write() = senders;
+	}
+
 	fn pending_receipts(&self, _best_block: BlockNumber) -> Option<Vec<RichReceipt>> {
 		Some(self.pending_receipts.lock().clone())
 	}
@@ -266,6 +283,7 @@ impl MinerService for TestMinerService {
 				block_gas_limit: 5_000_000.into(),
 				tx_gas_limit: 5_000_000.into(),
 				no_early_reject: false,
+				max_nonce_gap: U256::max_value(),
 			},
 			status: txpool::LightStatus {
 				mem_usage: 1_000,
@@ -277,6 +295,7 @@ impl MinerService for TestMinerService {
 				max_per_sender: 16,
 				max_mem_usage: 5_000,
 			},
+			evictions: Default::default(),
 		}
 	}
 
@@ -290,6 +309,10 @@ impl MinerService for TestMinerService {
 		20_000_000_000u64.into()
 	}
 
+	fn suggested_gas_price<C: BlockInfo>(&self, chain: &C) -> Option<U256> {
+		self.gas_price_oracle.suggested_gas_price(chain)
+	}
+
 	fn sensible_gas_limit(&self) -> U256 {
 		0x5208.into()
 	}
@@ -19,11 +19,12 @@ use snapshot::SnapshotService;
 use bytes::Bytes;
 use ethereum_types::H256;
 use parking_lot::Mutex;
-use types::snapshot::{ManifestData, RestorationStatus};
+use types::snapshot::{CreationStatus, ManifestData, RestorationStatus};
 
 /// Mocked snapshot service (used for sync info extensions).
 pub struct TestSnapshotService {
 	status: Mutex<RestorationStatus>,
+	creation_status: Mutex<CreationStatus>,
 }
 
 impl TestSnapshotService {
@@ -32,6 +33,7 @@ impl TestSnapshotService {
 	pub fn new() -> Self {
 		TestSnapshotService {
 			status: Mutex::new(RestorationStatus::Inactive),
+			creation_status: Mutex::new(CreationStatus::Inactive),
 		}
 	}
 
@@ -39,6 +41,11 @@ impl TestSnapshotService {
 	pub fn set_status(&self, status: RestorationStatus) {
 		*self.status.lock() = status;
 	}
+
+	/// Set the creation status.
+	pub fn set_creation_status(&self, status: CreationStatus) {
+		*self.creation_status.lock() = status;
+	}
 }
 
 impl SnapshotService for TestSnapshotService {
@@ -51,6 +58,7 @@ impl SnapshotService for TestSnapshotService {
 	fn abort_restore(&self) { }
 	fn restore_state_chunk(&self, _hash: H256, _chunk: Bytes) { }
 	fn restore_block_chunk(&self, _hash: H256, _chunk: Bytes) { }
+	fn creation_status(&self) -> CreationStatus { self.creation_status.lock().clone() }
 	fn abort_snapshot(&self) {}
 	fn shutdown(&self) { }
 }
@@ -17,21 +17,82 @@
 use std::sync::Arc;
 
 use ethcore::test_helpers::TestBlockChainClient;
+use ethereum_types::Address;
+use machine::executed::Executed;
+use trace::FlatTrace;
+use trace::trace::{Action, Call, CallResult, Res};
+use vm::CallType;
 
 use jsonrpc_core::IoHandler;
 use v1::{Debug, DebugClient};
 
 fn io() -> IoHandler {
-	let client = Arc::new(TestBlockChainClient::new());
+	io_with_client(TestBlockChainClient::new())
+}
 
+fn io_with_client(client: TestBlockChainClient) -> IoHandler {
 	let mut io = IoHandler::new();
-	io.extend_with(DebugClient::new(client).to_delegate());
+	io.extend_with(DebugClient::new(Arc::new(client)).to_delegate());
 	io
 }
 
+fn io_with_execution() -> IoHandler {
+	let client = TestBlockChainClient::new();
+	client.set_execution_result(Ok(Executed {
+		exception: None,
+		gas: 0x10000.into(),
+		gas_used: 0x5208.into(),
+		refunded: 0.into(),
+		cumulative_gas_used: 0x5208.into(),
+		logs: vec![],
+		contracts_created: vec![],
+		output: vec![1, 2, 3],
+		trace: vec![FlatTrace {
+			action: Action::Call(Call {
+				from: Address::from_low_u64_be(0xf),
+				to: Address::from_low_u64_be(0x10),
+				value: 0x1.into(),
+				gas: 0x100.into(),
+				input: vec![1, 2, 3],
+				call_type: CallType::Call,
+			}),
+			result: Res::Call(CallResult {
+				gas_used: 0x10.into(),
+				output: vec![1, 2, 3],
+			}),
+			subtraces: 0,
+			trace_address: vec![],
+		}],
+		vm_trace: None,
+		state_diff: None,
+	}));
+	io_with_client(client)
+}
+
 #[test]
 fn rpc_debug_get_bad_blocks() {
 	let request = r#"{"jsonrpc": "2.0", "method": "debug_getBadBlocks", "params": [], "id": 1}"#;
 	let response = "{\"jsonrpc\":\"2.0\",\"result\":[{\"author\":\"0x0000000000000000000000000000000000000000\",\"difficulty\":\"0x0\",\"extraData\":\"0x\",\"gasLimit\":\"0x0\",\"gasUsed\":\"0x0\",\"hash\":\"0x27bfb37e507ce90da141307204b1c6ba24194380613590ac50ca4b1d7198ff65\",\"logsBloom\":\"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000\",\"miner\":\"0x0000000000000000000000000000000000000000\",\"number\":\"0x0\",\"parentHash\":\"0x0000000000000000000000000000000000000000000000000000000000000000\",\"reason\":\"Invalid block\",\"receiptsRoot\":\"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421\",\"rlp\":\"\\\"0x010203\\\"\",\"sealFields\":[],\"sha3Uncles\":\"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347\",\"size\":\"0x3\",\"stateRoot\":\"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421\",\"timestamp\":\"0x0\",\"totalDifficulty\":null,\"transactions\":[],\"transactionsRoot\":\"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421\",\"uncles\":[]}],\"id\":1}";
 	assert_eq!(io().handle_request_sync(request), Some(response.to_owned()));
 }
+
+#[test]
+fn rpc_debug_trace_transaction() {
+	let request = r#"{"jsonrpc": "2.0", "method": "debug_traceTransaction", "params": ["0x0000000000000000000000000000000000000000000000000000000000000005"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"gas":21000,"failed":false,"returnValue":"010203","structLogs":[]},"id":1}"#;
+	assert_eq!(io_with_execution().handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_debug_trace_transaction_call_tracer() {
+	let request = r#"{"jsonrpc": "2.0", "method": "debug_traceTransaction", "params": ["0x0000000000000000000000000000000000000000000000000000000000000005", {"tracer": "callTracer"}], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"type":"CALL","from":"0x000000000000000000000000000000000000000f","to":"0x0000000000000000000000000000000000000010","value":"0x1","gas":"0x10000","gasUsed":"0x5208","input":"0x010203","output":"0x010203"},"id":1}"#;
+	assert_eq!(io_with_execution().handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_debug_trace_transaction_unknown_tracer() {
+	let request = r#"{"jsonrpc": "2.0", "method": "debug_traceTransaction", "params": ["0x0000000000000000000000000000000000000000000000000000000000000005", {"tracer": "prestateTracer"}], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: tracer","data":"\"unsupported tracer prestateTracer, only callTracer is available\""},"id":1}"#;
+	assert_eq!(io_with_execution().handle_request_sync(request), Some(response.to_owned()));
+}
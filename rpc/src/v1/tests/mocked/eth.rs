@@ -29,20 +29,25 @@ use miner::external::ExternalMiner;
 use parity_runtime::Runtime;
 use parking_lot::Mutex;
 use rlp;
+use serde_json;
 use rustc_hex::{FromHex, ToHex};
 use sync::SyncState;
+use vm;
 use types::{
 	ids::{BlockId, TransactionId},
 	transaction::{Transaction, Action},
 	log_entry::{LocalizedLogEntry, LogEntry},
 	receipt::{LocalizedReceipt, TransactionOutcome},
 	snapshot::RestorationStatus,
+	state_override::StorageOverride,
 };
 
 use jsonrpc_core::IoHandler;
+use jsonrpc_core::futures::Future;
 use v1::{Eth, EthClient, EthClientOptions, EthFilter, EthFilterClient};
 use v1::tests::helpers::{TestSyncProvider, Config, TestMinerService, TestSnapshotService};
 use v1::metadata::Metadata;
+use v1::types::BlockNumber;
 
 fn blockchain_client() -> Arc<TestBlockChainClient> {
 	let client = TestBlockChainClient::new();
@@ -410,6 +415,90 @@ fn rpc_eth_gas_price() {
 	assert_eq!(EthTester::default().io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_fee_history() {
+	let tester = EthTester::default();
+	for _ in 0..3 {
+		tester.client.add_block(EachBlockWith::Transaction, |mut header| {
+			header.set_gas_used(500_000.into());
+			header
+		});
+	}
+	tester.client.set_transaction_receipt(TransactionId::Hash(H256::zero()), LocalizedReceipt {
+		from: H160::zero(),
+		to: None,
+		transaction_hash: H256::zero(),
+		transaction_index: 0,
+		block_hash: H256::zero(),
+		block_number: 0,
+		cumulative_gas_used: 21_000.into(),
+		gas_used: 21_000.into(),
+		effective_gas_price: 200_000_000_000u64.into(),
+		contract_address: None,
+		logs: vec![],
+		log_bloom: Bloom::zero(),
+		outcome: TransactionOutcome::Unknown,
+	});
+
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_feeHistory", "params": ["0x2", "latest", [25, 75]], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"oldestBlock":"0x2","baseFeePerGas":["0x0","0x0","0x0"],"gasUsedRatio":[0.5,0.5],"reward":[["0x2e90edd000","0x2e90edd000"],["0x2e90edd000","0x2e90edd000"]]},"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+
+	// without a pending block the newest block is the latest one
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_feeHistory", "params": ["0x2", "pending", [25, 75]], "id": 1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_feeHistory", "params": ["0x10", "0x1"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"oldestBlock":"0x0","baseFeePerGas":["0x0","0x0","0x0"],"gasUsedRatio":[0.0,0.5],"reward":[]},"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_feeHistory", "params": ["0x1", "0x4"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Unknown block number"},"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_feeHistory", "params": ["0x1", "latest", [75, 25]], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: rewardPercentiles","data":"\"expected ascending values between 0 and 100\""},"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_eth_fee_history_rejects_non_finite_percentiles() {
+	// NaN and infinity can't be sent as JSON, so call the client directly
+	let client = blockchain_client();
+	let ap = accounts_provider();
+	let opt_ap = Arc::new(move || ap.accounts().unwrap_or_default()) as _;
+	let external_miner = Arc::new(ExternalMiner::new(Arc::new(Mutex::new(HashMap::new()))));
+	let eth = EthClient::new(&client, &snapshot_service(), &sync_provider(), &opt_ap, &miner_service(), &external_miner, Default::default());
+
+	for percentile in &[::std::f64::NAN, ::std::f64::INFINITY, ::std::f64::NEG_INFINITY] {
+		let result = eth.fee_history(1.into(), BlockNumber::Latest, Some(vec![25.0, *percentile])).wait();
+		assert_eq!(result.unwrap_err().message, "Couldn't parse parameters: rewardPercentiles");
+	}
+}
+
+#[test]
+fn rpc_eth_fee_history_clamps_block_count() {
+	let tester = EthTester::default();
+	tester.client.add_blocks(1100, EachBlockWith::Nothing);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_feeHistory", "params": ["0x1000", "latest"], "id": 1}"#;
+	let response = format!(
+		r#"{{"jsonrpc":"2.0","result":{{"oldestBlock":"0x4d","baseFeePerGas":[{}],"gasUsedRatio":[{}],"reward":[]}},"id":1}}"#,
+		vec![r#""0x0""#; 1025].join(","),
+		vec!["0.0"; 1024].join(","),
+	);
+	assert_eq!(tester.io.handle_request_sync(request), Some(response));
+}
+
+#[test]
+fn rpc_eth_get_proof_without_state() {
+	let tester = EthTester::default();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_getProof", "params": ["0x0000000000000000000000000000000000000001", ["0x0000000000000000000000000000000000000000000000000000000000000000"], "latest"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"This request is not supported because your node is running with state pruning. Run with --pruning=archive."},"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_eth_accounts() {
 	let tester = EthTester::default();
@@ -736,6 +825,148 @@ fn rpc_eth_call() {
 	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_call_with_state_overrides() {
+	let tester = EthTester::default();
+	tester.client.set_execution_result(Ok(Executed {
+		exception: None,
+		gas: U256::zero(),
+		gas_used: U256::from(0xff30),
+		refunded: U256::from(0x5),
+		cumulative_gas_used: U256::zero(),
+		logs: vec![],
+		contracts_created: vec![],
+		output: vec![0x12, 0x34, 0xff],
+		trace: vec![],
+		vm_trace: None,
+		state_diff: None,
+	}));
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_call",
+		"params": [{
+			"from": "0xb60e8dd61c5d32be8058bb8eb970870f07233155",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567"
+		},
+		"latest",
+		{
+			"0xd46e8dd67c5d32be8058bb8eb970870f07244567": {
+				"balance": "0x1",
+				"code": "0x6000",
+				"state": {}
+			}
+		}],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x1234ff","id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+
+	let overrides = tester.client.state_overrides.read().clone().unwrap();
+	let account = &overrides[&H160::from_str("d46e8dd67c5d32be8058bb8eb970870f07244567").unwrap()];
+	assert_eq!(account.balance, Some(U256::from(1)));
+	assert_eq!(account.code, Some(vec![0x60, 0x00]));
+	assert_eq!(account.storage, Some(StorageOverride::Full(Default::default())));
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_call",
+		"params": [{
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567"
+		},
+		"latest",
+		{
+			"0xd46e8dd67c5d32be8058bb8eb970870f07244567": { "state": {}, "stateDiff": {} }
+		}],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: stateOverrides","data":"\"both state and stateDiff given for account 0xd46e8dd67c5d32be8058bb8eb970870f07244567\""},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_eth_call_exceeding_gas_cap() {
+	let tester = EthTester::new_with_options(EthClientOptions::with(|options| {
+		options.gas_cap = Some(0x7000.into());
+	}));
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_call",
+		"params": [{
+			"from": "0xb60e8dd61c5d32be8058bb8eb970870f07233155",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567",
+			"gas": "0x76c0"
+		},
+		"latest"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32041,"message":"Gas 30400 exceeds the RPC gas cap of 28672."},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+fn failed_execution(exception: vm::Error, output: &str) -> Executed {
+	Executed {
+		exception: Some(exception),
+		gas: U256::zero(),
+		gas_used: U256::from(0xff30),
+		refunded: U256::zero(),
+		cumulative_gas_used: U256::zero(),
+		logs: vec![],
+		contracts_created: vec![],
+		output: output.from_hex().unwrap(),
+		trace: vec![],
+		vm_trace: None,
+		state_diff: None,
+	}
+}
+
+const REVERT_REASON_OUTPUT: &str = "08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000046e6f706500000000000000000000000000000000000000000000000000000000";
+
+fn eth_call_error(execution: Executed) -> String {
+	let tester = EthTester::default();
+	tester.client.set_execution_result(Ok(execution));
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_call",
+		"params": [{
+			"from": "0xb60e8dd61c5d32be8058bb8eb970870f07233155",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567"
+		},
+		"latest"],
+		"id": 1
+	}"#;
+	tester.io.handle_request_sync(request).unwrap()
+}
+
+#[test]
+fn rpc_eth_call_reverted_with_reason() {
+	let response = format!(
+		r#"{{"jsonrpc":"2.0","error":{{"code":-32015,"message":"execution reverted: nope","data":"0x{}"}},"id":1}}"#,
+		REVERT_REASON_OUTPUT,
+	);
+
+	assert_eq!(eth_call_error(failed_execution(vm::Error::Reverted, REVERT_REASON_OUTPUT)), response);
+}
+
+#[test]
+fn rpc_eth_call_reverted_without_reason() {
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32015,"message":"execution reverted","data":"0x"},"id":1}"#;
+
+	assert_eq!(eth_call_error(failed_execution(vm::Error::Reverted, "")), response);
+}
+
+#[test]
+fn rpc_eth_call_out_of_gas() {
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32015,"message":"VM execution error.","data":"Out of gas"},"id":1}"#;
+
+	assert_eq!(eth_call_error(failed_execution(vm::Error::OutOfGas, "")), response);
+}
+
 #[test]
 fn rpc_eth_call_default_block() {
 	let tester = EthTester::default();
@@ -940,6 +1171,7 @@ fn rpc_eth_transaction_receipt() {
 		block_number: 0x4510c,
 		cumulative_gas_used: U256::from(0x20),
 		gas_used: U256::from(0x10),
+		effective_gas_price: U256::from(0x1),
 		contract_address: None,
 		logs: vec![LocalizedLogEntry {
 			entry: LogEntry {
@@ -971,11 +1203,50 @@ fn rpc_eth_transaction_receipt() {
 		"params": ["0xb903239f8543d04b5dc1ba6579132b143087c68db1b2168786408fcbce568238"],
 		"id": 1
 	}"#;
-	let response = r#"{"jsonrpc":"2.0","result":{"blockHash":"0xed76641c68a1c641aee09a94b3b471f4dc0316efe5ac19cf488e2674cf8d05b5","blockNumber":"0x4510c","contractAddress":null,"cumulativeGasUsed":"0x20","from":"0xb60e8dd61c5d32be8058bb8eb970870f07233155","gasUsed":"0x10","logs":[{"address":"0x33990122638b9132ca29c723bdf037f1a891a70c","blockHash":"0xed76641c68a1c641aee09a94b3b471f4dc0316efe5ac19cf488e2674cf8d05b5","blockNumber":"0x4510c","data":"0x","logIndex":"0x1","removed":false,"topics":["0xa6697e974e6a320f454390be03f74955e8978f1a6971ea6730542e37b66179bc","0x4861736852656700000000000000000000000000000000000000000000000000"],"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000000","transactionIndex":"0x0","transactionLogIndex":"0x0","type":"mined"}],"logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","root":"0x0000000000000000000000000000000000000000000000000000000000000000","status":null,"to":"0xd46e8dd67c5d32be8058bb8eb970870f07244567","transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000000","transactionIndex":"0x0"},"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"blockHash":"0xed76641c68a1c641aee09a94b3b471f4dc0316efe5ac19cf488e2674cf8d05b5","blockNumber":"0x4510c","contractAddress":null,"cumulativeGasUsed":"0x20","effectiveGasPrice":"0x1","from":"0xb60e8dd61c5d32be8058bb8eb970870f07233155","gasUsed":"0x10","logs":[{"address":"0x33990122638b9132ca29c723bdf037f1a891a70c","blockHash":"0xed76641c68a1c641aee09a94b3b471f4dc0316efe5ac19cf488e2674cf8d05b5","blockNumber":"0x4510c","data":"0x","logIndex":"0x1","removed":false,"topics":["0xa6697e974e6a320f454390be03f74955e8978f1a6971ea6730542e37b66179bc","0x4861736852656700000000000000000000000000000000000000000000000000"],"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000000","transactionIndex":"0x0","transactionLogIndex":"0x0","type":"mined"}],"logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","root":"0x0000000000000000000000000000000000000000000000000000000000000000","status":null,"to":"0xd46e8dd67c5d32be8058bb8eb970870f07244567","transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000000","transactionIndex":"0x0"},"id":1}"#;
 
 	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+fn failed_receipt_revert_reason(execution: Executed) -> Option<String> {
+	let tester = EthTester::new_with_options(EthClientOptions::with(|options| {
+		options.revert_reasons = true;
+	}));
+	let hash = H256::from_low_u64_be(1);
+	tester.client.set_transaction_receipt(TransactionId::Hash(hash), LocalizedReceipt {
+		from: Address::from_low_u64_be(2),
+		to: Some(Address::from_low_u64_be(3)),
+		transaction_hash: hash,
+		transaction_index: 0,
+		block_hash: H256::from_low_u64_be(4),
+		block_number: 1,
+		cumulative_gas_used: U256::from(0xff30),
+		gas_used: U256::from(0xff30),
+		effective_gas_price: U256::from(0x1),
+		contract_address: None,
+		logs: vec![],
+		log_bloom: Bloom::zero(),
+		outcome: TransactionOutcome::StatusCode(0),
+	});
+	tester.client.set_execution_result(Ok(execution));
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getTransactionReceipt",
+		"params": ["0x0000000000000000000000000000000000000000000000000000000000000001"],
+		"id": 1
+	}"#;
+	let response: serde_json::Value = serde_json::from_str(&tester.io.handle_request_sync(request).unwrap()).unwrap();
+	response["result"].get("revertReason").map(|reason| reason.as_str().unwrap().to_owned())
+}
+
+#[test]
+fn rpc_eth_transaction_receipt_revert_reason() {
+	assert_eq!(failed_receipt_revert_reason(failed_execution(vm::Error::Reverted, REVERT_REASON_OUTPUT)), Some("nope".into()));
+	assert_eq!(failed_receipt_revert_reason(failed_execution(vm::Error::Reverted, "")), None);
+	assert_eq!(failed_receipt_revert_reason(failed_execution(vm::Error::OutOfGas, "")), None);
+}
+
 #[test]
 fn rpc_eth_transaction_receipt_null() {
 	let tester = EthTester::default();
@@ -19,18 +19,22 @@ use std::sync::Arc;
 use jsonrpc_core::MetaIoHandler;
 use jsonrpc_core::futures::{self, Stream, Future, sync::mpsc};
 use jsonrpc_pubsub::Session;
+use serde_json;
 
 use std::time::Duration;
 
 use v1::{EthPubSub, EthPubSubClient, Metadata};
+use v1::helpers::PubSubStats;
 use ethcore::test_helpers::{TestBlockChainClient, EachBlockWith};
 use parity_runtime::Runtime;
 use ethereum_types::{Address, H256};
 use client_traits::{BlockInfo, ChainNotify};
+use miner::pool::VerifiedTransaction;
 use types::{
 	chain_notify::{NewBlocks, ChainRoute, ChainRouteType},
 	log_entry::{LocalizedLogEntry, LogEntry},
 	ids::BlockId,
+	transaction::{Action, Transaction},
 };
 
 
@@ -49,7 +53,7 @@ fn should_subscribe_to_new_heads() {
 
 	let (_, pool_receiver) = mpsc::unbounded();
 
-	let pubsub = EthPubSubClient::new(Arc::new(client), el.executor(), pool_receiver);
+	let pubsub = EthPubSubClient::new(Arc::new(client), el.executor(), pool_receiver, Default::default());
 	let handler = pubsub.handler().upgrade().unwrap();
 	let pubsub = pubsub.to_delegate();
 
@@ -119,7 +123,7 @@ fn should_subscribe_to_logs() {
 
 	let (_, pool_receiver) = mpsc::unbounded();
 
-	let pubsub = EthPubSubClient::new(Arc::new(client), el.executor(), pool_receiver);
+	let pubsub = EthPubSubClient::new(Arc::new(client), el.executor(), pool_receiver, Default::default());
 	let handler = pubsub.handler().upgrade().unwrap();
 	let pubsub = pubsub.to_delegate();
 
@@ -160,6 +164,122 @@ fn should_subscribe_to_logs() {
 	assert_eq!(res, None);
 }
 
+fn reorg_log(block_hash: H256, block_number: u64, log_index: usize) -> LocalizedLogEntry {
+	LocalizedLogEntry {
+		entry: LogEntry {
+			address: Address::from_low_u64_be(5),
+			topics: vec![],
+			data: vec![],
+		},
+		block_hash,
+		block_number,
+		transaction_hash: H256::from_low_u64_be(100 + block_number),
+		transaction_index: 0,
+		log_index,
+		transaction_log_index: log_index,
+	}
+}
+
+// Block hash, log index and removed flag of the next log notification.
+fn next_log(receiver: mpsc::Receiver<String>) -> ((String, String, bool), mpsc::Receiver<String>) {
+	let (res, receiver) = receiver.into_future().wait().unwrap();
+	let notification: serde_json::Value = serde_json::from_str(&res.unwrap()).unwrap();
+	let log = &notification["params"]["result"];
+	let summary = (
+		log["blockHash"].as_str().unwrap().to_owned(),
+		log["logIndex"].as_str().unwrap().to_owned(),
+		log["removed"].as_bool().unwrap(),
+	);
+	(summary, receiver)
+}
+
+#[test]
+fn should_notify_removed_logs_of_reorgs_in_order() {
+	// given
+	let el = Runtime::with_thread_count(1);
+	let client = TestBlockChainClient::new();
+	let old = [H256::from_low_u64_be(11), H256::from_low_u64_be(12), H256::from_low_u64_be(13)];
+	let new = [H256::from_low_u64_be(21), H256::from_low_u64_be(22), H256::from_low_u64_be(23)];
+	client.set_logs(vec![
+		reorg_log(old[0], 1, 0),
+		reorg_log(old[1], 2, 0),
+		reorg_log(old[1], 2, 1),
+		reorg_log(old[2], 3, 0),
+		reorg_log(new[0], 1, 0),
+		reorg_log(new[1], 2, 0),
+		reorg_log(new[2], 3, 0),
+	]);
+	let expected = |hash: &H256, index: usize, removed: bool| (format!("0x{:x}", hash), format!("0x{:x}", index), removed);
+
+	let (_, pool_receiver) = mpsc::unbounded();
+
+	let pubsub = EthPubSubClient::new(Arc::new(client), el.executor(), pool_receiver, Default::default());
+	let handler = pubsub.handler().upgrade().unwrap();
+	let pubsub = pubsub.to_delegate();
+
+	let mut io = MetaIoHandler::default();
+	io.extend_with(pubsub);
+
+	let mut metadata = Metadata::default();
+	let (sender, receiver) = futures::sync::mpsc::channel(8);
+	metadata.session = Some(Arc::new(Session::new(sender)));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["logs", {}], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x4333966aca52ad0b","id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
+
+	// Import the old branch
+	let enacted = old.iter().map(|hash| (*hash, ChainRouteType::Enacted)).collect();
+	handler.new_blocks(NewBlocks::new(vec![], vec![], ChainRoute::new(enacted), vec![], vec![], DURATION_ZERO, false));
+	let mut receiver = receiver;
+	for &(ref hash, index) in &[(old[0], 0), (old[1], 0), (old[1], 1), (old[2], 0)] {
+		let (log, next) = next_log(receiver);
+		assert_eq!(log, expected(hash, index, false));
+		receiver = next;
+	}
+
+	// Reorg to the new branch: removed logs newest first, then the new ones
+	let route = old.iter().rev().map(|hash| (*hash, ChainRouteType::Retracted))
+		.chain(new.iter().map(|hash| (*hash, ChainRouteType::Enacted)))
+		.collect();
+	handler.new_blocks(NewBlocks::new(vec![], vec![], ChainRoute::new(route), vec![], vec![], DURATION_ZERO, false));
+	let reorg = [
+		expected(&old[2], 0, true),
+		expected(&old[1], 1, true),
+		expected(&old[1], 0, true),
+		expected(&old[0], 0, true),
+		expected(&new[0], 0, false),
+		expected(&new[1], 0, false),
+		expected(&new[2], 0, false),
+	];
+	for log in &reorg {
+		let (notified, next) = next_log(receiver);
+		assert_eq!(&notified, log);
+		receiver = next;
+	}
+
+	// Resubscribe from another session, replaying everything after the second old block
+	let mut metadata = Metadata::default();
+	let (sender, replay_receiver) = futures::sync::mpsc::channel(8);
+	metadata.session = Some(Arc::new(Session::new(sender)));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["logs", {"replayFrom": "0x000000000000000000000000000000000000000000000000000000000000000c"}], "id": 1}"#;
+	let response: serde_json::Value = serde_json::from_str(&io.handle_request_sync(request, metadata.clone()).unwrap()).unwrap();
+	assert!(response["result"].is_string());
+
+	let mut receiver = replay_receiver;
+	for log in Some(expected(&old[2], 0, false)).iter().chain(reorg.iter()) {
+		let (notified, next) = next_log(receiver);
+		assert_eq!(&notified, log);
+		receiver = next;
+	}
+
+	// Blocks that weren't notified recently can't be replayed
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["logs", {"replayFrom": "0x00000000000000000000000000000000000000000000000000000000000000ff"}], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: logs","data":"\"Block to replay from is not among the recently notified ones.\""},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata), Some(response.to_owned()));
+}
+
 #[test]
 fn should_subscribe_to_pending_transactions() {
 	// given
@@ -168,7 +288,7 @@ fn should_subscribe_to_pending_transactions() {
 
 	let (pool_sender, pool_receiver) = mpsc::unbounded();
 
-	let pubsub = EthPubSubClient::new(Arc::new(client), el.executor(), pool_receiver);
+	let pubsub = EthPubSubClient::new(Arc::new(client), el.executor(), pool_receiver, Default::default());
 	let pubsub = pubsub.to_delegate();
 
 	let mut io = MetaIoHandler::default();
@@ -180,7 +300,7 @@ fn should_subscribe_to_pending_transactions() {
 
 	// Fail if params are provided
 	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["newPendingTransactions", {}], "id": 1}"#;
-	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: newPendingTransactions","data":"\"Expected no parameters.\""},"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: newPendingTransactions","data":"\"Expected no parameters or a boolean.\""},"id":1}"#;
 	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
 
 	// Subscribe
@@ -208,13 +328,89 @@ fn should_subscribe_to_pending_transactions() {
 	assert_eq!(res, None);
 }
 
+#[test]
+fn should_subscribe_to_full_pending_transactions() {
+	// given
+	let el = Runtime::with_thread_count(1);
+	let client = TestBlockChainClient::new();
+	let stats = Arc::new(PubSubStats::default());
+
+	let (_, pool_receiver) = mpsc::unbounded();
+	let (bodies_sender, bodies_receiver) = mpsc::unbounded();
+
+	let mut pubsub = EthPubSubClient::new(Arc::new(client), el.executor(), pool_receiver, stats.clone());
+	pubsub.add_transactions_notifier(bodies_receiver);
+	let pubsub = pubsub.to_delegate();
+
+	let mut io = MetaIoHandler::default();
+	io.extend_with(pubsub);
+
+	let mut metadata = Metadata::default();
+	let (sender, receiver) = futures::sync::mpsc::channel(8);
+	metadata.session = Some(Arc::new(Session::new(sender)));
+
+	// Subscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["newPendingTransactions", true], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x4333966aca52ad0b","id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
+	let subscriptions = stats.subscriptions();
+	assert_eq!(subscriptions.len(), 1);
+	assert_eq!(subscriptions[0].id, "0x4333966aca52ad0b");
+	assert_eq!(subscriptions[0].kind, "newPendingTransactions");
+
+	// Send a new transaction
+	let tx = Transaction {
+		value: 5.into(),
+		gas: 21_000.into(),
+		gas_price: 2.into(),
+		action: Action::Create,
+		data: vec![1, 2, 3],
+		nonce: 0.into(),
+	}.fake_sign(Address::from_low_u64_be(3));
+	let hash = tx.hash();
+	bodies_sender.unbounded_send(Arc::new(vec![Arc::new(VerifiedTransaction::from_pending_block_transaction(tx))])).unwrap();
+
+	let (res, receiver) = receiver.into_future().wait().unwrap();
+	let res = res.unwrap();
+	assert!(res.contains(&format!(r#""hash":"0x{:x}""#, hash)), "{}", res);
+	assert!(res.contains(r#""subscription":"0x4333966aca52ad0b""#), "{}", res);
+
+	// And unsubscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_unsubscribe", "params": ["0x4333966aca52ad0b"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata), Some(response.to_owned()));
+	assert!(stats.subscriptions().is_empty());
+
+	let (res, _receiver) = receiver.into_future().wait().unwrap();
+	assert_eq!(res, None);
+}
+
+#[test]
+fn should_reject_full_pending_transactions_without_bodies() {
+	let el = Runtime::with_thread_count(1);
+	let client = TestBlockChainClient::new();
+	let (_, pool_receiver) = mpsc::unbounded();
+	let pubsub = EthPubSubClient::new(Arc::new(client), el.executor(), pool_receiver, Default::default());
+
+	let mut io = MetaIoHandler::default();
+	io.extend_with(pubsub.to_delegate());
+
+	let mut metadata = Metadata::default();
+	let (sender, _receiver) = futures::sync::mpsc::channel(8);
+	metadata.session = Some(Arc::new(Session::new(sender)));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["newPendingTransactions", true], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"Full pending transactions are not available on this node."},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata), Some(response.to_owned()));
+}
+
 #[test]
 fn eth_subscribe_syncing() {
 	// given
 	let el = Runtime::with_thread_count(1);
 	let client = TestBlockChainClient::new();
 	let (_, pool_receiver) = mpsc::unbounded();
-	let pubsub = EthPubSubClient::new(Arc::new(client), el.executor(), pool_receiver);
+	let pubsub = EthPubSubClient::new(Arc::new(client), el.executor(), pool_receiver, Default::default());
 	let pubsub = pubsub.to_delegate();
 
 	let mut io = MetaIoHandler::default();
@@ -21,18 +21,21 @@ use ethereum_types::{Address, U256, H256, BigEndianHash, Bloom};
 use ethstore::ethkey::{Generator, Random};
 use machine::executed::Executed;
 use miner::pool::local_transactions::Status as LocalTransactionStatus;
+use snapshot::SnapshotService;
 use sync::ManageNetwork;
 use types::{
 	ids::TransactionId,
 	receipt::{LocalizedReceipt, TransactionOutcome},
+	database_stats::{ColumnStats, CompactionStatus, DatabaseStats},
+	snapshot::CreationStatus,
 };
 
 use jsonrpc_core::IoHandler;
 use v1::{Parity, ParityClient};
 use v1::metadata::Metadata;
-use v1::helpers::NetworkSettings;
+use v1::helpers::{NetworkSettings, PubSubStats};
 use v1::helpers::external_signer::SignerService;
-use v1::tests::helpers::{TestSyncProvider, Config, TestMinerService, TestUpdater};
+use v1::tests::helpers::{TestSyncProvider, Config, TestMinerService, TestUpdater, TestSnapshotService};
 use super::manage_network::TestManageNetwork;
 use Host;
 
@@ -47,6 +50,8 @@ pub struct Dependencies {
 	pub settings: Arc<NetworkSettings>,
 	pub network: Arc<dyn ManageNetwork>,
 	pub ws_address: Option<Host>,
+	pub pubsub_stats: Arc<PubSubStats>,
+	pub snapshot: Arc<TestSnapshotService>,
 }
 
 impl Dependencies {
@@ -71,6 +76,8 @@ impl Dependencies {
 			}),
 			network: Arc::new(TestManageNetwork),
 			ws_address: Some("127.0.0.1:18546".into()),
+			pubsub_stats: Default::default(),
+			snapshot: Arc::new(TestSnapshotService::new()),
 		}
 	}
 
@@ -85,7 +92,8 @@ impl Dependencies {
 			self.settings.clone(),
 			signer,
 			self.ws_address.clone(),
-			None,
+			self.pubsub_stats.clone(),
+			Some(self.snapshot.clone() as Arc<dyn SnapshotService>),
 		)
 	}
 
@@ -249,6 +257,24 @@ fn rpc_parity_chain() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_list_storage_keys_paged() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_listStorageKeysPaged", "params":["0x0000000000000000000000000000000000000001", 10], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_listStorageKeysPaged", "params":["0x0000000000000000000000000000000000000001", 10, "0xabcd"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: token","data":"\"unknown continuation token\""},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_listStorageKeysPaged", "params":["0x0000000000000000000000000000000000000001", 0], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: count","data":"\"must be greater than zero\""},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_parity_net_peers() {
 	let deps = Dependencies::new();
@@ -326,6 +352,38 @@ fn rpc_parity_pending_transactions() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_pending_transactions_not_allowed() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+	let tx = |nonce: u64| ::types::transaction::Transaction {
+		value: 5.into(),
+		gas: 3.into(),
+		gas_price: 2.into(),
+		action: ::types::transaction::Action::Create,
+		data: vec![1, 2, 3],
+		nonce: nonce.into(),
+	}.fake_sign(Address::from_low_u64_be(3));
+	let (allowed, not_allowed) = (tx(0), tx(1));
+	deps.miner.pending_transactions.lock().insert(allowed.hash(), allowed);
+	deps.miner.pending_transactions.lock().insert(not_allowed.hash(), not_allowed.clone());
+	deps.miner.not_allowed_transactions.lock().insert(not_allowed.hash());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_pendingTransactions", "params":[], "id": 1}"#;
+	let response = io.handle_request_sync(request).unwrap();
+	let response: ::serde_json::Value = ::serde_json::from_str(&response).unwrap();
+	let transactions = response["result"].as_array().unwrap();
+
+	assert_eq!(transactions.len(), 2);
+	for transaction in transactions {
+		if transaction["hash"] == format!("{:?}", not_allowed.hash()) {
+			assert_eq!(transaction["status"], "notAllowed");
+		} else {
+			assert!(transaction.get("status").is_none());
+		}
+	}
+}
+
 #[test]
 fn rpc_parity_encrypt() {
 	let deps = Dependencies::new();
@@ -389,6 +447,17 @@ fn rpc_parity_transactions_stats() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_ws_stats() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_wsStats", "params":[], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[],"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_parity_local_transactions() {
 	let deps = Dependencies::new();
@@ -425,6 +494,45 @@ fn rpc_parity_chain_status() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_snapshot_status() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_snapshotStatus", "params":[], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"accounts":"0x0","blocks":"0x0","estimatedAccounts":null,"inProgress":false,"size":"0x0"},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	deps.snapshot.set_creation_status(CreationStatus::Ongoing {
+		accounts: 100,
+		estimated_accounts: Some(1600),
+		blocks: 30000,
+		size: 1024,
+	});
+	let response = r#"{"jsonrpc":"2.0","result":{"accounts":"0x64","blocks":"0x7530","estimatedAccounts":"0x640","inProgress":true,"size":"0x400"},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_db_stats() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	*deps.client.database_stats.write() = DatabaseStats {
+		columns: vec![ColumnStats {
+			column: 0,
+			size: 4096,
+			estimated_keys: 100,
+			pending_compaction_bytes: 1024,
+		}],
+		compaction: CompactionStatus::Ongoing { column: 0, done: 0, total: 9 },
+	};
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_dbStats", "params":[], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"columns":[{"column":0,"estimatedKeys":"0x64","pendingCompactionBytes":"0x400","size":"0x1000"}],"compactedColumns":0,"compacting":true,"compactingColumn":0,"compactionError":null,"totalColumns":9},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_parity_node_kind() {
 	let deps = Dependencies::new();
@@ -436,6 +544,17 @@ fn rpc_parity_node_kind() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_chain_spec_builtins() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_chainSpecBuiltins", "params":[], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"0x0000000000000000000000000000000000000001":{"active":true,"name":"ecrecover","pricing":{"linear":{"base":3000,"word":0}},"schedule":[]},"0x0000000000000000000000000000000000000002":{"active":true,"name":"sha256","pricing":{"linear":{"base":60,"word":12}},"schedule":[]},"0x0000000000000000000000000000000000000003":{"active":true,"name":"ripemd160","pricing":{"linear":{"base":600,"word":120}},"schedule":[]},"0x0000000000000000000000000000000000000004":{"active":true,"name":"identity","pricing":{"linear":{"base":15,"word":3}},"schedule":[]}},"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_parity_cid() {
 	let deps = Dependencies::new();
@@ -495,6 +614,7 @@ fn rpc_parity_block_receipts() {
 			block_number: 0,
 			cumulative_gas_used: 21_000.into(),
 			gas_used: 21_000.into(),
+			effective_gas_price: 1.into(),
 			contract_address: None,
 			logs: vec![],
 			log_bloom: Bloom::from_low_u64_be(1),
@@ -510,7 +630,7 @@ fn rpc_parity_block_receipts() {
 		"params": [],
 		"id": 1
 	}"#;
-	let response = r#"{"jsonrpc":"2.0","result":[{"blockHash":"0x0000000000000000000000000000000000000000000000000000000000000003","blockNumber":"0x0","contractAddress":null,"cumulativeGasUsed":"0x5208","from":"0x0000000000000000000000000000000000000009","gasUsed":"0x5208","logs":[],"logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001","root":null,"status":null,"to":null,"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000001","transactionIndex":"0x0"}],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[{"blockHash":"0x0000000000000000000000000000000000000000000000000000000000000003","blockNumber":"0x0","contractAddress":null,"cumulativeGasUsed":"0x5208","effectiveGasPrice":"0x1","from":"0x0000000000000000000000000000000000000009","gasUsed":"0x5208","logs":[],"logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001","root":null,"status":null,"to":null,"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000001","transactionIndex":"0x0"}],"id":1}"#;
 
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
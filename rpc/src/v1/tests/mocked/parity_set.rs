@@ -21,11 +21,14 @@ use ethereum_types::{U256, Address};
 
 use ethcore::miner::MinerService;
 use ethcore::test_helpers::TestBlockChainClient;
-use sync::ManageNetwork;
+use snapshot::SnapshotService;
+use sync::{ManageNetwork, SyncProvider, SyncState};
+use types::database_stats::CompactionStatus;
+use types::snapshot::RestorationStatus;
 
 use jsonrpc_core::IoHandler;
 use v1::{ParitySet, ParitySetClient};
-use v1::tests::helpers::{TestMinerService, TestUpdater};
+use v1::tests::helpers::{Config, TestMinerService, TestSnapshotService, TestSyncProvider, TestUpdater};
 use super::manage_network::TestManageNetwork;
 
 use fake_fetch::FakeFetch;
@@ -53,12 +56,36 @@ fn parity_set_client(
 	miner: &Arc<TestMinerService>,
 	updater: &Arc<TestUpdater>,
 	net: &Arc<TestManageNetwork>,
+) -> TestParitySetClient {
+	parity_set_client_with_sync(client, miner, updater, net, &sync_provider(), &snapshot_service())
+}
+
+fn sync_provider() -> Arc<TestSyncProvider> {
+	Arc::new(TestSyncProvider::new(Config {
+		network_id: 3,
+		num_peers: 120,
+	}))
+}
+
+fn snapshot_service() -> Arc<TestSnapshotService> {
+	Arc::new(TestSnapshotService::new())
+}
+
+fn parity_set_client_with_sync(
+	client: &Arc<TestBlockChainClient>,
+	miner: &Arc<TestMinerService>,
+	updater: &Arc<TestUpdater>,
+	net: &Arc<TestManageNetwork>,
+	sync: &Arc<TestSyncProvider>,
+	snapshot: &Arc<TestSnapshotService>,
 ) -> TestParitySetClient {
 	ParitySetClient::new(
 		client,
 		miner,
 		updater,
 		&(net.clone() as Arc<dyn ManageNetwork>),
+		sync.clone() as Arc<dyn SyncProvider>,
+		Some(snapshot.clone() as Arc<dyn SnapshotService>),
 		FakeFetch::new(Some(1)),
 	)
 }
@@ -185,6 +212,24 @@ fn rpc_parity_set_author() {
 	assert_eq!(miner.authoring_params().author, Address::from_str("cd1722f3947def4cf144679da39c4c32bdc35681").unwrap());
 }
 
+#[test]
+fn rpc_parity_set_priority_senders() {
+	let miner = miner_service();
+	let client = client_service();
+	let network = network_service();
+	let updater = updater_service();
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client, &miner, &updater, &network).to_delegate());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_setPrioritySenders", "params":[["0xcd1722f3947def4cf144679da39c4c32bdc35681"]], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+	let priority_senders = miner.priority_senders.read();
+	assert_eq!(priority_senders.len(), 1);
+	assert!(priority_senders.contains(&Address::from_str("cd1722f3947def4cf144679da39c4c32bdc35681").unwrap()));
+}
+
 #[test]
 fn rpc_parity_set_transactions_limit() {
 	let miner = miner_service();
@@ -244,6 +289,86 @@ fn rpc_parity_remove_transaction() {
 	assert_eq!(io.handle_request_sync(&request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_remove_transaction_refuses_mined_transaction() {
+	use types::transaction::{Transaction, Action};
+
+	let miner = miner_service();
+	let client = client_service();
+	let network = network_service();
+	let updater = updater_service();
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client, &miner, &updater, &network).to_delegate());
+
+	let signed = Transaction {
+		nonce: 1.into(),
+		gas_price: 0x9184e72a000u64.into(),
+		gas: 0x76c0.into(),
+		action: Action::Call(Address::from_low_u64_be(5)),
+		value: 0x9184e72au64.into(),
+		data: vec![]
+	}.fake_sign(Address::from_low_u64_be(2));
+	let hash = signed.hash();
+	miner.pending_transactions.lock().insert(hash, signed.clone());
+	client.add_block_with_transactions(&[signed], |header| header);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_removeTransaction", "params":[""#.to_owned() + &format!("0x{:x}", hash) + r#""], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32010,"message":"Transaction is already mined."},"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(&request), Some(response.to_owned()));
+	assert!(miner.pending_transactions.lock().contains_key(&hash));
+}
+
+#[test]
+fn rpc_parity_cancel_transaction() {
+	use accounts::AccountProvider;
+	use types::transaction::{Transaction, Action};
+	use v1::impls::ParitySetAccountsClient;
+	use v1::traits::ParitySetAccounts;
+
+	let account_provider = Arc::new(AccountProvider::transient_provider());
+	let address = account_provider.insert_account(::hash::keccak("cow").into(), &"password".into()).unwrap();
+
+	let miner = miner_service();
+	let client = client_service();
+	let mut io = IoHandler::new();
+	io.extend_with(
+		ParitySetAccountsClient::new(&client, &account_provider, &miner).to_delegate()
+	);
+
+	let original = Transaction {
+		nonce: 3.into(),
+		gas_price: 10_000.into(),
+		gas: 0x76c0.into(),
+		action: Action::Call(Address::from_low_u64_be(5)),
+		value: 0x9184e72au64.into(),
+		data: vec![]
+	}.fake_sign(address);
+	let hash = original.hash();
+	miner.pending_transactions.lock().insert(hash, original);
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_cancelTransaction", "params":[""#.to_owned() + &format!("0x{:x}", hash) + r#""], "id": 1}"#;
+
+	// locked account
+	let response = io.handle_request_sync(&request).unwrap();
+	assert!(response.contains(r#""code":-32020"#), "Should refuse to sign with a locked account.");
+	assert!(miner.imported_transactions.lock().is_empty());
+
+	// unlocked account
+	account_provider.unlock_account_permanently(address, "password".into()).unwrap();
+	let response = io.handle_request_sync(&request).unwrap();
+
+	let imported = miner.imported_transactions.lock();
+	assert_eq!(imported.len(), 1);
+	let cancel = &imported[0];
+	let expected = r#"{"jsonrpc":"2.0","result":""#.to_owned() + &format!("0x{:x}", cancel.hash()) + r#"","id":1}"#;
+	assert_eq!(response, expected);
+	assert_eq!(cancel.sender(), address);
+	assert_eq!(cancel.nonce, 3.into());
+	assert_eq!(cancel.action, Action::Call(address));
+	assert_eq!(cancel.value, 0.into());
+	assert_eq!(cancel.gas_price, 11_250.into());
+}
+
 #[test]
 fn rpc_parity_set_engine_signer() {
 	use accounts::AccountProvider;
@@ -257,7 +382,7 @@ fn rpc_parity_set_engine_signer() {
 	let miner = miner_service();
 	let mut io = IoHandler::new();
 	io.extend_with(
-		ParitySetAccountsClient::new(&account_provider, &miner).to_delegate()
+		ParitySetAccountsClient::new(&client_service(), &account_provider, &miner).to_delegate()
 	);
 
 	let request = r#"{"jsonrpc": "2.0", "method": "parity_setEngineSigner", "params":["0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826", "password"], "id": 1}"#;
@@ -268,3 +393,52 @@ fn rpc_parity_set_engine_signer() {
 	let signature = miner.signer.read().as_ref().unwrap().sign(::hash::keccak("x")).unwrap().to_vec();
 	assert_eq!(&format!("{}", signature.pretty()), "6f46069ded2154af6e806706e4f7f6fd310ac45f3c6dccb85f11c0059ee20a09245df0a0008bb84a10882b1298284bc93058e7bc5938ea728e77620061687a6401");
 }
+
+#[test]
+fn rpc_parity_db_compact() {
+	let miner = miner_service();
+	let client = client_service();
+	let network = network_service();
+	let updater = updater_service();
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client, &miner, &updater, &network).to_delegate());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_dbCompact", "params": [2], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+	assert_eq!(client.database_stats.read().compaction, CompactionStatus::Ongoing { column: 2, done: 0, total: 1 });
+
+	// only one compaction at a time
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_dbCompact", "params": [], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"Database compaction can't be started.","data":"A database compaction is already running"},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_db_compact_refused_while_restoring() {
+	let miner = miner_service();
+	let client = client_service();
+	let network = network_service();
+	let updater = updater_service();
+	let sync = sync_provider();
+	let snapshot = snapshot_service();
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client_with_sync(&client, &miner, &updater, &network, &sync, &snapshot).to_delegate());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_dbCompact", "params": [], "id": 1}"#;
+
+	snapshot.set_status(RestorationStatus::Ongoing {
+		state_chunks: 40,
+		block_chunks: 10,
+		state_chunks_done: 18,
+		block_chunks_done: 6,
+	});
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"Database compaction can't be started.","data":"a snapshot restoration is in progress"},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	snapshot.set_status(RestorationStatus::Inactive);
+	sync.status.write().state = SyncState::SnapshotData;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"Database compaction can't be started.","data":"a warp sync is in progress"},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+	assert_eq!(client.database_stats.read().compaction, CompactionStatus::Inactive);
+}
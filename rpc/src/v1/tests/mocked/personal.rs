@@ -73,7 +73,7 @@ fn setup_with(c: Config) -> PersonalTester {
 	let miner = miner_service();
 	let reservations = Arc::new(Mutex::new(nonce::Reservations::new(runtime.executor())));
 
-	let dispatcher = FullDispatcher::new(client, miner.clone(), reservations, 50);
+	let dispatcher = FullDispatcher::new(client, miner.clone(), reservations);
 	let personal = PersonalClient::new(&accounts, dispatcher, false, c.allow_experimental_rpcs);
 
 	let mut io = IoHandler::default();
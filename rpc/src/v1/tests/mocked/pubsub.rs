@@ -20,6 +20,8 @@ use jsonrpc_core::{self as core, MetaIoHandler};
 use jsonrpc_core::futures::{self, Stream, Future};
 use jsonrpc_pubsub::Session;
 
+use ethereum_types::H256;
+use miner::pool::{PoolEvent, TxStatus};
 use parity_runtime::Runtime;
 use v1::{PubSub, PubSubClient, Metadata};
 
@@ -75,3 +77,46 @@ fn should_subscribe_to_a_method() {
 	let (res, _receiver) = receiver.into_future().wait().unwrap();
 	assert_eq!(res, None);
 }
+
+#[test]
+fn should_subscribe_to_transactions_pool_events() {
+	// given
+	let el = Runtime::with_thread_count(1);
+	let mut pubsub = PubSubClient::new_test(rpc(), el.executor());
+	let (pool_sender, pool_receiver) = futures::sync::mpsc::unbounded();
+	pubsub.add_pool_events_notifier(pool_receiver);
+
+	let mut io = MetaIoHandler::default();
+	io.extend_with(pubsub.to_delegate());
+
+	let mut metadata = Metadata::default();
+	let (sender, receiver) = futures::sync::mpsc::channel(8);
+	metadata.session = Some(Arc::new(Session::new(sender)));
+
+	// Subscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_subscribe", "params": ["transactionsPool"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x4333966aca52ad0b","id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
+
+	// Check notifications
+	pool_sender.unbounded_send(Arc::new(vec![
+		PoolEvent { hash: H256::from_low_u64_be(1), event: TxStatus::Added, reason: None },
+		PoolEvent { hash: H256::from_low_u64_be(2), event: TxStatus::Rejected, reason: Some("Too cheap".into()) },
+	])).unwrap();
+
+	let (res, receiver) = receiver.into_future().wait().unwrap();
+	let response = r#"{"jsonrpc":"2.0","method":"parity_subscription","params":{"result":{"event":"added","hash":"0x0000000000000000000000000000000000000000000000000000000000000001"},"subscription":"0x4333966aca52ad0b"}}"#;
+	assert_eq!(res, Some(response.into()));
+
+	let (res, receiver) = receiver.into_future().wait().unwrap();
+	let response = r#"{"jsonrpc":"2.0","method":"parity_subscription","params":{"result":{"event":"rejected","hash":"0x0000000000000000000000000000000000000000000000000000000000000002","reason":"Too cheap"},"subscription":"0x4333966aca52ad0b"}}"#;
+	assert_eq!(res, Some(response.into()));
+
+	// And unsubscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_unsubscribe", "params": ["0x4333966aca52ad0b"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata), Some(response.to_owned()));
+
+	let (res, _receiver) = receiver.into_future().wait().unwrap();
+	assert_eq!(res, None);
+}
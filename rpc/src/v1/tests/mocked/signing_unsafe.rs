@@ -27,7 +27,7 @@ use rustc_hex::ToHex;
 use types::transaction::{Transaction, Action};
 
 use jsonrpc_core::IoHandler;
-use v1::{EthClientOptions, EthSigning, SigningUnsafeClient};
+use v1::{EthSigning, SigningUnsafeClient};
 use v1::helpers::nonce;
 use v1::helpers::dispatch::{self, FullDispatcher};
 use v1::tests::helpers::TestMinerService;
@@ -56,21 +56,14 @@ struct EthTester {
 
 impl Default for EthTester {
 	fn default() -> Self {
-		Self::new_with_options(Default::default())
-	}
-}
-
-impl EthTester {
-	pub fn new_with_options(options: EthClientOptions) -> Self {
 		let runtime = Runtime::with_thread_count(1);
 		let client = blockchain_client();
 		let accounts_provider = accounts_provider();
 		let ap = Arc::new(dispatch::Signer::new(accounts_provider.clone())) as _;
 		let miner = miner_service();
-		let gas_price_percentile = options.gas_price_percentile;
 		let reservations = Arc::new(Mutex::new(nonce::Reservations::new(runtime.executor())));
 
-		let dispatcher = FullDispatcher::new(client.clone(), miner.clone(), reservations, gas_price_percentile);
+		let dispatcher = FullDispatcher::new(client.clone(), miner.clone(), reservations);
 		let sign = SigningUnsafeClient::new(&ap, dispatcher).to_delegate();
 		let mut io: IoHandler<Metadata> = IoHandler::default();
 		io.extend_with(sign);
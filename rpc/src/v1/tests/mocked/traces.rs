@@ -68,7 +68,7 @@ fn io() -> Tester {
 		state_diff: None,
 	}));
 	let miner = Arc::new(TestMinerService::default());
-	let traces = TracesClient::new(&client);
+	let traces = TracesClient::new(&client, 2);
 	let mut io = IoHandler::default();
 	io.extend_with(traces.to_delegate());
 
@@ -100,6 +100,27 @@ fn rpc_trace_filter_missing_trace() {
 	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_trace_filter_max_count() {
+	let tester = io();
+	let mut traces = tester.client.traces.read().clone().unwrap();
+	traces.extend(traces.clone());
+	traces.extend(traces.clone());
+	traces[1].trace_address = vec![1];
+	*tester.client.traces.write() = Some(traces);
+
+	let request = r#"{"jsonrpc":"2.0","method":"trace_filter","params": [{}],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32041,"message":"Requested data size exceeds limit of 2 traces."},"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc":"2.0","method":"trace_filter","params": [{"count": 3}],"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc":"2.0","method":"trace_filter","params": [{"after": 1, "count": 1}],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[{"action":{"callType":"call","from":"0x000000000000000000000000000000000000000f","gas":"0x100","input":"0x010203","to":"0x0000000000000000000000000000000000000010","value":"0x1"},"blockHash":"0x000000000000000000000000000000000000000000000000000000000000000a","blockNumber":10,"result":null,"subtraces":0,"traceAddress":[1],"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000005","transactionPosition":0,"type":"call"}],"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_trace_block() {
 	let tester = io();
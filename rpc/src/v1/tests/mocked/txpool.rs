@@ -0,0 +1,93 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use ethcore::test_helpers::TestBlockChainClient;
+use ethereum_types::Address;
+use serde_json;
+use types::transaction::{Action, SignedTransaction, Transaction};
+
+use jsonrpc_core::IoHandler;
+use v1::{TxPool, TxPoolClient};
+use v1::tests::helpers::TestMinerService;
+
+fn transaction(sender: u64, nonce: u64, action: Action) -> SignedTransaction {
+	Transaction {
+		nonce: nonce.into(),
+		gas_price: 1_000.into(),
+		gas: 21_000.into(),
+		action,
+		value: 1.into(),
+		data: vec![],
+	}.fake_sign(Address::from_low_u64_be(sender))
+}
+
+/// Sender 2 has transactions with nonces 0 (already mined), 1, 2 and 4 at state nonce 1,
+/// sender 3 has a contract creation at state nonce 0.
+fn io() -> IoHandler {
+	let client = TestBlockChainClient::default();
+	client.set_nonce(Address::from_low_u64_be(2), 1.into());
+
+	let miner = TestMinerService::default();
+	let transactions = vec![
+		transaction(2, 4, Action::Call(Address::from_low_u64_be(5))),
+		transaction(2, 0, Action::Call(Address::from_low_u64_be(5))),
+		transaction(2, 2, Action::Call(Address::from_low_u64_be(5))),
+		transaction(2, 1, Action::Call(Address::from_low_u64_be(5))),
+		transaction(3, 0, Action::Create),
+	];
+	for tx in transactions {
+		miner.pending_transactions.lock().insert(tx.hash(), tx);
+	}
+
+	let mut io = IoHandler::new();
+	io.extend_with(TxPoolClient::new(Arc::new(client), Arc::new(miner)).to_delegate());
+	io
+}
+
+#[test]
+fn rpc_txpool_status() {
+	let request = r#"{"jsonrpc": "2.0", "method": "txpool_status", "params": [], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"pending":"0x3","queued":"0x1"},"id":1}"#;
+
+	assert_eq!(io().handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_txpool_content() {
+	let request = r#"{"jsonrpc": "2.0", "method": "txpool_content", "params": [], "id": 1}"#;
+	let response: serde_json::Value = serde_json::from_str(&io().handle_request_sync(request).unwrap()).unwrap();
+	let result = &response["result"];
+
+	let nonces = |txs: &serde_json::Value| txs.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+	assert_eq!(nonces(&result["pending"]["0x0000000000000000000000000000000000000002"]), vec!["1", "2"]);
+	assert_eq!(nonces(&result["pending"]["0x0000000000000000000000000000000000000003"]), vec!["0"]);
+	assert_eq!(nonces(&result["queued"]["0x0000000000000000000000000000000000000002"]), vec!["4"]);
+	assert!(result["queued"]["0x0000000000000000000000000000000000000003"].is_null());
+
+	let tx = &result["pending"]["0x0000000000000000000000000000000000000002"]["2"];
+	assert_eq!(tx["nonce"], "0x2");
+	assert_eq!(tx["from"], "0x0000000000000000000000000000000000000002");
+}
+
+#[test]
+fn rpc_txpool_inspect() {
+	let request = r#"{"jsonrpc": "2.0", "method": "txpool_inspect", "params": [], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"pending":{"0x0000000000000000000000000000000000000002":{"1":"0x0000000000000000000000000000000000000005: 1 wei + 21000 gas × 1000 wei","2":"0x0000000000000000000000000000000000000005: 1 wei + 21000 gas × 1000 wei"},"0x0000000000000000000000000000000000000003":{"0":"contract creation: 1 wei + 21000 gas × 1000 wei"}},"queued":{"0x0000000000000000000000000000000000000002":{"4":"0x0000000000000000000000000000000000000005: 1 wei + 21000 gas × 1000 wei"}}},"id":1}"#;
+
+	assert_eq!(io().handle_request_sync(request), Some(response.to_owned()));
+}
@@ -16,10 +16,11 @@
 
 //! Debug RPC interface.
 
+use ethereum_types::H256;
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 
-use v1::types::RichBlock;
+use v1::types::{DebugTrace, DebugTraceOptions, RichBlock};
 
 /// Debug RPC interface.
 #[rpc(server)]
@@ -27,4 +28,9 @@ pub trait Debug {
 	/// Returns recently seen bad blocks.
 	#[rpc(name = "debug_getBadBlocks")]
 	fn bad_blocks(&self) -> Result<Vec<RichBlock>>;
+
+	/// Replays a transaction and returns its geth style trace: the state of the VM before every
+	/// instruction, or the tree of calls made with the `callTracer` tracer.
+	#[rpc(name = "debug_traceTransaction")]
+	fn trace_transaction(&self, H256, Option<DebugTraceOptions>) -> Result<DebugTrace>;
 }
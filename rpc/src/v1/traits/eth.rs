@@ -19,8 +19,8 @@ use jsonrpc_core::{Result, BoxFuture};
 use jsonrpc_derive::rpc;
 use ethereum_types::{H64, H160, H256, U64, U256};
 
-use v1::types::{RichBlock, BlockNumber, Bytes, CallRequest, Filter, FilterChanges, Index, EthAccount};
-use v1::types::{Log, Receipt, SyncStatus, Transaction, Work};
+use v1::types::{RichBlock, BlockNumber, Bytes, CallRequest, Filter, FilterChanges, Index, EthAccount, StateOverrides};
+use v1::types::{FeeHistory, Log, Receipt, SyncStatus, Transaction, Work};
 
 /// Eth rpc interface.
 #[rpc(server)]
@@ -58,6 +58,11 @@ pub trait Eth {
 	#[rpc(name = "eth_gasPrice")]
 	fn gas_price(&self) -> BoxFuture<U256>;
 
+	/// Returns base fees, gas usage and priority fees at the given percentiles for a range of
+	/// blocks ending with the given one.
+	#[rpc(name = "eth_feeHistory")]
+	fn fee_history(&self, U256, BlockNumber, Option<Vec<f64>>) -> BoxFuture<FeeHistory>;
+
 	/// Returns accounts list.
 	#[rpc(name = "eth_accounts")]
 	fn accounts(&self) -> Result<Vec<H160>>;
@@ -118,9 +123,9 @@ pub trait Eth {
 	#[rpc(name = "eth_submitTransaction")]
 	fn submit_transaction(&self, Bytes) -> Result<H256>;
 
-	/// Call contract, returning the output data.
+	/// Call contract, returning the output data. Accounts can be overridden for the duration of the call.
 	#[rpc(name = "eth_call")]
-	fn call(&self, CallRequest, Option<BlockNumber>) -> BoxFuture<Bytes>;
+	fn call(&self, CallRequest, Option<BlockNumber>, Option<StateOverrides>) -> BoxFuture<Bytes>;
 
 	/// Estimate gas needed for execution of given contract.
 	#[rpc(name = "eth_estimateGas")]
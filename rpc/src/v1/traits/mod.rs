@@ -33,6 +33,7 @@ pub mod secretstore;
 pub mod signer;
 pub mod traces;
 pub mod transactions_pool;
+pub mod txpool;
 pub mod web3;
 
 pub use self::debug::Debug;
@@ -52,4 +53,5 @@ pub use self::secretstore::SecretStore;
 pub use self::signer::Signer;
 pub use self::traces::Traces;
 pub use self::transactions_pool::TransactionsPool;
+pub use self::txpool::TxPool;
 pub use self::web3::Web3;
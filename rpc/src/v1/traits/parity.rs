@@ -27,9 +27,10 @@ use v1::types::{
 	Peers, Transaction, RpcSettings, Histogram, RecoveredAccount,
 	TransactionStats, LocalTransactionStatus,
 	BlockNumber, ConsensusCapability, VersionInfo,
-	OperationsInfo, ChainStatus, Log, Filter,
-	RichHeader, Receipt,
+	OperationsInfo, ChainStatus, SnapshotStatus, DatabaseStats, Log, Filter,
+	RichHeader, Receipt, BuiltinInfo, StorageKeysPage,
 };
+use v1::types::pubsub::SubscriptionStats;
 
 /// Parity-specific rpc interface.
 #[rpc(server)]
@@ -119,6 +120,16 @@ pub trait Parity {
 	#[rpc(name = "parity_listStorageKeys")]
 	fn list_storage_keys(&self, H160, Option<u64>, Option<H256>, Option<BlockNumber>) -> Result<Option<Vec<H256>>>;
 
+	/// Returns at most `count` (second parameter) storage keys of the given address (first parameter)
+	/// if Fat DB is enabled (`--fat-db`), or null if not. The page carries an opaque token to pass
+	/// in as the third parameter to resume the walk after the last returned key, without starting
+	/// over from the storage root.
+	///
+	/// Tokens are only valid for the block they were issued for and are invalidated once the state
+	/// of that block gets pruned, in which case error `-32043` is returned.
+	#[rpc(name = "parity_listStorageKeysPaged")]
+	fn list_storage_keys_paged(&self, H160, u64, Option<Bytes>, Option<BlockNumber>) -> Result<Option<StorageKeysPage>>;
+
 	/// Encrypt some data with a public key under ECIES.
 	/// First parameter is the 512-byte destination public key, second is the message.
 	#[rpc(name = "parity_encryptMessage")]
@@ -154,6 +165,11 @@ pub trait Parity {
 	#[rpc(name = "parity_wsUrl")]
 	fn ws_url(&self) -> Result<String>;
 
+	/// Returns delivery statistics of every live pub-sub subscription, including the number of
+	/// notifications dropped because the subscriber couldn't keep up.
+	#[rpc(name = "parity_wsStats")]
+	fn ws_stats(&self) -> Result<Vec<SubscriptionStats>>;
+
 	/// Returns next nonce for particular sender. Should include all transactions in the queue.
 	#[rpc(name = "parity_nextNonce")]
 	fn next_nonce(&self, H160) -> BoxFuture<U256>;
@@ -186,6 +202,19 @@ pub trait Parity {
 	#[rpc(name = "parity_chainStatus")]
 	fn chain_status(&self) -> Result<ChainStatus>;
 
+	/// Get the progress of the snapshot being taken, if any.
+	#[rpc(name = "parity_snapshotStatus")]
+	fn snapshot_status(&self) -> Result<SnapshotStatus>;
+
+	/// Get the size statistics of the database columns and the progress of the manual compaction, if any.
+	#[rpc(name = "parity_dbStats")]
+	fn db_stats(&self) -> Result<DatabaseStats>;
+
+	/// Get the builtin contracts of the chain spec, with their activation state and pricing
+	/// at the latest block.
+	#[rpc(name = "parity_chainSpecBuiltins")]
+	fn chain_spec_builtins(&self) -> Result<BTreeMap<H160, BuiltinInfo>>;
+
 	/// Get node kind info.
 	#[rpc(name = "parity_nodeKind")]
 	fn node_kind(&self) -> Result<::v1::types::NodeKind>;
@@ -28,6 +28,14 @@ pub trait ParitySetAccounts {
 	/// Sets account for signing consensus messages.
 	#[rpc(name = "parity_setEngineSigner")]
 	fn set_engine_signer(&self, H160, String) -> Result<bool>;
+
+	/// Cancels a transaction from the transaction queue.
+	/// Replaces it with a zero-value transaction sent by its sender to itself, with the same nonce
+	/// and a bumped gas price, to race the original transaction on the network.
+	/// The sender account has to be unlocked. Refuses to cancel transactions that are already mined.
+	/// Returns the hash of the replacement transaction.
+	#[rpc(name = "parity_cancelTransaction")]
+	fn cancel_transaction(&self, H256) -> Result<H256>;
 }
 
 /// Parity-specific rpc interface for operations altering the settings.
@@ -69,6 +77,10 @@ pub trait ParitySet {
 	#[rpc(name = "parity_setMaxTransactionGas")]
 	fn set_tx_gas_limit(&self, U256) -> Result<bool>;
 
+	/// Sets the senders whose transactions are treated like local ones and included first in mined blocks.
+	#[rpc(name = "parity_setPrioritySenders")]
+	fn set_priority_senders(&self, Vec<H160>) -> Result<bool>;
+
 	/// Add a reserved peer.
 	#[rpc(name = "parity_addReservedPeer")]
 	fn add_reserved_peer(&self, String) -> Result<bool>;
@@ -122,7 +134,14 @@ pub trait ParitySet {
 	/// like scheduled transactions or transactions in future.
 	/// It might also work for some local transactions with to low gas price
 	/// or excessive gas limit that are not accepted by other peers whp.
-	/// Returns `true` when transaction was removed, `false` if it was not found.
+	/// Refuses to remove transactions that are already mined.
+	/// Returns the removed transaction, `null` if it was not found.
 	#[rpc(name = "parity_removeTransaction")]
 	fn remove_transaction(&self, H256) -> Result<Option<Transaction>>;
+
+	/// Starts a manual compaction of the given database column, or of the whole database, in the background.
+	/// Progress is reported by `parity_dbStats`. Refuses to start while another compaction,
+	/// a snapshot restoration or a warp sync is running.
+	#[rpc(name = "parity_dbCompact")]
+	fn db_compact(&self, Option<u32>) -> Result<bool>;
 }
@@ -27,6 +27,8 @@ pub trait PubSub {
 	type Metadata;
 
 	/// Subscribe to changes of any RPC method in Parity.
+	///
+	/// Subscribing to `transactionsPool` streams every transaction pool event as `{hash, event, reason?}`.
 	#[pubsub(subscription = "parity_subscription", subscribe, name = "parity_subscribe")]
 	fn parity_subscribe(&self, Self::Metadata, Subscriber<Value>, String, Option<Params>);
 
@@ -0,0 +1,39 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Geth-compatible txpool RPC interface.
+
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+
+use v1::types::{Transaction, TxPoolContent, TxPoolStatus};
+
+/// Geth-compatible txpool RPC interface.
+#[rpc(server)]
+pub trait TxPool {
+	/// Returns the number of pending and queued transactions in the pool.
+	#[rpc(name = "txpool_status")]
+	fn status(&self) -> Result<TxPoolStatus>;
+
+	/// Returns the pending and queued transactions in the pool, grouped by sender and nonce.
+	#[rpc(name = "txpool_content")]
+	fn content(&self) -> Result<TxPoolContent<Transaction>>;
+
+	/// Returns one-line summaries of the pending and queued transactions in the pool,
+	/// grouped by sender and nonce.
+	#[rpc(name = "txpool_inspect")]
+	fn inspect(&self) -> Result<TxPoolContent<String>>;
+}
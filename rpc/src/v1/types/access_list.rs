@@ -0,0 +1,69 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! EIP-2930 access list.
+
+use ethereum_types::{H160, H256};
+use types::transaction;
+
+/// An account and the storage keys of it a transaction declares to access.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+	/// Accessed account.
+	pub address: H160,
+	/// Accessed storage keys of the account.
+	pub storage_keys: Vec<H256>,
+}
+
+/// Access list of a transaction.
+pub type AccessList = Vec<AccessListItem>;
+
+impl From<transaction::AccessListItem> for AccessListItem {
+	fn from(item: transaction::AccessListItem) -> Self {
+		AccessListItem {
+			address: item.address,
+			storage_keys: item.storage_keys,
+		}
+	}
+}
+
+impl Into<transaction::AccessListItem> for AccessListItem {
+	fn into(self) -> transaction::AccessListItem {
+		transaction::AccessListItem {
+			address: self.address,
+			storage_keys: self.storage_keys,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use ethereum_types::{H160, H256};
+	use super::AccessListItem;
+
+	#[test]
+	fn access_list_item_serialization() {
+		let s = r#"{"address":"0x0000000000000000000000000000000000000001","storageKeys":["0x0000000000000000000000000000000000000000000000000000000000000000"]}"#;
+		let item: AccessListItem = serde_json::from_str(s).unwrap();
+		assert_eq!(item, AccessListItem {
+			address: H160::from_low_u64_be(1),
+			storage_keys: vec![H256::zero()],
+		});
+		assert_eq!(serde_json::to_string(&item).unwrap(), s);
+	}
+}
@@ -30,21 +30,31 @@ pub struct AccountInfo {
 #[derive(Debug, Default, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StorageProof {
+	/// Requested storage key
 	pub key: U256,
+	/// Storage value, zero if the slot is empty
 	pub value: U256,
+	/// Storage trie nodes on the path from `storage_hash` to the key
 	pub proof: Vec<Bytes>
 }
 
-/// Account information.
+/// Account state with Merkle proofs (EIP-1186).
 #[derive(Debug, Default, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EthAccount {
+	/// Account address
 	pub address: H160,
+	/// Account balance
 	pub balance: U256,
+	/// Account nonce
 	pub nonce: U256,
+	/// Hash of the account code
 	pub code_hash: H256,
+	/// Root of the account storage trie
 	pub storage_hash: H256,
+	/// State trie nodes on the path from the state root to the account
 	pub account_proof: Vec<Bytes>,
+	/// Proofs of the requested storage keys
 	pub storage_proof: Vec<StorageProof>,
 }
 
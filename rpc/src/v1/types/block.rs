@@ -20,6 +20,7 @@ use std::collections::BTreeMap;
 use ethereum_types::{H160, H256, U256, Bloom as H2048};
 use serde::ser::Error;
 use serde::{Serialize, Serializer};
+use types::BlockNumber;
 use types::encoded::Header as EthHeader;
 use v1::types::{Bytes, Transaction};
 
@@ -87,6 +88,9 @@ pub struct Block {
 	pub transactions: BlockTransactions,
 	/// Size in bytes
 	pub size: Option<U256>,
+	/// Base fee per gas, since EIP-1559
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub base_fee_per_gas: Option<U256>,
 }
 
 /// Block header representation.
@@ -128,16 +132,17 @@ pub struct Header {
 	pub seal_fields: Vec<Bytes>,
 	/// Size in bytes
 	pub size: Option<U256>,
+	/// Base fee per gas, since EIP-1559
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub base_fee_per_gas: Option<U256>,
 }
 
-impl From<EthHeader> for Header {
-	fn from(h: EthHeader) -> Self {
-		(&h).into()
-	}
-}
-
-impl<'a> From<&'a EthHeader> for Header {
-	fn from(h: &'a EthHeader) -> Self {
+impl Header {
+	/// Creates the header representation. From the EIP-1559 transition on the last
+	/// field of the header is the base fee rather than part of the seal.
+	pub fn new(h: &EthHeader, eip1559_transition: BlockNumber) -> Self {
+		let decoded = h.decode(eip1559_transition)
+			.expect("Client/Miner returns only valid headers. We only serialize headers from Client/Miner; qed");
 		Header {
 			hash: Some(h.hash()),
 			size: Some(h.rlp().as_raw().len().into()),
@@ -155,9 +160,8 @@ impl<'a> From<&'a EthHeader> for Header {
 			timestamp: h.timestamp().into(),
 			difficulty: h.difficulty(),
 			extra_data: h.extra_data().into(),
-			seal_fields: h.view().decode_seal()
-				.expect("Client/Miner returns only valid headers. We only serialize headers from Client/Miner; qed")
-				.into_iter().map(Into::into).collect(),
+			seal_fields: decoded.seal().iter().cloned().map(Into::into).collect(),
+			base_fee_per_gas: decoded.base_fee(),
 		}
 	}
 }
@@ -244,6 +248,7 @@ mod tests {
 			uncles: vec![],
 			transactions: BlockTransactions::Hashes(vec![].into()),
 			size: Some(69.into()),
+			base_fee_per_gas: None,
 		};
 		let serialized_block = serde_json::to_string(&block).unwrap();
 		let rich_block = RichBlock {
@@ -282,6 +287,7 @@ mod tests {
 			uncles: vec![],
 			transactions: BlockTransactions::Hashes(vec![].into()),
 			size: None,
+			base_fee_per_gas: None,
 		};
 		let serialized_block = serde_json::to_string(&block).unwrap();
 		let rich_block = RichBlock {
@@ -317,6 +323,7 @@ mod tests {
 			difficulty: U256::default(),
 			seal_fields: vec![Bytes::default(), Bytes::default()],
 			size: Some(69.into()),
+			base_fee_per_gas: None,
 		};
 		let serialized_header = serde_json::to_string(&header).unwrap();
 		let rich_header = RichHeader {
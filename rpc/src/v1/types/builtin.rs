@@ -0,0 +1,93 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Builtin contract pricing info.
+
+use ethjson::spec::{Builtin, Pricing, PricingAt, PricingInner};
+
+/// Activation state and pricing schedule of a builtin contract, in the chain spec format.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuiltinInfo {
+	/// Builtin name.
+	pub name: String,
+	/// Whether the builtin is active at the latest block.
+	pub active: bool,
+	/// Pricing in effect at the latest block.
+	pub pricing: Option<PricingInner>,
+	/// Pricings taking effect after the latest block.
+	pub schedule: Vec<PricingAt>,
+}
+
+impl BuiltinInfo {
+	/// Describes the builtin loaded from `spec` as of the block with the given number and timestamp.
+	pub fn new(spec: &Builtin, active: bool, number: u64, timestamp: u64) -> Self {
+		let pricings = match spec.pricing {
+			Pricing::Single(ref price) => vec![PricingAt {
+				activate_at: spec.activate_at,
+				activate_at_timestamp: spec.activate_at_timestamp,
+				price: price.clone(),
+			}],
+			Pricing::Multi(ref pricings) => pricings.clone(),
+		};
+
+		let (reached, schedule): (Vec<_>, Vec<_>) = pricings.into_iter().partition(|p| {
+			match (p.activate_at, p.activate_at_timestamp) {
+				(Some(block), _) => block.0 <= number.into(),
+				(None, Some(ts)) => ts.0 <= timestamp.into(),
+				(None, None) => true,
+			}
+		});
+
+		BuiltinInfo {
+			name: spec.name.clone(),
+			active,
+			pricing: reached.into_iter().last().map(|p| p.price),
+			schedule,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use ethjson::spec::Builtin;
+	use super::BuiltinInfo;
+
+	#[test]
+	fn builtin_info_serialization() {
+		let spec: Builtin = serde_json::from_str(r#"{
+			"name": "modexp",
+			"pricing": [
+				{ "activate_at": 0, "price": { "modexp": { "divisor": 20 } } },
+				{ "activate_at_timestamp": 1000, "price": { "modexp2565": { "divisor": 3 } } },
+				{ "activate_at": 100, "price": { "modexp7883": { "min_price": 500 } } }
+			]
+		}"#).unwrap();
+
+		let info = BuiltinInfo::new(&spec, true, 50, 1000);
+		assert_eq!(
+			serde_json::to_string(&info).unwrap(),
+			r#"{"name":"modexp","active":true,"pricing":{"modexp2565":{"divisor":3}},"schedule":[{"activate_at":"0x64","price":{"modexp7883":{"min_price":500}}}]}"#
+		);
+
+		let info = BuiltinInfo::new(&spec, true, 50, 999);
+		assert_eq!(
+			serde_json::to_string(&info).unwrap(),
+			r#"{"name":"modexp","active":true,"pricing":{"modexp":{"divisor":20}},"schedule":[{"activate_at_timestamp":"0x3e8","price":{"modexp2565":{"divisor":3}}},{"activate_at":"0x64","price":{"modexp7883":{"min_price":500}}}]}"#
+		);
+	}
+}
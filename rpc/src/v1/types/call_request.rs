@@ -14,8 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
-use ethereum_types::{H160, U256};
+use std::collections::BTreeMap;
+
+use ethereum_types::{H160, H256, U256};
+use jsonrpc_core::Error as RpcError;
+use types::state_override::{AccountOverride as EthAccountOverride, StateOverride, StorageOverride};
 use v1::helpers::CallRequest as Request;
+use v1::helpers::errors::invalid_params;
 use v1::types::Bytes;
 
 /// Call request
@@ -53,13 +58,54 @@ impl Into<Request> for CallRequest {
 	}
 }
 
+/// Account state override applied for the duration of a call
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+	/// Balance
+	pub balance: Option<U256>,
+	/// Nonce
+	pub nonce: Option<U256>,
+	/// Code
+	pub code: Option<Bytes>,
+	/// Storage replacing the whole storage of the account
+	pub state: Option<BTreeMap<H256, H256>>,
+	/// Storage slots to override, keeping the others
+	pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+/// Account state overrides by address
+pub type StateOverrides = BTreeMap<H160, AccountOverride>;
+
+/// Converts the overrides, rejecting accounts with both `state` and `stateDiff`.
+pub fn into_state_override(overrides: StateOverrides) -> Result<StateOverride, RpcError> {
+	overrides.into_iter().map(|(address, account)| {
+		let storage = match (account.state, account.state_diff) {
+			(Some(_), Some(_)) => return Err(invalid_params("stateOverrides", format!("both state and stateDiff given for account {:?}", address))),
+			(Some(state), None) => Some(StorageOverride::Full(state)),
+			(None, Some(diff)) => Some(StorageOverride::Diff(diff)),
+			(None, None) => None,
+		};
+
+		Ok((address, EthAccountOverride {
+			balance: account.balance,
+			nonce: account.nonce,
+			code: account.code.map(Into::into),
+			storage,
+		}))
+	}).collect()
+}
+
 #[cfg(test)]
 mod tests {
 	use std::str::FromStr;
 	use rustc_hex::FromHex;
 	use serde_json;
-	use ethereum_types::{U256, H160};
-	use super::CallRequest;
+	use std::collections::BTreeMap;
+	use ethereum_types::{U256, H160, H256};
+	use types::state_override::{AccountOverride as EthAccountOverride, StorageOverride};
+	use super::{CallRequest, StateOverrides, into_state_override};
 
 	#[test]
 	fn call_request_deserialize() {
@@ -123,4 +169,41 @@ mod tests {
 			nonce: None,
 		});
 	}
+
+	#[test]
+	fn state_overrides_deserialize() {
+		let s = r#"{
+			"0x0000000000000000000000000000000000000001": {
+				"balance": "0x10",
+				"nonce": "0x2",
+				"code": "0x6000",
+				"stateDiff": {
+					"0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002"
+				}
+			},
+			"0x0000000000000000000000000000000000000002": {
+				"state": {}
+			}
+		}"#;
+		let deserialized: StateOverrides = serde_json::from_str(s).unwrap();
+		let overrides = into_state_override(deserialized).unwrap();
+
+		let mut diff = BTreeMap::new();
+		diff.insert(H256::from_low_u64_be(1), H256::from_low_u64_be(2));
+		assert_eq!(overrides[&H160::from_low_u64_be(1)], EthAccountOverride {
+			balance: Some(U256::from(0x10)),
+			nonce: Some(U256::from(2)),
+			code: Some(vec![0x60, 0x00]),
+			storage: Some(StorageOverride::Diff(diff)),
+		});
+		assert_eq!(overrides[&H160::from_low_u64_be(2)].storage, Some(StorageOverride::Full(BTreeMap::new())));
+	}
+
+	#[test]
+	fn state_overrides_reject_state_and_state_diff() {
+		let s = r#"{"0x0000000000000000000000000000000000000001": {"state": {}, "stateDiff": {}}}"#;
+		let deserialized: StateOverrides = serde_json::from_str(s).unwrap();
+
+		assert!(into_state_override(deserialized).is_err());
+	}
 }
@@ -0,0 +1,102 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Database statistics.
+
+use ethereum_types::U64;
+use types::database_stats::{self, CompactionStatus};
+
+/// Size statistics of a database column.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnStats {
+	/// Column index.
+	pub column: u32,
+	/// Estimated size of the live data, in bytes.
+	pub size: U64,
+	/// Estimated number of keys.
+	pub estimated_keys: U64,
+	/// Estimated number of bytes compaction needs to rewrite.
+	pub pending_compaction_bytes: U64,
+}
+
+/// Database statistics and progress of the manual compaction.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStats {
+	/// Per-column statistics.
+	pub columns: Vec<ColumnStats>,
+	/// Whether a manual compaction is running.
+	pub compacting: bool,
+	/// Column being compacted, if any.
+	pub compacting_column: Option<u32>,
+	/// Number of columns compacted so far.
+	pub compacted_columns: u32,
+	/// Number of columns the running compaction covers.
+	pub total_columns: u32,
+	/// Error of the last compaction, if it failed.
+	pub compaction_error: Option<String>,
+}
+
+impl From<database_stats::DatabaseStats> for DatabaseStats {
+	fn from(stats: database_stats::DatabaseStats) -> Self {
+		let columns = stats.columns.into_iter()
+			.map(|c| ColumnStats {
+				column: c.column,
+				size: c.size.into(),
+				estimated_keys: c.estimated_keys.into(),
+				pending_compaction_bytes: c.pending_compaction_bytes.into(),
+			})
+			.collect();
+
+		let (compacting_column, compacted_columns, total_columns, compaction_error) = match stats.compaction {
+			CompactionStatus::Inactive => (None, 0, 0, None),
+			CompactionStatus::Ongoing { column, done, total } => (Some(column), done, total, None),
+			CompactionStatus::Failed(e) => (None, 0, 0, Some(e)),
+		};
+
+		DatabaseStats {
+			columns,
+			compacting: compacting_column.is_some(),
+			compacting_column,
+			compacted_columns,
+			total_columns,
+			compaction_error,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use types::database_stats::{self, CompactionStatus};
+	use super::DatabaseStats;
+
+	#[test]
+	fn test_serialize_database_stats() {
+		let stats = database_stats::DatabaseStats {
+			columns: vec![database_stats::ColumnStats {
+				column: 1,
+				size: 1024,
+				estimated_keys: 16,
+				pending_compaction_bytes: 512,
+			}],
+			compaction: CompactionStatus::Ongoing { column: 1, done: 0, total: 1 },
+		};
+		let serialized = serde_json::to_string(&DatabaseStats::from(stats)).unwrap();
+		assert_eq!(serialized, r#"{"columns":[{"column":1,"size":"0x400","estimatedKeys":"0x10","pendingCompactionBytes":"0x200"}],"compacting":true,"compactingColumn":1,"compactedColumns":0,"totalColumns":1,"compactionError":null}"#);
+	}
+}
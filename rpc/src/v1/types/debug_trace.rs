@@ -0,0 +1,365 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Geth compatible transaction traces returned by `debug_traceTransaction`.
+
+use std::collections::BTreeMap;
+
+use ethereum_types::{H160, U256};
+use machine::executed::Executed;
+use rustc_hex::ToHex;
+use trace as et;
+use trace::trace::{Action, Res};
+use types::engines::machine::Executed as RawExecuted;
+use vm::CallType;
+
+use v1::types::Bytes;
+
+/// Name of the tracer building the call tree.
+pub const CALL_TRACER: &str = "callTracer";
+
+/// Options of `debug_traceTransaction`.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugTraceOptions {
+	/// Tracer to use, the opcode level struct logger if absent.
+	pub tracer: Option<String>,
+	/// Don't capture the memory of every step.
+	#[serde(default)]
+	pub disable_memory: bool,
+	/// Don't capture the stack of every step.
+	#[serde(default)]
+	pub disable_stack: bool,
+	/// Don't capture the storage of every step.
+	#[serde(default)]
+	pub disable_storage: bool,
+}
+
+impl DebugTraceOptions {
+	/// Configuration of the struct logger.
+	pub fn struct_logger_config(&self) -> et::StructLoggerConfig {
+		et::StructLoggerConfig {
+			disable_memory: self.disable_memory,
+			disable_stack: self.disable_stack,
+			disable_storage: self.disable_storage,
+		}
+	}
+}
+
+/// Trace of a transaction, in the format of the requested tracer.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum DebugTrace {
+	/// Opcode level trace.
+	StructLogs(StructLogsTrace),
+	/// Call tree.
+	Call(CallFrame),
+}
+
+/// Opcode level trace of a transaction.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogsTrace {
+	/// Gas used by the transaction.
+	pub gas: u64,
+	/// Whether the transaction failed.
+	pub failed: bool,
+	/// Output of the transaction, hex encoded without prefix.
+	pub return_value: String,
+	/// State of the VM before every executed instruction.
+	pub struct_logs: Vec<StructLog>,
+}
+
+impl From<RawExecuted<et::FlatTrace, Vec<et::StructLog>>> for StructLogsTrace {
+	fn from(executed: RawExecuted<et::FlatTrace, Vec<et::StructLog>>) -> Self {
+		StructLogsTrace {
+			gas: executed.gas_used.low_u64(),
+			failed: executed.exception.is_some(),
+			return_value: executed.output.to_hex(),
+			struct_logs: executed.vm_trace.unwrap_or_default().into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// State of the VM before executing a single instruction.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+	/// Program counter.
+	pub pc: u64,
+	/// Mnemonic of the instruction.
+	pub op: String,
+	/// Gas left before executing the instruction.
+	pub gas: u64,
+	/// Gas cost of the instruction.
+	pub gas_cost: u64,
+	/// Depth of the call frame, starting at 1.
+	pub depth: u64,
+	/// Stack, bottom first.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<U256>>,
+	/// Memory in 32 byte words, hex encoded without prefix.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub memory: Option<Vec<String>>,
+	/// Storage slots accessed by the call frame, hex encoded without prefix.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<String, String>>,
+}
+
+impl From<et::StructLog> for StructLog {
+	fn from(log: et::StructLog) -> Self {
+		StructLog {
+			pc: log.pc as u64,
+			op: log.op_name()
+				.map(str::to_owned)
+				.unwrap_or_else(|| format!("opcode {:#x} not defined", log.instruction)),
+			gas: log.gas.low_u64(),
+			gas_cost: log.gas_cost.low_u64(),
+			depth: log.depth as u64,
+			stack: log.stack,
+			memory: log.memory.map(|memory| memory.chunks(32).map(|word| word.to_hex()).collect()),
+			storage: log.storage.map(|storage| storage.into_iter()
+				.map(|(key, value)| (format!("{:x}", key), format!("{:x}", value)))
+				.collect()
+			),
+		}
+	}
+}
+
+/// A call, create or self-destruct with the calls it made.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+	/// Kind of the frame, e.g. `CALL` or `CREATE`.
+	#[serde(rename = "type")]
+	pub call_type: String,
+	/// The caller.
+	pub from: H160,
+	/// The callee, absent for a failed create.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub to: Option<H160>,
+	/// Value transferred.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub value: Option<U256>,
+	/// Gas available to the frame.
+	pub gas: U256,
+	/// Gas used by the frame.
+	pub gas_used: U256,
+	/// Call data or init code.
+	pub input: Bytes,
+	/// Returned data or deployed code.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub output: Option<Bytes>,
+	/// Why the frame failed.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	/// Frames of the calls made, in order.
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub calls: Vec<CallFrame>,
+}
+
+impl CallFrame {
+	/// Builds the call tree of a transaction replayed with transaction tracing. The top-level
+	/// frame reports the gas of the whole transaction, `None` if nothing was traced.
+	pub fn from_executed(executed: Executed) -> Option<CallFrame> {
+		let mut traces = executed.trace.into_iter();
+		let mut frame = Self::from_traces(traces.next()?, &mut traces);
+		frame.gas = executed.gas;
+		frame.gas_used = executed.gas_used;
+		Some(frame)
+	}
+
+	/// Builds the frame of `trace` and its subtraces, which follow it depth first in `traces`.
+	fn from_traces<I: Iterator<Item = et::FlatTrace>>(trace: et::FlatTrace, traces: &mut I) -> CallFrame {
+		let subtraces = trace.subtraces;
+		let mut frame = CallFrame::from(trace);
+		for _ in 0..subtraces {
+			match traces.next() {
+				Some(sub) => frame.calls.push(Self::from_traces(sub, traces)),
+				None => break,
+			}
+		}
+		frame
+	}
+}
+
+impl From<et::FlatTrace> for CallFrame {
+	fn from(trace: et::FlatTrace) -> Self {
+		let mut frame = match trace.action {
+			Action::Call(call) => CallFrame {
+				call_type: match call.call_type {
+					CallType::None | CallType::Call => "CALL",
+					CallType::CallCode => "CALLCODE",
+					CallType::DelegateCall => "DELEGATECALL",
+					CallType::StaticCall => "STATICCALL",
+				}.into(),
+				from: call.from,
+				to: Some(call.to),
+				value: match call.call_type {
+					CallType::DelegateCall | CallType::StaticCall => None,
+					_ => Some(call.value),
+				},
+				gas: call.gas,
+				gas_used: U256::zero(),
+				input: call.input.into(),
+				output: None,
+				error: None,
+				calls: vec![],
+			},
+			Action::Create(create) => CallFrame {
+				call_type: "CREATE".into(),
+				from: create.from,
+				to: None,
+				value: Some(create.value),
+				gas: create.gas,
+				gas_used: U256::zero(),
+				input: create.init.into(),
+				output: None,
+				error: None,
+				calls: vec![],
+			},
+			Action::Suicide(suicide) => CallFrame {
+				call_type: "SELFDESTRUCT".into(),
+				from: suicide.address,
+				to: Some(suicide.refund_address),
+				value: Some(suicide.balance),
+				gas: U256::zero(),
+				gas_used: U256::zero(),
+				input: Bytes::default(),
+				output: None,
+				error: None,
+				calls: vec![],
+			},
+			Action::Reward(reward) => CallFrame {
+				call_type: "REWARD".into(),
+				from: reward.author,
+				to: None,
+				value: Some(reward.value),
+				gas: U256::zero(),
+				gas_used: U256::zero(),
+				input: Bytes::default(),
+				output: None,
+				error: None,
+				calls: vec![],
+			},
+		};
+
+		match trace.result {
+			Res::Call(result) => {
+				frame.gas_used = result.gas_used;
+				frame.output = Some(result.output.into());
+			},
+			Res::Create(result) => {
+				frame.gas_used = result.gas_used;
+				frame.to = Some(result.address);
+				frame.output = Some(result.code.into());
+			},
+			Res::FailedCall(error) | Res::FailedCreate(error) => {
+				// a failed frame consumes all of its gas
+				frame.gas_used = frame.gas;
+				frame.error = Some(error.to_string());
+			},
+			Res::None => {},
+		}
+		frame
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use std::collections::BTreeMap;
+	use ethereum_types::{H160, H256, U256, BigEndianHash};
+	use trace as et;
+	use trace::trace::{Action, Call, CallResult, Res};
+	use vm::CallType;
+	use super::{CallFrame, DebugTraceOptions, StructLog};
+
+	#[test]
+	fn debug_trace_options_deserialization() {
+		let s = r#"{"tracer":"callTracer","disableStorage":true,"timeout":"10s"}"#;
+		let deserialized: DebugTraceOptions = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized, DebugTraceOptions {
+			tracer: Some("callTracer".into()),
+			disable_memory: false,
+			disable_stack: false,
+			disable_storage: true,
+		});
+	}
+
+	#[test]
+	fn struct_log_serialization() {
+		let mut storage = BTreeMap::new();
+		storage.insert(BigEndianHash::from_uint(&U256::from(1)), H256::from_low_u64_be(0x2a));
+		let log = et::StructLog {
+			pc: 4,
+			instruction: 0x55,
+			gas: 94.into(),
+			gas_cost: 20_000.into(),
+			depth: 1,
+			stack: Some(vec![0x2a.into(), 0x1.into()]),
+			memory: Some(vec![0xff; 33]),
+			storage: Some(storage),
+		};
+
+		let serialized = serde_json::to_string(&StructLog::from(log)).unwrap();
+		assert_eq!(serialized, r#"{"pc":4,"op":"SSTORE","gas":94,"gasCost":20000,"depth":1,"stack":["0x2a","0x1"],"memory":["ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff","ff"],"storage":{"0000000000000000000000000000000000000000000000000000000000000001":"000000000000000000000000000000000000000000000000000000000000002a"}}"#);
+	}
+
+	#[test]
+	fn struct_log_serialization_without_state() {
+		let log = et::StructLog {
+			pc: 0,
+			instruction: 0x0c,
+			gas: 100.into(),
+			gas_cost: 0.into(),
+			depth: 2,
+			stack: None,
+			memory: None,
+			storage: None,
+		};
+
+		let serialized = serde_json::to_string(&StructLog::from(log)).unwrap();
+		assert_eq!(serialized, r#"{"pc":0,"op":"opcode 0xc not defined","gas":100,"gasCost":0,"depth":2}"#);
+	}
+
+	#[test]
+	fn call_frame_from_traces() {
+		let call = |trace_address: Vec<usize>, subtraces: usize| et::FlatTrace {
+			action: Action::Call(Call {
+				from: H160::from_low_u64_be(1),
+				to: H160::from_low_u64_be(2),
+				value: 0.into(),
+				gas: 0x100.into(),
+				input: vec![],
+				call_type: CallType::StaticCall,
+			}),
+			result: Res::Call(CallResult { gas_used: 0x10.into(), output: vec![1] }),
+			subtraces,
+			trace_address,
+		};
+		let mut traces = vec![call(vec![], 2), call(vec![0], 1), call(vec![0, 0], 0), call(vec![1], 0)].into_iter();
+		let root = traces.next().unwrap();
+
+		let frame = CallFrame::from_traces(root, &mut traces);
+		assert_eq!(frame.calls.len(), 2);
+		assert_eq!(frame.calls[0].calls.len(), 1);
+		assert!(frame.calls[1].calls.is_empty());
+
+		let serialized = serde_json::to_string(&frame.calls[1]).unwrap();
+		assert_eq!(serialized, r#"{"type":"STATICCALL","from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","gas":"0x100","gasUsed":"0x10","input":"0x","output":"0x01"}"#);
+	}
+}
@@ -0,0 +1,52 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Fee history of a range of blocks.
+
+use ethereum_types::U256;
+
+/// Fee market data of consecutive blocks, as returned by `eth_feeHistory`.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistory {
+	/// Number of the oldest block in the range.
+	pub oldest_block: U256,
+	/// Base fee of each block in the range followed by the base fee of the next block,
+	/// zero for blocks before EIP-1559.
+	pub base_fee_per_gas: Vec<U256>,
+	/// Ratio of gas used to gas limit of each block.
+	pub gas_used_ratio: Vec<f64>,
+	/// Priority fees paid at the requested percentiles of gas used, for each block.
+	pub reward: Vec<Vec<U256>>,
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use super::FeeHistory;
+
+	#[test]
+	fn fee_history_serialization() {
+		let history = FeeHistory {
+			oldest_block: 0x10.into(),
+			base_fee_per_gas: vec![0x7.into(), 0x8.into()],
+			gas_used_ratio: vec![0.5],
+			reward: vec![vec![0x1.into(), 0x2.into()]],
+		};
+		let serialized = serde_json::to_string(&history).unwrap();
+		assert_eq!(serialized, r#"{"oldestBlock":"0x10","baseFeePerGas":["0x7","0x8"],"gasUsedRatio":[0.5],"reward":[["0x1","0x2"]]}"#);
+	}
+}
@@ -19,14 +19,19 @@
 #[cfg(test)]
 mod eth_types;
 
+mod access_list;
 mod account_info;
 mod block;
 mod block_number;
+mod builtin;
 mod bytes;
 mod call_request;
 mod confirmations;
 mod consensus_status;
+mod database_stats;
+mod debug_trace;
 mod derivation;
+mod fee_history;
 mod filter;
 mod histogram;
 mod index;
@@ -38,29 +43,36 @@ mod provenance;
 mod receipt;
 mod rpc_settings;
 mod secretstore;
+mod storage_keys;
 mod sync;
 mod trace;
 mod trace_filter;
 mod transaction;
 mod transaction_request;
 mod transaction_condition;
+mod txpool;
 mod work;
 mod eip191;
 
 pub mod pubsub;
 
 pub use self::eip191::{EIP191Version, PresignedTransaction};
+pub use self::access_list::{AccessList, AccessListItem};
 pub use self::account_info::{AccountInfo, ExtAccountInfo, EthAccount, StorageProof, RecoveredAccount};
 pub use self::bytes::Bytes;
 pub use self::block::{RichBlock, Block, BlockTransactions, Header, RichHeader, Rich};
 pub use self::block_number::{BlockNumber, LightBlockNumber, block_number_to_id};
-pub use self::call_request::CallRequest;
+pub use self::builtin::BuiltinInfo;
+pub use self::call_request::{CallRequest, AccountOverride, StateOverrides, into_state_override};
 pub use self::confirmations::{
 	ConfirmationPayload, ConfirmationRequest, ConfirmationResponse, ConfirmationResponseWithToken,
 	TransactionModification, EIP191SignRequest, EthSignRequest, DecryptRequest, Either
 };
 pub use self::consensus_status::*;
+pub use self::database_stats::{ColumnStats, DatabaseStats};
+pub use self::debug_trace::{CallFrame, DebugTrace, DebugTraceOptions, StructLog, StructLogsTrace, CALL_TRACER};
 pub use self::derivation::{DeriveHash, DeriveHierarchical, Derive};
+pub use self::fee_history::FeeHistory;
 pub use self::filter::{Filter, FilterChanges};
 pub use self::histogram::Histogram;
 pub use self::index::Index;
@@ -72,15 +84,17 @@ pub use self::provenance::Origin;
 pub use self::receipt::Receipt;
 pub use self::rpc_settings::RpcSettings;
 pub use self::secretstore::EncryptedDocumentKey;
+pub use self::storage_keys::StorageKeysPage;
 pub use self::sync::{
 	SyncStatus, SyncInfo, Peers, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
-	TransactionStats, ChainStatus, EthProtocolInfo, PipProtocolInfo,
+	TransactionStats, ChainStatus, SnapshotStatus, EthProtocolInfo, PipProtocolInfo,
 };
 pub use self::trace::{LocalizedTrace, TraceResults, TraceResultsWithTransactionHash};
 pub use self::trace_filter::TraceFilter;
-pub use self::transaction::{Transaction, RichRawTransaction, LocalTransactionStatus};
+pub use self::transaction::{Transaction, RichRawTransaction, LocalTransactionStatus, PendingTransactionStatus};
 pub use self::transaction_request::TransactionRequest;
 pub use self::transaction_condition::TransactionCondition;
+pub use self::txpool::{TxPoolContent, TxPoolStatus};
 pub use self::work::Work;
 
 // TODO [ToDr] Refactor to a proper type Vec of enums?
@@ -20,10 +20,10 @@ use ethereum_types::H256;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::Error;
 use serde_json::{Value, from_value};
-use v1::types::{RichHeader, Filter, Log};
+use v1::types::{RichHeader, Filter, Log, SyncInfo, Transaction};
 
 /// Subscription result.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Result {
 	/// New block header.
 	Header(Box<RichHeader>),
@@ -31,6 +31,8 @@ pub enum Result {
 	Log(Box<Log>),
 	/// Transaction hash
 	TransactionHash(H256),
+	/// Transaction
+	Transaction(Box<Transaction>),
 	/// SyncStatus
 	SyncState(PubSubSyncStatus)
 }
@@ -41,6 +43,25 @@ pub enum Result {
 pub struct PubSubSyncStatus {
 	/// is_major_syncing?
 	pub syncing: bool,
+	/// Progress of the sync, set while syncing.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub status: Option<SyncInfo>,
+}
+
+/// Delivery statistics of a single subscription.
+#[derive(Debug, Serialize, Eq, PartialEq, Clone)]
+#[serde(rename_all="camelCase")]
+pub struct SubscriptionStats {
+	/// Subscription id
+	pub id: String,
+	/// Subscription kind
+	pub kind: String,
+	/// Notifications handed over to the transport
+	pub delivered: usize,
+	/// Notifications dropped because the subscriber couldn't keep up
+	pub dropped: usize,
+	/// Notifications waiting to be delivered
+	pub queued: usize,
 }
 
 impl Serialize for Result {
@@ -51,6 +72,7 @@ impl Serialize for Result {
 			Result::Header(ref header) => header.serialize(serializer),
 			Result::Log(ref log) => log.serialize(serializer),
 			Result::TransactionHash(ref hash) => hash.serialize(serializer),
+			Result::Transaction(ref transaction) => transaction.serialize(serializer),
 			Result::SyncState(ref sync) => sync.serialize(serializer),
 		}
 	}
@@ -78,6 +100,10 @@ pub enum Params {
 	None,
 	/// Log parameters.
 	Logs(Filter),
+	/// Log parameters, replaying the logs notified after the given block (`replayFrom`).
+	ReplayLogs(Filter, H256),
+	/// Whether to send full transaction objects instead of hashes.
+	FullTransactions(bool),
 }
 
 impl Default for Params {
@@ -89,13 +115,27 @@ impl Default for Params {
 impl<'a> Deserialize<'a> for Params {
 	fn deserialize<D>(deserializer: D) -> ::std::result::Result<Params, D::Error>
 	where D: Deserializer<'a> {
-		let v: Value = Deserialize::deserialize(deserializer)?;
+		let mut v: Value = Deserialize::deserialize(deserializer)?;
 
 		if v.is_null() {
 			return Ok(Params::None);
 		}
 
-		from_value(v.clone()).map(Params::Logs)
+		if let Value::Bool(full) = v {
+			return Ok(Params::FullTransactions(full));
+		}
+
+		let replay_from = match v.as_object_mut().and_then(|params| params.remove("replayFrom")) {
+			Some(hash) => Some(from_value::<H256>(hash)
+				.map_err(|e| D::Error::custom(format!("Invalid replayFrom block hash: {}", e)))?),
+			None => None,
+		};
+
+		from_value(v)
+			.map(|filter| match replay_from {
+				Some(hash) => Params::ReplayLogs(filter, hash),
+				None => Params::Logs(filter),
+			})
 			.map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {}", e)))
 	}
 }
@@ -103,8 +143,9 @@ impl<'a> Deserialize<'a> for Params {
 #[cfg(test)]
 mod tests {
 	use serde_json;
-	use super::{Result, Kind, Params};
-	use v1::types::{RichHeader, Header, Filter};
+	use ethereum_types::H256;
+	use super::{Result, Kind, Params, PubSubSyncStatus};
+	use v1::types::{RichHeader, Header, Filter, SyncInfo};
 	use v1::types::filter::VariadicValue;
 
 	#[test]
@@ -115,6 +156,33 @@ mod tests {
 		assert_eq!(serde_json::from_str::<Kind>(r#""syncing""#).unwrap(), Kind::Syncing);
 	}
 
+	#[test]
+	fn should_deserialize_full_transactions() {
+		assert_eq!(serde_json::from_str::<Params>(r#"true"#).unwrap(), Params::FullTransactions(true));
+		assert_eq!(serde_json::from_str::<Params>(r#"false"#).unwrap(), Params::FullTransactions(false));
+	}
+
+	#[test]
+	fn should_serialize_sync_status() {
+		let done = Result::SyncState(PubSubSyncStatus { syncing: false, status: None });
+		assert_eq!(serde_json::to_string(&done).unwrap(), r#"{"syncing":false}"#);
+
+		let syncing = Result::SyncState(PubSubSyncStatus {
+			syncing: true,
+			status: Some(SyncInfo {
+				starting_block: 0x10.into(),
+				current_block: 0x20.into(),
+				highest_block: 0x30.into(),
+				warp_chunks_amount: None,
+				warp_chunks_processed: None,
+			}),
+		});
+		assert_eq!(
+			serde_json::to_string(&syncing).unwrap(),
+			r#"{"syncing":true,"status":{"startingBlock":"0x10","currentBlock":"0x20","highestBlock":"0x30","warpChunksAmount":null,"warpChunksProcessed":null}}"#
+		);
+	}
+
 	#[test]
 	fn should_deserialize_logs() {
 		let none = serde_json::from_str::<Params>(r#"null"#).unwrap();
@@ -153,6 +221,23 @@ mod tests {
 		}));
 	}
 
+	#[test]
+	fn should_deserialize_replayed_logs() {
+		let replay = serde_json::from_str::<Params>(
+			r#"{"limit":10,"replayFrom":"0x0000000000000000000000000000000000000000000000000000000000000005"}"#
+		).unwrap();
+		assert_eq!(replay, Params::ReplayLogs(Filter {
+			from_block: None,
+			to_block: None,
+			block_hash: None,
+			address: None,
+			topics: None,
+			limit: Some(10),
+		}, H256::from_low_u64_be(5)));
+
+		assert!(serde_json::from_str::<Params>(r#"{"replayFrom":"0x05"}"#).is_err());
+	}
+
 	#[test]
 	fn should_serialize_header() {
 		let header = Result::Header(Box::new(RichHeader {
@@ -175,6 +260,7 @@ mod tests {
 				difficulty: Default::default(),
 				seal_fields: vec![Default::default(), Default::default()],
 				size: Some(69.into()),
+				base_fee_per_gas: None,
 			},
 		}));
 		let expected = r#"{"author":"0x0000000000000000000000000000000000000000","difficulty":"0x0","extraData":"0x","gasLimit":"0x0","gasUsed":"0x0","hash":"0x0000000000000000000000000000000000000000000000000000000000000000","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","miner":"0x0000000000000000000000000000000000000000","number":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","receiptsRoot":"0x0000000000000000000000000000000000000000000000000000000000000000","sealFields":["0x","0x"],"sha3Uncles":"0x0000000000000000000000000000000000000000000000000000000000000000","size":"0x45","stateRoot":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":"0x0","transactionsRoot":"0x0000000000000000000000000000000000000000000000000000000000000000"}"#;
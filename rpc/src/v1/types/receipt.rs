@@ -38,6 +38,9 @@ pub struct Receipt {
 	pub cumulative_gas_used: U256,
 	/// Gas used
 	pub gas_used: Option<U256>,
+	/// Price per unit of gas paid by the sender
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub effective_gas_price: Option<U256>,
 	/// Contract address
 	pub contract_address: Option<H160>,
 	/// Logs
@@ -50,6 +53,9 @@ pub struct Receipt {
 	/// Status code
 	#[serde(rename = "status")]
 	pub status_code: Option<U64>,
+	/// Reason the transaction reverted with, if it failed with an `Error(string)`
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub revert_reason: Option<String>,
 }
 
 impl Receipt {
@@ -79,11 +85,13 @@ impl From<LocalizedReceipt> for Receipt {
 			block_number: Some(r.block_number.into()),
 			cumulative_gas_used: r.cumulative_gas_used,
 			gas_used: Some(r.gas_used),
+			effective_gas_price: Some(r.effective_gas_price),
 			contract_address: r.contract_address.map(Into::into),
 			logs: r.logs.into_iter().map(Into::into).collect(),
 			status_code: Self::outcome_to_status_code(&r.outcome),
 			state_root: Self::outcome_to_state_root(r.outcome),
 			logs_bloom: r.log_bloom,
+			revert_reason: None,
 		}
 	}
 }
@@ -99,11 +107,13 @@ impl From<RichReceipt> for Receipt {
 			block_number: None,
 			cumulative_gas_used: r.cumulative_gas_used,
 			gas_used: Some(r.gas_used),
+			effective_gas_price: None,
 			contract_address: r.contract_address.map(Into::into),
 			logs: r.logs.into_iter().map(Into::into).collect(),
 			status_code: Self::outcome_to_status_code(&r.outcome),
 			state_root: Self::outcome_to_state_root(r.outcome),
 			logs_bloom: r.log_bloom,
+			revert_reason: None,
 		}
 	}
 }
@@ -119,11 +129,13 @@ impl From<EthReceipt> for Receipt {
 			block_number: None,
 			cumulative_gas_used: r.gas_used,
 			gas_used: None,
+			effective_gas_price: None,
 			contract_address: None,
 			logs: r.logs.into_iter().map(Into::into).collect(),
 			status_code: Self::outcome_to_status_code(&r.outcome),
 			state_root: Self::outcome_to_state_root(r.outcome),
 			logs_bloom: r.log_bloom,
+			revert_reason: None,
 		}
 	}
 }
@@ -147,6 +159,7 @@ mod tests {
 			block_number: Some(0x4510c.into()),
 			cumulative_gas_used: 0x20.into(),
 			gas_used: Some(0x10.into()),
+			effective_gas_price: None,
 			contract_address: None,
 			logs: vec![Log {
 				address: "33990122638b9132ca29c723bdf037f1a891a70c".parse().unwrap(),
@@ -167,6 +180,7 @@ mod tests {
 			logs_bloom: Bloom::from_low_u64_be(15),
 			state_root: Some(H256::from_low_u64_be(10)),
 			status_code: Some(1u64.into()),
+			revert_reason: None,
 		};
 
 		let serialized = serde_json::to_string(&receipt).unwrap();
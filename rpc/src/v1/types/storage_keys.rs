@@ -0,0 +1,51 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Paged listing of storage keys.
+
+use ethereum_types::H256;
+use v1::types::Bytes;
+
+/// A page of storage keys, as returned by `parity_listStorageKeysPaged`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageKeysPage {
+	/// Storage keys, in trie order.
+	pub keys: Vec<H256>,
+	/// Opaque token to pass in to fetch the following page, `None` if this is the last page.
+	pub next: Option<Bytes>,
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use ethereum_types::H256;
+	use super::StorageKeysPage;
+
+	#[test]
+	fn storage_keys_page_serialization() {
+		let page = StorageKeysPage {
+			keys: vec![H256::from_low_u64_be(1)],
+			next: Some(vec![0xab, 0xcd].into()),
+		};
+		let serialized = serde_json::to_string(&page).unwrap();
+		assert_eq!(serialized, r#"{"keys":["0x0000000000000000000000000000000000000000000000000000000000000001"],"next":"0xabcd"}"#);
+
+		let last = StorageKeysPage::default();
+		let serialized = serde_json::to_string(&last).unwrap();
+		assert_eq!(serialized, r#"{"keys":[],"next":null}"#);
+	}
+}
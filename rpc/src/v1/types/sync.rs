@@ -17,12 +17,13 @@
 use network::client_version::ClientVersion;
 use std::collections::BTreeMap;
 
-use ethereum_types::{U256, H512};
+use ethereum_types::{U64, U256, H512};
 use sync::{self, PeerInfo as SyncPeerInfo, TransactionStats as SyncTransactionStats};
+use types::snapshot::CreationStatus;
 use serde::{Serialize, Serializer};
 
 /// Sync info
-#[derive(Default, Debug, Serialize, PartialEq)]
+#[derive(Default, Debug, Serialize, PartialEq, Eq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SyncInfo {
 	/// Starting block
@@ -193,6 +194,37 @@ pub struct ChainStatus {
 	pub block_gap: Option<(U256, U256)>,
 }
 
+/// Status of the snapshot being taken.
+#[derive(Default, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotStatus {
+	/// Whether a snapshot is being taken.
+	pub in_progress: bool,
+	/// Number of accounts snapshotted so far.
+	pub accounts: U64,
+	/// Estimated total number of accounts, once at least one state part is completed.
+	pub estimated_accounts: Option<U64>,
+	/// Number of blocks snapshotted so far.
+	pub blocks: U64,
+	/// Size in bytes of the chunks written so far.
+	pub size: U64,
+}
+
+impl From<CreationStatus> for SnapshotStatus {
+	fn from(status: CreationStatus) -> Self {
+		match status {
+			CreationStatus::Inactive => SnapshotStatus::default(),
+			CreationStatus::Ongoing { accounts, estimated_accounts, blocks, size } => SnapshotStatus {
+				in_progress: true,
+				accounts: accounts.into(),
+				estimated_accounts: estimated_accounts.map(Into::into),
+				blocks: blocks.into(),
+				size: size.into(),
+			},
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use serde_json;
@@ -22,8 +22,8 @@ use machine::executive::{contract_address};
 use vm::CreateContractAddress;
 use ethereum_types::{H160, H256, H512, U64, U256};
 use miner;
-use types::transaction::{LocalizedTransaction, Action, PendingTransaction, SignedTransaction};
-use v1::types::{Bytes, TransactionCondition};
+use types::transaction::{LocalizedTransaction, Action, PendingTransaction, SignedTransaction, TypedTxId, UnverifiedTransaction};
+use v1::types::{AccessList, Bytes, TransactionCondition};
 
 /// Transaction
 #[derive(Debug, Default, Clone, PartialEq, Serialize)]
@@ -69,6 +69,48 @@ pub struct Transaction {
 	pub s: U256,
 	/// Transaction activates at specified block.
 	pub condition: Option<TransactionCondition>,
+	/// Transaction type, omitted for legacy transactions.
+	#[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+	pub transaction_type: Option<U64>,
+	/// Access list of an EIP-2930 transaction.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub access_list: Option<AccessList>,
+	/// Maximum fee per gas of an EIP-1559 transaction, equal to its gas price.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub max_fee_per_gas: Option<U256>,
+	/// Maximum priority fee per gas of an EIP-1559 transaction.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub max_priority_fee_per_gas: Option<U256>,
+	/// Status of a pending transaction the block author refuses to include, omitted otherwise.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub status: Option<PendingTransactionStatus>,
+}
+
+/// Status of a pending transaction which is kept in the queue, but not included in blocks.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PendingTransactionStatus {
+	/// Transaction is not allowed by the transaction permission contract.
+	NotAllowed,
+}
+
+/// Type and access list of a typed transaction, `None`s for legacy ones.
+fn typed_fields(t: &UnverifiedTransaction) -> (Option<U64>, Option<AccessList>) {
+	match t.tx_type() {
+		TypedTxId::Legacy => (None, None),
+		tx_type => (
+			Some(tx_type.to_u8().into()),
+			t.access_list().map(|list| list.iter().cloned().map(Into::into).collect()),
+		),
+	}
+}
+
+/// Maximum fee and maximum priority fee per gas of an EIP-1559 transaction, `None`s for other ones.
+fn fee_fields(t: &UnverifiedTransaction) -> (Option<U256>, Option<U256>) {
+	match t.tx_type() {
+		TypedTxId::EIP1559Transaction => (Some(t.max_fee_per_gas()), Some(t.max_priority_fee_per_gas())),
+		_ => (None, None),
+	}
 }
 
 /// Local Transaction Status
@@ -176,6 +218,8 @@ impl Transaction {
 	/// Convert `LocalizedTransaction` into RPC Transaction.
 	pub fn from_localized(mut t: LocalizedTransaction) -> Transaction {
 		let signature = t.signature();
+		let (transaction_type, access_list) = typed_fields(&t.signed);
+		let (max_fee_per_gas, max_priority_fee_per_gas) = fee_fields(&t.signed);
 		let scheme = CreateContractAddress::FromSenderAndNonce;
 		Transaction {
 			hash: t.hash(),
@@ -196,7 +240,7 @@ impl Transaction {
 				Action::Create => Some(contract_address(scheme, &t.sender(), &t.nonce, &t.data).0),
 				Action::Call(_) => None,
 			},
-			raw: ::rlp::encode(&t.signed).into(),
+			raw: t.signed.encode().into(),
 			public_key: t.recover_public().ok().map(Into::into),
 			chain_id: t.chain_id().map(U64::from),
 			standard_v: t.standard_v().into(),
@@ -204,12 +248,19 @@ impl Transaction {
 			r: signature.r().into(),
 			s: signature.s().into(),
 			condition: None,
+			transaction_type,
+			access_list,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			status: None,
 		}
 	}
 
 	/// Convert `SignedTransaction` into RPC Transaction.
 	pub fn from_signed(t: SignedTransaction) -> Transaction {
 		let signature = t.signature();
+		let (transaction_type, access_list) = typed_fields(&t);
+		let (max_fee_per_gas, max_priority_fee_per_gas) = fee_fields(&t);
 		let scheme = CreateContractAddress::FromSenderAndNonce;
 		Transaction {
 			hash: t.hash(),
@@ -230,7 +281,7 @@ impl Transaction {
 				Action::Create => Some(contract_address(scheme, &t.sender(), &t.nonce, &t.data).0),
 				Action::Call(_) => None,
 			},
-			raw: ::rlp::encode(&t).into(),
+			raw: t.encode().into(),
 			public_key: t.public_key().map(Into::into),
 			chain_id: t.chain_id().map(U64::from),
 			standard_v: t.standard_v().into(),
@@ -238,6 +289,11 @@ impl Transaction {
 			r: signature.r().into(),
 			s: signature.s().into(),
 			condition: None,
+			transaction_type,
+			access_list,
+			max_fee_per_gas,
+			max_priority_fee_per_gas,
+			status: None,
 		}
 	}
 
@@ -0,0 +1,63 @@
+// Copyright 2015-2019 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Geth-compatible txpool types.
+
+use std::collections::BTreeMap;
+
+use ethereum_types::{H160, U256};
+
+/// Number of transactions in the pool.
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct TxPoolStatus {
+	/// Transactions executable with the current state.
+	pub pending: U256,
+	/// Transactions waiting for a transaction with a lower nonce.
+	pub queued: U256,
+}
+
+/// Transactions in the pool by sender and (decimal) nonce.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct TxPoolContent<T> {
+	/// Transactions executable with the current state.
+	pub pending: BTreeMap<H160, BTreeMap<String, T>>,
+	/// Transactions waiting for a transaction with a lower nonce.
+	pub queued: BTreeMap<H160, BTreeMap<String, T>>,
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use super::{TxPoolContent, TxPoolStatus};
+
+	#[test]
+	fn status_serialization() {
+		let status = TxPoolStatus { pending: 16.into(), queued: 7.into() };
+		assert_eq!(serde_json::to_string(&status).unwrap(), r#"{"pending":"0x10","queued":"0x7"}"#);
+	}
+
+	#[test]
+	fn content_serialization() {
+		let content = TxPoolContent {
+			pending: vec![(Default::default(), vec![("806".to_owned(), "summary".to_owned())].into_iter().collect())].into_iter().collect(),
+			queued: Default::default(),
+		};
+		assert_eq!(
+			serde_json::to_string(&content).unwrap(),
+			r#"{"pending":{"0x0000000000000000000000000000000000000000":{"806":"summary"}},"queued":{}}"#
+		);
+	}
+}
@@ -17,7 +17,7 @@ export RUSTFLAGS=" -C link-arg=-s"
 echo "_____ Building target: "$CARGO_TARGET" _____"
 if [ "${CARGO_TARGET}" = "armv7-linux-androideabi" ]
 then
-  time cargo build --target $CARGO_TARGET --verbose --color=always --release -p parity-clib --features final
+  time cargo build --target $CARGO_TARGET --verbose --color=always --release -p parity-clib --features "final bls12-381 kzg"
 else
   if [ "${CARGO_TARGET}" = "x86_64-unknown-linux-gnu" ] || [ "${CARGO_TARGET}" = "x86_64-apple-darwin" ]
   then
@@ -25,7 +25,7 @@ else
     # If you change this please remember to also update .cargo/config
     export RUSTFLAGS="$RUSTFLAGS -Ctarget-feature=+aes,+sse2,+ssse3"
   fi
-  time cargo build --target $CARGO_TARGET --verbose --color=always --release --features final
+  time cargo build --target $CARGO_TARGET --verbose --color=always --release --features "final bls12-381 kzg"
   time cargo build --target $CARGO_TARGET --verbose --color=always --release -p evmbin
   time cargo build --target $CARGO_TARGET --verbose --color=always --release -p ethstore-cli
   time cargo build --target $CARGO_TARGET --verbose --color=always --release -p ethkey-cli
@@ -18,7 +18,7 @@ echo "_____ Building target: "$CARGO_TARGET" _____"
   # If you change this please remember to also update .cargo/config
 export RUSTFLAGS=" -Ctarget-feature=+aes,+sse2,+ssse3 -Ctarget-feature=+crt-static"
 
-time cargo build --target $CARGO_TARGET --verbose --release --features final
+time cargo build --target $CARGO_TARGET --verbose --release --features "final bls12-381 kzg"
 time cargo build --target $CARGO_TARGET --verbose --release -p evmbin
 time cargo build --target $CARGO_TARGET --verbose --release -p ethstore-cli
 time cargo build --target $CARGO_TARGET --verbose --release -p ethkey-cli
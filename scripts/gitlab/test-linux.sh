@@ -7,7 +7,7 @@ set -u # treat unset variables as error
 
 export CC="sccache gcc"
 export CXX="sccache g++"
-FEATURES="json-tests"
+FEATURES="json-tests bls12-381 kzg"
 
 OPTIONS="--release"
 #use nproc `linux only